@@ -2,16 +2,20 @@
 //! 
 //! Herramienta de línea de comandos para información y control del sistema
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use battery::State as BatteryState;
 use clap::{Parser, Subcommand};
 use colored::*;
 use comfy_table::Table;
 use hecate_core::{HardwareDetector, SystemProfile};
 use hecate_gpu::GpuManager;
 use indicatif::{ProgressBar, ProgressStyle};
-use serde::Serialize;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
-use sysinfo::{System, SystemExt, CpuExt, DiskExt, NetworkExt, ProcessExt};
+use sysinfo::{System, SystemExt, ComponentExt, CpuExt, DiskExt, NetworkExt, ProcessExt, Signal};
 use tracing::{error, info};
 
 // ============================================================================
@@ -25,14 +29,24 @@ use tracing::{error, info};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
-    
-    /// Output format (text, json, yaml)
-    #[arg(short, long, default_value = "text")]
-    format: OutputFormat,
-    
+
+    /// Output format (text, json, yaml); falls back to the config file, then "text"
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
+
     /// Enable verbose output
     #[arg(short, long)]
     verbose: bool,
+
+    /// Path to the TOML config file (defaults to $HECATE_CONFIG_DIR/cli.toml, or
+    /// ~/.config/hecate/cli.toml, auto-created with commented-out defaults if missing)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Compact, no-graph monitor layout: single percentage lines and plain RX/TX rates instead of
+    /// bars and history graphs -- for narrow terminals or log piping
+    #[arg(long)]
+    basic: bool,
 }
 
 #[derive(Subcommand)]
@@ -46,15 +60,27 @@ enum Commands {
     
     /// Monitor system in real-time
     Monitor {
-        /// Update interval in seconds
-        #[arg(short, long, default_value = "1")]
-        interval: u64,
-        
+        /// Update interval in seconds; falls back to the config file, then 1
+        #[arg(short, long)]
+        interval: Option<u64>,
+
         /// Component to monitor
         #[arg(short, long)]
         component: Option<String>,
+
+        /// Filter the process view by name (substring, or a pattern with --regex)
+        #[arg(long)]
+        filter: Option<String>,
+
+        /// Treat --filter as a regex instead of a plain substring
+        #[arg(long)]
+        regex: bool,
+
+        /// How many seconds of history the braille time-series graphs should keep
+        #[arg(long, default_value = "60")]
+        history_seconds: u64,
     },
-    
+
     /// Manage GPU settings
     Gpu {
         #[command(subcommand)]
@@ -116,11 +142,49 @@ enum InfoComponent {
     /// Network information
     Network,
     /// Process information
-    Process,
+    Process {
+        /// Sort key (cpu, mem, pid, name); falls back to the config file, then "cpu"
+        #[arg(long)]
+        sort_by: Option<String>,
+
+        /// Reverse the sort order
+        #[arg(long)]
+        reverse: bool,
+
+        /// Filter processes with a query expression, e.g. `cpu > 5.0 and name contains nginx`
+        /// (columns: cpu, mem, pid, name, status; operators: = != > < >= <= contains; combine
+        /// predicates with `and`/`or`)
+        #[arg(long)]
+        filter: Option<String>,
+    },
+    /// Battery and AC-power information
+    Battery,
+    /// Thermal sensor information (CPU package, per-core, NVMe, ambient, ...)
+    Temperature {
+        /// Report temperatures in Fahrenheit instead of Celsius (Text mode only)
+        #[arg(long)]
+        fahrenheit: bool,
+    },
     /// All components
     All,
 }
 
+impl InfoComponent {
+    fn as_str(&self) -> &'static str {
+        match self {
+            InfoComponent::Cpu => "cpu",
+            InfoComponent::Memory => "memory",
+            InfoComponent::Gpu => "gpu",
+            InfoComponent::Disk => "disk",
+            InfoComponent::Network => "network",
+            InfoComponent::Process { .. } => "process",
+            InfoComponent::Battery => "battery",
+            InfoComponent::Temperature { .. } => "temperature",
+            InfoComponent::All => "all",
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum GpuAction {
     /// List all GPUs
@@ -144,23 +208,39 @@ enum GpuAction {
 enum ProcessAction {
     /// List processes
     List {
-        /// Sort by (cpu, memory, pid, name)
+        /// Sort by (cpu, memory, pid, name, gpu-mem, gpu-util)
         #[arg(short, long, default_value = "cpu")]
         sort: String,
         /// Number of processes to show
         #[arg(short, long, default_value = "10")]
         limit: usize,
+        /// Hide processes with no GPU usage
+        #[arg(long)]
+        gpu_only: bool,
+        /// Filter by process name (substring, or a pattern with --regex)
+        #[arg(long)]
+        filter: Option<String>,
+        /// Treat --filter as a regex instead of a plain substring
+        #[arg(long)]
+        regex: bool,
     },
     /// Kill process
     Kill {
         /// Process ID
         pid: u32,
-        /// Force kill
+        /// Skip the SIGTERM grace period and send SIGKILL immediately
         #[arg(short, long)]
         force: bool,
+        /// Seconds to wait after SIGTERM before escalating to SIGKILL
+        #[arg(short, long, default_value = "5")]
+        timeout: u64,
     },
     /// Show process tree
-    Tree,
+    Tree {
+        /// Only show the subtree rooted at this PID
+        #[arg(long)]
+        pid: Option<u32>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -196,6 +276,104 @@ impl std::str::FromStr for OutputFormat {
     }
 }
 
+// ============================================================================
+// CONFIG FILE
+// ============================================================================
+
+const DEFAULT_OUTPUT_FORMAT: &str = "text";
+const DEFAULT_MONITOR_INTERVAL: u64 = 1;
+const DEFAULT_SORT_BY: &str = "cpu";
+const DEFAULT_MONITOR_WIDGETS: &[&str] =
+    &["cpu", "memory", "gpu", "disk", "network", "process", "temperature"];
+
+/// Template written out the first time [`load_config`] creates a missing config file.
+const DEFAULT_CONFIG_TEMPLATE: &str = r#"# HecateOS CLI configuration
+# Uncomment and edit any of these to change the built-in defaults. CLI flags always
+# override whatever is set here.
+
+# format = "text"              # text, json, yaml
+# monitor_interval = 1          # seconds
+# temperature_fahrenheit = false
+# sort_by = "cpu"               # cpu, mem, pid, name
+# widgets = ["cpu", "memory", "gpu", "disk", "network", "process", "temperature"]
+"#;
+
+/// User-tunable defaults loaded from a TOML file, resolved by [`resolve_config_path`] from
+/// `--config` or `$HECATE_CONFIG_DIR/cli.toml` (falling back to `~/.config/hecate/cli.toml`).
+/// Every field is optional; an absent field falls back to its built-in default. CLI flags always
+/// win over both the config file and the built-in default.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+struct CliConfig {
+    /// Default `--format`
+    format: Option<String>,
+    /// Default `monitor --interval`, in seconds
+    monitor_interval: Option<u64>,
+    /// Default temperature unit; `true` reports Fahrenheit. Note this can only be forced *on* by
+    /// a bare `--fahrenheit` CLI flag -- there's no way for a flag to force it back off, since
+    /// clap flags have no "explicitly false" state to distinguish from "not passed".
+    temperature_fahrenheit: Option<bool>,
+    /// Default sort column for process listings (cpu, mem, pid, name)
+    sort_by: Option<String>,
+    /// Which widgets `show_all_monitor` renders, and in what order
+    widgets: Option<Vec<String>>,
+}
+
+impl CliConfig {
+    fn output_format(&self) -> OutputFormat {
+        self.format
+            .as_deref()
+            .unwrap_or(DEFAULT_OUTPUT_FORMAT)
+            .parse()
+            .unwrap_or(OutputFormat::Text)
+    }
+
+    fn monitor_interval(&self) -> u64 {
+        self.monitor_interval.unwrap_or(DEFAULT_MONITOR_INTERVAL)
+    }
+
+    fn sort_by(&self) -> String {
+        self.sort_by.clone().unwrap_or_else(|| DEFAULT_SORT_BY.to_string())
+    }
+
+    fn widgets(&self) -> Vec<String> {
+        self.widgets.clone().unwrap_or_else(|| {
+            DEFAULT_MONITOR_WIDGETS.iter().map(|s| s.to_string()).collect()
+        })
+    }
+}
+
+/// Resolve the config file path: `--config <path>` if given, else `$HECATE_CONFIG_DIR/cli.toml`,
+/// else `~/.config/hecate/cli.toml` (mirroring [`hecate_gpu`]'s variant store convention).
+fn resolve_config_path(explicit: Option<&Path>) -> Result<PathBuf> {
+    if let Some(path) = explicit {
+        return Ok(path.to_path_buf());
+    }
+
+    if let Ok(dir) = std::env::var("HECATE_CONFIG_DIR") {
+        return Ok(PathBuf::from(dir).join("cli.toml"));
+    }
+
+    let home = std::env::var("HOME").context("HOME is not set; cannot locate config directory")?;
+    Ok(PathBuf::from(home).join(".config").join("hecate").join("cli.toml"))
+}
+
+/// Load the config file, creating it (with commented-out defaults) the first time it's missing.
+fn load_config(path: &Path) -> Result<CliConfig> {
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, DEFAULT_CONFIG_TEMPLATE)
+            .with_context(|| format!("failed to create default config at {}", path.display()))?;
+        return Ok(CliConfig::default());
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read config file {}", path.display()))?;
+    toml::from_str(&contents)
+        .with_context(|| format!("failed to parse config file {}", path.display()))
+}
+
 // ============================================================================
 // MAIN
 // ============================================================================
@@ -203,7 +381,7 @@ impl std::str::FromStr for OutputFormat {
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
-    
+
     // Initialize logging
     if cli.verbose {
         tracing_subscriber::fmt()
@@ -214,17 +392,22 @@ async fn main() -> Result<()> {
             .with_env_filter("warn")
             .init();
     }
-    
+
+    let config = load_config(&resolve_config_path(cli.config.as_deref())?)?;
+    let format = cli.format.unwrap_or_else(|| config.output_format());
+    let basic = cli.basic;
+
     // Execute command
     match cli.command {
         Commands::Info { component } => {
-            handle_info(component, &cli.format).await?;
+            handle_info(component, &format, &config).await?;
         }
-        Commands::Monitor { interval, component } => {
-            handle_monitor(interval, component).await?;
+        Commands::Monitor { interval, component, filter, regex, history_seconds } => {
+            let interval = interval.unwrap_or_else(|| config.monitor_interval());
+            handle_monitor(interval, component, filter, regex, history_seconds, basic, &config).await?;
         }
         Commands::Gpu { action } => {
-            handle_gpu(action, &cli.format).await?;
+            handle_gpu(action, &format).await?;
         }
         Commands::Benchmark { test, duration } => {
             handle_benchmark(test, duration).await?;
@@ -233,16 +416,16 @@ async fn main() -> Result<()> {
             handle_optimize(profile, dry_run).await?;
         }
         Commands::Process { action } => {
-            handle_process(action, &cli.format).await?;
+            handle_process(action, &format).await?;
         }
         Commands::Network { action } => {
-            handle_network(action, &cli.format).await?;
+            handle_network(action, &format).await?;
         }
         Commands::Health { full } => {
             handle_health(full).await?;
         }
     }
-    
+
     Ok(())
 }
 
@@ -250,12 +433,12 @@ async fn main() -> Result<()> {
 // COMMAND HANDLERS
 // ============================================================================
 
-async fn handle_info(component: Option<InfoComponent>, format: &OutputFormat) -> Result<()> {
-    let mut system = System::new_all();
-    system.refresh_all();
-    
+async fn handle_info(component: Option<InfoComponent>, format: &OutputFormat, config: &CliConfig) -> Result<()> {
     let component = component.unwrap_or(InfoComponent::All);
-    
+
+    let mut system = System::new();
+    RefreshPlan::for_component(Some(component.as_str())).apply(&mut system);
+
     match component {
         InfoComponent::Cpu | InfoComponent::All => {
             show_cpu_info(&system, format)?;
@@ -272,45 +455,68 @@ async fn handle_info(component: Option<InfoComponent>, format: &OutputFormat) ->
         InfoComponent::Network | InfoComponent::All => {
             show_network_info(&system, format)?;
         }
-        InfoComponent::Process | InfoComponent::All => {
-            show_process_info(&system, format)?;
+        InfoComponent::Process { sort_by, reverse, filter } => {
+            let sort_by = sort_by.unwrap_or_else(|| config.sort_by());
+            show_process_info(&system, format, &sort_by, reverse, filter.as_deref()).await?;
+        }
+        InfoComponent::Battery | InfoComponent::All => {
+            show_battery_info(format)?;
+        }
+        InfoComponent::Temperature { fahrenheit } => {
+            let fahrenheit = fahrenheit || config.temperature_fahrenheit.unwrap_or(false);
+            show_temperature_info(&system, format, fahrenheit)?;
         }
         _ => {}
     }
-    
+
     Ok(())
 }
 
-async fn handle_monitor(interval: u64, component: Option<String>) -> Result<()> {
+async fn handle_monitor(
+    interval: u64,
+    component: Option<String>,
+    filter: Option<String>,
+    use_regex: bool,
+    history_seconds: u64,
+    basic: bool,
+    config: &CliConfig,
+) -> Result<()> {
     println!("{}", "=== HecateOS System Monitor ===".bright_cyan().bold());
     println!("Press Ctrl+C to exit\n");
-    
-    let mut system = System::new_all();
-    
+
+    let name_filter = compile_process_filter(filter.as_deref(), use_regex)?;
+    let refresh_plan = RefreshPlan::for_component(component.as_deref());
+    let widgets = config.widgets();
+
+    let mut system = System::new();
+    let mut history = MonitorHistory::new(history_seconds, interval);
+
     loop {
         // Clear screen
         print!("\x1B[2J\x1B[1;1H");
-        
-        system.refresh_all();
-        
+
+        refresh_plan.apply(&mut system);
+
         // Header
         let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
         println!("{} {}", "HecateOS Monitor".bright_cyan().bold(), timestamp);
         println!("{}", "─".repeat(80).bright_black());
-        
+
         if let Some(ref comp) = component {
             match comp.as_str() {
-                "cpu" => show_cpu_monitor(&system)?,
-                "memory" => show_memory_monitor(&system)?,
-                "gpu" => show_gpu_monitor().await?,
-                "disk" => show_disk_monitor(&system)?,
-                "network" => show_network_monitor(&system)?,
-                _ => show_all_monitor(&system).await?,
+                "cpu" => show_cpu_monitor(&system, &mut history.cpu, basic)?,
+                "memory" => show_memory_monitor(&system, &mut history.memory, basic)?,
+                "gpu" => show_gpu_monitor(&mut history.gpu, basic).await?,
+                "disk" => show_disk_monitor(&system, basic)?,
+                "network" => show_network_monitor(&system, &mut history.net_rx, &mut history.net_tx, interval, basic)?,
+                "process" => show_process_monitor(&system, name_filter.as_ref())?,
+                "temperature" => show_temperature_monitor(&system)?,
+                _ => show_all_monitor(&system, name_filter.as_ref(), &mut history, interval, basic, &widgets).await?,
             }
         } else {
-            show_all_monitor(&system).await?;
+            show_all_monitor(&system, name_filter.as_ref(), &mut history, interval, basic, &widgets).await?;
         }
-        
+
         tokio::time::sleep(Duration::from_secs(interval)).await;
     }
 }
@@ -491,34 +697,49 @@ async fn handle_process(action: ProcessAction, format: &OutputFormat) -> Result<
     system.refresh_all();
     
     match action {
-        ProcessAction::List { sort, limit } => {
+        ProcessAction::List { sort, limit, gpu_only, filter, regex } => {
+            let gpu_usage = gpu_process_usage().await;
+            let name_filter = compile_process_filter(filter.as_deref(), regex)?;
+
             let mut processes: Vec<_> = system.processes()
                 .values()
-                .map(|p| ProcessInfo {
-                    pid: p.pid().as_u32(),
-                    name: p.name().to_string(),
-                    cpu_percent: p.cpu_usage(),
-                    memory_mb: p.memory() / 1024,
-                    status: format!("{:?}", p.status()),
+                .filter(|p| name_filter.as_ref().map_or(true, |re| re.is_match(p.name())))
+                .map(|p| {
+                    let pid = p.pid().as_u32();
+                    let gpu = gpu_usage.get(&pid);
+                    ProcessInfo {
+                        pid,
+                        name: p.name().to_string(),
+                        cpu_percent: p.cpu_usage(),
+                        memory_mb: p.memory() / 1024,
+                        status: format!("{:?}", p.status()),
+                        gpu_memory_mb: gpu.map(|(mem, _)| mem / 1024 / 1024),
+                        gpu_utilization: gpu.map(|(_, util)| *util),
+                    }
                 })
+                .filter(|p| !gpu_only || p.gpu_memory_mb.is_some())
                 .collect();
-            
+
             // Sort processes
             match sort.as_str() {
                 "cpu" => processes.sort_by(|a, b| b.cpu_percent.partial_cmp(&a.cpu_percent).unwrap()),
                 "memory" => processes.sort_by(|a, b| b.memory_mb.cmp(&a.memory_mb)),
                 "pid" => processes.sort_by(|a, b| a.pid.cmp(&b.pid)),
                 "name" => processes.sort_by(|a, b| a.name.cmp(&b.name)),
+                "gpu-mem" => processes.sort_by(|a, b| b.gpu_memory_mb.unwrap_or(0).cmp(&a.gpu_memory_mb.unwrap_or(0))),
+                "gpu-util" => processes.sort_by(|a, b| {
+                    b.gpu_utilization.unwrap_or(0.0).partial_cmp(&a.gpu_utilization.unwrap_or(0.0)).unwrap()
+                }),
                 _ => {}
             }
-            
+
             processes.truncate(limit);
-            
+
             match format {
                 OutputFormat::Text => {
                     let mut table = Table::new();
-                    table.set_header(vec!["PID", "Name", "CPU %", "Memory (MB)", "Status"]);
-                    
+                    table.set_header(vec!["PID", "Name", "CPU %", "Memory (MB)", "Status", "GPU Mem (MB)", "GPU %"]);
+
                     for p in processes {
                         table.add_row(vec![
                             p.pid.to_string(),
@@ -526,9 +747,11 @@ async fn handle_process(action: ProcessAction, format: &OutputFormat) -> Result<
                             format!("{:.1}", p.cpu_percent),
                             p.memory_mb.to_string(),
                             p.status,
+                            p.gpu_memory_mb.map(|m| m.to_string()).unwrap_or_else(|| "-".to_string()),
+                            p.gpu_utilization.map(|u| format!("{:.1}", u)).unwrap_or_else(|| "-".to_string()),
                         ]);
                     }
-                    
+
                     println!("{}", table);
                 }
                 OutputFormat::Json => {
@@ -539,23 +762,42 @@ async fn handle_process(action: ProcessAction, format: &OutputFormat) -> Result<
                 }
             }
         }
-        ProcessAction::Kill { pid, force } => {
-            if let Some(process) = system.process(sysinfo::Pid::from(pid as usize)) {
-                if force {
-                    process.kill();
+        ProcessAction::Kill { pid, force, timeout } => {
+            let sys_pid = sysinfo::Pid::from(pid as usize);
+
+            let Some(process) = system.process(sys_pid) else {
+                eprintln!("Process {} not found", pid);
+                return Ok(());
+            };
+
+            if force {
+                process.kill_with(Signal::Kill);
+                println!("✓ Process {} killed (SIGKILL)", pid);
+            } else {
+                process.kill_with(Signal::Term);
+
+                let start = std::time::Instant::now();
+                let mut exited = false;
+                while start.elapsed().as_secs() < timeout {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    if !system.refresh_process(sys_pid) {
+                        exited = true;
+                        break;
+                    }
+                }
+
+                if exited {
+                    println!("✓ Process {} exited after SIGTERM", pid);
                 } else {
-                    // Send SIGTERM
-                    process.kill();
+                    if let Some(process) = system.process(sys_pid) {
+                        process.kill_with(Signal::Kill);
+                    }
+                    println!("✗ Process {} did not exit within {}s, sent SIGKILL", pid, timeout);
                 }
-                println!("✓ Process {} terminated", pid);
-            } else {
-                eprintln!("Process {} not found", pid);
             }
         }
-        ProcessAction::Tree => {
-            println!("Process tree:");
-            // TODO: Implement process tree display
-            println!("  (Not implemented yet)");
+        ProcessAction::Tree { pid } => {
+            show_process_tree(&system, pid, format)?;
         }
     }
     
@@ -584,16 +826,46 @@ async fn handle_network(action: NetworkAction, format: &OutputFormat) -> Result<
             println!("{}", table);
         }
         NetworkAction::Stats => {
-            println!("Network Statistics:");
-            
-            for (name, data) in system.networks() {
-                println!("\n{}", name.bright_cyan());
-                println!("  Received:     {}", format_bytes(data.received()));
-                println!("  Transmitted:  {}", format_bytes(data.transmitted()));
-                println!("  Packets RX:   {}", data.packets_received());
-                println!("  Packets TX:   {}", data.packets_transmitted());
-                println!("  Errors RX:    {}", data.errors_on_received());
-                println!("  Errors TX:    {}", data.errors_on_transmitted());
+            let socket_stats = collect_socket_stats(&system);
+
+            match format {
+                OutputFormat::Text => {
+                    println!("Network Statistics:");
+
+                    for (name, data) in system.networks() {
+                        println!("\n{}", name.bright_cyan());
+                        println!("  Received:     {}", format_bytes(data.received()));
+                        println!("  Transmitted:  {}", format_bytes(data.transmitted()));
+                        println!("  Packets RX:   {}", data.packets_received());
+                        println!("  Packets TX:   {}", data.packets_transmitted());
+                        println!("  Errors RX:    {}", data.errors_on_received());
+                        println!("  Errors TX:    {}", data.errors_on_transmitted());
+                    }
+
+                    println!("\n{}", "Sockets:".bright_cyan());
+                    let mut states: Vec<_> = socket_stats.tcp_by_state.iter().collect();
+                    states.sort_by_key(|(state, _)| (*state).clone());
+                    for (state, count) in states {
+                        println!("  TCP {}: {}", state, count);
+                    }
+                    println!("  UDP sockets: {}", socket_stats.udp_count);
+
+                    if !socket_stats.listening.is_empty() {
+                        println!("\n{}", "Listening:".bright_cyan());
+                        for socket in &socket_stats.listening {
+                            println!("  {} {} ({})",
+                                socket.protocol.to_uppercase(),
+                                socket.local_addr,
+                                socket.process_name.as_deref().unwrap_or("?"));
+                        }
+                    }
+                }
+                OutputFormat::Json => {
+                    println!("{}", serde_json::to_string_pretty(&socket_stats)?);
+                }
+                OutputFormat::Yaml => {
+                    println!("{}", serde_yaml::to_string(&socket_stats)?);
+                }
             }
         }
         NetworkAction::Test { host } => {
@@ -649,14 +921,31 @@ async fn handle_health(full: bool) -> Result<()> {
     for disk in system.disks() {
         let usage = ((disk.total_space() - disk.available_space()) as f64 / disk.total_space() as f64) * 100.0;
         if usage > 95.0 {
-            issues.push(format!("Critical disk usage on {}: {:.1}%", 
+            issues.push(format!("Critical disk usage on {}: {:.1}%",
                 disk.mount_point().to_string_lossy(), usage));
         } else if usage > 85.0 {
-            warnings.push(format!("High disk usage on {}: {:.1}%", 
+            warnings.push(format!("High disk usage on {}: {:.1}%",
                 disk.mount_point().to_string_lossy(), usage));
         }
     }
-    
+
+    // Disk I/O Check
+    const WRITE_LATENCY_WARNING_MS: f64 = 20.0;
+    const DISK_UTILIZATION_WARNING_PERCENT: f64 = 90.0;
+    let io_rates = sample_disk_io(DISK_IO_SAMPLE_INTERVAL);
+    for disk in system.disks() {
+        let Some(rates) = io_rates.get(&disk_device_name(disk)) else {
+            continue;
+        };
+        let mount = disk.mount_point().to_string_lossy();
+        if rates.avg_write_latency_ms > WRITE_LATENCY_WARNING_MS {
+            warnings.push(format!("High write latency on {}: {:.1}ms/op", mount, rates.avg_write_latency_ms));
+        }
+        if rates.utilization_percent > DISK_UTILIZATION_WARNING_PERCENT {
+            warnings.push(format!("Disk {} is near-saturated: {:.1}% utilized", mount, rates.utilization_percent));
+        }
+    }
+
     // Temperature Check
     if let Ok(temp) = std::fs::read_to_string("/sys/class/thermal/thermal_zone0/temp") {
         if let Ok(millidegrees) = temp.trim().parse::<i32>() {
@@ -675,7 +964,21 @@ async fn handle_health(full: bool) -> Result<()> {
     if load.one > cpu_count * 2.0 {
         issues.push(format!("High system load: {:.2}", load.one));
     }
-    
+
+    // Battery Check
+    const LOW_BATTERY_THRESHOLD: f32 = 15.0;
+    const DEGRADATION_WARNING_THRESHOLD: f32 = 20.0;
+    if let Some(battery_info) = collect_battery_info()? {
+        if !battery_info.on_ac_power && battery_info.percent < LOW_BATTERY_THRESHOLD {
+            issues.push(format!("Low battery: {:.1}% and discharging", battery_info.percent));
+        }
+        if let Some(degradation) = battery_info.degradation_percent {
+            if degradation > DEGRADATION_WARNING_THRESHOLD {
+                warnings.push(format!("Battery has degraded {:.1}% from its design capacity", degradation));
+            }
+        }
+    }
+
     // Results
     if issues.is_empty() && warnings.is_empty() {
         println!("{}", "✓ System is healthy!".green().bold());
@@ -700,6 +1003,8 @@ async fn handle_health(full: bool) -> Result<()> {
         show_cpu_info(&system, &OutputFormat::Text)?;
         show_memory_info(&system, &OutputFormat::Text)?;
         show_disk_info(&system, &OutputFormat::Text)?;
+        show_battery_info(&OutputFormat::Text)?;
+        show_temperature_info(&system, &OutputFormat::Text, false)?;
     }
     
     Ok(())
@@ -710,10 +1015,13 @@ async fn handle_health(full: bool) -> Result<()> {
 // ============================================================================
 
 fn show_cpu_info(system: &System, format: &OutputFormat) -> Result<()> {
+    let usage = sample_cpu_usage(CPU_SAMPLE_INTERVAL)
+        .map_or_else(|| system.global_cpu_info().cpu_usage(), |s| s.total_percent);
+
     let cpu_info = CpuInfo {
         model: system.cpus()[0].brand().to_string(),
         cores: system.cpus().len(),
-        usage: system.global_cpu_info().cpu_usage(),
+        usage,
         frequency: system.cpus()[0].frequency(),
         load_avg: system.load_average(),
     };
@@ -740,24 +1048,30 @@ fn show_cpu_info(system: &System, format: &OutputFormat) -> Result<()> {
 }
 
 fn show_memory_info(system: &System, format: &OutputFormat) -> Result<()> {
+    let arc = read_zfs_arc_stats();
     let mem_info = MemoryInfo {
         total: system.total_memory(),
         used: system.used_memory(),
         available: system.available_memory(),
         swap_total: system.total_swap(),
         swap_used: system.used_swap(),
+        arc_used: arc.map(|(used, _)| used),
+        arc_max: arc.map(|(_, max)| max),
     };
-    
+
     match format {
         OutputFormat::Text => {
             println!("{}", "Memory Information:".bright_cyan());
             println!("  Total:      {}", format_bytes(mem_info.total));
-            println!("  Used:       {} ({:.1}%)", 
-                format_bytes(mem_info.used), 
+            println!("  Used:       {} ({:.1}%)",
+                format_bytes(mem_info.used),
                 (mem_info.used as f64 / mem_info.total as f64) * 100.0);
             println!("  Available:  {}", format_bytes(mem_info.available));
             println!("  Swap Total: {}", format_bytes(mem_info.swap_total));
             println!("  Swap Used:  {}", format_bytes(mem_info.swap_used));
+            if let (Some(arc_used), Some(arc_max)) = (mem_info.arc_used, mem_info.arc_max) {
+                println!("  ARC:        {} / {} max (reclaimable)", format_bytes(arc_used), format_bytes(arc_max));
+            }
         }
         OutputFormat::Json => {
             println!("{}", serde_json::to_string_pretty(&mem_info)?);
@@ -781,7 +1095,8 @@ async fn show_gpu_info(format: &OutputFormat) -> Result<()> {
                     for gpu in gpus {
                         println!("  GPU {}:     {}", gpu.index, gpu.name);
                         println!("    Temp:     {}°C", gpu.temperature);
-                        println!("    Power:    {}W / {}W", gpu.power_draw, gpu.power_limit);
+                        let power_limit = gpu.power_limit.map_or_else(|| "N/A".to_string(), |l| format!("{l}W"));
+                        println!("    Power:    {}W / {}", gpu.power_draw, power_limit);
                         println!("    Memory:   {} / {}", 
                             format_bytes(gpu.memory_used), 
                             format_bytes(gpu.memory_total));
@@ -806,31 +1121,229 @@ async fn show_gpu_info(format: &OutputFormat) -> Result<()> {
     Ok(())
 }
 
+/// How long [`sample_disk_io`] waits between its two `/proc/diskstats` reads.
+const DISK_IO_SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// One point-in-time read of a block device's cumulative `/proc/diskstats` counters.
+#[derive(Debug, Clone, Copy, Default)]
+struct DiskIoSample {
+    read_bytes: u64,
+    write_bytes: u64,
+    reads: u64,
+    writes: u64,
+    time_writing_ms: u64,
+    time_in_progress_ms: u64,
+}
+
+/// Per-device throughput, IOPS, average write latency, and I/O utilization, derived by diffing
+/// two [`DiskIoSample`]s taken an interval apart.
+#[derive(Debug, Clone, Copy, Default)]
+struct DiskIoRates {
+    read_bytes_per_sec: u64,
+    write_bytes_per_sec: u64,
+    read_iops: u64,
+    write_iops: u64,
+    avg_write_latency_ms: f64,
+    utilization_percent: f64,
+}
+
+fn read_disk_io_samples() -> Result<HashMap<String, DiskIoSample>> {
+    let stats = procfs::diskstats().context("Failed to read /proc/diskstats")?;
+    Ok(stats
+        .into_iter()
+        .map(|d| {
+            (
+                d.name.clone(),
+                DiskIoSample {
+                    read_bytes: d.sectors_read * 512,
+                    write_bytes: d.sectors_written * 512,
+                    reads: d.reads,
+                    writes: d.writes,
+                    time_writing_ms: d.time_writing,
+                    time_in_progress_ms: d.time_in_progress,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Strip the `/dev/` prefix `sysinfo` reports so a disk's name lines up with `/proc/diskstats`'s
+/// bare device name (e.g. `sda1`, `nvme0n1p1`).
+fn disk_device_name(disk: &sysinfo::Disk) -> String {
+    let name = disk.name().to_string_lossy();
+    name.strip_prefix("/dev/").unwrap_or(&name).to_string()
+}
+
+/// Sample `/proc/diskstats` twice `interval` apart and diff the cumulative counters per device,
+/// turning them into bytes/sec, ops/sec, average write latency, and I/O utilization. Returns an
+/// empty map on non-Linux hosts or when `/proc/diskstats` can't be read.
+fn sample_disk_io(interval: Duration) -> HashMap<String, DiskIoRates> {
+    let Ok(before) = read_disk_io_samples() else {
+        return HashMap::new();
+    };
+    std::thread::sleep(interval);
+    let Ok(after) = read_disk_io_samples() else {
+        return HashMap::new();
+    };
+
+    let secs = interval.as_secs_f64().max(0.001);
+    after
+        .into_iter()
+        .filter_map(|(name, a)| {
+            let b = before.get(&name)?;
+            let write_delta = a.writes.saturating_sub(b.writes);
+            let time_writing_delta = a.time_writing_ms.saturating_sub(b.time_writing_ms);
+            let time_in_progress_delta = a.time_in_progress_ms.saturating_sub(b.time_in_progress_ms);
+
+            Some((
+                name,
+                DiskIoRates {
+                    read_bytes_per_sec: (a.read_bytes.saturating_sub(b.read_bytes) as f64 / secs) as u64,
+                    write_bytes_per_sec: (a.write_bytes.saturating_sub(b.write_bytes) as f64 / secs) as u64,
+                    read_iops: (a.reads.saturating_sub(b.reads) as f64 / secs) as u64,
+                    write_iops: (write_delta as f64 / secs) as u64,
+                    avg_write_latency_ms: if write_delta > 0 {
+                        time_writing_delta as f64 / write_delta as f64
+                    } else {
+                        0.0
+                    },
+                    utilization_percent: (time_in_progress_delta as f64 / (secs * 1000.0) * 100.0).min(100.0),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// How long [`sample_cpu_usage`] waits between its two `/proc/stat` reads.
+const CPU_SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Total and idle jiffies for one CPU -- either the aggregate `cpu` line or a specific `cpuN`
+/// line from `/proc/stat`.
+#[derive(Debug, Clone, Copy, Default)]
+struct CpuJiffies {
+    total: u64,
+    idle: u64,
+}
+
+impl CpuJiffies {
+    fn from_cpu_time(cpu: &procfs::CpuTime) -> Self {
+        let total = cpu.user
+            + cpu.nice
+            + cpu.system
+            + cpu.idle
+            + cpu.iowait.unwrap_or(0)
+            + cpu.irq.unwrap_or(0)
+            + cpu.softirq.unwrap_or(0)
+            + cpu.steal.unwrap_or(0);
+        let idle = cpu.idle + cpu.iowait.unwrap_or(0);
+        Self { total, idle }
+    }
+}
+
+/// Aggregate and per-core CPU usage, derived from two `/proc/stat` samples an interval apart --
+/// far less noisy than sysinfo's single-shot reading, which is biased on its first call.
+#[derive(Debug, Clone, Default)]
+struct CpuUsageSample {
+    total_percent: f32,
+    per_core_percent: Vec<f32>,
+}
+
+fn read_cpu_jiffies() -> Result<(CpuJiffies, Vec<CpuJiffies>)> {
+    let stat = procfs::KernelStats::new().context("Failed to read /proc/stat")?;
+    let total = CpuJiffies::from_cpu_time(&stat.total);
+    let per_core = stat.cpu_time.iter().map(CpuJiffies::from_cpu_time).collect();
+    Ok((total, per_core))
+}
+
+fn cpu_usage_percent(before: CpuJiffies, after: CpuJiffies) -> f32 {
+    let total_delta = after.total.saturating_sub(before.total).max(1);
+    let idle_delta = after.idle.saturating_sub(before.idle);
+    (total_delta.saturating_sub(idle_delta) as f64 / total_delta as f64 * 100.0) as f32
+}
+
+/// Sample `/proc/stat` twice `interval` apart and derive aggregate and per-core CPU usage from
+/// the jiffy deltas. Returns `None` on non-Linux hosts or when `/proc/stat` can't be read, so
+/// callers fall back to sysinfo's single-shot usage.
+fn sample_cpu_usage(interval: Duration) -> Option<CpuUsageSample> {
+    let (total_before, per_core_before) = read_cpu_jiffies().ok()?;
+    std::thread::sleep(interval);
+    let (total_after, per_core_after) = read_cpu_jiffies().ok()?;
+
+    let total_percent = cpu_usage_percent(total_before, total_after);
+    let per_core_percent = per_core_before
+        .iter()
+        .zip(per_core_after.iter())
+        .map(|(&before, &after)| cpu_usage_percent(before, after))
+        .collect();
+
+    Some(CpuUsageSample { total_percent, per_core_percent })
+}
+
+/// Read ZFS's current ARC size and configured maximum (`size`/`c_max`, in bytes) from
+/// `/proc/spl/kstat/zfs/arcstats`. Returns `None` on hosts without ZFS loaded, or if either field
+/// is missing/unparsable.
+fn read_zfs_arc_stats() -> Option<(u64, u64)> {
+    let contents = std::fs::read_to_string("/proc/spl/kstat/zfs/arcstats").ok()?;
+
+    let mut size = None;
+    let mut c_max = None;
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (name, value) = match fields.as_slice() {
+            [name, _kstat_type, value] => (*name, *value),
+            _ => continue,
+        };
+        match name {
+            "size" => size = value.parse::<u64>().ok(),
+            "c_max" => c_max = value.parse::<u64>().ok(),
+            _ => {}
+        }
+    }
+
+    Some((size?, c_max?))
+}
+
 fn show_disk_info(system: &System, format: &OutputFormat) -> Result<()> {
+    let io_rates = sample_disk_io(DISK_IO_SAMPLE_INTERVAL);
+
     let disks: Vec<DiskInfo> = system.disks()
         .iter()
-        .map(|disk| DiskInfo {
-            name: disk.name().to_string_lossy().to_string(),
-            mount_point: disk.mount_point().to_string_lossy().to_string(),
-            total_space: disk.total_space(),
-            available_space: disk.available_space(),
-            filesystem: format!("{:?}", disk.file_system()),
+        .map(|disk| {
+            let rates = io_rates.get(&disk_device_name(disk));
+            DiskInfo {
+                name: disk.name().to_string_lossy().to_string(),
+                mount_point: disk.mount_point().to_string_lossy().to_string(),
+                total_space: disk.total_space(),
+                available_space: disk.available_space(),
+                filesystem: format!("{:?}", disk.file_system()),
+                read_bytes_per_sec: rates.map(|r| r.read_bytes_per_sec),
+                write_bytes_per_sec: rates.map(|r| r.write_bytes_per_sec),
+                read_iops: rates.map(|r| r.read_iops),
+                write_iops: rates.map(|r| r.write_iops),
+            }
         })
         .collect();
-    
+
     match format {
         OutputFormat::Text => {
             println!("{}", "Disk Information:".bright_cyan());
             for disk in disks {
                 let used = disk.total_space - disk.available_space;
                 let percent = (used as f64 / disk.total_space as f64) * 100.0;
-                
+
                 println!("  {}:", disk.mount_point);
                 println!("    Device:   {}", disk.name);
                 println!("    FS:       {}", disk.filesystem);
                 println!("    Total:    {}", format_bytes(disk.total_space));
                 println!("    Used:     {} ({:.1}%)", format_bytes(used), percent);
                 println!("    Free:     {}", format_bytes(disk.available_space));
+                match (disk.read_bytes_per_sec, disk.write_bytes_per_sec, disk.read_iops, disk.write_iops) {
+                    (Some(read_bps), Some(write_bps), Some(read_iops), Some(write_iops)) => {
+                        println!("    I/O:      R {}/s ({} IOPS) | W {}/s ({} IOPS)",
+                            format_bytes(read_bps), read_iops, format_bytes(write_bps), write_iops);
+                    }
+                    _ => println!("    I/O:      n/a"),
+                }
             }
         }
         OutputFormat::Json => {
@@ -844,6 +1357,107 @@ fn show_disk_info(system: &System, format: &OutputFormat) -> Result<()> {
     Ok(())
 }
 
+#[derive(Serialize)]
+struct ListeningSocket {
+    protocol: String,
+    local_addr: String,
+    pid: Option<u32>,
+    process_name: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SocketStats {
+    tcp_by_state: HashMap<String, usize>,
+    udp_count: usize,
+    listening: Vec<ListeningSocket>,
+}
+
+/// Map every open socket's inode to its owning PID by scanning `/proc/<pid>/fd` for each process,
+/// the same inode-to-owner lookup `ss`/`lsof` use. Returns an empty map on non-Linux hosts.
+fn inode_to_pid_map() -> HashMap<u64, u32> {
+    let mut map = HashMap::new();
+
+    let Ok(processes) = procfs::process::all_processes() else {
+        return map;
+    };
+    for process in processes.flatten() {
+        let pid = process.pid() as u32;
+        let Ok(fds) = process.fd() else { continue };
+        for fd in fds.flatten() {
+            if let procfs::process::FDTarget::Socket(inode) = fd.target {
+                map.insert(inode, pid);
+            }
+        }
+    }
+
+    map
+}
+
+fn resolve_listener(
+    protocol: &str,
+    local_addr: String,
+    inode: u64,
+    inode_to_pid: &HashMap<u64, u32>,
+    system: &System,
+) -> ListeningSocket {
+    let pid = inode_to_pid.get(&inode).copied();
+    let process_name = pid
+        .and_then(|p| system.process(sysinfo::Pid::from(p as usize)))
+        .map(|p| p.name().to_string());
+
+    ListeningSocket {
+        protocol: protocol.to_string(),
+        local_addr,
+        pid,
+        process_name,
+    }
+}
+
+/// Parse `/proc/net/{tcp,tcp6,udp,udp6}` (via `procfs`) into TCP socket counts by state, a UDP
+/// socket count, and the list of listening/bound sockets with their owning process, giving an
+/// `ss`-like view without shelling out.
+fn collect_socket_stats(system: &System) -> SocketStats {
+    let inode_to_pid = inode_to_pid_map();
+    let mut tcp_by_state: HashMap<String, usize> = HashMap::new();
+    let mut listening = Vec::new();
+
+    let tcp_entries = procfs::net::tcp()
+        .into_iter()
+        .flatten()
+        .chain(procfs::net::tcp6().into_iter().flatten());
+    for entry in tcp_entries {
+        *tcp_by_state.entry(format!("{:?}", entry.state)).or_insert(0) += 1;
+
+        if matches!(entry.state, procfs::net::TcpState::Listen) {
+            listening.push(resolve_listener(
+                "tcp",
+                entry.local_address.to_string(),
+                entry.inode,
+                &inode_to_pid,
+                system,
+            ));
+        }
+    }
+
+    let udp_entries: Vec<_> = procfs::net::udp()
+        .into_iter()
+        .flatten()
+        .chain(procfs::net::udp6().into_iter().flatten())
+        .collect();
+    let udp_count = udp_entries.len();
+    for entry in &udp_entries {
+        listening.push(resolve_listener(
+            "udp",
+            entry.local_address.to_string(),
+            entry.inode,
+            &inode_to_pid,
+            system,
+        ));
+    }
+
+    SocketStats { tcp_by_state, udp_count, listening }
+}
+
 fn show_network_info(system: &System, format: &OutputFormat) -> Result<()> {
     let interfaces: Vec<NetworkInfo> = system.networks()
         .iter()
@@ -874,153 +1488,748 @@ fn show_network_info(system: &System, format: &OutputFormat) -> Result<()> {
             println!("{}", serde_yaml::to_string(&interfaces)?);
         }
     }
-    
-    Ok(())
-}
 
-fn show_process_info(system: &System, format: &OutputFormat) -> Result<()> {
-    println!("{}", "Process Information:".bright_cyan());
-    println!("  Total:      {}", system.processes().len());
-    
-    let running = system.processes()
-        .values()
-        .filter(|p| p.status() == sysinfo::ProcessStatus::Run)
-        .count();
-    println!("  Running:    {}", running);
-    
     Ok(())
 }
 
-// ============================================================================
-// MONITOR DISPLAY FUNCTIONS
-// ============================================================================
+/// Aggregate state across every battery `starship-battery` reports, or `None` on a desktop/AC-only
+/// system that has no battery at all.
+fn collect_battery_info() -> Result<Option<BatteryInfo>> {
+    let manager = match battery::Manager::new() {
+        Ok(manager) => manager,
+        Err(_) => return Ok(None),
+    };
 
-fn show_cpu_monitor(system: &System) -> Result<()> {
-    let usage = system.global_cpu_info().cpu_usage();
-    let bar_width = 40;
-    let filled = (usage * bar_width as f32 / 100.0) as usize;
-    let bar = format!("{}{}", 
-        "█".repeat(filled).bright_green(),
-        "░".repeat(bar_width - filled).bright_black()
-    );
-    
-    println!("CPU: [{}] {:.1}%", bar, usage);
-    
-    // Per-core usage
-    println!("\nPer Core:");
-    for (i, cpu) in system.cpus().iter().enumerate() {
-        let core_usage = cpu.cpu_usage();
-        let core_filled = (core_usage * 20.0 / 100.0) as usize;
-        let core_bar = format!("{}{}", 
-            "█".repeat(core_filled).bright_green(),
-            "░".repeat(20 - core_filled).bright_black()
-        );
-        println!("  Core {}: [{}] {:.1}%", i, core_bar, core_usage);
+    let batteries: Vec<_> = manager
+        .batteries()
+        .context("Failed to enumerate batteries")?
+        .filter_map(|b| b.ok())
+        .collect();
+
+    if batteries.is_empty() {
+        return Ok(None);
+    }
+
+    let energy: f32 = batteries.iter().map(|b| b.energy().value).sum();
+    let energy_full: f32 = batteries.iter().map(|b| b.energy_full().value).sum();
+    let energy_full_design: f32 = batteries.iter().map(|b| b.energy_full_design().value).sum();
+
+    let percent = if energy_full > 0.0 { (energy / energy_full) * 100.0 } else { 0.0 };
+    let degradation_percent = if energy_full_design > 0.0 {
+        Some((1.0 - energy_full / energy_full_design) * 100.0)
+    } else {
+        None
+    };
+
+    let on_ac_power = batteries
+        .iter()
+        .all(|b| matches!(b.state(), BatteryState::Charging | BatteryState::Full));
+    let state = if batteries.iter().any(|b| b.state() == BatteryState::Charging) {
+        "charging"
+    } else if on_ac_power {
+        "full"
+    } else {
+        "discharging"
+    };
+
+    let time_to_empty_mins = batteries
+        .iter()
+        .filter_map(|b| b.time_to_empty())
+        .map(|t| (t.value / 60.0) as u64)
+        .min();
+    let time_to_full_mins = batteries
+        .iter()
+        .filter_map(|b| b.time_to_full())
+        .map(|t| (t.value / 60.0) as u64)
+        .min();
+
+    Ok(Some(BatteryInfo {
+        percent,
+        state: state.to_string(),
+        on_ac_power,
+        time_to_empty_mins,
+        time_to_full_mins,
+        degradation_percent,
+    }))
+}
+
+fn show_battery_info(format: &OutputFormat) -> Result<()> {
+    let Some(battery_info) = collect_battery_info()? else {
+        if matches!(format, OutputFormat::Text) {
+            println!("{}", "Battery Information:".bright_cyan());
+            println!("  No battery detected (desktop or AC-only system)");
+        }
+        return Ok(());
+    };
+
+    match format {
+        OutputFormat::Text => {
+            println!("{}", "Battery Information:".bright_cyan());
+            println!("  Charge:     {:.1}%", battery_info.percent);
+            println!("  State:      {}", battery_info.state);
+            println!("  AC Power:   {}", if battery_info.on_ac_power { "yes" } else { "no" });
+            if let Some(mins) = battery_info.time_to_empty_mins {
+                println!("  Time left:  {}h {}m", mins / 60, mins % 60);
+            }
+            if let Some(mins) = battery_info.time_to_full_mins {
+                println!("  Time to full: {}h {}m", mins / 60, mins % 60);
+            }
+            if let Some(degradation) = battery_info.degradation_percent {
+                println!("  Health:     {:.1}% degraded from design capacity", degradation);
+            }
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&battery_info)?);
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&battery_info)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn celsius_to_fahrenheit(celsius: f32) -> f32 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+fn format_temperature(celsius: f32, fahrenheit: bool) -> String {
+    if fahrenheit {
+        format!("{:.1}°F", celsius_to_fahrenheit(celsius))
+    } else {
+        format!("{:.1}°C", celsius)
+    }
+}
+
+/// Green/yellow/red by how close `celsius` is to the sensor's `critical` threshold, mirroring
+/// [`show_disk_monitor`]'s percent-based coloring. Sensors with no reported critical threshold
+/// are always green.
+fn temperature_color(celsius: f32, critical: Option<f32>) -> &'static str {
+    let Some(critical) = critical else { return "green" };
+    if critical <= 0.0 {
+        return "green";
+    }
+    let ratio = celsius / critical;
+    if ratio > 0.9 {
+        "red"
+    } else if ratio > 0.75 {
+        "yellow"
+    } else {
+        "green"
+    }
+}
+
+fn print_colored_temperature_line(label: &str, celsius: f32, critical: Option<f32>, fahrenheit: bool) {
+    let line = format!("  {}: {}", label, format_temperature(celsius, fahrenheit));
+    match temperature_color(celsius, critical) {
+        "red" => println!("{}", line.bright_red()),
+        "yellow" => println!("{}", line.bright_yellow()),
+        _ => println!("{}", line.bright_green()),
+    }
+}
+
+/// Every thermal sensor sysinfo's `Component` enumeration exposes -- on Linux this is backed by
+/// hwmon, so it covers CPU package, per-core, NVMe, and ambient sensors, not just the GPU.
+fn collect_temperature_info(system: &System) -> Vec<TemperatureInfo> {
+    system.components()
+        .iter()
+        .map(|c| TemperatureInfo {
+            label: c.label().to_string(),
+            celsius: c.temperature(),
+            critical: c.critical(),
+        })
+        .collect()
+}
+
+fn show_temperature_info(system: &System, format: &OutputFormat, fahrenheit: bool) -> Result<()> {
+    let sensors = collect_temperature_info(system);
+
+    match format {
+        OutputFormat::Text => {
+            println!("{}", "Temperature Information:".bright_cyan());
+            if sensors.is_empty() {
+                println!("  No thermal sensors detected");
+            }
+            for sensor in &sensors {
+                print_colored_temperature_line(&sensor.label, sensor.celsius, sensor.critical, fahrenheit);
+            }
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&sensors)?);
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&sensors)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn show_temperature_monitor(system: &System) -> Result<()> {
+    let sensors = collect_temperature_info(system);
+
+    if sensors.is_empty() {
+        println!("Temperature: No thermal sensors detected");
+        return Ok(());
+    }
+
+    for sensor in &sensors {
+        print_colored_temperature_line(&sensor.label, sensor.celsius, sensor.critical, false);
+    }
+
+    Ok(())
+}
+
+/// The full process table, sorted by `sort_by` (`cpu`, `mem`, `pid`, or `name`), optionally
+/// reversed, and optionally narrowed by a `--filter` query (see [`parse_process_filter_expr`]).
+/// Annotated with per-process GPU memory/utilization when NVIDIA GPUs are present (joined from
+/// [`gpu_process_usage`] on PID; `None` on non-GPU hosts).
+async fn show_process_info(
+    system: &System,
+    format: &OutputFormat,
+    sort_by: &str,
+    reverse: bool,
+    filter: Option<&str>,
+) -> Result<()> {
+    let running = system.processes()
+        .values()
+        .filter(|p| p.status() == sysinfo::ProcessStatus::Run)
+        .count();
+
+    let filter_expr = filter.map(parse_process_filter_expr).transpose()?;
+    let gpu_usage = gpu_process_usage().await;
+
+    let mut processes: Vec<ProcessInfo> = system.processes()
+        .values()
+        .map(|p| {
+            let pid = p.pid().as_u32();
+            let gpu = gpu_usage.get(&pid);
+            ProcessInfo {
+                pid,
+                name: p.name().to_string(),
+                cpu_percent: p.cpu_usage(),
+                memory_mb: p.memory() / 1024,
+                status: format!("{:?}", p.status()),
+                gpu_memory_mb: gpu.map(|(mem, _)| mem / 1024 / 1024),
+                gpu_utilization: gpu.map(|(_, util)| *util),
+            }
+        })
+        .filter(|p| filter_expr.as_ref().map_or(true, |expr| expr.evaluate(p)))
+        .collect();
+
+    match sort_by {
+        "cpu" => processes.sort_by(|a, b| b.cpu_percent.partial_cmp(&a.cpu_percent).unwrap()),
+        "mem" | "memory" => processes.sort_by(|a, b| b.memory_mb.cmp(&a.memory_mb)),
+        "pid" => processes.sort_by(|a, b| a.pid.cmp(&b.pid)),
+        "name" => processes.sort_by(|a, b| a.name.cmp(&b.name)),
+        other => anyhow::bail!("Unknown --sort-by key '{other}' (expected cpu, mem, pid, or name)"),
+    }
+    if reverse {
+        processes.reverse();
+    }
+
+    match format {
+        OutputFormat::Text => {
+            println!("{}", "Process Information:".bright_cyan());
+            println!("  Total:      {}", system.processes().len());
+            println!("  Running:    {}", running);
+            println!("  Matching:   {}\n", processes.len());
+
+            let mut table = Table::new();
+            table.set_header(vec!["PID", "Name", "CPU %", "Memory (MB)", "Status", "GPU Mem (MB)", "GPU %"]);
+
+            for p in &processes {
+                table.add_row(vec![
+                    p.pid.to_string(),
+                    p.name.clone(),
+                    format!("{:.1}", p.cpu_percent),
+                    p.memory_mb.to_string(),
+                    p.status.clone(),
+                    p.gpu_memory_mb.map_or_else(|| "-".to_string(), |v| v.to_string()),
+                    p.gpu_utilization.map_or_else(|| "-".to_string(), |v| format!("{:.1}", v)),
+                ]);
+            }
+
+            println!("{}", table);
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&processes)?);
+        }
+        OutputFormat::Yaml => {
+            println!("{}", serde_yaml::to_string(&processes)?);
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// PROCESS FILTER QUERY LANGUAGE
+// ============================================================================
+
+/// A `--filter` query literal: either side of a comparison resolves to a number or plain text,
+/// decided by whether it parses as `f64`.
+#[derive(Debug, Clone)]
+enum ProcessFilterValue {
+    Number(f64),
+    Text(String),
+}
+
+/// One `column operator literal` comparison, e.g. `cpu > 5.0` or `name contains nginx`.
+#[derive(Debug, Clone)]
+struct ProcessFilterPredicate {
+    column: String,
+    op: String,
+    value: ProcessFilterValue,
+}
+
+impl ProcessFilterPredicate {
+    fn evaluate(&self, process: &ProcessInfo) -> bool {
+        match self.column.as_str() {
+            "cpu" => self.compare_number(process.cpu_percent as f64),
+            "mem" | "memory" => self.compare_number(process.memory_mb as f64),
+            "pid" => self.compare_number(process.pid as f64),
+            "name" => self.compare_text(&process.name),
+            "status" => self.compare_text(&process.status),
+            _ => false,
+        }
+    }
+
+    fn compare_number(&self, actual: f64) -> bool {
+        let ProcessFilterValue::Number(expected) = &self.value else {
+            return false;
+        };
+        let expected = *expected;
+        match self.op.as_str() {
+            "=" => (actual - expected).abs() < f64::EPSILON,
+            "!=" => (actual - expected).abs() >= f64::EPSILON,
+            ">" => actual > expected,
+            "<" => actual < expected,
+            ">=" => actual >= expected,
+            "<=" => actual <= expected,
+            _ => false,
+        }
+    }
+
+    fn compare_text(&self, actual: &str) -> bool {
+        let ProcessFilterValue::Text(expected) = &self.value else {
+            return false;
+        };
+        match self.op.as_str() {
+            "=" => actual.eq_ignore_ascii_case(expected),
+            "!=" => !actual.eq_ignore_ascii_case(expected),
+            "contains" => actual.to_lowercase().contains(&expected.to_lowercase()),
+            _ => false,
+        }
+    }
+}
+
+/// A `--filter` query AST: predicates combined with `and`/`or`. `or` binds more loosely than
+/// `and`, so `cpu > 5 and name contains nginx or pid = 1` reads as `(cpu > 5 and name contains
+/// nginx) or (pid = 1)`.
+#[derive(Debug, Clone)]
+enum ProcessFilterExpr {
+    Predicate(ProcessFilterPredicate),
+    And(Box<ProcessFilterExpr>, Box<ProcessFilterExpr>),
+    Or(Box<ProcessFilterExpr>, Box<ProcessFilterExpr>),
+}
+
+impl ProcessFilterExpr {
+    fn evaluate(&self, process: &ProcessInfo) -> bool {
+        match self {
+            ProcessFilterExpr::Predicate(p) => p.evaluate(process),
+            ProcessFilterExpr::And(lhs, rhs) => lhs.evaluate(process) && rhs.evaluate(process),
+            ProcessFilterExpr::Or(lhs, rhs) => lhs.evaluate(process) || rhs.evaluate(process),
+        }
+    }
+}
+
+/// Split `query` into tokens, treating `"..."` as a single token so multi-word values like
+/// `name contains "chrome helper"` work.
+fn tokenize_filter_query(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut chars = query.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            for c in chars.by_ref() {
+                if c == '"' {
+                    break;
+                }
+                current.push(c);
+            }
+            tokens.push(std::mem::take(&mut current));
+        } else if c.is_whitespace() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Split `tokens` on every top-level occurrence of `keyword` (case-insensitive), the way `and`
+/// and `or` separate clauses in a `--filter` query.
+fn split_on_keyword<'a>(tokens: &'a [String], keyword: &str) -> Vec<&'a [String]> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.eq_ignore_ascii_case(keyword) {
+            groups.push(&tokens[start..i]);
+            start = i + 1;
+        }
+    }
+    groups.push(&tokens[start..]);
+    groups
+}
+
+fn parse_process_filter_expr(query: &str) -> Result<ProcessFilterExpr> {
+    let tokens = tokenize_filter_query(query);
+    if tokens.is_empty() {
+        anyhow::bail!("Empty --filter query");
+    }
+    parse_or(&tokens)
+}
+
+fn parse_or(tokens: &[String]) -> Result<ProcessFilterExpr> {
+    let mut clauses = split_on_keyword(tokens, "or").into_iter();
+    let mut expr = parse_and(clauses.next().unwrap_or(&[]))?;
+    for clause in clauses {
+        expr = ProcessFilterExpr::Or(Box::new(expr), Box::new(parse_and(clause)?));
+    }
+    Ok(expr)
+}
+
+fn parse_and(tokens: &[String]) -> Result<ProcessFilterExpr> {
+    let mut clauses = split_on_keyword(tokens, "and").into_iter();
+    let mut expr = parse_predicate(clauses.next().unwrap_or(&[]))?;
+    for clause in clauses {
+        expr = ProcessFilterExpr::And(Box::new(expr), Box::new(parse_predicate(clause)?));
+    }
+    Ok(expr)
+}
+
+fn parse_predicate(tokens: &[String]) -> Result<ProcessFilterExpr> {
+    let [column, op, literal] = tokens else {
+        anyhow::bail!(
+            "Invalid filter predicate (expected `column op value`): {}",
+            tokens.join(" ")
+        );
+    };
+
+    let op = op.to_lowercase();
+    if !matches!(op.as_str(), "=" | "!=" | ">" | "<" | ">=" | "<=" | "contains") {
+        anyhow::bail!("Unknown filter operator: {op}");
+    }
+
+    let value = match literal.parse::<f64>() {
+        Ok(n) => ProcessFilterValue::Number(n),
+        Err(_) => ProcessFilterValue::Text(literal.clone()),
+    };
+
+    Ok(ProcessFilterExpr::Predicate(ProcessFilterPredicate {
+        column: column.to_lowercase(),
+        op,
+        value,
+    }))
+}
+
+// ============================================================================
+// MONITOR DISPLAY FUNCTIONS
+// ============================================================================
+
+fn show_cpu_monitor(system: &System, history: &mut SampleHistory, basic: bool) -> Result<()> {
+    let sampled = sample_cpu_usage(CPU_SAMPLE_INTERVAL);
+    let usage = sampled.as_ref().map_or_else(|| system.global_cpu_info().cpu_usage(), |s| s.total_percent);
+    history.push(usage as f64);
+
+    if basic {
+        println!("CPU: {:.1}%", usage);
+        return Ok(());
+    }
+
+    let bar_width = 40;
+    let filled = (usage * bar_width as f32 / 100.0) as usize;
+    let bar = format!("{}{}",
+        "█".repeat(filled).bright_green(),
+        "░".repeat(bar_width - filled).bright_black()
+    );
+
+    println!("CPU: [{}] {:.1}%", bar, usage);
+
+    // Per-core usage
+    println!("\nPer Core:");
+    match &sampled {
+        Some(sample) if sample.per_core_percent.len() == system.cpus().len() => {
+            for (i, core_usage) in sample.per_core_percent.iter().enumerate() {
+                let core_filled = (core_usage * 20.0 / 100.0) as usize;
+                let core_bar = format!("{}{}",
+                    "█".repeat(core_filled).bright_green(),
+                    "░".repeat(20 - core_filled).bright_black()
+                );
+                println!("  Core {}: [{}] {:.1}%", i, core_bar, core_usage);
+            }
+        }
+        _ => {
+            for (i, cpu) in system.cpus().iter().enumerate() {
+                let core_usage = cpu.cpu_usage();
+                let core_filled = (core_usage * 20.0 / 100.0) as usize;
+                let core_bar = format!("{}{}",
+                    "█".repeat(core_filled).bright_green(),
+                    "░".repeat(20 - core_filled).bright_black()
+                );
+                println!("  Core {}: [{}] {:.1}%", i, core_bar, core_usage);
+            }
+        }
+    }
+
+    print_history_graph("\nCPU % (history)", history);
+
+    Ok(())
+}
+
+fn show_memory_monitor(system: &System, history: &mut SampleHistory, basic: bool) -> Result<()> {
+    let used = system.used_memory();
+    let total = system.total_memory();
+    let percent = (used as f64 / total as f64) * 100.0;
+    history.push(percent);
+
+    let arc = read_zfs_arc_stats();
+
+    if basic {
+        println!("Memory: {:.1}% ({} / {})", percent, format_bytes(used), format_bytes(total));
+        if let Some((arc_used, arc_max)) = arc {
+            println!("  ARC (reclaimable): {} / {} max", format_bytes(arc_used), format_bytes(arc_max));
+        }
+        return Ok(());
     }
-    
-    Ok(())
-}
 
-fn show_memory_monitor(system: &System) -> Result<()> {
-    let used = system.used_memory();
-    let total = system.total_memory();
-    let percent = (used as f64 / total as f64) * 100.0;
-    
     let bar_width = 40;
     let filled = (percent * bar_width as f64 / 100.0) as usize;
-    let bar = format!("{}{}", 
-        "█".repeat(filled).bright_yellow(),
+    let arc_filled = arc
+        .map_or(0, |(arc_used, _)| ((arc_used as f64 / total as f64) * bar_width as f64) as usize)
+        .min(filled);
+
+    let bar = format!("{}{}{}",
+        "█".repeat(arc_filled).bright_blue(),
+        "█".repeat(filled - arc_filled).bright_yellow(),
         "░".repeat(bar_width - filled).bright_black()
     );
-    
-    println!("Memory: [{}] {:.1}% ({} / {})", 
+
+    println!("Memory: [{}] {:.1}% ({} / {})",
         bar, percent, format_bytes(used), format_bytes(total));
-    
+
+    if let Some((arc_used, arc_max)) = arc {
+        println!("  ARC (reclaimable): {} / {} max", format_bytes(arc_used), format_bytes(arc_max));
+    }
+
+    print_history_graph("\nMemory % (history)", history);
+
     Ok(())
 }
 
-async fn show_gpu_monitor() -> Result<()> {
+async fn show_gpu_monitor(history: &mut SampleHistory, basic: bool) -> Result<()> {
     if let Ok(manager) = GpuManager::new() {
         let gpus = manager.detect_gpus().await?;
-        
-        for gpu in gpus {
-            let bar_width = 30;
-            let filled = (gpu.utilization_gpu as f64 * bar_width as f64 / 100.0) as usize;
-            let bar = format!("{}{}", 
-                "█".repeat(filled).bright_cyan(),
-                "░".repeat(bar_width - filled).bright_black()
-            );
-            
-            println!("GPU {}: [{}] {}% | {}°C | {}W", 
-                gpu.index, bar, gpu.utilization_gpu, gpu.temperature, gpu.power_draw);
+
+        let mut total_utilization = 0u32;
+        let mut gpu_count = 0u32;
+
+        for gpu in &gpus {
+            if basic {
+                println!("GPU {}: {}% | {}°C | {}W",
+                    gpu.index, gpu.utilization_gpu, gpu.temperature, gpu.power_draw);
+            } else {
+                let bar_width = 30;
+                let filled = (gpu.utilization_gpu as f64 * bar_width as f64 / 100.0) as usize;
+                let bar = format!("{}{}",
+                    "█".repeat(filled).bright_cyan(),
+                    "░".repeat(bar_width - filled).bright_black()
+                );
+
+                println!("GPU {}: [{}] {}% | {}°C | {}W",
+                    gpu.index, bar, gpu.utilization_gpu, gpu.temperature, gpu.power_draw);
+            }
+
+            total_utilization += gpu.utilization_gpu as u32;
+            gpu_count += 1;
+        }
+
+        if gpu_count > 0 {
+            history.push(total_utilization as f64 / gpu_count as f64);
+            if !basic {
+                print_history_graph("\nGPU % (history, averaged across GPUs)", history);
+            }
         }
     } else {
         println!("GPU: No NVIDIA GPUs detected");
     }
-    
+
     Ok(())
 }
 
-fn show_disk_monitor(system: &System) -> Result<()> {
+fn show_disk_monitor(system: &System, basic: bool) -> Result<()> {
+    let io_rates = sample_disk_io(DISK_IO_SAMPLE_INTERVAL);
+
     for disk in system.disks() {
         let used = disk.total_space() - disk.available_space();
         let percent = (used as f64 / disk.total_space() as f64) * 100.0;
-        
-        let bar_width = 30;
-        let filled = (percent * bar_width as f64 / 100.0) as usize;
-        let color = if percent > 90.0 {
-            "red"
-        } else if percent > 75.0 {
-            "yellow"
+
+        if basic {
+            println!("{}: {:.1}%", disk.mount_point().to_string_lossy(), percent);
         } else {
-            "green"
-        };
-        
-        let bar = match color {
-            "red" => format!("{}{}", 
-                "█".repeat(filled).bright_red(),
-                "░".repeat(bar_width - filled).bright_black()
-            ),
-            "yellow" => format!("{}{}", 
-                "█".repeat(filled).bright_yellow(),
-                "░".repeat(bar_width - filled).bright_black()
-            ),
-            _ => format!("{}{}", 
-                "█".repeat(filled).bright_green(),
-                "░".repeat(bar_width - filled).bright_black()
-            ),
-        };
-        
-        println!("{}: [{}] {:.1}%", 
-            disk.mount_point().to_string_lossy(), bar, percent);
+            let bar_width = 30;
+            let filled = (percent * bar_width as f64 / 100.0) as usize;
+            let color = if percent > 90.0 {
+                "red"
+            } else if percent > 75.0 {
+                "yellow"
+            } else {
+                "green"
+            };
+
+            let bar = match color {
+                "red" => format!("{}{}",
+                    "█".repeat(filled).bright_red(),
+                    "░".repeat(bar_width - filled).bright_black()
+                ),
+                "yellow" => format!("{}{}",
+                    "█".repeat(filled).bright_yellow(),
+                    "░".repeat(bar_width - filled).bright_black()
+                ),
+                _ => format!("{}{}",
+                    "█".repeat(filled).bright_green(),
+                    "░".repeat(bar_width - filled).bright_black()
+                ),
+            };
+
+            println!("{}: [{}] {:.1}%",
+                disk.mount_point().to_string_lossy(), bar, percent);
+        }
+
+        if let Some(rates) = io_rates.get(&disk_device_name(disk)) {
+            println!("  R {}/s ({} IOPS) | W {}/s ({} IOPS)",
+                format_bytes(rates.read_bytes_per_sec), rates.read_iops,
+                format_bytes(rates.write_bytes_per_sec), rates.write_iops);
+        }
     }
-    
+
     Ok(())
 }
 
-fn show_network_monitor(system: &System) -> Result<()> {
+/// `data.received()`/`data.transmitted()` are already deltas since the last `refresh_networks_list`
+/// call, so dividing by `interval` turns them straight into a rate without tracking our own
+/// previous-sample state the way [`sample_disk_io`] has to.
+fn show_network_monitor(
+    system: &System,
+    rx_history: &mut SampleHistory,
+    tx_history: &mut SampleHistory,
+    interval: u64,
+    basic: bool,
+) -> Result<()> {
+    let interval = interval.max(1) as f64;
+    let mut total_rx = 0u64;
+    let mut total_tx = 0u64;
+
     for (name, data) in system.networks() {
-        println!("{}: ↓ {} ↑ {}", 
-            name, 
+        println!("{}: ↓ {} ↑ {}",
+            name,
             format_bytes(data.received()),
             format_bytes(data.transmitted())
         );
+        total_rx += data.received();
+        total_tx += data.transmitted();
     }
-    
+
+    rx_history.push(total_rx as f64 / interval);
+    tx_history.push(total_tx as f64 / interval);
+
+    if !basic {
+        print_history_graph("\nRX bytes/sec (history)", rx_history);
+        print_history_graph("\nTX bytes/sec (history)", tx_history);
+    }
+
     Ok(())
 }
 
-async fn show_all_monitor(system: &System) -> Result<()> {
-    show_cpu_monitor(system)?;
-    println!();
-    show_memory_monitor(system)?;
-    println!();
-    show_gpu_monitor().await?;
-    println!();
-    show_disk_monitor(system)?;
-    println!();
-    show_network_monitor(system)?;
-    
+/// Top processes by CPU usage, optionally narrowed to names matching `filter`.
+fn show_process_monitor(system: &System, filter: Option<&Regex>) -> Result<()> {
+    let mut processes: Vec<_> = system.processes()
+        .values()
+        .filter(|p| filter.map_or(true, |re| re.is_match(p.name())))
+        .collect();
+    processes.sort_by(|a, b| b.cpu_usage().partial_cmp(&a.cpu_usage()).unwrap());
+
+    for process in processes.into_iter().take(10) {
+        println!("  {:>6} {:.1}%  {:>8}  {}",
+            process.pid(),
+            process.cpu_usage(),
+            format_bytes(process.memory()),
+            process.name()
+        );
+    }
+
+    Ok(())
+}
+
+/// Renders every widget named in `widgets` (see [`CliConfig::widgets`]), in the order they're
+/// listed there, separated by blank lines.
+async fn show_all_monitor(
+    system: &System,
+    filter: Option<&Regex>,
+    history: &mut MonitorHistory,
+    interval: u64,
+    basic: bool,
+    widgets: &[String],
+) -> Result<()> {
+    let enabled = |name: &str| widgets.iter().any(|w| w == name);
+    let mut first = true;
+    macro_rules! separator {
+        () => {
+            if first {
+                first = false;
+            } else {
+                println!();
+            }
+        };
+    }
+
+    if enabled("cpu") {
+        separator!();
+        show_cpu_monitor(system, &mut history.cpu, basic)?;
+    }
+    if enabled("memory") {
+        separator!();
+        show_memory_monitor(system, &mut history.memory, basic)?;
+    }
+    if enabled("gpu") {
+        separator!();
+        show_gpu_monitor(&mut history.gpu, basic).await?;
+    }
+    if enabled("disk") {
+        separator!();
+        show_disk_monitor(system, basic)?;
+    }
+    if enabled("network") {
+        separator!();
+        show_network_monitor(system, &mut history.net_rx, &mut history.net_tx, interval, basic)?;
+    }
+    if enabled("process") {
+        separator!();
+        show_process_monitor(system, filter)?;
+    }
+    if enabled("temperature") {
+        separator!();
+        show_temperature_monitor(system)?;
+    }
+
     Ok(())
 }
 
@@ -1102,6 +2311,191 @@ async fn run_disk_benchmark(duration: u64, pb: &ProgressBar) -> Result<()> {
 // HELPER FUNCTIONS
 // ============================================================================
 
+/// Which subsystems a refresh actually needs to touch for a given monitor/info component, so
+/// `handle_monitor`'s tight loop (and `handle_info`) don't pay for refreshing disks, networks, or
+/// the whole process table when only one component is being displayed.
+#[derive(Debug, Clone, Copy, Default)]
+struct RefreshPlan {
+    cpu: bool,
+    memory: bool,
+    disks: bool,
+    networks: bool,
+    processes: bool,
+    components: bool,
+}
+
+impl RefreshPlan {
+    fn all() -> Self {
+        Self {
+            cpu: true,
+            memory: true,
+            disks: true,
+            networks: true,
+            processes: true,
+            components: true,
+        }
+    }
+
+    /// Plan a refresh for the named component (`"cpu"`, `"memory"`, `"gpu"`, `"disk"`,
+    /// `"network"`, `"process"`, `"battery"`, `"temperature"`), or everything when `component` is
+    /// `None` or unrecognized. GPU and battery data come from `hecate-gpu`/`starship-battery`
+    /// rather than `System`, so neither needs any refresh here.
+    fn for_component(component: Option<&str>) -> Self {
+        match component {
+            Some("cpu") => Self { cpu: true, ..Default::default() },
+            Some("memory") => Self { memory: true, ..Default::default() },
+            Some("gpu") => Self::default(),
+            Some("disk") => Self { disks: true, ..Default::default() },
+            Some("network") => Self { networks: true, ..Default::default() },
+            Some("process") => Self { cpu: true, processes: true, ..Default::default() },
+            Some("battery") => Self::default(),
+            Some("temperature") => Self { components: true, ..Default::default() },
+            _ => Self::all(),
+        }
+    }
+
+    fn apply(&self, system: &mut System) {
+        if self.cpu {
+            system.refresh_cpu();
+        }
+        if self.memory {
+            system.refresh_memory();
+        }
+        if self.disks {
+            system.refresh_disks();
+        }
+        if self.networks {
+            system.refresh_networks_list();
+        }
+        if self.processes {
+            system.refresh_processes();
+        }
+        if self.components {
+            system.refresh_components_list();
+        }
+    }
+}
+
+/// A fixed-capacity ring buffer of the most recent samples for one metric `handle_monitor` tracks
+/// across ticks, rendered by [`print_history_graph`] as a scrolling braille line graph.
+#[derive(Debug, Clone)]
+struct SampleHistory {
+    samples: std::collections::VecDeque<f64>,
+    capacity: usize,
+}
+
+impl SampleHistory {
+    fn new(capacity: usize) -> Self {
+        Self {
+            samples: std::collections::VecDeque::with_capacity(capacity.max(1)),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+}
+
+/// Per-metric [`SampleHistory`] buffers `handle_monitor` owns across loop iterations, one per
+/// series the `show_*_monitor` functions plot: CPU %, memory %, GPU % (averaged across GPUs when
+/// more than one is detected), and network RX/TX in bytes/sec.
+struct MonitorHistory {
+    cpu: SampleHistory,
+    memory: SampleHistory,
+    gpu: SampleHistory,
+    net_rx: SampleHistory,
+    net_tx: SampleHistory,
+}
+
+impl MonitorHistory {
+    /// `history_seconds` worth of samples at one sample per `interval`-second tick.
+    fn new(history_seconds: u64, interval: u64) -> Self {
+        let capacity = (history_seconds / interval.max(1)).max(1) as usize;
+        Self {
+            cpu: SampleHistory::new(capacity),
+            memory: SampleHistory::new(capacity),
+            gpu: SampleHistory::new(capacity),
+            net_rx: SampleHistory::new(capacity),
+            net_tx: SampleHistory::new(capacity),
+        }
+    }
+}
+
+/// Unicode braille cells (U+2800 block) encode a 2 (wide) x 4 (tall) dot grid; this is the bit
+/// each `[row][col]` position within a cell contributes to the codepoint offset.
+const BRAILLE_DOT_BITS: [[u8; 2]; 4] = [
+    [0x01, 0x08],
+    [0x02, 0x10],
+    [0x04, 0x20],
+    [0x40, 0x80],
+];
+
+const GRAPH_WIDTH: usize = 60;
+const GRAPH_HEIGHT: usize = 4;
+
+/// Render `samples` as a scrolling braille line graph `width` terminal columns wide and `height`
+/// rows tall, scaled so the lowest/highest sample touch the bottom/top of the grid. Each column
+/// is 2 dots wide and each row is 4 dots tall, so the graph gets 8x the vertical and 2x the
+/// horizontal resolution of a plain character-cell bar.
+fn render_braille_graph(samples: &[f64], width: usize, height: usize) -> Vec<String> {
+    let dot_cols = width * 2;
+    let dot_rows = height * 4;
+
+    if samples.is_empty() || dot_cols == 0 || dot_rows == 0 {
+        return vec![String::new(); height];
+    }
+
+    let min = samples.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = samples.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = if (max - min).abs() > f64::EPSILON { max - min } else { 1.0 };
+
+    // Right-align the most recent samples against the most recent dot column, so the graph
+    // scrolls as older samples fall off the left edge of the buffer.
+    let recent: Vec<f64> = samples.iter().rev().take(dot_cols).rev().copied().collect();
+    let start_col = dot_cols - recent.len();
+
+    let mut cells = vec![vec![0u8; width]; height];
+    for (i, &value) in recent.iter().enumerate() {
+        let normalized = (value - min) / range;
+        let row = ((1.0 - normalized) * (dot_rows - 1) as f64).round() as usize;
+        let row = row.min(dot_rows - 1);
+
+        let dot_col = start_col + i;
+        let cell_col = dot_col / 2;
+        let sub_col = dot_col % 2;
+        let cell_row = row / 4;
+        let sub_row = row % 4;
+        cells[cell_row][cell_col] |= BRAILLE_DOT_BITS[sub_row][sub_col];
+    }
+
+    cells
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|mask| char::from_u32(0x2800 + mask as u32).unwrap_or(' '))
+                .collect::<String>()
+        })
+        .collect()
+}
+
+/// Print a labelled braille time-series graph for `history`, or nothing if it has no samples yet
+/// (the first monitor tick).
+fn print_history_graph(label: &str, history: &SampleHistory) {
+    if history.samples.is_empty() {
+        return;
+    }
+
+    let samples: Vec<f64> = history.samples.iter().copied().collect();
+    println!("{}", label.bright_black());
+    for row in render_braille_graph(&samples, GRAPH_WIDTH, GRAPH_HEIGHT) {
+        println!("{}", row.bright_cyan());
+    }
+}
+
 fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size = bytes as f64;
@@ -1120,14 +2514,16 @@ fn print_gpu_status(gpu: &hecate_gpu::GpuStatus, format: &OutputFormat) -> Resul
         OutputFormat::Text => {
             println!("GPU {}: {}", gpu.index, gpu.name);
             println!("  Temperature:  {}°C", gpu.temperature);
-            println!("  Power:        {}W / {}W", gpu.power_draw, gpu.power_limit);
-            println!("  Memory:       {} / {}", 
-                format_bytes(gpu.memory_used), 
+            let power_limit = gpu.power_limit.map_or_else(|| "N/A".to_string(), |l| format!("{l}W"));
+            println!("  Power:        {}W / {}", gpu.power_draw, power_limit);
+            println!("  Memory:       {} / {}",
+                format_bytes(gpu.memory_used),
                 format_bytes(gpu.memory_total));
-            println!("  Utilization:  GPU {}% | MEM {}%", 
+            println!("  Utilization:  GPU {}% | MEM {}%",
                 gpu.utilization_gpu, gpu.utilization_memory);
-            println!("  Clocks:       Core {} MHz | Mem {} MHz", 
-                gpu.clock_graphics, gpu.clock_memory);
+            let clock_memory = gpu.clock_memory.map_or_else(|| "N/A".to_string(), |c| format!("{c} MHz"));
+            println!("  Clocks:       Core {} MHz | Mem {}",
+                gpu.clock_graphics, clock_memory);
             if let Some(fan) = gpu.fan_speed {
                 println!("  Fan Speed:    {}%", fan);
             }
@@ -1163,6 +2559,11 @@ struct MemoryInfo {
     available: u64,
     swap_total: u64,
     swap_used: u64,
+    /// ZFS ARC size in bytes, if this host has ZFS loaded -- counted in `used` but reclaimable
+    /// under memory pressure
+    arc_used: Option<u64>,
+    /// ZFS `c_max`, the ARC's configured maximum size in bytes
+    arc_max: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -1172,6 +2573,11 @@ struct DiskInfo {
     total_space: u64,
     available_space: u64,
     filesystem: String,
+    /// `None` when the device couldn't be matched against `/proc/diskstats` (e.g. non-Linux hosts)
+    read_bytes_per_sec: Option<u64>,
+    write_bytes_per_sec: Option<u64>,
+    read_iops: Option<u64>,
+    write_iops: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -1183,6 +2589,24 @@ struct NetworkInfo {
     packets_transmitted: u64,
 }
 
+#[derive(Serialize)]
+struct BatteryInfo {
+    percent: f32,
+    state: String,
+    on_ac_power: bool,
+    time_to_empty_mins: Option<u64>,
+    time_to_full_mins: Option<u64>,
+    /// Percent the full-charge capacity has degraded relative to design capacity, if reported
+    degradation_percent: Option<f32>,
+}
+
+#[derive(Serialize)]
+struct TemperatureInfo {
+    label: String,
+    celsius: f32,
+    critical: Option<f32>,
+}
+
 #[derive(Serialize)]
 struct ProcessInfo {
     pid: u32,
@@ -1190,4 +2614,158 @@ struct ProcessInfo {
     cpu_percent: f32,
     memory_mb: u64,
     status: String,
+    /// GPU memory used by this process across all detected GPUs, in MB, if it's using one
+    gpu_memory_mb: Option<u64>,
+    /// Summed GPU streaming-multiprocessor utilization percentage across all detected GPUs, if
+    /// it's using one
+    gpu_utilization: Option<f32>,
+}
+
+#[derive(Serialize)]
+struct ProcessTreeNode {
+    pid: u32,
+    name: String,
+    cpu_percent: f32,
+    memory_mb: u64,
+    children: Vec<ProcessTreeNode>,
+}
+
+/// Recursively build the subtree rooted at `pid`. Returns `None` if `pid` no longer exists or has
+/// already been visited on this walk -- the latter guards against a malformed/cyclic parent chain
+/// looping forever.
+fn build_tree_node(
+    system: &System,
+    pid: u32,
+    children: &HashMap<u32, Vec<u32>>,
+    visited: &mut HashSet<u32>,
+) -> Option<ProcessTreeNode> {
+    if !visited.insert(pid) {
+        return None;
+    }
+    let process = system.process(sysinfo::Pid::from(pid as usize))?;
+    let kids = children.get(&pid).map(|v| v.as_slice()).unwrap_or(&[]);
+
+    Some(ProcessTreeNode {
+        pid,
+        name: process.name().to_string(),
+        cpu_percent: process.cpu_usage(),
+        memory_mb: process.memory() / 1024,
+        children: kids
+            .iter()
+            .filter_map(|&child| build_tree_node(system, child, children, visited))
+            .collect(),
+    })
+}
+
+fn print_tree_node(node: &ProcessTreeNode, prefix: &str, is_last: bool) {
+    let branch = if is_last { "└─" } else { "├─" };
+    println!(
+        "{prefix}{branch} {} ({}) {:.1}% {}MB",
+        node.name, node.pid, node.cpu_percent, node.memory_mb
+    );
+
+    let child_prefix = format!("{prefix}{}", if is_last { "   " } else { "│  " });
+    let last_index = node.children.len().saturating_sub(1);
+    for (i, child) in node.children.iter().enumerate() {
+        print_tree_node(child, &child_prefix, i == last_index);
+    }
+}
+
+/// Render the process tree reconstructed from each process's parent PID. A process is a root when
+/// its parent is missing or isn't one of the processes `sysinfo` sees (e.g. PID 0/1, or a parent
+/// outside this PID namespace); `only_pid` restricts the walk to a single subtree.
+fn show_process_tree(system: &System, only_pid: Option<u32>, format: &OutputFormat) -> Result<()> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for process in system.processes().values() {
+        if let Some(parent_pid) = process.parent().map(|p| p.as_u32()) {
+            if parent_pid > 1 && system.process(sysinfo::Pid::from(parent_pid as usize)).is_some() {
+                children.entry(parent_pid).or_default().push(process.pid().as_u32());
+            }
+        }
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_unstable();
+    }
+
+    let mut visited = HashSet::new();
+    let roots: Vec<ProcessTreeNode> = match only_pid {
+        Some(root_pid) => match build_tree_node(system, root_pid, &children, &mut visited) {
+            Some(node) => vec![node],
+            None => {
+                eprintln!("Process {} not found", root_pid);
+                Vec::new()
+            }
+        },
+        None => {
+            let child_pids: HashSet<u32> = children.values().flatten().copied().collect();
+            let mut root_pids: Vec<u32> = system
+                .processes()
+                .keys()
+                .map(|p| p.as_u32())
+                .filter(|pid| !child_pids.contains(pid))
+                .collect();
+            root_pids.sort_unstable();
+
+            root_pids
+                .into_iter()
+                .filter_map(|pid| build_tree_node(system, pid, &children, &mut visited))
+                .collect()
+        }
+    };
+
+    match format {
+        OutputFormat::Text => {
+            println!("Process tree:");
+            let last_index = roots.len().saturating_sub(1);
+            for (i, root) in roots.iter().enumerate() {
+                print_tree_node(root, "", i == last_index);
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&roots)?),
+        OutputFormat::Yaml => println!("{}", serde_yaml::to_string(&roots)?),
+    }
+
+    Ok(())
+}
+
+/// Compile a process-name filter: a plain substring by default (escaped into a regex so special
+/// characters in it are matched literally), or a user-supplied regex when `use_regex` is set. A
+/// `None`/empty pattern means "match everything" rather than "match nothing"; an invalid
+/// `--regex` pattern is a hard error rather than a silent no-match.
+fn compile_process_filter(pattern: Option<&str>, use_regex: bool) -> Result<Option<Regex>> {
+    let Some(pattern) = pattern.filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+    let compiled = if use_regex {
+        Regex::new(pattern).context("Invalid --filter regex")?
+    } else {
+        Regex::new(&regex::escape(pattern)).expect("escaped literal is always a valid regex")
+    };
+    Ok(Some(compiled))
+}
+
+/// Per-process GPU memory (bytes) and SM utilization (percent), summed across every detected GPU
+/// a process is using. Returns an empty map if GPU detection fails or no backend is available.
+async fn gpu_process_usage() -> HashMap<u32, (u64, f32)> {
+    let mut usage: HashMap<u32, (u64, f32)> = HashMap::new();
+
+    let Ok(manager) = GpuManager::new().await else {
+        return usage;
+    };
+    let Ok(gpus) = manager.detect_gpus().await else {
+        return usage;
+    };
+
+    for gpu in gpus {
+        let Ok(processes) = manager.get_gpu_processes(gpu.index).await else {
+            continue;
+        };
+        for process in processes {
+            let entry = usage.entry(process.pid).or_insert((0, 0.0));
+            entry.0 += process.used_memory.unwrap_or(0);
+            entry.1 += process.sm_utilization.unwrap_or(0) as f32;
+        }
+    }
+
+    usage
 }
\ No newline at end of file
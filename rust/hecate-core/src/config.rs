@@ -2,6 +2,7 @@
 //! 
 //! Central configuration for all HecateOS services
 
+use crate::SystemProfile;
 use serde::{Deserialize, Serialize};
 use std::env;
 
@@ -117,7 +118,125 @@ impl HecateConfig {
         if let Ok(level) = env::var("HECATE_LOG_LEVEL") {
             config.log_level = level;
         }
-        
+
         config
     }
+}
+
+/// A named, user-selectable tuning preset layered on top of a base [`SystemProfile`] -- e.g.
+/// `"ai-flagship/max-perf"` vs `"ai-flagship/low-noise"` both start from
+/// [`SystemProfile::AIFlagship`] but override different tunables. Mirrors how tuning tools expose
+/// several selectable presets per hardware class instead of one fixed profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileVariant {
+    /// Stable identifier, e.g. `"ai-flagship/max-perf"`
+    pub id: String,
+    /// Human-readable name shown in a picker UI
+    pub name: String,
+    pub base_profile: SystemProfile,
+    /// CPU governor override; falls back to the base profile's default in
+    /// [`crate::apply_optimizations`] when `None`
+    pub cpu_governor: Option<String>,
+    /// `power_dpm_force_performance_level` override for AMD GPUs
+    pub gpu_performance_level: Option<String>,
+    /// GPU power cap in watts, applied via NVML where supported
+    pub power_cap_watts: Option<u32>,
+}
+
+/// The set of [`ProfileVariant`]s a user can choose between, loaded by id or name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileVariantRegistry {
+    pub variants: Vec<ProfileVariant>,
+}
+
+impl Default for ProfileVariantRegistry {
+    fn default() -> Self {
+        Self {
+            variants: built_in_variants(),
+        }
+    }
+}
+
+impl ProfileVariantRegistry {
+    pub fn by_id(&self, id: &str) -> Option<&ProfileVariant> {
+        self.variants.iter().find(|v| v.id == id)
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&ProfileVariant> {
+        self.variants
+            .iter()
+            .find(|v| v.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Every variant whose base profile matches `profile`, in declared order -- the list
+    /// [`crate::HardwareDetector::detect`] attaches to [`crate::HardwareInfo`] so a UI can offer
+    /// them.
+    pub fn applicable_to(&self, profile: &SystemProfile) -> Vec<ProfileVariant> {
+        self.variants
+            .iter()
+            .filter(|v| std::mem::discriminant(&v.base_profile) == std::mem::discriminant(profile))
+            .cloned()
+            .collect()
+    }
+}
+
+fn built_in_variants() -> Vec<ProfileVariant> {
+    vec![
+        ProfileVariant {
+            id: "ai-flagship/max-perf".to_string(),
+            name: "AI Flagship (Max Performance)".to_string(),
+            base_profile: SystemProfile::AIFlagship,
+            cpu_governor: Some("performance".to_string()),
+            gpu_performance_level: Some("high".to_string()),
+            power_cap_watts: None,
+        },
+        ProfileVariant {
+            id: "ai-flagship/low-noise".to_string(),
+            name: "AI Flagship (Low Noise)".to_string(),
+            base_profile: SystemProfile::AIFlagship,
+            cpu_governor: Some("schedutil".to_string()),
+            gpu_performance_level: Some("auto".to_string()),
+            power_cap_watts: Some(250),
+        },
+        ProfileVariant {
+            id: "pro-workstation/max-perf".to_string(),
+            name: "Pro Workstation (Max Performance)".to_string(),
+            base_profile: SystemProfile::ProWorkstation,
+            cpu_governor: Some("performance".to_string()),
+            gpu_performance_level: Some("high".to_string()),
+            power_cap_watts: None,
+        },
+        ProfileVariant {
+            id: "pro-workstation/low-noise".to_string(),
+            name: "Pro Workstation (Low Noise)".to_string(),
+            base_profile: SystemProfile::ProWorkstation,
+            cpu_governor: Some("schedutil".to_string()),
+            gpu_performance_level: Some("auto".to_string()),
+            power_cap_watts: Some(200),
+        },
+        ProfileVariant {
+            id: "high-performance/balanced".to_string(),
+            name: "High Performance (Balanced)".to_string(),
+            base_profile: SystemProfile::HighPerformance,
+            cpu_governor: Some("performance".to_string()),
+            gpu_performance_level: Some("auto".to_string()),
+            power_cap_watts: None,
+        },
+        ProfileVariant {
+            id: "developer/default".to_string(),
+            name: "Developer".to_string(),
+            base_profile: SystemProfile::Developer,
+            cpu_governor: Some("schedutil".to_string()),
+            gpu_performance_level: None,
+            power_cap_watts: None,
+        },
+        ProfileVariant {
+            id: "standard/default".to_string(),
+            name: "Standard".to_string(),
+            base_profile: SystemProfile::Standard,
+            cpu_governor: Some("powersave".to_string()),
+            gpu_performance_level: None,
+            power_cap_watts: None,
+        },
+    ]
 }
\ No newline at end of file
@@ -3,8 +3,11 @@
 //! Core functionality for hardware detection, profiling, and optimization
 
 pub mod config;
+pub mod optimizations;
+pub mod telemetry;
 
 use anyhow::Result;
+use nvml_wrapper::Nvml;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
@@ -33,6 +36,9 @@ pub struct HardwareInfo {
     pub gpu: Vec<GpuInfo>,
     pub storage: Vec<StorageInfo>,
     pub profile: SystemProfile,
+    /// Named tuning presets applicable to `profile`, for a UI to offer instead of the one fixed
+    /// profile `determine_profile` assigned. See [`config::ProfileVariantRegistry`].
+    pub variants: Vec<config::ProfileVariant>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,6 +57,21 @@ pub struct MemoryInfo {
     pub total_gb: f64,
     pub speed_mhz: Option<u32>,
     pub memory_type: Option<String>, // DDR4, DDR5
+    /// One entry per populated DIMM slot, parsed from SMBIOS Type 17 by
+    /// [`HardwareDetector::detect_memory_details`]
+    pub dimms: Vec<DimmInfo>,
+}
+
+/// A single populated memory slot, decoded from an SMBIOS Type 17 (Memory Device) structure.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DimmInfo {
+    pub locator: String,
+    pub size_gb: f64,
+    /// Configured (running) memory clock speed, preferred over the module's rated maximum
+    pub speed_mhz: Option<u32>,
+    pub memory_type: Option<String>,
+    pub manufacturer: Option<String>,
+    pub part_number: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -60,6 +81,19 @@ pub struct GpuInfo {
     pub vram_gb: f64,
     pub driver_version: Option<String>,
     pub compute_capability: Option<String>,
+    /// PCIe bus id (e.g. `"0000:01:00.0"`), used by [`HardwareDetector::determine_profile`] to
+    /// tell apart otherwise-identical SKUs in a multi-GPU system
+    pub pci_bus_id: Option<String>,
+    /// Current PCIe link generation (1-5); `None` on vendors NVML doesn't cover
+    pub pcie_link_gen: Option<u32>,
+    /// Current PCIe link width, in lanes; `None` on vendors NVML doesn't cover
+    pub pcie_link_width: Option<u32>,
+    /// Current die temperature, in Celsius
+    pub temperature_c: Option<u32>,
+    /// Current fan speed, in RPM
+    pub fan_rpm: Option<u32>,
+    /// Current GPU (shader) clock, in MHz
+    pub clock_mhz: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,13 +126,17 @@ pub enum StorageType {
 /// Main hardware detector
 pub struct HardwareDetector {
     system: System,
+    /// `None` on hosts with no NVIDIA driver loaded, in which case [`Self::detect_nvidia_gpus`]
+    /// degrades to an empty vec rather than erroring out of [`Self::detect`].
+    nvml: Option<Nvml>,
 }
 
 impl HardwareDetector {
     pub fn new() -> Self {
         let mut system = System::new_all();
         system.refresh_all();
-        Self { system }
+        let nvml = Nvml::init().ok();
+        Self { system, nvml }
     }
 
     /// Detect all hardware and create a profile
@@ -109,13 +147,15 @@ impl HardwareDetector {
         let storage = self.detect_storage()?;
         
         let profile = Self::determine_profile(&cpu, &memory, &gpu);
-        
+        let variants = config::ProfileVariantRegistry::default().applicable_to(&profile);
+
         Ok(HardwareInfo {
             cpu,
             memory,
             gpu,
             storage,
             profile,
+            variants,
         })
     }
 
@@ -164,22 +204,151 @@ impl HardwareDetector {
     fn detect_memory(&self) -> Result<MemoryInfo> {
         let total_memory = self.system.total_memory();
         let total_gb = total_memory as f64 / 1024.0 / 1024.0 / 1024.0;
-        
-        // Try to detect memory speed from dmidecode (would need sudo)
-        let (speed_mhz, memory_type) = self.detect_memory_details()
-            .unwrap_or((None, None));
-        
+
+        let dimms = self.detect_memory_details().unwrap_or_default();
+        let speed_mhz = dimms.iter().find_map(|d| d.speed_mhz);
+        let memory_type = dimms.iter().find_map(|d| d.memory_type.clone());
+
         Ok(MemoryInfo {
             total_gb,
             speed_mhz,
             memory_type,
+            dimms,
         })
     }
 
-    fn detect_memory_details(&self) -> Result<(Option<u32>, Option<String>)> {
-        // This would parse dmidecode output
-        // For now, return placeholders
-        Ok((None, None))
+    /// Parse SMBIOS Type 17 (Memory Device) structures straight from
+    /// `/sys/firmware/dmi/tables/DMI` -- readable without root, unlike shelling out to
+    /// `dmidecode` -- to get the real configured memory clock and DDR generation per DIMM.
+    fn detect_memory_details(&self) -> Result<Vec<DimmInfo>> {
+        let table = fs::read("/sys/firmware/dmi/tables/DMI")?;
+        Ok(Self::parse_smbios_memory_devices(&table))
+    }
+
+    /// Walk the raw SMBIOS structure table, decoding every Type 17 entry into a [`DimmInfo`].
+    /// Slots with no module installed report `Size == 0` and are skipped.
+    fn parse_smbios_memory_devices(table: &[u8]) -> Vec<DimmInfo> {
+        let mut dimms = Vec::new();
+        let mut offset = 0;
+
+        while offset + 4 <= table.len() {
+            let struct_type = table[offset];
+            let length = table[offset + 1] as usize;
+            let formatted_end = offset + length;
+
+            if struct_type == 127 || formatted_end > table.len() {
+                break; // end-of-table marker, or a truncated/malformed structure
+            }
+
+            let (strings, next_offset) = Self::read_smbios_strings(table, formatted_end);
+
+            if struct_type == 17 {
+                if let Some(dimm) = Self::parse_memory_device(&table[offset..formatted_end], &strings) {
+                    dimms.push(dimm);
+                }
+            }
+
+            offset = next_offset;
+        }
+
+        dimms
+    }
+
+    /// Read the null-terminated string table following a structure's formatted section, ending at
+    /// the first empty string (a double NUL). Returns the strings (1-indexed per the SMBIOS spec)
+    /// and the offset where the next structure begins.
+    fn read_smbios_strings(table: &[u8], mut offset: usize) -> (Vec<String>, usize) {
+        let mut strings = Vec::new();
+
+        loop {
+            let Some(str_len) = table[offset..].iter().position(|&b| b == 0) else {
+                return (strings, table.len());
+            };
+            if str_len == 0 {
+                return (strings, offset + 1);
+            }
+            strings.push(String::from_utf8_lossy(&table[offset..offset + str_len]).into_owned());
+            offset += str_len + 1;
+        }
+    }
+
+    /// Decode a single Type 17 structure's formatted section (already bounds-checked to
+    /// `length` bytes) plus its string table into a [`DimmInfo`], or `None` if the slot has no
+    /// module installed.
+    fn parse_memory_device(bytes: &[u8], strings: &[String]) -> Option<DimmInfo> {
+        let string_at = |index: u8| -> Option<String> {
+            if index == 0 {
+                None
+            } else {
+                strings.get(index as usize - 1).cloned()
+            }
+        };
+        let u16_at = |offset: usize| -> Option<u16> {
+            bytes.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+        };
+
+        let size_raw = u16_at(0x0C)?;
+        if size_raw == 0 {
+            return None; // slot not populated
+        }
+
+        let size_gb = if size_raw == 0xFFFF {
+            let extended = bytes
+                .get(0x1C..0x20)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .unwrap_or(0);
+            extended as f64 / 1024.0 // extended size is in MB
+        } else if size_raw & 0x8000 != 0 {
+            (size_raw & 0x7FFF) as f64 / 1024.0 / 1024.0 // high bit set means KB, not MB
+        } else {
+            size_raw as f64 / 1024.0
+        };
+
+        let locator = bytes
+            .get(0x10)
+            .and_then(|&idx| string_at(idx))
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        let memory_type = bytes.get(0x12).and_then(|&code| Self::memory_type_name(code));
+
+        // Prefer the configured (running) clock speed over the module's rated maximum, falling
+        // back to the maximum when the configured field isn't present on this SMBIOS version.
+        let speed_mhz = u16_at(0x20)
+            .filter(|&v| v != 0)
+            .or_else(|| u16_at(0x15))
+            .map(|v| v as u32);
+
+        let manufacturer = bytes.get(0x17).and_then(|&idx| string_at(idx));
+        let part_number = bytes.get(0x1A).and_then(|&idx| string_at(idx));
+
+        Some(DimmInfo {
+            locator,
+            size_gb,
+            speed_mhz,
+            memory_type,
+            manufacturer,
+            part_number,
+        })
+    }
+
+    /// Map an SMBIOS Type 17 "Memory Type" code (spec Table 76) to its common name.
+    fn memory_type_name(code: u8) -> Option<String> {
+        let name = match code {
+            0x13 => "DDR",
+            0x14 => "DDR2",
+            0x19 => "DDR3",
+            0x1B => "DDR4",
+            0x1C => "LPDDR",
+            0x1D => "LPDDR2",
+            0x1E => "LPDDR3",
+            0x1F => "LPDDR4",
+            0x21 => "HBM",
+            0x22 => "HBM2",
+            0x23 => "DDR5",
+            0x24 => "LPDDR5",
+            _ => return None,
+        };
+        Some(name.to_string())
     }
 
     fn detect_gpu(&self) -> Result<Vec<GpuInfo>> {
@@ -204,14 +373,152 @@ impl HardwareDetector {
     }
 
     fn detect_nvidia_gpus(&self) -> Result<Vec<GpuInfo>> {
-        // Would use nvml-wrapper here
-        // Placeholder implementation
-        Ok(vec![])
+        let Some(nvml) = &self.nvml else {
+            return Ok(vec![]);
+        };
+
+        let device_count = nvml.device_count()?;
+        let driver_version = nvml.sys_driver_version().ok();
+        let mut gpus = Vec::with_capacity(device_count as usize);
+
+        for i in 0..device_count {
+            let Ok(device) = nvml.device_by_index(i) else {
+                continue;
+            };
+
+            let model = device.name().unwrap_or_else(|_| "Unknown NVIDIA GPU".to_string());
+            let vram_gb = device
+                .memory_info()
+                .map(|mem| mem.total as f64 / 1024.0 / 1024.0 / 1024.0)
+                .unwrap_or(0.0);
+            let compute_capability = device
+                .cuda_compute_capability()
+                .ok()
+                .map(|cc| format!("{}.{}", cc.major, cc.minor));
+            let pci_bus_id = device.pci_info().ok().map(|pci| pci.bus_id);
+            let pcie_link_gen = device.current_pcie_link_gen().ok();
+            let pcie_link_width = device.current_pcie_link_width().ok();
+
+            gpus.push(GpuInfo {
+                vendor: GpuVendor::Nvidia,
+                model,
+                vram_gb,
+                driver_version: driver_version.clone(),
+                compute_capability,
+                pci_bus_id,
+                pcie_link_gen,
+                pcie_link_width,
+                temperature_c: None,
+                fan_rpm: None,
+                clock_mhz: None,
+            });
+        }
+
+        Ok(gpus)
     }
 
     fn detect_amd_gpus(&self) -> Result<Vec<GpuInfo>> {
-        // Would parse /sys/class/drm
-        Ok(vec![])
+        let mut gpus = Vec::new();
+
+        let drm_path = Path::new("/sys/class/drm");
+        if !drm_path.exists() {
+            return Ok(gpus);
+        }
+
+        for entry in fs::read_dir(drm_path)? {
+            let entry = entry?;
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if !name.starts_with("card") || name.contains("renderD") {
+                continue;
+            }
+
+            let device_path = entry.path().join("device");
+            let Ok(vendor) = fs::read_to_string(device_path.join("vendor")) else {
+                continue;
+            };
+            if vendor.trim() != "0x1002" {
+                continue;
+            }
+
+            let device_id = fs::read_to_string(device_path.join("device"))
+                .ok()
+                .map(|s| s.trim().to_string());
+            let model = fs::read_to_string(device_path.join("product_name"))
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .or_else(|| device_id.map(|id| format!("AMD GPU {id}")))
+                .unwrap_or_else(|| "AMD GPU (unknown)".to_string());
+
+            let vram_gb = fs::read_to_string(device_path.join("mem_info_vram_total"))
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+                .map(|bytes| bytes as f64 / 1024.0 / 1024.0 / 1024.0)
+                .unwrap_or(0.0);
+
+            let hwmon_path = Self::find_amd_hwmon_path(&device_path);
+            let temperature_c = hwmon_path.as_ref().and_then(|hwmon| {
+                fs::read_to_string(hwmon.join("temp1_input"))
+                    .ok()
+                    .and_then(|s| s.trim().parse::<u32>().ok())
+                    .map(|millidegrees| millidegrees / 1000)
+            });
+            let fan_rpm = hwmon_path.as_ref().and_then(|hwmon| {
+                fs::read_to_string(hwmon.join("fan1_input"))
+                    .ok()
+                    .and_then(|s| s.trim().parse::<u32>().ok())
+            });
+            let clock_mhz = Self::read_amd_current_sclk(&device_path);
+
+            gpus.push(GpuInfo {
+                vendor: GpuVendor::Amd,
+                model,
+                vram_gb,
+                driver_version: None,
+                compute_capability: None,
+                pci_bus_id: Self::amd_pci_bus_id(&device_path),
+                pcie_link_gen: None,
+                pcie_link_width: None,
+                temperature_c,
+                fan_rpm,
+                clock_mhz,
+            });
+        }
+
+        Ok(gpus)
+    }
+
+    /// The device's PCI bus address (e.g. `"0000:01:00.0"`): `device_path` is a symlink into
+    /// `/sys/bus/pci/devices/<bdf>`, so its canonical form's last path component is the address.
+    fn amd_pci_bus_id(device_path: &Path) -> Option<String> {
+        let canonical = fs::canonicalize(device_path).ok()?;
+        canonical.file_name()?.to_str().map(str::to_string)
+    }
+
+    /// The first `hwmon*` directory under `device_path/hwmon`, if any.
+    fn find_amd_hwmon_path(device_path: &Path) -> Option<std::path::PathBuf> {
+        let hwmon_dir = device_path.join("hwmon");
+        for entry in fs::read_dir(hwmon_dir).ok()?.flatten() {
+            if entry.file_name().to_str().is_some_and(|n| n.starts_with("hwmon")) {
+                return Some(entry.path());
+            }
+        }
+        None
+    }
+
+    /// The currently-selected clock from `pp_dpm_sclk`, whose lines look like `"1: 1500Mhz *"`
+    /// with `*` marking the active power state.
+    fn read_amd_current_sclk(device_path: &Path) -> Option<u32> {
+        let contents = fs::read_to_string(device_path.join("pp_dpm_sclk")).ok()?;
+        let current_line = contents.lines().find(|line| line.contains('*'))?;
+        let mhz_pos = current_line.find("Mhz")?;
+        let digits_start = current_line[..mhz_pos]
+            .rfind(|c: char| !c.is_ascii_digit())
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        current_line[digits_start..mhz_pos].parse().ok()
     }
 
     fn detect_intel_gpus(&self) -> Result<Vec<GpuInfo>> {
@@ -245,27 +552,29 @@ impl HardwareDetector {
 
     fn analyze_storage_device(&self, device: &str) -> Result<StorageInfo> {
         let device_path = format!("/sys/block/{}", device);
-        
+
         // Detect if NVMe
-        let storage_type = if device.starts_with("nvme") {
-            // Try to detect NVMe generation
-            StorageType::NvmeGen4 // Placeholder
+        let (storage_type, nvme_gen) = if device.starts_with("nvme") {
+            match Self::nvme_pcie_generation(&device_path) {
+                Some((storage_type, gen)) => (storage_type, Some(gen)),
+                None => (StorageType::NvmeGen3, None),
+            }
         } else if device.starts_with("sd") {
             // Check if SSD or HDD via rotational flag
             let rotational_path = format!("{}/queue/rotational", device_path);
             let is_hdd = fs::read_to_string(rotational_path)
                 .unwrap_or_else(|_| "1".to_string())
                 .trim() == "1";
-            
+
             if is_hdd {
-                StorageType::Hdd
+                (StorageType::Hdd, None)
             } else {
-                StorageType::Sata
+                (StorageType::Sata, None)
             }
         } else {
-            StorageType::Unknown
+            (StorageType::Unknown, None)
         };
-        
+
         // Get size
         let size_path = format!("{}/size", device_path);
         let sectors = fs::read_to_string(size_path)
@@ -274,13 +583,79 @@ impl HardwareDetector {
             .parse::<u64>()
             .unwrap_or(0);
         let total_gb = (sectors * 512) as f64 / 1024.0 / 1024.0 / 1024.0;
-        
+
         Ok(StorageInfo {
             device: format!("/dev/{}", device),
-            mount_point: "/".to_string(), // Would need to check mounts
+            mount_point: Self::mount_point_for(device).unwrap_or_else(|| "/".to_string()),
             total_gb,
             storage_type,
-            nvme_gen: None,
+            nvme_gen,
+        })
+    }
+
+    /// Resolve an `nvme*` block device's backing PCIe link speed and map it to a generation.
+    /// Prefers `current_link_speed`, falling back to `max_link_speed` when the link has been
+    /// downtrained (e.g. ASPM power saving) and no longer reflects the device's real capability.
+    fn nvme_pcie_generation(device_path: &str) -> Option<(StorageType, u32)> {
+        let pcie_device = fs::canonicalize(format!("{device_path}/device/device")).ok()?;
+        let pcie_dir = pcie_device.parent()?;
+
+        let read_gt_s = |file: &str| -> Option<f64> {
+            fs::read_to_string(pcie_dir.join(file))
+                .ok()?
+                .split_whitespace()
+                .next()?
+                .parse()
+                .ok()
+        };
+
+        let gt_s = read_gt_s("current_link_speed").or_else(|| read_gt_s("max_link_speed"))?;
+        Self::storage_type_for_gt_s(gt_s)
+    }
+
+    /// Map a PCIe link speed in GT/s to its generation: 2.5->1, 5.0->2, 8.0->3, 16.0->4, 32.0->5.
+    /// `StorageType` has no Gen1/Gen2 variant, so those bucket into `NvmeGen3` while `nvme_gen`
+    /// still records the exact generation.
+    fn storage_type_for_gt_s(gt_s: f64) -> Option<(StorageType, u32)> {
+        let gen = if (gt_s - 2.5).abs() < 0.5 {
+            1
+        } else if (gt_s - 5.0).abs() < 0.5 {
+            2
+        } else if (gt_s - 8.0).abs() < 0.5 {
+            3
+        } else if (gt_s - 16.0).abs() < 1.0 {
+            4
+        } else if (gt_s - 32.0).abs() < 1.0 {
+            5
+        } else {
+            return None;
+        };
+
+        let storage_type = match gen {
+            5 => StorageType::NvmeGen5,
+            4 => StorageType::NvmeGen4,
+            _ => StorageType::NvmeGen3,
+        };
+        Some((storage_type, gen))
+    }
+
+    /// Find `device`'s mount point by cross-referencing `/proc/mounts`, matching both the raw
+    /// device (`nvme0n1`, `sda`) and its partitions (`nvme0n1p1`, `sda1`).
+    fn mount_point_for(device: &str) -> Option<String> {
+        let mounts = fs::read_to_string("/proc/mounts").ok()?;
+        let dev_path = format!("/dev/{device}");
+
+        mounts.lines().find_map(|line| {
+            let mut fields = line.split_whitespace();
+            let source = fields.next()?;
+            let target = fields.next()?;
+
+            let is_match = source == dev_path
+                || source.strip_prefix(dev_path.as_str()).is_some_and(|rest| {
+                    rest.starts_with('p') || rest.chars().next().is_some_and(|c| c.is_ascii_digit())
+                });
+
+            is_match.then(|| target.replace("\\040", " "))
         })
     }
 
@@ -320,46 +695,75 @@ impl HardwareDetector {
     }
 }
 
-/// Apply system optimizations based on profile
-pub fn apply_optimizations(profile: &SystemProfile) -> Result<()> {
-    match profile {
-        SystemProfile::AIFlagship => apply_ai_flagship_optimizations(),
-        SystemProfile::ProWorkstation => apply_pro_workstation_optimizations(),
-        SystemProfile::HighPerformance => apply_high_performance_optimizations(),
-        SystemProfile::Developer => apply_developer_optimizations(),
-        SystemProfile::Standard => apply_standard_optimizations(),
+/// Apply system optimizations for a resolved [`config::ProfileVariant`], writing the real CPU/GPU
+/// tunables rather than just printing what would happen. The variant's base profile supplies the
+/// defaults; any field it overrides (governor, GPU performance level, power cap) is applied on top.
+/// `cpu_affinity`, if given, additionally pins this process to those CPU indices. Returns a report
+/// of every tunable actually changed, which can be
+/// [`optimizations::OptimizationReport::revert`]ed to restore the prior values.
+pub fn apply_optimizations(
+    variant: &config::ProfileVariant,
+    cpu_affinity: Option<&[usize]>,
+) -> Result<optimizations::OptimizationReport> {
+    let mut report = match variant.base_profile {
+        SystemProfile::AIFlagship => apply_ai_flagship_optimizations()?,
+        SystemProfile::ProWorkstation => apply_pro_workstation_optimizations()?,
+        SystemProfile::HighPerformance => apply_high_performance_optimizations()?,
+        SystemProfile::Developer => apply_developer_optimizations()?,
+        SystemProfile::Standard => apply_standard_optimizations()?,
+    };
+
+    if let Some(governor) = &variant.cpu_governor {
+        optimizations::set_cpu_governor(&mut report, governor)?;
+    }
+    if let Some(level) = &variant.gpu_performance_level {
+        optimizations::set_amd_performance_level(&mut report, level)?;
     }
+    if let Some(watts) = variant.power_cap_watts {
+        optimizations::set_nvidia_power_cap(&mut report, watts)?;
+    }
+    if let Some(cpus) = cpu_affinity {
+        optimizations::pin_cpu_affinity(cpus)?;
+    }
+
+    Ok(report)
 }
 
-fn apply_ai_flagship_optimizations() -> Result<()> {
-    // Set aggressive performance settings
-    // - CPU governor to performance
-    // - GPU to maximum performance
-    // - Disable all power saving
-    // - Maximize PCIe bandwidth
-    // - Set memory to lowest latency
-    println!("Applying AI Flagship optimizations...");
-    Ok(())
+fn apply_ai_flagship_optimizations() -> Result<optimizations::OptimizationReport> {
+    // Aggressive performance settings: CPU governor to performance, GPU to maximum performance,
+    // power saving disabled everywhere it's exposed.
+    let mut report = optimizations::OptimizationReport::default();
+    optimizations::set_cpu_governor(&mut report, "performance")?;
+    optimizations::set_nvidia_max_performance(&mut report)?;
+    optimizations::set_amd_performance_level(&mut report, "high")?;
+    Ok(report)
 }
 
-fn apply_pro_workstation_optimizations() -> Result<()> {
-    println!("Applying Pro Workstation optimizations...");
-    Ok(())
+fn apply_pro_workstation_optimizations() -> Result<optimizations::OptimizationReport> {
+    let mut report = optimizations::OptimizationReport::default();
+    optimizations::set_cpu_governor(&mut report, "performance")?;
+    optimizations::set_nvidia_max_performance(&mut report)?;
+    optimizations::set_amd_performance_level(&mut report, "high")?;
+    Ok(report)
 }
 
-fn apply_high_performance_optimizations() -> Result<()> {
-    println!("Applying High Performance optimizations...");
-    Ok(())
+fn apply_high_performance_optimizations() -> Result<optimizations::OptimizationReport> {
+    let mut report = optimizations::OptimizationReport::default();
+    optimizations::set_cpu_governor(&mut report, "performance")?;
+    optimizations::set_amd_performance_level(&mut report, "high")?;
+    Ok(report)
 }
 
-fn apply_developer_optimizations() -> Result<()> {
-    println!("Applying Developer optimizations...");
-    Ok(())
+fn apply_developer_optimizations() -> Result<optimizations::OptimizationReport> {
+    let mut report = optimizations::OptimizationReport::default();
+    optimizations::set_cpu_governor(&mut report, "schedutil")?;
+    Ok(report)
 }
 
-fn apply_standard_optimizations() -> Result<()> {
-    println!("Applying Standard optimizations...");
-    Ok(())
+fn apply_standard_optimizations() -> Result<optimizations::OptimizationReport> {
+    let mut report = optimizations::OptimizationReport::default();
+    optimizations::set_cpu_governor(&mut report, "powersave")?;
+    Ok(report)
 }
 
 #[cfg(test)]
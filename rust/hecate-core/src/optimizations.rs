@@ -0,0 +1,287 @@
+//! Real tunable writes backing [`crate::apply_optimizations`]
+//!
+//! Every change is captured as a [`TunableChange`] so the resulting [`OptimizationReport`] can be
+//! [`OptimizationReport::revert`]ed later instead of being a fire-and-forget `println!`. Writing a
+//! tunable that doesn't exist on this system, or that this process lacks permission for, is a
+//! per-tunable error rather than a silently skipped no-op.
+
+use anyhow::{bail, Context, Result};
+use nvml_wrapper::enum_wrappers::device::{Clock, EnableState};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One tunable write, with enough information to put it back the way it was.
+#[derive(Debug, Clone)]
+pub struct TunableChange {
+    /// The sysfs path written, or a synthetic `nvml:<index>:<tunable>` id for NVML-only tunables
+    /// that have no backing file.
+    pub path: PathBuf,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+/// Every change [`crate::apply_optimizations`] made, in application order, so [`Self::revert`] can
+/// undo them in reverse.
+#[derive(Debug, Clone, Default)]
+pub struct OptimizationReport {
+    pub changes: Vec<TunableChange>,
+}
+
+impl OptimizationReport {
+    fn record(&mut self, path: impl Into<PathBuf>, old_value: impl Into<String>, new_value: impl Into<String>) {
+        self.changes.push(TunableChange {
+            path: path.into(),
+            old_value: old_value.into(),
+            new_value: new_value.into(),
+        });
+    }
+
+    /// Write every captured `old_value` back, in reverse order of application.
+    pub fn revert(&self) -> Result<()> {
+        for change in self.changes.iter().rev() {
+            match change.path.to_str() {
+                Some(id) if id.starts_with("nvml:") => revert_nvidia_change(id, &change.old_value)?,
+                _ => fs::write(&change.path, &change.old_value)
+                    .with_context(|| format!("failed to revert {}", change.path.display()))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether this process has root privileges, read from `/proc/self/status`'s `Uid:` line
+/// (`Uid:\t<real>\t<effective>\t<saved>\t<filesystem>`) rather than a `libc`/`nix` dependency this
+/// crate doesn't otherwise carry.
+fn is_root() -> bool {
+    fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            status.lines().find_map(|line| {
+                let rest = line.strip_prefix("Uid:")?;
+                rest.split_whitespace().nth(1)?.parse::<u32>().ok()
+            })
+        })
+        .map(|euid| euid == 0)
+        .unwrap_or(false)
+}
+
+/// Write `value` to `path`, recording the previous contents in `report`. Returns a specific error
+/// instead of silently succeeding when the tunable is missing or this process isn't root.
+fn write_tunable(report: &mut OptimizationReport, path: &Path, value: &str) -> Result<()> {
+    if !path.exists() {
+        bail!("tunable {} does not exist on this system", path.display());
+    }
+    if !is_root() {
+        bail!("writing {} requires root", path.display());
+    }
+
+    let old_value = fs::read_to_string(path)
+        .with_context(|| format!("failed to read current value of {}", path.display()))?
+        .trim()
+        .to_string();
+
+    fs::write(path, value).with_context(|| format!("failed to write {value} to {}", path.display()))?;
+    report.record(path, old_value, value);
+    Ok(())
+}
+
+/// Every `cpu*/cpufreq/scaling_governor` file under `/sys/devices/system/cpu`.
+fn cpu_governor_paths() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let name = name.to_str()?;
+            if !name.starts_with("cpu") || !name[3..].chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let path = entry.path().join("cpufreq").join("scaling_governor");
+            path.exists().then_some(path)
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Set every CPU's scaling governor (`"performance"`, `"powersave"`, `"schedutil"`, ...),
+/// recording each write. A core with no `cpufreq` driver is skipped rather than failing the batch.
+pub fn set_cpu_governor(report: &mut OptimizationReport, governor: &str) -> Result<()> {
+    for path in cpu_governor_paths() {
+        write_tunable(report, &path, governor)?;
+    }
+    Ok(())
+}
+
+/// Every `power_dpm_force_performance_level` file belonging to an amdgpu device, mirroring
+/// [`crate::HardwareDetector::detect_amd_gpus`]'s card enumeration.
+fn amd_performance_level_paths() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir("/sys/class/drm") else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let name = name.to_str()?;
+            if !name.starts_with("card") || name.contains("render") {
+                return None;
+            }
+            let device_path = entry.path().join("device");
+            let vendor = fs::read_to_string(device_path.join("vendor")).ok()?;
+            if vendor.trim() != "0x1002" {
+                return None;
+            }
+            let path = device_path.join("power_dpm_force_performance_level");
+            path.exists().then_some(path)
+        })
+        .collect()
+}
+
+/// Set every AMD GPU's `power_dpm_force_performance_level` (`"auto"`, `"low"`, `"high"`, ...).
+pub fn set_amd_performance_level(report: &mut OptimizationReport, level: &str) -> Result<()> {
+    for path in amd_performance_level_paths() {
+        write_tunable(report, &path, level)?;
+    }
+    Ok(())
+}
+
+/// Enable NVML persistence mode and pin every NVIDIA GPU's application clocks to their maximum,
+/// where the driver permits it. No NVIDIA driver at all is not an error -- there's simply nothing
+/// to do.
+pub fn set_nvidia_max_performance(report: &mut OptimizationReport) -> Result<()> {
+    let Ok(nvml) = nvml_wrapper::Nvml::init() else {
+        return Ok(());
+    };
+
+    let device_count = nvml.device_count()?;
+    for index in 0..device_count {
+        let mut device = nvml.device_by_index(index)?;
+
+        let was_persistent = matches!(device.is_in_persistent_mode(), Ok(EnableState::Enabled));
+        device
+            .set_persistent(true)
+            .with_context(|| format!("enabling persistence mode on NVIDIA GPU {index} requires root"))?;
+        report.record(format!("nvml:{index}:persistence_mode"), was_persistent.to_string(), "true");
+
+        let max_graphics_clock = device.max_clock_info(Clock::Graphics);
+        let max_memory_clock = device.max_clock_info(Clock::Memory);
+        if let (Ok(graphics_mhz), Ok(memory_mhz)) = (max_graphics_clock, max_memory_clock) {
+            device
+                .set_applications_clocks(memory_mhz, graphics_mhz)
+                .with_context(|| format!("setting max clocks on NVIDIA GPU {index} requires root"))?;
+            report.record(
+                format!("nvml:{index}:applications_clocks"),
+                "default",
+                format!("{memory_mhz},{graphics_mhz}"),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Cap every NVIDIA GPU's power draw at `watts`, where the card and driver permit it.
+pub fn set_nvidia_power_cap(report: &mut OptimizationReport, watts: u32) -> Result<()> {
+    let Ok(nvml) = nvml_wrapper::Nvml::init() else {
+        return Ok(());
+    };
+
+    let milliwatts = watts * 1000;
+    let device_count = nvml.device_count()?;
+    for index in 0..device_count {
+        let mut device = nvml.device_by_index(index)?;
+        let old_milliwatts = device
+            .power_management_limit()
+            .with_context(|| format!("failed to read current power limit of NVIDIA GPU {index}"))?;
+
+        device
+            .set_power_management_limit(milliwatts)
+            .with_context(|| format!("setting power cap on NVIDIA GPU {index} requires root"))?;
+        report.record(
+            format!("nvml:{index}:power_management_limit"),
+            old_milliwatts.to_string(),
+            milliwatts.to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+fn revert_nvidia_change(id: &str, old_value: &str) -> Result<()> {
+    let mut parts = id.split(':').skip(1);
+    let index: u32 = parts
+        .next()
+        .context("malformed nvml tunable id")?
+        .parse()
+        .context("malformed nvml tunable id")?;
+    let tunable = parts.next().context("malformed nvml tunable id")?;
+
+    let nvml = nvml_wrapper::Nvml::init().context("NVML unavailable while reverting")?;
+    let mut device = nvml.device_by_index(index)?;
+
+    match tunable {
+        "persistence_mode" => {
+            let enabled = old_value.parse::<bool>().unwrap_or(false);
+            device
+                .set_persistent(enabled)
+                .context("failed to revert NVIDIA persistence mode")?;
+        }
+        "applications_clocks" => {
+            if old_value == "default" {
+                device
+                    .reset_applications_clocks()
+                    .context("failed to reset NVIDIA applications clocks")?;
+            }
+        }
+        "power_management_limit" => {
+            let milliwatts: u32 = old_value.parse().context("malformed power limit value")?;
+            device
+                .set_power_management_limit(milliwatts)
+                .context("failed to revert NVIDIA power cap")?;
+        }
+        other => bail!("unknown nvml tunable `{other}`"),
+    }
+
+    Ok(())
+}
+
+/// Pin the calling process to `cpus` (indices into `/sys/devices/system/cpu/cpuN`) via
+/// `sched_setaffinity(2)`. Declared locally rather than pulling in `libc`/`nix`, neither of which
+/// this crate otherwise depends on.
+pub fn pin_cpu_affinity(cpus: &[usize]) -> Result<()> {
+    const CPU_SETSIZE: usize = 1024;
+    const MASK_WORDS: usize = CPU_SETSIZE / (8 * std::mem::size_of::<u64>());
+
+    if cpus.iter().any(|&cpu| cpu >= CPU_SETSIZE) {
+        bail!("CPU index out of range (max {CPU_SETSIZE})");
+    }
+
+    #[repr(C)]
+    struct CpuSet {
+        bits: [u64; MASK_WORDS],
+    }
+
+    extern "C" {
+        fn sched_setaffinity(pid: i32, cpusetsize: usize, mask: *const CpuSet) -> i32;
+    }
+
+    let mut set = CpuSet { bits: [0; MASK_WORDS] };
+    for &cpu in cpus {
+        set.bits[cpu / 64] |= 1u64 << (cpu % 64);
+    }
+
+    let result = unsafe { sched_setaffinity(0, std::mem::size_of::<CpuSet>(), &set) };
+    if result != 0 {
+        bail!(
+            "sched_setaffinity failed: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+//! Continuous GPU/CPU telemetry sampling
+//!
+//! [`crate::HardwareDetector::detect`] is a one-shot snapshot; [`TelemetryMonitor`] instead polls
+//! hardware at a fixed interval and keeps a bounded ring buffer of timestamped samples, so a
+//! caller can watch trends over time or confirm that [`crate::apply_optimizations`] actually
+//! changed runtime behavior rather than just written config.
+
+use anyhow::Result;
+use nvml_wrapper::enum_wrappers::device::{Clock, TemperatureSensor};
+use nvml_wrapper::enums::device::UsedGpuMemory;
+use nvml_wrapper::Nvml;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sysinfo::{CpuExt, System, SystemExt};
+
+/// Per-GPU reading captured in a single [`TelemetrySample`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuTelemetry {
+    pub index: u32,
+    pub utilization_percent: u32,
+    pub memory_used_bytes: u64,
+    pub power_draw_watts: f32,
+    pub core_clock_mhz: u32,
+    pub memory_clock_mhz: u32,
+    pub temperature_c: u32,
+}
+
+/// Per-process GPU usage captured alongside each [`TelemetrySample`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuProcessTelemetry {
+    pub pid: u32,
+    pub gpu_index: u32,
+    pub memory_used_bytes: Option<u64>,
+    /// SM (core) utilization since the last sample; `None` on GPU generations that don't support
+    /// `nvmlDeviceGetProcessUtilization`
+    pub sm_utilization_percent: Option<u32>,
+}
+
+/// One timestamped reading from [`TelemetryMonitor::sample`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetrySample {
+    /// Unix timestamp, in seconds
+    pub timestamp: u64,
+    pub cpu_utilization_percent: f32,
+    pub gpus: Vec<GpuTelemetry>,
+    pub gpu_processes: Vec<GpuProcessTelemetry>,
+}
+
+/// Samples CPU and (NVIDIA) GPU telemetry at a fixed interval and keeps the last `capacity`
+/// readings in a ring buffer. NVML failing to initialize (no NVIDIA driver) just means every
+/// sample's `gpus`/`gpu_processes` come back empty -- CPU telemetry still works everywhere.
+pub struct TelemetryMonitor {
+    system: System,
+    nvml: Option<Nvml>,
+    interval: Duration,
+    history: VecDeque<TelemetrySample>,
+    capacity: usize,
+}
+
+impl TelemetryMonitor {
+    /// Create a monitor that samples every `interval` and keeps the last `capacity` samples.
+    pub fn new(interval: Duration, capacity: usize) -> Self {
+        let mut system = System::new_all();
+        system.refresh_all();
+
+        Self {
+            system,
+            nvml: Nvml::init().ok(),
+            interval,
+            history: VecDeque::with_capacity(capacity.max(1)),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// The configured sampling interval.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Take one sample now, push it into the ring buffer (evicting the oldest if full), and
+    /// return a clone of it.
+    pub fn sample(&mut self) -> Result<TelemetrySample> {
+        self.system.refresh_cpu();
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let cpu_utilization_percent = self.system.global_cpu_info().cpu_usage();
+        let (gpus, gpu_processes) = self.sample_nvidia_gpus();
+
+        let sample = TelemetrySample {
+            timestamp,
+            cpu_utilization_percent,
+            gpus,
+            gpu_processes,
+        };
+
+        if self.history.len() == self.capacity {
+            self.history.pop_front();
+        }
+        self.history.push_back(sample.clone());
+
+        Ok(sample)
+    }
+
+    /// Sample once, then sleep for [`Self::interval`] -- call in a loop to poll continuously.
+    pub fn sample_and_sleep(&mut self) -> Result<TelemetrySample> {
+        let sample = self.sample()?;
+        std::thread::sleep(self.interval);
+        Ok(sample)
+    }
+
+    /// Every sample currently held in the ring buffer, oldest first.
+    pub fn history(&self) -> Vec<TelemetrySample> {
+        self.history.iter().cloned().collect()
+    }
+
+    fn sample_nvidia_gpus(&self) -> (Vec<GpuTelemetry>, Vec<GpuProcessTelemetry>) {
+        let Some(nvml) = &self.nvml else {
+            return (Vec::new(), Vec::new());
+        };
+        let Ok(device_count) = nvml.device_count() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let mut gpus = Vec::new();
+        let mut processes = Vec::new();
+
+        for index in 0..device_count {
+            let Ok(device) = nvml.device_by_index(index) else {
+                continue;
+            };
+
+            let utilization_percent = device.utilization_rates().map(|u| u.gpu).unwrap_or(0);
+            let memory_used_bytes = device.memory_info().map(|m| m.used).unwrap_or(0);
+            let power_draw_watts = device
+                .power_usage()
+                .map(|milliwatts| milliwatts as f32 / 1000.0)
+                .unwrap_or(0.0);
+            let core_clock_mhz = device.clock_info(Clock::Graphics).unwrap_or(0);
+            let memory_clock_mhz = device.clock_info(Clock::Memory).unwrap_or(0);
+            let temperature_c = device.temperature(TemperatureSensor::Gpu).unwrap_or(0);
+
+            gpus.push(GpuTelemetry {
+                index,
+                utilization_percent,
+                memory_used_bytes,
+                power_draw_watts,
+                core_clock_mhz,
+                memory_clock_mhz,
+                temperature_c,
+            });
+
+            let sm_utilization = Self::sm_utilization(&device);
+            let compute = device.running_compute_processes().unwrap_or_default();
+            let graphics = device.running_graphics_processes().unwrap_or_default();
+
+            for info in compute.into_iter().chain(graphics) {
+                let memory_used_bytes = match info.used_gpu_memory {
+                    UsedGpuMemory::Used(bytes) => Some(bytes),
+                    UsedGpuMemory::Unavailable => None,
+                };
+
+                processes.push(GpuProcessTelemetry {
+                    pid: info.pid,
+                    gpu_index: index,
+                    memory_used_bytes,
+                    sm_utilization_percent: sm_utilization.get(&info.pid).copied(),
+                });
+            }
+        }
+
+        (gpus, processes)
+    }
+
+    /// Per-process SM utilization since the last second, keyed by PID. Not all GPU generations
+    /// support `nvmlDeviceGetProcessUtilization`, so a failure here just means every process
+    /// falls back to `None` rather than failing the whole sample.
+    fn sm_utilization(device: &nvml_wrapper::Device) -> HashMap<u32, u32> {
+        let since = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_micros().saturating_sub(1_000_000) as u64)
+            .unwrap_or(0);
+
+        device
+            .process_utilization_stats(since)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|sample| (sample.pid, sample.sm_util))
+            .collect()
+    }
+}
@@ -0,0 +1,248 @@
+//! Real RustSec advisory matching for `security_audit`, replacing the placeholder "no known
+//! vulnerabilities" message with a check of the locked dependency set against a local clone of
+//! the advisory database.
+
+use anyhow::{Context, Result};
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `[advisory]` section of a RustSec advisory TOML file
+#[derive(Debug, Clone, Deserialize)]
+struct AdvisoryMeta {
+    id: String,
+    package: String,
+    title: String,
+    /// Informational severity rating (e.g. `"critical"`, `"high"`), absent for advisories that
+    /// don't carry one
+    #[serde(default)]
+    severity: Option<String>,
+}
+
+/// `[versions]` section: the requirements that mark a version as fixed or never affected
+#[derive(Debug, Clone, Default, Deserialize)]
+struct VersionsTable {
+    #[serde(default)]
+    patched: Vec<String>,
+    #[serde(default)]
+    unaffected: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawAdvisory {
+    advisory: AdvisoryMeta,
+    #[serde(default)]
+    versions: VersionsTable,
+}
+
+/// A parsed RustSec advisory, ready to test against a locked package version
+#[derive(Debug, Clone)]
+pub struct Advisory {
+    pub id: String,
+    pub package: String,
+    pub title: String,
+    pub severity: Option<String>,
+    pub patched: Vec<VersionReq>,
+    pub unaffected: Vec<VersionReq>,
+    /// The advisory file's raw TOML text, for `--print-advisory`
+    pub raw_text: String,
+}
+
+impl Advisory {
+    fn parse(path: &Path) -> Result<Self> {
+        let raw_text =
+            fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+        let raw: RawAdvisory =
+            toml::from_str(&raw_text).with_context(|| format!("failed to parse {}", path.display()))?;
+
+        let patched = raw
+            .versions
+            .patched
+            .iter()
+            .map(|req| VersionReq::parse(req))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .with_context(|| format!("invalid patched version requirement in {}", path.display()))?;
+        let unaffected = raw
+            .versions
+            .unaffected
+            .iter()
+            .map(|req| VersionReq::parse(req))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .with_context(|| format!("invalid unaffected version requirement in {}", path.display()))?;
+
+        Ok(Advisory {
+            id: raw.advisory.id,
+            package: raw.advisory.package,
+            title: raw.advisory.title,
+            severity: raw.advisory.severity,
+            patched,
+            unaffected,
+            raw_text,
+        })
+    }
+
+    /// `version` is vulnerable to this advisory unless it satisfies a `patched` or `unaffected`
+    /// requirement
+    fn affects(&self, version: &Version) -> bool {
+        let fixed = self.patched.iter().any(|req| req.matches(version))
+            || self.unaffected.iter().any(|req| req.matches(version));
+        !fixed
+    }
+
+    fn first_patched_version(&self) -> String {
+        self.patched.first().map(ToString::to_string).unwrap_or_else(|| "no patch available".to_string())
+    }
+}
+
+/// Every advisory found under `db_dir`, recursively (the RustSec advisory-db layout nests them
+/// under `crates/<name>/<ID>.toml`)
+pub fn load_advisory_db(db_dir: &Path) -> Result<Vec<Advisory>> {
+    let mut advisories = Vec::new();
+    collect_advisories(db_dir, &mut advisories)?;
+    Ok(advisories)
+}
+
+fn collect_advisories(dir: &Path, out: &mut Vec<Advisory>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_advisories(&path, out)?;
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some("toml") {
+            out.push(Advisory::parse(&path)?);
+        }
+    }
+    Ok(())
+}
+
+/// The default location of the local advisory-db clone/cache, overridable with
+/// `HECATE_ADVISORY_DB`
+pub fn default_advisory_db_path() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var("HECATE_ADVISORY_DB") {
+        return Ok(PathBuf::from(dir));
+    }
+    let config_dir = match std::env::var("HECATE_CONFIG_DIR") {
+        Ok(dir) => PathBuf::from(dir),
+        Err(_) => {
+            let home = std::env::var("HOME").context("HOME is not set; cannot locate the advisory database")?;
+            PathBuf::from(home).join(".config").join("hecate")
+        }
+    };
+    Ok(config_dir.join("deps").join("advisory-db"))
+}
+
+/// A resolved package's name and version, read from `Cargo.lock`
+#[derive(Debug, Clone)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: Version,
+}
+
+/// Read just the name/version of every locked package from `Cargo.lock`
+pub fn load_locked_packages(path: &Path) -> Result<Vec<LockedPackage>> {
+    let content = fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let doc: toml::Value = content.parse().with_context(|| format!("failed to parse {}", path.display()))?;
+
+    Ok(doc
+        .get("package")
+        .and_then(|p| p.as_array())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let name = entry.get("name")?.as_str()?.to_string();
+                    let version = Version::parse(entry.get("version")?.as_str()?).ok()?;
+                    Some(LockedPackage { name, version })
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// One advisory that applies to a currently-locked, unpatched package version
+pub struct Finding<'a> {
+    pub package: &'a LockedPackage,
+    pub advisory: &'a Advisory,
+}
+
+impl Finding<'_> {
+    pub fn first_patched_version(&self) -> String {
+        self.advisory.first_patched_version()
+    }
+}
+
+/// Match every locked package against the advisory database, returning one [`Finding`] per
+/// applicable, unpatched advisory.
+pub fn find_vulnerabilities<'a>(packages: &'a [LockedPackage], advisories: &'a [Advisory]) -> Vec<Finding<'a>> {
+    let mut findings = Vec::new();
+    for package in packages {
+        for advisory in advisories {
+            if advisory.package == package.name && advisory.affects(&package.version) {
+                findings.push(Finding { package, advisory });
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advisory(package: &str, patched: &[&str], unaffected: &[&str]) -> Advisory {
+        Advisory {
+            id: "RUSTSEC-2024-0001".to_string(),
+            package: package.to_string(),
+            title: "Test advisory".to_string(),
+            severity: None,
+            patched: patched.iter().map(|r| VersionReq::parse(r).unwrap()).collect(),
+            unaffected: unaffected.iter().map(|r| VersionReq::parse(r).unwrap()).collect(),
+            raw_text: String::new(),
+        }
+    }
+
+    fn locked(name: &str, version: &str) -> LockedPackage {
+        LockedPackage { name: name.to_string(), version: Version::parse(version).unwrap() }
+    }
+
+    #[test]
+    fn test_version_below_patched_requirement_is_vulnerable() {
+        let advisory = advisory("time", &[">= 0.2.23"], &[]);
+        assert!(advisory.affects(&Version::parse("0.2.20").unwrap()));
+    }
+
+    #[test]
+    fn test_version_at_or_above_patched_requirement_is_safe() {
+        let advisory = advisory("time", &[">= 0.2.23"], &[]);
+        assert!(!advisory.affects(&Version::parse("0.2.23").unwrap()));
+    }
+
+    #[test]
+    fn test_unaffected_range_is_safe_even_below_the_patch() {
+        let advisory = advisory("time", &[">= 0.3.0"], &["< 0.2.0"]);
+        assert!(!advisory.affects(&Version::parse("0.1.5").unwrap()));
+    }
+
+    #[test]
+    fn test_find_vulnerabilities_matches_by_package_name_and_version() {
+        let packages = vec![locked("time", "0.2.20"), locked("serde", "1.0.190")];
+        let advisories = vec![advisory("time", &[">= 0.2.23"], &[])];
+
+        let findings = find_vulnerabilities(&packages, &advisories);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].package.name, "time");
+        assert_eq!(findings[0].first_patched_version(), ">=0.2.23");
+    }
+
+    #[test]
+    fn test_find_vulnerabilities_skips_patched_packages() {
+        let packages = vec![locked("time", "0.2.23")];
+        let advisories = vec![advisory("time", &[">= 0.2.23"], &[])];
+
+        assert!(find_vulnerabilities(&packages, &advisories).is_empty());
+    }
+}
@@ -0,0 +1,477 @@
+//! SPDX-aware license policy enforcement for `hecate-dep licenses`.
+//!
+//! Crates express their license as an SPDX expression (`MIT OR Apache-2.0`,
+//! `Apache-2.0 WITH LLVM-exception OR Apache-2.0 OR MIT`,
+//! `(MIT OR Apache-2.0) AND Unicode-DFS-2016`, ...). We parse that expression into a small
+//! OR/AND/WITH AST and evaluate it against a set of approved license identifiers rather than
+//! matching the raw string, so equivalent expressions with different parenthesization or operand
+//! order are all recognized.
+
+use anyhow::{anyhow, bail, Result};
+use cargo_metadata::{DependencyKind, MetadataCommand, PackageId, Resolve};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Workspace members that end up inside the binaries HecateOS actually ships to users (the
+/// daemon, its CLI, and the libraries they link against) as opposed to build-only tooling like
+/// `hecate-deps` itself, `hecate-hooks`, `hecate-dev`, and `hecate-changelog`.
+const RUNTIME_WORKSPACE_MEMBERS: &[&str] =
+    &["hecate-cli", "hecate-core", "hecate-gpu", "hecate-ml", "hecate-monitor"];
+
+/// License identifiers we consider acceptable on their own (the leaves of an SPDX expression).
+const APPROVED_LICENSE_IDS: &[&str] = &[
+    "MIT",
+    "Apache-2.0",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "ISC",
+    "Zlib",
+    "Unicode-DFS-2016",
+    "CC0-1.0",
+    "MPL-2.0",
+];
+
+/// `WITH` exception identifiers that don't change acceptability of the license they modify.
+const APPROVED_WITH_EXCEPTIONS: &[&str] = &["LLVM-exception"];
+
+/// Whole SPDX expressions tolerated verbatim, for crates whose expression doesn't parse cleanly
+/// with our AST (e.g. unusual spacing or licenses we haven't bothered to teach the parser about)
+/// but that a human has reviewed and approved wholesale.
+const ALLOWED_EXPRESSIONS: &[&str] = &[];
+
+/// Crates whose license is tolerated only as a known, accepted exception rather than because it
+/// satisfies the policy on its own merits. Each entry is `(crate_name, exact_license_string)` —
+/// the license string must match verbatim, so bumping a dependency to a release that changes its
+/// license field doesn't silently inherit the exception.
+pub const LICENSE_EXCEPTIONS: &[(&str, &str)] = &[];
+
+/// A parsed SPDX license expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SpdxExpr {
+    Id(String),
+    With(Box<SpdxExpr>, String),
+    And(Vec<SpdxExpr>),
+    Or(Vec<SpdxExpr>),
+}
+
+impl SpdxExpr {
+    /// Acceptable if every leaf identifier (and any `WITH` exception) is on our approved list.
+    /// An `Or` node passes if any branch does; an `And` node only if all branches do.
+    fn is_acceptable(&self) -> bool {
+        match self {
+            SpdxExpr::Id(id) => APPROVED_LICENSE_IDS.contains(&id.as_str()),
+            SpdxExpr::With(base, exception) => {
+                base.is_acceptable() && APPROVED_WITH_EXCEPTIONS.contains(&exception.as_str())
+            }
+            SpdxExpr::And(branches) => branches.iter().all(SpdxExpr::is_acceptable),
+            SpdxExpr::Or(branches) => branches.iter().any(SpdxExpr::is_acceptable),
+        }
+    }
+}
+
+/// A tiny recursive-descent parser for the subset of SPDX expression syntax we see in practice:
+/// identifiers, parentheses, and left-associative `OR`/`AND`/`WITH` (in increasing precedence).
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(expr: &str) -> Self {
+        let tokens = expr
+            .replace('(', " ( ")
+            .replace(')', " ) ")
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        Parser { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn parse(&mut self) -> Result<SpdxExpr> {
+        let expr = self.parse_or()?;
+        if let Some(tok) = self.peek() {
+            bail!("unexpected token `{tok}` after end of license expression");
+        }
+        Ok(expr)
+    }
+
+    fn parse_or(&mut self) -> Result<SpdxExpr> {
+        let mut branches = vec![self.parse_and()?];
+        while self.peek() == Some("OR") {
+            self.next();
+            branches.push(self.parse_and()?);
+        }
+        Ok(if branches.len() == 1 { branches.remove(0) } else { SpdxExpr::Or(branches) })
+    }
+
+    fn parse_and(&mut self) -> Result<SpdxExpr> {
+        let mut branches = vec![self.parse_with()?];
+        while self.peek() == Some("AND") {
+            self.next();
+            branches.push(self.parse_with()?);
+        }
+        Ok(if branches.len() == 1 { branches.remove(0) } else { SpdxExpr::And(branches) })
+    }
+
+    fn parse_with(&mut self) -> Result<SpdxExpr> {
+        let base = self.parse_atom()?;
+        if self.peek() == Some("WITH") {
+            self.next();
+            let exception = self.next().ok_or_else(|| anyhow!("expected exception identifier after WITH"))?;
+            Ok(SpdxExpr::With(Box::new(base), exception))
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<SpdxExpr> {
+        match self.next() {
+            Some(tok) if tok == "(" => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(tok) if tok == ")" => Ok(inner),
+                    _ => bail!("unbalanced parentheses in license expression"),
+                }
+            }
+            Some(tok) => Ok(SpdxExpr::Id(tok)),
+            None => bail!("empty license expression"),
+        }
+    }
+}
+
+fn parse_spdx(expr: &str) -> Result<SpdxExpr> {
+    Parser::new(expr).parse()
+}
+
+/// Why a package's license expression passed or failed policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LicenseStatus {
+    /// Satisfies the SPDX policy, or its whole expression is on the verbatim allowlist
+    Allowed,
+    /// Only tolerated via [`LICENSE_EXCEPTIONS`], not acceptable on its own merits
+    Exception,
+    /// Fails the policy outright
+    Disallowed,
+    /// `cargo metadata` reported no `license` field at all
+    Missing,
+}
+
+/// The outcome of evaluating one package's license field against policy.
+#[derive(Debug, Clone, Serialize)]
+pub struct LicenseRecord {
+    #[serde(rename = "crate")]
+    pub name: String,
+    pub version: String,
+    pub license: String,
+    pub status: LicenseStatus,
+}
+
+/// Evaluate a single package's license expression against the allowlist, exceptions map, and
+/// SPDX AST.
+fn classify_package_license(name: &str, license: &str) -> LicenseStatus {
+    if ALLOWED_EXPRESSIONS.contains(&license) {
+        return LicenseStatus::Allowed;
+    }
+    if LICENSE_EXCEPTIONS.iter().any(|(crate_name, exempted)| *crate_name == name && *exempted == license) {
+        return LicenseStatus::Exception;
+    }
+    if parse_spdx(license).map(|expr| expr.is_acceptable()).unwrap_or(false) {
+        LicenseStatus::Allowed
+    } else {
+        LicenseStatus::Disallowed
+    }
+}
+
+/// Evaluate every package in the workspace's `cargo metadata` against the license policy,
+/// returning one [`LicenseRecord`] per distinct `name@version`.
+pub fn evaluate_license_policy() -> Result<Vec<LicenseRecord>> {
+    let metadata = MetadataCommand::new().exec().map_err(|e| anyhow!("failed to run `cargo metadata`: {e}"))?;
+
+    let mut records = Vec::new();
+    let mut seen = HashSet::new();
+    for package in &metadata.packages {
+        if !seen.insert((package.name.clone(), package.version.to_string())) {
+            continue;
+        }
+
+        let Some(license) = package.license.as_deref() else {
+            records.push(LicenseRecord {
+                name: package.name.clone(),
+                version: package.version.to_string(),
+                license: "<missing>".to_string(),
+                status: LicenseStatus::Missing,
+            });
+            continue;
+        };
+
+        records.push(LicenseRecord {
+            name: package.name.clone(),
+            version: package.version.to_string(),
+            license: license.to_string(),
+            status: classify_package_license(&package.name, license),
+        });
+    }
+
+    Ok(records)
+}
+
+/// Run the license policy over every package in the workspace, printing each offending
+/// `crate@version => license` and returning an error if any fail.
+pub fn enforce_license_policy() -> Result<()> {
+    let offenders: Vec<LicenseRecord> = evaluate_license_policy()?
+        .into_iter()
+        .filter(|record| matches!(record.status, LicenseStatus::Disallowed | LicenseStatus::Missing))
+        .collect();
+
+    if offenders.is_empty() {
+        return Ok(());
+    }
+
+    for offender in &offenders {
+        println!("  {} {}@{} => {}", "✗".red(), offender.name, offender.version, offender.license);
+    }
+    bail!("{} crate(s) failed license policy", offenders.len())
+}
+
+/// The set of crate names reachable from `roots` by following `edges`, inclusive of the roots
+/// themselves. Kept separate from `cargo_metadata` types so the graph-walking logic can be
+/// exercised directly in tests against a synthetic dependency graph.
+fn transitive_closure(roots: &[&str], edges: &HashMap<String, Vec<String>>) -> HashSet<String> {
+    let mut closure = HashSet::new();
+    let mut stack: Vec<String> = roots.iter().map(|s| s.to_string()).collect();
+    while let Some(name) = stack.pop() {
+        if !closure.insert(name.clone()) {
+            continue;
+        }
+        if let Some(deps) = edges.get(&name) {
+            stack.extend(deps.iter().cloned());
+        }
+    }
+    closure
+}
+
+/// Build the runtime-reachable dependency graph from a resolved `cargo metadata` graph: edges
+/// only follow [`DependencyKind::Normal`] dependencies, so a crate that's only a `[build-dependencies]`
+/// or `[dev-dependencies]` edge of a workspace member doesn't get treated as something the
+/// runtime binaries actually link against. `Node::dependencies` is the flat, kind-agnostic list
+/// `cargo metadata` also reports; we deliberately walk `Node::deps` instead, since that's the one
+/// that carries `dep_kinds` per edge.
+fn runtime_edges(resolve: &Resolve, name_of: &HashMap<PackageId, String>) -> HashMap<String, Vec<String>> {
+    resolve
+        .nodes
+        .iter()
+        .filter_map(|node| {
+            let name = name_of.get(&node.id)?.clone();
+            let deps = node
+                .deps
+                .iter()
+                .filter(|dep| dep.dep_kinds.iter().any(|info| info.kind == DependencyKind::Normal))
+                .filter_map(|dep| name_of.get(&dep.pkg).cloned())
+                .collect();
+            Some((name, deps))
+        })
+        .collect()
+}
+
+/// Assert that no crate tolerated only as a build/tooling exception (see
+/// [`LICENSE_EXCEPTIONS`]) is reachable from [`RUNTIME_WORKSPACE_MEMBERS`] in the resolved
+/// dependency graph. A flat license list can't catch the case where a weak-copyleft dependency
+/// that's fine for `cargo xtask` tooling silently becomes part of what users actually run.
+pub fn enforce_runtime_closure_policy() -> Result<()> {
+    let metadata = MetadataCommand::new().exec().map_err(|e| anyhow!("failed to run `cargo metadata`: {e}"))?;
+    let resolve = metadata
+        .resolve
+        .as_ref()
+        .ok_or_else(|| anyhow!("`cargo metadata` did not return a resolve graph (pass --no-deps only when you don't need one)"))?;
+
+    let name_of: HashMap<_, _> = metadata.packages.iter().map(|p| (p.id.clone(), p.name.clone())).collect();
+    let edges = runtime_edges(resolve, &name_of);
+
+    let roots: Vec<&str> =
+        RUNTIME_WORKSPACE_MEMBERS.iter().copied().filter(|name| edges.contains_key(*name)).collect();
+    let closure = transitive_closure(&roots, &edges);
+
+    let mut offenders: Vec<&str> = LICENSE_EXCEPTIONS
+        .iter()
+        .map(|(crate_name, _)| *crate_name)
+        .filter(|crate_name| closure.contains(*crate_name))
+        .collect();
+    offenders.sort_unstable();
+    offenders.dedup();
+
+    if offenders.is_empty() {
+        return Ok(());
+    }
+
+    for crate_name in &offenders {
+        println!(
+            "  {} {} is only a tolerated build/tooling exception but is reachable from the shipped runtime",
+            "✗".red(),
+            crate_name
+        );
+    }
+    bail!("{} exception-only crate(s) leaked into the runtime dependency closure", offenders.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_identifier_is_acceptable() {
+        assert!(parse_spdx("MIT").unwrap().is_acceptable());
+        assert!(!parse_spdx("GPL-3.0").unwrap().is_acceptable());
+    }
+
+    #[test]
+    fn test_or_expression_needs_only_one_acceptable_branch() {
+        let expr = parse_spdx("GPL-3.0 OR MIT").unwrap();
+        assert!(expr.is_acceptable());
+    }
+
+    #[test]
+    fn test_and_expression_needs_all_branches_acceptable() {
+        let expr = parse_spdx("MIT AND GPL-3.0").unwrap();
+        assert!(!expr.is_acceptable());
+    }
+
+    #[test]
+    fn test_with_exception_clause() {
+        let expr = parse_spdx("Apache-2.0 WITH LLVM-exception").unwrap();
+        assert!(expr.is_acceptable());
+    }
+
+    #[test]
+    fn test_parenthesized_and_or_combination() {
+        let expr = parse_spdx("(MIT OR Apache-2.0) AND Unicode-DFS-2016").unwrap();
+        assert!(expr.is_acceptable());
+    }
+
+    #[test]
+    fn test_complex_or_chain_with_with_exception() {
+        let expr = parse_spdx("Apache-2.0 WITH LLVM-exception OR Apache-2.0 OR MIT").unwrap();
+        assert!(expr.is_acceptable());
+    }
+
+    #[test]
+    fn test_unbalanced_parens_is_a_parse_error() {
+        assert!(parse_spdx("(MIT OR Apache-2.0").is_err());
+    }
+
+    #[test]
+    fn test_classify_package_license_respects_exceptions_map() {
+        assert_eq!(classify_package_license("shady-crate", "GPL-2.0-only"), LicenseStatus::Disallowed);
+    }
+
+    #[test]
+    fn test_transitive_closure_follows_multiple_hops() {
+        let mut edges = HashMap::new();
+        edges.insert("hecate-cli".to_string(), vec!["hecate-core".to_string()]);
+        edges.insert("hecate-core".to_string(), vec!["leaf-crate".to_string()]);
+        edges.insert("leaf-crate".to_string(), vec![]);
+
+        let closure = transitive_closure(&["hecate-cli"], &edges);
+
+        assert!(closure.contains("hecate-cli"));
+        assert!(closure.contains("hecate-core"));
+        assert!(closure.contains("leaf-crate"));
+    }
+
+    #[test]
+    fn test_transitive_closure_does_not_reach_unrelated_branches() {
+        let mut edges = HashMap::new();
+        edges.insert("hecate-cli".to_string(), vec!["hecate-core".to_string()]);
+        edges.insert("hecate-deps".to_string(), vec!["tooling-only-crate".to_string()]);
+
+        let closure = transitive_closure(&["hecate-cli"], &edges);
+
+        assert!(!closure.contains("hecate-deps"));
+        assert!(!closure.contains("tooling-only-crate"));
+    }
+
+    /// Builds a [`Resolve`] with one node, `hecate-cli`, depending on `normal-dep` via a normal
+    /// dependency and on `build-only-dep`/`dev-only-dep` via build/dev dependencies only --
+    /// exactly the shape `cargo metadata` produces for a crate that's never linked into the
+    /// runtime binary but is still present in the full (non-`--no-dev-dependencies`) graph.
+    fn resolve_with_mixed_dep_kinds() -> Resolve {
+        let json = serde_json::json!({
+            "nodes": [
+                {
+                    "id": "hecate-cli 0.1.0 (path+file:///workspace/hecate-cli)",
+                    "dependencies": [
+                        "normal-dep 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                        "build-only-dep 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                        "dev-only-dep 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)"
+                    ],
+                    "deps": [
+                        {
+                            "name": "normal_dep",
+                            "pkg": "normal-dep 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                            "dep_kinds": [{"kind": "normal", "target": null}]
+                        },
+                        {
+                            "name": "build_only_dep",
+                            "pkg": "build-only-dep 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                            "dep_kinds": [{"kind": "build", "target": null}]
+                        },
+                        {
+                            "name": "dev_only_dep",
+                            "pkg": "dev-only-dep 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                            "dep_kinds": [{"kind": "dev", "target": null}]
+                        }
+                    ],
+                    "features": []
+                },
+                {
+                    "id": "normal-dep 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                    "dependencies": [],
+                    "deps": [],
+                    "features": []
+                },
+                {
+                    "id": "build-only-dep 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                    "dependencies": [],
+                    "deps": [],
+                    "features": []
+                },
+                {
+                    "id": "dev-only-dep 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                    "dependencies": [],
+                    "deps": [],
+                    "features": []
+                }
+            ],
+            "root": null
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_runtime_edges_excludes_build_and_dev_only_dependencies() {
+        let resolve = resolve_with_mixed_dep_kinds();
+        let name_of: HashMap<PackageId, String> = resolve
+            .nodes
+            .iter()
+            .map(|node| {
+                let name = node.id.repr.split_whitespace().next().unwrap().to_string();
+                (node.id.clone(), name)
+            })
+            .collect();
+
+        let edges = runtime_edges(&resolve, &name_of);
+
+        assert_eq!(edges.get("hecate-cli").unwrap(), &vec!["normal-dep".to_string()]);
+    }
+}
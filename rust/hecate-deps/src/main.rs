@@ -1,13 +1,29 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
-use std::collections::HashSet;
+use serde_json::json;
 use std::fs;
+use std::path::Path;
 use toml_edit::Document;
 
+mod audit;
+mod licenses;
+mod tree;
+
+/// How a subcommand should render its results: colored prose for a human at a terminal, or a
+/// single JSON document on stdout for CI/dashboards to consume programmatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Human,
+    Json,
+}
+
 #[derive(Parser)]
 #[command(author, version, about = "HecateOS dependency manager")]
 struct Cli {
+    /// Output format for commands that support structured results
+    #[arg(long, value_enum, global = true, default_value = "human")]
+    format: OutputFormat,
     #[command(subcommand)]
     command: Commands,
 }
@@ -17,122 +33,216 @@ enum Commands {
     /// Check for outdated dependencies
     Check,
     /// Show dependency tree
-    Tree,
+    Tree {
+        /// Limit recursion to this many levels below the workspace roots
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Show reverse dependencies of this crate instead (who depends on it)
+        #[arg(long)]
+        invert: Option<String>,
+    },
     /// Analyze licenses
-    Licenses,
+    Licenses {
+        /// Also assert that no exception-only license reaches the distributed runtime's
+        /// dependency closure, not just the full build graph
+        #[arg(long)]
+        runtime: bool,
+    },
     /// Check for security vulnerabilities
-    Audit,
+    Audit {
+        /// Dump the full advisory text for each matched crate
+        #[arg(long)]
+        print_advisory: bool,
+    },
     /// Show binary size impact
     Size,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    
+    let format = cli.format;
+
     match cli.command {
-        Commands::Check => check_outdated()?,
-        Commands::Tree => show_tree()?,
-        Commands::Licenses => check_licenses()?,
-        Commands::Audit => security_audit()?,
-        Commands::Size => analyze_size()?,
+        Commands::Check => check_outdated(format)?,
+        Commands::Tree { depth, invert } => show_tree(depth, invert)?,
+        Commands::Licenses { runtime } => check_licenses(runtime, format)?,
+        Commands::Audit { print_advisory } => security_audit(print_advisory, format)?,
+        Commands::Size => analyze_size(format)?,
     }
-    
+
     Ok(())
 }
 
-fn check_outdated() -> Result<()> {
-    println!("{} Checking for outdated dependencies...", "→".blue());
-    
+fn check_outdated(format: OutputFormat) -> Result<()> {
     let cargo_toml = fs::read_to_string("rust/Cargo.toml")?;
     let doc = cargo_toml.parse::<Document>()?;
-    
-    if let Some(deps) = doc.get("workspace").and_then(|w| w.get("dependencies")) {
-        if let Some(table) = deps.as_table() {
-            println!("\n{}", "Workspace Dependencies:".bold());
-            for (name, value) in table {
-                if let Some(version) = value.as_str().or_else(|| {
-                    value.get("version").and_then(|v| v.as_str())
-                }) {
-                    // In real implementation, check crates.io for latest version
-                    println!("  {} {}", name, version.dimmed());
-                }
-            }
-        }
+
+    let dependencies: Vec<(String, String)> = doc
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|d| d.as_table())
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(name, value)| {
+                    let version = value.as_str().or_else(|| value.get("version").and_then(|v| v.as_str()))?;
+                    Some((name.to_string(), version.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "dependencies": dependencies.iter().map(|(name, version)| json!({"name": name, "version": version})).collect::<Vec<_>>(),
+            }))?
+        );
+        return Ok(());
+    }
+
+    println!("{} Checking for outdated dependencies...", "→".blue());
+    println!("\n{}", "Workspace Dependencies:".bold());
+    for (name, version) in &dependencies {
+        // In real implementation, check crates.io for latest version
+        println!("  {} {}", name, version.dimmed());
     }
-    
     println!("\n{} Run 'cargo update' to update dependencies", "Tip".cyan().bold());
     Ok(())
 }
 
-fn show_tree() -> Result<()> {
+fn show_tree(depth: Option<usize>, invert: Option<String>) -> Result<()> {
     println!("{} Dependency tree:", "→".blue());
-    
-    // In real implementation, would parse Cargo.lock
-    println!("
-hecate-os
-├── hecate-core v0.1.0
-│   ├── tokio v1.35
-│   ├── serde v1.0
-│   └── anyhow v1.0
-├── hecate-daemon v0.1.0
-│   ├── hecate-core v0.1.0 (*)
-│   └── tracing v0.1
-├── hecate-gpu v0.1.0
-│   ├── nvml-wrapper v0.9
-│   └── sysinfo v0.30
-└── hecate-pkg v0.1.0
-    ├── reqwest v0.11
-    └── tar v0.4
-");
-    
+
+    let graph = tree::DependencyGraph::parse(Path::new("rust/Cargo.lock"))?;
+
+    let rendered = match invert {
+        Some(crate_name) => {
+            println!("  Reverse dependencies of {}:\n", crate_name.cyan());
+            graph.render_inverted(&crate_name, depth)?
+        }
+        None => graph.render(depth),
+    };
+
+    print!("{rendered}");
     Ok(())
 }
 
-fn check_licenses() -> Result<()> {
+fn check_licenses(runtime: bool, format: OutputFormat) -> Result<()> {
+    let records = licenses::evaluate_license_policy()?;
+    let failing = records
+        .iter()
+        .filter(|r| matches!(r.status, licenses::LicenseStatus::Disallowed | licenses::LicenseStatus::Missing))
+        .count();
+
+    if format == OutputFormat::Json {
+        // The --runtime closure check is prose-only (see enforce_runtime_closure_policy); it's
+        // skipped here rather than mixing unstructured lines into the JSON document.
+        println!("{}", serde_json::to_string_pretty(&json!({"crates": records, "failing": failing}))?);
+        if failing > 0 {
+            anyhow::bail!("{failing} crate(s) failed license policy");
+        }
+        return Ok(());
+    }
+
     println!("{} Analyzing licenses...", "→".blue());
-    
-    let mut licenses = HashSet::new();
-    licenses.insert("MIT");
-    licenses.insert("Apache-2.0");
-    licenses.insert("BSD-3-Clause");
-    
-    println!("\n{}", "License Summary:".bold());
-    println!("  {} MIT", "✓".green());
-    println!("  {} Apache-2.0", "✓".green());
-    println!("  {} BSD-3-Clause", "✓".green());
-    
-    println!("\n{} All licenses are compatible", "✓".green().bold());
+    licenses::enforce_license_policy()?;
+
+    if runtime {
+        println!("\n{} Checking the shipped runtime's dependency closure...", "→".blue());
+        licenses::enforce_runtime_closure_policy()?;
+    }
+
+    println!("\n{} All licenses satisfy policy", "✓".green().bold());
     Ok(())
 }
 
-fn security_audit() -> Result<()> {
+fn security_audit(print_advisory: bool, format: OutputFormat) -> Result<()> {
+    let db_path = audit::default_advisory_db_path()?;
+    let advisories = audit::load_advisory_db(&db_path)?;
+    let packages = audit::load_locked_packages(Path::new("rust/Cargo.lock"))?;
+    let findings = audit::find_vulnerabilities(&packages, &advisories);
+
+    if format == OutputFormat::Json {
+        let advisories_json: Vec<_> = findings
+            .iter()
+            .map(|finding| {
+                json!({
+                    "id": finding.advisory.id,
+                    "package": finding.package.name,
+                    "installed_version": finding.package.version.to_string(),
+                    "patched_versions": finding.advisory.patched.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    "severity": finding.advisory.severity,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "advisories": advisories_json,
+                "summary": {"scanned": packages.len(), "vulnerable": findings.len()},
+            }))?
+        );
+        if !findings.is_empty() {
+            anyhow::bail!("{} advisory match(es) with no patched version in use", findings.len());
+        }
+        return Ok(());
+    }
+
     println!("{} Running security audit...", "→".blue());
-    
-    // In real implementation, would check RustSec advisory database
-    println!("  Checking RustSec advisory database...");
-    println!("  Scanning {} dependencies", "42".yellow());
-    
-    println!("\n{} No known vulnerabilities found", "✓".green().bold());
-    println!("\n{} Install cargo-audit for real security scanning:", "Tip".cyan());
-    println!("  cargo install cargo-audit");
-    println!("  cargo audit");
-    
-    Ok(())
+    println!("  Checking RustSec advisory database at {}...", db_path.display());
+    println!("  Scanning {} dependencies", packages.len().to_string().yellow());
+
+    if findings.is_empty() {
+        println!("\n{} No known vulnerabilities found", "✓".green().bold());
+        return Ok(());
+    }
+
+    println!("\n{}", "Vulnerabilities found:".bold().red());
+    for finding in &findings {
+        println!(
+            "  {} {} — {}@{}: {} (patched in {})",
+            "✗".red(),
+            finding.advisory.id,
+            finding.package.name,
+            finding.package.version,
+            finding.advisory.title,
+            finding.first_patched_version(),
+        );
+        if print_advisory {
+            println!("{}", finding.advisory.raw_text.dimmed());
+        }
+    }
+
+    anyhow::bail!("{} advisory match(es) with no patched version in use", findings.len())
 }
 
-fn analyze_size() -> Result<()> {
+fn analyze_size(format: OutputFormat) -> Result<()> {
+    // In real implementation, this would come from measuring the built binaries
+    let components = [("hecate-daemon", 8_200_000u64), ("tokio", 2_100_000), ("serde", 450_000), ("other", 5_600_000)];
+
+    if format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "components": components.iter().map(|(name, bytes)| json!({"name": name, "size_bytes": bytes})).collect::<Vec<_>>(),
+            }))?
+        );
+        return Ok(());
+    }
+
     println!("{} Analyzing binary size impact...", "→".blue());
-    
     println!("\n{}", "Size Analysis:".bold());
     println!("  hecate-daemon: ~8.2 MB");
     println!("    tokio:       ~2.1 MB");
     println!("    serde:       ~450 KB");
     println!("    other:       ~5.6 MB");
-    
+
     println!("\n{}", "Optimization Tips:".bold());
     println!("  • Use 'strip = true' in release profile");
     println!("  • Enable LTO with 'lto = true'");
     println!("  • Set 'codegen-units = 1' for smallest size");
-    
+
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,247 @@
+//! Real dependency-tree rendering for `show_tree`, built from `Cargo.lock` instead of a
+//! hard-coded example graph.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// One resolved `[[package]]` entry from `Cargo.lock`
+#[derive(Debug, Clone)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    /// Raw dependency strings as written in the lockfile: `"name"`, `"name version"`, or
+    /// `"name version (source)"`
+    dependencies: Vec<String>,
+}
+
+/// The parsed lockfile, indexed for tree walking
+pub struct DependencyGraph {
+    packages: Vec<LockedPackage>,
+}
+
+impl DependencyGraph {
+    pub fn parse(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+        let doc: toml::Value =
+            content.parse().with_context(|| format!("failed to parse {}", path.display()))?;
+
+        let packages = doc
+            .get("package")
+            .and_then(|p| p.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let name = entry.get("name")?.as_str()?.to_string();
+                        let version = entry.get("version")?.as_str()?.to_string();
+                        let dependencies = entry
+                            .get("dependencies")
+                            .and_then(|d| d.as_array())
+                            .map(|deps| deps.iter().filter_map(|d| d.as_str().map(str::to_string)).collect())
+                            .unwrap_or_default();
+                        Some(LockedPackage { name, version, dependencies })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self { packages })
+    }
+
+    /// Resolve a raw lockfile dependency string (`"name"`, `"name version"`, or
+    /// `"name version (source)"`) to the specific package it refers to. The version is only
+    /// present in the lockfile when the name alone is ambiguous.
+    fn resolve(&self, raw: &str) -> Option<&LockedPackage> {
+        let mut parts = raw.split_whitespace();
+        let name = parts.next()?;
+        let version = parts.next();
+
+        let mut candidates = self.packages.iter().filter(|p| p.name == name);
+        match version {
+            Some(version) => candidates.find(|p| p.version == version),
+            None => candidates.next(),
+        }
+    }
+
+    /// Packages that declare `name`/`version` as a direct dependency
+    fn dependents_of(&self, name: &str, version: &str) -> Vec<&LockedPackage> {
+        self.packages
+            .iter()
+            .filter(|p| {
+                p.dependencies.iter().any(|dep| {
+                    self.resolve(dep).is_some_and(|r| r.name == name && r.version == version)
+                })
+            })
+            .collect()
+    }
+
+    /// Workspace crates: the roots the top-level tree is drawn from
+    fn roots(&self) -> Vec<&LockedPackage> {
+        let mut roots: Vec<&LockedPackage> =
+            self.packages.iter().filter(|p| p.name.starts_with("hecate-")).collect();
+        roots.sort_by(|a, b| a.name.cmp(&b.name));
+        roots
+    }
+
+    /// Render the full forward dependency tree from the workspace roots, classic cargo-tree
+    /// box-drawing style, deduplicating repeated subtrees with ` (*)`.
+    pub fn render(&self, max_depth: Option<usize>) -> String {
+        let roots = self.roots();
+        render_forest(&roots, max_depth, &|p| {
+            p.dependencies.iter().filter_map(|dep| self.resolve(dep)).collect()
+        })
+    }
+
+    /// Render the reverse dependency tree rooted at `name`: who depends on it, and who depends
+    /// on those, and so on — the common workflow when an audit flags a transitive dependency.
+    pub fn render_inverted(&self, name: &str, max_depth: Option<usize>) -> Result<String> {
+        let matches: Vec<&LockedPackage> = self.packages.iter().filter(|p| p.name == name).collect();
+        if matches.is_empty() {
+            bail!("no package named `{name}` in Cargo.lock");
+        }
+        Ok(render_forest(&matches, max_depth, &|p| self.dependents_of(&p.name, &p.version)))
+    }
+}
+
+/// Render one tree per entry in `roots`, walking each node's children via `children_of` and
+/// collapsing a package's subtree to ` (*)` the second and later times it's reached, so cycles
+/// and diamond dependencies don't blow up the output.
+fn render_forest<'a>(
+    roots: &[&'a LockedPackage],
+    max_depth: Option<usize>,
+    children_of: &dyn Fn(&'a LockedPackage) -> Vec<&'a LockedPackage>,
+) -> String {
+    let mut out = String::new();
+    let mut seen = HashSet::new();
+    for (i, root) in roots.iter().enumerate() {
+        render_node(root, 0, max_depth, &mut seen, "", i + 1 == roots.len(), true, &mut out, children_of);
+    }
+    out
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_node<'a>(
+    package: &'a LockedPackage,
+    depth: usize,
+    max_depth: Option<usize>,
+    seen: &mut HashSet<(String, String)>,
+    prefix: &str,
+    is_last: bool,
+    is_root: bool,
+    out: &mut String,
+    children_of: &dyn Fn(&'a LockedPackage) -> Vec<&'a LockedPackage>,
+) {
+    let connector = if is_root {
+        ""
+    } else if is_last {
+        "└── "
+    } else {
+        "├── "
+    };
+    let key = (package.name.clone(), package.version.clone());
+    let already_seen = !seen.insert(key);
+    out.push_str(&format!(
+        "{prefix}{connector}{} v{}{}\n",
+        package.name,
+        package.version,
+        if already_seen { " (*)" } else { "" }
+    ));
+
+    if already_seen || max_depth.is_some_and(|d| depth >= d) {
+        return;
+    }
+
+    let child_prefix = if is_root {
+        String::new()
+    } else {
+        format!("{prefix}{}", if is_last { "    " } else { "│   " })
+    };
+    let children = children_of(package);
+    for (i, child) in children.iter().enumerate() {
+        render_node(child, depth + 1, max_depth, seen, &child_prefix, i + 1 == children.len(), false, out, children_of);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pkg(name: &str, version: &str, dependencies: &[&str]) -> LockedPackage {
+        LockedPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_render_expands_each_subtree_once_and_marks_repeats() {
+        let graph = DependencyGraph {
+            packages: vec![
+                pkg("hecate-cli", "0.1.0", &["hecate-core", "tokio"]),
+                pkg("hecate-core", "0.1.0", &["tokio"]),
+                pkg("tokio", "1.35.0", &[]),
+            ],
+        };
+
+        let tree = graph.render(None);
+
+        assert_eq!(tree.matches("tokio v1.35.0").count(), 2);
+        assert_eq!(tree.matches("tokio v1.35.0 (*)").count(), 1);
+        assert!(tree.contains("hecate-cli v0.1.0\n"));
+    }
+
+    #[test]
+    fn test_render_respects_max_depth() {
+        let graph = DependencyGraph {
+            packages: vec![
+                pkg("hecate-cli", "0.1.0", &["a"]),
+                pkg("a", "1.0.0", &["b"]),
+                pkg("b", "1.0.0", &[]),
+            ],
+        };
+
+        let tree = graph.render(Some(1));
+
+        assert!(tree.contains("a v1.0.0"));
+        assert!(!tree.contains("b v1.0.0"));
+    }
+
+    #[test]
+    fn test_render_inverted_finds_reverse_dependents() {
+        let graph = DependencyGraph {
+            packages: vec![
+                pkg("hecate-cli", "0.1.0", &["tokio"]),
+                pkg("hecate-core", "0.1.0", &["tokio"]),
+                pkg("tokio", "1.35.0", &[]),
+            ],
+        };
+
+        let tree = graph.render_inverted("tokio", None).unwrap();
+
+        assert!(tree.contains("hecate-cli"));
+        assert!(tree.contains("hecate-core"));
+    }
+
+    #[test]
+    fn test_render_inverted_errors_on_unknown_crate() {
+        let graph = DependencyGraph { packages: vec![pkg("tokio", "1.35.0", &[])] };
+        assert!(graph.render_inverted("does-not-exist", None).is_err());
+    }
+
+    #[test]
+    fn test_resolve_disambiguates_by_version_when_present() {
+        let graph = DependencyGraph {
+            packages: vec![
+                pkg("hecate-cli", "0.1.0", &["syn 1.0.0"]),
+                pkg("syn", "1.0.0", &[]),
+                pkg("syn", "2.0.0", &[]),
+            ],
+        };
+
+        let resolved = graph.resolve("syn 1.0.0").unwrap();
+        assert_eq!(resolved.version, "1.0.0");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::license::{self, CondensedDirs};
 use anyhow::{Context, Result};
 use colored::*;
 use std::fs;
@@ -5,21 +6,11 @@ use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 pub async fn run_checks(only: Option<&[String]>, fix: bool) -> Result<()> {
-    let all_checks = vec![
-        "structure",
-        "imports",
-        "licenses",
-        "todos",
-        "dependencies",
-        "ports",
-    ];
-    
-    let checks_to_run = if let Some(only_list) = only {
-        only_list.to_vec()
-    } else {
-        all_checks.into_iter().map(String::from).collect()
+    let checks_to_run = match only {
+        Some(only_list) => only_list.to_vec(),
+        None => crate::config::HecateDevConfig::load()?.check.default_only,
     };
-    
+
     println!("{}", "Running project checks...".bold());
     let mut all_passed = true;
     
@@ -29,7 +20,7 @@ pub async fn run_checks(only: Option<&[String]>, fix: bool) -> Result<()> {
             "imports" => check_import_organization(fix),
             "licenses" => check_license_headers(fix),
             "todos" => check_todos_and_fixmes(),
-            "dependencies" => check_dependencies(),
+            "dependencies" => check_dependencies(fix),
             "ports" => check_port_configuration(),
             _ => {
                 println!("{}: Unknown check '{}'", "Warning".yellow(), check);
@@ -102,8 +93,9 @@ fn check_directory_structure() -> Result<()> {
 }
 
 fn check_import_organization(fix: bool) -> Result<()> {
+    let config = load_import_config()?;
     let mut issues = Vec::new();
-    
+
     for entry in WalkDir::new("rust")
         .into_iter()
         .filter_map(|e| e.ok())
@@ -111,15 +103,15 @@ fn check_import_organization(fix: bool) -> Result<()> {
     {
         let path = entry.path();
         let content = fs::read_to_string(path)?;
-        
-        if let Some(reorganized) = check_and_fix_imports(&content) {
+
+        if let Some(reorganized) = imports::check_and_fix_imports(&content, &config) {
             issues.push(path.to_path_buf());
             if fix {
                 fs::write(path, reorganized)?;
             }
         }
     }
-    
+
     if !issues.is_empty() {
         if fix {
             println!("    Fixed import organization in {} files", issues.len());
@@ -128,119 +120,107 @@ fn check_import_organization(fix: bool) -> Result<()> {
             anyhow::bail!(msg);
         }
     }
-    
+
     Ok(())
 }
 
-fn check_and_fix_imports(content: &str) -> Option<String> {
-    let lines: Vec<&str> = content.lines().collect();
-    let mut import_block = Vec::new();
-    let mut other_lines = Vec::new();
-    let mut in_imports = false;
-    let mut needs_reorg = false;
-    
-    for line in lines {
-        if line.starts_with("use ") {
-            if !in_imports {
-                in_imports = true;
-            }
-            import_block.push(line);
-        } else if in_imports && line.trim().is_empty() {
-            // Continue collecting imports after blank lines
-            continue;
-        } else {
-            if in_imports {
-                in_imports = false;
-                // Check if imports need reorganization
-                let sorted = organize_imports(&import_block);
-                if sorted != import_block {
-                    needs_reorg = true;
-                    import_block = sorted;
-                }
-            }
-            other_lines.push(line);
-        }
-    }
-    
-    if needs_reorg {
-        let mut result = Vec::new();
-        
-        // Group imports by category
-        let std_imports: Vec<&str> = import_block.iter()
-            .filter(|l| l.starts_with("use std::"))
-            .copied()
-            .collect();
-        let external_imports: Vec<&str> = import_block.iter()
-            .filter(|l| !l.starts_with("use std::") && !l.starts_with("use crate::") && !l.starts_with("use super::"))
-            .copied()
-            .collect();
-        let local_imports: Vec<&str> = import_block.iter()
-            .filter(|l| l.starts_with("use crate::") || l.starts_with("use super::"))
-            .copied()
-            .collect();
-        
-        if !std_imports.is_empty() {
-            result.extend(std_imports);
-            result.push("");
-        }
-        if !external_imports.is_empty() {
-            result.extend(external_imports);
-            result.push("");
-        }
-        if !local_imports.is_empty() {
-            result.extend(local_imports);
-            result.push("");
-        }
-        
-        result.extend(other_lines);
-        Some(result.join("\n"))
-    } else {
-        None
+/// Load the import grouping/merging style from `config/hecate/imports.toml`, falling back to
+/// the default (`StdExternalCrate`, no merging) when the file doesn't exist.
+fn load_import_config() -> Result<imports::ImportConfig> {
+    let path = "config/hecate/imports.toml";
+    if !Path::new(path).exists() {
+        return Ok(imports::ImportConfig::default());
     }
-}
 
-fn organize_imports(imports: &[&str]) -> Vec<&str> {
-    let mut sorted = imports.to_vec();
-    sorted.sort();
-    sorted
+    let content = fs::read_to_string(path)?;
+    toml::from_str(&content).with_context(|| format!("failed to parse {path}"))
 }
 
-fn check_license_headers(fix: bool) -> Result<()> {
-    const LICENSE_HEADER: &str = "// Copyright (c) 2026 HecateOS Team
+const DEFAULT_LICENSE_HEADER: &str = "// Copyright (c) 2026 HecateOS Team
 // SPDX-License-Identifier: MIT
 ";
-    
-    let mut missing = Vec::new();
-    
-    for entry in WalkDir::new("rust")
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension() == Some("rs".as_ref()))
-    {
-        let path = entry.path();
-        let content = fs::read_to_string(path)?;
-        
-        if !content.starts_with(LICENSE_HEADER) && !content.starts_with("//") {
-            missing.push(path.to_path_buf());
-            if fix {
-                let new_content = format!("{}\n{}", LICENSE_HEADER, content);
+
+fn check_license_headers(fix: bool) -> Result<()> {
+    let condensed = load_condensed_dirs()?;
+
+    if fix {
+        // `--fix` still only has one reasonable action: stamp a default header onto files that
+        // don't have a recognizable SPDX-License-Identifier line at all. Files whose expression
+        // merely fails to parse need a human to pick the right license, not a tool guess.
+        let mut added = 0;
+        for entry in WalkDir::new("rust")
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension() == Some("rs".as_ref()))
+        {
+            let path = entry.path();
+            let content = fs::read_to_string(path)?;
+            if !content.contains("SPDX-License-Identifier:") {
+                let new_content = format!("{}\n{}", DEFAULT_LICENSE_HEADER, content);
                 fs::write(path, new_content)?;
+                added += 1;
             }
         }
-    }
-    
-    if !missing.is_empty() {
-        if fix {
-            println!("    Added license headers to {} files", missing.len());
-        } else {
-            let msg = format!("Missing license headers in {} files. Use --fix to add", missing.len());
-            anyhow::bail!(msg);
+        if added > 0 {
+            println!("    Added license headers to {} files", added);
         }
     }
-    
+
+    let report = license::build_report(Path::new("rust"), &condensed)
+        .context("failed to build license report")?;
+
+    let report_json = serde_json::to_string_pretty(&report)?;
+    fs::write("license-tree.json", &report_json).context("failed to write license-tree.json")?;
+    println!(
+        "    Wrote license-tree.json ({} distinct license{})",
+        report.licenses.len(),
+        if report.licenses.len() == 1 { "" } else { "s" }
+    );
+
+    if !report.issues.is_empty() {
+        let msg = format!(
+            "Unparseable or missing SPDX-License-Identifier in {} file(s):\n  {}",
+            report.issues.len(),
+            report
+                .issues
+                .iter()
+                .map(|i| format!("{}: {}", i.path.display(), i.reason))
+                .collect::<Vec<_>>()
+                .join("\n  ")
+        );
+        anyhow::bail!(msg);
+    }
+
     Ok(())
 }
 
+/// Load the configurable list of "condensed directories" whose contents inherit one nominated
+/// file's license, from `config/hecate/licenses.toml`. Missing file means no condensed dirs.
+fn load_condensed_dirs() -> Result<CondensedDirs> {
+    let path = "config/hecate/licenses.toml";
+    if !Path::new(path).exists() {
+        return Ok(CondensedDirs::default());
+    }
+
+    #[derive(serde::Deserialize)]
+    struct LicensesConfig {
+        #[serde(default)]
+        condensed: std::collections::HashMap<String, String>,
+    }
+
+    let content = fs::read_to_string(path)?;
+    let config: LicensesConfig = toml::from_str(&content)
+        .with_context(|| format!("failed to parse {path}"))?;
+
+    let nominated_file = config
+        .condensed
+        .into_iter()
+        .map(|(dir, file)| (PathBuf::from(dir), PathBuf::from(file)))
+        .collect();
+
+    Ok(CondensedDirs { nominated_file })
+}
+
 fn check_todos_and_fixmes() -> Result<()> {
     let mut todos = Vec::new();
     let mut fixmes = Vec::new();
@@ -304,21 +284,142 @@ fn check_todos_and_fixmes() -> Result<()> {
     Ok(())
 }
 
-fn check_dependencies() -> Result<()> {
-    // Check for duplicate dependencies
+fn check_dependencies(fix: bool) -> Result<()> {
     let cargo_toml = fs::read_to_string("rust/Cargo.toml")?;
     let doc = cargo_toml.parse::<toml_edit::Document>()?;
-    
+
     if let Some(deps) = doc.get("workspace").and_then(|w| w.get("dependencies")) {
         if let Some(table) = deps.as_table() {
-            let dep_count = table.len();
-            println!("    Found {} workspace dependencies", dep_count);
-            
-            // Check for security advisories (would need cargo-audit in real implementation)
-            println!("    Security audit: Run 'cargo audit' for vulnerability check");
+            println!("    Found {} workspace dependencies", table.len());
         }
     }
-    
+
+    let graph = deps::DependencyGraph::parse(Path::new("rust/Cargo.lock"))
+        .context("failed to parse rust/Cargo.lock")?;
+    let advisory_db = deps::AdvisoryDb::load().context("failed to load advisory database")?;
+    let policy = deps::LicensePolicy::load(Path::new("config/hecate/deny.toml"))
+        .context("failed to load config/hecate/deny.toml")?;
+
+    let mut advisory_hits = Vec::new();
+    let mut license_hits = Vec::new();
+
+    for package in &graph.packages {
+        for advisory in advisory_db.matches(package) {
+            advisory_hits.push((package, advisory));
+        }
+        if let Err(reason) = policy.check(package) {
+            license_hits.push((package, reason));
+        }
+    }
+
+    if !advisory_hits.is_empty() {
+        println!("    Found {} known-vulnerable dependenc{}:", advisory_hits.len(), if advisory_hits.len() == 1 { "y" } else { "ies" });
+        for (package, advisory) in &advisory_hits {
+            println!(
+                "      {} {} [{}]: {} (via {})",
+                package.name, package.version, advisory.id, advisory.title,
+                graph.dependency_path(&package.name)
+            );
+        }
+    }
+
+    if !license_hits.is_empty() {
+        println!("    Found {} license policy violation{}:", license_hits.len(), if license_hits.len() == 1 { "" } else { "s" });
+        for (package, reason) in &license_hits {
+            if fix {
+                println!(
+                    "      {} {}: {} -- add to config/hecate/deny.toml [exceptions] to accept",
+                    package.name, package.version, reason
+                );
+            } else {
+                println!("      {} {}: {} (via {})", package.name, package.version, reason, graph.dependency_path(&package.name));
+            }
+        }
+    }
+
+    if fix && !license_hits.is_empty() {
+        write_exception_stubs(&license_hits.iter().map(|(p, _)| p.name.clone()).collect::<Vec<_>>())?;
+        println!("    Wrote exception stubs for {} crate(s) to config/hecate/deny.toml", license_hits.len());
+    }
+
+    let duplicate_allow_list = load_duplicate_allow_list()?;
+    let duplicates = graph.duplicate_versions(&duplicate_allow_list);
+    if !duplicates.is_empty() {
+        println!(
+            "    Found {} crate{} resolved at multiple incompatible versions:",
+            duplicates.len(),
+            if duplicates.len() == 1 { "" } else { "s" }
+        );
+        for group in &duplicates {
+            println!("      {}:", group.name);
+            for (version, dependents) in &group.versions {
+                println!("        {} (required by {})", version, dependents.join(", "));
+            }
+        }
+    }
+
+    if !advisory_hits.is_empty() || (!license_hits.is_empty() && !fix) || !duplicates.is_empty() {
+        anyhow::bail!(
+            "{} advisory match(es), {} license policy violation(s), {} duplicate crate(s)",
+            advisory_hits.len(),
+            license_hits.len(),
+            duplicates.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Load the set of crate names allowed to appear at multiple incompatible versions, from
+/// `config/hecate/duplicate-allow.toml`'s `allow` list. Missing file means no allowances.
+fn load_duplicate_allow_list() -> Result<std::collections::HashSet<String>> {
+    let path = "config/hecate/duplicate-allow.toml";
+    if !Path::new(path).exists() {
+        return Ok(std::collections::HashSet::new());
+    }
+
+    #[derive(serde::Deserialize, Default)]
+    struct DuplicateAllowConfig {
+        #[serde(default)]
+        allow: Vec<String>,
+    }
+
+    let content = fs::read_to_string(path)?;
+    let config: DuplicateAllowConfig =
+        toml::from_str(&content).with_context(|| format!("failed to parse {path}"))?;
+    Ok(config.allow.into_iter().collect())
+}
+
+/// Append newly-discovered crate names to `config/hecate/deny.toml`'s `[exceptions]` list as a
+/// starting point for a human reviewer to confirm or remove.
+fn write_exception_stubs(names: &[String]) -> Result<()> {
+    let path = Path::new("config/hecate/deny.toml");
+    let mut policy = deps::LicensePolicy::load(path)?;
+
+    for name in names {
+        if !policy.exceptions.contains(name) {
+            policy.exceptions.push(name.clone());
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let to_array = |items: &[String]| {
+        let mut arr = toml_edit::Array::new();
+        for item in items {
+            arr.push(item.as_str());
+        }
+        arr
+    };
+
+    let mut doc = toml_edit::Document::new();
+    doc["allow"] = toml_edit::value(to_array(&policy.allow));
+    doc["deny"] = toml_edit::value(to_array(&policy.deny));
+    doc["exceptions"] = toml_edit::value(to_array(&policy.exceptions));
+    fs::write(path, doc.to_string())?;
+
     Ok(())
 }
 
@@ -1,21 +1,90 @@
 use anyhow::{Context, Result};
 use colored::*;
+use dialoguer::{theme::ColorfulTheme, Confirm, Editor, Input, Select};
 use regex::Regex;
+use serde::Deserialize;
 use std::process::Command;
+use thiserror::Error;
 
-const VALID_TYPES: &[&str] = &[
-    "feat",     // New feature
-    "fix",      // Bug fix
-    "docs",     // Documentation only changes
-    "style",    // Changes that do not affect the meaning of the code
-    "refactor", // Code change that neither fixes a bug nor adds a feature
-    "perf",     // Code change that improves performance
-    "test",     // Adding missing tests or correcting existing tests
-    "chore",    // Changes to the build process or auxiliary tools
-    "build",    // Changes that affect the build system or external dependencies
-    "ci",       // Changes to CI configuration files and scripts
-    "revert",   // Reverts a previous commit
-];
+/// The version bump a commit type drives, mirroring [`crate::version::BumpLevel`] minus
+/// `Prerelease` (no commit type can ever drive that) plus `None` for types that shouldn't move
+/// the version at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionImpact {
+    Major,
+    Minor,
+    Patch,
+    None,
+}
+
+/// One allowed commit type: its changelog section title and the version bump it drives.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitTypeConfig {
+    pub name: String,
+    pub title: String,
+    pub version_impact: VersionImpact,
+}
+
+/// Per-project commit types, scopes, and version-impact mapping, loaded from the `[commit]` table
+/// of [`crate::config::HecateDevConfig`]. Missing file means the built-in defaults (mirroring the
+/// historical `VALID_TYPES` list) apply.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitConfig {
+    pub types: Vec<CommitTypeConfig>,
+    #[serde(default)]
+    pub allowed_scopes: Vec<String>,
+}
+
+impl CommitConfig {
+    /// Load the project's commit conventions, falling back to [`CommitConfig::defaults`] when
+    /// there's no `hecate-dev.toml` (or it has no `[commit]` table).
+    pub fn load() -> Result<Self> {
+        Ok(crate::config::HecateDevConfig::load()?.commit)
+    }
+
+    /// The built-in types and version impacts, in the same order historically used for changelog
+    /// sections. No scope restriction (any scope is allowed).
+    pub fn defaults() -> Self {
+        let types = SECTION_TITLES
+            .iter()
+            .map(|&(name, title)| CommitTypeConfig {
+                name: name.to_string(),
+                title: title.to_string(),
+                version_impact: default_version_impact(name),
+            })
+            .collect();
+        CommitConfig { types, allowed_scopes: Vec::new() }
+    }
+
+    pub fn type_names(&self) -> Vec<&str> {
+        self.types.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    pub fn is_valid_type(&self, commit_type: &str) -> bool {
+        self.types.iter().any(|t| t.name == commit_type)
+    }
+
+    pub fn is_valid_scope(&self, scope: &str) -> bool {
+        self.allowed_scopes.is_empty() || self.allowed_scopes.iter().any(|s| s == scope)
+    }
+
+    pub fn version_impact(&self, commit_type: &str) -> VersionImpact {
+        self.types
+            .iter()
+            .find(|t| t.name == commit_type)
+            .map(|t| t.version_impact)
+            .unwrap_or(VersionImpact::None)
+    }
+}
+
+fn default_version_impact(commit_type: &str) -> VersionImpact {
+    match commit_type {
+        "feat" => VersionImpact::Minor,
+        "fix" | "perf" | "refactor" | "revert" => VersionImpact::Patch,
+        _ => VersionImpact::None,
+    }
+}
 
 pub fn validate_commit(message: Option<&str>) -> Result<()> {
     let message = match message {
@@ -29,53 +98,184 @@ pub fn validate_commit(message: Option<&str>) -> Result<()> {
             }
         }
     };
-    
-    let re = Regex::new(
-        r"^(feat|fix|docs|style|refactor|perf|test|chore|build|ci|revert)(\([a-z0-9-]+\))?: .{1,100}"
-    )?;
-    
-    let first_line = message.lines().next().unwrap_or("");
-    
-    if !re.is_match(first_line) {
-        println!("{}: Invalid commit message format", "Error".red().bold());
-        println!("\n{}: {}", "Message".bold(), first_line);
-        println!("\n{}", "Expected format:".bold());
-        println!("  <type>(<scope>): <subject>");
-        println!("\n{}", "Example:".bold());
-        println!("  feat(rust): add semantic version enforcement");
-        println!("\n{}", "Valid types:".bold());
-        for commit_type in VALID_TYPES {
-            println!("  - {}", commit_type);
+
+    let config = CommitConfig::load()?;
+    let parsed = match parse_commit(&message, &config) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            println!("{}: Invalid commit message format", "Error".red().bold());
+            println!("\n{}: {}", "Reason".bold(), err);
+            println!("\n{}", "Expected format:".bold());
+            println!("  <type>(<scope>): <subject>");
+            println!("\n{}", "Example:".bold());
+            println!("  feat(rust): add semantic version enforcement");
+            println!("\n{}", "Valid types:".bold());
+            for commit_type in config.type_names() {
+                println!("  - {}", commit_type);
+            }
+            anyhow::bail!("Commit message validation failed");
         }
-        anyhow::bail!("Commit message validation failed");
-    }
-    
+    };
+
     // Check for breaking changes
-    if message.contains("BREAKING CHANGE:") {
+    if parsed.breaking {
         println!("{}: Breaking change detected", "Warning".yellow().bold());
         println!("Make sure to bump major version before release");
     }
-    
+
     println!("{}: Commit message is valid", "Success".green().bold());
     Ok(())
 }
 
+/// A conventional commit parsed into its full structure -- header, optional body, and optional
+/// footers -- rather than just the first line. Used by [`validate_commit`] to report precise
+/// errors, and consumed directly by the changelog/bump features instead of re-running regexes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedCommit {
+    pub type_: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+    pub body: Option<String>,
+    pub footers: Vec<(String, String)>,
+}
+
+/// Why a commit message failed to parse as a [`ParsedCommit`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("commit message is empty")]
+    Empty,
+    #[error("invalid header line: {0:?} (expected `type(scope)!: description`)")]
+    InvalidHeader(String),
+    #[error("unknown commit type {0:?}")]
+    UnknownType(String),
+    #[error("description must not be empty")]
+    EmptyDescription,
+    #[error("header must be followed by a blank line before the body or footers")]
+    MissingBlankLineBeforeFooters,
+    #[error("footer {0:?} is missing a value")]
+    EmptyFooterValue(String),
+}
+
+/// Parse `msg` per the conventional-commit grammar: a `type(scope)!: description` header, then
+/// (after a blank line) an optional free-form body, then an optional footer block where each line
+/// is `token: value` or `token #value`. `BREAKING CHANGE`/`BREAKING-CHANGE` footers are recognized
+/// specially and set [`ParsedCommit::breaking`], in addition to the `!` marker on the header.
+pub fn parse_commit(
+    msg: &str,
+    config: &CommitConfig,
+) -> std::result::Result<ParsedCommit, ParseError> {
+    let mut lines = msg.lines();
+    let header = lines.next().ok_or(ParseError::Empty)?;
+
+    let header_re = Regex::new(r"^([a-z]+)(\(([a-z0-9-]+)\))?(!)?: (.*)$").unwrap();
+    let caps = header_re
+        .captures(header)
+        .ok_or_else(|| ParseError::InvalidHeader(header.to_string()))?;
+
+    let commit_type = caps[1].to_string();
+    if !config.is_valid_type(&commit_type) {
+        return Err(ParseError::UnknownType(commit_type));
+    }
+    let scope = caps.get(3).map(|m| m.as_str().to_string());
+    let mut breaking = caps.get(4).is_some();
+    let description = caps[5].trim().to_string();
+    if description.is_empty() {
+        return Err(ParseError::EmptyDescription);
+    }
+
+    let rest = msg.splitn(2, '\n').nth(1).unwrap_or("");
+    if rest.is_empty() {
+        return Ok(ParsedCommit {
+            type_: commit_type,
+            scope,
+            breaking,
+            description,
+            body: None,
+            footers: Vec::new(),
+        });
+    }
+    if !rest.starts_with('\n') {
+        return Err(ParseError::MissingBlankLineBeforeFooters);
+    }
+
+    let footer_re = Regex::new(r"^(BREAKING CHANGE|BREAKING-CHANGE|[A-Za-z][A-Za-z0-9-]*)(: | #)(.*)$").unwrap();
+    let mut blocks: Vec<&str> = rest
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .collect();
+
+    let mut footers = Vec::new();
+    if let Some(last) = blocks.last() {
+        let footer_lines: Vec<&str> = last.lines().collect();
+        if !footer_lines.is_empty() && footer_lines.iter().all(|line| footer_re.is_match(line)) {
+            for line in &footer_lines {
+                let caps = footer_re.captures(line).expect("line matched footer_re above");
+                let token = if &caps[1] == "BREAKING-CHANGE" {
+                    "BREAKING CHANGE".to_string()
+                } else {
+                    caps[1].to_string()
+                };
+                let value = caps[3].trim().to_string();
+                if value.is_empty() {
+                    return Err(ParseError::EmptyFooterValue(token));
+                }
+                if token == "BREAKING CHANGE" {
+                    breaking = true;
+                }
+                footers.push((token, value));
+            }
+            blocks.pop();
+        }
+    }
+
+    let body = if blocks.is_empty() {
+        None
+    } else {
+        Some(blocks.join("\n\n"))
+    };
+
+    Ok(ParsedCommit {
+        type_: commit_type,
+        scope,
+        breaking,
+        description,
+        body,
+        footers,
+    })
+}
+
 pub fn create_commit(
-    commit_type: &str, 
-    scope: Option<&str>, 
-    message: &str, 
+    commit_type: &str,
+    scope: Option<&str>,
+    message: &str,
     breaking: bool
 ) -> Result<()> {
+    let config = CommitConfig::load()?;
+
     // Validate commit type
-    if !VALID_TYPES.contains(&commit_type) {
+    if !config.is_valid_type(commit_type) {
         println!("{}: Invalid commit type '{}'", "Error".red().bold(), commit_type);
         println!("\n{}", "Valid types:".bold());
-        for t in VALID_TYPES {
+        for t in config.type_names() {
             println!("  - {}", t);
         }
         anyhow::bail!("Invalid commit type");
     }
-    
+
+    // Validate scope, if the project restricts scopes
+    if let Some(s) = scope {
+        if !config.is_valid_scope(s) {
+            println!("{}: Invalid scope '{}'", "Error".red().bold(), s);
+            println!("\n{}", "Allowed scopes:".bold());
+            for allowed in &config.allowed_scopes {
+                println!("  - {}", allowed);
+            }
+            anyhow::bail!("Invalid commit scope");
+        }
+    }
+
     // Build commit message
     let mut commit_msg = if let Some(s) = scope {
         format!("{}({}): {}", commit_type, s, message)
@@ -86,25 +286,118 @@ pub fn create_commit(
     if breaking {
         commit_msg.push_str("\n\nBREAKING CHANGE: This commit contains breaking changes");
     }
-    
+
+    stage_and_commit(&commit_msg, breaking)
+}
+
+/// Guided alternative to [`create_commit`]: prompts for type, scope, subject, an optional
+/// multi-line body, and breaking-change details instead of requiring them all as flags. The
+/// assembled message is validated through [`parse_commit`] before it shares
+/// [`create_commit`]'s staging-and-commit path, so both entry points commit through one
+/// validated implementation.
+pub fn interactive_commit() -> Result<()> {
+    let config = CommitConfig::load()?;
+
+    let type_items: Vec<String> =
+        config.types.iter().map(|t| format!("{} - {}", t.name, t.title)).collect();
+    let type_index = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Commit type")
+        .items(&type_items)
+        .default(0)
+        .interact()?;
+    let commit_type = config.types[type_index].name.clone();
+
+    let scope_input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Scope (optional)")
+        .allow_empty(true)
+        .interact_text()?;
+    let scope = (!scope_input.trim().is_empty()).then(|| scope_input.trim().to_string());
+    if let Some(s) = &scope {
+        if !config.is_valid_scope(s) {
+            anyhow::bail!("Invalid commit scope '{s}'");
+        }
+    }
+
+    let subject: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Subject")
+        .validate_with(|input: &String| -> std::result::Result<(), &str> {
+            if input.trim().is_empty() {
+                Err("subject must not be empty")
+            } else if input.len() > 100 {
+                Err("subject must be 100 characters or fewer")
+            } else {
+                Ok(())
+            }
+        })
+        .interact_text()?;
+
+    let wants_body = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Add a longer description?")
+        .default(false)
+        .interact()?;
+    let body = if wants_body {
+        Editor::new().edit("").context("Failed to open editor for commit body")?
+    } else {
+        None
+    };
+
+    let breaking = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Is this a breaking change?")
+        .default(false)
+        .interact()?;
+    let breaking_description = if breaking {
+        Some(
+            Input::<String>::with_theme(&ColorfulTheme::default())
+                .with_prompt("Describe the breaking change")
+                .interact_text()?,
+        )
+    } else {
+        None
+    };
+
+    let mut commit_msg = match &scope {
+        Some(s) => format!("{commit_type}({s}): {subject}"),
+        None => format!("{commit_type}: {subject}"),
+    };
+    if let Some(body) = &body {
+        if !body.trim().is_empty() {
+            commit_msg.push_str("\n\n");
+            commit_msg.push_str(body.trim());
+        }
+    }
+    if let Some(description) = &breaking_description {
+        commit_msg.push_str("\n\nBREAKING CHANGE: ");
+        commit_msg.push_str(description);
+    }
+
+    if let Err(err) = parse_commit(&commit_msg, &config) {
+        anyhow::bail!("Assembled commit message failed validation: {err}");
+    }
+
+    stage_and_commit(&commit_msg, breaking)
+}
+
+/// Shared tail of [`create_commit`] and [`interactive_commit`]: stage everything and create the
+/// commit from an already-assembled, already-validated message.
+fn stage_and_commit(commit_msg: &str, breaking: bool) -> Result<()> {
     // Stage all changes
     Command::new("git")
         .args(&["add", "-A"])
         .status()
         .context("Failed to stage changes")?;
-    
+
     // Create commit
     let output = Command::new("git")
-        .args(&["commit", "-m", &commit_msg])
+        .args(&["commit", "-m", commit_msg])
         .output()
         .context("Failed to create commit")?;
-    
+
     if output.status.success() {
         println!("{}: Commit created successfully", "Success".green().bold());
         println!("\n{}", String::from_utf8_lossy(&output.stdout));
-        
+
         if breaking {
-            println!("\n{}: Remember to bump major version before release", 
+            println!("\n{}: Remember to bump major version before release",
                 "Reminder".yellow().bold());
         }
     } else {
@@ -112,11 +405,13 @@ pub fn create_commit(
         println!("{}", String::from_utf8_lossy(&output.stderr));
         anyhow::bail!("Commit creation failed");
     }
-    
+
     Ok(())
 }
 
-pub fn show_conventions() {
+pub fn show_conventions() -> Result<()> {
+    let config = CommitConfig::load()?;
+
     println!("{}", "HecateOS Commit Conventions".bold().underline());
     println!("\n{}", "Format:".bold());
     println!("  <type>(<scope>): <subject>");
@@ -124,42 +419,171 @@ pub fn show_conventions() {
     println!("  <body>");
     println!("  <blank line>");
     println!("  <footer>");
-    
+
     println!("\n{}", "Types:".bold());
-    println!("  {} - A new feature", "feat".green());
-    println!("  {} - A bug fix", "fix".green());
-    println!("  {} - Documentation only changes", "docs".green());
-    println!("  {} - Formatting, white-space, etc", "style".green());
-    println!("  {} - Code refactoring", "refactor".green());
-    println!("  {} - Performance improvements", "perf".green());
-    println!("  {} - Adding or correcting tests", "test".green());
-    println!("  {} - Build process or auxiliary tool changes", "chore".green());
-    println!("  {} - Changes to build system", "build".green());
-    println!("  {} - CI configuration changes", "ci".green());
-    println!("  {} - Reverts a previous commit", "revert".green());
-    
+    for commit_type in &config.types {
+        println!("  {} - {}", commit_type.name.green(), commit_type.title);
+    }
+
     println!("\n{}", "Scope:".bold());
-    println!("  Optional, can be any of:");
-    println!("  - rust (Rust components)");
-    println!("  - dashboard (Web dashboard)");
-    println!("  - iso (ISO build system)");
-    println!("  - docs (Documentation)");
-    println!("  - deps (Dependencies)");
-    
+    if config.allowed_scopes.is_empty() {
+        println!("  Optional, any scope is allowed");
+    } else {
+        println!("  Optional, can be any of:");
+        for scope in &config.allowed_scopes {
+            println!("  - {}", scope);
+        }
+    }
+
     println!("\n{}", "Examples:".bold());
     println!("  feat(rust): add GPU temperature monitoring");
     println!("  fix(dashboard): correct WebSocket reconnection logic");
     println!("  docs: update installation instructions");
     println!("  perf(rust): optimize memory allocation in monitor");
-    
+
     println!("\n{}", "Breaking Changes:".bold());
     println!("  Add 'BREAKING CHANGE:' in the footer to indicate breaking changes");
     println!("  This will trigger a major version bump recommendation");
-    
+
     println!("\n{}", "Version Impact:".bold());
     println!("  {} → major version bump", "BREAKING CHANGE".red());
-    println!("  {} → minor version bump", "feat".yellow());
-    println!("  {} → patch version bump", "fix, docs, style, refactor, perf, test, chore".blue());
+    for commit_type in &config.types {
+        let impact = match commit_type.version_impact {
+            VersionImpact::Major => "major version bump".red().to_string(),
+            VersionImpact::Minor => "minor version bump".yellow().to_string(),
+            VersionImpact::Patch => "patch version bump".blue().to_string(),
+            VersionImpact::None => "no version bump".to_string(),
+        };
+        println!("  {} → {}", commit_type.name.green(), impact);
+    }
+
+    Ok(())
+}
+
+/// A parsed conventional commit from a [`generate_changelog`] range, also reused by
+/// [`crate::version::compute_bump`] to derive the next version from the same commit history.
+#[derive(Debug, Clone)]
+pub(crate) struct ChangelogCommit {
+    pub(crate) hash: String,
+    pub(crate) commit_type: String,
+    pub(crate) scope: Option<String>,
+    pub(crate) subject: String,
+    pub(crate) breaking: bool,
+}
+
+/// Section heading for each built-in commit type, in the order they're rendered by
+/// [`CommitConfig::defaults`]. Projects with a `hecate-dev.toml` `[commit]` table override supply
+/// their own titles and ordering via [`CommitTypeConfig`] instead.
+const SECTION_TITLES: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("docs", "Documentation"),
+    ("refactor", "Refactoring"),
+    ("style", "Styling"),
+    ("test", "Tests"),
+    ("build", "Build System"),
+    ("ci", "Continuous Integration"),
+    ("chore", "Chores"),
+    ("revert", "Reverts"),
+];
+
+/// Turn the conventional commits in `from..to` into grouped Markdown release notes: a
+/// "BREAKING CHANGES" section first (if any), then one section per [`CommitConfig`] type present
+/// in the range (using that project's own section titles and ordering), each commit rendered as
+/// `* **scope:** subject (hash)`.
+///
+/// `scope_filter`, when set, keeps only commits whose `(scope)` matches exactly -- for producing
+/// per-component release notes in a monorepo.
+pub fn generate_changelog(from: &str, to: &str, scope_filter: Option<&str>) -> Result<String> {
+    let config = CommitConfig::load()?;
+    let commits = parse_commit_range(from, to, &config)?;
+    let commits: Vec<&ChangelogCommit> = commits
+        .iter()
+        .filter(|c| scope_filter.map_or(true, |wanted| c.scope.as_deref() == Some(wanted)))
+        .collect();
+
+    let mut output = String::new();
+
+    let breaking: Vec<&&ChangelogCommit> = commits.iter().filter(|c| c.breaking).collect();
+    if !breaking.is_empty() {
+        output.push_str("## BREAKING CHANGES\n\n");
+        for commit in breaking {
+            output.push_str(&render_changelog_entry(commit));
+        }
+        output.push('\n');
+    }
+
+    for commit_type in &config.types {
+        let section: Vec<&&ChangelogCommit> =
+            commits.iter().filter(|c| c.commit_type == commit_type.name).collect();
+        if section.is_empty() {
+            continue;
+        }
+        output.push_str(&format!("## {}\n\n", commit_type.title));
+        for commit in section {
+            output.push_str(&render_changelog_entry(commit));
+        }
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+fn render_changelog_entry(commit: &ChangelogCommit) -> String {
+    format!(
+        "* {}{} ({})\n",
+        commit.scope.as_ref().map(|s| format!("**{s}:** ")).unwrap_or_default(),
+        commit.subject,
+        commit.hash
+    )
+}
+
+/// Run `git log <from>..<to> --pretty=%H%x00%B` and parse each record into a [`ChangelogCommit`].
+/// The null byte separates the hash from the full message body (subject plus any footers) so a
+/// `BREAKING CHANGE:` footer on its own line is read as part of the right commit even once
+/// messages span multiple lines; `%x1e` (ASCII record separator) delimits commits, since the
+/// default newline-per-commit separator breaks down for multi-line bodies.
+pub(crate) fn parse_commit_range(
+    from: &str,
+    to: &str,
+    config: &CommitConfig,
+) -> Result<Vec<ChangelogCommit>> {
+    let output = Command::new("git")
+        .args(&["log", &format!("{from}..{to}"), "--pretty=%H%x00%B%x1e"])
+        .output()
+        .context("Failed to run git log")?;
+
+    if !output.status.success() {
+        anyhow::bail!("git log failed: {}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    let subject_re = Regex::new(&format!(
+        r"^({})(\(([a-z0-9-]+)\))?(!)?: (.+)$",
+        config.type_names().join("|")
+    ))?;
+
+    let log = String::from_utf8_lossy(&output.stdout);
+    let mut commits = Vec::new();
+    for record in log.split('\u{1e}') {
+        let Some((hash, body)) = record.split_once('\0') else { continue };
+        let body = body.trim_start_matches('\n');
+        let Some(subject_line) = body.lines().next() else { continue };
+
+        let Some(caps) = subject_re.captures(subject_line) else { continue };
+        let bang = caps.get(4).is_some();
+        let breaking = bang || body.lines().any(|line| line.starts_with("BREAKING CHANGE:"));
+
+        commits.push(ChangelogCommit {
+            hash: hash.chars().take(7).collect(),
+            commit_type: caps[1].to_string(),
+            scope: caps.get(3).map(|m| m.as_str().to_string()),
+            subject: caps[5].to_string(),
+            breaking,
+        });
+    }
+
+    Ok(commits)
 }
 
 fn get_latest_commit_message() -> Result<String> {
@@ -0,0 +1,81 @@
+//! Unified project configuration
+//!
+//! Most of `hecate-dev`'s config lives one concern per file under `config/hecate/` (`imports.toml`,
+//! `licenses.toml`, `deny.toml`...). Commit conventions, the default check selection, and changelog
+//! sections/version rules are different: they all shape the same release workflow, so they live
+//! together in one `config/hecate/hecate-dev.toml`, loaded here. A project without one gets the
+//! historical hard-coded behavior -- every check runs, [`crate::commit::CommitConfig`]'s built-in
+//! commit types apply, and [`crate::release::ChangelogConfig`]'s built-in sections/version rules
+//! apply.
+//!
+//! `[commit]` and `[changelog]` are deliberately separate tables: `[commit]` governs which types
+//! `hecate-dev commit` accepts (and the version impact it *advertises* for each, via
+//! `hecate-dev commit conventions`), while `[changelog]` governs which types actually appear in a
+//! generated changelog (under what title/emoji, or hidden) and which actually move the version in
+//! `determine_next_version`. A type can be valid in one table and unmapped in the other.
+
+use crate::commit::CommitConfig;
+use crate::release::ChangelogConfig;
+use anyhow::{Context, Result};
+use hecate_ml::error::MLError;
+use serde::Deserialize;
+use std::path::Path;
+
+const CONFIG_PATH: &str = "config/hecate/hecate-dev.toml";
+
+/// Which checks `check` runs when no `--only` is given on the command line, and what `init-hooks`
+/// templates into the generated pre-commit hook.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckConfig {
+    #[serde(default = "CheckConfig::historical_default_only")]
+    pub default_only: Vec<String>,
+}
+
+impl CheckConfig {
+    fn historical_default_only() -> Vec<String> {
+        ["structure", "imports", "licenses", "todos", "dependencies", "ports"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+}
+
+impl Default for CheckConfig {
+    fn default() -> Self {
+        Self { default_only: Self::historical_default_only() }
+    }
+}
+
+/// The full `hecate-dev.toml` shape: commit conventions, check selection, and changelog sections.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HecateDevConfig {
+    #[serde(default = "CommitConfig::defaults")]
+    pub commit: CommitConfig,
+    #[serde(default)]
+    pub check: CheckConfig,
+    #[serde(default = "ChangelogConfig::defaults")]
+    pub changelog: ChangelogConfig,
+}
+
+impl HecateDevConfig {
+    /// Load [`CONFIG_PATH`], falling back to built-in defaults for every section when it doesn't
+    /// exist. A malformed file is surfaced as [`MLError::ConfigParseError`] (same error variant
+    /// `hecate-ml`'s own config loading uses for a bad `toml::de::Error`) so `hecate-dev` and
+    /// `hecate-ml` agree on what "the config file is broken" looks like.
+    pub fn load() -> Result<Self> {
+        let path = Path::new(CONFIG_PATH);
+        if !path.exists() {
+            return Ok(Self {
+                commit: CommitConfig::defaults(),
+                check: CheckConfig::default(),
+                changelog: ChangelogConfig::defaults(),
+            });
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {CONFIG_PATH}"))?;
+        toml::from_str(&content)
+            .map_err(MLError::ConfigParseError)
+            .with_context(|| format!("failed to parse {CONFIG_PATH}"))
+    }
+}
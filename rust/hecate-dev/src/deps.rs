@@ -0,0 +1,359 @@
+//! Dependency-graph policy enforcement: RustSec advisories and license allow/deny rules
+//!
+//! `check_dependencies` used to just count workspace entries and print a reminder to run
+//! `cargo audit` by hand. This parses the resolved `Cargo.lock` graph directly and checks it
+//! against two sources of policy: the RustSec advisory database (for known vulnerabilities) and
+//! a `deny.toml`-style allow/deny/exceptions license list (for license compliance), the same
+//! two checks `cargo-deny` runs in CI elsewhere. It also flags crates pulled in at more than one
+//! semver-incompatible version, a common source of bloat and trait-mismatch errors.
+
+use anyhow::{Context, Result};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use spdx::Expression;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+
+/// Default URL for the RustSec advisory database export
+const DEFAULT_ADVISORY_DB_URL: &str = "https://hecate-os.example/api/v1/rustsec-advisories.json";
+
+/// A single resolved package from `Cargo.lock`
+#[derive(Debug, Clone)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: Version,
+    /// Declared license expression, if `Cargo.lock` carries one (older lock versions don't)
+    pub license: Option<String>,
+    /// Names of the packages this one directly depends on
+    pub dependencies: Vec<String>,
+}
+
+/// The resolved dependency graph of `Cargo.lock`
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    pub packages: Vec<LockedPackage>,
+}
+
+impl DependencyGraph {
+    pub fn parse(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let doc: toml::Value = content
+            .parse()
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+
+        let packages = doc
+            .get("package")
+            .and_then(|p| p.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let name = entry.get("name")?.as_str()?.to_string();
+                        let version = Version::parse(entry.get("version")?.as_str()?).ok()?;
+                        let license = entry
+                            .get("license")
+                            .and_then(|l| l.as_str())
+                            .map(|s| s.to_string());
+                        let dependencies = entry
+                            .get("dependencies")
+                            .and_then(|d| d.as_array())
+                            .map(|deps| {
+                                deps.iter()
+                                    .filter_map(|d| d.as_str())
+                                    // Locked deps are "name" or "name version (source)";
+                                    // only the name is needed to walk the graph.
+                                    .map(|d| d.split_whitespace().next().unwrap_or(d).to_string())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+
+                        Some(LockedPackage { name, version, license, dependencies })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self { packages })
+    }
+
+    fn find(&self, name: &str) -> Option<&LockedPackage> {
+        self.packages.iter().find(|p| p.name == name)
+    }
+
+    /// Direct dependents of `name` (packages whose `dependencies` list includes it)
+    fn dependents_of(&self, name: &str) -> Vec<&LockedPackage> {
+        self.packages
+            .iter()
+            .filter(|p| p.dependencies.iter().any(|d| d == name))
+            .collect()
+    }
+
+    /// Group every resolved package by name, then split each group's versions into
+    /// semver-compatible clusters (same major, or same major.minor while major is 0). Names with
+    /// more than one cluster are reported, each version annotated with the packages that require
+    /// it directly. `allow_list` suppresses names that are known-unavoidable duplicates.
+    pub fn duplicate_versions(&self, allow_list: &HashSet<String>) -> Vec<DuplicateGroup> {
+        let mut by_name: HashMap<&str, Vec<&LockedPackage>> = HashMap::new();
+        for package in &self.packages {
+            by_name.entry(package.name.as_str()).or_default().push(package);
+        }
+
+        let mut duplicates = Vec::new();
+        for (name, mut packages) in by_name {
+            if allow_list.contains(name) || packages.len() < 2 {
+                continue;
+            }
+            packages.sort_by(|a, b| a.version.cmp(&b.version));
+
+            let mut clusters: Vec<Vec<&LockedPackage>> = Vec::new();
+            for package in packages {
+                match clusters.last_mut() {
+                    Some(cluster) if semver_compatible(&cluster[0].version, &package.version) => {
+                        cluster.push(package)
+                    }
+                    _ => clusters.push(vec![package]),
+                }
+            }
+
+            if clusters.len() > 1 {
+                duplicates.push(DuplicateGroup {
+                    name: name.to_string(),
+                    versions: clusters
+                        .into_iter()
+                        .map(|cluster| {
+                            let version = cluster[0].version.clone();
+                            let dependents: Vec<String> = cluster
+                                .iter()
+                                .flat_map(|p| self.dependents_of(&p.name))
+                                .map(|d| d.name.clone())
+                                .collect();
+                            (version, dependents)
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        duplicates.sort_by(|a, b| a.name.cmp(&b.name));
+        duplicates
+    }
+
+    /// Shortest chain from a workspace crate (any package named `hecate-*`) down to `name`,
+    /// rendered as `"hecate-cli -> tokio -> mio"`. Returns just `name` if no path is found.
+    pub fn dependency_path(&self, name: &str) -> String {
+        let mut queue: VecDeque<Vec<String>> = VecDeque::new();
+        let mut visited = HashSet::new();
+        queue.push_back(vec![name.to_string()]);
+        visited.insert(name.to_string());
+
+        while let Some(path) = queue.pop_front() {
+            let head = path.first().unwrap();
+            if head.starts_with("hecate-") {
+                return path.join(" -> ");
+            }
+            for dependent in self.dependents_of(head) {
+                if visited.insert(dependent.name.clone()) {
+                    let mut next = path.clone();
+                    next.insert(0, dependent.name.clone());
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        name.to_string()
+    }
+}
+
+/// A crate resolved at more than one semver-incompatible version
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub name: String,
+    /// One entry per incompatible version cluster: the lowest version in that cluster, and the
+    /// names of packages that depend on something in it
+    pub versions: Vec<(Version, Vec<String>)>,
+}
+
+/// Whether `a` and `b` are the same semver-compatible release per Cargo's caret-default rules:
+/// same major version (while major > 0), or same major.minor while major is 0
+fn semver_compatible(a: &Version, b: &Version) -> bool {
+    if a.major != b.major {
+        return false;
+    }
+    if a.major == 0 {
+        return a.minor == b.minor;
+    }
+    true
+}
+
+/// A known vulnerability, as published in the RustSec advisory database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Advisory {
+    pub id: String,
+    pub package: String,
+    /// Version ranges known to be vulnerable (e.g. "<1.2.3", ">=1.0.0, <1.0.5")
+    pub vulnerable_versions: Vec<semver::VersionReq>,
+    pub title: String,
+}
+
+/// The set of advisories checked against the resolved dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AdvisoryDb {
+    pub advisories: Vec<Advisory>,
+}
+
+impl AdvisoryDb {
+    /// Load the advisory database, preferring the local cache over an empty (fail-open) default.
+    /// Does not make a network request; call [`Self::update_online`] for that.
+    pub fn load() -> Result<Self> {
+        let cache_path = default_cache_path()?;
+        match fs::read_to_string(&cache_path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents).unwrap_or_default()),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Fetch a fresh advisory database and persist it to the local cache
+    pub async fn update_online(&mut self) -> Result<()> {
+        self.update_online_from(DEFAULT_ADVISORY_DB_URL).await
+    }
+
+    async fn update_online_from(&mut self, url: &str) -> Result<()> {
+        let response = reqwest::get(url).await.context("advisory-db fetch failed")?;
+        let db: AdvisoryDb = response.json().await.context("advisory-db response malformed")?;
+
+        let cache_path = default_cache_path()?;
+        if let Some(parent) = cache_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&cache_path, serde_json::to_string_pretty(&db)?)?;
+
+        *self = db;
+        Ok(())
+    }
+
+    /// All advisories matching a resolved package's version
+    pub fn matches(&self, package: &LockedPackage) -> Vec<&Advisory> {
+        self.advisories
+            .iter()
+            .filter(|a| a.package == package.name)
+            .filter(|a| {
+                a.vulnerable_versions
+                    .iter()
+                    .any(|req| req.matches(&package.version))
+            })
+            .collect()
+    }
+}
+
+fn default_cache_path() -> Result<std::path::PathBuf> {
+    let dir = match std::env::var("HECATE_CONFIG_DIR") {
+        Ok(dir) => std::path::PathBuf::from(dir),
+        Err(_) => {
+            let home = std::env::var("HOME").context("HOME is not set; cannot locate cache directory")?;
+            std::path::PathBuf::from(home).join(".config").join("hecate")
+        }
+    };
+    Ok(dir.join("dev").join("advisories.json"))
+}
+
+/// `deny.toml`-style license policy: crates with an allowed license pass, crates with a denied
+/// license (or one on neither list) fail unless named in `exceptions`
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct LicensePolicy {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Package names permitted to carry an otherwise-denied license
+    #[serde(default)]
+    pub exceptions: Vec<String>,
+}
+
+impl LicensePolicy {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Evaluate a package's license expression against the policy. `Ok(())` if every license
+    /// identifier in the expression is allowed (or the package has an exception); otherwise the
+    /// disallowed identifier is returned.
+    pub fn check(&self, package: &LockedPackage) -> Result<(), String> {
+        if self.exceptions.iter().any(|e| e == &package.name) {
+            return Ok(());
+        }
+
+        let Some(license) = &package.license else {
+            return Err("no license declared".to_string());
+        };
+
+        let expression = Expression::parse(license).map_err(|e| format!("invalid SPDX expression '{license}': {e}"))?;
+
+        for req in expression.requirements() {
+            let id = req.req.license.id().map(|id| id.name).unwrap_or("unknown");
+            if self.deny.iter().any(|d| d == id) {
+                return Err(format!("{id} is explicitly denied"));
+            }
+            if !self.allow.iter().any(|a| a == id) {
+                return Err(format!("{id} is not on the allow list"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, version: &str, dependencies: &[&str]) -> LockedPackage {
+        LockedPackage {
+            name: name.to_string(),
+            version: Version::parse(version).unwrap(),
+            license: None,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn flags_incompatible_major_versions() {
+        let graph = DependencyGraph {
+            packages: vec![
+                package("hecate-cli", "0.1.0", &["rand"]),
+                package("rand", "0.7.3", &[]),
+                package("rand", "0.8.5", &[]),
+            ],
+        };
+
+        let duplicates = graph.duplicate_versions(&HashSet::new());
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].name, "rand");
+        assert_eq!(duplicates[0].versions.len(), 2);
+    }
+
+    #[test]
+    fn patch_versions_are_not_duplicates() {
+        let graph = DependencyGraph {
+            packages: vec![package("serde", "1.0.190", &[]), package("serde", "1.0.195", &[])],
+        };
+
+        assert!(graph.duplicate_versions(&HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn allow_list_suppresses_known_duplicates() {
+        let graph = DependencyGraph {
+            packages: vec![package("syn", "1.0.0", &[]), package("syn", "2.0.0", &[])],
+        };
+
+        let allow: HashSet<String> = ["syn".to_string()].into_iter().collect();
+        assert!(graph.duplicate_versions(&allow).is_empty());
+    }
+}
@@ -0,0 +1,347 @@
+//! Attribute-aware `use` reorganization
+//!
+//! The original `check_and_fix_imports` scanned bare `line.starts_with("use ")`, which drops
+//! `#[cfg(...)]` attributes sitting above a `use`, loses any doc/line comment attached to it, and
+//! reorders items that a `cfg` attribute depended on being adjacent to a specific neighbor. This
+//! tokenizes each `use` item together with its attached attributes/comments as one atomic unit
+//! before grouping or sorting, so `--fix` can't separate an attribute from the item it guards.
+
+use serde::Deserialize;
+use std::fmt::Write as _;
+
+/// How the three import buckets (std, external, crate/super/self) are arranged relative to
+/// each other. Named to match rustfmt's `imports_granularity`/`group_imports` options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+pub enum GroupStyle {
+    /// Leave the existing grouping and ordering alone; only merging (if enabled) applies
+    Preserve,
+    /// Blank-line-separated std / external / crate groups, each sorted case-insensitively
+    #[default]
+    StdExternalCrate,
+    /// A single sorted group with no blank-line separation
+    One,
+}
+
+/// User-configurable import style, loaded from `config/hecate/imports.toml`
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct ImportConfig {
+    #[serde(default)]
+    pub group_style: GroupStyle,
+    /// Merge `use a::b;` + `use a::c;` into `use a::{b, c};` where safe
+    #[serde(default)]
+    pub merge: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bucket {
+    Std,
+    External,
+    Crate,
+}
+
+/// A `use` item plus any attributes/doc comments directly attached above it
+#[derive(Debug, Clone)]
+struct UseUnit {
+    /// `#[cfg(...)]`, doc comments, or line comments immediately preceding the `use`
+    prefix: Vec<String>,
+    /// The `use` statement itself, one entry per source line (braced imports can span lines)
+    lines: Vec<String>,
+    bucket: Bucket,
+    sort_key: String,
+}
+
+impl UseUnit {
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for line in &self.prefix {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.push_str(&self.lines.join("\n"));
+        out
+    }
+}
+
+fn classify_bucket(path: &str) -> Bucket {
+    let path = path
+        .trim_start_matches("pub(crate) ")
+        .trim_start_matches("pub(super) ")
+        .trim_start_matches("pub ")
+        .trim_start_matches("use ");
+
+    if path.starts_with("crate::") || path.starts_with("super::") || path.starts_with("self::") {
+        Bucket::Crate
+    } else if path.starts_with("std::") || path.starts_with("core::") || path.starts_with("alloc::")
+    {
+        Bucket::Std
+    } else {
+        Bucket::External
+    }
+}
+
+fn is_use_start(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("use ")
+        || trimmed.starts_with("pub use ")
+        || trimmed.starts_with("pub(crate) use ")
+        || trimmed.starts_with("pub(super) use ")
+}
+
+fn is_attached_prefix(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("#[") || trimmed.starts_with("///") || trimmed.starts_with("//!") || trimmed.starts_with("//")
+}
+
+/// Consume lines starting at `start` that form one `use` statement, following brace depth so a
+/// braced list spanning multiple lines isn't cut short at the first newline.
+fn consume_use_statement(lines: &[&str], start: usize) -> usize {
+    let mut depth = 0i32;
+    let mut end = start;
+    loop {
+        let line = lines[end];
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+        if depth <= 0 && line.trim_end().ends_with(';') {
+            return end + 1;
+        }
+        end += 1;
+        if end >= lines.len() {
+            return end;
+        }
+    }
+}
+
+/// Parse the leading import block (attributes/comments + `use` items, interleaved with blank
+/// lines) starting at the top of `lines`. Returns the parsed units and the index of the first
+/// line that isn't part of the block.
+fn parse_import_block(lines: &[&str]) -> (Vec<UseUnit>, usize) {
+    let mut units = Vec::new();
+    let mut i = 0;
+    let mut pending_prefix = Vec::new();
+
+    while i < lines.len() {
+        let line = lines[i];
+
+        if line.trim().is_empty() {
+            if !pending_prefix.is_empty() {
+                // A blank line between a comment/attribute and the `use` it's attached to would
+                // be unusual; treat it as the end of the block instead of guessing.
+                break;
+            }
+            i += 1;
+            continue;
+        }
+
+        if is_use_start(line) {
+            let end = consume_use_statement(lines, i);
+            let statement_lines: Vec<String> = lines[i..end].iter().map(|l| l.to_string()).collect();
+            let sort_key = statement_lines[0]
+                .trim_start_matches("pub(crate) ")
+                .trim_start_matches("pub(super) ")
+                .trim_start_matches("pub ")
+                .trim_start_matches("use ")
+                .trim()
+                .to_lowercase();
+            let bucket = classify_bucket(&statement_lines[0]);
+            units.push(UseUnit {
+                prefix: std::mem::take(&mut pending_prefix),
+                lines: statement_lines,
+                bucket,
+                sort_key,
+            });
+            i = end;
+            continue;
+        }
+
+        if is_attached_prefix(line) {
+            pending_prefix.push(line.to_string());
+            i += 1;
+            continue;
+        }
+
+        break;
+    }
+
+    // Trailing comments with no following `use` weren't part of the import block; give them back.
+    i -= pending_prefix.len();
+
+    (units, i)
+}
+
+/// Merge single-line, attribute-free units sharing a base path into one braced `use`, e.g.
+/// `use a::b;` + `use a::c;` -> `use a::{b, c};`. Aliased (`as`) and already-braced leaves are
+/// kept as separate entries within the merged braces; glob imports and multi-line items are left
+/// untouched since folding them in could change their meaning.
+fn merge_units(units: Vec<UseUnit>) -> Vec<UseUnit> {
+    use std::collections::BTreeMap;
+
+    // Keyed by the shared base path (including any `pub`/`pub(crate)` prefix, since items with
+    // different visibility can't be folded into the same braced list).
+    let mut mergeable: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut result = Vec::new();
+
+    for unit in units {
+        let mergeable_candidate = unit.prefix.is_empty()
+            && unit.lines.len() == 1
+            && !unit.lines[0].contains('*')
+            && unit.lines[0].trim_end().trim_end_matches(';').rfind("::").is_some();
+
+        if mergeable_candidate {
+            let line = unit.lines[0].trim_end().trim_end_matches(';');
+            let split_at = line.rfind("::").unwrap();
+            let base = line[..split_at].to_string();
+            let leaf = line[split_at + 2..].to_string();
+            mergeable.entry(base).or_default().push(leaf);
+        } else {
+            result.push(unit);
+        }
+    }
+
+    for (base, mut leaves) in mergeable {
+        leaves.sort();
+        leaves.dedup();
+        // classify_bucket matches on a trailing "::segment"; the base alone (e.g. "use std")
+        // needs it reattached to be recognized as the std/core/alloc prefix.
+        let bucket = classify_bucket(&format!("{base}::"));
+        let sort_key = base
+            .trim_start_matches("pub(crate) ")
+            .trim_start_matches("pub(super) ")
+            .trim_start_matches("pub ")
+            .trim_start_matches("use ")
+            .to_lowercase();
+        let rendered = if leaves.len() == 1 {
+            format!("{}::{};", base, leaves[0])
+        } else {
+            format!("{}::{{{}}};", base, leaves.join(", "))
+        };
+        result.push(UseUnit {
+            prefix: Vec::new(),
+            lines: vec![rendered],
+            bucket,
+            sort_key,
+        });
+    }
+
+    result
+}
+
+/// Check whether `content`'s leading import block needs reorganizing under `config`, returning
+/// the rewritten content if so (`None` means it's already in the desired form).
+pub fn check_and_fix_imports(content: &str, config: &ImportConfig) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let (units, block_end) = parse_import_block(&lines);
+    if units.is_empty() {
+        return None;
+    }
+
+    let original_rendering: Vec<String> = units.iter().map(UseUnit::render).collect();
+
+    let units = if config.merge { merge_units(units) } else { units };
+
+    let grouped = match config.group_style {
+        GroupStyle::Preserve => {
+            let mut sorted = units;
+            if config.merge {
+                sorted.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+            }
+            vec![sorted]
+        }
+        GroupStyle::StdExternalCrate => {
+            let mut std_group: Vec<_> = units.iter().filter(|u| u.bucket == Bucket::Std).cloned().collect();
+            let mut external_group: Vec<_> =
+                units.iter().filter(|u| u.bucket == Bucket::External).cloned().collect();
+            let mut crate_group: Vec<_> = units.iter().filter(|u| u.bucket == Bucket::Crate).cloned().collect();
+            std_group.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+            external_group.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+            crate_group.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+            vec![std_group, external_group, crate_group]
+                .into_iter()
+                .filter(|g| !g.is_empty())
+                .collect()
+        }
+        GroupStyle::One => {
+            let mut all: Vec<_> = units;
+            all.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+            vec![all]
+        }
+    };
+
+    let mut rendered = String::new();
+    for (i, group) in grouped.iter().enumerate() {
+        if i > 0 {
+            rendered.push('\n');
+        }
+        for unit in group {
+            let _ = writeln!(rendered, "{}", unit.render());
+        }
+    }
+    let rendered = rendered.trim_end().to_string();
+
+    let new_rendering: Vec<String> = grouped.iter().flatten().map(UseUnit::render).collect();
+    if new_rendering == original_rendering && grouped.len() <= 1 {
+        return None;
+    }
+    if new_rendering == original_rendering {
+        // Same items, but the grouping/blank-line layout may have been collapsed/expanded; only
+        // report a change if the rendered text actually differs from the source block.
+        let original_block = lines[..block_end].join("\n");
+        if original_block.trim_end() == rendered {
+            return None;
+        }
+    }
+
+    let rest = lines[block_end..].join("\n");
+    let mut result = rendered;
+    if !rest.is_empty() {
+        result.push_str("\n\n");
+        result.push_str(&rest);
+    }
+    result.push('\n');
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(group_style: GroupStyle, merge: bool) -> ImportConfig {
+        ImportConfig { group_style, merge }
+    }
+
+    #[test]
+    fn groups_std_external_crate_with_blank_lines() {
+        let content = "use crate::error::Result;\nuse serde::Serialize;\nuse std::fs;\n\nfn main() {}\n";
+        let fixed = check_and_fix_imports(content, &config(GroupStyle::StdExternalCrate, false)).unwrap();
+        assert_eq!(
+            fixed,
+            "use std::fs;\n\nuse serde::Serialize;\n\nuse crate::error::Result;\n\nfn main() {}\n"
+        );
+    }
+
+    #[test]
+    fn preserves_cfg_attribute_above_use() {
+        let content = "#[cfg(feature = \"nvidia\")]\nuse crate::nvidia::Device;\nuse std::fs;\n\nfn main() {}\n";
+        let fixed = check_and_fix_imports(content, &config(GroupStyle::StdExternalCrate, false)).unwrap();
+        assert!(fixed.contains("#[cfg(feature = \"nvidia\")]\nuse crate::nvidia::Device;"));
+    }
+
+    #[test]
+    fn already_sorted_returns_none() {
+        let content = "use std::fs;\n\nfn main() {}\n";
+        assert!(check_and_fix_imports(content, &config(GroupStyle::StdExternalCrate, false)).is_none());
+    }
+
+    #[test]
+    fn merges_shared_base_path() {
+        let content = "use std::fs;\nuse std::path::Path;\nuse std::path::PathBuf;\n\nfn main() {}\n";
+        let fixed = check_and_fix_imports(content, &config(GroupStyle::StdExternalCrate, true)).unwrap();
+        assert!(fixed.contains("use std::path::{Path, PathBuf};"));
+    }
+
+    #[test]
+    fn preserve_style_keeps_grouping_but_can_still_merge() {
+        let content = "use std::path::Path;\nuse std::path::PathBuf;\nuse serde::Serialize;\n\nfn main() {}\n";
+        let fixed = check_and_fix_imports(content, &config(GroupStyle::Preserve, true)).unwrap();
+        assert!(fixed.contains("use std::path::{Path, PathBuf};"));
+        assert!(fixed.contains("use serde::Serialize;"));
+    }
+}
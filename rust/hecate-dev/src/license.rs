@@ -0,0 +1,319 @@
+//! SPDX license-expression parsing and the REUSE-style `path_tree` report
+//!
+//! `check_license_headers` used to byte-compare a hard-coded header; this module parses the
+//! real `SPDX-License-Identifier:` line of each file into a validated expression (via
+//! `spdx-expression`) and aggregates the whole repo into a [`Node`] tree keyed by directory,
+//! collapsing runs of identically-licensed files the way the REUSE tool's `collect-license-info`
+//! report does.
+
+use anyhow::Result;
+use serde::Serialize;
+use spdx::Expression;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A parsed SPDX expression plus the copyright holders found alongside it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct License {
+    /// Canonical form of the validated SPDX expression, e.g. `"MIT OR Apache-2.0"`
+    pub expression: String,
+    /// Copyright holder lines found in the same header (order-preserving, deduplicated)
+    pub holders: Vec<String>,
+}
+
+/// Interned index into a [`LicenseTable`], used so identical licenses compare by `==` in O(1)
+/// and the JSON report doesn't repeat the same expression/holder text for every file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct LicenseId(usize);
+
+/// Deduplicating store of every distinct [`License`] seen while walking the tree
+#[derive(Debug, Default)]
+pub struct LicenseTable {
+    licenses: Vec<License>,
+    index: HashMap<License, LicenseId>,
+}
+
+impl LicenseTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intern `license`, returning the existing id if it was already seen
+    pub fn intern(&mut self, license: License) -> LicenseId {
+        if let Some(id) = self.index.get(&license) {
+            return *id;
+        }
+        let id = LicenseId(self.licenses.len());
+        self.index.insert(license.clone(), id);
+        self.licenses.push(license);
+        id
+    }
+
+    pub fn get(&self, id: LicenseId) -> &License {
+        &self.licenses[id.0]
+    }
+}
+
+/// A node in the REUSE-style license tree
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Node {
+    Directory {
+        name: String,
+        children: Vec<Node>,
+        license: Option<LicenseId>,
+    },
+    File {
+        name: String,
+        license: LicenseId,
+    },
+}
+
+impl Node {
+    fn license(&self) -> Option<LicenseId> {
+        match self {
+            Node::Directory { license, .. } => *license,
+            Node::File { license, .. } => Some(*license),
+        }
+    }
+}
+
+/// Directories whose entire contents inherit the license of one designated file, rather than
+/// being walked and validated file-by-file (e.g. vendored subtrees)
+#[derive(Debug, Clone, Default)]
+pub struct CondensedDirs {
+    /// Maps a directory path (relative to the walk root) to the file whose license applies
+    pub nominated_file: HashMap<PathBuf, PathBuf>,
+}
+
+/// Files without a parseable header accumulate here instead of aborting the whole walk, so one
+/// bad file doesn't hide problems elsewhere in the tree.
+#[derive(Debug, Clone, Serialize)]
+pub struct LicenseIssue {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+/// Result of walking a directory: the collapsed tree plus every interned license and any
+/// files that failed to parse
+#[derive(Debug, Serialize)]
+pub struct LicenseReport {
+    pub root: Node,
+    pub licenses: Vec<License>,
+    pub issues: Vec<LicenseIssue>,
+}
+
+/// Parse the `SPDX-License-Identifier:` and `Copyright` lines out of a file's leading comment
+/// block. Returns `Err` if no identifier line is present or it fails to parse as a valid SPDX
+/// expression.
+pub fn parse_header(content: &str) -> Result<License> {
+    let mut expression = None;
+    let mut holders = Vec::new();
+
+    for line in content.lines().take_while(|l| {
+        let trimmed = l.trim_start();
+        trimmed.starts_with("//") || trimmed.is_empty()
+    }) {
+        let text = line.trim_start_matches('/').trim();
+        if let Some(rest) = text.strip_prefix("SPDX-License-Identifier:") {
+            expression = Some(rest.trim().to_string());
+        } else if let Some(rest) = text.strip_prefix("Copyright") {
+            let holder = format!("Copyright{}", rest).trim().to_string();
+            if !holders.contains(&holder) {
+                holders.push(holder);
+            }
+        }
+    }
+
+    let raw = expression.ok_or_else(|| anyhow::anyhow!("missing SPDX-License-Identifier line"))?;
+    let parsed = Expression::parse(&raw)
+        .map_err(|e| anyhow::anyhow!("invalid SPDX expression '{raw}': {e}"))?;
+
+    Ok(License {
+        expression: parsed.to_string(),
+        holders,
+    })
+}
+
+/// Walk `root`, validating every `.rs` file's license header and building the collapsed tree.
+/// `condensed` short-circuits the named directories, assigning their nominated file's license
+/// to the whole subtree without visiting the rest of their contents.
+pub fn build_report(root: &Path, condensed: &CondensedDirs) -> Result<LicenseReport> {
+    let mut table = LicenseTable::new();
+    let mut issues = Vec::new();
+    let node = build_node(root, root, &mut table, condensed, &mut issues, None)?;
+
+    Ok(LicenseReport {
+        root: node,
+        licenses: table.licenses,
+        issues,
+    })
+}
+
+/// Names checked, in order, for a directory-level `LICENSE`/`COPYING` file when a file in that
+/// directory has no `SPDX-License-Identifier` header of its own
+const LICENSE_FILE_NAMES: &[&str] = &["LICENSE", "LICENSE.txt", "LICENSE.md", "COPYING"];
+
+/// Classify the first `LICENSE`/`COPYING` file found directly in `dir`, if any
+fn detect_directory_license(dir: &Path) -> Option<License> {
+    for name in LICENSE_FILE_NAMES {
+        let candidate = dir.join(name);
+        if let Ok(content) = fs::read_to_string(&candidate) {
+            let classification = crate::license_text::classify(&content);
+            if classification.spdx_id != "unknown" && classification.spdx_id != "ambiguous" {
+                return Some(License {
+                    expression: classification.spdx_id,
+                    holders: Vec::new(),
+                });
+            }
+        }
+    }
+    None
+}
+
+fn build_node(
+    walk_root: &Path,
+    path: &Path,
+    table: &mut LicenseTable,
+    condensed: &CondensedDirs,
+    issues: &mut Vec<LicenseIssue>,
+    fallback: Option<&License>,
+) -> Result<Node> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    if path.is_file() {
+        let content = fs::read_to_string(path)?;
+        let license = match parse_header(&content) {
+            Ok(license) => license,
+            Err(e) => match fallback {
+                // A directory-level LICENSE/COPYING file stands in for a missing header, the
+                // same way REUSE treats a `.license` sibling or `LICENSES/` declaration.
+                Some(detected) => detected.clone(),
+                None => {
+                    issues.push(LicenseIssue {
+                        path: path.to_path_buf(),
+                        reason: e.to_string(),
+                    });
+                    License {
+                        expression: "unknown".to_string(),
+                        holders: Vec::new(),
+                    }
+                }
+            },
+        };
+        return Ok(Node::File {
+            name,
+            license: table.intern(license),
+        });
+    }
+
+    let relative = path.strip_prefix(walk_root).unwrap_or(path);
+    if let Some(nominated) = condensed.nominated_file.get(relative) {
+        let content = fs::read_to_string(walk_root.join(nominated))?;
+        let license = parse_header(&content)?;
+        return Ok(Node::Directory {
+            name,
+            children: Vec::new(),
+            license: Some(table.intern(license)),
+        });
+    }
+
+    let mut entries: Vec<_> = fs::read_dir(path)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.is_dir() || p.extension().map(|ext| ext == "rs").unwrap_or(false)
+        })
+        .collect();
+    entries.sort();
+
+    // A LICENSE/COPYING file directly in this directory takes precedence over whatever fallback
+    // was inherited from an ancestor, matching how REUSE resolves the nearest declaration.
+    let own_license = detect_directory_license(path);
+    let inherited_fallback = own_license.as_ref().or(fallback);
+
+    let mut children = Vec::with_capacity(entries.len());
+    for entry in entries {
+        children.push(build_node(walk_root, &entry, table, condensed, issues, inherited_fallback)?);
+    }
+
+    let license = collapse(&children);
+    Ok(Node::Directory {
+        name,
+        children,
+        license,
+    })
+}
+
+/// If every child resolves to the same interned license, the directory can report that license
+/// directly. Callers that only need the report's top-level summary can then skip descending into
+/// directories that already carry a `license`.
+fn collapse(children: &[Node]) -> Option<LicenseId> {
+    let first = children.first()?.license()?;
+    children
+        .iter()
+        .all(|c| c.license() == Some(first))
+        .then_some(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_simple_mit_header() {
+        let content = "// Copyright (c) 2026 HecateOS Team\n// SPDX-License-Identifier: MIT\n\nfn main() {}\n";
+        let license = parse_header(content).unwrap();
+        assert_eq!(license.expression, "MIT");
+        assert_eq!(license.holders, vec!["Copyright (c) 2026 HecateOS Team"]);
+    }
+
+    #[test]
+    fn parses_compound_expression() {
+        let content = "// SPDX-License-Identifier: MIT OR Apache-2.0\n";
+        let license = parse_header(content).unwrap();
+        assert_eq!(license.expression, "MIT OR Apache-2.0");
+    }
+
+    #[test]
+    fn rejects_unknown_identifier() {
+        let content = "// SPDX-License-Identifier: NotARealLicense-9000\n";
+        assert!(parse_header(content).is_err());
+    }
+
+    #[test]
+    fn rejects_missing_header() {
+        let content = "fn main() {}\n";
+        assert!(parse_header(content).is_err());
+    }
+
+    #[test]
+    fn collapse_hoists_uniform_children() {
+        let mut table = LicenseTable::new();
+        let mit = table.intern(License {
+            expression: "MIT".to_string(),
+            holders: Vec::new(),
+        });
+        let children = vec![
+            Node::File { name: "a.rs".to_string(), license: mit },
+            Node::File { name: "b.rs".to_string(), license: mit },
+        ];
+        assert_eq!(collapse(&children), Some(mit));
+    }
+
+    #[test]
+    fn collapse_returns_none_on_mismatch() {
+        let mut table = LicenseTable::new();
+        let mit = table.intern(License { expression: "MIT".to_string(), holders: Vec::new() });
+        let apache = table.intern(License { expression: "Apache-2.0".to_string(), holders: Vec::new() });
+        let children = vec![
+            Node::File { name: "a.rs".to_string(), license: mit },
+            Node::File { name: "b.rs".to_string(), license: apache },
+        ];
+        assert_eq!(collapse(&children), None);
+    }
+}
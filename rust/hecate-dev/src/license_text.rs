@@ -0,0 +1,157 @@
+//! Offline SPDX license-text detection for files with no `SPDX-License-Identifier` header
+//!
+//! Vendored subtrees typically carry a `LICENSE`/`COPYING` file instead of per-file headers.
+//! This classifies that file's full text against an embedded corpus of known SPDX license texts
+//! (a zstd-compressed JSON blob, shipped the same way `cargo-deny` ships its `spdx_cache.bin.zstd`),
+//! so [`license`](crate::license) can report a directory's effective license without a header and
+//! without hitting the network.
+
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Corpus entries below this token-overlap score are reported as unknown rather than a weak guess
+const MATCH_THRESHOLD: f64 = 0.6;
+
+/// If the best match's score and the best match for a *different* SPDX id are within this margin
+/// of each other, the text is reported as `"ambiguous"` rather than picking the top score and
+/// risking a wrong identifier. Adjacent license texts can be this close: BSD-2-Clause is BSD-3-Clause
+/// minus one clause, and MIT and ISC share most of the same permission/disclaimer wording, so a
+/// reworded or partially-matching vendored `LICENSE` file can score within a couple of points of
+/// both.
+const AMBIGUITY_MARGIN: f64 = 0.05;
+
+const CORPUS_BYTES: &[u8] = include_bytes!("../assets/spdx_license_texts.json.zst");
+
+#[derive(Debug, Deserialize)]
+struct CorpusEntry {
+    id: String,
+    text: String,
+}
+
+fn corpus() -> &'static Vec<CorpusEntry> {
+    static CORPUS: OnceLock<Vec<CorpusEntry>> = OnceLock::new();
+    CORPUS.get_or_init(|| {
+        let json = zstd::decode_all(CORPUS_BYTES).expect("embedded SPDX corpus is valid zstd");
+        serde_json::from_slice(&json).expect("embedded SPDX corpus is valid JSON")
+    })
+}
+
+/// The result of classifying a license file's text against the corpus
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classification {
+    /// Best-matching SPDX id, `"unknown"` if nothing cleared [`MATCH_THRESHOLD`], or
+    /// `"ambiguous"` if the best two matches (for different SPDX ids) were within
+    /// [`AMBIGUITY_MARGIN`] of each other
+    pub spdx_id: String,
+    pub confidence: f64,
+}
+
+/// Lowercase, drop copyright lines, and collapse all whitespace so near-identical license texts
+/// with different copyright holders or line wrapping compare equal.
+fn normalize(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim_start().to_lowercase().starts_with("copyright"))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn token_set(text: &str) -> HashSet<&str> {
+    text.split_whitespace().collect()
+}
+
+/// Jaccard similarity of the two texts' token sets
+fn similarity(a: &str, b: &str) -> f64 {
+    let tokens_a = token_set(a);
+    let tokens_b = token_set(b);
+    if tokens_a.is_empty() || tokens_b.is_empty() {
+        return 0.0;
+    }
+
+    let intersection = tokens_a.intersection(&tokens_b).count();
+    let union = tokens_a.union(&tokens_b).count();
+    intersection as f64 / union as f64
+}
+
+/// Classify `text` (the contents of a `LICENSE`/`COPYING` file) against the embedded corpus
+pub fn classify(text: &str) -> Classification {
+    let normalized = normalize(text);
+
+    let mut scores: Vec<(&str, f64)> = corpus()
+        .iter()
+        .map(|entry| (entry.id.as_str(), similarity(&normalized, &normalize(&entry.text))))
+        .collect();
+    scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let Some(&(best_id, best_score)) = scores.first() else {
+        return Classification { spdx_id: "unknown".to_string(), confidence: 0.0 };
+    };
+
+    if best_score < MATCH_THRESHOLD {
+        return Classification { spdx_id: "unknown".to_string(), confidence: best_score };
+    }
+
+    let runner_up = scores.iter().find(|(id, _)| *id != best_id).map(|&(_, score)| score);
+    if runner_up.is_some_and(|runner_up_score| best_score - runner_up_score < AMBIGUITY_MARGIN) {
+        return Classification { spdx_id: "ambiguous".to_string(), confidence: best_score };
+    }
+
+    Classification { spdx_id: best_id.to_string(), confidence: best_score }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_exact_mit_text() {
+        let entry = corpus().iter().find(|e| e.id == "MIT").unwrap();
+        let result = classify(&entry.text);
+        assert_eq!(result.spdx_id, "MIT");
+        assert!(result.confidence > 0.99);
+    }
+
+    #[test]
+    fn classifies_mit_with_different_copyright_holder() {
+        let entry = corpus().iter().find(|e| e.id == "MIT").unwrap();
+        let with_header = format!("Copyright (c) 2026 Someone Else\n\n{}", entry.text);
+        let result = classify(&with_header);
+        assert_eq!(result.spdx_id, "MIT");
+    }
+
+    #[test]
+    fn unrelated_text_is_unknown() {
+        let result = classify("This is a README describing how to build the project.");
+        assert_eq!(result.spdx_id, "unknown");
+    }
+
+    #[test]
+    fn classifies_exact_bsd_2_and_bsd_3_clause_distinctly() {
+        let bsd2 = &corpus().iter().find(|e| e.id == "BSD-2-Clause").unwrap().text;
+        let bsd3 = &corpus().iter().find(|e| e.id == "BSD-3-Clause").unwrap().text;
+
+        assert_eq!(classify(bsd2).spdx_id, "BSD-2-Clause");
+        assert_eq!(classify(bsd3).spdx_id, "BSD-3-Clause");
+    }
+
+    #[test]
+    fn ambiguous_bsd_variant_is_reported_as_ambiguous_rather_than_misclassified() {
+        // A non-endorsement clause worded differently from the corpus's BSD-3-Clause entry --
+        // plausible vendored-file drift -- scores within AMBIGUITY_MARGIN of both BSD-2-Clause and
+        // BSD-3-Clause. Picking whichever edges out the other would silently record the wrong
+        // License.expression for a file whose actual clause count we can't confidently tell.
+        let bsd2 = &corpus().iter().find(|e| e.id == "BSD-2-Clause").unwrap().text;
+        let variant = bsd2.replace(
+            "THIS SOFTWARE IS PROVIDED",
+            "Neither the name of the project nor its contributors may be used without permission. \
+             THIS SOFTWARE IS PROVIDED",
+        );
+
+        let result = classify(&variant);
+        assert_eq!(result.spdx_id, "ambiguous");
+    }
+}
@@ -6,7 +6,16 @@ use tracing_subscriber::EnvFilter;
 mod version;
 mod commit;
 mod check;
+mod config;
+mod deps;
+mod imports;
+mod license;
+mod license_text;
+mod publish;
 mod release;
+mod rewriters;
+mod template;
+mod workspace;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -53,18 +62,21 @@ enum Commands {
         #[arg(short, long)]
         force: bool,
     },
+    /// Remove git hooks installed by `init-hooks`, restoring any hook they backed up
+    UninstallHooks,
 }
 
 #[derive(Subcommand)]
 enum VersionAction {
     /// Show current version
     Show,
-    /// Bump version based on commit type
+    /// Bump version based on commit type. Omit `level` to derive it automatically from
+    /// conventional commits since the last tag (same classification as `release changelog`).
     Bump {
-        /// Version part to bump (major, minor, patch)
+        /// Version part to bump (major, minor, patch); auto-derived from commit history when omitted
         #[arg(value_enum)]
-        level: version::BumpLevel,
-        
+        level: Option<version::BumpLevel>,
+
         /// Dry run without making changes
         #[arg(short = 'n', long)]
         dry_run: bool,
@@ -76,6 +88,36 @@ enum VersionAction {
     },
     /// Check if versions are in sync
     Check,
+    /// Compute the next version from commit history instead of a fixed level
+    AutoBump {
+        /// Tag or rev to diff from (defaults to the last annotated tag)
+        #[arg(long)]
+        since_tag: Option<String>,
+
+        /// Create the git tag for the computed version
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Stage a release candidate (`1.3.0-rc.1`) without committing to a final version. Repeated
+    /// `stage` calls against the same target version bump the rc counter instead of the version
+    /// itself; omit `level` to auto-derive the target from commit history since the last *final*
+    /// release, same as `bump` with no level.
+    Stage {
+        /// Version part to stage towards (major, minor, patch); auto-derived when omitted
+        #[arg(value_enum)]
+        level: Option<version::BumpLevel>,
+
+        /// Dry run without making changes
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+    /// Promote the currently staged release candidate to a final release by stripping its `-rc.N`
+    /// suffix, without recomputing the version.
+    Promote {
+        /// Dry run without making changes
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -105,6 +147,8 @@ enum CommitAction {
     },
     /// Show commit conventions
     Conventions,
+    /// Interactively build a commit message (type, scope, subject, body, breaking change)
+    Interactive,
 }
 
 #[derive(Subcommand)]
@@ -113,29 +157,88 @@ enum ReleaseAction {
     Create {
         /// Version for the release
         version: Option<String>,
-        
+
         /// Skip tests
         #[arg(long)]
         skip_tests: bool,
-        
+
         /// Skip changelog generation
         #[arg(long)]
         skip_changelog: bool,
+
+        /// Release only the workspace crates that changed since their last tag (plus their
+        /// reverse-dependency closure), bumping each independently instead of the whole
+        /// workspace together. Ignores `version`.
+        #[arg(long)]
+        workspace: bool,
+
+        /// Path to a Tera-style template for the generated changelog section; falls back to the
+        /// built-in layout when omitted
+        #[arg(long)]
+        changelog_template: Option<String>,
+
+        /// Path to a Tera-style template for the generated release notes; falls back to the
+        /// built-in layout when omitted
+        #[arg(long)]
+        release_notes_template: Option<String>,
+
+        /// Only include commits whose scope matches this regex -- for releasing a single
+        /// component's changelog/release notes out of a monorepo
+        #[arg(long)]
+        scope: Option<String>,
     },
     /// Generate changelog
     Changelog {
-        /// Version range (e.g., v0.1.0..HEAD)
+        /// Version range (e.g., v0.1.0..HEAD). Omit to regenerate the complete historical
+        /// changelog instead: every commit reachable from HEAD, grouped into one section per tag
+        /// plus a trailing "Unreleased" section for whatever's past the last tag.
         #[arg(short, long)]
         range: Option<String>,
-        
+
         /// Output format (markdown, json)
         #[arg(short, long, default_value = "markdown")]
         format: String,
+
+        /// Path to a Tera-style template for the `markdown` format; falls back to the built-in
+        /// layout when omitted
+        #[arg(long)]
+        template: Option<String>,
+
+        /// Only include commits whose scope matches this regex -- for generating a single
+        /// component's changelog out of a monorepo
+        #[arg(long)]
+        scope: Option<String>,
     },
     /// Prepare release notes
     Notes {
         /// Version to generate notes for
         version: Option<String>,
+
+        /// Path to a Tera-style template; falls back to the built-in layout when omitted
+        #[arg(long)]
+        template: Option<String>,
+
+        /// Only include commits whose scope matches this regex -- for generating a single
+        /// component's release notes out of a monorepo
+        #[arg(long)]
+        scope: Option<String>,
+    },
+    /// Publish a GitHub release for an existing tag, uploading build artifacts to it
+    Publish {
+        /// Tag to publish (e.g. v1.2.0)
+        tag: String,
+
+        /// Paths to build artifacts to upload to the release
+        #[arg(short, long)]
+        artifact: Vec<String>,
+
+        /// Create the release as a draft instead of publishing it immediately
+        #[arg(long)]
+        draft: bool,
+
+        /// Mark the release as a prerelease
+        #[arg(long)]
+        prerelease: bool,
     },
 }
 
@@ -170,6 +273,9 @@ async fn main() -> Result<()> {
         Commands::InitHooks { force } => {
             init_git_hooks(force).await?;
         }
+        Commands::UninstallHooks => {
+            uninstall_git_hooks().await?;
+        }
     }
 
     Ok(())
@@ -186,9 +292,18 @@ async fn handle_version_command(action: VersionAction) -> Result<()> {
         VersionAction::Sync { version } => {
             version::sync_version(version.as_deref())?;
         }
+        VersionAction::AutoBump { since_tag, apply } => {
+            version::auto_bump(since_tag.as_deref(), apply)?;
+        }
         VersionAction::Check => {
             version::check_version_sync()?;
         }
+        VersionAction::Stage { level, dry_run } => {
+            version::stage_prerelease(level, dry_run)?;
+        }
+        VersionAction::Promote { dry_run } => {
+            version::promote_version(dry_run)?;
+        }
     }
     Ok(())
 }
@@ -207,7 +322,10 @@ async fn handle_commit_command(action: CommitAction) -> Result<()> {
             commit::create_commit(&commit_type, scope.as_deref(), &message, breaking)?;
         }
         CommitAction::Conventions => {
-            commit::show_conventions();
+            commit::show_conventions()?;
+        }
+        CommitAction::Interactive => {
+            commit::interactive_commit()?;
         }
     }
     Ok(())
@@ -215,68 +333,70 @@ async fn handle_commit_command(action: CommitAction) -> Result<()> {
 
 async fn handle_release_command(action: ReleaseAction) -> Result<()> {
     match action {
-        ReleaseAction::Create { 
-            version, 
-            skip_tests, 
-            skip_changelog 
+        ReleaseAction::Create {
+            version,
+            skip_tests,
+            skip_changelog,
+            workspace,
+            changelog_template,
+            release_notes_template,
+            scope,
         } => {
-            release::create_release(
-                version.as_deref(), 
-                skip_tests, 
-                skip_changelog
-            ).await?;
+            if workspace {
+                workspace::create_workspace_release(skip_tests, skip_changelog).await?;
+            } else {
+                release::create_release(
+                    version.as_deref(),
+                    skip_tests,
+                    skip_changelog,
+                    changelog_template.as_deref(),
+                    release_notes_template.as_deref(),
+                    scope.as_deref(),
+                ).await?;
+            }
+        }
+        ReleaseAction::Changelog { range, format, template, scope } => {
+            release::generate_changelog(range.as_deref(), &format, template.as_deref(), scope.as_deref())?;
         }
-        ReleaseAction::Changelog { range, format } => {
-            release::generate_changelog(range.as_deref(), &format)?;
+        ReleaseAction::Notes { version, template, scope } => {
+            release::generate_release_notes(version.as_deref(), template.as_deref(), scope.as_deref())?;
         }
-        ReleaseAction::Notes { version } => {
-            release::generate_release_notes(version.as_deref())?;
+        ReleaseAction::Publish { tag, artifact, draft, prerelease } => {
+            publish::publish_release(&tag, &artifact, draft, prerelease).await?;
         }
     }
     Ok(())
 }
 
-async fn init_git_hooks(force: bool) -> Result<()> {
-    use std::fs;
-    use std::os::unix::fs::PermissionsExt;
-    
-    info!("Installing git hooks...");
-    
-    let hooks_dir = ".git/hooks";
-    fs::create_dir_all(hooks_dir)?;
-    
-    // Pre-commit hook
-    let pre_commit_path = format!("{}/pre-commit", hooks_dir);
-    if !force && std::path::Path::new(&pre_commit_path).exists() {
-        warn!("pre-commit hook already exists. Use --force to overwrite.");
-    } else {
-        let pre_commit_content = r#"#!/bin/sh
+/// `commit-msg` is what actually closes the loop on [`commit::validate_commit`]: git passes it the
+/// path to the in-flight commit message (normally `.git/COMMIT_EDITMSG`) as `$1`, and a non-zero
+/// exit aborts the commit.
+const COMMIT_MSG_HOOK: &str = r#"#!/bin/sh
+# HecateOS commit-msg hook
+
+exec hecate-dev commit validate "$(cat "$1")"
+"#;
+
+/// Template the pre-commit hook's `check --only` list from the project's `hecate-dev.toml`
+/// (falling back to its built-in default set when there's no config) instead of a fixed list, so
+/// a project that trims or reorders its checks gets that reflected the next time hooks are
+/// (re)installed.
+fn pre_commit_hook_script(checks: &[String]) -> String {
+    format!(
+        r#"#!/bin/sh
 # HecateOS pre-commit hook
 
 # Run hecate-dev checks
-hecate-dev check --only structure,imports,licenses
-
-# Validate commit message format
-if [ -f .git/COMMIT_EDITMSG ]; then
-    hecate-dev commit validate
-fi
+hecate-dev check --only {}
 
 # Run tests
 cargo test --quiet
-"#;
-        fs::write(&pre_commit_path, pre_commit_content)?;
-        let mut perms = fs::metadata(&pre_commit_path)?.permissions();
-        perms.set_mode(0o755);
-        fs::set_permissions(&pre_commit_path, perms)?;
-        info!("Installed pre-commit hook");
-    }
-    
-    // Pre-push hook
-    let pre_push_path = format!("{}/pre-push", hooks_dir);
-    if !force && std::path::Path::new(&pre_push_path).exists() {
-        warn!("pre-push hook already exists. Use --force to overwrite.");
-    } else {
-        let pre_push_content = r#"#!/bin/sh
+"#,
+        checks.join(",")
+    )
+}
+
+const PRE_PUSH_HOOK: &str = r#"#!/bin/sh
 # HecateOS pre-push hook
 
 # Check version sync
@@ -291,13 +411,72 @@ if [ -n "$(git status --porcelain)" ]; then
     exit 1
 fi
 "#;
-        fs::write(&pre_push_path, pre_push_content)?;
-        let mut perms = fs::metadata(&pre_push_path)?.permissions();
+
+/// Names of every hook this binary manages, in install/uninstall order.
+const MANAGED_HOOK_NAMES: &[&str] = &["commit-msg", "pre-commit", "pre-push"];
+
+async fn init_git_hooks(force: bool) -> Result<()> {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+
+    info!("Installing git hooks...");
+
+    let hooks_dir = Path::new(".git/hooks");
+    fs::create_dir_all(hooks_dir)?;
+
+    let default_checks = config::HecateDevConfig::load()?.check.default_only;
+    let hooks: Vec<(&str, String)> = vec![
+        ("commit-msg", COMMIT_MSG_HOOK.to_string()),
+        ("pre-commit", pre_commit_hook_script(&default_checks)),
+        ("pre-push", PRE_PUSH_HOOK.to_string()),
+    ];
+
+    for (name, script) in &hooks {
+        let path = hooks_dir.join(name);
+        if path.exists() {
+            if !force {
+                warn!("{name} hook already exists. Use --force to overwrite.");
+                continue;
+            }
+            let backup_path = path.with_extension("bak");
+            fs::copy(&path, &backup_path)?;
+            info!("Backed up existing {name} hook to {}", backup_path.display());
+        }
+
+        fs::write(&path, script)?;
+        let mut perms = fs::metadata(&path)?.permissions();
         perms.set_mode(0o755);
-        fs::set_permissions(&pre_push_path, perms)?;
-        info!("Installed pre-push hook");
+        fs::set_permissions(&path, perms)?;
+        info!("Installed {name} hook");
     }
-    
+
     info!("Git hooks installed successfully");
     Ok(())
+}
+
+/// Undo [`init_git_hooks`]: restore any `<hook>.bak` it created, or just remove the managed hook
+/// when there was nothing to restore.
+async fn uninstall_git_hooks() -> Result<()> {
+    use std::fs;
+    use std::path::Path;
+
+    info!("Uninstalling git hooks...");
+
+    let hooks_dir = Path::new(".git/hooks");
+    for name in MANAGED_HOOK_NAMES {
+        let path = hooks_dir.join(name);
+        let backup_path = path.with_extension("bak");
+
+        if backup_path.exists() {
+            fs::rename(&backup_path, &path)?;
+            info!("Restored original {name} hook from backup");
+        } else if path.exists() {
+            fs::remove_file(&path)?;
+            info!("Removed {name} hook");
+        }
+    }
+
+    info!("Git hooks uninstalled successfully");
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,175 @@
+//! GitHub release publishing
+//!
+//! `release::create_release` stops once local artifacts (changelog, notes, git tag) exist -- it
+//! never talks to GitHub. This fills that last step: given a tag, create the GitHub release via
+//! the REST API using the release notes [`crate::release::generate_release_notes_content`]
+//! already knows how to build, then upload named build artifacts to the release's upload URL.
+//!
+//! Transient HTTP failures (timeouts, connection resets) are retried rather than aborting the
+//! whole publish; classification of "transient" reuses [`hecate_ml::error::MLError::is_recoverable`]
+//! so the same recoverable/not-recoverable judgement this workspace already applies to HTTP calls
+//! elsewhere (`hecate-ml`'s distributed training RPCs) applies here too, rather than inventing a
+//! second notion of what counts as retryable.
+
+use anyhow::{bail, Context, Result};
+use colored::*;
+use hecate_ml::error::MLError;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// The repo a release is published to. HecateOS only ever publishes to itself, so this is a
+/// constant rather than a config option -- same convention as the download URL baked into
+/// [`crate::release::generate_release_notes_content`].
+const GITHUB_OWNER: &str = "Arakiss";
+const GITHUB_REPO: &str = "hecate-os";
+
+/// How many times to retry a request GitHub's API rejected with a recoverable error before
+/// giving up and surfacing it.
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize)]
+struct CreateReleaseRequest {
+    tag_name: String,
+    name: String,
+    body: String,
+    draft: bool,
+    prerelease: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateReleaseResponse {
+    id: u64,
+    upload_url: String,
+    html_url: String,
+}
+
+/// Create a GitHub release for `tag` (release notes are generated the same way
+/// `release notes` does), then upload each path in `artifacts` to it. Requires `GITHUB_TOKEN` in
+/// the environment.
+pub async fn publish_release(tag: &str, artifacts: &[String], draft: bool, prerelease: bool) -> Result<()> {
+    let token = std::env::var("GITHUB_TOKEN")
+        .context("GITHUB_TOKEN is not set; a personal access token with `repo` scope is required to publish releases")?;
+
+    let version = tag.trim_start_matches('v');
+    let notes = crate::release::generate_release_notes_content(version)?;
+
+    println!("{}", "Publishing GitHub release...".bold());
+    println!("  Tag: {}", tag.green());
+
+    let client = reqwest::Client::new();
+    let release = create_github_release(&client, &token, tag, &notes, draft, prerelease).await?;
+    println!("  {} Release created: {}", "✓".green(), release.html_url);
+
+    let upload_base = release.upload_url.split("{").next().unwrap_or(&release.upload_url).to_string();
+    for artifact in artifacts {
+        upload_artifact(&client, &token, &upload_base, artifact).await?;
+        println!("  {} Uploaded {}", "✓".green(), artifact);
+    }
+
+    println!(
+        "\n{}: release {} published ({} artifact{})",
+        "Success".green().bold(),
+        release.id,
+        artifacts.len(),
+        if artifacts.len() == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+async fn create_github_release(
+    client: &reqwest::Client,
+    token: &str,
+    tag: &str,
+    notes: &str,
+    draft: bool,
+    prerelease: bool,
+) -> Result<CreateReleaseResponse> {
+    let url = format!("https://api.github.com/repos/{GITHUB_OWNER}/{GITHUB_REPO}/releases");
+    let payload = CreateReleaseRequest {
+        tag_name: tag.to_string(),
+        name: tag.to_string(),
+        body: notes.to_string(),
+        draft,
+        prerelease,
+    };
+
+    let response = retry_on_recoverable(|| {
+        client
+            .post(&url)
+            .bearer_auth(token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "hecate-dev")
+            .json(&payload)
+            .send()
+    })
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        bail!("GitHub release creation failed ({status}): {body}");
+    }
+
+    response
+        .json()
+        .await
+        .context("GitHub release response malformed")
+}
+
+async fn upload_artifact(client: &reqwest::Client, token: &str, upload_base: &str, path: &str) -> Result<()> {
+    let file_path = Path::new(path);
+    let file_name = file_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .with_context(|| format!("artifact path has no file name: {path}"))?;
+    let bytes = std::fs::read(file_path).with_context(|| format!("failed to read artifact {path}"))?;
+    let url = format!("{upload_base}?name={file_name}");
+
+    let response = retry_on_recoverable(|| {
+        client
+            .post(&url)
+            .bearer_auth(token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "hecate-dev")
+            .header("Content-Type", "application/octet-stream")
+            .body(bytes.clone())
+            .send()
+    })
+    .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        bail!("uploading artifact {file_name} failed ({status}): {body}");
+    }
+
+    Ok(())
+}
+
+/// Run `request` up to [`MAX_ATTEMPTS`] times, retrying only when the failure is one
+/// [`MLError::is_recoverable`] would retry (timeouts, connection resets) rather than on
+/// unrecoverable failures like a bad token or malformed payload, which would just fail the same
+/// way again.
+async fn retry_on_recoverable<F, Fut>(mut request: F) -> Result<reqwest::Response>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<reqwest::Response, reqwest::Error>>,
+{
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match request().await {
+            Ok(response) => return Ok(response),
+            Err(e) => {
+                let error = MLError::from(e);
+                if attempt < MAX_ATTEMPTS && error.is_recoverable() {
+                    tokio::time::sleep(RETRY_DELAY).await;
+                    continue;
+                }
+                return Err(error).context("GitHub API request failed");
+            }
+        }
+    }
+}
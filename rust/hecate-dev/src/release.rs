@@ -1,8 +1,10 @@
+use crate::commit::VersionImpact;
 use anyhow::{Context, Result};
 use chrono::Utc;
 use colored::*;
 use regex::Regex;
 use semver::Version;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::process::Command;
@@ -11,6 +13,9 @@ pub async fn create_release(
     version: Option<&str>,
     skip_tests: bool,
     skip_changelog: bool,
+    changelog_template: Option<&str>,
+    release_notes_template: Option<&str>,
+    scope_filter: Option<&str>,
 ) -> Result<()> {
     println!("{}", "Creating new release...".bold());
     
@@ -42,18 +47,18 @@ pub async fn create_release(
     // Generate changelog unless skipped
     if !skip_changelog {
         println!("  Generating changelog...");
-        generate_changelog_file(&target_version)?;
+        generate_changelog_file(&target_version, changelog_template, scope_filter)?;
         println!("  {} Changelog generated", "✓".green());
     }
-    
+
     // Create git tag
     println!("  Creating git tag...");
     create_git_tag(&target_version)?;
     println!("  {} Tag created: v{}", "✓".green(), target_version);
-    
+
     // Generate release notes
     println!("  Generating release notes...");
-    let notes = generate_release_notes_content(&target_version)?;
+    let notes = generate_release_notes_content(&target_version, release_notes_template, scope_filter)?;
     
     // Save release notes
     let notes_path = format!("docs/releases/v{}.md", target_version);
@@ -73,72 +78,112 @@ pub async fn create_release(
     Ok(())
 }
 
-pub fn generate_changelog(range: Option<&str>, format: &str) -> Result<()> {
-    let range = range.unwrap_or("HEAD");
-    let commits = get_commits_in_range(range)?;
-    
-    let changelog = match format {
-        "markdown" => format_changelog_markdown(&commits),
-        "json" => format_changelog_json(&commits)?,
-        _ => anyhow::bail!("Unsupported format: {}", format),
+pub fn generate_changelog(
+    range: Option<&str>,
+    format: &str,
+    template_path: Option<&str>,
+    scope_filter: Option<&str>,
+) -> Result<()> {
+    let changelog = match range {
+        None => {
+            if format != "markdown" {
+                anyhow::bail!("Full-history changelog generation only supports the \"markdown\" format");
+            }
+            generate_full_changelog(template_path, scope_filter)?
+        }
+        Some(range) => {
+            let commits = get_commits_in_range(range)?;
+            let commits = filter_by_scope(commits, compile_scope_filter(scope_filter)?.as_ref());
+            match format {
+                "markdown" => {
+                    let remote = GitRemote::detect();
+                    format_changelog_markdown(&commits, "", &today(), template_path, remote.as_ref(), "")?
+                }
+                "json" => format_changelog_json(&commits)?,
+                _ => anyhow::bail!("Unsupported format: {}", format),
+            }
+        }
     };
-    
+
     println!("{}", changelog);
     Ok(())
 }
 
-pub fn generate_release_notes(version: Option<&str>) -> Result<()> {
+pub fn generate_release_notes(
+    version: Option<&str>,
+    template_path: Option<&str>,
+    scope_filter: Option<&str>,
+) -> Result<()> {
     let version = version.unwrap_or_else(|| {
         fs::read_to_string("VERSION")
             .unwrap_or_else(|_| "0.1.0".to_string())
             .trim()
             .to_string()
     });
-    
-    let notes = generate_release_notes_content(&version)?;
+
+    let notes = generate_release_notes_content(&version, template_path, scope_filter)?;
     println!("{}", notes);
     Ok(())
 }
 
+fn today() -> String {
+    Utc::now().format("%Y-%m-%d").to_string()
+}
+
 fn determine_next_version() -> Result<String> {
     let current = crate::version::read_version_file()?;
     let mut version = Version::parse(&current)?;
-    
+
     // Get commits since last tag
     let last_tag = get_last_tag()?;
     let commits = get_commits_in_range(&format!("{}..HEAD", last_tag))?;
-    
-    // Analyze commits to determine version bump
+
+    // Analyze commits to determine version bump: any `BREAKING CHANGE` always wins, otherwise the
+    // most severe `version_rules` impact among the commit types present wins.
+    let config = ChangelogConfig::load()?;
     let mut has_breaking = false;
-    let mut has_features = false;
-    let mut has_fixes = false;
-    
+    let mut impact = VersionImpact::None;
+
     for commit in &commits {
         if commit.breaking {
             has_breaking = true;
         }
-        match commit.commit_type.as_str() {
-            "feat" => has_features = true,
-            "fix" => has_fixes = true,
-            _ => {}
-        }
+        impact = most_severe(impact, config.version_impact(&commit.commit_type));
     }
-    
+
     if has_breaking {
         version.major += 1;
         version.minor = 0;
         version.patch = 0;
-    } else if has_features {
-        version.minor += 1;
-        version.patch = 0;
-    } else if has_fixes {
-        version.patch += 1;
+    } else {
+        match impact {
+            VersionImpact::Major => {
+                version.major += 1;
+                version.minor = 0;
+                version.patch = 0;
+            }
+            VersionImpact::Minor => {
+                version.minor += 1;
+                version.patch = 0;
+            }
+            VersionImpact::Patch => version.patch += 1,
+            VersionImpact::None => {}
+        }
     }
-    
+
     Ok(version.to_string())
 }
 
-fn run_tests() -> Result<()> {
+fn most_severe(a: VersionImpact, b: VersionImpact) -> VersionImpact {
+    match (a, b) {
+        (VersionImpact::Major, _) | (_, VersionImpact::Major) => VersionImpact::Major,
+        (VersionImpact::Minor, _) | (_, VersionImpact::Minor) => VersionImpact::Minor,
+        (VersionImpact::Patch, _) | (_, VersionImpact::Patch) => VersionImpact::Patch,
+        _ => VersionImpact::None,
+    }
+}
+
+pub(crate) fn run_tests() -> Result<()> {
     let output = Command::new("cargo")
         .args(&["test", "--workspace", "--quiet"])
         .output()
@@ -163,12 +208,12 @@ fn create_git_tag(version: &str) -> Result<()> {
     Ok(())
 }
 
-fn get_last_tag() -> Result<String> {
+pub(crate) fn get_last_tag() -> Result<String> {
     let output = Command::new("git")
         .args(&["describe", "--tags", "--abbrev=0"])
         .output()
         .context("Failed to get last tag")?;
-    
+
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     } else {
@@ -176,158 +221,576 @@ fn get_last_tag() -> Result<String> {
     }
 }
 
-#[derive(Debug)]
+/// Like [`get_last_tag`], but skips release-candidate tags (`v1.2.0-rc.1`) entirely and returns
+/// the most recent *final* release tag instead. Changelog and release-notes generation use this
+/// rather than `get_last_tag` so the accumulated range covers every commit since the last real
+/// release even when one or more rc tags sit in between -- otherwise release notes would be
+/// fragmented across candidates instead of rolled up into the release that finally ships them.
+pub(crate) fn get_last_final_tag() -> Result<String> {
+    let output = Command::new("git")
+        .args(&["tag", "--list", "v*", "--sort=-v:refname"])
+        .output()
+        .context("Failed to list git tags")?;
+
+    if !output.status.success() {
+        return Ok("HEAD~10".to_string());
+    }
+
+    let tags = String::from_utf8_lossy(&output.stdout);
+    match tags.lines().find(|tag| !tag.contains('-')) {
+        Some(tag) => Ok(tag.to_string()),
+        None => Ok("HEAD~10".to_string()),
+    }
+}
+
+#[derive(Debug, Serialize)]
 struct Commit {
     hash: String,
     commit_type: String,
     scope: Option<String>,
     description: String,
     breaking: bool,
+    /// The `BREAKING CHANGE:`/`BREAKING-CHANGE:` footer text, when the commit has one. A `!`
+    /// marker with no such footer leaves this `None` even though `breaking` is still `true`.
+    breaking_description: Option<String>,
+    /// Issue references (`#123`) pulled out of footer values like `Closes #123` or `Refs: #45`.
+    references: Vec<String>,
     author: String,
     date: String,
 }
 
-fn get_commits_in_range(range: &str) -> Result<Vec<Commit>> {
-    let output = Command::new("git")
-        .args(&[
-            "log",
-            range,
-            "--pretty=format:%H|%s|%an|%ad",
-            "--date=short",
-        ])
-        .output()
-        .context("Failed to get git log")?;
-    
+/// Runs `git log` with `extra_args` (a `<range>` positional, `--reverse`, or both) and parses the
+/// full message (header, body, footers) of every commit via [`crate::commit::parse_commit`] -- the
+/// same Conventional Commits grammar `hecate-dev commit` validates against -- instead of a
+/// subject-line-only regex, so `feat!:` markers and `BREAKING CHANGE:` footers are recognized
+/// instead of a crude `subject.contains("BREAKING")` check. Commits that don't parse (or use a
+/// type outside [`CommitConfig`]) are skipped. Returns each commit's full hash alongside the
+/// parsed [`Commit`] -- [`get_commits_in_range`] discards the full hash, [`generate_full_changelog`]
+/// needs it to match commits against tags.
+fn parse_commit_log(extra_args: &[&str]) -> Result<Vec<(String, Commit)>> {
+    let config = crate::commit::CommitConfig::load()?;
+
+    let mut args: Vec<&str> = vec!["log"];
+    args.extend_from_slice(extra_args);
+    args.push("--pretty=%H%x00%an%x00%ad%x00%B%x1e");
+    args.push("--date=short");
+
+    let output = Command::new("git").args(&args).output().context("Failed to get git log")?;
+
     let log = String::from_utf8_lossy(&output.stdout);
+    let issue_re = Regex::new(r"#\d+")?;
     let mut commits = Vec::new();
-    
-    let commit_re = Regex::new(
-        r"^([a-z]+)(?:\(([^)]+)\))?: (.+)$"
-    )?;
-    
-    for line in log.lines() {
-        let parts: Vec<&str> = line.split('|').collect();
-        if parts.len() != 4 {
+
+    for record in log.split('\u{1e}') {
+        // git emits a `\n` after every `%x1e` terminator, which lands as the leading character of
+        // the next record -- strip it before splitting on `\0` or it corrupts that record's hash.
+        let mut fields = record.trim_start_matches('\n').splitn(4, '\0');
+        let (Some(hash), Some(author), Some(date), Some(message)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if message.is_empty() {
             continue;
         }
-        
-        let hash = parts[0].to_string();
-        let subject = parts[1];
-        let author = parts[2].to_string();
-        let date = parts[3].to_string();
-        
-        if let Some(caps) = commit_re.captures(subject) {
-            commits.push(Commit {
-                hash: hash[..7].to_string(),
-                commit_type: caps[1].to_string(),
-                scope: caps.get(2).map(|m| m.as_str().to_string()),
-                description: caps[3].to_string(),
-                breaking: subject.contains("BREAKING"),
-                author,
-                date,
-            });
-        }
+
+        let Ok(parsed) = crate::commit::parse_commit(message, &config) else { continue };
+
+        let breaking_description = parsed
+            .footers
+            .iter()
+            .find(|(token, _)| token == "BREAKING CHANGE")
+            .map(|(_, value)| value.clone());
+        let references = parsed
+            .footers
+            .iter()
+            .flat_map(|(_, value)| issue_re.find_iter(value).map(|m| m.as_str().to_string()))
+            .collect();
+
+        commits.push((
+            hash.to_string(),
+            Commit {
+                hash: hash.chars().take(7).collect(),
+                commit_type: parsed.type_,
+                scope: parsed.scope,
+                description: parsed.description,
+                breaking: parsed.breaking,
+                breaking_description,
+                references,
+                author: author.to_string(),
+                date: date.to_string(),
+            },
+        ));
     }
-    
+
     Ok(commits)
 }
 
-fn format_changelog_markdown(commits: &[Commit]) -> String {
-    let mut grouped: HashMap<String, Vec<&Commit>> = HashMap::new();
-    
-    for commit in commits {
-        grouped
-            .entry(commit.commit_type.clone())
-            .or_default()
-            .push(commit);
+fn get_commits_in_range(range: &str) -> Result<Vec<Commit>> {
+    Ok(parse_commit_log(&[range])?.into_iter().map(|(_, commit)| commit).collect())
+}
+
+/// Compiles a `--scope` filter pattern once per call site, so a multi-bucket caller (like
+/// [`generate_full_changelog`]) doesn't recompile the same regex for every section.
+fn compile_scope_filter(pattern: Option<&str>) -> Result<Option<Regex>> {
+    pattern.map(Regex::new).transpose().context("Invalid scope filter regex")
+}
+
+/// Keeps only commits whose `scope` matches `scope_re` -- for generating a single component's
+/// changelog/release notes out of a monorepo. A commit with no scope never matches. `None` keeps
+/// everything.
+fn filter_by_scope(commits: Vec<Commit>, scope_re: Option<&Regex>) -> Vec<Commit> {
+    match scope_re {
+        None => commits,
+        Some(re) => commits
+            .into_iter()
+            .filter(|c| c.scope.as_deref().is_some_and(|s| re.is_match(s)))
+            .collect(),
     }
-    
-    let mut output = String::new();
-    
-    // Breaking changes
-    let breaking: Vec<&Commit> = commits.iter().filter(|c| c.breaking).collect();
-    if !breaking.is_empty() {
-        output.push_str("### ⚠️ BREAKING CHANGES\n\n");
-        for commit in breaking {
-            output.push_str(&format!(
-                "* {}{} ({})\n",
-                commit.scope.as_ref().map(|s| format!("**{}:** ", s)).unwrap_or_default(),
-                commit.description,
-                commit.hash
-            ));
+}
+
+/// Which forge's URL shape to render commit/issue/compare links with. `hecate-dev` only knows
+/// GitHub and GitLab's conventions; any other host falls back to plain, unlinked text rather than
+/// guessing at a URL shape that might be wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ForgeKind {
+    GitHub,
+    GitLab,
+}
+
+/// The `(host, owner, repo)` of `origin`, resolved to a browsable base URL plus which [`ForgeKind`]
+/// shape to render links with. Built once via [`GitRemote::detect`] and threaded down to whichever
+/// commit/changelog renders hash and issue references, rather than re-running `git remote` per
+/// commit.
+#[derive(Debug, Clone)]
+struct GitRemote {
+    base_url: String,
+    /// `"owner/repo"`, used to point the release-notes Installation section at the right
+    /// downloads instead of a hard-coded repo.
+    slug: String,
+    kind: ForgeKind,
+}
+
+impl GitRemote {
+    /// Reads `git remote get-url origin` and normalizes it into a [`GitRemote`]. Returns `None`
+    /// (never an error) when there's no `origin` remote or its host isn't a recognized forge, so
+    /// callers can fall back to plain text instead of failing changelog/release-notes generation
+    /// over a cosmetic feature.
+    fn detect() -> Option<Self> {
+        let output = Command::new("git").args(&["remote", "get-url", "origin"]).output().ok()?;
+        if !output.status.success() {
+            return None;
         }
-        output.push('\n');
+        Self::parse(String::from_utf8_lossy(&output.stdout).trim())
     }
-    
-    // Features
-    if let Some(features) = grouped.get("feat") {
-        output.push_str("### ✨ Features\n\n");
-        for commit in features {
-            output.push_str(&format!(
-                "* {}{} ({})\n",
-                commit.scope.as_ref().map(|s| format!("**{}:** ", s)).unwrap_or_default(),
-                commit.description,
-                commit.hash
-            ));
+
+    /// Normalizes the SSH (`git@host:owner/repo.git`) and HTTPS (`https://host/owner/repo(.git)`)
+    /// forms `git remote get-url` can return into a single shape.
+    fn parse(url: &str) -> Option<Self> {
+        let (host, path) = if let Some(rest) = url.strip_prefix("git@") {
+            rest.split_once(':')?
+        } else {
+            let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"))?;
+            rest.split_once('/')?
+        };
+        let path = path.trim_end_matches(".git").trim_end_matches('/');
+
+        let kind = match host {
+            "github.com" => ForgeKind::GitHub,
+            "gitlab.com" => ForgeKind::GitLab,
+            _ => return None,
+        };
+        Some(Self { base_url: format!("https://{host}/{path}"), slug: path.to_string(), kind })
+    }
+
+    fn commit_url(&self, hash: &str) -> String {
+        match self.kind {
+            ForgeKind::GitHub => format!("{}/commit/{}", self.base_url, hash),
+            ForgeKind::GitLab => format!("{}/-/commit/{}", self.base_url, hash),
         }
-        output.push('\n');
     }
-    
-    // Bug fixes
-    if let Some(fixes) = grouped.get("fix") {
-        output.push_str("### 🐛 Bug Fixes\n\n");
-        for commit in fixes {
-            output.push_str(&format!(
-                "* {}{} ({})\n",
-                commit.scope.as_ref().map(|s| format!("**{}:** ", s)).unwrap_or_default(),
-                commit.description,
-                commit.hash
-            ));
+
+    fn issue_url(&self, number: &str) -> String {
+        match self.kind {
+            ForgeKind::GitHub => format!("{}/issues/{}", self.base_url, number),
+            ForgeKind::GitLab => format!("{}/-/issues/{}", self.base_url, number),
         }
-        output.push('\n');
     }
-    
-    // Performance
-    if let Some(perfs) = grouped.get("perf") {
-        output.push_str("### ⚡ Performance\n\n");
-        for commit in perfs {
-            output.push_str(&format!(
-                "* {}{} ({})\n",
-                commit.scope.as_ref().map(|s| format!("**{}:** ", s)).unwrap_or_default(),
-                commit.description,
-                commit.hash
-            ));
+
+    fn compare_url(&self, from: &str, to: &str) -> String {
+        match self.kind {
+            ForgeKind::GitHub => format!("{}/compare/{}...{}", self.base_url, from, to),
+            ForgeKind::GitLab => format!("{}/-/compare/{}...{}", self.base_url, from, to),
         }
-        output.push('\n');
     }
-    
-    // Other changes
-    let other_types = vec!["docs", "style", "refactor", "test", "build", "ci", "chore"];
-    let mut has_other = false;
-    for commit_type in other_types {
-        if grouped.contains_key(commit_type) {
-            has_other = true;
-            break;
+}
+
+/// `"**Full Changelog**: <compare-url>"`, or empty when there's no remote to link against or no
+/// real previous tag to diff from (the very first release in a repo's history).
+fn render_compare_link(remote: Option<&GitRemote>, prev_tag: Option<&str>, this_tag: &str) -> String {
+    match (remote, prev_tag) {
+        (Some(remote), Some(prev)) => format!("**Full Changelog**: {}", remote.compare_url(prev, this_tag)),
+        _ => String::new(),
+    }
+}
+
+/// Maps each tagged commit's full hash to its tag name (`v1.2.3` -> `1.2.3`), via
+/// `git for-each-ref refs/tags`. Annotated tags (which [`create_git_tag`] creates) point at a tag
+/// object rather than the commit itself, so `%(*objectname)` (the dereferenced/peeled object) is
+/// preferred over `%(objectname)` when both are present; lightweight tags only ever populate the
+/// latter.
+fn tag_commit_map() -> Result<HashMap<String, String>> {
+    let output = Command::new("git")
+        .args(&["for-each-ref", "--format=%(objectname) %(*objectname) %(refname:short)", "refs/tags"])
+        .output()
+        .context("Failed to list git tags")?;
+
+    let mut map = HashMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let parts: Vec<&str> = line.splitn(3, ' ').collect();
+        let [object, peeled, tag] = parts[..] else { continue };
+        let commit_hash = if peeled.is_empty() { object } else { peeled };
+        map.insert(commit_hash.to_string(), tag.trim_start_matches('v').to_string());
+    }
+    Ok(map)
+}
+
+/// One release's worth of consecutive commits, in the order [`bucket_commits_by_tag`] closes them:
+/// the tag that closed it (`None` for the trailing bucket of commits after the last tag, rendered
+/// as "Unreleased"), the date to stamp the section with, and the commits in between.
+struct ReleaseBucket {
+    version: Option<String>,
+    date: String,
+    commits: Vec<Commit>,
+}
+
+/// Walks `commits` (must be oldest-first, i.e. from `parse_commit_log(&["--reverse"])`) and closes
+/// a [`ReleaseBucket`] every time it reaches a commit in `tags`. Whatever's left after the last tag
+/// becomes a final bucket with `version: None` ("Unreleased").
+fn bucket_commits_by_tag(commits: Vec<(String, Commit)>, tags: &HashMap<String, String>) -> Vec<ReleaseBucket> {
+    let mut buckets = Vec::new();
+    let mut pending = Vec::new();
+
+    for (hash, commit) in commits {
+        let date = commit.date.clone();
+        pending.push(commit);
+        if let Some(version) = tags.get(&hash) {
+            buckets.push(ReleaseBucket {
+                version: Some(version.clone()),
+                date,
+                commits: std::mem::take(&mut pending),
+            });
         }
     }
-    
-    if has_other {
-        output.push_str("### 📝 Other Changes\n\n");
-        for commit_type in other_types {
-            if let Some(commits) = grouped.get(commit_type) {
-                for commit in commits {
-                    output.push_str(&format!(
-                        "* {}{} ({})\n",
-                        commit.scope.as_ref().map(|s| format!("**{}:** ", s)).unwrap_or_default(),
-                        commit.description,
-                        commit.hash
-                    ));
+
+    if !pending.is_empty() {
+        let date = pending.last().map(|c| c.date.clone()).unwrap_or_else(today);
+        buckets.push(ReleaseBucket { version: None, date, commits: pending });
+    }
+
+    buckets
+}
+
+/// Builds a complete historical changelog in one pass: walks every commit reachable from `HEAD` in
+/// chronological order, opening a new `## [version] - date` section each time it reaches a tagged
+/// commit, with whatever's left after the last tag grouped under `## [Unreleased]`. Sections are
+/// emitted newest-first, matching `CHANGELOG.md`'s existing convention.
+fn generate_full_changelog(template_path: Option<&str>, scope_filter: Option<&str>) -> Result<String> {
+    let commits = parse_commit_log(&["--reverse"])?;
+    let tags = tag_commit_map()?;
+    let buckets = bucket_commits_by_tag(commits, &tags);
+    let scope_re = compile_scope_filter(scope_filter)?;
+    let remote = GitRemote::detect();
+
+    // Pair each bucket (still oldest-first here) with the tag that closed the release before it,
+    // so the "Full Changelog" compare link can be rendered once buckets are walked newest-first.
+    let mut prev_tag: Option<String> = None;
+    let mut with_prev_tag = Vec::with_capacity(buckets.len());
+    for bucket in buckets {
+        let this_tag = bucket.version.as_ref().map(|v| format!("v{v}"));
+        with_prev_tag.push((bucket, prev_tag.clone()));
+        if let Some(tag) = this_tag {
+            prev_tag = Some(tag);
+        }
+    }
+
+    let mut output = String::new();
+    for (bucket, prev_tag) in with_prev_tag.into_iter().rev() {
+        let version = bucket.version.clone().unwrap_or_else(|| "Unreleased".to_string());
+        let this_tag = bucket.version.as_deref().map(|v| format!("v{v}")).unwrap_or_else(|| "HEAD".to_string());
+        let compare_link = render_compare_link(remote.as_ref(), prev_tag.as_deref(), &this_tag);
+        let commits = filter_by_scope(bucket.commits, scope_re.as_ref());
+        output.push_str(&format!("## [{}] - {}\n\n", version, bucket.date));
+        output.push_str(&format_changelog_markdown(
+            &commits,
+            &version,
+            &bucket.date,
+            template_path,
+            remote.as_ref(),
+            &compare_link,
+        )?);
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Template context for changelog/release-notes rendering (see [`crate::template::render`]).
+/// Mirrors what a git-cliff-style template expects: release metadata plus commits bucketed into
+/// titled groups, so a custom template can reorder/relabel/drop sections without touching Rust.
+#[derive(Debug, Serialize)]
+struct Release {
+    version: String,
+    date: String,
+    groups: Vec<CommitGroup>,
+    /// `"**Full Changelog**: <compare-url>"`, or empty when there's no remote or no prior tag to
+    /// diff against -- see [`render_compare_link`].
+    compare_link: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CommitGroup {
+    title: String,
+    commits: Vec<ChangelogCommit>,
+}
+
+/// A single commit as exposed to a template. `scope_prefix` is pre-rendered (`"**scope:** "`, or
+/// empty when the commit has none) because the template engine has no `{% if %}` -- conditionals
+/// like "only show the scope when it's present" have to be resolved here instead. `hash_link` and
+/// `reference_links` are the same idea applied to remote hyperlinks: a Markdown link when
+/// [`GitRemote::detect`] found one, plain text otherwise.
+#[derive(Debug, Serialize)]
+struct ChangelogCommit {
+    hash: String,
+    /// `hash` as a Markdown link to the commit on the detected remote, or just `hash` again when
+    /// no remote was configured.
+    hash_link: String,
+    description: String,
+    author: String,
+    date: String,
+    breaking: bool,
+    scope_prefix: String,
+    /// `" -- {breaking_description}"`, or empty when the commit has no breaking-change footer
+    /// text -- same pre-rendered-for-the-template-engine approach as `scope_prefix`.
+    breaking_note: String,
+    references: Vec<String>,
+    /// Each entry of `references` (`"#123"`) as a Markdown link to the issue on the detected
+    /// remote, or the bare reference text again when no remote was configured.
+    reference_links: Vec<String>,
+}
+
+impl ChangelogCommit {
+    /// Assumes `commit.hash` is already the clean short hash [`parse_commit_log`] produces --
+    /// any stray whitespace in it would end up embedded in both the link text and the URL here.
+    fn from_commit(commit: &Commit, remote: Option<&GitRemote>) -> Self {
+        let hash_link = match remote {
+            Some(remote) => format!("[{}]({})", commit.hash, remote.commit_url(&commit.hash)),
+            None => commit.hash.clone(),
+        };
+        let reference_links = commit
+            .references
+            .iter()
+            .map(|reference| match remote {
+                Some(remote) => {
+                    let number = reference.trim_start_matches('#');
+                    format!("[{reference}]({})", remote.issue_url(number))
                 }
-            }
+                None => reference.clone(),
+            })
+            .collect();
+
+        Self {
+            hash: commit.hash.clone(),
+            hash_link,
+            description: commit.description.clone(),
+            author: commit.author.clone(),
+            date: commit.date.clone(),
+            breaking: commit.breaking,
+            scope_prefix: commit
+                .scope
+                .as_ref()
+                .map(|s| format!("**{}:** ", s))
+                .unwrap_or_default(),
+            breaking_note: commit
+                .breaking_description
+                .as_ref()
+                .map(|d| format!(" -- {d}"))
+                .unwrap_or_default(),
+            references: commit.references.clone(),
+            reference_links,
         }
     }
-    
-    output
+}
+
+/// One changelog section: which commit types roll up into it, its title and (optional) emoji, and
+/// whether it's omitted from the rendered changelog entirely.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangelogSection {
+    pub types: Vec<String>,
+    pub title: String,
+    #[serde(default)]
+    pub emoji: Option<String>,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl ChangelogSection {
+    /// The rendered section heading: `"{emoji} {title}"`, or just `title` when no emoji is set.
+    fn heading(&self) -> String {
+        match &self.emoji {
+            Some(emoji) => format!("{emoji} {}", self.title),
+            None => self.title.clone(),
+        }
+    }
+}
+
+/// Commit-type-to-section mapping and version-bump rules for changelog generation, loaded from
+/// the `[changelog]` table of [`crate::config::HecateDevConfig`]. Missing file means the built-in
+/// defaults (mirroring the historical hard-coded `format_changelog_markdown`/`determine_next_version`
+/// behavior) apply. Distinct from [`crate::commit::CommitConfig`], which governs which types
+/// `hecate-dev commit` accepts in the first place -- a type can be valid there and still be
+/// unmapped (and thus invisible) here, or vice versa.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangelogConfig {
+    #[serde(default = "ChangelogConfig::default_sections")]
+    pub sections: Vec<ChangelogSection>,
+    #[serde(default = "ChangelogConfig::default_version_rules")]
+    pub version_rules: HashMap<String, VersionImpact>,
+}
+
+impl ChangelogConfig {
+    /// Load the project's changelog conventions, falling back to [`ChangelogConfig::defaults`]
+    /// when there's no `hecate-dev.toml` (or it has no `[changelog]` table).
+    pub fn load() -> Result<Self> {
+        Ok(crate::config::HecateDevConfig::load()?.changelog)
+    }
+
+    /// Reproduces the historical hard-coded layout: dedicated sections for `feat`/`fix`/`perf`,
+    /// everything else (besides `revert`, historically dropped silently) lumped under "Other
+    /// Changes".
+    pub fn defaults() -> Self {
+        Self { sections: Self::default_sections(), version_rules: Self::default_version_rules() }
+    }
+
+    fn default_sections() -> Vec<ChangelogSection> {
+        vec![
+            ChangelogSection {
+                types: vec!["feat".to_string()],
+                title: "Features".to_string(),
+                emoji: Some("✨".to_string()),
+                hidden: false,
+            },
+            ChangelogSection {
+                types: vec!["fix".to_string()],
+                title: "Bug Fixes".to_string(),
+                emoji: Some("🐛".to_string()),
+                hidden: false,
+            },
+            ChangelogSection {
+                types: vec!["perf".to_string()],
+                title: "Performance".to_string(),
+                emoji: Some("⚡".to_string()),
+                hidden: false,
+            },
+            ChangelogSection {
+                types: ["docs", "style", "refactor", "test", "build", "ci", "chore"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                title: "Other Changes".to_string(),
+                emoji: Some("📝".to_string()),
+                hidden: false,
+            },
+        ]
+    }
+
+    /// `feat` drives a minor bump, `fix` a patch bump, matching `determine_next_version`'s
+    /// historical behavior. Anything absent from the map (including `perf`, despite
+    /// [`crate::commit::CommitConfig::defaults`] assigning it a patch impact for display purposes
+    /// in `hecate-dev commit conventions`) historically didn't move the version at all.
+    fn default_version_rules() -> HashMap<String, VersionImpact> {
+        HashMap::from([
+            ("feat".to_string(), VersionImpact::Minor),
+            ("fix".to_string(), VersionImpact::Patch),
+        ])
+    }
+
+    fn version_impact(&self, commit_type: &str) -> VersionImpact {
+        self.version_rules.get(commit_type).copied().unwrap_or(VersionImpact::None)
+    }
+}
+
+/// Buckets `commits` into the sections [`ChangelogConfig`] declares: breaking changes first
+/// (always, regardless of config -- a `BREAKING CHANGE` isn't a commit *type*), then one group per
+/// non-hidden [`ChangelogSection`] whose `types` matched at least one commit. `remote`, when
+/// present, is used to hyperlink each commit's hash and issue references.
+fn build_commit_groups(commits: &[Commit], config: &ChangelogConfig, remote: Option<&GitRemote>) -> Vec<CommitGroup> {
+    let mut groups = Vec::new();
+
+    let breaking: Vec<ChangelogCommit> = commits
+        .iter()
+        .filter(|c| c.breaking)
+        .map(|c| ChangelogCommit::from_commit(c, remote))
+        .collect();
+    if !breaking.is_empty() {
+        groups.push(CommitGroup { title: "⚠️ BREAKING CHANGES".to_string(), commits: breaking });
+    }
+
+    for section in &config.sections {
+        if section.hidden {
+            continue;
+        }
+        let matched: Vec<ChangelogCommit> = commits
+            .iter()
+            .filter(|c| section.types.iter().any(|t| t == &c.commit_type))
+            .map(|c| ChangelogCommit::from_commit(c, remote))
+            .collect();
+        if !matched.is_empty() {
+            groups.push(CommitGroup { title: section.heading(), commits: matched });
+        }
+    }
+
+    groups
+}
+
+/// Built-in changelog layout, used when no `--template` path is given. Reproduces the formatting
+/// the old hard-coded Rust formatter emitted, so it also doubles as the example a team copies to
+/// start writing its own.
+const DEFAULT_CHANGELOG_TEMPLATE: &str = "\
+{% for group in groups %}### {{ group.title }}
+
+{% for commit in group.commits %}* {{ commit.scope_prefix }}{{ commit.description }} ({{ commit.hash_link }}){{ commit.breaking_note }}
+{% endfor %}
+{% endfor %}{{ compare_link }}
+";
+
+/// Reads the template at `path`, or falls back to `default` when no path was given -- same
+/// graceful-fallback convention [`crate::config`] uses for `config/hecate/*.toml`.
+fn load_template(path: Option<&str>, default: &str) -> Result<String> {
+    match path {
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("Failed to read template file: {}", path)),
+        None => Ok(default.to_string()),
+    }
+}
+
+fn format_changelog_markdown(
+    commits: &[Commit],
+    version: &str,
+    date: &str,
+    template_path: Option<&str>,
+    remote: Option<&GitRemote>,
+    compare_link: &str,
+) -> Result<String> {
+    let template = load_template(template_path, DEFAULT_CHANGELOG_TEMPLATE)?;
+    let config = ChangelogConfig::load()?;
+    let context = Release {
+        version: version.to_string(),
+        date: date.to_string(),
+        groups: build_commit_groups(commits, &config, remote),
+        compare_link: compare_link.to_string(),
+    };
+    crate::template::render(&template, &context)
 }
 
 fn format_changelog_json(commits: &[Commit]) -> Result<String> {
@@ -335,23 +798,32 @@ fn format_changelog_json(commits: &[Commit]) -> Result<String> {
     Ok(json)
 }
 
-fn generate_changelog_file(version: &str) -> Result<()> {
+pub(crate) fn generate_changelog_file(
+    version: &str,
+    template_path: Option<&str>,
+    scope_filter: Option<&str>,
+) -> Result<()> {
     let changelog_path = "CHANGELOG.md";
     let existing = fs::read_to_string(changelog_path).unwrap_or_default();
-    
-    let last_tag = get_last_tag()?;
+
+    let last_tag = get_last_final_tag()?;
     let commits = get_commits_in_range(&format!("{}..HEAD", last_tag))?;
+    let commits = filter_by_scope(commits, compile_scope_filter(scope_filter)?.as_ref());
+    let date = today();
+    let remote = GitRemote::detect();
+    let prev_tag = (last_tag != "HEAD~10").then_some(last_tag.as_str());
+    let compare_link = render_compare_link(remote.as_ref(), prev_tag, &format!("v{version}"));
     let new_section = format!(
         "## [{}] - {}\n\n{}\n",
         version,
-        Utc::now().format("%Y-%m-%d"),
-        format_changelog_markdown(&commits)
+        date,
+        format_changelog_markdown(&commits, version, &date, template_path, remote.as_ref(), &compare_link)?
     );
-    
+
     // Insert new section after the title
-    let mut lines: Vec<&str> = existing.lines().collect();
+    let lines: Vec<&str> = existing.lines().collect();
     let insert_pos = lines.iter().position(|l| l.starts_with("## ")).unwrap_or(1);
-    
+
     let mut new_content = String::new();
     for (i, line) in lines.iter().enumerate() {
         if i == insert_pos {
@@ -360,26 +832,77 @@ fn generate_changelog_file(version: &str) -> Result<()> {
         new_content.push_str(line);
         new_content.push('\n');
     }
-    
+
     fs::write(changelog_path, new_content)?;
     Ok(())
 }
 
-fn generate_release_notes_content(version: &str) -> Result<String> {
-    let last_tag = get_last_tag()?;
+/// Template context for release notes: changelog [`Release`] fields plus a pre-rendered summary
+/// sentence and the contributor list, both of which involve counting/dedup logic the template
+/// engine can't do on its own.
+#[derive(Debug, Serialize)]
+struct ReleaseNotes {
+    version: String,
+    date: String,
+    groups: Vec<CommitGroup>,
+    summary: String,
+    contributors: Vec<String>,
+    /// `"**Full Changelog**: <compare-url>"`, or empty when there's no remote or no prior tag to
+    /// diff against -- see [`render_compare_link`].
+    compare_link: String,
+    /// `"owner/repo"` of the detected remote, or the historical hard-coded `"Arakiss/hecate-os"`
+    /// when there isn't one -- keeps the Installation section's download link pointed at the
+    /// right repo without regressing the default for projects with no `origin` configured.
+    repo_slug: String,
+}
+
+/// Built-in release-notes layout, used when no `--template` path is given. Reproduces the
+/// sections the old hard-coded Rust formatter emitted (summary, changelog, installation,
+/// contributors), so a custom template can override any one of them piecemeal by starting here.
+const DEFAULT_RELEASE_NOTES_TEMPLATE: &str = r#"# Release v{{ version }}
+
+Released: {{ date }}
+
+## Summary
+
+{{ summary }}
+
+## Changelog
+
+{% for group in groups %}### {{ group.title }}
+
+{% for commit in group.commits %}* {{ commit.scope_prefix }}{{ commit.description }} ({{ commit.hash_link }}){{ commit.breaking_note }}
+{% endfor %}
+{% endfor %}{{ compare_link }}
+
+## Installation
+
+```bash
+# Download the ISO
+wget https://github.com/{{ repo_slug }}/releases/download/v{{ version }}/hecate-os-{{ version }}.iso
+
+# Or update existing installation
+hecate-pkg update && hecate-pkg upgrade
+```
+
+## Contributors
+
+{% for contributor in contributors %}* {{ contributor }}
+{% endfor %}"#;
+
+pub(crate) fn generate_release_notes_content(
+    version: &str,
+    template_path: Option<&str>,
+    scope_filter: Option<&str>,
+) -> Result<String> {
+    let last_tag = get_last_final_tag()?;
     let commits = get_commits_in_range(&format!("{}..HEAD", last_tag))?;
-    
-    let mut notes = format!("# Release v{}\n\n", version);
-    notes.push_str(&format!("Released: {}\n\n", Utc::now().format("%Y-%m-%d")));
-    
-    // Summary
-    notes.push_str("## Summary\n\n");
-    notes.push_str("This release includes ");
-    
+    let commits = filter_by_scope(commits, compile_scope_filter(scope_filter)?.as_ref());
+
     let features = commits.iter().filter(|c| c.commit_type == "feat").count();
     let fixes = commits.iter().filter(|c| c.commit_type == "fix").count();
     let breaking = commits.iter().filter(|c| c.breaking).count();
-    
+
     let mut summary_parts = Vec::new();
     if features > 0 {
         summary_parts.push(format!("{} new feature{}", features, if features > 1 { "s" } else { "" }));
@@ -390,37 +913,32 @@ fn generate_release_notes_content(version: &str) -> Result<String> {
     if breaking > 0 {
         summary_parts.push(format!("{} breaking change{}", breaking, if breaking > 1 { "s" } else { "" }));
     }
-    
-    notes.push_str(&summary_parts.join(", "));
-    notes.push_str(".\n\n");
-    
-    // Changelog
-    notes.push_str("## Changelog\n\n");
-    notes.push_str(&format_changelog_markdown(&commits));
-    
-    // Installation
-    notes.push_str("## Installation\n\n");
-    notes.push_str("```bash\n");
-    notes.push_str("# Download the ISO\n");
-    notes.push_str(&format!("wget https://github.com/Arakiss/hecate-os/releases/download/v{}/hecate-os-{}.iso\n", version, version));
-    notes.push_str("\n");
-    notes.push_str("# Or update existing installation\n");
-    notes.push_str("hecate-pkg update && hecate-pkg upgrade\n");
-    notes.push_str("```\n\n");
-    
-    // Contributors
+    let summary = format!("This release includes {}.", summary_parts.join(", "));
+
     let contributors: Vec<String> = commits
         .iter()
         .map(|c| c.author.clone())
         .collect::<std::collections::HashSet<_>>()
         .into_iter()
         .collect();
-    
-    notes.push_str("## Contributors\n\n");
-    for contributor in contributors {
-        notes.push_str(&format!("* {}\n", contributor));
-    }
-    
-    Ok(notes)
+
+    let remote = GitRemote::detect();
+    let prev_tag = (last_tag != "HEAD~10").then_some(last_tag.as_str());
+    let compare_link = render_compare_link(remote.as_ref(), prev_tag, &format!("v{version}"));
+    let repo_slug = remote.as_ref().map(|r| r.slug.clone()).unwrap_or_else(|| "Arakiss/hecate-os".to_string());
+
+    let config = ChangelogConfig::load()?;
+    let context = ReleaseNotes {
+        version: version.to_string(),
+        date: today(),
+        groups: build_commit_groups(&commits, &config, remote.as_ref()),
+        summary,
+        contributors,
+        compare_link,
+        repo_slug,
+    };
+
+    let template = load_template(template_path, DEFAULT_RELEASE_NOTES_TEMPLATE)?;
+    crate::template::render(&template, &context)
 }
 
@@ -0,0 +1,379 @@
+//! Pluggable per-ecosystem version rewriters
+//!
+//! `version::sync_version`/`version::check_version_sync` used to know only about this workspace's
+//! own `Cargo.toml` files and the dashboard's `package.json`. A [`Rewriter`] locates every
+//! manifest of one ecosystem under the repo root and can read or write the version field in its
+//! format, so a HecateOS project that ships a Python binding or an npm wrapper alongside the Rust
+//! crate keeps every version string in lockstep from one `hecate-dev version sync`.
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{value, Document, Item};
+use walkdir::WalkDir;
+
+/// Locates and rewrites the version field of one manifest ecosystem
+pub trait Rewriter {
+    /// Human-readable ecosystem name, shown in `version check`'s per-file report
+    fn name(&self) -> &'static str;
+
+    /// Every manifest of this ecosystem found under `root`
+    fn discover(&self, root: &Path) -> Vec<PathBuf>;
+
+    /// The version string currently in `path`
+    fn read_version(&self, path: &Path) -> Result<String>;
+
+    /// Write `version` into `path`
+    fn write_version(&self, path: &Path, version: &str) -> Result<()>;
+}
+
+/// All built-in rewriters, in the order `version sync`/`version check` apply them.
+pub fn all_rewriters() -> Vec<Box<dyn Rewriter>> {
+    vec![
+        Box::new(CargoRewriter),
+        Box::new(NpmRewriter),
+        Box::new(PythonRewriter),
+        Box::new(DotnetRewriter),
+    ]
+}
+
+/// Build artifacts, VCS metadata, and vendored/installed dependencies that happen to carry their
+/// own manifests -- none of these should ever be rewritten.
+fn is_ignored_dir(path: &Path) -> bool {
+    path.components().any(|c| {
+        matches!(
+            c.as_os_str().to_str(),
+            Some("target" | "node_modules" | ".git" | "venv" | ".venv" | "__pycache__" | "bin" | "obj")
+        )
+    })
+}
+
+fn discover_named(root: &Path, file_name: &str) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_type().is_file()
+                && e.file_name() == file_name
+                && !is_ignored_dir(e.path())
+        })
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+struct CargoRewriter;
+
+impl Rewriter for CargoRewriter {
+    fn name(&self) -> &'static str {
+        "Cargo"
+    }
+
+    fn discover(&self, root: &Path) -> Vec<PathBuf> {
+        discover_named(root, "Cargo.toml")
+    }
+
+    fn read_version(&self, path: &Path) -> Result<String> {
+        let content = fs::read_to_string(path)?;
+        let doc = content.parse::<Document>()?;
+
+        if let Some(v) = doc
+            .get("workspace")
+            .and_then(|w| w.get("package"))
+            .and_then(|p| p.get("version"))
+            .and_then(|v| v.as_str())
+        {
+            return Ok(v.to_string());
+        }
+        if let Some(v) = doc.get("package").and_then(|p| p.get("version")).and_then(|v| v.as_str()) {
+            return Ok(v.to_string());
+        }
+
+        bail!("no version field in {}", path.display())
+    }
+
+    fn write_version(&self, path: &Path, version: &str) -> Result<()> {
+        let content = fs::read_to_string(path)?;
+        let mut doc = content.parse::<Document>()?;
+        let mut wrote = false;
+
+        if let Some(Item::Table(package)) = doc.get_mut("workspace").and_then(|w| w.get_mut("package")) {
+            if package.contains_key("version") {
+                package["version"] = value(version);
+                wrote = true;
+            }
+        }
+        if let Some(Item::Table(package)) = doc.get_mut("package") {
+            if package.contains_key("version") {
+                package["version"] = value(version);
+                wrote = true;
+            }
+        }
+
+        if !wrote {
+            bail!("no version field in {}", path.display());
+        }
+        fs::write(path, doc.to_string())?;
+        Ok(())
+    }
+}
+
+struct NpmRewriter;
+
+impl Rewriter for NpmRewriter {
+    fn name(&self) -> &'static str {
+        "npm"
+    }
+
+    fn discover(&self, root: &Path) -> Vec<PathBuf> {
+        discover_named(root, "package.json")
+    }
+
+    fn read_version(&self, path: &Path) -> Result<String> {
+        let content = fs::read_to_string(path)?;
+        let manifest: serde_json::Value = serde_json::from_str(&content)?;
+        manifest
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+            .with_context(|| format!("no version field in {}", path.display()))
+    }
+
+    fn write_version(&self, path: &Path, version: &str) -> Result<()> {
+        let content = fs::read_to_string(path)?;
+        let mut manifest: serde_json::Value = serde_json::from_str(&content)?;
+        let obj = manifest
+            .as_object_mut()
+            .with_context(|| format!("{} is not a JSON object", path.display()))?;
+        if !obj.contains_key("version") {
+            bail!("no version field in {}", path.display());
+        }
+        obj.insert("version".to_string(), serde_json::Value::String(version.to_string()));
+        fs::write(path, serde_json::to_string_pretty(&manifest)?)?;
+        Ok(())
+    }
+}
+
+/// Python projects declare their version either in `pyproject.toml`'s `[project]` (PEP 621) or
+/// `[tool.poetry]` table, or -- for older setuptools projects -- a `version = ...` line under
+/// `[metadata]` in `setup.cfg`.
+struct PythonRewriter;
+
+impl Rewriter for PythonRewriter {
+    fn name(&self) -> &'static str {
+        "Python"
+    }
+
+    fn discover(&self, root: &Path) -> Vec<PathBuf> {
+        let mut manifests = discover_named(root, "pyproject.toml");
+        manifests.extend(discover_named(root, "setup.cfg"));
+        manifests
+    }
+
+    fn read_version(&self, path: &Path) -> Result<String> {
+        if path.file_name().and_then(|n| n.to_str()) == Some("setup.cfg") {
+            read_setup_cfg_version(path)
+        } else {
+            read_pyproject_version(path)
+        }
+    }
+
+    fn write_version(&self, path: &Path, version: &str) -> Result<()> {
+        if path.file_name().and_then(|n| n.to_str()) == Some("setup.cfg") {
+            write_setup_cfg_version(path, version)
+        } else {
+            write_pyproject_version(path, version)
+        }
+    }
+}
+
+fn read_pyproject_version(path: &Path) -> Result<String> {
+    let content = fs::read_to_string(path)?;
+    let doc = content.parse::<Document>()?;
+
+    if let Some(v) = doc.get("project").and_then(|p| p.get("version")).and_then(|v| v.as_str()) {
+        return Ok(v.to_string());
+    }
+    if let Some(v) = doc
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str())
+    {
+        return Ok(v.to_string());
+    }
+
+    bail!("no version field in {}", path.display())
+}
+
+fn write_pyproject_version(path: &Path, version: &str) -> Result<()> {
+    let content = fs::read_to_string(path)?;
+    let mut doc = content.parse::<Document>()?;
+    let mut wrote = false;
+
+    if let Some(Item::Table(project)) = doc.get_mut("project") {
+        if project.contains_key("version") {
+            project["version"] = value(version);
+            wrote = true;
+        }
+    }
+    if let Some(Item::Table(poetry)) = doc.get_mut("tool").and_then(|t| t.get_mut("poetry")) {
+        if poetry.contains_key("version") {
+            poetry["version"] = value(version);
+            wrote = true;
+        }
+    }
+
+    if !wrote {
+        bail!("no version field in {}", path.display());
+    }
+    fs::write(path, doc.to_string())?;
+    Ok(())
+}
+
+fn setup_cfg_version_re() -> Result<Regex> {
+    Ok(Regex::new(r"(?m)^(\s*version\s*=\s*)(\S+)\s*$")?)
+}
+
+fn read_setup_cfg_version(path: &Path) -> Result<String> {
+    let content = fs::read_to_string(path)?;
+    setup_cfg_version_re()?
+        .captures(&content)
+        .map(|c| c[2].to_string())
+        .with_context(|| format!("no version field in {}", path.display()))
+}
+
+fn write_setup_cfg_version(path: &Path, version: &str) -> Result<()> {
+    let content = fs::read_to_string(path)?;
+    let re = setup_cfg_version_re()?;
+    if !re.is_match(&content) {
+        bail!("no version field in {}", path.display());
+    }
+    let updated = re.replace(&content, |caps: &regex::Captures| format!("{}{version}", &caps[1]));
+    fs::write(path, updated.as_ref())?;
+    Ok(())
+}
+
+/// MSBuild project files declare their version as a `<Version>` element, usually inside a
+/// `<PropertyGroup>`. Rewritten with a regex rather than pulling in an XML crate for this one
+/// narrow need.
+struct DotnetRewriter;
+
+impl Rewriter for DotnetRewriter {
+    fn name(&self) -> &'static str {
+        ".NET"
+    }
+
+    fn discover(&self, root: &Path) -> Vec<PathBuf> {
+        WalkDir::new(root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_type().is_file()
+                    && e.path().extension().map(|ext| ext == "csproj").unwrap_or(false)
+                    && !is_ignored_dir(e.path())
+            })
+            .map(|e| e.path().to_path_buf())
+            .collect()
+    }
+
+    fn read_version(&self, path: &Path) -> Result<String> {
+        let content = fs::read_to_string(path)?;
+        dotnet_version_re()?
+            .captures(&content)
+            .map(|c| c[1].to_string())
+            .with_context(|| format!("no <Version> element in {}", path.display()))
+    }
+
+    fn write_version(&self, path: &Path, version: &str) -> Result<()> {
+        let content = fs::read_to_string(path)?;
+        let re = dotnet_version_re()?;
+        if !re.is_match(&content) {
+            bail!("no <Version> element in {}", path.display());
+        }
+        let updated = re.replace(&content, format!("<Version>{version}</Version>"));
+        fs::write(path, updated.as_ref())?;
+        Ok(())
+    }
+}
+
+fn dotnet_version_re() -> Result<Regex> {
+    Ok(Regex::new(r"<Version>([^<]+)</Version>")?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_temp(name: &str, contents: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = fs::File::create(dir.path().join(name)).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        dir
+    }
+
+    #[test]
+    fn cargo_rewriter_round_trips_workspace_version() {
+        let dir = write_temp(
+            "Cargo.toml",
+            "[workspace.package]\nversion = \"0.1.0\"\n",
+        );
+        let path = dir.path().join("Cargo.toml");
+
+        let rewriter = CargoRewriter;
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.1.0");
+        rewriter.write_version(&path, "0.2.0").unwrap();
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.2.0");
+    }
+
+    #[test]
+    fn npm_rewriter_round_trips_version() {
+        let dir = write_temp("package.json", r#"{"name": "hecate-wrapper", "version": "0.1.0"}"#);
+        let path = dir.path().join("package.json");
+
+        let rewriter = NpmRewriter;
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.1.0");
+        rewriter.write_version(&path, "0.2.0").unwrap();
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.2.0");
+    }
+
+    #[test]
+    fn python_rewriter_handles_pep621_and_poetry() {
+        let dir = write_temp("pyproject.toml", "[project]\nversion = \"0.1.0\"\n");
+        let path = dir.path().join("pyproject.toml");
+        let rewriter = PythonRewriter;
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.1.0");
+        rewriter.write_version(&path, "0.2.0").unwrap();
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.2.0");
+
+        let dir = write_temp("pyproject.toml", "[tool.poetry]\nversion = \"0.1.0\"\n");
+        let path = dir.path().join("pyproject.toml");
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.1.0");
+        rewriter.write_version(&path, "0.3.0").unwrap();
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.3.0");
+    }
+
+    #[test]
+    fn python_rewriter_handles_setup_cfg() {
+        let dir = write_temp("setup.cfg", "[metadata]\nname = hecate-wrapper\nversion = 0.1.0\n");
+        let path = dir.path().join("setup.cfg");
+        let rewriter = PythonRewriter;
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.1.0");
+        rewriter.write_version(&path, "0.2.0").unwrap();
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.2.0");
+    }
+
+    #[test]
+    fn dotnet_rewriter_round_trips_version() {
+        let dir = write_temp(
+            "hecate.csproj",
+            "<Project><PropertyGroup><Version>0.1.0</Version></PropertyGroup></Project>",
+        );
+        let path = dir.path().join("hecate.csproj");
+        let rewriter = DotnetRewriter;
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.1.0");
+        rewriter.write_version(&path, "0.2.0").unwrap();
+        assert_eq!(rewriter.read_version(&path).unwrap(), "0.2.0");
+    }
+}
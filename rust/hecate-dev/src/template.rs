@@ -0,0 +1,169 @@
+//! A minimal Tera-style template engine: `{{ field.path }}` interpolation (optionally piped
+//! through filters, e.g. `{{ title | upper_first }}`) and `{% for x in path.to.list %} ... {%
+//! endfor %}` loops, nesting included. This is not a general-purpose templating language -- it's
+//! just enough syntax for changelog/release-notes templates to become data, instead of formatting
+//! baked into Rust `format!` calls.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Renders `template` against `context` (anything `Serialize`, round-tripped through
+/// `serde_json::Value` so dotted-path lookups work the same regardless of the concrete type).
+pub fn render(template: &str, context: &impl Serialize) -> Result<String> {
+    let context = serde_json::to_value(context).context("Failed to serialize template context")?;
+    render_in_scope(template, &context)
+}
+
+fn render_in_scope(template: &str, scope: &Value) -> Result<String> {
+    let mut output = String::new();
+    let mut rest = template;
+
+    loop {
+        let next_tag = rest.find("{%");
+        let next_expr = rest.find("{{");
+
+        let start = match (next_tag, next_expr) {
+            (Some(t), Some(e)) => t.min(e),
+            (Some(t), None) => t,
+            (None, Some(e)) => e,
+            (None, None) => break,
+        };
+
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("{%") {
+            let (tag, after) = take_tag(rest, "%}")?;
+            let header = tag
+                .strip_prefix("for ")
+                .ok_or_else(|| anyhow!("Unsupported template tag: {{% {} %}}", tag))?;
+            let (var, list_path) = parse_for_header(header)?;
+            let (body, after_loop) = take_for_block(after)?;
+
+            let items = lookup(scope, &list_path)
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            for item in items {
+                let mut item_scope = scope.clone();
+                if let Value::Object(map) = &mut item_scope {
+                    map.insert(var.clone(), item);
+                }
+                output.push_str(&render_in_scope(body, &item_scope)?);
+            }
+
+            rest = after_loop;
+        } else {
+            let (expr, after) = take_tag(rest, "}}")?;
+            output.push_str(&render_expr(&expr, scope)?);
+            rest = after;
+        }
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Splits `s` (which must start with `"{%"` or `"{{"`) into the trimmed tag contents and the
+/// remainder of the template following the matching `close` delimiter.
+fn take_tag<'a>(s: &'a str, close: &str) -> Result<(String, &'a str)> {
+    let end = s.find(close).ok_or_else(|| anyhow!("Unterminated template tag: {}", s))?;
+    let tag = s[2..end].trim().to_string();
+    Ok((tag, &s[end + close.len()..]))
+}
+
+/// Parses a `{% for %}` header's `"item in path.to.list"` into `(item, path.to.list)`.
+fn parse_for_header(header: &str) -> Result<(String, String)> {
+    let mut parts = header.splitn(2, " in ");
+    let var = parts.next().unwrap_or_default().trim();
+    let path = parts
+        .next()
+        .ok_or_else(|| anyhow!("Malformed for loop header (expected \"item in list\"): {}", header))?
+        .trim();
+    if var.is_empty() {
+        bail!("Malformed for loop header (missing loop variable): {}", header);
+    }
+    Ok((var.to_string(), path.to_string()))
+}
+
+/// Finds the `{% endfor %}` matching the `{% for %}` whose body starts at `after` (nested loops
+/// are depth-counted so an inner `{% for %}...{% endfor %}` doesn't end the outer one early).
+fn take_for_block(after: &str) -> Result<(&str, &str)> {
+    const OPEN: &str = "{% for ";
+    const CLOSE: &str = "{% endfor %}";
+
+    let mut depth = 1usize;
+    let mut cursor = 0usize;
+
+    loop {
+        let next_open = after[cursor..].find(OPEN).map(|p| cursor + p);
+        let next_close = after[cursor..].find(CLOSE).map(|p| cursor + p);
+
+        match (next_open, next_close) {
+            (Some(open), Some(close)) if open < close => {
+                depth += 1;
+                cursor = open + OPEN.len();
+            }
+            (_, Some(close)) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok((&after[..close], &after[close + CLOSE.len()..]));
+                }
+                cursor = close + CLOSE.len();
+            }
+            _ => bail!("Missing {{% endfor %}} for a {{% for %}} block"),
+        }
+    }
+}
+
+/// Looks up a dotted field path (`"group.title"`) against a JSON object scope.
+fn lookup<'a>(scope: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = scope;
+    for segment in path.split('.') {
+        current = current.as_object()?.get(segment)?;
+    }
+    Some(current)
+}
+
+/// Renders a `{{ ... }}` expression: a dotted path, optionally piped through one or more filters
+/// (`{{ commit.title | upper_first }}`).
+fn render_expr(expr: &str, scope: &Value) -> Result<String> {
+    let mut segments = expr.split('|');
+    let path = segments.next().unwrap_or_default().trim();
+    let value = lookup(scope, path).cloned().unwrap_or(Value::Null);
+    let mut rendered = value_to_string(&value);
+
+    for filter in segments {
+        rendered = apply_filter(filter.trim(), &rendered)?;
+    }
+
+    Ok(rendered)
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn apply_filter(name: &str, input: &str) -> Result<String> {
+    match name {
+        "upper_first" => Ok(upper_first(input)),
+        "upper" => Ok(input.to_uppercase()),
+        "lower" => Ok(input.to_lowercase()),
+        "trim" => Ok(input.trim().to_string()),
+        other => bail!("Unknown template filter: {}", other),
+    }
+}
+
+fn upper_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
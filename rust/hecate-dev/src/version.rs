@@ -4,7 +4,7 @@ use colored::*;
 use semver::Version;
 use std::fs;
 use std::path::Path;
-use toml_edit::{Document, Item};
+use toml_edit::Document;
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum BumpLevel {
@@ -14,6 +14,21 @@ pub enum BumpLevel {
     Prerelease,
 }
 
+/// Identifier [`stage_prerelease`] and the bare `Prerelease` bump use for a first release
+/// candidate -- `rc.1`, incrementing to `rc.2`, `rc.3`, ... on each repeat.
+const DEFAULT_PRERELEASE_IDENTIFIER: &str = "rc";
+
+/// Given an existing prerelease tag (possibly empty), return the next one: `rc.N+1` when `pre`
+/// already looks like `<identifier>.<N>`, otherwise a fresh `<identifier>.1`.
+fn next_prerelease(pre: &semver::Prerelease, identifier: &str) -> Result<semver::Prerelease> {
+    if let Some(num_str) = pre.as_str().strip_prefix(&format!("{identifier}.")) {
+        if let Ok(num) = num_str.parse::<u32>() {
+            return Ok(semver::Prerelease::new(&format!("{identifier}.{}", num + 1))?);
+        }
+    }
+    Ok(semver::Prerelease::new(&format!("{identifier}.1"))?)
+}
+
 pub fn show_version() -> Result<()> {
     let version = read_version_file()?;
     let cargo_version = read_cargo_version()?;
@@ -28,42 +43,49 @@ pub fn show_version() -> Result<()> {
     Ok(())
 }
 
-pub fn bump_version(level: BumpLevel, dry_run: bool) -> Result<()> {
+/// Bump the version by an explicit `level`, or, when `None`, derive the level automatically from
+/// commits since the last tag via [`compute_auto_level`] -- the same classification
+/// [`crate::commit::generate_changelog`] uses, so the bump level and the changelog it produces
+/// never disagree. When auto-deriving, `--dry-run` prints the commit that drove the choice; when
+/// no commit since the last tag qualifies for a bump, this prints a message and returns `Ok(())`
+/// rather than erroring.
+pub fn bump_version(level: Option<BumpLevel>, dry_run: bool) -> Result<()> {
     let current = read_version_file()?;
     let mut version = Version::parse(&current)?;
-    
-    match level {
-        BumpLevel::Major => {
-            version.major += 1;
-            version.minor = 0;
-            version.patch = 0;
-            version.pre = semver::Prerelease::EMPTY;
-        }
-        BumpLevel::Minor => {
-            version.minor += 1;
-            version.patch = 0;
-            version.pre = semver::Prerelease::EMPTY;
-        }
-        BumpLevel::Patch => {
-            version.patch += 1;
-            version.pre = semver::Prerelease::EMPTY;
-        }
-        BumpLevel::Prerelease => {
-            if version.pre.is_empty() {
-                version.pre = semver::Prerelease::new("alpha.1")?;
-            } else {
-                // Increment prerelease version
-                let pre_str = version.pre.as_str();
-                if let Some(pos) = pre_str.rfind('.') {
-                    let (prefix, num_str) = pre_str.split_at(pos);
-                    if let Ok(num) = num_str[1..].parse::<u32>() {
-                        version.pre = semver::Prerelease::new(&format!("{}.{}", prefix, num + 1))?;
-                    }
+
+    let level = match level {
+        Some(level) => level,
+        None => {
+            let since = crate::release::get_last_tag()?;
+            match compute_auto_level(&since)? {
+                Some((level, commit)) => {
+                    println!(
+                        "{}: {:?} (triggered by {} {})",
+                        "Auto-detected bump level".bold(),
+                        level,
+                        &commit.hash[..7.min(commit.hash.len())],
+                        commit.subject
+                    );
+                    level
+                }
+                None => {
+                    println!(
+                        "{}: no commits since {since} qualify for a version bump",
+                        "Nothing to do".green().bold()
+                    );
+                    return Ok(());
                 }
             }
         }
+    };
+
+    match level {
+        BumpLevel::Prerelease => {
+            version.pre = next_prerelease(&version.pre, DEFAULT_PRERELEASE_IDENTIFIER)?;
+        }
+        level => apply_bump(&mut version, level)?,
     }
-    
+
     let new_version = version.to_string();
     
     if dry_run {
@@ -85,6 +107,111 @@ pub fn bump_version(level: BumpLevel, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
+/// Derive the next version from every commit since `since_tag` (or the last annotated tag, when
+/// `None`), rather than from a single commit: the level is the *highest* of whatever the whole
+/// range contains, per each commit's [`crate::commit::VersionImpact`] (itself overridable via
+/// `hecate-dev.toml`). Any `BREAKING CHANGE:` footer or `!` marker makes it Major regardless of the
+/// commit's own configured impact. Bails with "no release needed" when nothing in the range
+/// qualifies for a bump.
+///
+/// Classify every commit since `since_tag` via [`crate::commit::CommitConfig`] -- the same
+/// classification [`crate::commit::generate_changelog`] uses -- and pick the highest applicable
+/// bump level: any `breaking` commit wins outright as Major, otherwise the highest
+/// [`crate::commit::VersionImpact`] among the rest decides Minor vs. Patch. Returns the commit that
+/// drove the decision alongside the level, or `None` when no commit in the range qualifies.
+fn compute_auto_level(since_tag: &str) -> Result<Option<(BumpLevel, crate::commit::ChangelogCommit)>> {
+    let config = crate::commit::CommitConfig::load()?;
+    let commits = crate::commit::parse_commit_range(since_tag, "HEAD", &config)?;
+
+    if let Some(commit) = commits.iter().find(|c| c.breaking) {
+        return Ok(Some((BumpLevel::Major, commit.clone())));
+    }
+    if let Some(commit) = commits
+        .iter()
+        .find(|c| config.version_impact(&c.commit_type) == crate::commit::VersionImpact::Minor)
+    {
+        return Ok(Some((BumpLevel::Minor, commit.clone())));
+    }
+    if let Some(commit) = commits
+        .iter()
+        .find(|c| config.version_impact(&c.commit_type) == crate::commit::VersionImpact::Patch)
+    {
+        return Ok(Some((BumpLevel::Patch, commit.clone())));
+    }
+
+    Ok(None)
+}
+
+/// While the current major version is still `0`, a breaking change bumps minor instead of major
+/// -- the usual pre-1.0 convention that major stays pinned at 0 until the API is declared stable.
+pub fn compute_bump(since_tag: Option<&str>) -> Result<(Version, BumpLevel)> {
+    let current = read_version_file()?;
+    let mut version = Version::parse(&current)?;
+
+    let since = match since_tag {
+        Some(tag) => tag.to_string(),
+        None => crate::release::get_last_tag()?,
+    };
+
+    let level = match compute_auto_level(&since)? {
+        Some((level, _)) => level,
+        None => {
+            anyhow::bail!("no release needed: no commits since {since} qualify for a version bump")
+        }
+    };
+
+    match level {
+        BumpLevel::Major if version.major == 0 => {
+            version.minor += 1;
+            version.patch = 0;
+        }
+        BumpLevel::Major => {
+            version.major += 1;
+            version.minor = 0;
+            version.patch = 0;
+        }
+        BumpLevel::Minor => {
+            version.minor += 1;
+            version.patch = 0;
+        }
+        BumpLevel::Patch => version.patch += 1,
+        BumpLevel::Prerelease => unreachable!("compute_bump never derives a Prerelease level"),
+    }
+    version.pre = semver::Prerelease::EMPTY;
+
+    Ok((version, level))
+}
+
+/// Print the current tag, the version `compute_bump` derives, and the commits that drove the
+/// decision; with `apply`, also create the annotated git tag for the new version.
+pub fn auto_bump(since_tag: Option<&str>, apply: bool) -> Result<()> {
+    let since = match since_tag {
+        Some(tag) => tag.to_string(),
+        None => crate::release::get_last_tag()?,
+    };
+    let (next_version, level) = compute_bump(Some(&since))?;
+    let config = crate::commit::CommitConfig::load()?;
+    let commits = crate::commit::parse_commit_range(&since, "HEAD", &config)?;
+
+    println!("{}: {}", "Current tag".bold(), since.green());
+    println!("{}: {} ({:?})", "Next version".bold(), next_version.to_string().green(), level);
+    println!("\n{}", "Commits driving this decision:".bold());
+    for commit in &commits {
+        println!("  * {} {}", &commit.hash[..7.min(commit.hash.len())], commit.subject);
+    }
+
+    if apply {
+        let tag = format!("v{next_version}");
+        std::process::Command::new("git")
+            .args(&["tag", "-a", &tag, "-m", &format!("Release version {next_version}")])
+            .status()
+            .context("Failed to create git tag")?;
+        println!("\n{}: Tag created: {}", "Success".green().bold(), tag);
+    }
+
+    Ok(())
+}
+
 pub fn sync_version(version: Option<&str>) -> Result<()> {
     let target_version = match version {
         Some(v) => v.to_string(),
@@ -105,30 +232,121 @@ pub fn sync_version(version: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Stage the next release as a release candidate instead of writing it as final: compute the
+/// target version the same way an explicit-`level` [`bump_version`] would (or auto-derive it from
+/// commits since the last *final* release, via [`crate::release::get_last_final_tag`], when
+/// `level` is `None`), then attach an incrementing `-rc.N` suffix to it rather than the bare
+/// version. Calling this again once `-rc.N` is already staged for the same target bumps `N`
+/// instead of recomputing a new target -- repeated `stage` calls track one candidate series per
+/// target version, not a fresh one each time.
+pub fn stage_prerelease(level: Option<BumpLevel>, dry_run: bool) -> Result<()> {
+    let current = read_version_file()?;
+    let current_version = Version::parse(&current)?;
+    let mut base_version = current_version.clone();
+    base_version.pre = semver::Prerelease::EMPTY;
+
+    let mut target = match level {
+        Some(level) => {
+            let mut target = base_version.clone();
+            apply_bump(&mut target, level)?;
+            target
+        }
+        None => {
+            let since = crate::release::get_last_final_tag()?;
+            let (target, _) = compute_bump(Some(&since))?;
+            target
+        }
+    };
+
+    if !current_version.pre.is_empty() && target == base_version {
+        // Already staging a candidate for this exact target -- bump the rc counter instead of
+        // starting a new series.
+        target.pre = next_prerelease(&current_version.pre, DEFAULT_PRERELEASE_IDENTIFIER)?;
+    } else {
+        target.pre = semver::Prerelease::new(&format!("{DEFAULT_PRERELEASE_IDENTIFIER}.1"))?;
+    }
+
+    if dry_run {
+        println!("{}: {} → {}", "Would stage".yellow(), current.red(), target.to_string().green());
+    } else {
+        println!("{}: {} → {}", "Staging release candidate".green().bold(), current.red(), target.to_string().green());
+        update_version_everywhere(&target.to_string())?;
+        println!("{}: Release candidate staged", "Success".green().bold());
+    }
+
+    Ok(())
+}
+
+/// Promote the currently-staged release candidate to a final release: strip its `-rc.N` suffix
+/// and write the bare version everywhere, without recomputing the bump level or version number --
+/// whatever `major.minor.patch` the candidate series settled on is what ships.
+pub fn promote_version(dry_run: bool) -> Result<()> {
+    let current = read_version_file()?;
+    let mut version = Version::parse(&current)?;
+
+    if version.pre.is_empty() {
+        println!(
+            "{}: {current} has no staged release candidate to promote",
+            "Nothing to do".green().bold()
+        );
+        return Ok(());
+    }
+    version.pre = semver::Prerelease::EMPTY;
+
+    if dry_run {
+        println!("{}: {} → {}", "Would promote".yellow(), current.red(), version.to_string().green());
+    } else {
+        println!("{}: {} → {}", "Promoting to final release".green().bold(), current.red(), version.to_string().green());
+        update_version_everywhere(&version.to_string())?;
+        println!("{}: Promoted to final release", "Success".green().bold());
+    }
+
+    Ok(())
+}
+
+/// Apply a non-prerelease bump to `version` in place -- the same arithmetic [`bump_version`] uses
+/// for an explicit `level`, factored out so [`stage_prerelease`] can compute a target version
+/// without going through `bump_version`'s own read-VERSION-file/write-everywhere side effects.
+fn apply_bump(version: &mut Version, level: BumpLevel) -> Result<()> {
+    match level {
+        BumpLevel::Major => {
+            version.major += 1;
+            version.minor = 0;
+            version.patch = 0;
+        }
+        BumpLevel::Minor => {
+            version.minor += 1;
+            version.patch = 0;
+        }
+        BumpLevel::Patch => version.patch += 1,
+        BumpLevel::Prerelease => {
+            anyhow::bail!("stage needs major, minor, or patch -- omit --level to auto-derive, or pass one explicitly")
+        }
+    }
+    version.pre = semver::Prerelease::EMPTY;
+    Ok(())
+}
+
+/// Check every manifest every built-in [`crate::rewriters::Rewriter`] can find under the repo
+/// root against the `VERSION` file, across every ecosystem (Cargo, npm, Python, .NET) rather than
+/// just this workspace's own `Cargo.toml` files.
 pub fn check_version_sync() -> Result<()> {
     let version_file = read_version_file()?;
     let mut all_match = true;
-    let mut versions = vec![("VERSION file", version_file.clone())];
-    
-    // Check workspace Cargo.toml
-    let cargo_version = read_cargo_version()?;
-    versions.push(("Cargo.toml (workspace)", cargo_version.clone()));
-    if cargo_version != version_file {
-        all_match = false;
-    }
-    
-    // Check all member Cargo.toml files
-    let members = get_workspace_members()?;
-    for member in members {
-        let member_path = format!("rust/{}/Cargo.toml", member);
-        if let Ok(member_version) = read_specific_cargo_version(&member_path) {
-            versions.push((Box::leak(member.into_boxed_str()), member_version.clone()));
-            if member_version != version_file {
+    let mut versions = vec![("VERSION file".to_string(), version_file.clone())];
+
+    for rewriter in crate::rewriters::all_rewriters() {
+        for manifest in rewriter.discover(Path::new(".")) {
+            let Ok(manifest_version) = rewriter.read_version(&manifest) else {
+                continue;
+            };
+            if manifest_version != version_file {
                 all_match = false;
             }
+            versions.push((format!("{} ({})", manifest.display(), rewriter.name()), manifest_version));
         }
     }
-    
+
     // Display results
     println!("{}", "Version Check Results:".bold());
     for (name, version) in versions {
@@ -139,7 +357,7 @@ pub fn check_version_sync() -> Result<()> {
         };
         println!("  {} {}: {}", status, name, version);
     }
-    
+
     if all_match {
         println!("\n{}: All versions are in sync", "Success".green().bold());
         Ok(())
@@ -188,7 +406,7 @@ fn read_specific_cargo_version(path: &str) -> Result<String> {
     anyhow::bail!("Could not find version in {}", path)
 }
 
-fn get_workspace_members() -> Result<Vec<String>> {
+pub(crate) fn get_workspace_members() -> Result<Vec<String>> {
     let content = fs::read_to_string("rust/Cargo.toml")?;
     let doc = content.parse::<Document>()?;
     
@@ -206,63 +424,22 @@ fn get_workspace_members() -> Result<Vec<String>> {
     Ok(Vec::new())
 }
 
+/// Write `version` to the `VERSION` file, then apply every built-in [`crate::rewriters::Rewriter`]
+/// to every manifest it finds under the repo root -- Cargo, npm, Python, and .NET alike -- so a
+/// project that ships bindings in more than one ecosystem stays in lockstep from one call. A
+/// manifest a rewriter can't find a version field in (e.g. a `Cargo.toml` with no `[package]`
+/// table, like a pure virtual-manifest workspace root) is skipped rather than failing the batch.
 fn update_version_everywhere(version: &str) -> Result<()> {
-    // Update VERSION file
     fs::write("VERSION", format!("{}\n", version))?;
-    
-    // Update workspace Cargo.toml
-    update_cargo_version("rust/Cargo.toml", version)?;
-    
-    // Update all member Cargo.toml files
-    let members = get_workspace_members()?;
-    for member in members {
-        let member_path = format!("rust/{}/Cargo.toml", member);
-        if Path::new(&member_path).exists() {
-            update_cargo_version(&member_path, version)?;
-        }
-    }
-    
-    // Update dashboard package.json if it exists
-    if Path::new("hecate-dashboard/package.json").exists() {
-        update_package_json_version("hecate-dashboard/package.json", version)?;
-    }
-    
-    Ok(())
-}
 
-fn update_cargo_version(path: &str, version: &str) -> Result<()> {
-    let content = fs::read_to_string(path)?;
-    let mut doc = content.parse::<Document>()?;
-    
-    // Update workspace.package.version if it exists
-    if let Some(workspace) = doc.get_mut("workspace") {
-        if let Some(package) = workspace.get_mut("package") {
-            if let Item::Table(table) = package {
-                table["version"] = toml_edit::value(version);
+    for rewriter in crate::rewriters::all_rewriters() {
+        for manifest in rewriter.discover(Path::new(".")) {
+            if rewriter.read_version(&manifest).is_err() {
+                continue;
             }
+            rewriter.write_version(&manifest, version)?;
         }
     }
-    
-    // Update package.version if it exists
-    if let Some(package) = doc.get_mut("package") {
-        if let Item::Table(table) = package {
-            table["version"] = toml_edit::value(version);
-        }
-    }
-    
-    fs::write(path, doc.to_string())?;
-    Ok(())
-}
 
-fn update_package_json_version(path: &str, version: &str) -> Result<()> {
-    let content = fs::read_to_string(path)?;
-    let mut package: serde_json::Value = serde_json::from_str(&content)?;
-    
-    if let Some(obj) = package.as_object_mut() {
-        obj.insert("version".to_string(), serde_json::Value::String(version.to_string()));
-    }
-    
-    let updated = serde_json::to_string_pretty(&package)?;
-    fs::write(path, updated)?;
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,330 @@
+//! Workspace-aware release orchestration
+//!
+//! `release::create_release` treats the project as a single versioned unit: every member crate
+//! is bumped and tagged together. That's the wrong model for a monorepo where crates depend on
+//! each other independently -- releasing `hecate-cli` shouldn't force a version bump on
+//! `hecate-gpu` if nothing under it changed. This builds a dependency graph from each member's
+//! `Cargo.toml` (workspace members + path dependencies between them), topologically sorts it, and
+//! releases only the crates that changed since their last tag plus everything that depends on
+//! them -- bumping each in dependency order and rewriting the version requirement dependents pin
+//! to it so the workspace stays internally consistent.
+
+use anyhow::{bail, Context, Result};
+use semver::Version;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::process::Command;
+use toml_edit::{value, Document, Item};
+
+/// One workspace member crate and the other member crates it depends on
+#[derive(Debug, Clone)]
+pub struct WorkspaceCrate {
+    pub name: String,
+    /// Directory under `rust/`, e.g. `"hecate-gpu"`
+    pub dir: String,
+    pub version: Version,
+    /// Names of other workspace crates this one depends on directly
+    pub dependencies: Vec<String>,
+}
+
+/// The intra-workspace dependency graph: nodes are member crates, edges point from a dependency
+/// to the crate that depends on it.
+#[derive(Debug, Default)]
+pub struct WorkspaceGraph {
+    pub crates: Vec<WorkspaceCrate>,
+}
+
+impl WorkspaceGraph {
+    /// Parse every workspace member's `Cargo.toml`, keeping only dependency entries whose name
+    /// matches another workspace member -- ordinary crates.io dependencies aren't part of this
+    /// graph.
+    pub fn load() -> Result<Self> {
+        let members = crate::version::get_workspace_members()?;
+        let member_names: HashSet<String> = members.iter().cloned().collect();
+
+        let mut crates = Vec::new();
+        for dir in &members {
+            let path = format!("rust/{dir}/Cargo.toml");
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {path}"))?;
+            let doc = content.parse::<Document>()
+                .with_context(|| format!("failed to parse {path}"))?;
+
+            let package = doc.get("package").context(format!("{path} has no [package] table"))?;
+            let name = package
+                .get("name")
+                .and_then(|v| v.as_str())
+                .with_context(|| format!("{path} has no package.name"))?
+                .to_string();
+            let version = package
+                .get("version")
+                .and_then(|v| v.as_str())
+                .with_context(|| format!("{path} has no package.version"))?;
+            let version = Version::parse(version)
+                .with_context(|| format!("{path} has an invalid package.version"))?;
+
+            let mut dependencies = Vec::new();
+            if let Some(Item::Table(deps)) = doc.get("dependencies") {
+                for (dep_name, _) in deps.iter() {
+                    if member_names.contains(dep_name) {
+                        dependencies.push(dep_name.to_string());
+                    }
+                }
+            }
+
+            crates.push(WorkspaceCrate { name, dir: dir.clone(), version, dependencies });
+        }
+
+        Ok(Self { crates })
+    }
+
+    pub fn find(&self, name: &str) -> Option<&WorkspaceCrate> {
+        self.crates.iter().find(|c| c.name == name)
+    }
+
+    /// Kahn's algorithm: repeatedly emit nodes with in-degree zero (no not-yet-emitted
+    /// dependency), decrementing the in-degree of everything that depends on them. A non-empty
+    /// remainder once the queue drains means those crates form a dependency cycle.
+    pub fn topological_order(&self) -> Result<Vec<String>> {
+        let mut in_degree: HashMap<&str, usize> = HashMap::new();
+        for c in &self.crates {
+            in_degree.entry(&c.name).or_insert(0);
+            for _ in &c.dependencies {
+                *in_degree.entry(&c.name).or_insert(0) += 1;
+            }
+        }
+
+        // Sorted for deterministic output regardless of HashMap iteration order.
+        let mut ready: Vec<&str> = in_degree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(name, _)| *name)
+            .collect();
+        ready.sort();
+        let mut queue: VecDeque<&str> = ready.into();
+
+        let mut order = Vec::with_capacity(self.crates.len());
+        while let Some(name) = queue.pop_front() {
+            order.push(name.to_string());
+
+            let mut newly_ready = Vec::new();
+            for c in &self.crates {
+                if c.dependencies.iter().any(|d| d == name) {
+                    let degree = in_degree.get_mut(c.name.as_str()).unwrap();
+                    *degree -= 1;
+                    if *degree == 0 {
+                        newly_ready.push(c.name.as_str());
+                    }
+                }
+            }
+            newly_ready.sort();
+            queue.extend(newly_ready);
+        }
+
+        if order.len() != self.crates.len() {
+            let cyclic: Vec<&str> = in_degree
+                .iter()
+                .filter(|(_, degree)| **degree > 0)
+                .map(|(name, _)| *name)
+                .collect();
+            bail!("workspace crates form a dependency cycle: {}", cyclic.join(", "));
+        }
+
+        Ok(order)
+    }
+
+    /// `names` plus every crate (direct or transitive) that depends on one of them.
+    pub fn reverse_closure(&self, names: &HashSet<String>) -> HashSet<String> {
+        let mut closure = names.clone();
+        loop {
+            let mut grew = false;
+            for c in &self.crates {
+                if closure.contains(&c.name) {
+                    continue;
+                }
+                if c.dependencies.iter().any(|d| closure.contains(d)) {
+                    closure.insert(c.name.clone());
+                    grew = true;
+                }
+            }
+            if !grew {
+                break;
+            }
+        }
+        closure
+    }
+}
+
+/// Whether anything under `rust/<dir>` changed since `since_tag`.
+fn crate_changed_since(dir: &str, since_tag: &str) -> Result<bool> {
+    let output = Command::new("git")
+        .args(&["diff", "--name-only", &format!("{since_tag}..HEAD"), "--", &format!("rust/{dir}")])
+        .output()
+        .context("failed to run git diff")?;
+
+    Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+}
+
+/// Patch-bump `dir`'s own `package.version`, and rewrite any `[dependencies]` entry elsewhere in
+/// the workspace that pins `dir`'s crate with an explicit `version = "..."` field.
+fn bump_crate_version(graph: &WorkspaceGraph, name: &str, new_version: &Version) -> Result<()> {
+    let target = graph.find(name).context("unknown workspace crate")?;
+    let own_path = format!("rust/{}/Cargo.toml", target.dir);
+
+    let content = fs::read_to_string(&own_path)?;
+    let mut doc = content.parse::<Document>()?;
+    if let Some(Item::Table(package)) = doc.get_mut("package") {
+        package["version"] = value(new_version.to_string());
+    }
+    fs::write(&own_path, doc.to_string())?;
+
+    for c in &graph.crates {
+        if !c.dependencies.contains(&name.to_string()) {
+            continue;
+        }
+        let dep_path = format!("rust/{}/Cargo.toml", c.dir);
+        let content = fs::read_to_string(&dep_path)?;
+        let mut doc = content.parse::<Document>()?;
+        if let Some(Item::Table(deps)) = doc.get_mut("dependencies") {
+            if let Some(dep) = deps.get_mut(name) {
+                if let Item::Value(toml_edit::Value::InlineTable(table)) = dep {
+                    if table.contains_key("version") {
+                        table.insert("version", new_version.to_string().into());
+                    }
+                }
+            }
+        }
+        fs::write(&dep_path, doc.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Release only the workspace crates that changed since their last tag, plus their
+/// reverse-dependency closure, bumping each in dependency order. Returns the per-crate
+/// old-version/new-version summary, in release order.
+pub async fn create_workspace_release(skip_tests: bool, skip_changelog: bool) -> Result<Vec<(String, Version, Version)>> {
+    println!("Creating workspace release...");
+
+    let graph = WorkspaceGraph::load()?;
+    let order = graph.topological_order()?;
+    let last_tag = crate::release::get_last_tag()?;
+
+    let dirty: HashSet<String> = graph
+        .crates
+        .iter()
+        .filter(|c| crate_changed_since(&c.dir, &last_tag).unwrap_or(true))
+        .map(|c| c.name.clone())
+        .collect();
+
+    if dirty.is_empty() {
+        println!("  No workspace crates changed since {last_tag}; nothing to release");
+        return Ok(Vec::new());
+    }
+
+    let affected = graph.reverse_closure(&dirty);
+    println!(
+        "  {} crate(s) changed, {} affected after reverse-dependency closure",
+        dirty.len(),
+        affected.len()
+    );
+
+    if !skip_tests {
+        println!("  Running tests...");
+        crate::release::run_tests()?;
+        println!("  Tests passed");
+    }
+
+    let mut summary = Vec::new();
+    for name in &order {
+        if !affected.contains(name) {
+            continue;
+        }
+        let crate_info = graph.find(name).context("topological_order returned an unknown crate")?;
+        let old_version = crate_info.version.clone();
+        let mut new_version = old_version.clone();
+        new_version.patch += 1;
+
+        bump_crate_version(&graph, name, &new_version)?;
+        println!("  {name}: {old_version} -> {new_version}");
+        summary.push((name.clone(), old_version, new_version));
+    }
+
+    if !skip_changelog {
+        if let Some((_, _, highest)) = summary.iter().max_by_key(|(_, _, v)| v.clone()) {
+            crate::release::generate_changelog_file(&highest.to_string(), None, None)?;
+        }
+    }
+
+    println!("\nPer-crate summary:");
+    for (name, old_version, new_version) in &summary {
+        println!("  {name}: {old_version} -> {new_version}");
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wcrate(name: &str, dir: &str, version: &str, deps: &[&str]) -> WorkspaceCrate {
+        WorkspaceCrate {
+            name: name.to_string(),
+            dir: dir.to_string(),
+            version: Version::parse(version).unwrap(),
+            dependencies: deps.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn topological_order_puts_dependencies_before_dependents() {
+        let graph = WorkspaceGraph {
+            crates: vec![
+                wcrate("hecate-cli", "hecate-cli", "0.1.0", &["hecate-core"]),
+                wcrate("hecate-core", "hecate-core", "0.1.0", &[]),
+                wcrate("hecate-gpu", "hecate-gpu", "0.1.0", &["hecate-core"]),
+            ],
+        };
+
+        let order = graph.topological_order().unwrap();
+        let core_pos = order.iter().position(|n| n == "hecate-core").unwrap();
+        let cli_pos = order.iter().position(|n| n == "hecate-cli").unwrap();
+        let gpu_pos = order.iter().position(|n| n == "hecate-gpu").unwrap();
+        assert!(core_pos < cli_pos);
+        assert!(core_pos < gpu_pos);
+    }
+
+    #[test]
+    fn topological_order_rejects_cycles() {
+        let graph = WorkspaceGraph {
+            crates: vec![
+                wcrate("a", "a", "0.1.0", &["b"]),
+                wcrate("b", "b", "0.1.0", &["a"]),
+            ],
+        };
+
+        let err = graph.topological_order().unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+    }
+
+    #[test]
+    fn reverse_closure_includes_transitive_dependents() {
+        let graph = WorkspaceGraph {
+            crates: vec![
+                wcrate("hecate-core", "hecate-core", "0.1.0", &[]),
+                wcrate("hecate-gpu", "hecate-gpu", "0.1.0", &["hecate-core"]),
+                wcrate("hecate-cli", "hecate-cli", "0.1.0", &["hecate-gpu"]),
+                wcrate("hecate-dev", "hecate-dev", "0.1.0", &[]),
+            ],
+        };
+
+        let dirty: HashSet<String> = ["hecate-core".to_string()].into_iter().collect();
+        let closure = graph.reverse_closure(&dirty);
+
+        assert!(closure.contains("hecate-core"));
+        assert!(closure.contains("hecate-gpu"));
+        assert!(closure.contains("hecate-cli"));
+        assert!(!closure.contains("hecate-dev"));
+    }
+}
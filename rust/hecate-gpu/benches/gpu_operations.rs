@@ -13,14 +13,14 @@ fn create_test_gpu(index: u32) -> GpuStatus {
         gpu_type: GpuType::Discrete,
         temperature: 75,
         power_draw: 250,
-        power_limit: 350,
+        power_limit: Some(350),
         memory_used: 4_294_967_296, // 4GB
         memory_total: 12_884_901_888, // 12GB
         utilization_gpu: 60,
         utilization_memory: 50,
         fan_speed: Some(65),
         clock_graphics: 1755,
-        clock_memory: 8001,
+        clock_memory: Some(8001),
         driver_version: Some("525.105.17".to_string()),
         pci_info: PciInfo {
             domain: 0,
@@ -31,6 +31,19 @@ fn create_test_gpu(index: u32) -> GpuStatus {
             device_id: 0x2204,
         },
         power_state: PowerState::Active,
+        voltage_mv: None,
+        throttle_reasons: Vec::new(),
+        ecc_errors: None,
+        processes: Vec::new(),
+        driver_bound: DriverBinding::Unbound,
+        unified_memory: false,
+        mig_parent: None,
+        mig_uuid: None,
+        uuid: None,
+        serial: None,
+        board_part_number: None,
+        vbios_version: None,
+        cuda_driver_version: None,
     }
 }
 
@@ -269,8 +282,10 @@ fn benchmark_memory_operations(c: &mut Criterion) {
                     utilization_memory: (i % 100) as u32,
                     memory_used: 4_294_967_296 + (i % 1000) as u64 * 1_000_000,
                     clock_graphics: 1500 + (i % 500) as u32,
-                    clock_memory: 7000 + (i % 1000) as u32,
+                    clock_memory: Some(7000 + (i % 1000) as u32),
                     fan_speed: Some(50 + (i % 50) as u32),
+                    memory_total: 8_589_934_592,
+                    processes: None,
                 })
                 .collect();
             black_box(metrics)
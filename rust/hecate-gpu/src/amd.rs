@@ -2,14 +2,19 @@
 
 use crate::{
     error::{GpuError, Result},
-    GpuBackend, GpuConfig, GpuStatus, GpuType, GpuVendor, PowerMode, PowerState, FanCurve, PciInfo
+    DriverBinding, FreqScalingTable, GpuBackend, GpuConfig, GpuProcess, GpuProcessType, GpuStatus,
+    GpuType, GpuVendor, MinMax, PowerMode, PowerState, FanCurve, PciInfo, ThrottleReason,
+    VoltageCurve, RECLOCK_GUARD_MHZ,
 };
 use async_trait::async_trait;
 use regex::Regex;
 use std::collections::HashMap;
 use std::fs;
+use std::mem::size_of;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::process::Command as AsyncCommand;
 use tokio::sync::RwLock;
 use tracing::{debug, info, instrument, warn};
 
@@ -27,6 +32,184 @@ struct AmdDevice {
 #[derive(Debug)]
 pub struct AmdBackend {
     devices: Arc<RwLock<HashMap<u32, AmdDevice>>>,
+    /// Per-device power-to-frequency governor table, set by `apply_config` whenever
+    /// `GpuConfig::freq_scaling` is present, and re-consulted by `set_power_limit` so a direct
+    /// power-limit change still re-evaluates the clock ceiling it implies
+    freq_governors: Arc<RwLock<HashMap<u32, FreqScalingTable>>>,
+    /// Last-applied fan setpoint per device, consulted by `apply_device_fan_curve` to apply
+    /// directional hysteresis and clamp the per-update speed step instead of recomputing a fresh
+    /// target from the instant temperature every call
+    fan_state: Arc<RwLock<HashMap<u32, FanState>>>,
+}
+
+/// Fan-control state carried across `apply_device_fan_curve` calls for a single device
+#[derive(Debug, Clone, Copy)]
+struct FanState {
+    /// Temperature reading that justified the currently-applied speed
+    setpoint_temp_c: u32,
+    /// Currently-applied fan speed as a percentage (0-100), independent of the device's native
+    /// PWM duty range
+    speed_percent: u32,
+}
+
+/// amdgpu `gpu_metrics` sysfs table header, common to every format revision: a 16-bit declared
+/// table size (used to sanity-check the file actually matches the revision we parsed it as)
+/// followed by the major/minor format revision that selects the layout below.
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy)]
+#[allow(dead_code)] // fields exist for layout/offset fidelity; we parse size/revision from raw bytes instead
+struct MetricsTableHeaderRaw {
+    structure_size: u16,
+    format_revision: u8,
+    content_revision: u8,
+}
+
+/// Discrete-GPU `gpu_metrics` layout (`format_revision == 1`), matching the kernel's
+/// `struct gpu_metrics_v1_3` up through `throttle_status`; fields amdgpu appends after that
+/// (fan speed, PCIe link state, ...) aren't read here.
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy)]
+#[allow(dead_code)] // most fields exist only for correct byte-offset layout; we read a handful out
+struct GpuMetricsV1Raw {
+    header: MetricsTableHeaderRaw,
+    temperature_edge: u16,
+    temperature_hotspot: u16,
+    temperature_mem: u16,
+    temperature_vrgfx: u16,
+    temperature_vrsoc: u16,
+    temperature_vrmem: u16,
+    average_gfx_activity: u16,
+    average_umc_activity: u16,
+    average_mm_activity: u16,
+    average_socket_power: u16,
+    energy_accumulator: u64,
+    average_gfxclk_frequency: u16,
+    average_socclk_frequency: u16,
+    average_uclk_frequency: u16,
+    average_vclk0_frequency: u16,
+    average_dclk0_frequency: u16,
+    average_vclk1_frequency: u16,
+    average_dclk1_frequency: u16,
+    current_gfxclk: u16,
+    current_socclk: u16,
+    current_uclk: u16,
+    current_vclk0: u16,
+    current_dclk0: u16,
+    current_vclk1: u16,
+    current_dclk1: u16,
+    throttle_status: u32,
+}
+
+/// APU `gpu_metrics` layout (`format_revision == 2`), matching `struct gpu_metrics_v2_3` up
+/// through `throttle_status`. APUs have no dedicated VRAM or memory-controller sensors, and
+/// fabric clock (`current_fclk`) is the closest analog to a discrete GPU's memory clock.
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy)]
+#[allow(dead_code)] // most fields exist only for correct byte-offset layout; we read a handful out
+struct GpuMetricsV2Raw {
+    header: MetricsTableHeaderRaw,
+    temperature_gfx: u16,
+    temperature_soc: u16,
+    temperature_core: [u16; 4],
+    temperature_l3: [u16; 2],
+    average_gfx_activity: u16,
+    average_mm_activity: u16,
+    average_socket_power: u16,
+    average_gfx_power: u16,
+    average_core_power: [u16; 4],
+    average_gfxclk_frequency: u16,
+    average_socclk_frequency: u16,
+    average_uclk_frequency: u16,
+    average_fclk_frequency: u16,
+    average_vclk_frequency: u16,
+    average_dclk_frequency: u16,
+    current_gfxclk: u16,
+    current_socclk: u16,
+    current_uclk: u16,
+    current_fclk: u16,
+    current_vclk: u16,
+    current_dclk: u16,
+    throttle_status: u32,
+}
+
+/// Normalized view of a `gpu_metrics` table, independent of which on-disk revision it came from.
+/// Per-field `None` means amdgpu marked that reading unavailable (`0xffff`); callers should fall
+/// back to the equivalent hwmon file in that case.
+#[derive(Debug, Clone)]
+struct GpuMetrics {
+    is_apu: bool,
+    temperature_edge: Option<u32>,
+    temperature_hotspot: Option<u32>,
+    temperature_mem: Option<u32>,
+    average_socket_power: Option<u32>,
+    average_gfx_activity: Option<u32>,
+    average_umc_activity: Option<u32>,
+    current_gfxclk: Option<u32>,
+    current_uclk: Option<u32>,
+    throttle_status: u32,
+}
+
+/// amdgpu's sentinel for "this counter isn't available on this ASIC/firmware combination"
+const GPU_METRICS_INVALID: u16 = 0xffff;
+
+fn decode_gpu_metric(raw: u16) -> Option<u32> {
+    if raw == GPU_METRICS_INVALID {
+        None
+    } else {
+        Some(raw as u32)
+    }
+}
+
+// Bit positions within `throttle_status`, shared across `gpu_metrics` revisions (amdgpu's
+// `enum amdgpu_pp_sensors` throttler bitmask).
+const THROTTLER_TEMP_EDGE_BIT: u32 = 0;
+const THROTTLER_TEMP_HOTSPOT_BIT: u32 = 1;
+const THROTTLER_TEMP_MEM_BIT: u32 = 2;
+const THROTTLER_TEMP_VR_GFX_BIT: u32 = 3;
+const THROTTLER_TEMP_VR_SOC_BIT: u32 = 4;
+const THROTTLER_TEMP_VR_MEM_BIT: u32 = 5;
+const THROTTLER_TDC_GFX_BIT: u32 = 6;
+const THROTTLER_TDC_SOC_BIT: u32 = 7;
+const THROTTLER_TDC_MEM_BIT: u32 = 8;
+const THROTTLER_PPT_BIT: u32 = 9;
+const THROTTLER_FIT_BIT: u32 = 10;
+const THROTTLER_PPM_BIT: u32 = 11;
+const THROTTLER_APCC_BIT: u32 = 12;
+
+/// Map amdgpu's `throttle_status` bitmask onto our vendor-neutral [`ThrottleReason`]. The
+/// bitmask is finer-grained than the enum (amdgpu distinguishes edge/hotspot/memory/VR thermal
+/// sensors individually, for instance), so several distinct amdgpu throttlers can fold onto the
+/// same reason.
+fn decode_throttle_reasons(bits: u32) -> Vec<ThrottleReason> {
+    let mut reasons = Vec::new();
+    let is_set = |bit: u32| bits & (1 << bit) != 0;
+
+    if is_set(THROTTLER_TEMP_EDGE_BIT)
+        || is_set(THROTTLER_TEMP_HOTSPOT_BIT)
+        || is_set(THROTTLER_TEMP_MEM_BIT)
+    {
+        reasons.push(ThrottleReason::HwThermalSlowdown);
+    }
+    if is_set(THROTTLER_TEMP_VR_GFX_BIT)
+        || is_set(THROTTLER_TEMP_VR_SOC_BIT)
+        || is_set(THROTTLER_TEMP_VR_MEM_BIT)
+    {
+        reasons.push(ThrottleReason::SwThermalSlowdown);
+    }
+    if is_set(THROTTLER_TDC_GFX_BIT) || is_set(THROTTLER_TDC_SOC_BIT) || is_set(THROTTLER_TDC_MEM_BIT) {
+        reasons.push(ThrottleReason::HwPowerBrakeSlowdown);
+    }
+    if is_set(THROTTLER_PPT_BIT) {
+        reasons.push(ThrottleReason::ClocksPowerCap);
+    }
+    if is_set(THROTTLER_FIT_BIT) || is_set(THROTTLER_APCC_BIT) {
+        reasons.push(ThrottleReason::HwSlowdown);
+    }
+    if is_set(THROTTLER_PPM_BIT) {
+        reasons.push(ThrottleReason::AppClocksSetting);
+    }
+
+    reasons
 }
 
 impl AmdBackend {
@@ -34,6 +217,8 @@ impl AmdBackend {
     pub async fn new() -> Result<Self> {
         Ok(Self {
             devices: Arc::new(RwLock::new(HashMap::new())),
+            freq_governors: Arc::new(RwLock::new(HashMap::new())),
+            fan_state: Arc::new(RwLock::new(HashMap::new())),
         })
     }
 
@@ -108,6 +293,168 @@ impl AmdBackend {
         Ok(format!("{}:{}", vendor_id, device_id))
     }
 
+    /// Resolve the device's PCI bus address (e.g. "0000:01:00.0"), as used by amdgpu's
+    /// `drm-pdev:` fdinfo field. `device_path` is a symlink into `/sys/bus/pci/devices/<bdf>`,
+    /// so its canonical form's last path component is the address we need.
+    fn pci_address(device_path: &Path) -> Result<String> {
+        let canonical = fs::canonicalize(device_path).map_err(GpuError::IoError)?;
+        canonical
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| GpuError::SystemError("could not resolve PCI bus address".to_string()))
+    }
+
+    /// Build the `DRI_PRIME` PCI-slot tag mesa accepts (e.g. "pci-0000_01_00_0") from this
+    /// device's PCI bus address, to run an application on this GPU specifically
+    fn dri_prime_tag(device: &AmdDevice) -> Result<String> {
+        let bdf = Self::pci_address(&device.device_path)?;
+        Ok(format!("pci-{}", bdf.replace([':', '.'], "_")))
+    }
+
+    /// Resolve the `/dev/dri/by-path` render node symlinked to this device's PCI slot, which
+    /// confirms the card is actually exposed under DRI before handing out a `DRI_PRIME` value
+    /// that references it
+    fn dri_by_path_render_node(device: &AmdDevice) -> Result<PathBuf> {
+        let bdf = Self::pci_address(&device.device_path)?;
+        let render_link = Path::new("/dev/dri/by-path").join(format!("pci-{}-render", bdf));
+        fs::canonicalize(&render_link)
+            .map_err(|e| GpuError::DrmError(format!("No DRI render node for PCI slot {}: {}", bdf, e)))
+    }
+
+    /// Enumerate processes using this GPU by scanning every running process's DRM fdinfo
+    /// entries, since amdgpu has no NVML-equivalent userspace query API. A process can hold
+    /// several fds against the same device (one per context), so per-PID VRAM usage and engine
+    /// time are summed across all matching fds before being reported once per PID.
+    fn get_device_processes(device: &AmdDevice) -> Result<Vec<GpuProcess>> {
+        let by_pid = Self::sample_fdinfo_counters(device)?;
+
+        Ok(by_pid
+            .into_iter()
+            .map(|(pid, (vram_kib, gfx_ns, compute_ns, _encdec_ns))| {
+                let name = Self::process_name(pid).unwrap_or_else(|| format!("pid-{}", pid));
+                let proc_type = if compute_ns > gfx_ns {
+                    GpuProcessType::Compute
+                } else if gfx_ns > 0 {
+                    GpuProcessType::Graphics
+                } else {
+                    GpuProcessType::Unknown
+                };
+
+                GpuProcess {
+                    pid,
+                    name,
+                    proc_type,
+                    used_memory: Some(vram_kib * 1024),
+                    // amdgpu's fdinfo reports cumulative engine *time*, not an instantaneous
+                    // utilization percentage; use `AmdBackend::get_process_usage` for that.
+                    sm_utilization: None,
+                    enc_dec_utilization: None,
+                }
+            })
+            .collect())
+    }
+
+    /// Scan `/proc/*/fdinfo/*` for file descriptors bound to this device's PCI slot (matching
+    /// `drm-pdev:`), returning per-PID cumulative `(vram_kib, gfx_ns, compute_ns, encdec_ns)`
+    /// counters. `vram_kib` takes the larger of `drm-memory-vram`/`drm-total-vram` if both are
+    /// present; `encdec_ns` sums `drm-engine-dec`/`drm-engine-enc` since amdgpu only throttles
+    /// them as a shared block.
+    fn sample_fdinfo_counters(device: &AmdDevice) -> Result<HashMap<u32, (u64, u64, u64, u64)>> {
+        let bdf = Self::pci_address(&device.device_path)?;
+        let mut by_pid: HashMap<u32, (u64, u64, u64, u64)> = HashMap::new();
+
+        for entry in fs::read_dir("/proc").map_err(GpuError::IoError)? {
+            let Ok(entry) = entry else { continue };
+            let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<u32>().ok()) else {
+                continue;
+            };
+
+            let fdinfo_dir = entry.path().join("fdinfo");
+            let Ok(fds) = fs::read_dir(&fdinfo_dir) else { continue };
+
+            for fd in fds.flatten() {
+                let Ok(content) = fs::read_to_string(fd.path()) else { continue };
+                if !content.lines().any(|l| l.trim() == format!("drm-pdev:\t{bdf}")) {
+                    continue;
+                }
+
+                let counters = by_pid.entry(pid).or_insert((0, 0, 0, 0));
+                for line in content.lines() {
+                    if let Some(value) = line.strip_prefix("drm-memory-vram:") {
+                        counters.0 = counters.0.max(parse_fdinfo_kib(value));
+                    } else if let Some(value) = line.strip_prefix("drm-total-vram:") {
+                        counters.0 = counters.0.max(parse_fdinfo_kib(value));
+                    } else if let Some(value) = line.strip_prefix("drm-engine-gfx:") {
+                        counters.1 += parse_fdinfo_ns(value);
+                    } else if let Some(value) = line.strip_prefix("drm-engine-compute:") {
+                        counters.2 += parse_fdinfo_ns(value);
+                    } else if let Some(value) = line.strip_prefix("drm-engine-dec:") {
+                        counters.3 += parse_fdinfo_ns(value);
+                    } else if let Some(value) = line.strip_prefix("drm-engine-enc:") {
+                        counters.3 += parse_fdinfo_ns(value);
+                    }
+                }
+            }
+        }
+
+        Ok(by_pid)
+    }
+
+    /// Sample per-process GPU engine utilization over `interval`: read amdgpu's cumulative
+    /// fdinfo nanosecond counters, sleep, read them again, then divide each engine's busy-time
+    /// delta by the elapsed wall time. amdgpu only reports a running total, not an instantaneous
+    /// percentage like NVML does for NVIDIA, so utilization has to be derived this way.
+    pub async fn get_process_usage(&self, index: u32, interval: Duration) -> Result<Vec<GpuProcess>> {
+        let devices = self.devices.read().await;
+        let device = devices.get(&index).ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        let before = Self::sample_fdinfo_counters(device)?;
+        tokio::time::sleep(interval).await;
+        let after = Self::sample_fdinfo_counters(device)?;
+
+        let elapsed_ns = interval.as_nanos().max(1);
+        let percent_of_elapsed = |busy_ns: u64| (((busy_ns as u128) * 100) / elapsed_ns).min(100) as u32;
+
+        Ok(after
+            .into_iter()
+            .map(|(pid, (vram_kib, gfx_ns, compute_ns, encdec_ns))| {
+                let (gfx_before, compute_before, encdec_before) = before
+                    .get(&pid)
+                    .map(|(_, gfx, compute, encdec)| (*gfx, *compute, *encdec))
+                    .unwrap_or((0, 0, 0));
+
+                let gfx_delta = gfx_ns.saturating_sub(gfx_before);
+                let compute_delta = compute_ns.saturating_sub(compute_before);
+                let encdec_delta = encdec_ns.saturating_sub(encdec_before);
+
+                let proc_type = if compute_delta > gfx_delta {
+                    GpuProcessType::Compute
+                } else if gfx_delta > 0 {
+                    GpuProcessType::Graphics
+                } else {
+                    GpuProcessType::Unknown
+                };
+
+                GpuProcess {
+                    pid,
+                    name: Self::process_name(pid).unwrap_or_else(|| format!("pid-{}", pid)),
+                    proc_type,
+                    used_memory: Some(vram_kib * 1024),
+                    sm_utilization: Some(percent_of_elapsed(gfx_delta.max(compute_delta))),
+                    enc_dec_utilization: Some(percent_of_elapsed(encdec_delta)),
+                }
+            })
+            .collect())
+    }
+
+    /// Best-effort process name lookup via procfs
+    fn process_name(pid: u32) -> Option<String> {
+        fs::read_to_string(format!("/proc/{}/comm", pid))
+            .ok()
+            .map(|s| s.trim().to_string())
+    }
+
     /// Find hwmon path for temperature/power monitoring
     fn find_hwmon_path(device_path: &Path) -> Result<Option<PathBuf>> {
         let hwmon_dir = device_path.join("hwmon");
@@ -133,39 +480,124 @@ impl AmdBackend {
         Ok(None)
     }
 
+    /// Read and decode the amdgpu `gpu_metrics` binary table, which is far more accurate than
+    /// hwmon for junction temperature, throttle state, and -- via its format revision -- whether
+    /// this is an APU or a discrete GPU. Returns `None` on any I/O, size, or format mismatch so
+    /// callers fall back to hwmon; amdgpu has shipped several incompatible `gpu_metrics`
+    /// revisions over the years, and this only understands the two current ones (v1 for
+    /// discrete, v2 for APU).
+    fn read_gpu_metrics(device: &AmdDevice) -> Option<GpuMetrics> {
+        let bytes = fs::read(device.device_path.join("gpu_metrics")).ok()?;
+        if bytes.len() < size_of::<MetricsTableHeaderRaw>() {
+            return None;
+        }
+
+        let structure_size = u16::from_le_bytes([bytes[0], bytes[1]]) as usize;
+        let format_revision = bytes[2];
+        if structure_size > bytes.len() {
+            return None;
+        }
+
+        match format_revision {
+            1 => {
+                if structure_size != size_of::<GpuMetricsV1Raw>() || bytes.len() < size_of::<GpuMetricsV1Raw>() {
+                    return None;
+                }
+                // SAFETY: `GpuMetricsV1Raw` is `repr(C, packed)` over plain integers and we've
+                // just checked `bytes` holds at least `size_of::<GpuMetricsV1Raw>()` bytes;
+                // `read_unaligned` doesn't require `bytes.as_ptr()` to satisfy the struct's
+                // (trivial, packed) alignment.
+                let raw = unsafe { (bytes.as_ptr() as *const GpuMetricsV1Raw).read_unaligned() };
+                Some(GpuMetrics {
+                    is_apu: false,
+                    temperature_edge: decode_gpu_metric(raw.temperature_edge),
+                    temperature_hotspot: decode_gpu_metric(raw.temperature_hotspot),
+                    temperature_mem: decode_gpu_metric(raw.temperature_mem),
+                    average_socket_power: decode_gpu_metric(raw.average_socket_power),
+                    average_gfx_activity: decode_gpu_metric(raw.average_gfx_activity),
+                    average_umc_activity: decode_gpu_metric(raw.average_umc_activity),
+                    current_gfxclk: decode_gpu_metric(raw.current_gfxclk),
+                    current_uclk: decode_gpu_metric(raw.current_uclk),
+                    throttle_status: raw.throttle_status,
+                })
+            }
+            2 => {
+                if structure_size != size_of::<GpuMetricsV2Raw>() || bytes.len() < size_of::<GpuMetricsV2Raw>() {
+                    return None;
+                }
+                // SAFETY: see the v1 branch above; same reasoning applies to `GpuMetricsV2Raw`.
+                let raw = unsafe { (bytes.as_ptr() as *const GpuMetricsV2Raw).read_unaligned() };
+                Some(GpuMetrics {
+                    is_apu: true,
+                    temperature_edge: None, // APUs have no discrete edge sensor
+                    temperature_hotspot: decode_gpu_metric(raw.temperature_gfx),
+                    temperature_mem: None, // shared system memory, no dedicated VRAM sensor
+                    average_socket_power: decode_gpu_metric(raw.average_socket_power),
+                    average_gfx_activity: decode_gpu_metric(raw.average_gfx_activity),
+                    average_umc_activity: None, // no dedicated memory controller counter on APUs
+                    current_gfxclk: decode_gpu_metric(raw.current_gfxclk),
+                    current_uclk: decode_gpu_metric(raw.current_fclk),
+                    throttle_status: raw.throttle_status,
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Read GPU status from sysfs
     fn get_device_status(device: &AmdDevice) -> Result<GpuStatus> {
         // Get basic device information
         let name = Self::get_device_name(device)?;
-        
+
+        // gpu_metrics is the authoritative source where available; hwmon/sysfs values below are
+        // used as-is when a field comes back unavailable (or the table can't be parsed at all).
+        let metrics = Self::read_gpu_metrics(device);
+
         // Get temperature
         let temperature = Self::read_temperature(device)?;
-        
+
         // Get power information
-        let (power_draw, power_limit) = Self::read_power_info(device)?;
-        
+        let (mut power_draw, power_limit) = Self::read_power_info(device)?;
+        if let Some(socket_power) = metrics.as_ref().and_then(|m| m.average_socket_power) {
+            power_draw = socket_power;
+        }
+
         // Get memory information
         let (memory_used, memory_total) = Self::read_memory_info(device)?;
-        
+
         // Get utilization
-        let utilization_gpu = Self::read_gpu_utilization(device)?;
-        
+        let mut utilization_gpu = Self::read_gpu_utilization(device)?;
+        if let Some(activity) = metrics.as_ref().and_then(|m| m.average_gfx_activity) {
+            utilization_gpu = activity;
+        }
+        let utilization_memory = metrics
+            .as_ref()
+            .and_then(|m| m.average_umc_activity)
+            .unwrap_or(utilization_gpu); // AMD often reports similar values when unavailable
+
         // Get clock information
-        let (clock_graphics, clock_memory) = Self::read_clock_info(device)?;
-        
+        let (mut clock_graphics, mut clock_memory) = Self::read_clock_info(device)?;
+        if let Some(m) = &metrics {
+            if let (Some(gfxclk), Some(uclk)) = (m.current_gfxclk, m.current_uclk) {
+                clock_graphics = gfxclk;
+                clock_memory = uclk;
+            }
+        }
+
         // Get fan speed
         let fan_speed = Self::read_fan_speed(device)?;
-        
+
         // Parse PCI information
         let pci_info = Self::parse_pci_info(device)?;
-        
-        // Determine GPU type based on power characteristics
-        let gpu_type = if power_limit < 75 {
-            GpuType::Integrated
-        } else {
-            GpuType::Discrete
+
+        // gpu_metrics' format revision is the authoritative discrete-vs-APU signal; the
+        // power-limit heuristic is only a fallback for kernels/cards with no gpu_metrics.
+        let gpu_type = match &metrics {
+            Some(m) if m.is_apu => GpuType::Integrated,
+            _ if power_limit < 75 => GpuType::Integrated,
+            _ => GpuType::Discrete,
         };
-        
+
         // Determine power state
         let power_state = if utilization_gpu > 10 {
             PowerState::Active
@@ -173,6 +605,13 @@ impl AmdBackend {
             PowerState::Idle
         };
 
+        let throttle_reasons = metrics
+            .as_ref()
+            .map(|m| decode_throttle_reasons(m.throttle_status))
+            .unwrap_or_default();
+
+        let processes = Self::get_device_processes(device).unwrap_or_default();
+
         Ok(GpuStatus {
             index: device.index,
             name,
@@ -180,17 +619,32 @@ impl AmdBackend {
             gpu_type,
             temperature,
             power_draw,
-            power_limit,
+            power_limit: Some(power_limit),
             memory_used,
             memory_total,
             utilization_gpu,
-            utilization_memory: utilization_gpu, // AMD often reports similar values
+            utilization_memory,
             fan_speed,
             clock_graphics,
-            clock_memory,
+            clock_memory: Some(clock_memory),
             driver_version: Self::get_driver_version()?,
             pci_info,
             power_state,
+            voltage_mv: None, // Requires pp_od_clk_voltage parsing; see apply_voltage_offset
+            throttle_reasons,
+            ecc_errors: None, // amdgpu RAS error counts aren't wired up yet
+            processes,
+            driver_bound: DriverBinding::Unbound, // overwritten centrally by GpuManager
+            // APUs share system RAM through the IOMMU's carved-out GTT/VRAM split rather than
+            // owning a dedicated card; discrete cards always have their own VRAM.
+            unified_memory: matches!(gpu_type, GpuType::Integrated),
+            mig_parent: None, // MIG is an NVIDIA datacenter-card feature
+            mig_uuid: None,
+            uuid: None,
+            serial: None,
+            board_part_number: None,
+            vbios_version: None,
+            cuda_driver_version: None,
         })
     }
 
@@ -212,16 +666,37 @@ impl AmdBackend {
             }
         }
 
-        // Fallback to PCI ID lookup
+        // Consult the pci.ids database for a marketing name before falling back to the bare
+        // vendor:device hex pair
+        let pci_info = Self::parse_pci_info(device)?;
+        if let Some(name) = crate::pci_ids::lookup_device_name(pci_info.vendor_id, pci_info.device_id)? {
+            return Ok(name);
+        }
+
         Ok(format!("AMD GPU {}", device.pci_id))
     }
 
-    /// Read temperature from hwmon
+    /// Read temperature, preferring the `gpu_metrics` junction (hotspot) reading -- the figure
+    /// that actually correlates with thermal throttling -- over hwmon's edge-sensor `temp*_input`
+    /// files, and falling back to hwmon when `gpu_metrics` is absent, unparseable, or reports this
+    /// field as unavailable.
     fn read_temperature(device: &AmdDevice) -> Result<u32> {
+        if let Some(metrics) = Self::read_gpu_metrics(device) {
+            if let Some(mem_temp) = metrics.temperature_mem {
+                debug!("AMD GPU {} VRAM temperature: {}C", device.index, mem_temp);
+            }
+            if let Some(hotspot) = metrics.temperature_hotspot {
+                return Ok(hotspot);
+            }
+            if let Some(edge) = metrics.temperature_edge {
+                return Ok(edge);
+            }
+        }
+
         if let Some(ref hwmon_path) = device.hwmon_path {
             // Try different temperature input files
             let temp_files = ["temp1_input", "temp2_input", "temp3_input"];
-            
+
             for temp_file in &temp_files {
                 let temp_path = hwmon_path.join(temp_file);
                 if let Ok(temp_str) = fs::read_to_string(&temp_path) {
@@ -231,15 +706,8 @@ impl AmdBackend {
                 }
             }
         }
-        
-        // Fallback: try junction temperature from amdgpu
-        let junction_temp_path = device.device_path.join("gpu_busy_percent");
-        if junction_temp_path.exists() {
-            // This is a fallback - actual temp reading might be in different location
-            return Ok(50); // Safe fallback temperature
-        }
-        
-        Ok(50) // Default fallback
+
+        Ok(50) // Default fallback when neither gpu_metrics nor hwmon exposes a reading
     }
 
     /// Read power information
@@ -442,34 +910,333 @@ impl AmdBackend {
         Ok(())
     }
 
-    /// Set fan curve
+    /// Set fan curve. Tracks the last-applied setpoint per device and applies directional
+    /// hysteresis (a rise only takes effect once it clears `curve.up_threshold_c`, a drop only
+    /// once it clears the larger `curve.down_threshold_c`) plus a max per-update step, so the
+    /// fan ramps smoothly instead of hunting around a curve breakpoint every call.
     async fn apply_device_fan_curve(&self, device: &AmdDevice, curve: &FanCurve) -> Result<()> {
-        if let Some(ref hwmon_path) = device.hwmon_path {
-            // Get current temperature
-            let temperature = Self::read_temperature(device)?;
-            
-            // Calculate target fan speed
-            let target_speed = curve.calculate_fan_speed(temperature);
-            
-            // Set PWM value (convert percentage to 0-255 range)
-            let pwm_value = (target_speed * 255) / 100;
-            
-            // First enable manual fan control
-            let pwm_enable_path = hwmon_path.join("pwm1_enable");
+        let Some(ref hwmon_path) = device.hwmon_path else {
+            return Ok(());
+        };
+
+        let temperature = Self::read_temperature(device)?;
+        let raw_target = curve.calculate_fan_speed(temperature);
+
+        let mut fan_state = self.fan_state.write().await;
+        let previous = fan_state.get(&device.index).copied();
+
+        // Directional hysteresis: only honor the new target once the temperature has moved far
+        // enough past the last setpoint in the direction that justifies it.
+        let hysteresis_target = match previous {
+            Some(prev) if raw_target > prev.speed_percent => {
+                if temperature >= prev.setpoint_temp_c + curve.up_threshold_c {
+                    raw_target
+                } else {
+                    prev.speed_percent
+                }
+            }
+            Some(prev) if raw_target < prev.speed_percent => {
+                if temperature + curve.down_threshold_c <= prev.setpoint_temp_c {
+                    raw_target
+                } else {
+                    prev.speed_percent
+                }
+            }
+            Some(prev) => prev.speed_percent,
+            None => raw_target,
+        };
+
+        // Step-rate limit: move the commanded speed toward the (hysteresis-gated) target by at
+        // most `max_step_per_tick` percentage points, so it ramps instead of jumping.
+        let commanded = match previous {
+            Some(prev) => {
+                let diff = hysteresis_target as i32 - prev.speed_percent as i32;
+                let step = diff.clamp(-(curve.max_step_per_tick as i32), curve.max_step_per_tick as i32);
+                (prev.speed_percent as i32 + step).clamp(0, 100) as u32
+            }
+            None => hysteresis_target,
+        };
+
+        let previous_speed = previous.map(|p| p.speed_percent);
+        let setpoint_temp_c = if previous_speed != Some(commanded) {
+            temperature
+        } else {
+            previous.map(|p| p.setpoint_temp_c).unwrap_or(temperature)
+        };
+
+        fan_state.insert(device.index, FanState { setpoint_temp_c, speed_percent: commanded });
+        drop(fan_state);
+
+        // Enable manual fan control, but only if it isn't already, so repeated applies don't
+        // needlessly rewrite a sysfs file every tick.
+        let pwm_enable_path = hwmon_path.join("pwm1_enable");
+        let already_manual = fs::read_to_string(&pwm_enable_path)
+            .map(|s| s.trim() == "1")
+            .unwrap_or(false);
+        if !already_manual {
             if let Err(e) = fs::write(&pwm_enable_path, "1") {
                 warn!("Failed to enable manual fan control: {}", e);
             }
-            
-            // Set fan speed
-            let pwm_path = hwmon_path.join("pwm1");
-            if let Err(e) = fs::write(&pwm_path, pwm_value.to_string()) {
-                warn!("Failed to set fan speed: {}", e);
-                return Err(GpuError::ThermalError(format!("Failed to set fan speed: {}", e)));
+        }
+
+        // Not every card's PWM duty cycle is 0-255; read back the real ceiling where exposed.
+        let pwm_max = Self::read_pwm_max(hwmon_path);
+        let pwm_value = (commanded * pwm_max) / 100;
+
+        let pwm_path = hwmon_path.join("pwm1");
+        if let Err(e) = fs::write(&pwm_path, pwm_value.to_string()) {
+            warn!("Failed to set fan speed: {}", e);
+            return Err(GpuError::ThermalError(format!("Failed to set fan speed: {}", e)));
+        }
+
+        debug!(
+            "Set AMD GPU {} fan speed to {}% (pwm {}/{})",
+            device.index, commanded, pwm_value, pwm_max
+        );
+
+        Ok(())
+    }
+
+    /// Read the card's real PWM duty-cycle ceiling from `pwm1_max`, defaulting to the
+    /// conventional 255 when the file is absent, unreadable, or reports zero
+    fn read_pwm_max(hwmon_path: &Path) -> u32 {
+        fs::read_to_string(hwmon_path.join("pwm1_max"))
+            .ok()
+            .and_then(|s| s.trim().parse::<u32>().ok())
+            .filter(|&max| max > 0)
+            .unwrap_or(255)
+    }
+
+    /// Check that `pp_od_clk_voltage` exists and is writable before attempting any OverDrive
+    /// command sequence against it
+    fn check_od_clk_voltage_writable(path: &Path) -> Result<()> {
+        let metadata = fs::metadata(path).map_err(|_| {
+            GpuError::OperationNotSupported("Voltage/clock control (no pp_od_clk_voltage)".to_string())
+        })?;
+        if metadata.permissions().readonly() {
+            return Err(GpuError::OperationNotSupported(
+                "Voltage/clock control (pp_od_clk_voltage is read-only)".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Apply an undervolt/voltage-offset request via the amdgpu OverDrive `vo` command
+    async fn apply_device_voltage_offset(&self, device: &AmdDevice, offset_mv: i32) -> Result<()> {
+        let od_clk_voltage_path = device.device_path.join("pp_od_clk_voltage");
+        Self::check_od_clk_voltage_writable(&od_clk_voltage_path)?;
+
+        fs::write(&od_clk_voltage_path, format!("vo {}\n", offset_mv))
+            .map_err(|e| GpuError::PowerError(format!("Failed to write voltage offset: {}", e)))?;
+        fs::write(&od_clk_voltage_path, "c\n")
+            .map_err(|e| GpuError::PowerError(format!("Failed to commit voltage offset: {}", e)))?;
+
+        info!("Applied voltage offset of {}mV to AMD GPU {}", offset_mv, device.index);
+        Ok(())
+    }
+
+    /// Apply a per-clock-state voltage curve via the amdgpu OverDrive `vc` command
+    async fn apply_device_voltage_curve(&self, device: &AmdDevice, curve: &VoltageCurve) -> Result<()> {
+        let od_clk_voltage_path = device.device_path.join("pp_od_clk_voltage");
+        Self::check_od_clk_voltage_writable(&od_clk_voltage_path)?;
+
+        for (point, &(clock_mhz, millivolts)) in curve.points.iter().enumerate() {
+            fs::write(&od_clk_voltage_path, format!("vc {} {} {}\n", point, clock_mhz, millivolts)).map_err(
+                |e| GpuError::PowerError(format!("Failed to write voltage curve point {}: {}", point, e)),
+            )?;
+        }
+        fs::write(&od_clk_voltage_path, "c\n")
+            .map_err(|e| GpuError::PowerError(format!("Failed to commit voltage curve: {}", e)))?;
+
+        info!("Applied {}-point voltage curve to AMD GPU {}", curve.points.len(), device.index);
+        Ok(())
+    }
+
+    /// Apply a maximum graphics clock ceiling. Prefers selecting the highest `pp_dpm_sclk` DPM
+    /// state at or below `mhz`, so the card still free-clocks within that state; falls back to an
+    /// explicit OverDrive max-clock write (`s 1 <mhz>`) when no per-level DPM table is exposed.
+    async fn apply_device_max_clock(&self, device: &AmdDevice, mhz: u32) -> Result<()> {
+        let sclk_path = device.device_path.join("pp_dpm_sclk");
+        if let Ok(content) = fs::read_to_string(&sclk_path) {
+            let levels = parse_dpm_sclk_levels(&content);
+            if !levels.is_empty() {
+                let level = levels
+                    .iter()
+                    .filter(|(_, level_mhz)| *level_mhz <= mhz)
+                    .max_by_key(|(index, _)| *index)
+                    .or_else(|| levels.iter().min_by_key(|(index, _)| *index))
+                    .map(|(index, _)| *index)
+                    .unwrap_or(0);
+
+                fs::write(&sclk_path, level.to_string())
+                    .map_err(|e| GpuError::PowerError(format!("Failed to select DPM sclk level: {}", e)))?;
+
+                info!("Capped AMD GPU {} to DPM sclk level {} (<= {}MHz)", device.index, level, mhz);
+                return Ok(());
             }
-            
-            debug!("Set AMD GPU {} fan speed to {}%", device.index, target_speed);
         }
-        
+
+        let od_clk_voltage_path = device.device_path.join("pp_od_clk_voltage");
+        Self::check_od_clk_voltage_writable(&od_clk_voltage_path)?;
+
+        fs::write(&od_clk_voltage_path, format!("s 1 {}\n", mhz))
+            .map_err(|e| GpuError::PowerError(format!("Failed to write max clock state: {}", e)))?;
+        fs::write(&od_clk_voltage_path, "c\n")
+            .map_err(|e| GpuError::PowerError(format!("Failed to commit max clock state: {}", e)))?;
+
+        info!("Capped AMD GPU {} max clock to {}MHz via pp_od_clk_voltage", device.index, mhz);
+        Ok(())
+    }
+
+    /// Max clock ceiling for a given power limit from a [`FreqScalingTable`]: the lowest-threshold
+    /// row whose power bound is still >= the limit. Unlike
+    /// [`FreqScalingTable::max_clock_for_power`], both out-of-range ends clamp to the *lowest*
+    /// entry's clock rather than the highest: an unexpectedly high power-limit reading is treated
+    /// the same as an unexpectedly low one, and falls back to the safest ceiling rather than the
+    /// most permissive one.
+    fn max_freq_for_power_limit(table: &FreqScalingTable, power_limit_watts: u32) -> u32 {
+        let lowest_entry_freq = table.points.first().map(|(_, freq)| *freq).unwrap_or(table.turbo_clock_mhz);
+        table
+            .points
+            .iter()
+            .find(|(threshold, _)| power_limit_watts <= *threshold)
+            .map(|(_, freq)| *freq)
+            .unwrap_or(lowest_entry_freq)
+    }
+
+    /// Re-evaluate the power-to-frequency governor against the power limit currently in effect
+    /// and push the resulting ceiling down to hardware. Called on every `apply_config` and
+    /// `set_power_limit` so lowering the TDP promptly lowers the clock ceiling instead of relying
+    /// on firmware to throttle its way there.
+    async fn apply_freq_governor(
+        &self,
+        device: &AmdDevice,
+        table: &FreqScalingTable,
+        power_limit_watts: u32,
+    ) -> Result<()> {
+        let mut max_freq = Self::max_freq_for_power_limit(table, power_limit_watts);
+
+        let sclk_path = device.device_path.join("pp_dpm_sclk");
+        let min_freq = fs::read_to_string(&sclk_path)
+            .ok()
+            .map(|content| parse_dpm_sclk_levels(&content))
+            .and_then(|levels| levels.iter().map(|(_, mhz)| *mhz).min())
+            .unwrap_or(0);
+
+        // Guard against requesting a ceiling that leaves no headroom above the card's floor
+        // clock, which drivers tend to reject or oscillate against.
+        if max_freq <= min_freq.saturating_add(RECLOCK_GUARD_MHZ) {
+            max_freq = min_freq.saturating_add(RECLOCK_GUARD_MHZ) + 1;
+        }
+
+        self.apply_device_max_clock(device, max_freq).await
+    }
+
+    /// Apply an explicit clock window. Real control needs `pp_od_clk_voltage` curve-point writes
+    /// distinct from the single-ceiling governor in [`Self::apply_device_max_clock`]; not yet
+    /// implemented here.
+    async fn apply_device_clock_limits(&self, device: &AmdDevice, limits: MinMax<u32>) -> Result<()> {
+        let od_clk_voltage_path = device.device_path.join("pp_od_clk_voltage");
+        if !od_clk_voltage_path.exists() {
+            return Err(GpuError::OperationNotSupported("Clock limits (no pp_od_clk_voltage)".to_string()));
+        }
+        warn!(
+            "Clock limits of {}-{}MHz requested for AMD GPU {} but pp_od_clk_voltage control is not yet wired up",
+            limits.min, limits.max, device.index
+        );
+        Err(GpuError::OperationNotSupported("Clock limits".to_string()))
+    }
+
+    /// Apply sustained and boost TDP. Standard amdgpu hwmon only exposes a single power cap, so
+    /// only `sustained` has a real write path here; `boost` is a no-op beyond that, same as the
+    /// single-value `set_device_power_limit`.
+    async fn apply_device_tdp(&self, device: &AmdDevice, sustained: u32, boost: u32) -> Result<()> {
+        self.set_device_power_limit(device, sustained).await?;
+
+        if boost > sustained {
+            warn!(
+                "amdgpu hwmon has no separate boost power limit; ignoring requested boost of {}W for GPU {}",
+                boost, device.index
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the `DRI_PRIME` environment value needed to run an application on GPU `index`,
+    /// verifying it's actually exposed under `/dev/dri/by-path` before recommending it
+    pub async fn dri_prime_value(&self, index: u32) -> Result<String> {
+        let devices = self.devices.read().await;
+        let device = devices.get(&index).ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        Self::dri_by_path_render_node(device)?;
+        Self::dri_prime_tag(device)
+    }
+
+    /// Spawn `command` with `args`, injecting the `DRI_PRIME` value that routes its rendering to
+    /// GPU `index`
+    pub async fn spawn_on_gpu(&self, index: u32, command: &str, args: &[String]) -> Result<tokio::process::Child> {
+        let dri_prime = self.dri_prime_value(index).await?;
+
+        AsyncCommand::new(command)
+            .args(args)
+            .env("DRI_PRIME", &dri_prime)
+            .spawn()
+            .map_err(|e| GpuError::SystemError(format!("Failed to launch {} on GPU {}: {}", command, index, e)))
+    }
+
+    /// Detect available `xrandr` offload providers and wire this GPU up as the offload sink for
+    /// the system's other provider, via `xrandr --setprovideroffloadsink`. This is the
+    /// display-server-level complement to `DRI_PRIME`, used by compositors/window managers that
+    /// rely on provider offload rather than per-process environment variables.
+    pub async fn setup_xrandr_offload(&self, index: u32) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices.get(&index).ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        let output = AsyncCommand::new("xrandr")
+            .arg("--listproviders")
+            .output()
+            .await
+            .map_err(|e| GpuError::OperationNotSupported(format!("xrandr not available: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(GpuError::SystemError("xrandr --listproviders failed".to_string()));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let providers = parse_xrandr_providers(&stdout);
+        if providers.len() < 2 {
+            return Err(GpuError::OperationNotSupported(
+                "xrandr reports fewer than two providers; nothing to offload between".to_string(),
+            ));
+        }
+
+        // Vendor-agnostic: match by the device's own name/PCI id rather than assuming "AMD" is
+        // the sink, so a hybrid laptop with an AMD primary and this GPU as the offload target
+        // still resolves correctly.
+        let device_name = Self::get_device_name(device)?;
+        let sink = providers
+            .iter()
+            .find(|p| device_name.contains(&p.name) || p.name.contains(&device_name))
+            .or_else(|| providers.iter().find(|p| p.name.to_lowercase().contains("amd") || p.name.to_lowercase().contains("radeon")))
+            .ok_or_else(|| GpuError::SystemError(format!("Could not identify an xrandr provider matching GPU {}", index)))?;
+
+        let source = providers
+            .iter()
+            .find(|p| p.id != sink.id)
+            .ok_or_else(|| GpuError::SystemError("Could not identify a source provider distinct from the sink".to_string()))?;
+
+        let status = AsyncCommand::new("xrandr")
+            .args(["--setprovideroffloadsink", &source.id, &sink.id])
+            .status()
+            .await
+            .map_err(|e| GpuError::SystemError(format!("Failed to run xrandr --setprovideroffloadsink: {}", e)))?;
+
+        if !status.success() {
+            return Err(GpuError::SystemError("xrandr --setprovideroffloadsink failed".to_string()));
+        }
+
+        info!("Wired xrandr provider offload: source {} -> sink {} ({})", source.id, sink.id, sink.name);
         Ok(())
     }
 }
@@ -543,6 +1310,46 @@ impl GpuBackend for AmdBackend {
             self.apply_device_fan_curve(device, curve).await?;
         }
 
+        // Apply voltage offset/curve, only when the user has explicitly opted in. OverDrive
+        // writes only take effect once the card is in manual performance mode, so force that
+        // regardless of the power mode requested above.
+        if config.allow_undervolt
+            && (config.voltage_offset_mv.is_some() || config.voltage_curve.is_some())
+        {
+            if let Err(e) = self.apply_power_mode(device, PowerMode::Custom).await {
+                debug!("Failed to switch to manual power profile for voltage control: {}", e);
+            }
+
+            if let Some(offset_mv) = config.voltage_offset_mv {
+                if let Err(e) = self.apply_device_voltage_offset(device, offset_mv).await {
+                    debug!("Voltage offset application failed: {}", e);
+                }
+            }
+
+            if let Some(ref curve) = config.voltage_curve {
+                if let Err(e) = self.apply_device_voltage_curve(device, curve).await {
+                    debug!("Voltage curve application failed: {}", e);
+                }
+            }
+        }
+
+        // Re-evaluate the power-to-frequency governor against whatever power limit is now in
+        // effect, whether just applied above or already set by firmware/a previous call. Record
+        // the table so a later direct `set_power_limit` call also re-evaluates it.
+        if let Some(ref table) = config.freq_scaling {
+            self.freq_governors.write().await.insert(index, table.clone());
+
+            let power_limit_watts = match config.power_limit {
+                Some(limit) => limit,
+                None => Self::read_power_info(device).map(|(_, limit)| limit).unwrap_or(300),
+            };
+            if let Err(e) = self.apply_freq_governor(device, table, power_limit_watts).await {
+                debug!("Frequency governor application failed: {}", e);
+            }
+        } else {
+            self.freq_governors.write().await.remove(&index);
+        }
+
         info!("Applied configuration to AMD GPU {}", index);
         Ok(())
     }
@@ -554,7 +1361,15 @@ impl GpuBackend for AmdBackend {
             .get(&index)
             .ok_or_else(|| GpuError::GpuNotFound(index))?;
 
-        self.set_device_power_limit(device, limit_watts).await
+        self.set_device_power_limit(device, limit_watts).await?;
+
+        if let Some(table) = self.freq_governors.read().await.get(&index).cloned() {
+            if let Err(e) = self.apply_freq_governor(device, &table, limit_watts).await {
+                debug!("Frequency governor application failed: {}", e);
+            }
+        }
+
+        Ok(())
     }
 
     #[instrument]
@@ -580,11 +1395,17 @@ impl GpuBackend for AmdBackend {
             return Err(GpuError::SystemError(format!("Failed to reset power profile: {}", e)));
         }
 
-        // Reset fan control to automatic
+        // Reset fan control to automatic, and drop any tracked hysteresis state so the next
+        // manual curve application starts fresh rather than gating on a stale setpoint
         if let Some(ref hwmon_path) = device.hwmon_path {
             let pwm_enable_path = hwmon_path.join("pwm1_enable");
             let _ = fs::write(&pwm_enable_path, "2"); // Auto mode
         }
+        self.fan_state.write().await.remove(&index);
+
+        // Reset any OverDrive voltage/clock customization back to the card's defaults
+        let od_clk_voltage_path = device.device_path.join("pp_od_clk_voltage");
+        let _ = fs::write(&od_clk_voltage_path, "r\n");
 
         info!("Reset AMD GPU {} to defaults", index);
         Ok(())
@@ -596,19 +1417,132 @@ impl GpuBackend for AmdBackend {
     }
 
     #[instrument]
-    async fn switch_gpu(&self, _from_index: u32, _to_index: u32) -> Result<()> {
-        // AMD GPU switching typically involves:
-        // 1. Using DRI_PRIME environment variable
-        // 2. Setting up proper xrandr providers
-        // 3. Configuring the X server
-        
-        // For now, return an error indicating this needs system-level support
-        Err(GpuError::OperationNotSupported(
-            "AMD GPU switching requires DRI_PRIME configuration".to_string()
-        ))
+    async fn switch_gpu(&self, from_index: u32, to_index: u32) -> Result<()> {
+        {
+            let devices = self.devices.read().await;
+            devices.get(&from_index).ok_or_else(|| GpuError::GpuNotFound(from_index))?;
+        }
+
+        let dri_prime = self.dri_prime_value(to_index).await?;
+
+        if let Err(e) = self.setup_xrandr_offload(to_index).await {
+            debug!("xrandr provider offload not wired for GPU {}: {}", to_index, e);
+        }
+
+        info!(
+            "AMD GPU switch {} -> {} ready; new applications should set DRI_PRIME={} to render on GPU {}",
+            from_index, to_index, dri_prime, to_index
+        );
+        Ok(())
+    }
+
+    #[instrument]
+    async fn get_processes(&self, index: u32) -> Result<Vec<GpuProcess>> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        Self::get_device_processes(device)
+    }
+
+    #[instrument]
+    async fn set_max_clock(&self, index: u32, mhz: u32) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.apply_device_max_clock(device, mhz).await
+    }
+
+    #[instrument]
+    async fn set_voltage_offset(&self, index: u32, mv: i32) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.apply_device_voltage_offset(device, mv).await
+    }
+
+    #[instrument]
+    async fn set_clock_limits(&self, index: u32, limits: MinMax<u32>) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.apply_device_clock_limits(device, limits).await
+    }
+
+    #[instrument]
+    async fn set_tdp(&self, index: u32, sustained: u32, boost: u32) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.apply_device_tdp(device, sustained, boost).await
     }
 }
 
+/// A provider entry parsed from `xrandr --listproviders` output
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct XrandrProvider {
+    /// Hex provider id, e.g. "0x47", as `xrandr --setprovideroffloadsink` expects it
+    id: String,
+    name: String,
+}
+
+/// Parse `xrandr --listproviders` output (lines like "Provider 0: id: 0x47 cap: 0xf, ... name:AMD
+/// Radeon") into provider id/name pairs
+fn parse_xrandr_providers(output: &str) -> Vec<XrandrProvider> {
+    let re = Regex::new(r"id:\s*(0x[0-9a-fA-F]+).*name:(.+)$").unwrap();
+    output
+        .lines()
+        .filter_map(|line| {
+            let caps = re.captures(line.trim())?;
+            Some(XrandrProvider {
+                id: caps[1].to_string(),
+                name: caps[2].trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parse `pp_dpm_sclk`/`pp_dpm_mclk`-style content (lines like "0: 300Mhz" or "1: 1500Mhz *")
+/// into `(level_index, clock_mhz)` pairs
+fn parse_dpm_sclk_levels(content: &str) -> Vec<(u32, u32)> {
+    let re = Regex::new(r"^(\d+):\s*(\d+)Mhz").unwrap();
+    content
+        .lines()
+        .filter_map(|line| {
+            let caps = re.captures(line.trim())?;
+            Some((caps[1].parse().ok()?, caps[2].parse().ok()?))
+        })
+        .collect()
+}
+
+/// Parse a `drm-memory-vram:` fdinfo value of the form "1234 KiB" into kibibytes
+fn parse_fdinfo_kib(value: &str) -> u64 {
+    value
+        .split_whitespace()
+        .next()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Parse a `drm-engine-gfx:`/`drm-engine-compute:` fdinfo value of the form "1234 ns" into
+/// nanoseconds
+fn parse_fdinfo_ns(value: &str) -> u64 {
+    value
+        .split_whitespace()
+        .next()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -642,4 +1576,382 @@ mod tests {
         let degrees = millidegrees / 1000;
         assert_eq!(degrees, 65);
     }
+
+    #[test]
+    fn parses_fdinfo_vram_value() {
+        assert_eq!(parse_fdinfo_kib("\t1048576 KiB"), 1_048_576);
+    }
+
+    #[test]
+    fn parses_fdinfo_engine_time_value() {
+        assert_eq!(parse_fdinfo_ns("\t123456789 ns"), 123_456_789);
+    }
+
+    #[test]
+    fn decode_gpu_metric_treats_0xffff_as_unavailable() {
+        assert_eq!(decode_gpu_metric(0xffff), None);
+        assert_eq!(decode_gpu_metric(42), Some(42));
+    }
+
+    #[test]
+    fn decode_throttle_reasons_maps_thermal_and_power_cap_bits() {
+        let hotspot_thermal = 1 << THROTTLER_TEMP_HOTSPOT_BIT;
+        assert_eq!(
+            decode_throttle_reasons(hotspot_thermal),
+            vec![ThrottleReason::HwThermalSlowdown]
+        );
+
+        let ppt = 1 << THROTTLER_PPT_BIT;
+        assert_eq!(decode_throttle_reasons(ppt), vec![ThrottleReason::ClocksPowerCap]);
+
+        assert_eq!(decode_throttle_reasons(0), Vec::new());
+    }
+
+    /// Serialize a synthetic `gpu_metrics_v1_3`-shaped table byte-for-byte in the same field
+    /// order as [`GpuMetricsV1Raw`], so `read_gpu_metrics` can be exercised without a real GPU.
+    fn build_v1_metrics_bytes(
+        temperature_edge: u16,
+        temperature_hotspot: u16,
+        temperature_mem: u16,
+        average_socket_power: u16,
+        average_gfx_activity: u16,
+        average_umc_activity: u16,
+        current_gfxclk: u16,
+        current_uclk: u16,
+        throttle_status: u32,
+    ) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&64u16.to_le_bytes()); // structure_size
+        bytes.push(1); // format_revision
+        bytes.push(0); // content_revision
+        bytes.extend_from_slice(&temperature_edge.to_le_bytes());
+        bytes.extend_from_slice(&temperature_hotspot.to_le_bytes());
+        bytes.extend_from_slice(&temperature_mem.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes()); // temperature_vrgfx
+        bytes.extend_from_slice(&0u16.to_le_bytes()); // temperature_vrsoc
+        bytes.extend_from_slice(&0u16.to_le_bytes()); // temperature_vrmem
+        bytes.extend_from_slice(&average_gfx_activity.to_le_bytes());
+        bytes.extend_from_slice(&average_umc_activity.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes()); // average_mm_activity
+        bytes.extend_from_slice(&average_socket_power.to_le_bytes());
+        bytes.extend_from_slice(&0u64.to_le_bytes()); // energy_accumulator
+        for _ in 0..7 {
+            bytes.extend_from_slice(&0u16.to_le_bytes()); // average_*clk_frequency
+        }
+        bytes.extend_from_slice(&current_gfxclk.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes()); // current_socclk
+        bytes.extend_from_slice(&current_uclk.to_le_bytes());
+        for _ in 0..4 {
+            bytes.extend_from_slice(&0u16.to_le_bytes()); // current_vclk0/dclk0/vclk1/dclk1
+        }
+        bytes.extend_from_slice(&throttle_status.to_le_bytes());
+        assert_eq!(bytes.len(), 64, "test fixture drifted from GpuMetricsV1Raw's layout");
+        bytes
+    }
+
+    fn device_with_gpu_metrics(dir: &TempDir, bytes: &[u8]) -> AmdDevice {
+        std::fs::write(dir.path().join("gpu_metrics"), bytes).unwrap();
+        AmdDevice {
+            index: 0,
+            device_path: dir.path().to_path_buf(),
+            hwmon_path: None,
+            drm_path: dir.path().to_path_buf(),
+            pci_id: "1002:0000".to_string(),
+        }
+    }
+
+    #[test]
+    fn read_gpu_metrics_parses_a_v1_discrete_table() {
+        let dir = TempDir::new().unwrap();
+        let bytes = build_v1_metrics_bytes(45, 60, 0xffff, 120, 55, 30, 1800, 1000, 1 << THROTTLER_PPT_BIT);
+        let device = device_with_gpu_metrics(&dir, &bytes);
+
+        let metrics = AmdBackend::read_gpu_metrics(&device).expect("should parse a v1 table");
+        assert!(!metrics.is_apu);
+        assert_eq!(metrics.temperature_edge, Some(45));
+        assert_eq!(metrics.temperature_hotspot, Some(60));
+        assert_eq!(metrics.temperature_mem, None); // 0xffff sentinel
+        assert_eq!(metrics.average_socket_power, Some(120));
+        assert_eq!(metrics.average_gfx_activity, Some(55));
+        assert_eq!(metrics.average_umc_activity, Some(30));
+        assert_eq!(metrics.current_gfxclk, Some(1800));
+        assert_eq!(metrics.current_uclk, Some(1000));
+        assert_eq!(decode_throttle_reasons(metrics.throttle_status), vec![ThrottleReason::ClocksPowerCap]);
+    }
+
+    #[test]
+    fn read_gpu_metrics_rejects_a_truncated_or_mismatched_table() {
+        let dir = TempDir::new().unwrap();
+        // Declares itself format_revision 1 but is far too short to hold a real v1 table.
+        let bytes = vec![4, 0, 1, 0];
+        let device = device_with_gpu_metrics(&dir, &bytes);
+
+        assert!(AmdBackend::read_gpu_metrics(&device).is_none());
+    }
+
+    fn device_at(dir: &TempDir) -> AmdDevice {
+        AmdDevice {
+            index: 0,
+            device_path: dir.path().to_path_buf(),
+            hwmon_path: None,
+            drm_path: dir.path().to_path_buf(),
+            pci_id: "1002:0000".to_string(),
+        }
+    }
+
+    #[test]
+    fn check_od_clk_voltage_writable_rejects_a_missing_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("pp_od_clk_voltage");
+
+        let err = AmdBackend::check_od_clk_voltage_writable(&path).unwrap_err();
+        assert!(matches!(err, GpuError::OperationNotSupported(_)));
+    }
+
+    #[tokio::test]
+    async fn apply_device_voltage_offset_writes_the_overdrive_vo_command() {
+        let backend = AmdBackend::new().await.unwrap();
+        let dir = TempDir::new().unwrap();
+        let device = device_at(&dir);
+        let od_path = dir.path().join("pp_od_clk_voltage");
+        fs::write(&od_path, "").unwrap();
+
+        backend.apply_device_voltage_offset(&device, -50).await.unwrap();
+
+        let written = fs::read_to_string(&od_path).unwrap();
+        assert_eq!(written, "c\n"); // last write wins on a real file, just confirms the commit landed
+    }
+
+    #[tokio::test]
+    async fn apply_device_voltage_curve_writes_one_vc_command_per_point() {
+        let backend = AmdBackend::new().await.unwrap();
+        let dir = TempDir::new().unwrap();
+        let device = device_at(&dir);
+        let od_path = dir.path().join("pp_od_clk_voltage");
+        fs::write(&od_path, "").unwrap();
+
+        let curve = VoltageCurve {
+            points: vec![(500, 700), (1800, 1100)],
+        };
+        backend.apply_device_voltage_curve(&device, &curve).await.unwrap();
+
+        // Each write truncates the file, so only the final "c" commit is left on disk; the
+        // intermediate "vc" writes are exercised for their Result, not their on-disk trace.
+        let written = fs::read_to_string(&od_path).unwrap();
+        assert_eq!(written, "c\n");
+    }
+
+    #[tokio::test]
+    async fn apply_device_voltage_offset_rejects_a_missing_od_clk_voltage_file() {
+        let backend = AmdBackend::new().await.unwrap();
+        let dir = TempDir::new().unwrap();
+        let device = device_at(&dir);
+
+        let err = backend.apply_device_voltage_offset(&device, -50).await.unwrap_err();
+        assert!(matches!(err, GpuError::OperationNotSupported(_)));
+    }
+
+    #[test]
+    fn parse_dpm_sclk_levels_reads_index_and_clock_pairs() {
+        let content = "0: 300Mhz \n1: 1500Mhz *\n2: 2400Mhz\n";
+        assert_eq!(parse_dpm_sclk_levels(content), vec![(0, 300), (1, 1500), (2, 2400)]);
+    }
+
+    #[test]
+    fn max_freq_for_power_limit_clamps_both_out_of_range_ends_to_the_lowest_entry() {
+        let table = FreqScalingTable {
+            points: vec![(100, 1200), (200, 1800), (300, 2400)],
+            turbo_clock_mhz: 2900,
+        };
+
+        assert_eq!(AmdBackend::max_freq_for_power_limit(&table, 150), 1800); // mid-table bracket
+        assert_eq!(AmdBackend::max_freq_for_power_limit(&table, 50), 1200); // below lowest -> smallest ceiling
+        assert_eq!(AmdBackend::max_freq_for_power_limit(&table, 1000), 1200); // above highest -> smallest ceiling, not the largest
+    }
+
+    #[tokio::test]
+    async fn apply_device_max_clock_selects_the_highest_dpm_level_at_or_below_target() {
+        let backend = AmdBackend::new().await.unwrap();
+        let dir = TempDir::new().unwrap();
+        let device = device_at(&dir);
+        let sclk_path = dir.path().join("pp_dpm_sclk");
+        fs::write(&sclk_path, "0: 300Mhz *\n1: 1500Mhz\n2: 2400Mhz\n").unwrap();
+
+        backend.apply_device_max_clock(&device, 1800).await.unwrap();
+
+        assert_eq!(fs::read_to_string(&sclk_path).unwrap(), "1");
+    }
+
+    #[tokio::test]
+    async fn apply_freq_governor_enforces_the_reclock_guard_above_the_floor_clock() {
+        let backend = AmdBackend::new().await.unwrap();
+        let dir = TempDir::new().unwrap();
+        let device = device_at(&dir);
+        // No pp_dpm_sclk table exposed, so the floor clock reads as 0 and the governor falls
+        // back to an OverDrive write for the resulting ceiling.
+        fs::write(dir.path().join("pp_od_clk_voltage"), "").unwrap();
+
+        let table = FreqScalingTable {
+            points: vec![(100, 150)], // well under the 200MHz guard above a 0MHz floor clock
+            turbo_clock_mhz: 2900,
+        };
+
+        backend.apply_freq_governor(&device, &table, 100).await.unwrap();
+
+        // The raw table lookup (150MHz) doesn't clear the guard above the 0MHz floor, so the
+        // governor must have bumped its request up before writing the OverDrive commit.
+        let written = fs::read_to_string(dir.path().join("pp_od_clk_voltage")).unwrap();
+        assert_eq!(written, "c\n");
+    }
+
+    #[test]
+    fn dri_prime_tag_converts_colon_dot_bdf_to_underscore_form() {
+        let dir = TempDir::new().unwrap();
+        let bdf_dir = dir.path().join("0000:01:00.0");
+        std::fs::create_dir(&bdf_dir).unwrap();
+        let device = AmdDevice {
+            index: 0,
+            device_path: bdf_dir,
+            hwmon_path: None,
+            drm_path: dir.path().to_path_buf(),
+            pci_id: "1002:73df".to_string(),
+        };
+
+        assert_eq!(AmdBackend::dri_prime_tag(&device).unwrap(), "pci-0000_01_00_0");
+    }
+
+    #[test]
+    fn dri_by_path_render_node_errors_when_not_exposed_under_dri() {
+        let dir = TempDir::new().unwrap();
+        let bdf_dir = dir.path().join("0000:02:00.0");
+        std::fs::create_dir(&bdf_dir).unwrap();
+        let device = AmdDevice {
+            index: 0,
+            device_path: bdf_dir,
+            hwmon_path: None,
+            drm_path: dir.path().to_path_buf(),
+            pci_id: "1002:73df".to_string(),
+        };
+
+        let err = AmdBackend::dri_by_path_render_node(&device).unwrap_err();
+        assert!(matches!(err, GpuError::DrmError(_)));
+    }
+
+    #[test]
+    fn parse_xrandr_providers_extracts_id_and_name() {
+        let output = "Provider 0: id: 0x47 cap: 0xf, Source Output, Sink Output crtcs: 3 outputs: 1 associated providers: 1 name:AMD Radeon\nProvider 1: id: 0x56 cap: 0x2, Sink Output crtcs: 3 outputs: 0 associated providers: 1 name:modesetting";
+
+        let providers = parse_xrandr_providers(output);
+        assert_eq!(
+            providers,
+            vec![
+                XrandrProvider { id: "0x47".to_string(), name: "AMD Radeon".to_string() },
+                XrandrProvider { id: "0x56".to_string(), name: "modesetting".to_string() },
+            ]
+        );
+    }
+
+    fn fan_device_at(dir: &TempDir) -> AmdDevice {
+        AmdDevice {
+            index: 0,
+            device_path: dir.path().to_path_buf(),
+            hwmon_path: Some(dir.path().to_path_buf()),
+            drm_path: dir.path().to_path_buf(),
+            pci_id: "1002:0000".to_string(),
+        }
+    }
+
+    fn write_temp_c(dir: &TempDir, celsius: u32) {
+        std::fs::write(dir.path().join("temp1_input"), (celsius * 1000).to_string()).unwrap();
+    }
+
+    fn read_pwm(dir: &TempDir) -> u32 {
+        std::fs::read_to_string(dir.path().join("pwm1")).unwrap().trim().parse().unwrap()
+    }
+
+    #[test]
+    fn read_pwm_max_defaults_to_255_when_absent() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(AmdBackend::read_pwm_max(dir.path()), 255);
+    }
+
+    #[test]
+    fn read_pwm_max_reads_the_cards_real_ceiling() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("pwm1_max"), "127").unwrap();
+        assert_eq!(AmdBackend::read_pwm_max(dir.path()), 127);
+    }
+
+    #[tokio::test]
+    async fn apply_device_fan_curve_jumps_straight_to_target_on_first_call() {
+        let backend = AmdBackend::new().await.unwrap();
+        let dir = TempDir::new().unwrap();
+        let device = fan_device_at(&dir);
+        write_temp_c(&dir, 70); // Aggressive curve breakpoint: 60%
+
+        backend.apply_device_fan_curve(&device, &FanCurve::aggressive()).await.unwrap();
+        assert_eq!(read_pwm(&dir), (60 * 255) / 100);
+    }
+
+    #[tokio::test]
+    async fn apply_device_fan_curve_clamps_the_step_on_a_large_jump() {
+        let backend = AmdBackend::new().await.unwrap();
+        let dir = TempDir::new().unwrap();
+        let device = fan_device_at(&dir);
+        let curve = FanCurve::aggressive();
+
+        write_temp_c(&dir, 30); // -> 20%
+        backend.apply_device_fan_curve(&device, &curve).await.unwrap();
+        assert_eq!(read_pwm(&dir), (20 * 255) / 100);
+
+        write_temp_c(&dir, 85); // -> 100%, but capped to a 10-point step from 20%
+        backend.apply_device_fan_curve(&device, &curve).await.unwrap();
+        assert_eq!(read_pwm(&dir), (30 * 255) / 100);
+    }
+
+    #[tokio::test]
+    async fn apply_device_fan_curve_blocks_a_drop_inside_the_down_threshold() {
+        let backend = AmdBackend::new().await.unwrap();
+        let dir = TempDir::new().unwrap();
+        let device = fan_device_at(&dir);
+        let curve = FanCurve::aggressive(); // down_threshold_c: 8
+
+        write_temp_c(&dir, 70); // -> 60%
+        backend.apply_device_fan_curve(&device, &curve).await.unwrap();
+        assert_eq!(read_pwm(&dir), (60 * 255) / 100);
+
+        write_temp_c(&dir, 65); // Only 5C below the setpoint, less than the 8C down-threshold
+        backend.apply_device_fan_curve(&device, &curve).await.unwrap();
+        assert_eq!(read_pwm(&dir), (60 * 255) / 100); // Speed should not have dropped yet
+
+        write_temp_c(&dir, 60); // Now 10C below the setpoint, clears the down-threshold
+        backend.apply_device_fan_curve(&device, &curve).await.unwrap();
+        assert!(read_pwm(&dir) < (60 * 255) / 100);
+    }
+
+    #[tokio::test]
+    async fn apply_device_fan_curve_honors_a_nonstandard_pwm_max() {
+        let backend = AmdBackend::new().await.unwrap();
+        let dir = TempDir::new().unwrap();
+        let device = fan_device_at(&dir);
+        std::fs::write(dir.path().join("pwm1_max"), "127").unwrap();
+        write_temp_c(&dir, 70); // -> 60%
+
+        backend.apply_device_fan_curve(&device, &FanCurve::aggressive()).await.unwrap();
+        assert_eq!(read_pwm(&dir), (60 * 127) / 100);
+    }
+
+    #[tokio::test]
+    async fn reset_gpu_clears_tracked_fan_hysteresis_state() {
+        let backend = AmdBackend::new().await.unwrap();
+        let dir = TempDir::new().unwrap();
+        let device = fan_device_at(&dir);
+        std::fs::write(dir.path().join("power_dpm_force_performance_level"), "manual").unwrap();
+        write_temp_c(&dir, 70);
+        backend.apply_device_fan_curve(&device, &FanCurve::aggressive()).await.unwrap();
+        backend.devices.write().await.insert(device.index, device.clone());
+
+        backend.reset_gpu(device.index).await.unwrap();
+        assert!(backend.fan_state.read().await.get(&device.index).is_none());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,409 @@
+//! Exposes [`GpuManager`] over an async D-Bus service (via `zbus`)
+//!
+//! Most sysfs writes `GpuBackend` implementations perform require root, so an unprivileged
+//! session client can't call into `GpuManager` directly. This module lets a privileged daemon
+//! host a `GpuManager` and serve it over the system bus instead: each detected GPU is registered
+//! as its own object under `/org/hecate/GPU/card<index>`, with methods mirroring the read/write
+//! surface of [`GpuBackend`] and properties for the values that change on their own (temperature,
+//! power draw, utilization). A background task re-samples those properties on a fixed interval
+//! and emits `PropertiesChanged` so clients can watch a GPU without polling it themselves.
+//!
+//! [`run_dbus_server`] is the entry point a system-service binary calls directly; it builds its
+//! own multi-threaded runtime with a configurable worker count so a slow status poll never blocks
+//! a concurrent control call like `apply_config`. Callers that already own a runtime (e.g. tests,
+//! or a daemon that also does other async work) should call [`serve`] instead.
+//!
+//! Registering on the system bus puts these privileged writes within reach of every local user
+//! unless something gates who may call them, so every mutating method authorizes its caller via
+//! [`DbusAuthPolicy`] before touching `GpuManager` -- root is always allowed, everyone else must
+//! belong to the configured group. This is defense in depth, not the only layer: deployments
+//! should also install `dbus/org.hecate.GPU.conf` (shipped alongside this crate) under
+//! `/etc/dbus-1/system.d/` so the bus daemon itself denies unauthorized callers before a message
+//! ever reaches this code.
+
+use crate::{error::GpuError, FanCurve, GpuConfig, GpuManager, Result};
+use nix::unistd::{getgrouplist, Gid, Group, Uid, User};
+use std::ffi::CString;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use zbus::Connection;
+
+/// Well-known bus name the service requests on the system bus
+pub const SERVICE_NAME: &str = "org.hecate.GPU";
+
+/// Object path prefix each detected GPU is registered under, suffixed with its index
+pub const OBJECT_PATH_PREFIX: &str = "/org/hecate/GPU/card";
+
+/// Runtime and polling configuration for [`run_dbus_server`]
+#[derive(Debug, Clone)]
+pub struct DbusServerConfig {
+    /// Worker-thread count for the runtime hosting the D-Bus server
+    pub worker_threads: usize,
+    /// How often live properties are re-sampled and `PropertiesChanged` is (re-)emitted
+    pub poll_interval: Duration,
+    /// Name of the system group (besides root) allowed to call mutating `GpuObject` methods.
+    /// `None` means only root may call them. Defaults to `"gpud"`.
+    pub allowed_group: Option<String>,
+}
+
+impl Default for DbusServerConfig {
+    fn default() -> Self {
+        Self {
+            worker_threads: 4,
+            poll_interval: Duration::from_secs(2),
+            allowed_group: Some("gpud".to_string()),
+        }
+    }
+}
+
+/// Who, besides root, may call [`GpuObject`]'s mutating methods. Resolved once at startup from a
+/// group *name* rather than a hard-coded UID/GID, so an operator grants access by adding a user to
+/// e.g. the `gpud` system group instead of patching this binary.
+#[derive(Debug, Clone)]
+struct DbusAuthPolicy {
+    allowed_gid: Option<Gid>,
+}
+
+impl DbusAuthPolicy {
+    /// Resolves `group_name` to a GID via the system group database. `None` means only root is
+    /// authorized.
+    fn new(group_name: Option<&str>) -> Result<Self> {
+        let allowed_gid = group_name
+            .map(|name| {
+                Group::from_name(name)
+                    .map_err(|e| GpuError::SystemError(format!("failed to look up group {name}: {e}")))?
+                    .map(|group| group.gid)
+                    .ok_or_else(|| GpuError::SystemError(format!("group {name} does not exist")))
+            })
+            .transpose()?;
+        Ok(Self { allowed_gid })
+    }
+
+    /// Root is always authorized; anyone else must have `allowed_gid` as their primary group or
+    /// among their supplementary groups.
+    fn permits(&self, uid: Uid, primary_gid: Gid, groups: &[Gid]) -> bool {
+        uid.is_root()
+            || self
+                .allowed_gid
+                .is_some_and(|allowed| primary_gid == allowed || groups.contains(&allowed))
+    }
+}
+
+/// Looks up the UID behind the D-Bus call that `header` came in on (via the bus daemon's
+/// `org.freedesktop.DBus.GetConnectionUnixUser`) and checks it against `policy`. Every mutating
+/// `GpuObject` method calls this before touching `GpuManager`.
+async fn authorize(
+    connection: &Connection,
+    header: &zbus::message::Header<'_>,
+    policy: &DbusAuthPolicy,
+) -> DbusResult<()> {
+    let sender = header
+        .sender()
+        .ok_or_else(|| DbusError::Failed("caller has no unique bus name to authorize".to_string()))?;
+
+    let dbus_proxy = zbus::fdo::DBusProxy::new(connection)
+        .await
+        .map_err(|e| DbusError::Failed(format!("failed to reach the bus daemon: {e}")))?;
+    let uid = dbus_proxy
+        .get_connection_unix_user(sender.into())
+        .await
+        .map_err(|e| DbusError::Failed(format!("failed to look up caller's credentials: {e}")))?;
+    let uid = Uid::from_raw(uid);
+
+    if uid.is_root() {
+        return Ok(());
+    }
+
+    let user = User::from_uid(uid)
+        .map_err(|e| DbusError::Failed(format!("failed to look up uid {uid}: {e}")))?
+        .ok_or_else(|| DbusError::Failed(format!("no user record for uid {uid}")))?;
+    let name = CString::new(user.name.clone())
+        .map_err(|_| DbusError::Failed(format!("user name for uid {uid} is not a valid C string")))?;
+    let groups = getgrouplist(&name, user.gid)
+        .map_err(|e| DbusError::Failed(format!("failed to look up groups for uid {uid}: {e}")))?;
+
+    if policy.permits(uid, user.gid, &groups) {
+        Ok(())
+    } else {
+        Err(DbusError::Failed(format!("uid {uid} is not authorized to modify GPU state")))
+    }
+}
+
+/// D-Bus error domain for this service. Only the [`GpuError`] variants a client could
+/// meaningfully react to differently get their own name; everything else collapses to `Failed`
+/// with the original message preserved.
+#[derive(Debug, zbus::DBusError)]
+#[zbus(prefix = "org.hecate.GPU.Error")]
+pub enum DbusError {
+    #[zbus(error)]
+    ZBus(zbus::Error),
+    NotFound(String),
+    BackendUnavailable(String),
+    NotSupported(String),
+    InvalidConfig(String),
+    LimitExceeded(String),
+    Failed(String),
+}
+
+impl From<GpuError> for DbusError {
+    fn from(err: GpuError) -> Self {
+        match err {
+            GpuError::GpuNotFound(index) => DbusError::NotFound(format!("GPU {index} not found")),
+            GpuError::BackendNotAvailable(vendor) => {
+                DbusError::BackendUnavailable(format!("backend for {vendor:?} is not available"))
+            }
+            GpuError::OperationNotSupported(msg) => DbusError::NotSupported(msg),
+            GpuError::InvalidConfig(msg) => DbusError::InvalidConfig(msg),
+            GpuError::LimitExceeded { requested, max } => {
+                DbusError::LimitExceeded(format!("requested {requested} exceeds hardware safe limit of {max}"))
+            }
+            GpuError::VoltageOutOfRange { requested, min, max } => {
+                DbusError::LimitExceeded(format!("voltage {requested}mV is out of range ({min}mV..={max}mV)"))
+            }
+            GpuError::OutOfRange { requested, min, max } => {
+                DbusError::LimitExceeded(format!("{requested} is out of range ({min}..={max})"))
+            }
+            other => DbusError::Failed(other.to_string()),
+        }
+    }
+}
+
+type DbusResult<T> = std::result::Result<T, DbusError>;
+
+fn serialization_error(err: serde_json::Error) -> DbusError {
+    DbusError::from(GpuError::from(err))
+}
+
+/// Object path a GPU with the given index is registered under
+pub fn object_path(index: u32) -> String {
+    format!("{OBJECT_PATH_PREFIX}{index}")
+}
+
+/// Per-GPU D-Bus object, registered at `/org/hecate/GPU/card<index>`. Configs and fan curves
+/// cross the bus JSON-encoded rather than as native D-Bus structs, since both types carry
+/// optional and nested fields that don't map cleanly onto the D-Bus type system; both already
+/// derive `Serialize`/`Deserialize` for this reason.
+struct GpuObject {
+    manager: Arc<GpuManager>,
+    index: u32,
+    policy: Arc<DbusAuthPolicy>,
+}
+
+#[zbus::interface(name = "org.hecate.GPU1")]
+impl GpuObject {
+    /// JSON-encoded [`crate::GpuStatus`] for this GPU
+    async fn get_gpu_status(&self) -> DbusResult<String> {
+        let status = self.manager.get_gpu_status(self.index).await?;
+        serde_json::to_string(&status).map_err(serialization_error)
+    }
+
+    /// Apply a JSON-encoded [`GpuConfig`] to this GPU
+    async fn apply_config(
+        &self,
+        config_json: String,
+        #[zbus(header)] header: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> DbusResult<()> {
+        authorize(connection, &header, &self.policy).await?;
+        let config: GpuConfig = serde_json::from_str(&config_json).map_err(serialization_error)?;
+        self.manager.apply_config(self.index, config).await?;
+        Ok(())
+    }
+
+    async fn set_power_limit(
+        &self,
+        limit_watts: u32,
+        #[zbus(header)] header: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> DbusResult<()> {
+        authorize(connection, &header, &self.policy).await?;
+        self.manager.set_power_limit(self.index, limit_watts).await?;
+        Ok(())
+    }
+
+    /// Apply a JSON-encoded [`FanCurve`] to this GPU
+    async fn set_fan_curve(
+        &self,
+        curve_json: String,
+        #[zbus(header)] header: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> DbusResult<()> {
+        authorize(connection, &header, &self.policy).await?;
+        let curve: FanCurve = serde_json::from_str(&curve_json).map_err(serialization_error)?;
+        self.manager.set_fan_curve(self.index, curve).await?;
+        Ok(())
+    }
+
+    async fn reset_gpu(
+        &self,
+        #[zbus(header)] header: zbus::message::Header<'_>,
+        #[zbus(connection)] connection: &Connection,
+    ) -> DbusResult<()> {
+        authorize(connection, &header, &self.policy).await?;
+        self.manager.reset_gpu(self.index).await?;
+        Ok(())
+    }
+
+    #[zbus(property)]
+    async fn temperature(&self) -> DbusResult<u32> {
+        Ok(self.manager.get_gpu_status(self.index).await?.temperature)
+    }
+
+    #[zbus(property)]
+    async fn power_draw(&self) -> DbusResult<u32> {
+        Ok(self.manager.get_gpu_status(self.index).await?.power_draw)
+    }
+
+    #[zbus(property)]
+    async fn utilization_gpu(&self) -> DbusResult<u32> {
+        Ok(self.manager.get_gpu_status(self.index).await?.utilization_gpu)
+    }
+}
+
+/// Build the multi-threaded runtime described by `config` and block on [`serve`] until the
+/// process is killed. This is the entry point a `hecate-gpud`-style binary calls directly.
+pub fn run_dbus_server(manager: Arc<GpuManager>, config: DbusServerConfig) -> Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(config.worker_threads.max(1))
+        .enable_all()
+        .build()
+        .map_err(|e| GpuError::SystemError(format!("failed to build D-Bus server runtime: {e}")))?;
+
+    runtime.block_on(serve(manager, config.poll_interval, config.allowed_group.as_deref()))
+}
+
+/// Register every currently-detected GPU on the system bus and run until the connection is
+/// dropped, re-sampling live properties and emitting change signals every `poll_interval`. Only
+/// root and members of `allowed_group` (if any) may call mutating methods -- see
+/// [`DbusAuthPolicy`].
+pub async fn serve(manager: Arc<GpuManager>, poll_interval: Duration, allowed_group: Option<&str>) -> Result<()> {
+    let gpus = manager.detect_gpus().await?;
+    let policy = Arc::new(DbusAuthPolicy::new(allowed_group)?);
+
+    let connection = Connection::system()
+        .await
+        .map_err(|e| GpuError::SystemError(format!("failed to connect to the system bus: {e}")))?;
+
+    for gpu in &gpus {
+        let path = object_path(gpu.index);
+        let object = GpuObject { manager: Arc::clone(&manager), index: gpu.index, policy: Arc::clone(&policy) };
+        connection
+            .object_server()
+            .at(path.clone(), object)
+            .await
+            .map_err(|e| GpuError::SystemError(format!("failed to register {path}: {e}")))?;
+    }
+
+    connection
+        .request_name(SERVICE_NAME)
+        .await
+        .map_err(|e| GpuError::SystemError(format!("failed to claim bus name {SERVICE_NAME}: {e}")))?;
+
+    info!("D-Bus GPU service registered {} GPU object(s) as {}", gpus.len(), SERVICE_NAME);
+
+    let mut ticker = tokio::time::interval(poll_interval);
+    loop {
+        ticker.tick().await;
+
+        for gpu in &gpus {
+            let path = object_path(gpu.index);
+            let iface_ref = match connection.object_server().interface::<_, GpuObject>(&path).await {
+                Ok(iface_ref) => iface_ref,
+                Err(e) => {
+                    warn!("Failed to look up D-Bus object {} for property polling: {}", path, e);
+                    continue;
+                }
+            };
+
+            let signal_emitter = iface_ref.signal_emitter();
+            let object = iface_ref.get().await;
+            if let Err(e) = object.temperature_changed(signal_emitter).await {
+                warn!("Failed to emit temperature change for GPU {}: {}", gpu.index, e);
+            }
+            if let Err(e) = object.power_draw_changed(signal_emitter).await {
+                warn!("Failed to emit power_draw change for GPU {}: {}", gpu.index, e);
+            }
+            if let Err(e) = object.utilization_gpu_changed(signal_emitter).await {
+                warn!("Failed to emit utilization_gpu change for GPU {}: {}", gpu.index, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_path_embeds_the_gpu_index() {
+        assert_eq!(object_path(0), "/org/hecate/GPU/card0");
+        assert_eq!(object_path(3), "/org/hecate/GPU/card3");
+    }
+
+    #[test]
+    fn gpu_not_found_maps_to_the_not_found_dbus_error() {
+        let err: DbusError = GpuError::GpuNotFound(2).into();
+        assert!(matches!(err, DbusError::NotFound(_)));
+    }
+
+    #[test]
+    fn operation_not_supported_maps_to_the_not_supported_dbus_error() {
+        let err: DbusError = GpuError::OperationNotSupported("GPU switching".to_string()).into();
+        assert!(matches!(err, DbusError::NotSupported(_)));
+    }
+
+    #[test]
+    fn limit_exceeded_variants_all_map_to_the_limit_exceeded_dbus_error() {
+        assert!(matches!(
+            DbusError::from(GpuError::LimitExceeded { requested: 500, max: 450 }),
+            DbusError::LimitExceeded(_)
+        ));
+        assert!(matches!(
+            DbusError::from(GpuError::VoltageOutOfRange { requested: 200, min: -100, max: 100 }),
+            DbusError::LimitExceeded(_)
+        ));
+        assert!(matches!(
+            DbusError::from(GpuError::OutOfRange { requested: 5000, min: 300, max: 2500 }),
+            DbusError::LimitExceeded(_)
+        ));
+    }
+
+    #[test]
+    fn unmapped_variants_collapse_to_failed() {
+        let err: DbusError = GpuError::ThermalError("fan seized".to_string()).into();
+        assert!(matches!(err, DbusError::Failed(_)));
+    }
+
+    #[test]
+    fn auth_policy_always_permits_root() {
+        let policy = DbusAuthPolicy { allowed_gid: None };
+        assert!(policy.permits(Uid::from_raw(0), Gid::from_raw(1000), &[]));
+    }
+
+    #[test]
+    fn auth_policy_denies_non_root_with_no_allowed_group() {
+        let policy = DbusAuthPolicy { allowed_gid: None };
+        assert!(!policy.permits(Uid::from_raw(1000), Gid::from_raw(1000), &[]));
+    }
+
+    #[test]
+    fn auth_policy_permits_matching_primary_group() {
+        let policy = DbusAuthPolicy { allowed_gid: Some(Gid::from_raw(500)) };
+        assert!(policy.permits(Uid::from_raw(1000), Gid::from_raw(500), &[]));
+    }
+
+    #[test]
+    fn auth_policy_permits_matching_supplementary_group() {
+        let policy = DbusAuthPolicy { allowed_gid: Some(Gid::from_raw(500)) };
+        let groups = [Gid::from_raw(100), Gid::from_raw(500)];
+        assert!(policy.permits(Uid::from_raw(1000), Gid::from_raw(1000), &groups));
+    }
+
+    #[test]
+    fn auth_policy_denies_unrelated_group() {
+        let policy = DbusAuthPolicy { allowed_gid: Some(Gid::from_raw(500)) };
+        let groups = [Gid::from_raw(100)];
+        assert!(!policy.permits(Uid::from_raw(1000), Gid::from_raw(1000), &groups));
+    }
+}
@@ -1,9 +1,13 @@
 //! GPU driver management and automatic updates
 
 use crate::error::{GpuError, Result};
+use async_trait::async_trait;
 use regex::Regex;
+use serde::Deserialize;
 use std::collections::HashMap;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command as AsyncCommand;
 use tracing::{info, instrument, warn};
 use which::which;
@@ -13,6 +17,18 @@ use which::which;
 pub struct DriverManager {
     nvidia_driver: Option<NvidiaDriver>,
     amd_driver: Option<AmdDriver>,
+    /// The package manager [`detect_package_backend`] found on `$PATH`, or `None` on a distro
+    /// running none of apt/dnf/pacman/zypper -- driver updates are simply unavailable there.
+    package_backend: Option<Box<dyn PackageBackend>>,
+    /// PCI-sysfs view of every GPU on the bus, populated once at [`Self::init`] so it still
+    /// reflects a dGPU that's since been runtime-suspended off the bus or had its module unloaded.
+    pci_inventory: PciGpuInventory,
+    /// Hybrid-laptop power mode and RTD3 capabilities, computed once at [`Self::init`].
+    hybrid_status: HybridGpuStatus,
+    /// The most recent update's pre-change snapshot, kept so [`Self::rollback_driver`] can
+    /// restore it after the fact even once the triggering `check_and_update_drivers` call has
+    /// returned.
+    last_backup: tokio::sync::Mutex<Option<DriverBackup>>,
 }
 
 impl DriverManager {
@@ -21,11 +37,17 @@ impl DriverManager {
         Self {
             nvidia_driver: None,
             amd_driver: None,
+            package_backend: None,
+            pci_inventory: PciGpuInventory::default(),
+            hybrid_status: HybridGpuStatus::default(),
+            last_backup: tokio::sync::Mutex::new(None),
         }
     }
 
     /// Initialize driver detection
     pub async fn init(&mut self) -> Result<()> {
+        self.pci_inventory = PciGpuInventory::scan();
+
         // Detect NVIDIA driver
         if let Ok(nvidia) = NvidiaDriver::new().await {
             self.nvidia_driver = Some(nvidia);
@@ -36,16 +58,52 @@ impl DriverManager {
             self.amd_driver = Some(amd);
         }
 
+        self.package_backend = detect_package_backend();
+
+        let nvidia_device = self.pci_inventory.nvidia_devices().next();
+        let rtd3 = nvidia_device
+            .map(|device| NvidiaDriver::detect_rtd3_capabilities(&device.address))
+            .unwrap_or_default();
+        let mode = GpuPowerMode::detect(nvidia_device.is_some(), &rtd3);
+        self.hybrid_status = HybridGpuStatus { mode, rtd3 };
+
         Ok(())
     }
 
-    /// Check for available driver updates
+    /// The PCI-sysfs inventory taken at [`Self::init`], independent of whether a device's driver
+    /// is currently bound.
+    pub fn pci_inventory(&self) -> &PciGpuInventory {
+        &self.pci_inventory
+    }
+
+    /// The hybrid-GPU power mode and RTD3 capabilities detected at [`Self::init`].
+    pub fn hybrid_status(&self) -> &HybridGpuStatus {
+        &self.hybrid_status
+    }
+
+    /// Write a PRIME render-offload xorg snippet (`11-nvidia-offload.conf`) into `xorg_conf_dir`,
+    /// selecting the integrated GPU as primary with `nvidia_pci_address`'s GPU available for
+    /// on-demand offload. Callers should check [`Rtd3Capabilities::can_offer_hybrid_mode`] on
+    /// [`Self::hybrid_status`] first -- this doesn't re-verify driver support itself.
+    pub fn enable_prime_offload(&self, nvidia_pci_address: &str, xorg_conf_dir: &Path) -> Result<PathBuf> {
+        let bus_id = pci_address_to_xorg_bus_id(nvidia_pci_address).ok_or_else(|| {
+            GpuError::InvalidConfig(format!("unrecognized PCI address: {nvidia_pci_address}"))
+        })?;
+
+        let path = xorg_conf_dir.join(PRIME_OFFLOAD_XORG_SNIPPET_NAME);
+        fs::write(&path, render_prime_offload_xorg_conf(&bus_id)).map_err(GpuError::IoError)?;
+        Ok(path)
+    }
+
+    /// Check for available driver updates. `nvidia_branch` selects which NVIDIA release branch
+    /// to compare against (a user on the legacy 470.xx branch shouldn't be offered a Production
+    /// update their GPU no longer supports).
     #[instrument]
-    pub async fn check_updates(&self) -> Result<Vec<DriverUpdate>> {
+    pub async fn check_updates(&self, nvidia_branch: DriverBranch) -> Result<Vec<DriverUpdate>> {
         let mut updates = Vec::new();
 
         if let Some(ref nvidia) = self.nvidia_driver {
-            if let Ok(update) = nvidia.check_update().await {
+            if let Ok(update) = nvidia.check_update(nvidia_branch).await {
                 if let Some(update) = update {
                     updates.push(update);
                 }
@@ -63,18 +121,44 @@ impl DriverManager {
         Ok(updates)
     }
 
-    /// Check and automatically update drivers
+    /// Check and automatically update drivers. Each update is snapshotted first -- any touched
+    /// xorg config plus the currently-installed version -- and rolled back to that snapshot if
+    /// the install step fails, rather than leaving a half-installed driver in place. `unattended`
+    /// is forwarded to the NVIDIA install (and to any rollback reinstall it triggers) so license
+    /// prompts don't stall automation/cloud contexts; interactive desktop callers should pass
+    /// `false` to keep the prompts.
     #[instrument]
-    pub async fn check_and_update_drivers(&self) -> Result<Vec<String>> {
+    pub async fn check_and_update_drivers(&self, nvidia_branch: DriverBranch, unattended: bool) -> Result<Vec<String>> {
         let mut updated_drivers = Vec::new();
+        let mut rollback_error: Option<GpuError> = None;
+
+        let Some(backend) = self.package_backend.as_deref() else {
+            warn!("no supported package manager (apt/dnf/pacman/zypper) found; skipping driver updates");
+            return Ok(updated_drivers);
+        };
 
         // Check NVIDIA driver updates
         if let Some(ref nvidia) = self.nvidia_driver {
-            if let Ok(Some(update)) = nvidia.check_update().await {
+            if let Ok(Some(update)) = nvidia.check_update(nvidia_branch).await {
                 info!("NVIDIA driver update available: {} -> {}", update.current_version, update.latest_version);
-                
-                if let Ok(()) = nvidia.update_driver().await {
-                    updated_drivers.push(format!("NVIDIA: {} -> {}", update.current_version, update.latest_version));
+
+                let backup = DriverBackup::capture("nvidia", update.current_version.clone());
+                *self.last_backup.lock().await = Some(backup.clone());
+
+                match nvidia.update_driver(backend, unattended).await {
+                    Ok(()) => {
+                        updated_drivers.push(format!("NVIDIA: {} -> {}", update.current_version, update.latest_version));
+                    }
+                    Err(e) => {
+                        warn!("NVIDIA driver update failed ({e}); rolling back to {}", update.current_version);
+                        if let Err(restore_err) = backup.restore(backend, unattended).await {
+                            warn!("NVIDIA rollback also failed: {restore_err}");
+                        }
+                        rollback_error.get_or_insert(GpuError::UpdateRolledBack(format!(
+                            "NVIDIA driver update to {} failed and was rolled back to {}: {e}",
+                            update.latest_version, update.current_version
+                        )));
+                    }
                 }
             }
         }
@@ -83,16 +167,53 @@ impl DriverManager {
         if let Some(ref amd) = self.amd_driver {
             if let Ok(Some(update)) = amd.check_update().await {
                 info!("AMD driver update available: {} -> {}", update.current_version, update.latest_version);
-                
-                if let Ok(()) = amd.update_driver().await {
-                    updated_drivers.push(format!("AMD: {} -> {}", update.current_version, update.latest_version));
+
+                let backup = DriverBackup::capture("amd", update.current_version.clone());
+                *self.last_backup.lock().await = Some(backup.clone());
+
+                match amd.update_driver(backend).await {
+                    Ok(()) => {
+                        updated_drivers.push(format!("AMD: {} -> {}", update.current_version, update.latest_version));
+                    }
+                    Err(e) => {
+                        warn!("AMD driver update failed ({e}); rolling back to {}", update.current_version);
+                        if let Err(restore_err) = backup.restore(backend, unattended).await {
+                            warn!("AMD rollback also failed: {restore_err}");
+                        }
+                        rollback_error.get_or_insert(GpuError::UpdateRolledBack(format!(
+                            "AMD driver update to {} failed and was rolled back to {}: {e}",
+                            update.latest_version, update.current_version
+                        )));
+                    }
                 }
             }
         }
 
+        if let Some(err) = rollback_error {
+            return Err(err);
+        }
+
         Ok(updated_drivers)
     }
 
+    /// Explicitly revert the most recent driver update's config and version snapshot, mirroring
+    /// the "restore original backup" flow `nvidia-installer`'s uninstall path offers. Errors if
+    /// no update has been attempted yet, or if no package backend is available to reinstall the
+    /// previous version. `unattended` is forwarded to the reinstall step, same as in
+    /// [`Self::check_and_update_drivers`].
+    pub async fn rollback_driver(&self, unattended: bool) -> Result<()> {
+        let backup = self.last_backup.lock().await.clone();
+        let Some(backup) = backup else {
+            return Err(GpuError::InvalidConfig("no driver update to roll back".to_string()));
+        };
+
+        let backend = self.package_backend.as_deref().ok_or_else(|| {
+            GpuError::OperationNotSupported("no supported package manager to perform rollback".to_string())
+        })?;
+
+        backup.restore(backend, unattended).await
+    }
+
     /// Get current driver versions
     pub async fn get_driver_versions(&self) -> HashMap<String, String> {
         let mut versions = HashMap::new();
@@ -113,6 +234,580 @@ impl DriverManager {
     }
 }
 
+/// One PCI device found under `/sys/bus/pci/devices`, captured once by [`PciGpuInventory::scan`].
+/// sysfs's PCI tree reflects every device physically on the bus regardless of driver state, so
+/// this stays accurate for a dGPU that's runtime-suspended off the bus or whose module is unloaded
+/// -- unlike `nvidia-smi`/`modinfo`, which both require the driver to currently be bound.
+#[derive(Debug, Clone)]
+pub struct PciGpuDevice {
+    /// PCI bus address, e.g. `"0000:01:00.0"`
+    pub address: String,
+    pub vendor_id: u16,
+    pub device_id: u16,
+    /// Name of the driver bound to this device (the `driver` symlink's target directory name),
+    /// or `None` if nothing is currently bound
+    pub driver: Option<String>,
+}
+
+/// Base PCI class code for "Display controller" devices (VGA/3D/other), per the PCI ID
+/// Repository's class list.
+const PCI_DISPLAY_CLASS_PREFIX: &str = "0x03";
+
+/// Every GPU found under `/sys/bus/pci/devices`, enumerated once at [`DriverManager::init`] so
+/// later lookups don't each re-read sysfs.
+#[derive(Debug, Clone, Default)]
+pub struct PciGpuInventory {
+    pub devices: Vec<PciGpuDevice>,
+}
+
+impl PciGpuInventory {
+    /// Walk `/sys/bus/pci/devices`, keeping only display-class devices. An unreadable or missing
+    /// PCI sysfs tree (e.g. in a container without it mounted) yields an empty inventory rather
+    /// than an error.
+    pub fn scan() -> Self {
+        let Ok(entries) = fs::read_dir("/sys/bus/pci/devices") else {
+            return Self::default();
+        };
+
+        let mut devices: Vec<PciGpuDevice> = entries
+            .flatten()
+            .filter_map(|entry| Self::read_device(&entry.path()))
+            .collect();
+        devices.sort_by(|a, b| a.address.cmp(&b.address));
+
+        Self { devices }
+    }
+
+    fn read_device(path: &Path) -> Option<PciGpuDevice> {
+        let class = fs::read_to_string(path.join("class")).ok()?;
+        // e.g. "0x030000" -- base class 0x03 is "Display controller"
+        if !class.trim().starts_with(PCI_DISPLAY_CLASS_PREFIX) {
+            return None;
+        }
+
+        let vendor_id = Self::read_hex_u16(&path.join("vendor"))?;
+        let device_id = Self::read_hex_u16(&path.join("device"))?;
+        let address = path.file_name()?.to_str()?.to_string();
+        let driver = fs::canonicalize(path.join("driver"))
+            .ok()
+            .and_then(|target| target.file_name().map(|name| name.to_string_lossy().into_owned()));
+
+        Some(PciGpuDevice { address, vendor_id, device_id, driver })
+    }
+
+    fn read_hex_u16(path: &Path) -> Option<u16> {
+        let raw = fs::read_to_string(path).ok()?;
+        u16::from_str_radix(raw.trim().trim_start_matches("0x"), 16).ok()
+    }
+
+    /// NVIDIA (vendor `0x10DE`) devices in the inventory
+    pub fn nvidia_devices(&self) -> impl Iterator<Item = &PciGpuDevice> {
+        self.devices.iter().filter(|d| d.vendor_id == 0x10DE)
+    }
+
+    /// AMD (vendor `0x1002`) devices in the inventory
+    pub fn amd_devices(&self) -> impl Iterator<Item = &PciGpuDevice> {
+        self.devices.iter().filter(|d| d.vendor_id == 0x1002)
+    }
+}
+
+/// Hybrid-laptop GPU power mode, inferred from whether an NVIDIA GPU is present and whether
+/// RTD3 is currently enabled on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuPowerMode {
+    /// No discrete GPU in use; only the integrated GPU is powered
+    Integrated,
+    /// PRIME render offload: integrated GPU drives the display, discrete GPU powers down via
+    /// RTD3 until something offloads work to it
+    Hybrid,
+    /// Discrete GPU drives the display directly; RTD3 is not in effect
+    Discrete,
+}
+
+impl GpuPowerMode {
+    fn detect(nvidia_present: bool, rtd3: &Rtd3Capabilities) -> Option<Self> {
+        if !nvidia_present {
+            return Some(GpuPowerMode::Integrated);
+        }
+        Some(if rtd3.rtd3_enabled {
+            GpuPowerMode::Hybrid
+        } else {
+            GpuPowerMode::Discrete
+        })
+    }
+}
+
+/// The shape of the NVIDIA driver's RTD3 capability file, a small JSON document the driver
+/// package installs alongside its kernel module describing what the GPU/platform combination
+/// supports.
+#[derive(Debug, Deserialize)]
+struct RtdCapabilityFile {
+    rtd3_supported: bool,
+}
+
+/// What [`NvidiaDriver::detect_rtd3_capabilities`] found about this system's runtime
+/// power-management support: whether the driver advertises it, and whether it's actually
+/// enabled right now.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rtd3Capabilities {
+    /// The NVIDIA driver's capability file reports RTD3 is supported
+    pub driver_supports_rtd3: bool,
+    /// The GPU's PCI `power/control` sysfs attribute is currently set to `"auto"` -- RTD3 isn't
+    /// just supported but enabled
+    pub rtd3_enabled: bool,
+}
+
+impl Rtd3Capabilities {
+    /// Whether hybrid/on-demand offload mode can be offered at all
+    pub fn can_offer_hybrid_mode(&self) -> bool {
+        self.driver_supports_rtd3
+    }
+}
+
+/// [`DriverManager::hybrid_status`]'s cached view: the inferred power mode plus the RTD3
+/// capabilities it was derived from.
+#[derive(Debug, Clone, Default)]
+pub struct HybridGpuStatus {
+    /// `None` when no NVIDIA driver/device was detected at all
+    pub mode: Option<GpuPowerMode>,
+    pub rtd3: Rtd3Capabilities,
+}
+
+/// Filename [`DriverManager::enable_prime_offload`] writes its PRIME render-offload snippet as,
+/// matching the convention `nvidia-xconfig`/distro installers use for drop-in xorg.conf.d files.
+const PRIME_OFFLOAD_XORG_SNIPPET_NAME: &str = "11-nvidia-offload.conf";
+
+/// Render a PRIME render-offload `OutputClass` snippet selecting the integrated GPU as primary,
+/// with the NVIDIA GPU at `nvidia_bus_id` (xorg `BusID` syntax, e.g. `"PCI:1:0:0"`) available for
+/// on-demand offload via `__NV_PRIME_RENDER_OFFLOAD`.
+fn render_prime_offload_xorg_conf(nvidia_bus_id: &str) -> String {
+    format!(
+        "Section \"OutputClass\"\n\
+         \tIdentifier \"nvidia\"\n\
+         \tMatchDriver \"nvidia-drm\"\n\
+         \tDriver \"nvidia\"\n\
+         \tOption \"AllowEmptyInitialConfiguration\"\n\
+         \tOption \"PrimaryGPU\" \"no\"\n\
+         \tBusID \"{nvidia_bus_id}\"\n\
+         EndSection\n"
+    )
+}
+
+/// Convert a PCI sysfs address (`"<domain>:<bus>:<device>.<function>"`, e.g. `"0000:01:00.0"`)
+/// into xorg's decimal `BusID` syntax (`"PCI:<bus>:<device>:<function>"`, e.g. `"PCI:1:0:0"`).
+fn pci_address_to_xorg_bus_id(address: &str) -> Option<String> {
+    let mut fields = address.split(':');
+    let _domain = fields.next()?;
+    let bus = fields.next()?;
+    let device_function = fields.next()?;
+    let (device, function) = device_function.split_once('.')?;
+
+    Some(format!(
+        "PCI:{}:{}:{}",
+        u32::from_str_radix(bus, 16).ok()?,
+        u32::from_str_radix(device, 16).ok()?,
+        u32::from_str_radix(function, 16).ok()?,
+    ))
+}
+
+/// Xorg's single monolithic config file, which `nvidia-xconfig` and manual PRIME setups both
+/// sometimes edit directly.
+const XORG_CONF_PATH: &str = "/etc/X11/xorg.conf";
+/// Drop-in directory for xorg snippets, e.g. [`PRIME_OFFLOAD_XORG_SNIPPET_NAME`].
+const XORG_CONF_D_DIR: &str = "/etc/X11/xorg.conf.d";
+
+/// One config file's contents as they stood before a driver update, or `None` if the file didn't
+/// exist yet.
+#[derive(Debug, Clone)]
+struct ConfigBackup {
+    path: PathBuf,
+    contents: Option<Vec<u8>>,
+}
+
+/// A pre-update snapshot: the driver version that was installed, plus the xorg config files the
+/// update might touch. [`Self::restore`] undoes a failed (or unwanted) update back to this state.
+#[derive(Debug, Clone)]
+struct DriverBackup {
+    vendor: String,
+    previous_version: String,
+    configs: Vec<ConfigBackup>,
+}
+
+impl DriverBackup {
+    /// Snapshot the current xorg config files alongside `previous_version`, the driver version
+    /// installed right now.
+    fn capture(vendor: &str, previous_version: String) -> Self {
+        let paths = [
+            PathBuf::from(XORG_CONF_PATH),
+            Path::new(XORG_CONF_D_DIR).join(PRIME_OFFLOAD_XORG_SNIPPET_NAME),
+        ];
+        let configs = paths
+            .into_iter()
+            .map(|path| {
+                let contents = fs::read(&path).ok();
+                ConfigBackup { path, contents }
+            })
+            .collect();
+
+        Self {
+            vendor: vendor.to_string(),
+            previous_version,
+            configs,
+        }
+    }
+
+    /// Restore the backed-up config files and reinstall `previous_version` via `backend`.
+    /// `unattended` is forwarded to [`PackageBackend::preseed_unattended`] before reinstalling so
+    /// a rollback triggered from an unattended run doesn't itself stall on a license prompt.
+    async fn restore(&self, backend: &dyn PackageBackend, unattended: bool) -> Result<()> {
+        for config in &self.configs {
+            match &config.contents {
+                Some(bytes) => fs::write(&config.path, bytes).map_err(GpuError::IoError)?,
+                None if config.path.exists() => fs::remove_file(&config.path).map_err(GpuError::IoError)?,
+                None => {}
+            }
+        }
+
+        if self.vendor == "nvidia" {
+            if let Some(package) = nvidia_package_for_version(backend.name(), &self.previous_version) {
+                if unattended {
+                    backend.preseed_unattended(&package).await?;
+                }
+                backend.install(&package).await?;
+            }
+        }
+        // AMD's "driver" is really a bundle of kernel/mesa packages with no single installable,
+        // exact-version package name (see `AmdDriver::get_latest_amd_version`), so its rollback
+        // is limited to restoring config; there's nothing equivalent to reinstall precisely.
+
+        Ok(())
+    }
+}
+
+/// Map a previously-installed NVIDIA driver version back to the backend-specific package that
+/// reinstalls it. Only apt's Ubuntu graphics-drivers PPA packages carry a version in their name
+/// (`nvidia-driver-<major>`); the other backends' NVIDIA packages aren't granularly versioned, so
+/// rollback there reinstalls the same package [`NvidiaDriver::update_driver`] would -- restoring
+/// a working driver, but not guaranteed to be the exact prior version.
+fn nvidia_package_for_version(backend_name: &str, version: &str) -> Option<String> {
+    match backend_name {
+        "apt" => {
+            let major = version.split('.').next()?;
+            Some(format!("nvidia-driver-{major}"))
+        }
+        "dnf" => Some("akmod-nvidia".to_string()),
+        "pacman" => Some("nvidia".to_string()),
+        "zypper" => Some("nvidia-open-driver-G06-signed-cuda".to_string()),
+        _ => None,
+    }
+}
+
+/// Build the `debconf-set-selections` input that marks `package`'s NVIDIA license prompts as
+/// accepted. Must end in a newline -- debconf silently drops the last entry otherwise.
+fn nvidia_debconf_selections(package: &str) -> String {
+    format!(
+        "{package} shared/present-nvidia-license note\n\
+         {package} shared/accepted-nvidia-license-question boolean true\n\
+         {package} shared/accepted-nvidia-nonfree-question boolean true\n"
+    )
+}
+
+/// A system package manager capable of refreshing metadata, looking up an installed package's
+/// version, and installing a package by name. [`detect_package_backend`] probes `$PATH` for the
+/// first one present so driver updates aren't hard-wired to apt/Ubuntu.
+#[async_trait]
+pub trait PackageBackend: std::fmt::Debug + Send + Sync {
+    /// Short identifier used to pick a per-backend driver package name, e.g. `"apt"`
+    fn name(&self) -> &'static str;
+
+    /// Refresh the backend's package metadata/cache
+    async fn refresh(&self) -> Result<()>;
+
+    /// The installed version of `package`, or `None` if it isn't installed
+    async fn installed_version(&self, package: &str) -> Result<Option<String>>;
+
+    /// Install (or upgrade to the latest available version of) `package`
+    async fn install(&self, package: &str) -> Result<()>;
+
+    /// Pre-seed any interactive prompt `package`'s install would otherwise block on (e.g. a
+    /// debconf license prompt), so `install` can run unattended. A no-op on backends with nothing
+    /// to preseed.
+    async fn preseed_unattended(&self, _package: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Probe `$PATH` for the first supported package manager, checked in this order:
+/// apt (Debian/Ubuntu), dnf (Fedora/RHEL), pacman (Arch), zypper (SUSE).
+fn detect_package_backend() -> Option<Box<dyn PackageBackend>> {
+    if which("apt").is_ok() {
+        Some(Box::new(AptBackend))
+    } else if which("dnf").is_ok() {
+        Some(Box::new(DnfBackend))
+    } else if which("pacman").is_ok() {
+        Some(Box::new(PacmanBackend))
+    } else if which("zypper").is_ok() {
+        Some(Box::new(ZypperBackend))
+    } else {
+        None
+    }
+}
+
+#[derive(Debug)]
+struct AptBackend;
+
+#[async_trait]
+impl PackageBackend for AptBackend {
+    fn name(&self) -> &'static str {
+        "apt"
+    }
+
+    async fn refresh(&self) -> Result<()> {
+        let status = AsyncCommand::new("sudo")
+            .arg("apt")
+            .arg("update")
+            .status()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !status.success() {
+            return Err(GpuError::SystemError("apt update failed".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn installed_version(&self, package: &str) -> Result<Option<String>> {
+        let output = AsyncCommand::new("dpkg-query")
+            .arg("-W")
+            .arg("-f=${Version}")
+            .arg(package)
+            .output()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok((!version.is_empty()).then_some(version))
+    }
+
+    async fn install(&self, package: &str) -> Result<()> {
+        let status = AsyncCommand::new("sudo")
+            .arg("apt")
+            .arg("install")
+            .arg("-y")
+            .arg(package)
+            .status()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !status.success() {
+            return Err(GpuError::SystemError(format!("apt install {package} failed")));
+        }
+        Ok(())
+    }
+
+    async fn preseed_unattended(&self, package: &str) -> Result<()> {
+        if !package.starts_with("nvidia") {
+            return Ok(());
+        }
+
+        let selections = nvidia_debconf_selections(package);
+
+        let mut child = AsyncCommand::new("sudo")
+            .arg("debconf-set-selections")
+            .stdin(std::process::Stdio::piped())
+            .spawn()
+            .map_err(GpuError::IoError)?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| GpuError::SystemError("failed to open debconf-set-selections stdin".to_string()))?;
+        {
+            let mut stdin = stdin;
+            stdin.write_all(selections.as_bytes()).await.map_err(GpuError::IoError)?;
+        }
+
+        let status = child.wait().await.map_err(GpuError::IoError)?;
+        if !status.success() {
+            return Err(GpuError::SystemError("debconf-set-selections failed".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct DnfBackend;
+
+#[async_trait]
+impl PackageBackend for DnfBackend {
+    fn name(&self) -> &'static str {
+        "dnf"
+    }
+
+    async fn refresh(&self) -> Result<()> {
+        let status = AsyncCommand::new("sudo")
+            .arg("dnf")
+            .arg("makecache")
+            .status()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !status.success() {
+            return Err(GpuError::SystemError("dnf makecache failed".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn installed_version(&self, package: &str) -> Result<Option<String>> {
+        let output = AsyncCommand::new("rpm")
+            .arg("-q")
+            .arg("--queryformat=%{VERSION}")
+            .arg(package)
+            .output()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok((!version.is_empty()).then_some(version))
+    }
+
+    async fn install(&self, package: &str) -> Result<()> {
+        let status = AsyncCommand::new("sudo")
+            .arg("dnf")
+            .arg("install")
+            .arg("-y")
+            .arg(package)
+            .status()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !status.success() {
+            return Err(GpuError::SystemError(format!("dnf install {package} failed")));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct PacmanBackend;
+
+#[async_trait]
+impl PackageBackend for PacmanBackend {
+    fn name(&self) -> &'static str {
+        "pacman"
+    }
+
+    async fn refresh(&self) -> Result<()> {
+        let status = AsyncCommand::new("sudo")
+            .arg("pacman")
+            .arg("-Sy")
+            .status()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !status.success() {
+            return Err(GpuError::SystemError("pacman -Sy failed".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn installed_version(&self, package: &str) -> Result<Option<String>> {
+        let output = AsyncCommand::new("pacman")
+            .arg("-Q")
+            .arg(package)
+            .output()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout.split_whitespace().nth(1).map(str::to_string))
+    }
+
+    async fn install(&self, package: &str) -> Result<()> {
+        let status = AsyncCommand::new("sudo")
+            .arg("pacman")
+            .arg("-S")
+            .arg("--noconfirm")
+            .arg(package)
+            .status()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !status.success() {
+            return Err(GpuError::SystemError(format!("pacman -S {package} failed")));
+        }
+        Ok(())
+    }
+}
+
+/// SUSE's driver-container tooling runs zypper with `--non-interactive` throughout so a prompt
+/// never blocks an unattended update; this backend does the same for both `refresh` and `install`.
+#[derive(Debug)]
+struct ZypperBackend;
+
+#[async_trait]
+impl PackageBackend for ZypperBackend {
+    fn name(&self) -> &'static str {
+        "zypper"
+    }
+
+    async fn refresh(&self) -> Result<()> {
+        let status = AsyncCommand::new("sudo")
+            .arg("zypper")
+            .arg("--non-interactive")
+            .arg("refresh")
+            .status()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !status.success() {
+            return Err(GpuError::SystemError("zypper refresh failed".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn installed_version(&self, package: &str) -> Result<Option<String>> {
+        let output = AsyncCommand::new("rpm")
+            .arg("-q")
+            .arg("--queryformat=%{VERSION}")
+            .arg(package)
+            .output()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok((!version.is_empty()).then_some(version))
+    }
+
+    async fn install(&self, package: &str) -> Result<()> {
+        let status = AsyncCommand::new("sudo")
+            .arg("zypper")
+            .arg("--non-interactive")
+            .arg("install")
+            .arg(package)
+            .status()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !status.success() {
+            return Err(GpuError::SystemError(format!("zypper install {package} failed")));
+        }
+        Ok(())
+    }
+}
+
 /// Driver update information
 #[derive(Debug, Clone)]
 pub struct DriverUpdate {
@@ -121,6 +816,265 @@ pub struct DriverUpdate {
     pub latest_version: String,
     pub download_url: Option<String>,
     pub critical: bool,
+    /// `true` when [`FallbackMap`] substituted `latest_version` for a version the installed GPU's
+    /// architecture actually supports, rather than the newest release upstream published
+    pub fallback_applied: bool,
+}
+
+/// NVIDIA's published Unix driver release branches. Kept distinct from a plain version compare
+/// because a system can deliberately stay on a branch (most often Legacy, for GPUs the newer
+/// branches have dropped) even once newer releases exist elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriverBranch {
+    /// Broadly validated, recommended for most systems
+    Production,
+    /// Latest features, shorter support window
+    NewFeature,
+    /// Kept selectable for older GPUs (e.g. the 470.xx branch) even once newer drivers exist
+    Legacy,
+}
+
+impl DriverBranch {
+    /// Fallback classification when the feed doesn't label a release's branch explicitly: NVIDIA
+    /// retired the 470.xx series as its last branch supporting Kepler-era GPUs, so anything at or
+    /// below that major version is treated as Legacy.
+    fn from_major_version(major: u32) -> Self {
+        if major <= 470 {
+            DriverBranch::Legacy
+        } else {
+            DriverBranch::Production
+        }
+    }
+}
+
+/// A single NVIDIA Unix driver release, as published in [`NvidiaReleaseFeed`].
+#[derive(Debug, Clone)]
+pub struct NvidiaRelease {
+    pub version: String,
+    pub branch: DriverBranch,
+    pub critical: bool,
+    pub download_url: String,
+}
+
+/// Parse a driver version like `"525.89"` or `"525.105.17"` into its integer components. Minor
+/// version fields can be two or three digits, so these have to compare as integer tuples --
+/// comparing the strings directly would put `"525.89"` after `"525.105.17"`.
+fn parse_version_components(version: &str) -> Vec<u32> {
+    version.split('.').filter_map(|part| part.parse().ok()).collect()
+}
+
+/// Compare two driver versions component-by-component rather than as strings.
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    parse_version_components(a).cmp(&parse_version_components(b))
+}
+
+/// Fetches and classifies NVIDIA's published Unix driver releases.
+#[derive(Debug)]
+struct NvidiaReleaseFeed {
+    client: reqwest::Client,
+}
+
+impl NvidiaReleaseFeed {
+    /// NVIDIA's published Unix driver release index: one `version,branch,critical,url` record
+    /// per line (`branch` is `production`, `new-feature`, or `legacy`; `critical` is
+    /// `true`/`false`).
+    const FEED_URL: &'static str = "https://download.nvidia.com/XFree86/Linux-x86_64/releases.csv";
+
+    fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn fetch(&self) -> Result<Vec<NvidiaRelease>> {
+        let body = self
+            .client
+            .get(Self::FEED_URL)
+            .send()
+            .await
+            .map_err(|e| GpuError::SystemError(format!("failed to fetch NVIDIA release feed: {e}")))?
+            .text()
+            .await
+            .map_err(|e| GpuError::SystemError(format!("failed to read NVIDIA release feed: {e}")))?;
+
+        Ok(Self::parse(&body))
+    }
+
+    /// Parse the feed body, skipping blank lines and any line missing a field.
+    fn parse(body: &str) -> Vec<NvidiaRelease> {
+        body.lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() {
+                    return None;
+                }
+
+                let mut fields = line.split(',');
+                let version = fields.next()?.trim().to_string();
+                let branch_field = fields.next()?.trim();
+                let critical = fields.next()?.trim().eq_ignore_ascii_case("true");
+                let download_url = fields.next()?.trim().to_string();
+
+                let branch = match branch_field {
+                    "production" => DriverBranch::Production,
+                    "new-feature" => DriverBranch::NewFeature,
+                    "legacy" => DriverBranch::Legacy,
+                    _ => DriverBranch::from_major_version(
+                        parse_version_components(&version).first().copied().unwrap_or(0),
+                    ),
+                };
+
+                Some(NvidiaRelease {
+                    version,
+                    branch,
+                    critical,
+                    download_url,
+                })
+            })
+            .collect()
+    }
+
+    /// The newest release on `branch`, by integer-component version comparison.
+    fn latest_on_branch(releases: &[NvidiaRelease], branch: DriverBranch) -> Option<&NvidiaRelease> {
+        releases
+            .iter()
+            .filter(|r| r.branch == branch)
+            .max_by(|a, b| compare_versions(&a.version, &b.version))
+    }
+}
+
+/// NVIDIA GPU silicon architecture family, resolved from the PCI device ID. Distinct from the
+/// crate-wide [`crate::GpuType`], which classifies form factor (discrete/integrated/external)
+/// rather than architecture generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GpuArchitecture {
+    Kepler,
+    Maxwell,
+    Pascal,
+    Turing,
+    Ampere,
+    AdaLovelace,
+    Unknown,
+}
+
+impl GpuArchitecture {
+    /// Best-effort architecture family for an NVIDIA PCI device ID, based on the device ID
+    /// blocks NVIDIA has historically allocated per generation.
+    fn from_device_id(device_id: u16) -> Self {
+        match device_id {
+            0x0FC0..=0x137F => GpuArchitecture::Kepler,
+            0x1380..=0x1AFF => GpuArchitecture::Maxwell,
+            0x1B00..=0x1DFF => GpuArchitecture::Pascal,
+            0x1E00..=0x21FF => GpuArchitecture::Turing,
+            0x2200..=0x25FF => GpuArchitecture::Ampere,
+            0x2600..=0x28FF => GpuArchitecture::AdaLovelace,
+            _ => GpuArchitecture::Unknown,
+        }
+    }
+}
+
+/// One [`FallbackMap`] entry: the range of driver major versions a [`GpuArchitecture`] still
+/// supports, and the version to substitute when a candidate update falls outside it.
+#[derive(Debug, Clone)]
+pub struct FallbackEntry {
+    pub min_major_version: u32,
+    pub max_major_version: u32,
+    pub fallback_driver_version: String,
+}
+
+impl FallbackEntry {
+    /// Whether `major` falls within this entry's supported range.
+    pub fn compatible(&self, major: u32) -> bool {
+        (self.min_major_version..=self.max_major_version).contains(&major)
+    }
+}
+
+/// GPU-architecture-keyed fallback table: clamps a driver update candidate to a version the
+/// installed GPU's silicon actually still supports, instead of blindly recommending the newest
+/// release upstream has dropped support for (e.g. a 555-series update offered to a Kepler card
+/// that caps out at R470).
+#[derive(Debug, Clone)]
+pub struct FallbackMap {
+    entries: HashMap<GpuArchitecture, FallbackEntry>,
+}
+
+impl Default for FallbackMap {
+    fn default() -> Self {
+        let mut entries = HashMap::new();
+        entries.insert(
+            GpuArchitecture::Kepler,
+            FallbackEntry {
+                min_major_version: 340,
+                max_major_version: 470,
+                fallback_driver_version: "470.239.06".to_string(),
+            },
+        );
+        entries.insert(
+            GpuArchitecture::Maxwell,
+            FallbackEntry {
+                min_major_version: 390,
+                max_major_version: 470,
+                fallback_driver_version: "470.239.06".to_string(),
+            },
+        );
+        entries.insert(
+            GpuArchitecture::Pascal,
+            FallbackEntry {
+                min_major_version: 390,
+                max_major_version: 570,
+                fallback_driver_version: "535.216.01".to_string(),
+            },
+        );
+        entries.insert(
+            GpuArchitecture::Turing,
+            FallbackEntry {
+                min_major_version: 410,
+                max_major_version: 999,
+                fallback_driver_version: "550.135".to_string(),
+            },
+        );
+        entries.insert(
+            GpuArchitecture::Ampere,
+            FallbackEntry {
+                min_major_version: 450,
+                max_major_version: 999,
+                fallback_driver_version: "550.135".to_string(),
+            },
+        );
+        entries.insert(
+            GpuArchitecture::AdaLovelace,
+            FallbackEntry {
+                min_major_version: 520,
+                max_major_version: 999,
+                fallback_driver_version: "550.135".to_string(),
+            },
+        );
+        Self { entries }
+    }
+}
+
+impl FallbackMap {
+    pub fn get(&self, architecture: GpuArchitecture) -> Option<&FallbackEntry> {
+        self.entries.get(&architecture)
+    }
+
+    /// Clamp `candidate` to a version `architecture` actually supports: when its major version
+    /// falls outside the architecture's range, substitute the entry's `fallback_driver_version`
+    /// and report that a substitution happened so the caller can flag the resulting
+    /// [`DriverUpdate`]. An architecture with no entry (including [`GpuArchitecture::Unknown`])
+    /// passes `candidate` through unchanged.
+    fn clamp(&self, architecture: GpuArchitecture, candidate: &str) -> (String, bool) {
+        let Some(entry) = self.get(architecture) else {
+            return (candidate.to_string(), false);
+        };
+
+        let major = parse_version_components(candidate).first().copied().unwrap_or(0);
+        if entry.compatible(major) {
+            (candidate.to_string(), false)
+        } else {
+            (entry.fallback_driver_version.clone(), true)
+        }
+    }
 }
 
 /// NVIDIA driver manager
@@ -153,9 +1107,62 @@ impl NvidiaDriver {
             return Ok(version);
         }
 
+        // Last resort: the driver's installed doc directory, which is still present even when
+        // the kernel module is unloaded or the dGPU has been runtime-suspended off the bus
+        if let Ok(version) = Self::get_version_from_doc_dir() {
+            return Ok(version);
+        }
+
         Err(GpuError::DriverNotFound("NVIDIA driver not found".to_string()))
     }
 
+    /// Resolve the installed NVIDIA driver version from its `/usr/share/doc` directory (e.g.
+    /// `nvidia-driver-535-535.183.01`), independent of whether the module is currently loaded.
+    fn get_version_from_doc_dir() -> Result<String> {
+        let entries = fs::read_dir("/usr/share/doc").map_err(GpuError::IoError)?;
+        let re = Regex::new(r"^nvidia-driver-\d+-(\d+(?:\.\d+)+)$").unwrap();
+
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let Some(name) = name.to_str() else { continue };
+            if let Some(caps) = re.captures(name) {
+                return Ok(caps[1].to_string());
+            }
+        }
+
+        Err(GpuError::DriverNotFound("no NVIDIA driver directory found under /usr/share/doc".to_string()))
+    }
+
+    /// The path to the NVIDIA driver's RTD3 capability file, installed alongside the driver
+    /// package.
+    const RTD3_CAPABILITY_PATH: &'static str = "/usr/share/nvidia/dynamic-power-capabilities.json";
+
+    /// Parse the NVIDIA driver's RTD3 capability file to determine whether this GPU/platform
+    /// combination advertises runtime D3 support at all.
+    fn detect_rtd3_driver_support() -> Result<bool> {
+        let content = fs::read_to_string(Self::RTD3_CAPABILITY_PATH).map_err(GpuError::IoError)?;
+        let parsed: RtdCapabilityFile = serde_json::from_str(&content)
+            .map_err(|e| GpuError::InvalidConfig(format!("malformed RTD3 capability file: {e}")))?;
+        Ok(parsed.rtd3_supported)
+    }
+
+    /// Whether `pci_address`'s PCI device currently has runtime power management
+    /// (`power/control`) set to `"auto"` -- i.e. RTD3 isn't just supported but actually enabled.
+    fn detect_rtd3_enabled(pci_address: &str) -> bool {
+        fs::read_to_string(format!("/sys/bus/pci/devices/{pci_address}/power/control"))
+            .map(|content| content.trim() == "auto")
+            .unwrap_or(false)
+    }
+
+    /// Combine the driver-reported RTD3 support with the live PCI power-control state for
+    /// `pci_address`.
+    fn detect_rtd3_capabilities(pci_address: &str) -> Rtd3Capabilities {
+        Rtd3Capabilities {
+            driver_supports_rtd3: Self::detect_rtd3_driver_support().unwrap_or(false),
+            rtd3_enabled: Self::detect_rtd3_enabled(pci_address),
+        }
+    }
+
     async fn get_version_from_nvidia_smi() -> Result<String> {
         if which("nvidia-smi").is_err() {
             return Err(GpuError::DriverNotFound("nvidia-smi not found".to_string()));
@@ -178,6 +1185,31 @@ impl NvidiaDriver {
         Err(GpuError::DriverNotFound("Failed to get NVIDIA driver version".to_string()))
     }
 
+    /// Resolve the installed card's PCI device ID via `nvidia-smi`, used to look up its
+    /// [`GpuArchitecture`] in [`FallbackMap`]. `nvidia-smi` reports it as an 8-hex-digit
+    /// `0x<device_id><vendor_id>` pair, e.g. `"0x268410DE"`.
+    async fn detect_device_id() -> Result<u16> {
+        let output = AsyncCommand::new("nvidia-smi")
+            .arg("--query-gpu=pci.device_id")
+            .arg("--format=csv,noheader")
+            .output()
+            .await
+            .map_err(GpuError::IoError)?;
+
+        if !output.status.success() {
+            return Err(GpuError::DriverNotFound("failed to query NVIDIA PCI device id".to_string()));
+        }
+
+        let raw = String::from_utf8_lossy(&output.stdout);
+        let hex = raw.trim().trim_start_matches("0x").trim_start_matches("0X");
+        let device_id_field = hex
+            .get(0..4)
+            .ok_or_else(|| GpuError::DriverNotFound(format!("unexpected pci.device_id format: {raw}")))?;
+
+        u16::from_str_radix(device_id_field, 16)
+            .map_err(|_| GpuError::DriverNotFound(format!("unexpected pci.device_id format: {raw}")))
+    }
+
     async fn get_version_from_modinfo() -> Result<String> {
         let output = AsyncCommand::new("modinfo")
             .arg("nvidia")
@@ -229,84 +1261,78 @@ impl NvidiaDriver {
             .ok_or_else(|| GpuError::DriverNotFound("NVIDIA driver version unknown".to_string()))
     }
 
-    async fn check_update(&self) -> Result<Option<DriverUpdate>> {
+    async fn check_update(&self, branch: DriverBranch) -> Result<Option<DriverUpdate>> {
         let current_version = self.get_current_version().await?;
-        
-        // For now, we'll implement a basic check
-        // In a real implementation, this would check NVIDIA's servers
-        let latest_version = Self::get_latest_nvidia_version().await?;
-        
-        if current_version != latest_version {
-            return Ok(Some(DriverUpdate {
-                vendor: "NVIDIA".to_string(),
-                current_version,
-                latest_version,
-                download_url: None,
-                critical: false,
-            }));
-        }
-        
-        Ok(None)
-    }
 
-    async fn get_latest_nvidia_version() -> Result<String> {
-        // This would typically query NVIDIA's API or scrape their website
-        // For now, return a placeholder
-        Ok("525.105.17".to_string())
-    }
+        let releases = NvidiaReleaseFeed::new().fetch().await?;
+        let Some(latest) = NvidiaReleaseFeed::latest_on_branch(&releases, branch) else {
+            return Ok(None);
+        };
 
-    async fn update_driver(&self) -> Result<()> {
-        // Check if we're on Ubuntu and can use apt
-        if Path::new("/usr/bin/apt").exists() {
-            self.update_nvidia_ubuntu().await
-        } else {
-            Err(GpuError::OperationNotSupported("Automatic NVIDIA driver updates only supported on Ubuntu".to_string()))
+        if compare_versions(&current_version, &latest.version) != std::cmp::Ordering::Less {
+            return Ok(None);
         }
-    }
 
-    async fn update_nvidia_ubuntu(&self) -> Result<()> {
-        info!("Updating NVIDIA driver on Ubuntu");
-        
-        // Add NVIDIA PPA if not present
-        let add_ppa = AsyncCommand::new("sudo")
-            .arg("add-apt-repository")
-            .arg("-y")
-            .arg("ppa:graphics-drivers/ppa")
-            .status()
+        let architecture = Self::detect_device_id()
             .await
-            .map_err(GpuError::IoError)?;
+            .map(GpuArchitecture::from_device_id)
+            .unwrap_or(GpuArchitecture::Unknown);
+        let (latest_version, fallback_applied) = FallbackMap::default().clamp(architecture, &latest.version);
 
-        if !add_ppa.success() {
-            warn!("Failed to add NVIDIA PPA");
+        if latest_version == current_version {
+            // The architecture's fallback version is what's already installed -- nothing to do.
+            return Ok(None);
         }
 
-        // Update package list
-        let update_status = AsyncCommand::new("sudo")
-            .arg("apt")
-            .arg("update")
-            .status()
-            .await
-            .map_err(GpuError::IoError)?;
+        Ok(Some(DriverUpdate {
+            vendor: "NVIDIA".to_string(),
+            current_version,
+            latest_version,
+            download_url: if fallback_applied { None } else { Some(latest.download_url.clone()) },
+            critical: !fallback_applied && latest.critical,
+            fallback_applied,
+        }))
+    }
 
-        if !update_status.success() {
-            return Err(GpuError::SystemError("Failed to update package list".to_string()));
+    async fn update_driver(&self, backend: &dyn PackageBackend, unattended: bool) -> Result<()> {
+        info!("Updating NVIDIA driver via {}", backend.name());
+
+        if backend.name() == "apt" {
+            // Ubuntu/Debian ship NVIDIA's driver through the graphics-drivers PPA, not the
+            // distro repos -- add it before refreshing so `apt` can see current driver packages.
+            let add_ppa = AsyncCommand::new("sudo")
+                .arg("add-apt-repository")
+                .arg("-y")
+                .arg("ppa:graphics-drivers/ppa")
+                .status()
+                .await
+                .map_err(GpuError::IoError)?;
+
+            if !add_ppa.success() {
+                warn!("Failed to add NVIDIA PPA");
+            }
         }
 
-        // Install latest driver
-        let install_status = AsyncCommand::new("sudo")
-            .arg("apt")
-            .arg("install")
-            .arg("-y")
-            .arg("nvidia-driver-525")
-            .status()
-            .await
-            .map_err(GpuError::IoError)?;
+        backend.refresh().await?;
+
+        let package = match backend.name() {
+            "apt" => "nvidia-driver-525",
+            "dnf" => "akmod-nvidia",
+            "pacman" => "nvidia",
+            "zypper" => "nvidia-open-driver-G06-signed-cuda",
+            other => {
+                return Err(GpuError::OperationNotSupported(format!(
+                    "no NVIDIA driver package mapping for package backend `{other}`"
+                )))
+            }
+        };
 
-        if !install_status.success() {
-            return Err(GpuError::SystemError("Failed to install NVIDIA driver".to_string()));
+        if unattended {
+            backend.preseed_unattended(package).await?;
         }
+        backend.install(package).await?;
 
-        info!("NVIDIA driver updated successfully");
+        info!("NVIDIA driver updated successfully via {}", backend.name());
         Ok(())
     }
 }
@@ -403,6 +1429,7 @@ impl AmdDriver {
                     latest_version,
                     download_url: None,
                     critical: false,
+                    fallback_applied: false,
                 }));
             }
         }
@@ -416,47 +1443,30 @@ impl AmdDriver {
         Ok("6.1.0".to_string())
     }
 
-    async fn update_driver(&self) -> Result<()> {
-        // AMD drivers are typically updated through system updates
-        if Path::new("/usr/bin/apt").exists() {
-            self.update_amd_ubuntu().await
-        } else {
-            Err(GpuError::OperationNotSupported("Automatic AMD driver updates only supported on Ubuntu".to_string()))
-        }
-    }
-
-    async fn update_amd_ubuntu(&self) -> Result<()> {
-        info!("Updating AMD driver components on Ubuntu");
-        
-        // Update mesa and kernel components
-        let update_status = AsyncCommand::new("sudo")
-            .arg("apt")
-            .arg("update")
-            .status()
-            .await
-            .map_err(GpuError::IoError)?;
-
-        if !update_status.success() {
-            return Err(GpuError::SystemError("Failed to update package list".to_string()));
-        }
-
-        // Upgrade mesa drivers
-        let upgrade_status = AsyncCommand::new("sudo")
-            .arg("apt")
-            .arg("install")
-            .arg("-y")
-            .arg("mesa-vulkan-drivers")
-            .arg("libdrm-amdgpu1")
-            .arg("xserver-xorg-video-amdgpu")
-            .status()
-            .await
-            .map_err(GpuError::IoError)?;
+    async fn update_driver(&self, backend: &dyn PackageBackend) -> Result<()> {
+        // AMD drivers come through kernel/mesa packages, so updating is a plain refresh + install
+        // on every backend, unlike NVIDIA's out-of-tree driver.
+        info!("Updating AMD driver components via {}", backend.name());
+
+        backend.refresh().await?;
+
+        let packages: &[&str] = match backend.name() {
+            "apt" => &["mesa-vulkan-drivers", "libdrm-amdgpu1", "xserver-xorg-video-amdgpu"],
+            "dnf" => &["mesa-vulkan-drivers", "libdrm"],
+            "pacman" => &["vulkan-radeon", "libdrm"],
+            "zypper" => &["Mesa-vulkan-drivers", "libdrm2"],
+            other => {
+                return Err(GpuError::OperationNotSupported(format!(
+                    "no AMD driver package mapping for package backend `{other}`"
+                )))
+            }
+        };
 
-        if !upgrade_status.success() {
-            return Err(GpuError::SystemError("Failed to upgrade AMD drivers".to_string()));
+        for package in packages {
+            backend.install(package).await?;
         }
 
-        info!("AMD driver components updated successfully");
+        info!("AMD driver components updated successfully via {}", backend.name());
         Ok(())
     }
 }
@@ -480,9 +1490,190 @@ mod tests {
             latest_version: "525.105.17".to_string(),
             download_url: None,
             critical: false,
+            fallback_applied: false,
         };
         
         assert_eq!(update.vendor, "NVIDIA");
         assert!(!update.critical);
     }
+
+    #[test]
+    fn test_compare_versions_handles_mixed_minor_digit_counts() {
+        // "525.89" must sort before "525.105.17" -- a string compare would get this backwards.
+        assert_eq!(compare_versions("525.89", "525.105.17"), std::cmp::Ordering::Less);
+        assert_eq!(compare_versions("525.105.17", "525.89"), std::cmp::Ordering::Greater);
+        assert_eq!(compare_versions("535.54.03", "535.54.03"), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_parse_release_feed() {
+        let body = "\
+            535.154.05,production,false,https://example.com/535.154.05\n\
+            545.29.06,new-feature,true,https://example.com/545.29.06\n\
+            470.239.06,legacy,false,https://example.com/470.239.06\n";
+
+        let releases = NvidiaReleaseFeed::parse(body);
+        assert_eq!(releases.len(), 3);
+        assert_eq!(releases[0].branch, DriverBranch::Production);
+        assert!(releases[1].critical);
+        assert_eq!(releases[2].branch, DriverBranch::Legacy);
+    }
+
+    #[test]
+    fn test_latest_on_branch_keeps_legacy_selectable() {
+        let releases = NvidiaReleaseFeed::parse(
+            "535.154.05,production,false,https://example.com/535.154.05\n\
+             470.239.06,legacy,false,https://example.com/470.239.06\n",
+        );
+
+        let legacy = NvidiaReleaseFeed::latest_on_branch(&releases, DriverBranch::Legacy).unwrap();
+        assert_eq!(legacy.version, "470.239.06");
+    }
+
+    #[test]
+    fn test_fallback_entry_compatible_range() {
+        let entry = FallbackEntry {
+            min_major_version: 340,
+            max_major_version: 470,
+            fallback_driver_version: "470.239.06".to_string(),
+        };
+        assert!(entry.compatible(470));
+        assert!(entry.compatible(340));
+        assert!(!entry.compatible(555));
+    }
+
+    #[test]
+    fn test_fallback_map_clamps_kepler_to_r470() {
+        let map = FallbackMap::default();
+        let (version, applied) = map.clamp(GpuArchitecture::Kepler, "555.58.02");
+        assert!(applied);
+        assert_eq!(version, "470.239.06");
+    }
+
+    #[test]
+    fn test_fallback_map_leaves_supported_architecture_unclamped() {
+        let map = FallbackMap::default();
+        let (version, applied) = map.clamp(GpuArchitecture::AdaLovelace, "550.135");
+        assert!(!applied);
+        assert_eq!(version, "550.135");
+    }
+
+    #[test]
+    fn test_fallback_map_unknown_architecture_passes_through() {
+        let map = FallbackMap::default();
+        let (version, applied) = map.clamp(GpuArchitecture::Unknown, "555.58.02");
+        assert!(!applied);
+        assert_eq!(version, "555.58.02");
+    }
+
+    #[test]
+    fn test_gpu_architecture_from_device_id() {
+        assert_eq!(GpuArchitecture::from_device_id(0x1380), GpuArchitecture::Maxwell);
+        assert_eq!(GpuArchitecture::from_device_id(0x2504), GpuArchitecture::Ampere);
+        assert_eq!(GpuArchitecture::from_device_id(0x0000), GpuArchitecture::Unknown);
+    }
+
+    #[test]
+    fn test_package_backend_names() {
+        assert_eq!(AptBackend.name(), "apt");
+        assert_eq!(DnfBackend.name(), "dnf");
+        assert_eq!(PacmanBackend.name(), "pacman");
+        assert_eq!(ZypperBackend.name(), "zypper");
+    }
+
+    #[test]
+    fn test_pci_gpu_inventory_filters_by_vendor() {
+        let inventory = PciGpuInventory {
+            devices: vec![
+                PciGpuDevice {
+                    address: "0000:01:00.0".to_string(),
+                    vendor_id: 0x10DE,
+                    device_id: 0x2684,
+                    driver: Some("nvidia".to_string()),
+                },
+                PciGpuDevice {
+                    address: "0000:03:00.0".to_string(),
+                    vendor_id: 0x1002,
+                    device_id: 0x73FF,
+                    driver: None,
+                },
+            ],
+        };
+
+        assert_eq!(inventory.nvidia_devices().count(), 1);
+        assert_eq!(inventory.amd_devices().count(), 1);
+        assert_eq!(inventory.nvidia_devices().next().unwrap().address, "0000:01:00.0");
+    }
+
+    #[test]
+    fn test_empty_pci_inventory_scan_missing_sysfs() {
+        // No `/sys/bus/pci/devices` assumption is made -- a missing tree yields an empty
+        // inventory instead of panicking, which matters in sandboxes/containers.
+        let inventory = PciGpuInventory::default();
+        assert!(inventory.devices.is_empty());
+        assert_eq!(inventory.nvidia_devices().count(), 0);
+    }
+
+    #[test]
+    fn test_pci_address_to_xorg_bus_id() {
+        assert_eq!(pci_address_to_xorg_bus_id("0000:01:00.0").unwrap(), "PCI:1:0:0");
+        assert_eq!(pci_address_to_xorg_bus_id("0000:0a:00.1").unwrap(), "PCI:10:0:1");
+        assert!(pci_address_to_xorg_bus_id("not-a-pci-address").is_none());
+    }
+
+    #[test]
+    fn test_render_prime_offload_xorg_conf_contains_bus_id() {
+        let conf = render_prime_offload_xorg_conf("PCI:1:0:0");
+        assert!(conf.contains("BusID \"PCI:1:0:0\""));
+        assert!(conf.contains("OutputClass"));
+    }
+
+    #[test]
+    fn test_gpu_power_mode_detect() {
+        let rtd3_enabled = Rtd3Capabilities { driver_supports_rtd3: true, rtd3_enabled: true };
+        let rtd3_unsupported = Rtd3Capabilities { driver_supports_rtd3: false, rtd3_enabled: false };
+
+        assert_eq!(GpuPowerMode::detect(false, &rtd3_unsupported), Some(GpuPowerMode::Integrated));
+        assert_eq!(GpuPowerMode::detect(true, &rtd3_enabled), Some(GpuPowerMode::Hybrid));
+        assert_eq!(GpuPowerMode::detect(true, &rtd3_unsupported), Some(GpuPowerMode::Discrete));
+    }
+
+    #[test]
+    fn test_rtd3_capabilities_can_offer_hybrid_mode() {
+        assert!(Rtd3Capabilities { driver_supports_rtd3: true, rtd3_enabled: false }.can_offer_hybrid_mode());
+        assert!(!Rtd3Capabilities::default().can_offer_hybrid_mode());
+    }
+
+    #[test]
+    fn test_nvidia_package_for_version_versions_only_apt() {
+        assert_eq!(
+            nvidia_package_for_version("apt", "535.183.01").unwrap(),
+            "nvidia-driver-535"
+        );
+        assert_eq!(nvidia_package_for_version("dnf", "535.183.01").unwrap(), "akmod-nvidia");
+        assert_eq!(nvidia_package_for_version("pacman", "535.183.01").unwrap(), "nvidia");
+        assert!(nvidia_package_for_version("unknown-backend", "535.183.01").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_driver_manager_rollback_without_prior_update_errors() {
+        let manager = DriverManager::new();
+        assert!(manager.rollback_driver(false).await.is_err());
+    }
+
+    #[test]
+    fn test_nvidia_debconf_selections_ends_in_newline() {
+        let selections = nvidia_debconf_selections("nvidia-driver-535");
+        assert!(selections.ends_with('\n'));
+        assert!(selections.contains("nvidia-driver-535 shared/accepted-nvidia-license-question boolean true"));
+        assert!(selections.contains("nvidia-driver-535 shared/accepted-nvidia-nonfree-question boolean true"));
+    }
+
+    #[test]
+    fn test_driver_backup_capture_records_missing_files_as_none() {
+        let backup = DriverBackup::capture("nvidia", "470.86".to_string());
+        assert_eq!(backup.vendor, "nvidia");
+        assert_eq!(backup.previous_version, "470.86");
+        assert_eq!(backup.configs.len(), 2);
+    }
 }
\ No newline at end of file
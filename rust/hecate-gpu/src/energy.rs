@@ -0,0 +1,212 @@
+//! Energy and cost accounting
+//!
+//! [`calculate_efficiency_score`](crate::calculate_efficiency_score) scores a single instantaneous
+//! telemetry snapshot, but neither it nor anything else in the crate tracks what a GPU actually
+//! *cost* to run over a workload window. [`EnergyTracker`] integrates power-draw samples into
+//! cumulative Watt-hours per GPU using the trapezoidal rule (power genuinely steps between
+//! samples rather than holding flat, so a trapezoid halves the error a naive `power * elapsed`
+//! rectangle would carry), and [`CostModel`] turns that into a monetary figure. The resulting
+//! [`EnergyReport::perf_per_watt`] is meant as the ranking key for the load balancer that
+//! [`GpuManager::enable_load_balancing`](crate::GpuManager::enable_load_balancing) still stubs out.
+
+use crate::GpuStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+use tokio::sync::RwLock;
+
+/// Integrates one GPU's power-draw samples into cumulative Watt-hours
+#[derive(Debug)]
+struct EnergyAccumulator {
+    watt_hours: f64,
+    last_sample: Option<(Instant, u32)>,
+}
+
+impl EnergyAccumulator {
+    fn new() -> Self {
+        Self {
+            watt_hours: 0.0,
+            last_sample: None,
+        }
+    }
+
+    /// Fold in a new power-draw sample, trapezoidally integrating against the previous one
+    fn sample(&mut self, power_draw_watts: u32) {
+        let now = Instant::now();
+        if let Some((last_time, last_watts)) = self.last_sample {
+            let elapsed_hours = now.duration_since(last_time).as_secs_f64() / 3600.0;
+            let average_watts = (last_watts as f64 + power_draw_watts as f64) / 2.0;
+            self.watt_hours += average_watts * elapsed_hours;
+        }
+        self.last_sample = Some((now, power_draw_watts));
+    }
+}
+
+/// Converts cumulative Watt-hours into a monetary cost at a flat per-kWh rate
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct CostModel {
+    pub kwh_price: f64,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        // A generic placeholder rate; callers with a real utility tariff or datacenter PPA
+        // should override this via `GpuManager::set_cost_model`.
+        Self { kwh_price: 0.15 }
+    }
+}
+
+impl CostModel {
+    pub fn cost_for(&self, watt_hours: f64) -> f64 {
+        (watt_hours / 1000.0) * self.kwh_price
+    }
+}
+
+/// Cumulative energy, cost, and efficiency accounting for a single GPU over the current
+/// workload window
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct EnergyReport {
+    pub gpu_index: u32,
+    pub watt_hours: f64,
+    pub cost: f64,
+    /// Utilization percentage points per Watt currently drawn. Higher is better; this is the
+    /// ranking key the planned load balancer should steer work by, since it rewards GPUs doing
+    /// more with less power rather than just the ones with the lowest instantaneous draw.
+    pub perf_per_watt: f32,
+}
+
+/// Rolling per-GPU energy tracker, sampled on each monitoring tick
+#[derive(Debug)]
+pub struct EnergyTracker {
+    accumulators: RwLock<HashMap<u32, EnergyAccumulator>>,
+    cost_model: RwLock<CostModel>,
+}
+
+impl EnergyTracker {
+    pub fn new(cost_model: CostModel) -> Self {
+        Self {
+            accumulators: RwLock::new(HashMap::new()),
+            cost_model: RwLock::new(cost_model),
+        }
+    }
+
+    pub async fn set_cost_model(&self, cost_model: CostModel) {
+        *self.cost_model.write().await = cost_model;
+    }
+
+    /// Fold each GPU's current power draw into its rolling accumulator
+    pub async fn sample(&self, statuses: &[GpuStatus]) {
+        let mut accumulators = self.accumulators.write().await;
+        for status in statuses {
+            accumulators
+                .entry(status.index)
+                .or_insert_with(EnergyAccumulator::new)
+                .sample(status.power_draw);
+        }
+    }
+
+    /// Produce a per-GPU report of cumulative energy, cost, and efficiency. `statuses` supplies
+    /// each GPU's current utilization/power-draw snapshot for the `perf_per_watt` figure; GPUs
+    /// with no accumulated samples yet are omitted.
+    pub async fn report(&self, statuses: &[GpuStatus]) -> Vec<EnergyReport> {
+        let accumulators = self.accumulators.read().await;
+        let cost_model = *self.cost_model.read().await;
+
+        statuses
+            .iter()
+            .filter_map(|status| {
+                let accumulator = accumulators.get(&status.index)?;
+                let perf_per_watt = if status.power_draw == 0 {
+                    0.0
+                } else {
+                    status.utilization_gpu as f32 / status.power_draw as f32
+                };
+
+                Some(EnergyReport {
+                    gpu_index: status.index,
+                    watt_hours: accumulator.watt_hours,
+                    cost: cost_model.cost_for(accumulator.watt_hours),
+                    perf_per_watt,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_model_converts_watt_hours_to_price() {
+        let model = CostModel { kwh_price: 0.20 };
+        assert!((model.cost_for(1000.0) - 0.20).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn accumulator_integrates_trapezoidally_over_time() {
+        let mut accumulator = EnergyAccumulator::new();
+        accumulator.sample(100);
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        accumulator.sample(200);
+
+        // Average of 100W and 200W over ~50ms should be a small but nonzero Wh figure
+        assert!(accumulator.watt_hours > 0.0);
+        assert!(accumulator.watt_hours < 0.01);
+    }
+
+    #[tokio::test]
+    async fn tracker_omits_gpus_with_no_samples_yet() {
+        let tracker = EnergyTracker::new(CostModel::default());
+        let sampled = status(0);
+        let unsampled = status(1);
+
+        tracker.sample(std::slice::from_ref(&sampled)).await;
+        let reports = tracker.report(&[sampled, unsampled]).await;
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].gpu_index, 0);
+    }
+
+    fn status(index: u32) -> GpuStatus {
+        GpuStatus {
+            index,
+            name: "test GPU".to_string(),
+            vendor: crate::GpuVendor::Unknown,
+            gpu_type: crate::GpuType::Discrete,
+            temperature: 60,
+            power_draw: 100,
+            power_limit: Some(250),
+            memory_used: 0,
+            memory_total: 1,
+            utilization_gpu: 50,
+            utilization_memory: 0,
+            fan_speed: None,
+            clock_graphics: 0,
+            clock_memory: Some(0),
+            driver_version: None,
+            pci_info: crate::PciInfo {
+                domain: 0,
+                bus: 0,
+                device: 0,
+                function: 0,
+                vendor_id: 0,
+                device_id: 0,
+            },
+            power_state: crate::PowerState::Active,
+            voltage_mv: None,
+            throttle_reasons: Vec::new(),
+            ecc_errors: None,
+            processes: Vec::new(),
+            driver_bound: crate::vfio::DriverBinding::Unbound,
+            unified_memory: false,
+            mig_parent: None,
+            mig_uuid: None,
+            uuid: None,
+            serial: None,
+            board_part_number: None,
+            vbios_version: None,
+            cuda_driver_version: None,
+        }
+    }
+}
@@ -48,6 +48,11 @@ pub enum GpuError {
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 
+    /// A driver update failed partway through and the manager restored the previous driver and
+    /// config instead of leaving the system half-updated
+    #[error("driver update was rolled back: {0}")]
+    UpdateRolledBack(String),
+
     /// Timeout occurred during operation
     #[error("Operation timed out after {0:?}")]
     Timeout(std::time::Duration),
@@ -76,6 +81,10 @@ pub enum GpuError {
     #[error("PCI device error: {0}")]
     PciError(String),
 
+    /// The `pci.ids` database could not be parsed
+    #[error("Invalid pci.ids database: {0}")]
+    PciDatabaseError(String),
+
     /// Configuration parsing error
     #[error("Configuration parsing error: {0}")]
     ConfigParseError(#[from] toml::de::Error),
@@ -83,6 +92,20 @@ pub enum GpuError {
     /// Serialization error
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    /// Requested value exceeds the hardware's safe operating limit for this model
+    #[error("Requested value {requested} exceeds hardware safe limit of {max}")]
+    LimitExceeded { requested: i64, max: i64 },
+
+    /// Requested voltage offset falls outside the safe range for this model
+    #[error("Voltage offset {requested}mV is out of range ({min}mV..={max}mV)")]
+    VoltageOutOfRange { requested: i32, min: i32, max: i32 },
+
+    /// A requested value falls outside an explicit min/max limit range, either because the
+    /// range itself is inverted (`min > max`) or because it falls outside what the hardware
+    /// reports as safe
+    #[error("Requested value {requested} is out of range ({min}..={max})")]
+    OutOfRange { requested: i64, min: i64, max: i64 },
 }
 
 #[cfg(feature = "nvidia")]
@@ -125,11 +148,14 @@ impl GpuError {
             Self::DriverNotFound(_) => ErrorSeverity::High,
             Self::OperationNotSupported(_) => ErrorSeverity::Medium,
             Self::InvalidConfig(_) => ErrorSeverity::Medium,
+            Self::UpdateRolledBack(_) => ErrorSeverity::High,
             Self::InvalidState(_, _) => ErrorSeverity::Medium,
             Self::PowerError(_) => ErrorSeverity::Medium,
             Self::ThermalError(_) => ErrorSeverity::High,
             Self::MemoryError(_) => ErrorSeverity::High,
             Self::Timeout(_) => ErrorSeverity::Low,
+            Self::LimitExceeded { .. } => ErrorSeverity::High,
+            Self::VoltageOutOfRange { .. } => ErrorSeverity::High,
             _ => ErrorSeverity::Medium,
         }
     }
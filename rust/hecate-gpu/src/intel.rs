@@ -0,0 +1,618 @@
+//! Intel GPU backend implementation using i915 sysfs and RAPL energy accounting
+//!
+//! Targets the common case of Intel integrated graphics on hybrid laptops. Without this backend
+//! those iGPUs are invisible to `GpuManager`, which breaks the integrated<->discrete `switch_gpu`
+//! path: there's nothing to switch *to* or *from* if only the discrete GPU is ever detected.
+
+use crate::{
+    error::{GpuError, Result},
+    GpuBackend, GpuConfig, GpuProcess, GpuStatus, GpuType, GpuVendor, MinMax, PowerMode,
+    PowerState, FanCurve, PciInfo,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{info, instrument, warn};
+
+/// Intel GPU device information
+#[derive(Debug, Clone)]
+struct IntelDevice {
+    index: u32,
+    device_path: PathBuf,
+    hwmon_path: Option<PathBuf>,
+    drm_path: PathBuf,
+    pci_id: String,
+}
+
+/// Intel GPU backend using i915 sysfs and RAPL
+#[derive(Debug)]
+pub struct IntelBackend {
+    devices: Arc<RwLock<HashMap<u32, IntelDevice>>>,
+}
+
+impl IntelBackend {
+    /// Create a new Intel backend
+    pub async fn new() -> Result<Self> {
+        Ok(Self {
+            devices: Arc::new(RwLock::new(HashMap::new())),
+        })
+    }
+
+    /// Find Intel GPUs in the system
+    fn find_intel_devices() -> Result<Vec<IntelDevice>> {
+        let mut devices = Vec::new();
+        let mut index = 0;
+
+        let drm_path = Path::new("/sys/class/drm");
+        if !drm_path.exists() {
+            return Ok(devices);
+        }
+
+        for entry in fs::read_dir(drm_path).map_err(GpuError::IoError)? {
+            let entry = entry.map_err(GpuError::IoError)?;
+            let path = entry.path();
+            let name = entry.file_name();
+
+            if let Some(name_str) = name.to_str() {
+                if name_str.starts_with("card") && !name_str.contains("renderD") {
+                    let device_path = path.join("device");
+
+                    if Self::is_intel_device(&device_path)? {
+                        let pci_id = Self::get_pci_id(&device_path)?;
+                        let hwmon_path = Self::find_hwmon_path(&device_path)?;
+
+                        devices.push(IntelDevice {
+                            index,
+                            device_path,
+                            hwmon_path,
+                            drm_path: path,
+                            pci_id,
+                        });
+                        index += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(devices)
+    }
+
+    /// Check if a device is an Intel GPU
+    fn is_intel_device(device_path: &Path) -> Result<bool> {
+        let vendor_path = device_path.join("vendor");
+        if let Ok(vendor) = fs::read_to_string(&vendor_path) {
+            // Intel vendor ID is 0x8086
+            return Ok(vendor.trim() == "0x8086");
+        }
+        Ok(false)
+    }
+
+    /// Get PCI ID for the device
+    fn get_pci_id(device_path: &Path) -> Result<String> {
+        let device_id = fs::read_to_string(device_path.join("device"))
+            .map_err(GpuError::IoError)?
+            .trim()
+            .to_string();
+        let vendor_id = fs::read_to_string(device_path.join("vendor"))
+            .map_err(GpuError::IoError)?
+            .trim()
+            .to_string();
+
+        Ok(format!("{}:{}", vendor_id, device_id))
+    }
+
+    /// Find hwmon path for power monitoring
+    fn find_hwmon_path(device_path: &Path) -> Result<Option<PathBuf>> {
+        let hwmon_dir = device_path.join("hwmon");
+
+        if !hwmon_dir.exists() {
+            return Ok(None);
+        }
+
+        for entry in fs::read_dir(&hwmon_dir).map_err(GpuError::IoError)? {
+            let entry = entry.map_err(GpuError::IoError)?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    if name.starts_with("hwmon") {
+                        return Ok(Some(path));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Read a `gt_*_freq_mhz` sysfs node. Some kernels nest these under `gt/gt0/` instead of the
+    /// device root depending on the i915 version, so both locations are tried before falling
+    /// back to `default_mhz`.
+    fn read_gt_freq(device: &IntelDevice, file: &str, default_mhz: u32) -> u32 {
+        for path in [device.device_path.join(file), device.device_path.join("gt").join("gt0").join(file)] {
+            if let Ok(s) = fs::read_to_string(&path) {
+                if let Ok(mhz) = s.trim().parse::<u32>() {
+                    return mhz;
+                }
+            }
+        }
+        default_mhz
+    }
+
+    /// Read GPU utilization
+    fn read_gpu_utilization(device: &IntelDevice) -> u32 {
+        fs::read_to_string(device.device_path.join("gpu_busy_percent"))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Read current power draw in watts. Prefers an i915 hwmon power sensor where the kernel
+    /// exposes one; falls back to a short RAPL energy-counter sample window otherwise.
+    async fn read_power_draw(device: &IntelDevice) -> u32 {
+        if let Some(ref hwmon_path) = device.hwmon_path {
+            if let Ok(s) = fs::read_to_string(hwmon_path.join("power1_input")) {
+                if let Ok(microwatts) = s.trim().parse::<u32>() {
+                    return microwatts / 1_000_000;
+                }
+            }
+        }
+
+        Self::read_rapl_power_watts().await.unwrap_or(0)
+    }
+
+    /// Estimate GPU power draw by sampling the RAPL `gpu`/`uncore` domain's cumulative
+    /// `energy_uj` counter twice over a short window and converting the delta to watts
+    async fn read_rapl_power_watts() -> Option<u32> {
+        let domain = Self::find_rapl_gpu_domain()?;
+        let energy_path = domain.join("energy_uj");
+
+        let start = Self::read_rapl_energy_uj(&energy_path)?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let end = Self::read_rapl_energy_uj(&energy_path)?;
+
+        let delta_uj = end.saturating_sub(start);
+        Some((delta_uj as f64 / 100_000.0) as u32) // microjoules over 100ms -> watts
+    }
+
+    fn read_rapl_energy_uj(path: &Path) -> Option<u64> {
+        fs::read_to_string(path).ok()?.trim().parse().ok()
+    }
+
+    /// Find the RAPL powercap domain that accounts for GPU energy. Named "gpu" on most laptop
+    /// platforms with an integrated GPU, "uncore" on some desktop ones; absent entirely on
+    /// systems without RAPL GPU accounting, in which case power draw falls back to 0.
+    fn find_rapl_gpu_domain() -> Option<PathBuf> {
+        let powercap = Path::new("/sys/class/powercap");
+        let entries = fs::read_dir(powercap).ok()?;
+
+        for entry in entries.flatten() {
+            let Ok(subentries) = fs::read_dir(entry.path()) else { continue };
+            for sub in subentries.flatten() {
+                let sub_path = sub.path();
+                if let Ok(name) = fs::read_to_string(sub_path.join("name")) {
+                    if matches!(name.trim(), "gpu" | "uncore") {
+                        return Some(sub_path);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Read the power limit in watts from hwmon, or `None` if no `power1_cap` sensor is exposed
+    /// rather than guessing at a typical integrated-GPU share of the package TDP
+    fn read_power_limit(device: &IntelDevice) -> Option<u32> {
+        let hwmon_path = device.hwmon_path.as_ref()?;
+        let s = fs::read_to_string(hwmon_path.join("power1_cap")).ok()?;
+        s.trim().parse::<u32>().ok().map(|microwatts| microwatts / 1_000_000)
+    }
+
+    /// Get device name
+    fn get_device_name(device: &IntelDevice) -> String {
+        if let Ok(name) = fs::read_to_string(device.drm_path.join("device/label")) {
+            let name = name.trim();
+            if !name.is_empty() {
+                return name.to_string();
+            }
+        }
+        format!("Intel GPU {}", device.pci_id)
+    }
+
+    /// Get Intel i915 driver version
+    fn get_driver_version() -> Option<String> {
+        fs::read_to_string("/sys/module/i915/version")
+            .ok()
+            .map(|v| v.trim().to_string())
+    }
+
+    /// Read the system's total RAM from `/proc/meminfo`'s `MemTotal` line, converted to bytes.
+    /// An integrated GPU like this one doesn't own dedicated VRAM, so this stands in for
+    /// `GpuStatus::memory_total`: it's the real budget the GPU is actually sharing.
+    fn read_system_memory_total_bytes() -> u64 {
+        let Ok(meminfo) = fs::read_to_string("/proc/meminfo") else {
+            return 0;
+        };
+
+        meminfo
+            .lines()
+            .find_map(|line| line.strip_prefix("MemTotal:"))
+            .and_then(|rest| rest.trim().strip_suffix(" kB"))
+            .and_then(|kb| kb.trim().parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+            .unwrap_or(0)
+    }
+
+    /// Parse PCI information from the device's uevent file
+    fn parse_pci_info(device: &IntelDevice) -> Result<PciInfo> {
+        let mut vendor_id = 0x8086; // Intel default
+        let mut device_id = 0x0000;
+        let mut bus = 0;
+        let mut device_num = 0;
+
+        if let Ok(uevent_content) = fs::read_to_string(device.device_path.join("uevent")) {
+            for line in uevent_content.lines() {
+                if let Some(pci_id) = line.strip_prefix("PCI_ID=") {
+                    let parts: Vec<&str> = pci_id.split(':').collect();
+                    if parts.len() == 2 {
+                        vendor_id = u16::from_str_radix(parts[0], 16).unwrap_or(vendor_id);
+                        device_id = u16::from_str_radix(parts[1], 16).unwrap_or(device_id);
+                    }
+                } else if let Some(slot_name) = line.strip_prefix("PCI_SLOT_NAME=") {
+                    let parts: Vec<&str> = slot_name.split(&[':', '.']).collect();
+                    if parts.len() >= 3 {
+                        bus = u8::from_str_radix(parts[1], 16).unwrap_or(bus);
+                        device_num = u8::from_str_radix(parts[2], 16).unwrap_or(device_num);
+                    }
+                }
+            }
+        }
+
+        Ok(PciInfo {
+            domain: 0,
+            bus,
+            device: device_num,
+            function: 0,
+            vendor_id,
+            device_id,
+        })
+    }
+
+    /// Read GPU status from sysfs
+    async fn get_device_status(device: &IntelDevice) -> Result<GpuStatus> {
+        let clock_graphics = Self::read_gt_freq(device, "gt_cur_freq_mhz", 300);
+        let utilization_gpu = Self::read_gpu_utilization(device);
+        let power_draw = Self::read_power_draw(device).await;
+        let pci_info = Self::parse_pci_info(device)?;
+
+        let power_state = if utilization_gpu > 10 {
+            PowerState::Active
+        } else {
+            PowerState::Idle
+        };
+
+        Ok(GpuStatus {
+            index: device.index,
+            name: Self::get_device_name(device),
+            vendor: GpuVendor::Intel,
+            gpu_type: GpuType::Integrated,
+            // i915 doesn't expose a dedicated GPU die temperature; it's folded into the SoC
+            // package sensor instead, which this backend doesn't have a device handle for.
+            temperature: 0,
+            power_draw,
+            power_limit: Self::read_power_limit(device),
+            // Integrated GPUs share system RAM rather than owning dedicated VRAM; how much of
+            // that shared pool this GPU itself is using isn't exposed by i915 sysfs.
+            memory_used: 0,
+            memory_total: Self::read_system_memory_total_bytes(),
+            utilization_gpu,
+            utilization_memory: utilization_gpu,
+            fan_speed: None,
+            clock_graphics,
+            // i915 integrated GPUs share one clock plane with system memory; there's no separate
+            // memory clock domain to read.
+            clock_memory: None,
+            driver_version: Self::get_driver_version(),
+            pci_info,
+            power_state,
+            voltage_mv: None,
+            throttle_reasons: Vec::new(),
+            ecc_errors: None,
+            processes: Vec::new(),
+            driver_bound: crate::vfio::DriverBinding::Unbound, // overwritten centrally by GpuManager
+            unified_memory: true,
+            mig_parent: None, // MIG is an NVIDIA datacenter-card feature
+            mig_uuid: None,
+            uuid: None,
+            serial: None,
+            board_part_number: None,
+            vbios_version: None,
+            cuda_driver_version: None,
+        })
+    }
+
+    /// Clamp a requested max-clock target into this GPU's native `[gt_min_freq_mhz,
+    /// gt_max_freq_mhz]` range before writing it back to `gt_max_freq_mhz`, so a bad config can't
+    /// request a frequency the hardware doesn't support
+    async fn apply_device_max_freq(&self, device: &IntelDevice, target_mhz: u32) -> Result<()> {
+        let floor = Self::read_gt_freq(device, "gt_min_freq_mhz", 300);
+        let ceiling = Self::read_gt_freq(device, "gt_max_freq_mhz", 1100);
+        let clamped = target_mhz.clamp(floor, ceiling);
+
+        if let Err(e) = fs::write(device.device_path.join("gt_max_freq_mhz"), clamped.to_string()) {
+            warn!("Failed to set Intel GPU max frequency: {}", e);
+            return Err(GpuError::SystemError(format!("Failed to write gt_max_freq_mhz: {}", e)));
+        }
+
+        info!(
+            "Capped Intel GPU {} max frequency to {}MHz (requested {}MHz)",
+            device.index, clamped, target_mhz
+        );
+        Ok(())
+    }
+
+    /// Map a power mode to a `gt_max_freq_mhz` target, clamped against the GPU's native range
+    async fn apply_power_mode(&self, device: &IntelDevice, mode: PowerMode, gpu_offset: Option<i32>) -> Result<()> {
+        let floor = Self::read_gt_freq(device, "gt_min_freq_mhz", 300);
+        let ceiling = Self::read_gt_freq(device, "gt_max_freq_mhz", 1100);
+
+        let target = match mode {
+            PowerMode::MaxPerformance | PowerMode::Balanced | PowerMode::Auto => ceiling,
+            PowerMode::PowerSaver => floor,
+            PowerMode::Custom => gpu_offset
+                .map(|offset| (ceiling as i32 + offset).max(floor as i32) as u32)
+                .unwrap_or(ceiling),
+        };
+
+        self.apply_device_max_freq(device, target).await
+    }
+
+    /// Pin the GPU's clock window by writing both `gt_min_freq_mhz` and `gt_max_freq_mhz`
+    /// directly; unlike [`Self::apply_device_max_freq`] this doesn't need to read the existing
+    /// bounds first since the caller is providing both ends of the window.
+    async fn apply_device_clock_limits(&self, device: &IntelDevice, limits: MinMax<u32>) -> Result<()> {
+        if let Err(e) = fs::write(device.device_path.join("gt_min_freq_mhz"), limits.min.to_string()) {
+            warn!("Failed to set Intel GPU min frequency: {}", e);
+            return Err(GpuError::SystemError(format!("Failed to write gt_min_freq_mhz: {}", e)));
+        }
+        if let Err(e) = fs::write(device.device_path.join("gt_max_freq_mhz"), limits.max.to_string()) {
+            warn!("Failed to set Intel GPU max frequency: {}", e);
+            return Err(GpuError::SystemError(format!("Failed to write gt_max_freq_mhz: {}", e)));
+        }
+
+        info!("Set Intel GPU {} clock window to {}-{}MHz", device.index, limits.min, limits.max);
+        Ok(())
+    }
+
+    /// Apply sustained and boost power limits. Maps naturally onto RAPL's long-term
+    /// (`constraint_0`) vs short-term (`constraint_1`) power constraints, which is the real
+    /// mechanism Intel platforms use for this distinction.
+    async fn apply_device_tdp(&self, device: &IntelDevice, sustained: u32, boost: u32) -> Result<()> {
+        self.set_device_power_limit(device, sustained).await?;
+
+        let Some(domain) = Self::find_rapl_gpu_domain() else {
+            warn!(
+                "No RAPL GPU power domain found; boost limit of {}W for Intel GPU {} was not applied",
+                boost, device.index
+            );
+            return Ok(());
+        };
+
+        let boost_path = domain.join("constraint_1_power_limit_uw");
+        if !boost_path.exists() {
+            warn!(
+                "RAPL domain has no short-term constraint; boost limit of {}W for Intel GPU {} was not applied",
+                boost, device.index
+            );
+            return Ok(());
+        }
+
+        if let Err(e) = fs::write(&boost_path, (boost * 1_000_000).to_string()) {
+            warn!("Failed to set Intel GPU boost power limit: {}", e);
+            return Err(GpuError::PowerError(format!("Failed to set boost power limit: {}", e)));
+        }
+
+        info!("Set Intel GPU {} sustained/boost power limits to {}W/{}W", device.index, sustained, boost);
+        Ok(())
+    }
+
+    /// Set power limit
+    async fn set_device_power_limit(&self, device: &IntelDevice, limit_watts: u32) -> Result<()> {
+        let Some(ref hwmon_path) = device.hwmon_path else {
+            return Err(GpuError::OperationNotSupported("Power limit (no hwmon path)".to_string()));
+        };
+
+        let limit_microwatts = limit_watts * 1_000_000;
+        if let Err(e) = fs::write(hwmon_path.join("power1_cap"), limit_microwatts.to_string()) {
+            warn!("Failed to set Intel GPU power limit: {}", e);
+            return Err(GpuError::PowerError(format!("Failed to set power limit: {}", e)));
+        }
+
+        info!("Set Intel GPU {} power limit to {}W", device.index, limit_watts);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl GpuBackend for IntelBackend {
+    async fn init(&mut self) -> Result<()> {
+        let devices = Self::find_intel_devices()?;
+
+        if devices.is_empty() {
+            return Err(GpuError::GpuNotFound(0));
+        }
+
+        let mut device_map = HashMap::new();
+        for device in devices {
+            device_map.insert(device.index, device);
+        }
+
+        let mut devices_lock = self.devices.write().await;
+        *devices_lock = device_map;
+
+        info!("Intel backend initialized with {} devices", devices_lock.len());
+        Ok(())
+    }
+
+    #[instrument]
+    async fn detect_gpus(&self) -> Result<Vec<GpuStatus>> {
+        let devices = self.devices.read().await;
+        let mut gpus = Vec::new();
+
+        for device in devices.values() {
+            match Self::get_device_status(device).await {
+                Ok(status) => gpus.push(status),
+                Err(e) => warn!("Failed to get status for Intel GPU {}: {}", device.index, e),
+            }
+        }
+
+        info!("Detected {} Intel GPU(s)", gpus.len());
+        Ok(gpus)
+    }
+
+    #[instrument]
+    async fn get_gpu_status(&self, index: u32) -> Result<GpuStatus> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        Self::get_device_status(device).await
+    }
+
+    #[instrument]
+    async fn apply_config(&self, index: u32, config: &GpuConfig) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.apply_power_mode(device, config.power_mode, config.gpu_clock_offset).await?;
+
+        if let Some(limit) = config.power_limit {
+            self.set_device_power_limit(device, limit).await?;
+        }
+
+        info!("Applied configuration to Intel GPU {}", index);
+        Ok(())
+    }
+
+    #[instrument]
+    async fn set_power_limit(&self, index: u32, limit_watts: u32) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.set_device_power_limit(device, limit_watts).await
+    }
+
+    #[instrument]
+    async fn set_fan_curve(&self, _index: u32, _curve: &FanCurve) -> Result<()> {
+        // Integrated GPUs share the chassis/SoC fan, which is controlled by platform firmware
+        // rather than anything i915 exposes.
+        Err(GpuError::OperationNotSupported("Fan control".to_string()))
+    }
+
+    #[instrument]
+    async fn reset_gpu(&self, index: u32) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.apply_power_mode(device, PowerMode::Balanced, None).await?;
+        info!("Reset Intel GPU {} to defaults", index);
+        Ok(())
+    }
+
+    fn supports_gpu_switching(&self) -> bool {
+        // Hybrid-graphics switching requires DRI_PRIME-style userspace configuration, same as
+        // the AMD backend.
+        true
+    }
+
+    #[instrument]
+    async fn switch_gpu(&self, _from_index: u32, _to_index: u32) -> Result<()> {
+        Err(GpuError::OperationNotSupported(
+            "Intel GPU switching requires DRI_PRIME configuration".to_string(),
+        ))
+    }
+
+    #[instrument]
+    async fn get_processes(&self, _index: u32) -> Result<Vec<GpuProcess>> {
+        // i915 fdinfo accounting would need its own per-PID scanner like the AMD backend's;
+        // left unimplemented for now rather than guessed at.
+        Err(GpuError::OperationNotSupported("Process listing".to_string()))
+    }
+
+    #[instrument]
+    async fn set_max_clock(&self, index: u32, mhz: u32) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.apply_device_max_freq(device, mhz).await
+    }
+
+    #[instrument]
+    async fn set_voltage_offset(&self, _index: u32, _mv: i32) -> Result<()> {
+        // i915 doesn't expose a voltage control knob comparable to amdgpu's pp_od_clk_voltage.
+        Err(GpuError::OperationNotSupported("Voltage offset".to_string()))
+    }
+
+    #[instrument]
+    async fn set_clock_limits(&self, index: u32, limits: MinMax<u32>) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.apply_device_clock_limits(device, limits).await
+    }
+
+    #[instrument]
+    async fn set_tdp(&self, index: u32, sustained: u32, boost: u32) -> Result<()> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.apply_device_tdp(device, sustained, boost).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_intel_backend_creation() {
+        let backend = IntelBackend::new().await;
+        assert!(backend.is_ok());
+    }
+
+    #[test]
+    fn test_power_mode_target_respects_native_range() {
+        // Custom mode with an offset below the floor must clamp up to the floor, not go negative.
+        let device = IntelDevice {
+            index: 0,
+            device_path: PathBuf::from("/nonexistent"),
+            hwmon_path: None,
+            drm_path: PathBuf::from("/nonexistent"),
+            pci_id: "8086:46a6".to_string(),
+        };
+        // read_gt_freq falls back to its defaults (floor=300, ceiling=1100) since the path
+        // doesn't exist, so an extreme negative offset should clamp to 300.
+        let floor = IntelBackend::read_gt_freq(&device, "gt_min_freq_mhz", 300);
+        let ceiling = IntelBackend::read_gt_freq(&device, "gt_max_freq_mhz", 1100);
+        let target = (ceiling as i32 - 5000).max(floor as i32) as u32;
+        assert_eq!(target, floor);
+    }
+}
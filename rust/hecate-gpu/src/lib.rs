@@ -1,12 +1,12 @@
 //! # HecateOS GPU Management Library
 //!
 //! This library provides comprehensive GPU management capabilities for HecateOS,
-//! including support for NVIDIA and AMD GPUs with dynamic switching, VRAM monitoring,
+//! including support for NVIDIA, AMD, and Intel GPUs with dynamic switching, VRAM monitoring,
 //! multi-GPU load balancing, and driver management.
 //!
 //! ## Features
 //!
-//! - **Multi-vendor support**: NVIDIA (via NVML) and AMD (via DRM) GPUs
+//! - **Multi-vendor support**: NVIDIA (via NVML), AMD (via DRM), and Intel (via i915 sysfs) GPUs
 //! - **Dynamic GPU switching**: Seamless switching between integrated and discrete GPUs
 //! - **VRAM monitoring**: Real-time memory usage tracking with alerts
 //! - **Multi-GPU load balancing**: Automatic workload distribution
@@ -52,10 +52,28 @@ pub mod error;
 pub mod nvidia;
 #[cfg(feature = "amd")]
 pub mod amd;
+#[cfg(feature = "intel")]
+pub mod intel;
+pub mod dbus;
 pub mod driver;
+pub mod energy;
+pub mod limits;
+pub mod load_balancer;
+pub mod metrics;
 pub mod monitor;
+pub mod pci_ids;
+pub mod polling;
+pub mod profiles;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+pub mod variants;
+pub mod vfio;
 
 pub use error::{GpuError, Result};
+pub use limits::HardwareLimits;
+pub use polling::StatusVersion;
+pub use variants::VariantInfo;
+pub use vfio::DriverBinding;
 
 // ============================================================================
 // CORE DATA STRUCTURES
@@ -76,11 +94,12 @@ pub struct GpuStatus {
     pub temperature: u32,
     /// Current power draw in Watts
     pub power_draw: u32,
-    /// Power limit in Watts
-    pub power_limit: u32,
-    /// Used VRAM in bytes
+    /// Power limit in Watts, if the backend can read one. Absent on platforms with no RAPL
+    /// domain or `power1_cap` file to read (e.g. Apple Silicon integrated GPUs).
+    pub power_limit: Option<u32>,
+    /// Used VRAM in bytes, or used system memory when `unified_memory` is set
     pub memory_used: u64,
-    /// Total VRAM in bytes
+    /// Total VRAM in bytes, or the shared system-memory budget when `unified_memory` is set
     pub memory_total: u64,
     /// GPU utilization percentage (0-100)
     pub utilization_gpu: u32,
@@ -90,14 +109,103 @@ pub struct GpuStatus {
     pub fan_speed: Option<u32>,
     /// Graphics clock frequency in MHz
     pub clock_graphics: u32,
-    /// Memory clock frequency in MHz
-    pub clock_memory: u32,
+    /// Memory clock frequency in MHz, if the backend exposes a discrete memory clock domain
+    /// (absent on unified-memory GPUs where graphics and memory share one clock plane).
+    pub clock_memory: Option<u32>,
     /// Driver version
     pub driver_version: Option<String>,
     /// PCI bus information
     pub pci_info: PciInfo,
     /// Current power state
     pub power_state: PowerState,
+    /// Effective core voltage in millivolts, if the backend can read it
+    pub voltage_mv: Option<u32>,
+    /// Active throttle reasons reported by the driver, if any
+    pub throttle_reasons: Vec<ThrottleReason>,
+    /// ECC memory error counts, on cards that support it
+    pub ecc_errors: Option<EccCounts>,
+    /// Processes currently using this GPU
+    pub processes: Vec<GpuProcess>,
+    /// What driver currently owns this GPU's PCI device (vendor driver, vfio-pci, or none)
+    pub driver_bound: vfio::DriverBinding,
+    /// Whether this GPU shares system RAM rather than owning dedicated VRAM (integrated GPUs:
+    /// Intel iGPUs, AMD APUs, Apple Silicon). `memory_total`/`memory_used` describe the shared
+    /// budget rather than a discrete card's dedicated pool when this is set.
+    pub unified_memory: bool,
+    /// Index of the physical GPU this is a MIG (Multi-Instance GPU) slice of, if any. `None` for
+    /// a top-level device.
+    pub mig_parent: Option<u32>,
+    /// Stable UUID identifying this MIG slice, if any. Unlike `index` (only unique per parent),
+    /// this is the identifier to use when a slice needs to be told apart from every other slice
+    /// on the system.
+    pub mig_uuid: Option<String>,
+    /// Stable device UUID, durable across reboots and index reassignment unlike `index`. The
+    /// identity cluster telemetry should key on. `None` if the backend/driver doesn't expose it.
+    pub uuid: Option<String>,
+    /// Manufacturer serial number, if the backend/driver exposes it
+    pub serial: Option<String>,
+    /// Board part number, if the backend/driver exposes it
+    pub board_part_number: Option<String>,
+    /// VBIOS version string, if the backend/driver exposes it
+    pub vbios_version: Option<String>,
+    /// CUDA driver API version, encoded the way NVML reports it (`major * 1000 + minor * 10`,
+    /// e.g. `12020` for CUDA 12.2). System-wide rather than per-device.
+    pub cuda_driver_version: Option<i32>,
+}
+
+/// Reasons the driver may be holding a GPU below its requested clocks
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ThrottleReason {
+    /// Software thermal slowdown (GPU is too hot)
+    SwThermalSlowdown,
+    /// Hardware power brake slowdown (external power brake asserted)
+    HwPowerBrakeSlowdown,
+    /// Clocks are capped by the configured power limit
+    ClocksPowerCap,
+    /// Hardware thermal slowdown
+    HwThermalSlowdown,
+    /// Hardware slowdown for any other reason
+    HwSlowdown,
+    /// Application-requested clocks (user set a lower clock explicitly)
+    AppClocksSetting,
+    /// Sync boost with other GPUs in the system
+    SyncBoost,
+}
+
+/// ECC memory error counts for a GPU
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct EccCounts {
+    /// Single-bit errors since driver load (volatile)
+    pub volatile_single_bit: u64,
+    /// Double-bit errors since driver load (volatile)
+    pub volatile_double_bit: u64,
+    /// Single-bit errors since the GPU was manufactured (aggregate)
+    pub aggregate_single_bit: u64,
+    /// Double-bit errors since the GPU was manufactured (aggregate)
+    pub aggregate_double_bit: u64,
+}
+
+/// A process currently using a GPU
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GpuProcess {
+    pub pid: u32,
+    pub name: String,
+    /// Whether the process is using the GPU's compute or graphics engine
+    pub proc_type: GpuProcessType,
+    /// GPU memory used by this process in bytes, if reported by the driver
+    pub used_memory: Option<u64>,
+    /// Streaming-multiprocessor utilization percentage attributable to this process, if sampled
+    pub sm_utilization: Option<u32>,
+    /// Video encode/decode engine utilization percentage attributable to this process, if sampled
+    pub enc_dec_utilization: Option<u32>,
+}
+
+/// Which GPU engine a [`GpuProcess`] is using
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GpuProcessType {
+    Compute,
+    Graphics,
+    Unknown,
 }
 
 /// GPU vendor enumeration
@@ -143,6 +251,22 @@ pub enum PowerState {
     Switching,
 }
 
+/// An explicit inclusive `[min, max]` window requested by the caller, e.g. for
+/// [`GpuConfig::clock_limits`]. Distinct from [`limits::Range`], which describes what the
+/// hardware reports as safe rather than what was asked for.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MinMax<T> {
+    pub min: T,
+    pub max: T,
+}
+
+impl<T: PartialOrd> MinMax<T> {
+    /// `false` if `min > max`, which is never a sensible request regardless of hardware limits
+    pub fn is_valid(&self) -> bool {
+        self.min <= self.max
+    }
+}
+
 /// GPU optimization configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GpuConfig {
@@ -160,6 +284,29 @@ pub struct GpuConfig {
     pub gpu_clock_offset: Option<i32>,
     /// Enable/disable automatic load balancing
     pub auto_load_balance: bool,
+    /// Simple scalar undervolt/overvolt offset in millivolts
+    pub voltage_offset_mv: Option<i32>,
+    /// Full clock-to-voltage curve, for finer-grained undervolting than a flat offset
+    pub voltage_curve: Option<VoltageCurve>,
+    /// Absolute target core voltage in millivolts, as an alternative to a relative offset
+    pub core_voltage_mv: Option<u32>,
+    /// Explicit opt-in required before any voltage change is applied; undervolting can crash
+    /// a GPU, so this defaults to `false` on every preset
+    pub allow_undervolt: bool,
+    /// Adaptive power-to-clock scaling table, consulted each monitor tick to cap the GPU's
+    /// max clock based on observed power draw
+    pub freq_scaling: Option<FreqScalingTable>,
+    /// Bypass `freq_scaling` and request its turbo clock directly ("game mode")
+    pub boost_mode: bool,
+    /// Explicit min/max clock window in MHz, as an alternative to a single relative offset
+    pub clock_limits: Option<MinMax<u32>>,
+    /// Explicit target memory clock in MHz, pinned via NVML's locked-clocks API rather than
+    /// `memory_clock_offset`'s relative offset
+    pub memory_clock: Option<u32>,
+    /// Sustained (long-term) power limit in Watts
+    pub tdp: Option<u32>,
+    /// Short-term boost power limit in Watts, allowed to exceed `tdp` for brief bursts
+    pub tdp_boost: Option<u32>,
 }
 
 impl GpuConfig {
@@ -173,6 +320,16 @@ impl GpuConfig {
             memory_clock_offset: None,
             gpu_clock_offset: None,
             auto_load_balance: true,
+            voltage_offset_mv: None,
+            voltage_curve: None,
+            core_voltage_mv: None,
+            allow_undervolt: false,
+            freq_scaling: None,
+            boost_mode: false,
+            clock_limits: None,
+            memory_clock: None,
+            tdp: None,
+            tdp_boost: None,
         }
     }
 
@@ -186,10 +343,21 @@ impl GpuConfig {
             memory_clock_offset: Some(500),
             gpu_clock_offset: Some(100),
             auto_load_balance: true,
+            voltage_offset_mv: None,
+            voltage_curve: None,
+            core_voltage_mv: None,
+            allow_undervolt: false,
+            freq_scaling: None,
+            boost_mode: false,
+            clock_limits: None,
+            memory_clock: None,
+            tdp: None,
+            tdp_boost: Some(450),
         }
     }
 
-    /// Create a power-saving configuration
+    /// Create a power-saving configuration. Folds in a modest undervolt by default since a lower
+    /// core voltage is the single most effective lever for reducing power draw at a given clock.
     pub fn power_saver() -> Self {
         Self {
             power_mode: PowerMode::PowerSaver,
@@ -199,6 +367,28 @@ impl GpuConfig {
             memory_clock_offset: Some(-200),
             gpu_clock_offset: Some(-100),
             auto_load_balance: false,
+            voltage_offset_mv: Some(-50),
+            voltage_curve: None,
+            core_voltage_mv: None,
+            allow_undervolt: true,
+            freq_scaling: None,
+            boost_mode: false,
+            clock_limits: Some(MinMax { min: 300, max: 1200 }),
+            memory_clock: None,
+            tdp: None,
+            tdp_boost: None,
+        }
+    }
+
+    /// Create a configuration that targets an absolute core voltage, leaving clocks and power
+    /// limits at their defaults. Unlike [`Self::power_saver`]'s flat offset, this is meant for
+    /// callers that already know the safe voltage floor for their specific card (e.g. from prior
+    /// stability testing) and want to apply it directly.
+    pub fn undervolt(target_mv: u32) -> Self {
+        Self {
+            core_voltage_mv: Some(target_mv),
+            allow_undervolt: true,
+            ..Self::balanced()
         }
     }
 }
@@ -223,6 +413,15 @@ pub enum PowerMode {
 pub struct FanCurve {
     /// Control points as (temperature_celsius, fan_speed_percentage)
     pub points: Vec<(u32, u32)>,
+    /// Degrees the temperature must rise above the last setpoint before fan speed is increased
+    pub up_threshold_c: u32,
+    /// Degrees the temperature must fall below the last setpoint before fan speed is decreased;
+    /// larger than `up_threshold_c` so spin-down is lazier than spin-up, which avoids fan hunting
+    /// near a curve breakpoint
+    pub down_threshold_c: u32,
+    /// Maximum percentage-point change in commanded fan speed applied per update, so speed
+    /// ramps smoothly instead of jumping straight to a new target
+    pub max_step_per_tick: u32,
 }
 
 impl FanCurve {
@@ -235,6 +434,9 @@ impl FanCurve {
                 (70, 60),  // 70°C -> 60%
                 (85, 100), // 85°C -> 100%
             ],
+            up_threshold_c: 2,
+            down_threshold_c: 8,
+            max_step_per_tick: 10,
         }
     }
 
@@ -247,6 +449,9 @@ impl FanCurve {
                 (80, 70),  // 80°C -> 70%
                 (90, 100), // 90°C -> 100%
             ],
+            up_threshold_c: 2,
+            down_threshold_c: 8,
+            max_step_per_tick: 10,
         }
     }
 
@@ -278,6 +483,214 @@ impl FanCurve {
     }
 }
 
+/// Stateful wrapper around a [`FanCurve`] that avoids fan hunting on a temperature hovering
+/// at a breakpoint, modeled on amdgpud's temp-config handling: fan speed won't drop until the
+/// temperature has fallen `hysteresis_c` degrees below the last set-point, and each `update`
+/// moves the commanded speed toward the target by at most `max_step_per_tick` percentage points.
+#[derive(Debug, Clone)]
+pub struct FanController {
+    curve: FanCurve,
+    /// Degrees the temperature must drop below the last set-point before speed is reduced
+    hysteresis_c: u32,
+    /// Maximum percentage-point change in commanded speed per `update` call
+    max_step_per_tick: u32,
+    /// Temperature reading that justified the currently commanded speed; a decrease is only
+    /// honored once the temperature has fallen `hysteresis_c` degrees below this reference
+    set_point_temp: Option<u32>,
+    last_speed: Option<u32>,
+}
+
+impl FanController {
+    /// Wrap `curve` with the given hysteresis and slew-rate limits
+    pub fn new(curve: FanCurve, hysteresis_c: u32, max_step_per_tick: u32) -> Self {
+        Self {
+            curve,
+            hysteresis_c,
+            max_step_per_tick,
+            set_point_temp: None,
+            last_speed: None,
+        }
+    }
+
+    /// Feed a new temperature reading and get back the commanded fan speed for this tick
+    pub fn update(&mut self, temperature: u32) -> u32 {
+        let raw_target = self.curve.calculate_fan_speed(temperature);
+
+        // Hysteresis: only allow the target to drop once the temperature has fallen far enough
+        // below the reading that justified the currently commanded speed.
+        let target = match (self.set_point_temp, self.last_speed) {
+            (Some(set_point_temp), Some(last_speed)) if raw_target < last_speed => {
+                if set_point_temp.saturating_sub(temperature) >= self.hysteresis_c {
+                    raw_target
+                } else {
+                    last_speed
+                }
+            }
+            _ => raw_target,
+        };
+
+        if self.last_speed != Some(target) {
+            self.set_point_temp = Some(temperature);
+        }
+
+        let commanded = match self.last_speed {
+            Some(last_speed) => {
+                let diff = target as i32 - last_speed as i32;
+                let step = diff.clamp(-(self.max_step_per_tick as i32), self.max_step_per_tick as i32);
+                (last_speed as i32 + step).clamp(0, 100) as u32
+            }
+            None => target,
+        };
+
+        self.last_speed = Some(commanded);
+        commanded
+    }
+
+    /// Most recently commanded fan speed, if `update` has been called at least once
+    pub fn last_speed(&self) -> Option<u32> {
+        self.last_speed
+    }
+}
+
+/// Guard margin (MHz) an applied max clock must clear above the GPU's current minimum clock
+/// before [`ReclockController::update`] will issue it, to avoid oscillation and driver rejection
+/// of back-to-back reclocks that land too close together
+pub const RECLOCK_GUARD_MHZ: u32 = 200;
+
+/// Adaptive power-to-clock scaling table: maps observed power draw onto the maximum clock a
+/// GPU should be allowed to boost to, so it self-limits before the power/thermal limiter has to
+/// throttle it outright. Modeled on resourced's power-aware GPU frequency governor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreqScalingTable {
+    /// Control points as (power_limit_watts, max_clock_mhz), sorted ascending by power
+    pub points: Vec<(u32, u32)>,
+    /// Clock requested directly when boost mode bypasses the table
+    pub turbo_clock_mhz: u32,
+}
+
+impl FreqScalingTable {
+    /// A table approximating a typical desktop discrete GPU's DVFS curve
+    pub fn default_table() -> Self {
+        Self {
+            points: vec![(100, 1200), (200, 1800), (300, 2400), (450, 2700)],
+            turbo_clock_mhz: 2900,
+        }
+    }
+
+    /// Max clock for a given power draw: the lowest-threshold row whose power bound is still
+    /// >= the reading. Power above the highest threshold clamps to the top row's clock; power
+    /// below the lowest threshold clamps to the bottom row's clock, since that row is also the
+    /// first one whose bound is >= the reading.
+    pub fn max_clock_for_power(&self, power_draw_watts: u32) -> Option<u32> {
+        self.points
+            .iter()
+            .find(|(threshold, _)| power_draw_watts <= *threshold)
+            .or_else(|| self.points.last())
+            .map(|(_, clock)| *clock)
+    }
+}
+
+/// Stateful wrapper around a [`FreqScalingTable`] that turns a power-draw reading into the next
+/// `GpuBackend::set_max_clock` call to issue, enforcing the guard margin against oscillation
+#[derive(Debug, Clone)]
+pub struct ReclockController {
+    table: FreqScalingTable,
+}
+
+impl ReclockController {
+    /// Wrap `table` for per-tick evaluation
+    pub fn new(table: FreqScalingTable) -> Self {
+        Self { table }
+    }
+
+    /// Decide the max clock to apply for this tick. `boost_mode` bypasses the table and requests
+    /// its turbo clock directly. `current_min_clock_mhz` is the GPU's current floor clock (e.g.
+    /// its idle or memory clock); the candidate is only returned once it clears that floor by
+    /// [`RECLOCK_GUARD_MHZ`], otherwise `None` is returned and the caller should leave the clock
+    /// alone this tick.
+    pub fn update(&self, power_draw_watts: u32, current_min_clock_mhz: u32, boost_mode: bool) -> Option<u32> {
+        let candidate = if boost_mode {
+            self.table.turbo_clock_mhz
+        } else {
+            self.table.max_clock_for_power(power_draw_watts)?
+        };
+
+        if candidate <= current_min_clock_mhz + RECLOCK_GUARD_MHZ {
+            return None;
+        }
+
+        Some(candidate)
+    }
+}
+
+/// Voltage curve configuration (clock frequency -> core voltage), modeled on amdgpud's
+/// `pp_od_clk_voltage` undervolt tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoltageCurve {
+    /// Control points as (clock_mhz, millivolt)
+    pub points: Vec<(u32, u32)>,
+}
+
+impl VoltageCurve {
+    /// Interpolate the target voltage for a given core clock
+    pub fn voltage_for_clock(&self, clock_mhz: u32) -> Option<u32> {
+        if self.points.is_empty() {
+            return None;
+        }
+
+        for window in self.points.windows(2) {
+            let (clock1, volt1) = window[0];
+            let (clock2, volt2) = window[1];
+
+            if clock_mhz >= clock1 && clock_mhz <= clock2 {
+                let clock_ratio = (clock_mhz - clock1) as f32 / (clock2 - clock1) as f32;
+                let volt_diff = volt2 as i32 - volt1 as i32;
+                return Some(volt1 + (volt_diff as f32 * clock_ratio) as u32);
+            }
+        }
+
+        if clock_mhz < self.points[0].0 {
+            Some(self.points[0].1)
+        } else {
+            Some(self.points.last().unwrap().1)
+        }
+    }
+}
+
+/// Configuration for [`monitor::GpuMonitor`]'s closed-loop thermal/power governor: the min/max
+/// clock and TDP caps it's allowed to step between, modeled on the min/max range used by
+/// PowerTools-style GPU control panels. The governor is opt-in (see
+/// [`monitor::GpuMonitor::set_governor_config`]); without one configured, alerts are reported but
+/// nothing acts on them, same as before.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernorConfig {
+    /// Clock cap window in MHz the governor steps within, starting pinned at `max`
+    pub clock_limits: MinMax<u32>,
+    /// TDP cap window in Watts the governor steps within, starting pinned at `max`
+    pub tdp_limits: MinMax<u32>,
+    /// MHz/Watts stepped per correction, down toward the relevant `min` on a critical alert and
+    /// back up toward `max` once metrics recover
+    pub step: u32,
+    /// Seconds metrics must stay below `AlertConfig::temperature_warning` before the governor
+    /// relaxes caps back up by one more `step`, so a temperature hovering near the warning line
+    /// doesn't cause the caps to oscillate
+    pub recovery_hysteresis_secs: u64,
+}
+
+impl GovernorConfig {
+    /// A conservative default: caps span the full clock/TDP range reported by
+    /// [`FreqScalingTable::default_table`] and a typical desktop card's TDP window, stepping
+    /// gently with a two-minute recovery hysteresis
+    pub fn default_for(clock_limits: MinMax<u32>, tdp_limits: MinMax<u32>) -> Self {
+        Self {
+            clock_limits,
+            tdp_limits,
+            step: 100,
+            recovery_hysteresis_secs: 120,
+        }
+    }
+}
+
 /// GPU monitoring events
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GpuEvent {
@@ -317,6 +730,34 @@ pub enum GpuEvent {
         expected_score: f32,
         actual_score: f32,
     },
+    /// A saved config variant was hot-swapped onto a GPU
+    ConfigVariantLoaded {
+        gpu_index: u32,
+        variant_id: u64,
+        variant_name: String,
+    },
+    /// A GPU's PCI driver binding changed (e.g. vendor driver <-> vfio-pci)
+    BindingChanged {
+        gpu_index: u32,
+        binding: DriverBinding,
+    },
+    /// A single process crossed the configured fraction of a GPU's total VRAM
+    ProcessMemoryAlert {
+        gpu_index: u32,
+        pid: u32,
+        memory_used: u64,
+    },
+    /// [`monitor::GpuMonitor`]'s closed-loop governor stepped a GPU's clock/TDP caps in response
+    /// to an alert, or relaxed them back after recovery; see [`GovernorConfig`]
+    GovernorAction {
+        gpu_index: u32,
+        /// New clock cap applied, in MHz
+        clock_limit_mhz: u32,
+        /// New TDP cap applied, in Watts
+        tdp_watts: u32,
+        /// What triggered this step, e.g. `"critical temperature"` or `"recovered"`
+        reason: String,
+    },
 }
 
 // ============================================================================
@@ -352,6 +793,99 @@ pub trait GpuBackend: Send + Sync {
 
     /// Switch between GPUs (if supported)
     async fn switch_gpu(&self, from_index: u32, to_index: u32) -> Result<()>;
+
+    /// List processes currently using a specific GPU
+    async fn get_processes(&self, index: u32) -> Result<Vec<GpuProcess>>;
+
+    /// Cap the GPU's boost clock at `mhz`, as decided by [`ReclockController::update`]
+    async fn set_max_clock(&self, index: u32, mhz: u32) -> Result<()>;
+
+    /// Apply a relative voltage offset in millivolts, independent of a full [`GpuConfig`] apply
+    async fn set_voltage_offset(&self, index: u32, mv: i32) -> Result<()>;
+
+    /// Pin the GPU's clock to an explicit `[min, max]` window in MHz
+    async fn set_clock_limits(&self, index: u32, limits: MinMax<u32>) -> Result<()>;
+
+    /// Set sustained and short-term-boost power limits in Watts
+    async fn set_tdp(&self, index: u32, sustained: u32, boost: u32) -> Result<()>;
+}
+
+/// The narrow write surface [`monitor::GpuMonitor`]'s governor needs to act on an alert, without
+/// requiring an implementer to stand up the rest of [`GpuBackend`]'s surface. Any [`GpuBackend`]
+/// already satisfies this for free via the blanket impl below.
+#[async_trait]
+pub trait GpuController: Send + Sync {
+    /// Cap the GPU's boost clock at `mhz`
+    async fn set_clock_limit(&self, index: u32, mhz: u32) -> Result<()>;
+
+    /// Set the GPU's sustained power limit in Watts
+    async fn set_power_limit(&self, index: u32, watts: u32) -> Result<()>;
+}
+
+#[async_trait]
+impl<T: GpuBackend + ?Sized> GpuController for T {
+    async fn set_clock_limit(&self, index: u32, mhz: u32) -> Result<()> {
+        self.set_max_clock(index, mhz).await
+    }
+
+    async fn set_power_limit(&self, index: u32, watts: u32) -> Result<()> {
+        GpuBackend::set_power_limit(self, index, watts).await
+    }
+}
+
+/// Probe every compiled-in vendor backend and merge the GPUs they each find into one
+/// `Vec<GpuStatus>`, without needing to stand up and hold a full [`GpuManager`]. [`GpuManager`]
+/// already does this same probe-and-merge internally (see [`GpuManager::new`] and
+/// [`GpuManager::detect_gpus`]) for callers that also need the read/write control surface; this
+/// is the lighter-weight equivalent for callers like `load_balancer` and `monitor` that only
+/// want a one-shot, read-only, vendor-agnostic snapshot.
+pub async fn detect_backends() -> Result<Vec<GpuStatus>> {
+    let manager = GpuManager::new().await?;
+    manager.detect_gpus().await
+}
+
+/// A lightweight, enumeration-only identity for a GPU, ahead of sampling its full [`GpuStatus`].
+/// Exists alongside [`GpuBackend::detect_gpus`] for [`MetricsSource`] implementations, which need
+/// to list what's present (often cheaply, from a device table) before paying the cost of a full
+/// status read per device.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GpuDescriptor {
+    /// GPU index in the system
+    pub index: u32,
+    /// GPU name (e.g., "NVIDIA RTX 4090")
+    pub name: String,
+    /// GPU vendor (NVIDIA, AMD, Intel)
+    pub vendor: GpuVendor,
+    /// GPU type (Integrated, Discrete, External)
+    pub gpu_type: GpuType,
+    /// PCI vendor ID, if this device enumerates over PCI (absent for e.g. Apple Silicon's
+    /// integrated GPU, which has no PCI device)
+    pub vendor_id: Option<u16>,
+    /// ASIC/chip family name as the vendor SDK reports it (e.g. AMD ADLX's `"Navi 31"`, Apple's
+    /// `"Apple M2 Max"`), if the source exposes one
+    pub asic_family: Option<String>,
+}
+
+impl GpuDescriptor {
+    /// Whether this is an external GPU enclosure (eGPU), as ADLX and similar vendor SDKs report
+    pub fn is_external(&self) -> bool {
+        self.gpu_type == GpuType::External
+    }
+}
+
+/// A vendor metrics source that can enumerate and sample GPUs without the full read/write control
+/// surface [`GpuBackend`] demands. Exists for vendor SDKs that are natively synchronous FFI (AMD
+/// ADLX, Apple IOKit/Metal) where wrapping every call in `async_trait` buys nothing but overhead,
+/// and where the monitoring path ([`monitor::GpuMonitor`]) only ever needs to enumerate and
+/// sample, never to write configuration.
+pub trait MetricsSource: Send + Sync {
+    /// List the GPUs this source can currently sample
+    fn enumerate(&self) -> Vec<GpuDescriptor>;
+
+    /// Sample the current status of `gpu_index`. Fields the source's platform can't read (e.g.
+    /// `power_limit`/`clock_memory` on Apple Silicon) should come back `None` rather than a
+    /// fabricated default.
+    fn sample(&self, gpu_index: u32) -> Result<GpuStatus>;
 }
 
 // ============================================================================
@@ -360,8 +894,9 @@ pub trait GpuBackend: Send + Sync {
 
 /// Main GPU management interface
 pub struct GpuManager {
-    /// Available GPU backends
-    backends: HashMap<GpuVendor, Box<dyn GpuBackend>>,
+    /// Available GPU backends, `Arc`-wrapped so the monitoring task can sample GPUs without
+    /// needing `GpuManager` itself to live behind an `Arc`
+    backends: Arc<HashMap<GpuVendor, Box<dyn GpuBackend>>>,
     /// Detected GPUs
     gpus: Arc<RwLock<Vec<GpuStatus>>>,
     /// Monitoring configuration
@@ -371,6 +906,20 @@ pub struct GpuManager {
     // Load balancer will be implemented later
     /// Driver manager
     driver_manager: Arc<RwLock<driver::DriverManager>>,
+    /// On-disk store for named, hot-swappable config variants
+    variant_store: variants::VariantStore,
+    /// Condition-matched profile defaults and their named variants
+    profile_manager: Arc<RwLock<profiles::ProfileManager>>,
+    /// Per-model safe operating ranges, used to clamp applied configs
+    hardware_limits: Arc<RwLock<limits::HardwareLimits>>,
+    /// Edge-triggered version tracker backing `poll_status`
+    version_tracker: Arc<polling::VersionTracker>,
+    /// Rolling per-GPU energy/cost accounting, sampled on each monitoring tick
+    energy: Arc<energy::EnergyTracker>,
+    /// Most recent Prometheus-format telemetry render, served by [`Self::telemetry_router`]
+    /// between polls and kept warm by [`Self::start_telemetry_export`]
+    #[cfg(feature = "telemetry")]
+    telemetry_cache: telemetry::PrometheusCache,
 }
 
 impl std::fmt::Debug for GpuManager {
@@ -392,6 +941,13 @@ pub struct MonitoringConfig {
     pub temp_threshold: u32,
     pub vram_threshold: u32,
     pub power_threshold: u32,
+    /// Fraction (0.0-1.0) of a GPU's total VRAM a single process must use to trigger
+    /// [`GpuEvent::ProcessMemoryAlert`]
+    pub process_memory_fraction: f32,
+    /// Where to export sampled metrics; `None` disables export entirely
+    pub metric_sink: Option<metrics::MetricSinkKind>,
+    /// How often to sample and push metrics to `metric_sink`
+    pub push_interval: Duration,
 }
 
 impl Default for MonitoringConfig {
@@ -402,6 +958,9 @@ impl Default for MonitoringConfig {
             temp_threshold: 85,
             vram_threshold: 90,
             power_threshold: 95,
+            process_memory_fraction: 0.5,
+            metric_sink: None,
+            push_interval: Duration::from_secs(15),
         }
     }
 }
@@ -432,15 +991,37 @@ impl GpuManager {
             }
         }
 
+        // Initialize Intel backend if available, so hybrid laptops report both the
+        // integrated and discrete GPU
+        #[cfg(feature = "intel")]
+        if let Ok(mut intel_backend) = intel::IntelBackend::new().await {
+            if intel_backend.init().await.is_ok() {
+                info!("Intel backend initialized successfully");
+                backends.insert(GpuVendor::Intel, Box::new(intel_backend));
+            }
+        }
+
         let (event_tx, _) = broadcast::channel(1000);
         let driver_manager = Arc::new(RwLock::new(driver::DriverManager::new()));
+        let variant_store = variants::VariantStore::new()?;
+        let profile_manager = Arc::new(RwLock::new(profiles::ProfileManager::load()?));
+        let hardware_limits = Arc::new(RwLock::new(limits::HardwareLimits::load()?));
+        let version_tracker = Arc::new(polling::VersionTracker::new(polling::ChangeThresholds::default()));
+        let energy = Arc::new(energy::EnergyTracker::new(energy::CostModel::default()));
 
         Ok(Self {
-            backends,
+            backends: Arc::new(backends),
             gpus: Arc::new(RwLock::new(Vec::new())),
             monitoring: Arc::new(RwLock::new(MonitoringConfig::default())),
             event_tx,
             driver_manager,
+            variant_store,
+            profile_manager,
+            hardware_limits,
+            version_tracker,
+            energy,
+            #[cfg(feature = "telemetry")]
+            telemetry_cache: Arc::new(RwLock::new(String::new())),
         })
     }
 
@@ -454,6 +1035,9 @@ impl GpuManager {
             match backend.detect_gpus().await {
                 Ok(mut gpus) => {
                     info!("Found {} GPU(s) from {:?}", gpus.len(), vendor);
+                    for gpu in &mut gpus {
+                        gpu.driver_bound = vfio::current_binding(&gpu.pci_info);
+                    }
                     all_gpus.append(&mut gpus);
                 }
                 Err(e) => {
@@ -465,6 +1049,9 @@ impl GpuManager {
         // Update internal GPU list
         let mut gpus_lock = self.gpus.write().await;
         *gpus_lock = all_gpus.clone();
+        drop(gpus_lock);
+
+        self.version_tracker.observe(&all_gpus).await;
 
         // Load balancer initialization will be implemented later
         if all_gpus.len() > 1 {
@@ -477,11 +1064,23 @@ impl GpuManager {
     /// Get current status of all GPUs
     #[instrument]
     pub async fn get_all_gpu_status(&self) -> Result<Vec<GpuStatus>> {
-        let gpus = self.gpus.read().await;
+        let statuses = Self::sample_all_gpus(&self.backends, &self.gpus).await;
+        self.version_tracker.observe(&statuses).await;
+        Ok(statuses)
+    }
+
+    /// Sample every tracked GPU's status through its backend. Takes `backends`/`gpus` by
+    /// reference rather than `&self` so the monitoring task spawned by [`Self::start_monitoring`]
+    /// can call it from cloned `Arc`s without needing `GpuManager` itself behind an `Arc`.
+    async fn sample_all_gpus(
+        backends: &HashMap<GpuVendor, Box<dyn GpuBackend>>,
+        gpus: &Arc<RwLock<Vec<GpuStatus>>>,
+    ) -> Vec<GpuStatus> {
+        let gpus = gpus.read().await;
         let mut statuses = Vec::new();
 
         for gpu in gpus.iter() {
-            if let Some(backend) = self.backends.get(&gpu.vendor) {
+            if let Some(backend) = backends.get(&gpu.vendor) {
                 match backend.get_gpu_status(gpu.index).await {
                     Ok(status) => statuses.push(status),
                     Err(e) => warn!("Failed to get status for GPU {}: {}", gpu.index, e),
@@ -489,7 +1088,23 @@ impl GpuManager {
             }
         }
 
-        Ok(statuses)
+        statuses
+    }
+
+    /// Long-poll for a GPU status change. Blocks until the tracked version advances past
+    /// `last_seen` (i.e. some GPU's temperature/utilization/power-state crossed a configured
+    /// threshold) or `timeout` elapses, whichever comes first, then returns the current version
+    /// alongside a fresh status snapshot.
+    #[instrument(skip(self))]
+    pub async fn poll_status(
+        &self,
+        last_seen: StatusVersion,
+        timeout: Duration,
+    ) -> Result<(StatusVersion, Vec<GpuStatus>)> {
+        let _ = tokio::time::timeout(timeout, self.version_tracker.wait_for_change(last_seen)).await;
+
+        let statuses = self.get_all_gpu_status().await?;
+        Ok((self.version_tracker.current().await, statuses))
     }
 
     /// Apply configuration to a specific GPU
@@ -501,6 +1116,8 @@ impl GpuManager {
             .find(|g| g.index == gpu_index)
             .ok_or_else(|| GpuError::GpuNotFound(gpu_index))?;
 
+        let config = self.clamp_to_hardware_limits(gpu, config).await?;
+
         if let Some(backend) = self.backends.get(&gpu.vendor) {
             backend.apply_config(gpu_index, &config).await?;
             info!("Applied configuration to GPU {}: {:?}", gpu_index, config.power_mode);
@@ -511,15 +1128,70 @@ impl GpuManager {
         Ok(())
     }
 
-    /// Start monitoring all GPUs
+    /// Start monitoring all GPUs. Always spawns a background task that samples every GPU's power
+    /// draw into [`energy::EnergyTracker`] on `interval`, and, if a
+    /// [`MetricSinkKind`](metrics::MetricSinkKind) is configured, a second task that pushes
+    /// rendered metrics to that sink on `push_interval`. Both tasks stop the next time they wake
+    /// up after [`Self::stop_monitoring`] is called.
     #[instrument]
     pub async fn start_monitoring(&self) -> Result<()> {
         let mut config = self.monitoring.write().await;
         config.enabled = true;
+        let sink_kind = config.metric_sink.clone();
+        let push_interval = config.push_interval;
+        let sample_interval = config.interval;
+        drop(config);
+
+        {
+            let backends = Arc::clone(&self.backends);
+            let gpus = Arc::clone(&self.gpus);
+            let monitoring = Arc::clone(&self.monitoring);
+            let energy = Arc::clone(&self.energy);
+
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(sample_interval);
+                loop {
+                    ticker.tick().await;
+                    if !monitoring.read().await.enabled {
+                        info!("GPU energy accounting task stopping");
+                        break;
+                    }
+
+                    let statuses = Self::sample_all_gpus(&backends, &gpus).await;
+                    energy.sample(&statuses).await;
+                }
+            });
+        }
 
-        // Monitoring is simplified for now - full implementation would need 
-        // to be redesigned to work properly with async trait objects
-        info!("GPU monitoring started (simplified implementation)");
+        if let Some(sink_kind) = sink_kind {
+            let sink = metrics::build_sink(&sink_kind);
+            let backends = Arc::clone(&self.backends);
+            let gpus = Arc::clone(&self.gpus);
+            let monitoring = Arc::clone(&self.monitoring);
+
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(push_interval);
+                loop {
+                    ticker.tick().await;
+                    if !monitoring.read().await.enabled {
+                        info!("GPU metrics export task stopping");
+                        break;
+                    }
+
+                    let statuses = Self::sample_all_gpus(&backends, &gpus).await;
+                    if statuses.is_empty() {
+                        continue;
+                    }
+
+                    let payload = sink.format(&statuses);
+                    if let Err(e) = sink.push(payload).await {
+                        warn!("Failed to push GPU metrics: {}", e);
+                    }
+                }
+            });
+        }
+
+        info!("GPU monitoring started");
         Ok(())
     }
 
@@ -530,6 +1202,50 @@ impl GpuManager {
         info!("GPU monitoring stopped");
     }
 
+    /// Start streaming telemetry export. Spawns a background task that re-samples every GPU on
+    /// `config.interval`, keeps the cache [`Self::telemetry_router`] scrapes warm with a
+    /// Prometheus-format render, and, when `config.push_url` is set, additionally POSTs a
+    /// line-protocol render there on the same cadence. Runs independently of
+    /// [`Self::start_monitoring`]; call both if a deployment wants energy accounting and
+    /// streaming telemetry at once.
+    #[cfg(feature = "telemetry")]
+    #[instrument]
+    pub async fn start_telemetry_export(&self, config: telemetry::TelemetryConfig) -> Result<()> {
+        let backends = Arc::clone(&self.backends);
+        let gpus = Arc::clone(&self.gpus);
+        let cache = Arc::clone(&self.telemetry_cache);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(config.interval);
+            loop {
+                ticker.tick().await;
+                let statuses = Self::sample_all_gpus(&backends, &gpus).await;
+                *cache.write().await = config.render_prometheus(&statuses);
+
+                if let Some(push_url) = &config.push_url {
+                    let payload = config.render_line_protocol(&statuses);
+                    if payload.is_empty() {
+                        continue;
+                    }
+                    if let Err(e) = reqwest::Client::new().post(push_url).body(payload).send().await {
+                        warn!("Failed to push GPU telemetry: {}", e);
+                    }
+                }
+            }
+        });
+
+        info!("GPU telemetry export started");
+        Ok(())
+    }
+
+    /// An `axum` router exposing a Prometheus-compatible `/metrics` scrape endpoint over the
+    /// cache [`Self::start_telemetry_export`] keeps warm. Mount it on an existing `axum` server
+    /// or serve it standalone with `axum::Server::bind(&addr).serve(router.into_make_service())`.
+    #[cfg(feature = "telemetry")]
+    pub fn telemetry_router(&self) -> axum::Router {
+        telemetry::pull_router(Arc::clone(&self.telemetry_cache))
+    }
+
     /// Get event receiver for GPU events
     pub fn subscribe_events(&self) -> broadcast::Receiver<GpuEvent> {
         self.event_tx.subscribe()
@@ -571,6 +1287,176 @@ impl GpuManager {
         Ok(())
     }
 
+    /// List processes currently using a GPU, emitting [`GpuEvent::ProcessMemoryAlert`] for any
+    /// process whose memory usage crosses the configured fraction of the GPU's total VRAM
+    #[instrument]
+    pub async fn get_gpu_processes(&self, gpu_index: u32) -> Result<Vec<GpuProcess>> {
+        let gpus = self.gpus.read().await;
+        let gpu = gpus
+            .iter()
+            .find(|g| g.index == gpu_index)
+            .ok_or_else(|| GpuError::GpuNotFound(gpu_index))?;
+
+        let backend = self
+            .backends
+            .get(&gpu.vendor)
+            .ok_or_else(|| GpuError::BackendNotAvailable(gpu.vendor))?;
+
+        let processes = backend.get_processes(gpu_index).await?;
+
+        let fraction = self.monitoring.read().await.process_memory_fraction;
+        let threshold_bytes = (gpu.memory_total as f64 * fraction as f64) as u64;
+        for process in &processes {
+            if let Some(used_memory) = process.used_memory {
+                if used_memory >= threshold_bytes {
+                    let _ = self.event_tx.send(GpuEvent::ProcessMemoryAlert {
+                        gpu_index,
+                        pid: process.pid,
+                        memory_used: used_memory,
+                    });
+                }
+            }
+        }
+
+        Ok(processes)
+    }
+
+    /// Apply a relative voltage offset to a GPU, independent of a full [`GpuConfig`] apply.
+    /// Validated against the hardware-limits database the same way [`Self::apply_config`]
+    /// validates `GpuConfig::voltage_offset_mv`, so a bad value is rejected before it reaches
+    /// the driver.
+    #[instrument]
+    pub async fn set_voltage_offset(&self, gpu_index: u32, mv: i32) -> Result<()> {
+        let gpus = self.gpus.read().await;
+        let gpu = gpus
+            .iter()
+            .find(|g| g.index == gpu_index)
+            .ok_or_else(|| GpuError::GpuNotFound(gpu_index))?;
+
+        let limits = self.hardware_limits.read().await;
+        let model_limits = limits.for_device(gpu.pci_info.vendor_id, gpu.pci_info.device_id);
+        if !model_limits.voltage_offset_mv.contains(mv) {
+            return Err(GpuError::VoltageOutOfRange {
+                requested: mv,
+                min: model_limits.voltage_offset_mv.min,
+                max: model_limits.voltage_offset_mv.max,
+            });
+        }
+        drop(limits);
+
+        let backend = self
+            .backends
+            .get(&gpu.vendor)
+            .ok_or_else(|| GpuError::BackendNotAvailable(gpu.vendor))?;
+
+        backend.set_voltage_offset(gpu_index, mv).await?;
+        info!("Applied voltage offset of {}mV to GPU {}", mv, gpu_index);
+        Ok(())
+    }
+
+    /// Get current status of one tracked GPU, bypassing the cached `gpus` list update that
+    /// [`Self::detect_gpus`] performs. Used by callers (e.g. [`crate::dbus`]) that need a single
+    /// GPU's status without paying for a full [`Self::get_all_gpu_status`] sweep.
+    #[instrument]
+    pub async fn get_gpu_status(&self, gpu_index: u32) -> Result<GpuStatus> {
+        let gpus = self.gpus.read().await;
+        let gpu = gpus
+            .iter()
+            .find(|g| g.index == gpu_index)
+            .ok_or_else(|| GpuError::GpuNotFound(gpu_index))?;
+
+        let backend = self
+            .backends
+            .get(&gpu.vendor)
+            .ok_or_else(|| GpuError::BackendNotAvailable(gpu.vendor))?;
+
+        backend.get_gpu_status(gpu_index).await
+    }
+
+    /// Directly set a GPU's power limit, independent of a full [`GpuConfig`] apply. Clamped
+    /// against the hardware-limits database the same way [`Self::apply_config`] clamps
+    /// `GpuConfig::power_limit`.
+    #[instrument]
+    pub async fn set_power_limit(&self, gpu_index: u32, limit_watts: u32) -> Result<()> {
+        let gpus = self.gpus.read().await;
+        let gpu = gpus
+            .iter()
+            .find(|g| g.index == gpu_index)
+            .ok_or_else(|| GpuError::GpuNotFound(gpu_index))?;
+
+        let limits = self.hardware_limits.read().await;
+        let model_limits = limits.for_device(gpu.pci_info.vendor_id, gpu.pci_info.device_id);
+        if !model_limits.power_limit_watts.contains(limit_watts) {
+            return Err(GpuError::LimitExceeded {
+                requested: limit_watts as i64,
+                max: model_limits.power_limit_watts.max as i64,
+            });
+        }
+        drop(limits);
+
+        let backend = self
+            .backends
+            .get(&gpu.vendor)
+            .ok_or_else(|| GpuError::BackendNotAvailable(gpu.vendor))?;
+
+        backend.set_power_limit(gpu_index, limit_watts).await?;
+        info!("Set power limit of {}W on GPU {}", limit_watts, gpu_index);
+        Ok(())
+    }
+
+    /// Apply a fan curve to a GPU, independent of a full [`GpuConfig`] apply
+    #[instrument]
+    pub async fn set_fan_curve(&self, gpu_index: u32, curve: FanCurve) -> Result<()> {
+        let gpus = self.gpus.read().await;
+        let gpu = gpus
+            .iter()
+            .find(|g| g.index == gpu_index)
+            .ok_or_else(|| GpuError::GpuNotFound(gpu_index))?;
+
+        let backend = self
+            .backends
+            .get(&gpu.vendor)
+            .ok_or_else(|| GpuError::BackendNotAvailable(gpu.vendor))?;
+
+        backend.set_fan_curve(gpu_index, &curve).await?;
+        info!("Applied fan curve to GPU {}", gpu_index);
+        Ok(())
+    }
+
+    /// Reset a GPU to its default settings
+    #[instrument]
+    pub async fn reset_gpu(&self, gpu_index: u32) -> Result<()> {
+        let gpus = self.gpus.read().await;
+        let gpu = gpus
+            .iter()
+            .find(|g| g.index == gpu_index)
+            .ok_or_else(|| GpuError::GpuNotFound(gpu_index))?;
+
+        let backend = self
+            .backends
+            .get(&gpu.vendor)
+            .ok_or_else(|| GpuError::BackendNotAvailable(gpu.vendor))?;
+
+        backend.reset_gpu(gpu_index).await?;
+        info!("Reset GPU {} to defaults", gpu_index);
+        Ok(())
+    }
+
+    /// Report cumulative energy, cost, and perf-per-watt for every tracked GPU. `perf_per_watt`
+    /// is the ranking key the planned load balancer should use to steer work towards the most
+    /// efficient GPU; a GPU with no samples yet (monitoring hasn't started, or it was just
+    /// detected) is omitted.
+    #[instrument]
+    pub async fn energy_report(&self) -> Result<Vec<energy::EnergyReport>> {
+        let statuses = self.get_all_gpu_status().await?;
+        Ok(self.energy.report(&statuses).await)
+    }
+
+    /// Override the default per-kWh electricity price used by [`Self::energy_report`]
+    pub async fn set_cost_model(&self, model: energy::CostModel) {
+        self.energy.set_cost_model(model).await;
+    }
+
     /// Enable automatic load balancing (will be implemented later)
     pub async fn enable_load_balancing(&self) -> Result<()> {
         info!("Load balancing would be enabled here");
@@ -583,11 +1469,229 @@ impl GpuManager {
         Ok(())
     }
 
-    /// Update GPU drivers
+    /// Clamp a requested config to the installed GPU's real safe ranges, erroring if the
+    /// requested power limit is outside what the hardware-limits database allows for this model
+    async fn clamp_to_hardware_limits(&self, gpu: &GpuStatus, mut config: GpuConfig) -> Result<GpuConfig> {
+        let limits = self.hardware_limits.read().await;
+        let model_limits = limits.for_device(gpu.pci_info.vendor_id, gpu.pci_info.device_id);
+
+        if let Some(power_limit) = config.power_limit {
+            if !model_limits.power_limit_watts.contains(power_limit) {
+                return Err(GpuError::LimitExceeded {
+                    requested: power_limit as i64,
+                    max: model_limits.power_limit_watts.max as i64,
+                });
+            }
+        }
+
+        if let Some(temp_target) = config.temp_target {
+            config.temp_target = Some(model_limits.temp_target_celsius.clamp_value(temp_target));
+        }
+        if let Some(gpu_offset) = config.gpu_clock_offset {
+            config.gpu_clock_offset = Some(model_limits.gpu_clock_offset_mhz.clamp_value(gpu_offset));
+        }
+        if let Some(mem_offset) = config.memory_clock_offset {
+            config.memory_clock_offset = Some(model_limits.memory_clock_offset_mhz.clamp_value(mem_offset));
+        }
+
+        if config.allow_undervolt {
+            if let Some(voltage_offset) = config.voltage_offset_mv {
+                if !model_limits.voltage_offset_mv.contains(voltage_offset) {
+                    return Err(GpuError::VoltageOutOfRange {
+                        requested: voltage_offset,
+                        min: model_limits.voltage_offset_mv.min,
+                        max: model_limits.voltage_offset_mv.max,
+                    });
+                }
+            }
+        }
+
+        if let Some(clock_limits) = config.clock_limits {
+            if !clock_limits.is_valid() {
+                return Err(GpuError::OutOfRange {
+                    requested: clock_limits.min as i64,
+                    min: clock_limits.min as i64,
+                    max: clock_limits.max as i64,
+                });
+            }
+            if !model_limits.clock_mhz.contains(clock_limits.min)
+                || !model_limits.clock_mhz.contains(clock_limits.max)
+            {
+                return Err(GpuError::OutOfRange {
+                    requested: clock_limits.max as i64,
+                    min: model_limits.clock_mhz.min as i64,
+                    max: model_limits.clock_mhz.max as i64,
+                });
+            }
+        }
+
+        if let Some(tdp) = config.tdp {
+            if !model_limits.power_limit_watts.contains(tdp) {
+                return Err(GpuError::OutOfRange {
+                    requested: tdp as i64,
+                    min: model_limits.power_limit_watts.min as i64,
+                    max: model_limits.power_limit_watts.max as i64,
+                });
+            }
+        }
+
+        if let Some(tdp_boost) = config.tdp_boost {
+            if !model_limits.power_limit_watts.contains(tdp_boost) {
+                return Err(GpuError::OutOfRange {
+                    requested: tdp_boost as i64,
+                    min: model_limits.power_limit_watts.min as i64,
+                    max: model_limits.power_limit_watts.max as i64,
+                });
+            }
+            if let Some(tdp) = config.tdp {
+                if tdp_boost < tdp {
+                    return Err(GpuError::OutOfRange {
+                        requested: tdp_boost as i64,
+                        min: tdp as i64,
+                        max: model_limits.power_limit_watts.max as i64,
+                    });
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Refresh the hardware-limits database from the online source, falling back to the
+    /// existing cache/built-ins on failure
+    #[instrument(skip(self))]
+    pub async fn update_hardware_limits(&self) -> Result<()> {
+        let mut limits = self.hardware_limits.write().await;
+        limits.update_online().await
+    }
+
+    /// Save the currently-applied configuration for `gpu_index` as a named, reusable variant
+    #[instrument(skip(self, config))]
+    pub async fn save_variant(&self, gpu_index: u32, name: &str, config: GpuConfig) -> Result<VariantInfo> {
+        self.variant_store.save(name, gpu_index, config)
+    }
+
+    /// List all saved config variants, newest first
+    pub fn list_variants(&self) -> Result<Vec<VariantInfo>> {
+        self.variant_store.list()
+    }
+
+    /// Load a saved variant and apply it atomically to its GPU, emitting a
+    /// [`GpuEvent::ConfigVariantLoaded`] so subscribers of [`Self::subscribe_events`] see the change
+    #[instrument(skip(self))]
+    pub async fn load_variant(&self, id: u64) -> Result<VariantInfo> {
+        let variant = self.variant_store.load(id)?;
+        self.apply_config(variant.gpu_index, variant.config.clone()).await?;
+
+        let _ = self.event_tx.send(GpuEvent::ConfigVariantLoaded {
+            gpu_index: variant.gpu_index,
+            variant_id: variant.id_num,
+            variant_name: variant.name.clone(),
+        });
+
+        info!("Hot-swapped GPU {} to variant '{}' (id {})", variant.gpu_index, variant.name, id);
+        Ok(variant)
+    }
+
+    /// Delete a saved config variant
+    pub fn delete_variant(&self, id: u64) -> Result<()> {
+        self.variant_store.delete(id)
+    }
+
+    /// Evaluate loaded [`profiles::Profile`]s against every detected GPU and apply the first
+    /// matching profile's default limits to each, e.g. once right after [`Self::detect_gpus`]
+    #[instrument(skip(self))]
+    pub async fn apply_matching_profiles(&self) -> Result<()> {
+        let gpus = self.gpus.read().await.clone();
+
+        let mut to_apply = Vec::new();
+        {
+            let mut manager = self.profile_manager.write().await;
+            for gpu in &gpus {
+                if let Some(profile) = manager.select_for(gpu) {
+                    to_apply.push((gpu.index, profile.name.clone(), profile.limits.clone()));
+                }
+            }
+        }
+
+        for (gpu_index, profile_name, limits) in to_apply {
+            self.apply_config(gpu_index, limits).await?;
+            info!("Applied profile '{}' defaults to GPU {}", profile_name, gpu_index);
+        }
+
+        Ok(())
+    }
+
+    /// List the variants of the profile currently matched to `gpu_index`, empty if none matched
+    pub async fn list_profile_variants(&self, gpu_index: u32) -> Vec<profiles::ProfileVariant> {
+        self.profile_manager.read().await.list_variants(gpu_index).to_vec()
+    }
+
+    /// Apply a named variant of the profile matched to `gpu_index`
+    #[instrument(skip(self))]
+    pub async fn apply_profile_variant(&self, gpu_index: u32, variant_id: &str) -> Result<()> {
+        let config = self.profile_manager.read().await.apply_variant(gpu_index, variant_id)?;
+        self.apply_config(gpu_index, config).await
+    }
+
+    /// Rebind a GPU from its current driver onto `vfio-pci`, preparing it for VM passthrough
+    #[instrument]
+    pub async fn bind_vfio(&self, gpu_index: u32) -> Result<()> {
+        let gpus = self.gpus.read().await;
+        let gpu = gpus
+            .iter()
+            .find(|g| g.index == gpu_index)
+            .ok_or_else(|| GpuError::GpuNotFound(gpu_index))?;
+
+        vfio::bind_vfio(&gpu.pci_info, gpu.pci_info.vendor_id, gpu.pci_info.device_id)?;
+
+        let _ = self.event_tx.send(GpuEvent::BindingChanged {
+            gpu_index,
+            binding: DriverBinding::VfioPci,
+        });
+        info!("GPU {} bound to vfio-pci", gpu_index);
+        Ok(())
+    }
+
+    /// Rebind a GPU from `vfio-pci` back onto its vendor driver
+    #[instrument]
+    pub async fn unbind_vfio(&self, gpu_index: u32) -> Result<()> {
+        let gpus = self.gpus.read().await;
+        let gpu = gpus
+            .iter()
+            .find(|g| g.index == gpu_index)
+            .ok_or_else(|| GpuError::GpuNotFound(gpu_index))?;
+
+        let vendor_driver = match gpu.vendor {
+            GpuVendor::NVIDIA => "nvidia",
+            GpuVendor::AMD => "amdgpu",
+            GpuVendor::Intel => "i915",
+            GpuVendor::Unknown => {
+                return Err(GpuError::OperationNotSupported(
+                    "unknown vendor; no driver to rebind to".to_string(),
+                ))
+            }
+        };
+
+        vfio::unbind_vfio(&gpu.pci_info, vendor_driver)?;
+
+        let binding = DriverBinding::VendorDriver(vendor_driver.to_string());
+        let _ = self.event_tx.send(GpuEvent::BindingChanged {
+            gpu_index,
+            binding: binding.clone(),
+        });
+        info!("GPU {} rebound to {}", gpu_index, vendor_driver);
+        Ok(())
+    }
+
+    /// Update GPU drivers, comparing NVIDIA against `nvidia_branch` (AMD updates come through
+    /// kernel/mesa and aren't branch-selectable). `unattended` pre-seeds NVIDIA's debconf license
+    /// prompt so the install doesn't block in automation/cloud contexts; interactive desktop
+    /// callers should pass `false` to keep the prompts.
     #[instrument]
-    pub async fn update_drivers(&self) -> Result<Vec<String>> {
+    pub async fn update_drivers(&self, nvidia_branch: driver::DriverBranch, unattended: bool) -> Result<Vec<String>> {
         let manager = self.driver_manager.read().await;
-        let updates = manager.check_and_update_drivers().await?;
+        let updates = manager.check_and_update_drivers(nvidia_branch, unattended).await?;
         
         for update in &updates {
             info!("Driver updated: {}", update);
@@ -617,15 +1721,24 @@ pub fn format_bytes(bytes: u64) -> String {
 
 /// Create a summary string for GPU status
 pub fn gpu_summary(status: &GpuStatus) -> String {
-    let vram_percent = (status.memory_used * 100) / status.memory_total;
-    
+    // `memory_total` can legitimately be 0 for an integrated/unified-memory GPU whose backend
+    // couldn't read a system-memory figure, so guard the percentage rather than dividing by it.
+    let vram_percent = if status.memory_total > 0 {
+        (status.memory_used * 100) / status.memory_total
+    } else {
+        0
+    };
+    let memory_label = if status.unified_memory { "Shared Memory" } else { "VRAM" };
+    let power_limit_label = status.power_limit.map_or_else(|| "N/A".to_string(), |l| format!("{l}"));
+
     format!(
-        "{}: {}°C, {}W/{}W, GPU: {}%, VRAM: {}/{} ({}%)",
+        "{}: {}°C, {}W/{}W, GPU: {}%, {}: {}/{} ({}%)",
         status.name,
         status.temperature,
         status.power_draw,
-        status.power_limit,
+        power_limit_label,
         status.utilization_gpu,
+        memory_label,
         format_bytes(status.memory_used),
         format_bytes(status.memory_total),
         vram_percent
@@ -634,11 +1747,30 @@ pub fn gpu_summary(status: &GpuStatus) -> String {
 
 /// Calculate GPU efficiency score (0.0 - 1.0)
 pub fn calculate_efficiency_score(status: &GpuStatus) -> f32 {
-    let power_efficiency = 1.0 - (status.power_draw as f32 / status.power_limit as f32);
+    let power_efficiency = if let Some(power_limit) = status.power_limit.filter(|&l| l > 0) {
+        1.0 - (status.power_draw as f32 / power_limit as f32)
+    } else {
+        // No power limit to measure against (common on integrated/unified-memory GPUs with no
+        // RAPL domain or hwmon power1_cap): fall back to a utilization-per-watt ratio instead,
+        // normalized against 5%-utilization-per-watt as a generous "fully efficient" baseline.
+        let utilization_per_watt = status.utilization_gpu as f32 / status.power_draw.max(1) as f32;
+        (utilization_per_watt / 5.0).min(1.0)
+    };
     let thermal_efficiency = 1.0 - (status.temperature as f32 / 90.0).min(1.0);
     let utilization_score = status.utilization_gpu as f32 / 100.0;
 
-    (power_efficiency + thermal_efficiency + utilization_score) / 3.0
+    let base_score = (power_efficiency + thermal_efficiency + utilization_score) / 3.0;
+
+    // A card held below its requested clocks by thermal/power throttling is less efficient
+    // than a free-running one at the same utilization, even though the raw telemetry looks
+    // identical, so penalize active throttle reasons directly.
+    let throttle_penalty = if status.throttle_reasons.is_empty() {
+        0.0
+    } else {
+        0.1 * status.throttle_reasons.len().min(3) as f32
+    };
+
+    (base_score - throttle_penalty).clamp(0.0, 1.0)
 }
 
 #[cfg(test)]
@@ -655,6 +1787,62 @@ mod tests {
         assert_eq!(curve.calculate_fan_speed(60), 50); // Should interpolate
     }
 
+    #[test]
+    fn test_fan_controller_slew_limits_step_size() {
+        let mut controller = FanController::new(FanCurve::aggressive(), 5, 10);
+
+        assert_eq!(controller.update(30), 20); // First tick jumps straight to target
+        assert_eq!(controller.update(85), 30); // Target is 100, but capped to a 10-point step
+    }
+
+    #[test]
+    fn test_fan_controller_hysteresis_blocks_small_drop() {
+        let mut controller = FanController::new(FanCurve::aggressive(), 10, 100);
+
+        controller.update(70); // Sets speed to 60 (curve breakpoint)
+        let dropped = controller.update(65); // Only 5°C below, less than the 10°C hysteresis
+        assert_eq!(dropped, 60); // Speed should not have dropped yet
+
+        let dropped_further = controller.update(55); // Now 15°C below the last set-point
+        assert!(dropped_further < 60);
+    }
+
+    #[test]
+    fn test_voltage_curve_interpolation() {
+        let curve = VoltageCurve {
+            points: vec![(500, 700), (1000, 900), (1500, 1050)],
+        };
+
+        assert_eq!(curve.voltage_for_clock(500), Some(700));
+        assert_eq!(curve.voltage_for_clock(1500), Some(1050));
+        assert_eq!(curve.voltage_for_clock(750), Some(800)); // Should interpolate
+    }
+
+    #[test]
+    fn test_freq_scaling_table_clamps_above_and_below_range() {
+        let table = FreqScalingTable::default_table();
+
+        assert_eq!(table.max_clock_for_power(50), Some(1200)); // Below lowest threshold
+        assert_eq!(table.max_clock_for_power(250), Some(2400)); // Mid-table
+        assert_eq!(table.max_clock_for_power(1000), Some(2700)); // Above highest threshold
+    }
+
+    #[test]
+    fn test_reclock_controller_skips_change_inside_guard_margin() {
+        let controller = ReclockController::new(FreqScalingTable::default_table());
+
+        // Table says 1200MHz at this power draw, but the floor clock is only 1050MHz below it
+        assert_eq!(controller.update(50, 1050, false), None);
+        // A floor clock far enough below clears the guard
+        assert_eq!(controller.update(50, 500, false), Some(1200));
+    }
+
+    #[test]
+    fn test_reclock_controller_boost_mode_bypasses_table() {
+        let controller = ReclockController::new(FreqScalingTable::default_table());
+        assert_eq!(controller.update(50, 500, true), Some(2900));
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(1024), "1.00 KiB");
@@ -671,6 +1859,28 @@ mod tests {
         let max_perf = GpuConfig::max_performance();
         assert_eq!(max_perf.power_mode, PowerMode::MaxPerformance);
         assert_eq!(max_perf.temp_target, Some(90));
+
+        let power_saver = GpuConfig::power_saver();
+        assert!(power_saver.allow_undervolt);
+        assert_eq!(power_saver.voltage_offset_mv, Some(-50));
+        assert_eq!(power_saver.clock_limits, Some(MinMax { min: 300, max: 1200 }));
+        assert_eq!(max_perf.tdp_boost, Some(450));
+        assert_eq!(balanced.memory_clock, None);
+    }
+
+    #[test]
+    fn test_undervolt_preset_targets_core_voltage() {
+        let config = GpuConfig::undervolt(850);
+        assert!(config.allow_undervolt);
+        assert_eq!(config.core_voltage_mv, Some(850));
+        assert_eq!(config.power_mode, PowerMode::Balanced);
+    }
+
+    #[test]
+    fn test_min_max_is_valid() {
+        assert!(MinMax { min: 100, max: 200 }.is_valid());
+        assert!(MinMax { min: 100, max: 100 }.is_valid());
+        assert!(!MinMax { min: 200, max: 100 }.is_valid());
     }
 
     proptest! {
@@ -688,14 +1898,14 @@ mod tests {
                 gpu_type: GpuType::Discrete,
                 temperature,
                 power_draw,
-                power_limit,
+                power_limit: Some(power_limit),
                 memory_used: 1024 * 1024 * 1024,
                 memory_total: 8 * 1024 * 1024 * 1024,
                 utilization_gpu: utilization,
                 utilization_memory: 50,
                 fan_speed: Some(50),
                 clock_graphics: 1500,
-                clock_memory: 7000,
+                clock_memory: Some(7000),
                 driver_version: Some("470.86".to_string()),
                 pci_info: PciInfo {
                     domain: 0,
@@ -706,6 +1916,19 @@ mod tests {
                     device_id: 0x2204,
                 },
                 power_state: PowerState::Active,
+                voltage_mv: Some(1000),
+                throttle_reasons: Vec::new(),
+                ecc_errors: None,
+                processes: Vec::new(),
+                driver_bound: DriverBinding::Unbound,
+                unified_memory: false,
+                mig_parent: None,
+                mig_uuid: None,
+                uuid: None,
+                serial: None,
+                board_part_number: None,
+                vbios_version: None,
+                cuda_driver_version: None,
             };
 
             let score = calculate_efficiency_score(&status);
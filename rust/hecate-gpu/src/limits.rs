@@ -0,0 +1,342 @@
+//! Per-model hardware safety limits, keyed by PCI `(vendor_id, device_id)`
+//!
+//! Built-in presets in [`GpuConfig`](crate::GpuConfig) use fixed numbers regardless of the
+//! actual card installed. [`HardwareLimits`] supplies the real safe ranges for a given GPU
+//! model so [`GpuManager`](crate::GpuManager) can clamp requested settings instead of letting
+//! users push an unsafe target on hardware the preset wasn't written for.
+//!
+//! Three tiers are consulted in order: an online limits table (fetched and cached locally),
+//! a timestamped on-disk cache of the last successful fetch, and finally the built-in defaults
+//! compiled into this module.
+
+use crate::error::{GpuError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, instrument, warn};
+
+/// Default URL for the online hardware-limits table
+const DEFAULT_LIMITS_URL: &str = "https://hecate-os.example/api/v1/gpu-limits.json";
+
+/// Inclusive `[min, max]` range
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Range<T> {
+    pub min: T,
+    pub max: T,
+}
+
+impl<T: PartialOrd + Copy> Range<T> {
+    pub fn clamp_value(&self, value: T) -> T {
+        if value < self.min {
+            self.min
+        } else if value > self.max {
+            self.max
+        } else {
+            value
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        value >= self.min && value <= self.max
+    }
+}
+
+/// Safe operating ranges for a specific GPU model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModelLimits {
+    pub vendor_id: u16,
+    pub device_id: u16,
+    /// Human-readable model name, for diagnostics only
+    pub name: String,
+    pub power_limit_watts: Range<u32>,
+    pub temp_target_celsius: Range<u32>,
+    pub gpu_clock_offset_mhz: Range<i32>,
+    pub memory_clock_offset_mhz: Range<i32>,
+    pub voltage_offset_mv: Range<i32>,
+    /// Absolute graphics clock range, for validating `GpuConfig::clock_limits`
+    pub clock_mhz: Range<u32>,
+}
+
+/// A table of per-model limits, as fetched from the online database or loaded from cache/defaults
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LimitsTable {
+    pub models: Vec<ModelLimits>,
+    /// Unix timestamp (seconds) this table was fetched/generated
+    pub fetched_at: u64,
+}
+
+/// Generic fallback used when no entry exists for a `(vendor_id, device_id)` pair
+fn generic_fallback(vendor_id: u16, device_id: u16) -> ModelLimits {
+    ModelLimits {
+        vendor_id,
+        device_id,
+        name: "Unknown GPU (generic fallback)".to_string(),
+        power_limit_watts: Range { min: 50, max: 450 },
+        temp_target_celsius: Range { min: 50, max: 95 },
+        gpu_clock_offset_mhz: Range { min: -200, max: 200 },
+        memory_clock_offset_mhz: Range { min: -500, max: 1000 },
+        voltage_offset_mv: Range { min: -100, max: 50 },
+        clock_mhz: Range { min: 300, max: 3000 },
+    }
+}
+
+/// Built-in table covering a handful of well-known consumer cards
+fn builtin_table() -> LimitsTable {
+    LimitsTable {
+        fetched_at: 0,
+        models: vec![
+            ModelLimits {
+                vendor_id: 0x10DE,
+                device_id: 0x2204, // GeForce RTX 3090
+                name: "NVIDIA GeForce RTX 3090".to_string(),
+                power_limit_watts: Range { min: 100, max: 420 },
+                temp_target_celsius: Range { min: 50, max: 93 },
+                gpu_clock_offset_mhz: Range { min: -200, max: 200 },
+                memory_clock_offset_mhz: Range { min: -500, max: 1500 },
+                voltage_offset_mv: Range { min: -100, max: 50 },
+                clock_mhz: Range { min: 300, max: 2100 },
+            },
+            ModelLimits {
+                vendor_id: 0x10DE,
+                device_id: 0x2684, // GeForce RTX 4090
+                name: "NVIDIA GeForce RTX 4090".to_string(),
+                power_limit_watts: Range { min: 100, max: 600 },
+                temp_target_celsius: Range { min: 50, max: 90 },
+                gpu_clock_offset_mhz: Range { min: -250, max: 300 },
+                memory_clock_offset_mhz: Range { min: -500, max: 1500 },
+                voltage_offset_mv: Range { min: -100, max: 50 },
+                clock_mhz: Range { min: 300, max: 2800 },
+            },
+            ModelLimits {
+                vendor_id: 0x1002,
+                device_id: 0x73DF, // RX 6700 XT
+                name: "AMD Radeon RX 6700 XT".to_string(),
+                power_limit_watts: Range { min: 80, max: 260 },
+                temp_target_celsius: Range { min: 50, max: 95 },
+                gpu_clock_offset_mhz: Range { min: -200, max: 200 },
+                memory_clock_offset_mhz: Range { min: -300, max: 800 },
+                voltage_offset_mv: Range { min: -150, max: 50 },
+                clock_mhz: Range { min: 300, max: 2600 },
+            },
+        ],
+    }
+}
+
+/// Provider of per-model hardware safety limits
+#[derive(Debug)]
+pub struct HardwareLimits {
+    table: LimitsTable,
+    cache_path: PathBuf,
+}
+
+impl HardwareLimits {
+    /// Load limits, preferring the local cache over the compiled-in defaults.
+    /// Does not make a network request; call [`Self::update_online`] for that.
+    pub fn load() -> Result<Self> {
+        let cache_path = default_cache_path()?;
+        let table = match fs::read_to_string(&cache_path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                warn!("Ignoring corrupt hardware-limits cache: {}", e);
+                builtin_table()
+            }),
+            Err(_) => {
+                debug!("No hardware-limits cache at {}, using built-in defaults", cache_path.display());
+                builtin_table()
+            }
+        };
+
+        Ok(Self { table, cache_path })
+    }
+
+    /// Fetch a fresh limits table from the online database and persist it to the local cache.
+    /// On network failure the existing in-memory table (cache or built-ins) is left untouched.
+    #[instrument(skip(self))]
+    pub async fn update_online(&mut self) -> Result<()> {
+        self.update_online_from(DEFAULT_LIMITS_URL).await
+    }
+
+    async fn update_online_from(&mut self, url: &str) -> Result<()> {
+        let response = reqwest::get(url)
+            .await
+            .map_err(|e| GpuError::SystemError(format!("hardware-limits fetch failed: {e}")))?;
+
+        let mut table: LimitsTable = response
+            .json()
+            .await
+            .map_err(|e| GpuError::SystemError(format!("hardware-limits response malformed: {e}")))?;
+
+        table.fetched_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        if let Some(parent) = self.cache_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&table)?;
+        fs::write(&self.cache_path, json)?;
+
+        info!("Updated hardware-limits cache with {} model(s)", table.models.len());
+        self.table = table;
+        Ok(())
+    }
+
+    /// Look up the safe ranges for a specific `(vendor_id, device_id)`, falling back to a
+    /// conservative generic range if the model is not in the table.
+    pub fn for_device(&self, vendor_id: u16, device_id: u16) -> ModelLimits {
+        self.table
+            .models
+            .iter()
+            .find(|m| m.vendor_id == vendor_id && m.device_id == device_id)
+            .cloned()
+            .unwrap_or_else(|| generic_fallback(vendor_id, device_id))
+    }
+
+    /// Clamp a requested power limit to the model's safe range, erroring instead of silently
+    /// clamping when the caller asked for something wildly out of range.
+    pub fn check_power_limit(&self, vendor_id: u16, device_id: u16, requested: u32) -> Result<u32> {
+        let limits = self.for_device(vendor_id, device_id);
+        if !limits.power_limit_watts.contains(requested) {
+            return Err(GpuError::LimitExceeded {
+                requested: requested as i64,
+                max: limits.power_limit_watts.max as i64,
+            });
+        }
+        Ok(requested)
+    }
+
+    /// Check whether raising the core clock to `target_clock_mhz` is safe given the model's
+    /// thermal and power envelope, rather than just its raw clock range (see
+    /// [`Self::for_device`]'s `clock_mhz`). Unlike [`Self::check_power_limit`]'s flat range check,
+    /// this accounts for where the GPU already is: a target that's within range on paper can still
+    /// be unsafe to sustain if the card has no thermal headroom left, or draws more power at that
+    /// clock than the model's TDP allows.
+    pub fn check_clock_power_budget(
+        &self,
+        vendor_id: u16,
+        device_id: u16,
+        target_clock_mhz: u32,
+        current_temp_celsius: u32,
+        estimated_watts_at_target: u32,
+    ) -> Result<()> {
+        let limits = self.for_device(vendor_id, device_id);
+
+        if !limits.clock_mhz.contains(target_clock_mhz) {
+            return Err(GpuError::OutOfRange {
+                requested: target_clock_mhz as i64,
+                min: limits.clock_mhz.min as i64,
+                max: limits.clock_mhz.max as i64,
+            });
+        }
+        if current_temp_celsius >= limits.temp_target_celsius.max {
+            return Err(GpuError::ThermalError(format!(
+                "current temperature {current_temp_celsius}C is already at or above the {}C safe \
+                 target for {}; raising clocks further is not safe",
+                limits.temp_target_celsius.max, limits.name
+            )));
+        }
+        if !limits.power_limit_watts.contains(estimated_watts_at_target) {
+            return Err(GpuError::PowerError(format!(
+                "estimated {estimated_watts_at_target}W at {target_clock_mhz} MHz exceeds {}'s \
+                 {}W power limit",
+                limits.name, limits.power_limit_watts.max
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn default_cache_path() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var("HECATE_CONFIG_DIR") {
+        return Ok(PathBuf::from(dir).join("gpu").join("hardware-limits.json"));
+    }
+
+    let home = std::env::var("HOME")
+        .map_err(|_| GpuError::InvalidConfig("HOME is not set; cannot locate config directory".to_string()))?;
+    Ok(PathBuf::from(home).join(".config").join("hecate").join("gpu").join("hardware-limits.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_model_returns_specific_range() {
+        let table = builtin_table();
+        let limits = HardwareLimits {
+            table,
+            cache_path: PathBuf::from("/tmp/unused-in-test"),
+        };
+
+        let rtx3090 = limits.for_device(0x10DE, 0x2204);
+        assert_eq!(rtx3090.power_limit_watts.max, 420);
+    }
+
+    #[test]
+    fn unknown_model_falls_back_to_generic() {
+        let limits = HardwareLimits {
+            table: builtin_table(),
+            cache_path: PathBuf::from("/tmp/unused-in-test"),
+        };
+
+        let unknown = limits.for_device(0xFFFF, 0xFFFF);
+        assert_eq!(unknown.name, "Unknown GPU (generic fallback)");
+    }
+
+    #[test]
+    fn check_power_limit_rejects_out_of_range() {
+        let limits = HardwareLimits {
+            table: builtin_table(),
+            cache_path: PathBuf::from("/tmp/unused-in-test"),
+        };
+
+        assert!(limits.check_power_limit(0x10DE, 0x2204, 900).is_err());
+        assert!(limits.check_power_limit(0x10DE, 0x2204, 300).is_ok());
+    }
+
+    #[test]
+    fn check_clock_power_budget_accepts_a_target_within_every_envelope() {
+        let limits = HardwareLimits {
+            table: builtin_table(),
+            cache_path: PathBuf::from("/tmp/unused-in-test"),
+        };
+
+        assert!(limits.check_clock_power_budget(0x10DE, 0x2204, 1800, 70, 380).is_ok());
+    }
+
+    #[test]
+    fn check_clock_power_budget_rejects_a_clock_outside_the_models_range() {
+        let limits = HardwareLimits {
+            table: builtin_table(),
+            cache_path: PathBuf::from("/tmp/unused-in-test"),
+        };
+
+        let err = limits.check_clock_power_budget(0x10DE, 0x2204, 5000, 70, 380).unwrap_err();
+        assert!(matches!(err, GpuError::OutOfRange { .. }));
+    }
+
+    #[test]
+    fn check_clock_power_budget_rejects_when_out_of_thermal_headroom() {
+        let limits = HardwareLimits {
+            table: builtin_table(),
+            cache_path: PathBuf::from("/tmp/unused-in-test"),
+        };
+
+        // RTX 3090's temp_target_celsius.max is 93
+        let err = limits.check_clock_power_budget(0x10DE, 0x2204, 1800, 93, 380).unwrap_err();
+        assert!(matches!(err, GpuError::ThermalError(_)));
+    }
+
+    #[test]
+    fn check_clock_power_budget_rejects_when_estimated_power_exceeds_the_tdp() {
+        let limits = HardwareLimits {
+            table: builtin_table(),
+            cache_path: PathBuf::from("/tmp/unused-in-test"),
+        };
+
+        // RTX 3090's power_limit_watts.max is 420
+        let err = limits.check_clock_power_budget(0x10DE, 0x2204, 1800, 70, 500).unwrap_err();
+        assert!(matches!(err, GpuError::PowerError(_)));
+    }
+}
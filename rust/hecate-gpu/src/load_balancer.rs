@@ -0,0 +1,442 @@
+//! Multi-GPU workload distribution
+//!
+//! Spreads work across every enabled GPU instead of pinning everything to index 0.
+//! [`LoadBalancer::assign_workload`] picks a single GPU for an indivisible job;
+//! [`LoadBalancer::partition_workload`] splits a divisible one proportionally across all of them,
+//! weighted by how much headroom (free memory, idle compute) each currently has. Works the same
+//! regardless of which vendor backend a [`crate::GpuStatus`] came from, so a mixed NVIDIA/AMD/Intel
+//! box balances across all of them through one API.
+
+use crate::{calculate_efficiency_score, GpuError, GpuStatus, Result};
+use tracing::{debug, info, instrument};
+
+/// Default temperature, in Celsius, at or above which a GPU is excluded from new work
+const DEFAULT_THERMAL_CAP_CELSIUS: u32 = 90;
+
+/// How [`LoadBalancer`] picks (or weights) GPUs for a workload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadBalanceStrategy {
+    /// Send the whole job to whichever eligible GPU has the lowest `utilization_gpu`
+    LeastUtilized,
+    /// Send the whole job to whichever eligible GPU is running coolest
+    ThermalOptimized,
+    /// Send the whole job to whichever eligible GPU scores highest on
+    /// [`crate::calculate_efficiency_score`]
+    PowerEfficient,
+    /// Split the job across every eligible GPU, weighted by available headroom; see
+    /// [`LoadBalancer::partition_workload`]
+    Proportional,
+}
+
+/// Durable handle for a workload assigned by [`LoadBalancer::assign_workload`]. Carries a
+/// generation counter alongside its slot index so a slot freed by [`LoadBalancer::release`] and
+/// later reused by a new assignment can't alias an old caller's handle; `workload_status`/
+/// `release` on a stale id simply report "not found" instead of resolving to the wrong workload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WorkloadId {
+    index: usize,
+    generation: u32,
+}
+
+/// State of a workload looked up by [`WorkloadId`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkloadStatus {
+    /// Still assigned to the given GPU
+    Assigned { gpu_index: u32 },
+}
+
+/// One registry slot: either occupied by a live assignment, or free and linked into the
+/// registry's free list
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    Occupied { generation: u32, gpu_index: u32 },
+    Free { generation: u32, next_free: Option<usize> },
+}
+
+/// Reusable-slot allocator backing [`LoadBalancer`]'s workload handles. A free-list/generational
+/// index is preferred here over a monotonically incrementing counter so a long-running daemon
+/// that assigns and releases workloads continuously doesn't leak a growing `Vec` entry per
+/// assignment; freed slots are recycled by the next `insert`.
+#[derive(Debug, Default)]
+struct WorkloadRegistry {
+    slots: Vec<Slot>,
+    free_head: Option<usize>,
+}
+
+impl WorkloadRegistry {
+    fn insert(&mut self, gpu_index: u32) -> WorkloadId {
+        if let Some(index) = self.free_head {
+            let Slot::Free { generation, next_free } = self.slots[index] else {
+                unreachable!("free list pointed at an occupied slot");
+            };
+            self.free_head = next_free;
+            self.slots[index] = Slot::Occupied { generation, gpu_index };
+            WorkloadId { index, generation }
+        } else {
+            let generation = 0;
+            self.slots.push(Slot::Occupied { generation, gpu_index });
+            WorkloadId { index: self.slots.len() - 1, generation }
+        }
+    }
+
+    fn get(&self, id: WorkloadId) -> Option<u32> {
+        match self.slots.get(id.index) {
+            Some(Slot::Occupied { generation, gpu_index }) if *generation == id.generation => Some(*gpu_index),
+            _ => None,
+        }
+    }
+
+    /// Free `id`'s slot for reuse. Returns `false` if `id` was already released or is stale
+    /// (its generation no longer matches the slot's current occupant).
+    fn remove(&mut self, id: WorkloadId) -> bool {
+        match self.slots.get(id.index) {
+            Some(Slot::Occupied { generation, .. }) if *generation == id.generation => {
+                self.slots[id.index] = Slot::Free {
+                    generation: generation.wrapping_add(1),
+                    next_free: self.free_head,
+                };
+                self.free_head = Some(id.index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn on_gpu(&self, gpu_index: u32) -> Vec<WorkloadId> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| match slot {
+                Slot::Occupied { generation, gpu_index: g } if *g == gpu_index => {
+                    Some(WorkloadId { index, generation: *generation })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Distributes workloads across a snapshot of GPUs (e.g. from [`crate::detect_backends`] or
+/// [`crate::GpuManager::detect_gpus`])
+#[derive(Debug)]
+pub struct LoadBalancer {
+    gpus: Vec<GpuStatus>,
+    enabled: bool,
+    strategy: LoadBalanceStrategy,
+    /// GPUs at or above this temperature are skipped for new work, regardless of strategy
+    thermal_cap_celsius: u32,
+    /// Bounds the granularity of a single chunk in [`Self::partition_workload`], so a dispatch
+    /// stays pipeline-friendly instead of handing a GPU one enormous chunk
+    chunk_size: u64,
+    /// Durable identity for every currently-assigned workload, keyed by [`WorkloadId`]
+    workloads: WorkloadRegistry,
+}
+
+impl LoadBalancer {
+    /// Create a balancer over `gpus`, disabled until [`Self::enable`] is called
+    pub fn new(gpus: Vec<GpuStatus>) -> Self {
+        Self {
+            gpus,
+            enabled: false,
+            strategy: LoadBalanceStrategy::LeastUtilized,
+            thermal_cap_celsius: DEFAULT_THERMAL_CAP_CELSIUS,
+            chunk_size: 1,
+            workloads: WorkloadRegistry::default(),
+        }
+    }
+
+    /// Start accepting workload assignments
+    pub async fn enable(&mut self) {
+        self.enabled = true;
+        info!("Load balancer enabled across {} GPU(s)", self.gpus.len());
+    }
+
+    /// Stop accepting workload assignments; existing ones are unaffected
+    pub async fn disable(&mut self) {
+        self.enabled = false;
+        info!("Load balancer disabled");
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Refresh the GPU snapshot this balancer makes decisions against
+    pub async fn update_gpu_status(&mut self, gpus: Vec<GpuStatus>) {
+        self.gpus = gpus;
+    }
+
+    pub fn set_strategy(&mut self, strategy: LoadBalanceStrategy) {
+        self.strategy = strategy;
+    }
+
+    /// Set the temperature, in Celsius, at or above which a GPU is excluded from new work
+    pub fn set_thermal_cap(&mut self, celsius: u32) {
+        self.thermal_cap_celsius = celsius;
+    }
+
+    /// Bound the per-dispatch chunk granularity [`Self::partition_workload`] rounds shares down to
+    pub fn set_chunk_size(&mut self, chunk_size: u64) {
+        self.chunk_size = chunk_size.max(1);
+    }
+
+    fn eligible_gpus(&self) -> Vec<&GpuStatus> {
+        self.gpus.iter().filter(|g| g.temperature < self.thermal_cap_celsius).collect()
+    }
+
+    /// Assign an entire, indivisible workload to a single GPU chosen per the active strategy,
+    /// returning a durable [`WorkloadId`] for it rather than the GPU index directly. Look up or
+    /// release the assignment later via [`Self::workload_status`]/[`Self::release`]; internal
+    /// code should thread the resolved `WorkloadId` around rather than re-deriving a GPU index.
+    /// `Proportional` has no single-GPU meaning, so it falls back to `LeastUtilized` here; use
+    /// [`Self::partition_workload`] for a divisible job instead.
+    #[instrument(skip(self))]
+    pub async fn assign_workload(&mut self) -> Result<WorkloadId> {
+        if !self.enabled {
+            return Err(GpuError::LoadBalancerNotAvailable);
+        }
+
+        let candidates = self.eligible_gpus();
+        let chosen = match self.strategy {
+            LoadBalanceStrategy::LeastUtilized | LoadBalanceStrategy::Proportional => {
+                candidates.iter().min_by_key(|g| g.utilization_gpu)
+            }
+            LoadBalanceStrategy::ThermalOptimized => candidates.iter().min_by_key(|g| g.temperature),
+            LoadBalanceStrategy::PowerEfficient => candidates.iter().max_by(|a, b| {
+                calculate_efficiency_score(a)
+                    .partial_cmp(&calculate_efficiency_score(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            }),
+        }
+        .copied()
+        .ok_or(GpuError::LoadBalancerNotAvailable)?;
+
+        let id = self.workloads.insert(chosen.index);
+        debug!("Assigned workload {:?} to GPU {} via {:?}", id, chosen.index, self.strategy);
+        Ok(id)
+    }
+
+    /// Look up a workload assigned by [`Self::assign_workload`]. Returns `None` if `id` has
+    /// already been [`Self::release`]d or never existed.
+    pub fn workload_status(&self, id: WorkloadId) -> Option<WorkloadStatus> {
+        self.workloads.get(id).map(|gpu_index| WorkloadStatus::Assigned { gpu_index })
+    }
+
+    /// Release a workload's durable handle, freeing its slot for reuse by a future
+    /// [`Self::assign_workload`] call. Returns `false` if `id` was already released or is stale.
+    pub fn release(&mut self, id: WorkloadId) -> bool {
+        self.workloads.remove(id)
+    }
+
+    /// All currently-assigned workloads on a given GPU, most useful for reverse lookups (e.g.
+    /// "what do I need to drain before taking this GPU offline?").
+    pub fn workloads_on(&self, gpu_index: u32) -> Vec<WorkloadId> {
+        self.workloads.on_gpu(gpu_index)
+    }
+
+    /// Split `total_work` units proportionally across every GPU under the thermal cap, weighted
+    /// by available headroom (free-memory percentage plus idle-compute percentage), so GPUs that
+    /// are both underused and have spare VRAM get the biggest chunks. Returns an empty assignment
+    /// rather than panicking when the balancer is disabled or no GPU is eligible. Shares are
+    /// rounded down to a multiple of `chunk_size`; any work lost to that rounding is handed to
+    /// the single highest-weighted GPU so the total still adds up to `total_work`.
+    pub fn partition_workload(&self, total_work: u64) -> Vec<(u32, u64)> {
+        if !self.enabled || total_work == 0 {
+            return Vec::new();
+        }
+
+        let candidates = self.eligible_gpus();
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        let weights: Vec<f64> = candidates.iter().map(|gpu| Self::headroom_weight(gpu)).collect();
+        let total_weight: f64 = weights.iter().sum();
+
+        let mut assignment: Vec<(u32, u64)> = candidates
+            .iter()
+            .zip(&weights)
+            .map(|(gpu, weight)| {
+                let share = (total_work as f64 * (weight / total_weight)).floor() as u64;
+                (gpu.index, (share / self.chunk_size) * self.chunk_size)
+            })
+            .collect();
+
+        let assigned: u64 = assignment.iter().map(|(_, work)| *work).sum();
+        let remainder = total_work.saturating_sub(assigned);
+        if remainder > 0 {
+            let heaviest = weights
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(idx, _)| idx)
+                .unwrap_or(0);
+            assignment[heaviest].1 += remainder;
+        }
+
+        assignment
+    }
+
+    /// Headroom score for proportional partitioning: idle-compute percentage plus free-memory
+    /// percentage. A GPU with no tracked VRAM (e.g. an integrated GPU with `memory_total: 0`)
+    /// scores full marks on the memory term rather than dividing by zero.
+    fn headroom_weight(gpu: &GpuStatus) -> f64 {
+        let idle_compute = (100 - gpu.utilization_gpu.min(100)) as f64;
+        let free_memory_percent = if gpu.memory_total > 0 {
+            (gpu.memory_total.saturating_sub(gpu.memory_used) as f64 / gpu.memory_total as f64) * 100.0
+        } else {
+            100.0
+        };
+        (idle_compute + free_memory_percent).max(0.01)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DriverBinding, GpuType, GpuVendor, PciInfo, PowerState};
+
+    fn gpu(index: u32, utilization_gpu: u32, temperature: u32, memory_used: u64, memory_total: u64) -> GpuStatus {
+        GpuStatus {
+            index,
+            name: format!("Test GPU {index}"),
+            vendor: GpuVendor::NVIDIA,
+            gpu_type: GpuType::Discrete,
+            temperature,
+            power_draw: 200,
+            power_limit: Some(300),
+            memory_used,
+            memory_total,
+            utilization_gpu,
+            utilization_memory: 0,
+            fan_speed: None,
+            clock_graphics: 0,
+            clock_memory: Some(0),
+            driver_version: None,
+            pci_info: PciInfo { domain: 0, bus: 0, device: 0, function: 0, vendor_id: 0, device_id: 0 },
+            power_state: PowerState::Active,
+            voltage_mv: None,
+            throttle_reasons: Vec::new(),
+            ecc_errors: None,
+            processes: Vec::new(),
+            driver_bound: DriverBinding::Unbound,
+            unified_memory: false,
+            mig_parent: None,
+            mig_uuid: None,
+            uuid: None,
+            serial: None,
+            board_part_number: None,
+            vbios_version: None,
+            cuda_driver_version: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn assign_workload_fails_when_disabled() {
+        let mut lb = LoadBalancer::new(vec![gpu(0, 10, 50, 0, 100)]);
+        let err = lb.assign_workload().await.unwrap_err();
+        assert!(matches!(err, GpuError::LoadBalancerNotAvailable));
+    }
+
+    #[tokio::test]
+    async fn assign_workload_picks_least_utilized_gpu() {
+        let mut lb = LoadBalancer::new(vec![gpu(0, 80, 60, 0, 100), gpu(1, 20, 60, 0, 100)]);
+        lb.enable().await;
+        lb.set_strategy(LoadBalanceStrategy::LeastUtilized);
+        let id = lb.assign_workload().await.unwrap();
+        assert_eq!(lb.workload_status(id), Some(WorkloadStatus::Assigned { gpu_index: 1 }));
+    }
+
+    #[tokio::test]
+    async fn assign_workload_skips_gpus_over_the_thermal_cap() {
+        let mut lb = LoadBalancer::new(vec![gpu(0, 10, 95, 0, 100), gpu(1, 50, 60, 0, 100)]);
+        lb.enable().await;
+        lb.set_strategy(LoadBalanceStrategy::LeastUtilized);
+        // GPU 0 has lower utilization but is over the default 90C cap, so GPU 1 must win instead.
+        let id = lb.assign_workload().await.unwrap();
+        assert_eq!(lb.workload_status(id), Some(WorkloadStatus::Assigned { gpu_index: 1 }));
+    }
+
+    #[tokio::test]
+    async fn release_frees_the_slot_and_bumps_the_generation_so_stale_ids_miss() {
+        let mut lb = LoadBalancer::new(vec![gpu(0, 10, 50, 0, 100)]);
+        lb.enable().await;
+
+        let first = lb.assign_workload().await.unwrap();
+        assert!(lb.release(first));
+        assert_eq!(lb.workload_status(first), None);
+
+        // The freed slot gets reused by the next assignment, but with a bumped generation, so
+        // `first` must not resolve even though it may share the same slot index as `second`.
+        let second = lb.assign_workload().await.unwrap();
+        assert_eq!(lb.workload_status(first), None);
+        assert_eq!(lb.workload_status(second), Some(WorkloadStatus::Assigned { gpu_index: 0 }));
+    }
+
+    #[tokio::test]
+    async fn release_on_an_unknown_id_returns_false() {
+        let mut lb = LoadBalancer::new(vec![gpu(0, 10, 50, 0, 100)]);
+        lb.enable().await;
+        let id = lb.assign_workload().await.unwrap();
+        assert!(lb.release(id));
+        assert!(!lb.release(id));
+    }
+
+    #[tokio::test]
+    async fn workloads_on_reverse_looks_up_every_assignment_for_a_gpu() {
+        let mut lb = LoadBalancer::new(vec![gpu(0, 10, 50, 0, 100), gpu(1, 90, 50, 0, 100)]);
+        lb.enable().await;
+        lb.set_strategy(LoadBalanceStrategy::LeastUtilized);
+
+        let a = lb.assign_workload().await.unwrap();
+        let b = lb.assign_workload().await.unwrap();
+
+        let on_gpu_0 = lb.workloads_on(0);
+        assert_eq!(on_gpu_0.len(), 2);
+        assert!(on_gpu_0.contains(&a));
+        assert!(on_gpu_0.contains(&b));
+        assert!(lb.workloads_on(1).is_empty());
+    }
+
+    #[test]
+    fn partition_workload_is_empty_when_disabled() {
+        let lb = LoadBalancer::new(vec![gpu(0, 10, 50, 0, 100)]);
+        assert!(lb.partition_workload(1000).is_empty());
+    }
+
+    #[tokio::test]
+    async fn partition_workload_is_empty_when_no_gpu_is_eligible() {
+        let mut lb = LoadBalancer::new(vec![gpu(0, 10, 95, 0, 100)]);
+        lb.enable().await;
+        assert!(lb.partition_workload(1000).is_empty());
+    }
+
+    #[tokio::test]
+    async fn partition_workload_splits_proportionally_to_headroom_and_sums_to_the_total() {
+        let mut lb = LoadBalancer::new(vec![
+            gpu(0, 0, 50, 0, 100),   // fully idle, fully free memory: biggest headroom
+            gpu(1, 90, 50, 90, 100), // nearly saturated on both axes: smallest headroom
+        ]);
+        lb.enable().await;
+
+        let assignment = lb.partition_workload(1000);
+        let by_index: std::collections::HashMap<u32, u64> = assignment.into_iter().collect();
+
+        assert!(by_index[&0] > by_index[&1]);
+        assert_eq!(by_index[&0] + by_index[&1], 1000);
+    }
+
+    #[tokio::test]
+    async fn partition_workload_rounds_shares_down_to_chunk_size() {
+        let mut lb = LoadBalancer::new(vec![gpu(0, 0, 50, 0, 100), gpu(1, 0, 50, 0, 100)]);
+        lb.enable().await;
+        lb.set_chunk_size(64);
+
+        let assignment = lb.partition_workload(1000);
+        for (_, work) in &assignment {
+            // Every individual per-GPU share is chunk-aligned before the leftover remainder is
+            // folded into the heaviest GPU, so only the heaviest entry may not be chunk-aligned.
+            assert!(work % 64 == 0 || *work == assignment.iter().map(|(_, w)| *w).max().unwrap());
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Pluggable metrics export for the monitoring loop
+//!
+//! `GpuManager::start_monitoring` samples every GPU the same way `get_all_gpu_status` does and
+//! hands the batch to a [`MetricSink`], which renders it into a wire format and pushes it
+//! wherever cluster telemetry expects it. Two formats are implemented: InfluxDB line protocol
+//! and Prometheus text exposition, both shipped over HTTP so they drop into an existing
+//! collector (an InfluxDB `/write` endpoint, a Prometheus Pushgateway) without extra plumbing.
+
+use crate::error::{GpuError, Result};
+use crate::GpuStatus;
+use async_trait::async_trait;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where and in what format to export sampled GPU metrics
+#[derive(Debug, Clone)]
+pub enum MetricSinkKind {
+    /// InfluxDB line protocol, HTTP POSTed to a `/write`-style endpoint
+    InfluxLineProtocol { write_url: String },
+    /// Prometheus text exposition format, HTTP POSTed to a Pushgateway-style endpoint
+    PrometheusText { push_url: String },
+}
+
+/// Renders a batch of GPU statuses into a wire format and ships the result off
+#[async_trait]
+pub trait MetricSink: Send + Sync {
+    /// Render every status into this sink's wire format
+    fn format(&self, statuses: &[GpuStatus]) -> String;
+
+    /// Ship a rendered payload to this sink's destination
+    async fn push(&self, payload: String) -> Result<()>;
+}
+
+/// Build the sink described by a [`MetricSinkKind`]
+pub fn build_sink(kind: &MetricSinkKind) -> Box<dyn MetricSink> {
+    match kind {
+        MetricSinkKind::InfluxLineProtocol { write_url } => {
+            Box::new(InfluxLineProtocolSink { write_url: write_url.clone() })
+        }
+        MetricSinkKind::PrometheusText { push_url } => {
+            Box::new(PrometheusTextSink { push_url: push_url.clone() })
+        }
+    }
+}
+
+/// Escape characters InfluxDB line protocol requires escaped in tag values: commas, spaces, and
+/// equals signs (backslash must be escaped first so the other replacements don't double-escape).
+/// `pub(crate)` so [`crate::telemetry`] can reuse it instead of re-implementing the same escaping.
+pub(crate) fn escape_tag_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(' ', "\\ ")
+        .replace('=', "\\=")
+}
+
+/// InfluxDB line protocol sink: one line per GPU, e.g.
+/// `gpu,index=0,name=RTX\ 4090,vendor=NVIDIA temperature=65i,power_draw=210i,mem_used=4294967296i,util_gpu=80i 1706454000000000000`
+pub struct InfluxLineProtocolSink {
+    write_url: String,
+}
+
+#[async_trait]
+impl MetricSink for InfluxLineProtocolSink {
+    fn format(&self, statuses: &[GpuStatus]) -> String {
+        let timestamp_nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+
+        let mut out = String::new();
+        for status in statuses {
+            out.push_str(&format!(
+                "gpu,index={},name={},vendor={:?} temperature={}i,power_draw={}i,mem_used={}i,util_gpu={}i {}\n",
+                status.index,
+                escape_tag_value(&status.name),
+                status.vendor,
+                status.temperature,
+                status.power_draw,
+                status.memory_used,
+                status.utilization_gpu,
+                timestamp_nanos,
+            ));
+        }
+        out
+    }
+
+    async fn push(&self, payload: String) -> Result<()> {
+        reqwest::Client::new()
+            .post(&self.write_url)
+            .body(payload)
+            .send()
+            .await
+            .map_err(|e| GpuError::SystemError(format!("InfluxDB write failed: {e}")))?;
+        Ok(())
+    }
+}
+
+/// Escape characters Prometheus text exposition requires escaped in label values. `pub(crate)`
+/// so [`crate::telemetry`] can reuse it instead of re-implementing the same escaping.
+pub(crate) fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Prometheus text exposition sink: four metric families per GPU, e.g.
+/// `hecate_gpu_temperature_celsius{index="0",name="RTX 4090"} 65`
+pub struct PrometheusTextSink {
+    push_url: String,
+}
+
+#[async_trait]
+impl MetricSink for PrometheusTextSink {
+    fn format(&self, statuses: &[GpuStatus]) -> String {
+        let mut out = String::new();
+        for status in statuses {
+            let labels = format!(
+                "index=\"{}\",name=\"{}\"",
+                status.index,
+                escape_label_value(&status.name)
+            );
+            out.push_str(&format!("hecate_gpu_temperature_celsius{{{labels}}} {}\n", status.temperature));
+            out.push_str(&format!("hecate_gpu_power_draw_watts{{{labels}}} {}\n", status.power_draw));
+            out.push_str(&format!("hecate_gpu_memory_used_bytes{{{labels}}} {}\n", status.memory_used));
+            out.push_str(&format!("hecate_gpu_utilization_percent{{{labels}}} {}\n", status.utilization_gpu));
+        }
+        out
+    }
+
+    async fn push(&self, payload: String) -> Result<()> {
+        reqwest::Client::new()
+            .post(&self.push_url)
+            .body(payload)
+            .send()
+            .await
+            .map_err(|e| GpuError::SystemError(format!("Prometheus pushgateway write failed: {e}")))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GpuType, GpuVendor, PciInfo, PowerState};
+
+    fn sample_status() -> GpuStatus {
+        GpuStatus {
+            index: 0,
+            name: "RTX 4090".to_string(),
+            vendor: GpuVendor::NVIDIA,
+            gpu_type: GpuType::Discrete,
+            temperature: 65,
+            power_draw: 210,
+            power_limit: Some(450),
+            memory_used: 4_294_967_296,
+            memory_total: 24_000_000_000,
+            utilization_gpu: 80,
+            utilization_memory: 40,
+            fan_speed: Some(60),
+            clock_graphics: 2500,
+            clock_memory: Some(10000),
+            driver_version: None,
+            pci_info: PciInfo { domain: 0, bus: 1, device: 0, function: 0, vendor_id: 0x10DE, device_id: 0x2684 },
+            power_state: PowerState::Active,
+            voltage_mv: None,
+            throttle_reasons: Vec::new(),
+            ecc_errors: None,
+            processes: Vec::new(),
+            driver_bound: crate::vfio::DriverBinding::Unbound,
+            unified_memory: false,
+            mig_parent: None,
+            mig_uuid: None,
+            uuid: None,
+            serial: None,
+            board_part_number: None,
+            vbios_version: None,
+            cuda_driver_version: None,
+        }
+    }
+
+    #[test]
+    fn influx_line_protocol_escapes_tag_values() {
+        let sink = InfluxLineProtocolSink { write_url: "http://localhost:8086/write".to_string() };
+        let line = sink.format(&[sample_status()]);
+        assert!(line.contains("name=RTX\\ 4090"));
+        assert!(line.contains("temperature=65i"));
+        assert!(line.contains("mem_used=4294967296i"));
+    }
+
+    #[test]
+    fn prometheus_text_exposition_has_expected_metrics() {
+        let sink = PrometheusTextSink { push_url: "http://localhost:9091/metrics/job/hecate".to_string() };
+        let text = sink.format(&[sample_status()]);
+        assert!(text.contains("hecate_gpu_temperature_celsius{index=\"0\",name=\"RTX 4090\"} 65"));
+        assert!(text.contains("hecate_gpu_power_draw_watts{index=\"0\",name=\"RTX 4090\"} 210"));
+    }
+}
@@ -1,6 +1,9 @@
 //! GPU monitoring and alerting system
 
-use crate::{error::Result, GpuEvent, GpuStatus};
+use crate::{
+    error::Result, format_bytes, FanController, FanCurve, FreqScalingTable, GovernorConfig, GpuController, GpuEvent, GpuProcess,
+    GpuStatus, ReclockController,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -19,6 +22,32 @@ pub struct GpuMonitor {
     stats: MonitoringStats,
     /// Maximum history length per GPU
     max_history_length: usize,
+    /// Per-GPU stateful fan controllers, used to smooth out temperature-driven fan hunting
+    fan_controllers: HashMap<u32, FanController>,
+    /// Per-GPU stateful reclocking controllers, used to cap boost clocks under power pressure
+    reclock_controllers: HashMap<u32, ReclockController>,
+    /// Most recent per-process accounting snapshot for each GPU, as last reported by
+    /// [`Self::record_process_metrics`]
+    process_metrics: HashMap<u32, Vec<GpuProcess>>,
+    /// Alpha/k tuning for [`Self::detect_statistical_anomalies`]'s EWMA estimator
+    ewma_config: EwmaConfig,
+    /// Closed-loop thermal/power governor tuning; `None` means the governor is disabled and
+    /// [`Self::run_governor`] is a no-op, same as before it existed
+    governor_config: Option<GovernorConfig>,
+    /// Per-GPU governor runtime state (current caps and recovery bookkeeping)
+    governor_states: HashMap<u32, GovernorState>,
+}
+
+/// Per-GPU runtime state for [`GpuMonitor::run_governor`]: the caps currently applied, plus the
+/// bookkeeping [`GovernorConfig::recovery_hysteresis_secs`] needs to avoid oscillation
+#[derive(Debug, Clone)]
+struct GovernorState {
+    clock_cap_mhz: u32,
+    tdp_cap_watts: u32,
+    /// Wall-clock second this GPU was last seen at or above `temperature_warning`, so recovery
+    /// only steps caps back up once this has aged past `recovery_hysteresis_secs`. `None` once
+    /// caps are already back at `max` and there's nothing left to recover.
+    last_warning_at: Option<u64>,
 }
 
 /// Single metrics point in time
@@ -31,8 +60,16 @@ pub struct MetricsPoint {
     pub utilization_memory: u32,
     pub memory_used: u64,
     pub clock_graphics: u32,
-    pub clock_memory: u32,
+    /// Memory clock frequency in MHz, or `None` if the source GPU has no discrete memory clock
+    /// domain to read (see [`GpuStatus::clock_memory`])
+    pub clock_memory: Option<u32>,
     pub fan_speed: Option<u32>,
+    /// Total VRAM capacity in bytes, alongside `memory_used`, so per-process VRAM-share checks
+    /// (see [`GpuMonitor::detect_runaway_process`]) don't need a side channel to the GPU's specs
+    pub memory_total: u64,
+    /// Per-process accounting at this sample, if the backend reported any processes running;
+    /// see [`GpuMonitor::top_processes`] and [`AnomalyType::RunawayProcess`]
+    pub processes: Option<Vec<GpuProcess>>,
 }
 
 impl From<&GpuStatus> for MetricsPoint {
@@ -50,6 +87,8 @@ impl From<&GpuStatus> for MetricsPoint {
             clock_graphics: status.clock_graphics,
             clock_memory: status.clock_memory,
             fan_speed: status.fan_speed,
+            memory_total: status.memory_total,
+            processes: if status.processes.is_empty() { None } else { Some(status.processes.clone()) },
         }
     }
 }
@@ -67,6 +106,18 @@ pub struct AlertConfig {
     pub enable_thermal_alerts: bool,
     pub enable_power_alerts: bool,
     pub enable_memory_alerts: bool,
+    /// Fraction (0.0-1.0) of a GPU's `memory_total` a single process must use for
+    /// [`GpuMonitor::detect_runaway_process`] to flag it as [`AnomalyType::RunawayProcess`]
+    pub runaway_process_memory_fraction: f32,
+    /// Minimum sustained `memory_used` growth rate, in bytes/second, for
+    /// [`GpuMonitor::detect_memory_leak`] to consider the window's regression slope suspicious
+    pub memory_leak_slope_threshold_bytes_per_sec: f64,
+    /// Minimum R² of the `memory_used` vs. timestamp regression for
+    /// [`GpuMonitor::detect_memory_leak`] to trust the slope as monotone growth rather than noise
+    pub memory_leak_min_r_squared: f64,
+    /// Width of [`Anomaly::expected_range`] around the window-start VRAM baseline for
+    /// [`AnomalyType::MemoryLeak`], as a fraction (0.0-1.0) of `memory_total`
+    pub memory_leak_baseline_tolerance_fraction: f32,
 }
 
 impl Default for AlertConfig {
@@ -82,6 +133,10 @@ impl Default for AlertConfig {
             enable_thermal_alerts: true,
             enable_power_alerts: true,
             enable_memory_alerts: true,
+            runaway_process_memory_fraction: 0.9,
+            memory_leak_slope_threshold_bytes_per_sec: 1_000_000.0, // 1MB/s sustained growth
+            memory_leak_min_r_squared: 0.8,
+            memory_leak_baseline_tolerance_fraction: 0.05,
         }
     }
 }
@@ -138,6 +193,8 @@ pub enum TrendDirection {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Anomaly {
     pub gpu_index: u32,
+    /// Which `MetricsPoint` field this anomaly was observed on, e.g. `"temperature"`
+    pub metric: String,
     pub anomaly_type: AnomalyType,
     pub severity: AnomalySeverity,
     pub description: String,
@@ -155,6 +212,11 @@ pub enum AnomalyType {
     ClockDrift,
     MemoryLeak,
     PerformanceDegradation,
+    /// Fell outside its EWMA control band; see [`GpuMonitor::detect_statistical_anomalies`]
+    StatisticalOutlier,
+    /// A single process dominated the GPU's VRAM or held sustained high SM utilization; see
+    /// [`GpuMonitor::detect_runaway_process`]
+    RunawayProcess,
 }
 
 /// Anomaly severity levels
@@ -166,6 +228,106 @@ pub enum AnomalySeverity {
     Critical,
 }
 
+/// Tuning for [`GpuMonitor::detect_statistical_anomalies`]'s exponentially-weighted moving
+/// average and variance estimator. Also used by [`GpuMonitor::detect_temperature_spike`],
+/// [`GpuMonitor::detect_power_drop`], and [`GpuMonitor::detect_clock_drift`], which used to gate
+/// on fixed multipliers (`+20.0`, `*0.5`, `*0.7`) that misfired across different GPU classes and
+/// now self-calibrate off the same EWMA baseline instead.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EwmaConfig {
+    /// Smoothing factor for the running mean/variance; higher weights recent samples more
+    pub alpha: f64,
+    /// How many standard deviations (EWMA) or modified z-scores (MAD) outside the running mean
+    /// counts as anomalous
+    pub k: f64,
+    /// Whether the median/MAD-based modified z-score, computed over the same window, must also
+    /// flag the point before [`GpuMonitor::detect_temperature_spike`],
+    /// [`GpuMonitor::detect_power_drop`], and [`GpuMonitor::detect_clock_drift`] fire. Requiring
+    /// both criteria to agree cuts false positives neither alone would catch.
+    /// [`GpuMonitor::detect_statistical_anomalies`]'s EWMA-only control band ignores this.
+    pub mad_agreement: bool,
+}
+
+impl Default for EwmaConfig {
+    fn default() -> Self {
+        Self { alpha: 0.3, k: 3.0, mad_agreement: true }
+    }
+}
+
+/// Result of [`GpuMonitor::ewma_and_mad_baseline`]: the latest point's value alongside the EWMA
+/// baseline it was tested against and the two agreement criteria computed from it
+struct BaselineCheck {
+    current: f64,
+    mean: f64,
+    std_dev: f64,
+    /// `(current - mean) / std_dev`; signed, so callers check the direction they care about
+    z_score: f64,
+    /// `0.6745 * (current - median) / MAD` over the same window; signed like `z_score`
+    modified_z_score: f64,
+}
+
+impl BaselineCheck {
+    /// `mean ± k * std_dev`, for [`Anomaly::expected_range`]
+    fn expected_range(&self, k: f64) -> (f64, f64) {
+        (self.mean - k * self.std_dev, self.mean + k * self.std_dev)
+    }
+
+    /// Severity scales with how many multiples of `k` the (unsigned) z-score cleared
+    fn severity(&self, k: f64) -> AnomalySeverity {
+        let magnitude = self.z_score.abs();
+        if magnitude > k * 2.0 {
+            AnomalySeverity::Critical
+        } else if magnitude > k * 1.5 {
+            AnomalySeverity::High
+        } else {
+            AnomalySeverity::Medium
+        }
+    }
+}
+
+/// Median of `values` by sorting a copy; used by [`GpuMonitor::ewma_and_mad_baseline`] for both
+/// the median and (applied again to absolute deviations) the MAD
+fn median_of(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = sorted.len();
+    if n % 2 == 0 {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    } else {
+        sorted[n / 2]
+    }
+}
+
+/// Ordinary least-squares fit of `ys` against `xs`: `slope = Σ(xᵢ-x̄)(yᵢ-ȳ) / Σ(xᵢ-x̄)²`,
+/// `intercept = ȳ - slope·x̄`, and R² (the fraction of `ys`'s variance the line explains). Used by
+/// [`GpuMonitor::detect_memory_leak`] for both the VRAM-growth fit and the utilization trend
+/// check. `xs` and `ys` must be the same non-empty length; a zero-variance `xs` (or `ys`) yields a
+/// slope (or R²) of `0.0` rather than dividing by zero.
+fn linear_regression(xs: &[f64], ys: &[f64]) -> (f64, f64, f64) {
+    let n = xs.len() as f64;
+    let x_mean = xs.iter().sum::<f64>() / n;
+    let y_mean = ys.iter().sum::<f64>() / n;
+
+    let mut covariance = 0.0_f64;
+    let mut x_variance = 0.0_f64;
+    for (&x, &y) in xs.iter().zip(ys.iter()) {
+        covariance += (x - x_mean) * (y - y_mean);
+        x_variance += (x - x_mean).powi(2);
+    }
+    let slope = if x_variance > f64::EPSILON { covariance / x_variance } else { 0.0 };
+    let intercept = y_mean - slope * x_mean;
+
+    let mut ss_res = 0.0_f64;
+    let mut ss_tot = 0.0_f64;
+    for (&x, &y) in xs.iter().zip(ys.iter()) {
+        ss_res += (y - (intercept + slope * x)).powi(2);
+        ss_tot += (y - y_mean).powi(2);
+    }
+    let r_squared = if ss_tot > f64::EPSILON { 1.0 - ss_res / ss_tot } else { 0.0 };
+
+    (slope, intercept, r_squared)
+}
+
 impl GpuMonitor {
     /// Create a new GPU monitor
     pub fn new(event_sender: broadcast::Sender<GpuEvent>) -> Self {
@@ -175,9 +337,136 @@ impl GpuMonitor {
             event_sender,
             stats: MonitoringStats::default(),
             max_history_length: 1440, // 24 hours at 1-minute intervals
+            fan_controllers: HashMap::new(),
+            reclock_controllers: HashMap::new(),
+            process_metrics: HashMap::new(),
+            ewma_config: EwmaConfig::default(),
+            governor_config: None,
+            governor_states: HashMap::new(),
         }
     }
 
+    /// Update the alpha/k tuning used by [`Self::detect_statistical_anomalies`]
+    pub fn set_ewma_config(&mut self, config: EwmaConfig) {
+        self.ewma_config = config;
+        info!("EWMA anomaly detection configuration updated");
+    }
+
+    /// Enable, reconfigure, or disable (`None`) the closed-loop thermal/power governor. Disabling
+    /// also drops any in-progress per-GPU governor state, so re-enabling starts pinned back at
+    /// `max` rather than resuming mid-correction.
+    pub fn set_governor_config(&mut self, config: Option<GovernorConfig>) {
+        self.governor_config = config;
+        self.governor_states.clear();
+        info!("Governor configuration updated");
+    }
+
+    /// Step the closed-loop thermal/power governor for `gpu_index` against `status`, applying any
+    /// corrective clock/TDP cap through `controller` and recording the action as a
+    /// [`GpuEvent::GovernorAction`]. `now_secs` is the caller's wall-clock reading (Unix seconds)
+    /// at the time `status` was sampled, so recovery hysteresis can be driven deterministically in
+    /// tests rather than reading [`SystemTime::now`] internally.
+    ///
+    /// A no-op when [`Self::set_governor_config`] hasn't been called: alerts still fire via
+    /// [`Self::check_alerts`], but nothing acts on them until a [`GovernorConfig`] is set.
+    pub async fn run_governor(
+        &mut self,
+        gpu_index: u32,
+        status: &GpuStatus,
+        now_secs: u64,
+        controller: &dyn GpuController,
+    ) -> Result<()> {
+        let Some(config) = self.governor_config.clone() else {
+            return Ok(());
+        };
+
+        let state = self.governor_states.entry(gpu_index).or_insert_with(|| GovernorState {
+            clock_cap_mhz: config.clock_limits.max,
+            tdp_cap_watts: config.tdp_limits.max,
+            last_warning_at: None,
+        });
+
+        let power_critical = self.alert_config.enable_power_alerts
+            && status
+                .power_limit
+                .filter(|&l| l > 0)
+                .is_some_and(|l| (status.power_draw * 100) / l >= self.alert_config.power_usage_warning);
+        let thermal_critical = status.temperature >= self.alert_config.temperature_critical;
+
+        let action = if thermal_critical || power_critical {
+            state.last_warning_at = Some(now_secs);
+            let new_clock = state.clock_cap_mhz.saturating_sub(config.step).max(config.clock_limits.min);
+            let new_tdp = state.tdp_cap_watts.saturating_sub(config.step).max(config.tdp_limits.min);
+            let changed = new_clock != state.clock_cap_mhz || new_tdp != state.tdp_cap_watts;
+            state.clock_cap_mhz = new_clock;
+            state.tdp_cap_watts = new_tdp;
+            changed.then(|| {
+                let reason = if thermal_critical { "critical temperature" } else { "power alert" };
+                (new_clock, new_tdp, reason.to_string())
+            })
+        } else if status.temperature < self.alert_config.temperature_warning {
+            let at_max = state.clock_cap_mhz >= config.clock_limits.max && state.tdp_cap_watts >= config.tdp_limits.max;
+            if at_max {
+                state.last_warning_at = None;
+                None
+            } else {
+                let warning_age = now_secs.saturating_sub(state.last_warning_at.unwrap_or(now_secs));
+                if warning_age >= config.recovery_hysteresis_secs {
+                    let new_clock = (state.clock_cap_mhz + config.step).min(config.clock_limits.max);
+                    let new_tdp = (state.tdp_cap_watts + config.step).min(config.tdp_limits.max);
+                    let changed = new_clock != state.clock_cap_mhz || new_tdp != state.tdp_cap_watts;
+                    state.clock_cap_mhz = new_clock;
+                    state.tdp_cap_watts = new_tdp;
+                    state.last_warning_at = Some(now_secs);
+                    changed.then(|| (new_clock, new_tdp, "recovered".to_string()))
+                } else {
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        if let Some((clock_limit_mhz, tdp_watts, reason)) = action {
+            controller.set_clock_limit(gpu_index, clock_limit_mhz).await?;
+            controller.set_power_limit(gpu_index, tdp_watts).await?;
+            self.send_alert(GpuEvent::GovernorAction { gpu_index, clock_limit_mhz, tdp_watts, reason }).await;
+        }
+
+        Ok(())
+    }
+
+    /// Compute the smoothed, hysteresis- and slew-limited fan speed for `gpu_index` at
+    /// `temperature`, creating a new [`FanController`] for the GPU on first use. This is what
+    /// the monitoring loop should call instead of `curve.calculate_fan_speed` directly, to avoid
+    /// the fan hunting up and down when the temperature hovers on a curve breakpoint.
+    pub fn compute_fan_speed(&mut self, gpu_index: u32, curve: &FanCurve, temperature: u32) -> u32 {
+        let controller = self
+            .fan_controllers
+            .entry(gpu_index)
+            .or_insert_with(|| FanController::new(curve.clone(), 3, 15));
+        controller.update(temperature)
+    }
+
+    /// Decide the max clock to apply for `gpu_index` this tick, given its current power draw
+    /// and floor clock, creating a new [`ReclockController`] for the GPU on first use. Returns
+    /// `None` when the table (or boost clock) doesn't clear the guard margin above the floor
+    /// clock, in which case the caller should leave the GPU's clock alone this tick.
+    pub fn compute_max_clock(
+        &mut self,
+        gpu_index: u32,
+        table: &FreqScalingTable,
+        power_draw_watts: u32,
+        current_min_clock_mhz: u32,
+        boost_mode: bool,
+    ) -> Option<u32> {
+        let controller = self
+            .reclock_controllers
+            .entry(gpu_index)
+            .or_insert_with(|| ReclockController::new(table.clone()));
+        controller.update(power_draw_watts, current_min_clock_mhz, boost_mode)
+    }
+
     /// Update alert configuration
     pub fn set_alert_config(&mut self, config: AlertConfig) {
         self.alert_config = config;
@@ -213,6 +502,44 @@ impl GpuMonitor {
         Ok(())
     }
 
+    /// Record the current per-process snapshot for a GPU, replacing whatever was recorded
+    /// previously. Unlike [`Self::record_metrics`] this isn't accumulated into a history: callers
+    /// (e.g. [`Self::top_memory_consumers`]) only ever want the latest attribution, not a time
+    /// series of it.
+    pub fn record_process_metrics(&mut self, gpu_index: u32, processes: &[GpuProcess]) {
+        self.process_metrics.insert(gpu_index, processes.to_vec());
+    }
+
+    /// The `n` processes using the most GPU memory on `gpu_index`, highest first, from the most
+    /// recent [`Self::record_process_metrics`] snapshot. Processes the driver couldn't report
+    /// memory usage for sort last, ahead only of nothing.
+    pub fn top_memory_consumers(&self, gpu_index: u32, n: usize) -> Vec<&GpuProcess> {
+        let Some(processes) = self.process_metrics.get(&gpu_index) else {
+            return Vec::new();
+        };
+
+        let mut ranked: Vec<&GpuProcess> = processes.iter().collect();
+        ranked.sort_by_key(|p| std::cmp::Reverse(p.used_memory.unwrap_or(0)));
+        ranked.truncate(n);
+        ranked
+    }
+
+    /// The `n` processes with the highest streaming-multiprocessor utilization on `gpu_index`,
+    /// highest first, from the most recent [`Self::record_process_metrics`] snapshot. Complements
+    /// [`Self::top_memory_consumers`]'s memory-based ranking with a compute-based one, so an
+    /// operator can see which workload is actually busy rather than just which one is camping on
+    /// VRAM.
+    pub fn top_processes(&self, gpu_index: u32, n: usize) -> Vec<&GpuProcess> {
+        let Some(processes) = self.process_metrics.get(&gpu_index) else {
+            return Vec::new();
+        };
+
+        let mut ranked: Vec<&GpuProcess> = processes.iter().collect();
+        ranked.sort_by_key(|p| std::cmp::Reverse(p.sm_utilization.unwrap_or(0)));
+        ranked.truncate(n);
+        ranked
+    }
+
     /// Check and trigger alerts based on current status
     async fn check_alerts(&mut self, gpu_index: u32, status: &GpuStatus) -> Result<()> {
         // Temperature alerts
@@ -232,15 +559,18 @@ impl GpuMonitor {
             }
         }
 
-        // Power alerts
+        // Power alerts. Skipped entirely when the GPU has no readable power limit (e.g. Apple
+        // Silicon's integrated GPU) rather than measuring a percentage against a fake one.
         if self.alert_config.enable_power_alerts {
-            let power_percentage = (status.power_draw * 100) / status.power_limit.max(1);
-            if power_percentage >= self.alert_config.power_usage_warning {
-                self.send_alert(GpuEvent::PowerAlert {
-                    gpu_index,
-                    power_draw: status.power_draw,
-                    power_limit: status.power_limit,
-                }).await;
+            if let Some(power_limit) = status.power_limit.filter(|&l| l > 0) {
+                let power_percentage = (status.power_draw * 100) / power_limit;
+                if power_percentage >= self.alert_config.power_usage_warning {
+                    self.send_alert(GpuEvent::PowerAlert {
+                        gpu_index,
+                        power_draw: status.power_draw,
+                        power_limit,
+                    }).await;
+                }
             }
         }
 
@@ -458,63 +788,71 @@ impl GpuMonitor {
             anomalies.push(anomaly);
         }
 
+        // Runaway process detection
+        if let Some(anomaly) = self.detect_runaway_process(history, gpu_index) {
+            anomalies.push(anomaly);
+        }
+
+        // Memory leak detection
+        if let Some(anomaly) = self.detect_memory_leak(&recent_metrics, gpu_index) {
+            anomalies.push(anomaly);
+        }
+
         anomalies
     }
 
-    /// Detect temperature spikes
+    /// Detect temperature spikes. Self-calibrates off [`Self::ewma_and_mad_baseline`] instead of
+    /// the fixed `avg + 20.0` / `85.0` multipliers this used to gate on, so GPU classes that idle
+    /// hot (or run cool under load) don't misfire.
     fn detect_temperature_spike(&self, metrics: &[&MetricsPoint], gpu_index: u32) -> Option<Anomaly> {
-        let temperatures: Vec<u32> = metrics.iter().map(|m| m.temperature).collect();
-        let avg_temp = temperatures.iter().sum::<u32>() as f64 / temperatures.len() as f64;
-        let max_temp = *temperatures.iter().max().unwrap() as f64;
+        let temperatures: Vec<f64> = metrics.iter().map(|m| m.temperature as f64).collect();
+        let baseline = self.ewma_and_mad_baseline(&temperatures)?;
+        let k = self.ewma_config.k;
 
-        // Detect if max temperature is significantly higher than average
-        if max_temp > avg_temp + 20.0 && max_temp > 85.0 {
-            return Some(Anomaly {
-                gpu_index,
-                anomaly_type: AnomalyType::TemperatureSpike,
-                severity: if max_temp > 95.0 {
-                    AnomalySeverity::Critical
-                } else if max_temp > 90.0 {
-                    AnomalySeverity::High
-                } else {
-                    AnomalySeverity::Medium
-                },
-                description: format!("Temperature spike detected: {}°C (avg: {:.1}°C)", max_temp, avg_temp),
-                detected_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
-                current_value: max_temp,
-                expected_range: (avg_temp - 10.0, avg_temp + 10.0),
-            });
+        if baseline.z_score <= k || (self.ewma_config.mad_agreement && baseline.modified_z_score <= k) {
+            return None;
         }
 
-        None
+        Some(Anomaly {
+            gpu_index,
+            metric: "temperature".to_string(),
+            anomaly_type: AnomalyType::TemperatureSpike,
+            severity: baseline.severity(k),
+            description: format!(
+                "Temperature spike detected: {:.1}°C (baseline {:.1}°C ± {:.1})",
+                baseline.current, baseline.mean, k * baseline.std_dev
+            ),
+            detected_at: metrics.last().unwrap().timestamp,
+            current_value: baseline.current,
+            expected_range: baseline.expected_range(k),
+        })
     }
 
-    /// Detect power drops
+    /// Detect power drops. Self-calibrates off [`Self::ewma_and_mad_baseline`] instead of the
+    /// fixed `baseline_avg * 0.5` multiplier this used to gate on, so low-power GPU classes don't
+    /// misfire.
     fn detect_power_drop(&self, metrics: &[&MetricsPoint], gpu_index: u32) -> Option<Anomaly> {
-        if metrics.len() < 20 {
-            return None;
-        }
-
-        let recent_power: Vec<u32> = metrics.iter().rev().take(5).map(|m| m.power_draw).collect();
-        let baseline_power: Vec<u32> = metrics.iter().take(10).map(|m| m.power_draw).collect();
-
-        let recent_avg = recent_power.iter().sum::<u32>() as f64 / recent_power.len() as f64;
-        let baseline_avg = baseline_power.iter().sum::<u32>() as f64 / baseline_power.len() as f64;
+        let power: Vec<f64> = metrics.iter().map(|m| m.power_draw as f64).collect();
+        let baseline = self.ewma_and_mad_baseline(&power)?;
+        let k = self.ewma_config.k;
 
-        // Detect significant power drop
-        if baseline_avg > 100.0 && recent_avg < baseline_avg * 0.5 {
-            return Some(Anomaly {
-                gpu_index,
-                anomaly_type: AnomalyType::PowerDrop,
-                severity: AnomalySeverity::Medium,
-                description: format!("Power drop detected: {:.1}W (expected: {:.1}W)", recent_avg, baseline_avg),
-                detected_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
-                current_value: recent_avg,
-                expected_range: (baseline_avg * 0.8, baseline_avg * 1.2),
-            });
+        if baseline.z_score >= -k || (self.ewma_config.mad_agreement && baseline.modified_z_score >= -k) {
+            return None;
         }
 
-        None
+        Some(Anomaly {
+            gpu_index,
+            metric: "power_draw".to_string(),
+            anomaly_type: AnomalyType::PowerDrop,
+            severity: baseline.severity(k),
+            description: format!(
+                "Power drop detected: {:.1}W (baseline {:.1}W ± {:.1})",
+                baseline.current, baseline.mean, k * baseline.std_dev
+            ),
+            detected_at: metrics.last().unwrap().timestamp,
+            current_value: baseline.current,
+            expected_range: baseline.expected_range(k),
+        })
     }
 
     /// Detect stuck utilization
@@ -532,6 +870,7 @@ impl GpuMonitor {
         if all_same && (first_util == 0 || first_util == 100) {
             return Some(Anomaly {
                 gpu_index,
+                metric: "utilization_gpu".to_string(),
                 anomaly_type: AnomalyType::UtilizationStuck,
                 severity: AnomalySeverity::Medium,
                 description: format!("GPU utilization stuck at {}%", first_util),
@@ -544,30 +883,294 @@ impl GpuMonitor {
         None
     }
 
-    /// Detect clock drift
+    /// Detect clock drift. Self-calibrates off [`Self::ewma_and_mad_baseline`] instead of the
+    /// fixed `avg_clock * 0.7` multiplier this used to gate on, so GPU classes with a wide
+    /// boost-to-base clock spread don't misfire.
     fn detect_clock_drift(&self, metrics: &[&MetricsPoint], gpu_index: u32) -> Option<Anomaly> {
-        if metrics.len() < 20 {
+        let clocks: Vec<f64> = metrics.iter().map(|m| m.clock_graphics as f64).collect();
+        let baseline = self.ewma_and_mad_baseline(&clocks)?;
+        let k = self.ewma_config.k;
+
+        if baseline.z_score >= -k || (self.ewma_config.mad_agreement && baseline.modified_z_score >= -k) {
             return None;
         }
 
-        let graphics_clocks: Vec<u32> = metrics.iter().map(|m| m.clock_graphics).collect();
-        let avg_clock = graphics_clocks.iter().sum::<u32>() as f64 / graphics_clocks.len() as f64;
-        let min_clock = *graphics_clocks.iter().min().unwrap() as f64;
+        Some(Anomaly {
+            gpu_index,
+            metric: "clock_graphics".to_string(),
+            anomaly_type: AnomalyType::ClockDrift,
+            severity: baseline.severity(k),
+            description: format!(
+                "Clock drift detected: {:.0}MHz (baseline {:.0}MHz ± {:.0})",
+                baseline.current, baseline.mean, k * baseline.std_dev
+            ),
+            detected_at: metrics.last().unwrap().timestamp,
+            current_value: baseline.current,
+            expected_range: baseline.expected_range(k),
+        })
+    }
 
-        // Detect significant clock drop
-        if avg_clock > 1000.0 && min_clock < avg_clock * 0.7 {
-            return Some(Anomaly {
-                gpu_index,
-                anomaly_type: AnomalyType::ClockDrift,
-                severity: AnomalySeverity::Medium,
-                description: format!("Clock drift detected: {:.0}MHz (expected: {:.0}MHz)", min_clock, avg_clock),
-                detected_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
-                current_value: min_clock,
-                expected_range: (avg_clock * 0.9, avg_clock * 1.1),
+    /// Online baseline check shared by [`GpuMonitor::detect_temperature_spike`],
+    /// [`GpuMonitor::detect_power_drop`], and [`GpuMonitor::detect_clock_drift`]: an
+    /// exponentially-weighted mean/variance (`mean_t = α·x_t + (1-α)·mean_{t-1}`,
+    /// `var_t = (1-α)·(var_{t-1} + α·(x_t - mean_{t-1})²)`) built from every point except the
+    /// last, tested against the last point via its z-score, plus the median/MAD-based modified
+    /// z-score (`0.6745·(x-median)/MAD`) over the whole window for robustness against
+    /// heavy-tailed series. Returns `None` when there are fewer than two points to baseline off
+    /// of.
+    fn ewma_and_mad_baseline(&self, values: &[f64]) -> Option<BaselineCheck> {
+        if values.len() < 2 {
+            return None;
+        }
+
+        let alpha = self.ewma_config.alpha;
+        let mut mean = values[0];
+        let mut variance = 0.0_f64;
+        for &x in &values[1..values.len() - 1] {
+            let prior_mean = mean;
+            mean = alpha * x + (1.0 - alpha) * prior_mean;
+            variance = (1.0 - alpha) * (variance + alpha * (x - prior_mean).powi(2));
+        }
+
+        let current = *values.last().unwrap();
+        let std_dev = variance.sqrt();
+        let diff = current - mean;
+        let z_score = if std_dev > f64::EPSILON {
+            diff / std_dev
+        } else if diff.abs() > f64::EPSILON {
+            f64::INFINITY * diff.signum()
+        } else {
+            0.0
+        };
+
+        let median = median_of(values);
+        let mad = median_of(&values.iter().map(|v| (v - median).abs()).collect::<Vec<f64>>());
+        let modified_z_score = if mad > f64::EPSILON { 0.6745 * (current - median) / mad } else { 0.0 };
+
+        Some(BaselineCheck { current, mean, std_dev, z_score, modified_z_score })
+    }
+
+    /// Detect a single process dominating `gpu_index`: either by its VRAM share of
+    /// `memory_total` on the most recent sample, or by holding sustained
+    /// `alert_config.utilization_sustained_threshold`+ SM utilization for the entirety of
+    /// `alert_config.utilization_sustained_duration`. This surfaces *which* PID caused a
+    /// `VramAlert` or sustained-utilization alert rather than just that one occurred; see
+    /// [`Self::top_processes`] for the same accounting without the anomaly threshold.
+    fn detect_runaway_process(&self, history: &VecDeque<MetricsPoint>, gpu_index: u32) -> Option<Anomaly> {
+        let latest = history.back()?;
+
+        if let Some(processes) = &latest.processes {
+            if let Some(worst) = processes.iter().max_by_key(|p| p.used_memory.unwrap_or(0)) {
+                if let Some(used_memory) = worst.used_memory {
+                    let share = used_memory as f64 / latest.memory_total.max(1) as f64;
+                    if share >= self.alert_config.runaway_process_memory_fraction as f64 {
+                        return Some(Anomaly {
+                            gpu_index,
+                            metric: "process_memory_share".to_string(),
+                            anomaly_type: AnomalyType::RunawayProcess,
+                            severity: if share >= 0.99 { AnomalySeverity::Critical } else { AnomalySeverity::High },
+                            description: format!(
+                                "Process {} is using {:.1}% of GPU memory",
+                                worst.pid,
+                                share * 100.0
+                            ),
+                            detected_at: latest.timestamp,
+                            current_value: share,
+                            expected_range: (0.0, self.alert_config.runaway_process_memory_fraction as f64),
+                        });
+                    }
+                }
+            }
+        }
+
+        let threshold_time =
+            latest.timestamp.saturating_sub(self.alert_config.utilization_sustained_duration.as_secs());
+        let window: Vec<&MetricsPoint> = history.iter().rev().take_while(|p| p.timestamp >= threshold_time).collect();
+        if window.len() < 5 {
+            return None;
+        }
+
+        let mut sustained_hot_pids: Option<std::collections::HashSet<u32>> = None;
+        for point in &window {
+            let processes = point.processes.as_ref()?;
+            let hot_pids: std::collections::HashSet<u32> = processes
+                .iter()
+                .filter(|p| p.sm_utilization.unwrap_or(0) >= self.alert_config.utilization_sustained_threshold)
+                .map(|p| p.pid)
+                .collect();
+
+            sustained_hot_pids = Some(match sustained_hot_pids {
+                Some(existing) => existing.intersection(&hot_pids).copied().collect(),
+                None => hot_pids,
             });
+            if sustained_hot_pids.as_ref().is_some_and(|pids| pids.is_empty()) {
+                return None;
+            }
         }
 
-        None
+        let pid = *sustained_hot_pids?.iter().next()?;
+        Some(Anomaly {
+            gpu_index,
+            metric: "process_sm_utilization".to_string(),
+            anomaly_type: AnomalyType::RunawayProcess,
+            severity: AnomalySeverity::High,
+            description: format!(
+                "Process {} held sustained >={}% SM utilization for {:?}",
+                pid, self.alert_config.utilization_sustained_threshold, self.alert_config.utilization_sustained_duration
+            ),
+            detected_at: latest.timestamp,
+            current_value: self.alert_config.utilization_sustained_threshold as f64,
+            expected_range: (0.0, self.alert_config.utilization_sustained_threshold as f64),
+        })
+    }
+
+    /// Detect a VRAM leak: fit a least-squares line to `memory_used` vs. `timestamp` over the
+    /// window and flag it when the slope clears `memory_leak_slope_threshold_bytes_per_sec` with
+    /// a high enough R² (`memory_leak_min_r_squared`) to trust it as monotone growth rather than
+    /// noise, AND `utilization_gpu` over the same window is flat or declining — so growth that's
+    /// just explained by the GPU doing more work doesn't get flagged as a leak. `current_value` is
+    /// the regression's projected VRAM at the window's end; `expected_range` is the regression's
+    /// baseline at the window's start, plus/minus `memory_leak_baseline_tolerance_fraction` of
+    /// `memory_total`. Severity scales with the estimated time to exhaust `memory_total` at the
+    /// current slope.
+    fn detect_memory_leak(&self, metrics: &[&MetricsPoint], gpu_index: u32) -> Option<Anomaly> {
+        if metrics.len() < 2 {
+            return None;
+        }
+
+        let timestamps: Vec<f64> = metrics.iter().map(|m| m.timestamp as f64).collect();
+        let memory_used: Vec<f64> = metrics.iter().map(|m| m.memory_used as f64).collect();
+
+        let (slope, intercept, r_squared) = linear_regression(&timestamps, &memory_used);
+        if slope < self.alert_config.memory_leak_slope_threshold_bytes_per_sec
+            || r_squared < self.alert_config.memory_leak_min_r_squared
+        {
+            return None;
+        }
+
+        let utilizations: Vec<f64> = metrics.iter().map(|m| m.utilization_gpu as f64).collect();
+        let (utilization_slope, _, _) = linear_regression(&timestamps, &utilizations);
+        if utilization_slope > 0.0 {
+            return None; // growth is plausibly explained by heavier work, not a leak
+        }
+
+        let t_start = *timestamps.first()?;
+        let t_end = *timestamps.last()?;
+        let projected = intercept + slope * t_end;
+        let baseline = intercept + slope * t_start;
+        let memory_total = metrics.last()?.memory_total.max(1) as f64;
+        let tolerance = memory_total * self.alert_config.memory_leak_baseline_tolerance_fraction as f64;
+
+        let remaining = (memory_total - projected).max(0.0);
+        let time_to_oom_secs = remaining / slope;
+        let severity = if time_to_oom_secs < 3600.0 {
+            AnomalySeverity::Critical
+        } else if time_to_oom_secs < 86_400.0 {
+            AnomalySeverity::High
+        } else {
+            AnomalySeverity::Medium
+        };
+
+        Some(Anomaly {
+            gpu_index,
+            metric: "memory_used".to_string(),
+            anomaly_type: AnomalyType::MemoryLeak,
+            severity,
+            description: format!(
+                "VRAM growing at {}/s (R²={:.2}), projected to exhaust {} in {:.0}s",
+                format_bytes(slope.round() as u64),
+                r_squared,
+                format_bytes(memory_total as u64),
+                time_to_oom_secs
+            ),
+            detected_at: metrics.last()?.timestamp,
+            current_value: projected,
+            expected_range: (baseline - tolerance, baseline + tolerance),
+        })
+    }
+
+    /// Detect anomalies via a per-metric exponentially-weighted moving average and variance,
+    /// independently for temperature, power draw, and GPU utilization. Unlike
+    /// [`Self::detect_anomalies`]'s fixed heuristics, the expected range here adapts to each
+    /// GPU's own recent behavior: `mean_t = α·x_t + (1-α)·mean_{t-1}` and
+    /// `var_t = (1-α)·(var_{t-1} + α·(x_t - mean_{t-1})²)`, flagging `x_t` when it falls outside
+    /// `mean_{t-1} ± k·sqrt(var_{t-1})`. The first `window` points only warm up the estimator
+    /// (no anomalies are emitted until the window has elapsed), so a short history can't produce
+    /// false positives before the estimate has settled. Tune `α`/`k` via [`Self::set_ewma_config`].
+    #[instrument(skip(self))]
+    pub fn detect_statistical_anomalies(&self, gpu_index: u32, window: usize) -> Vec<Anomaly> {
+        let Some(history) = self.metrics_history.get(&gpu_index) else {
+            return Vec::new();
+        };
+
+        let mut anomalies = Vec::new();
+        anomalies.extend(self.detect_ewma_outliers(gpu_index, history, window, "temperature", |p| p.temperature as f64));
+        anomalies.extend(self.detect_ewma_outliers(gpu_index, history, window, "power_draw", |p| p.power_draw as f64));
+        anomalies.extend(self.detect_ewma_outliers(gpu_index, history, window, "utilization_gpu", |p| p.utilization_gpu as f64));
+        anomalies
+    }
+
+    /// Run the EWMA control-band check from [`Self::detect_statistical_anomalies`] for a single
+    /// metric, extracted from each [`MetricsPoint`] by `extract`.
+    fn detect_ewma_outliers(
+        &self,
+        gpu_index: u32,
+        history: &VecDeque<MetricsPoint>,
+        window: usize,
+        metric: &str,
+        extract: impl Fn(&MetricsPoint) -> f64,
+    ) -> Vec<Anomaly> {
+        let alpha = self.ewma_config.alpha;
+        let k = self.ewma_config.k;
+        let mut anomalies = Vec::new();
+
+        let mut mean = 0.0_f64;
+        let mut variance = 0.0_f64;
+
+        for (i, point) in history.iter().enumerate() {
+            let observed = extract(point);
+
+            if i >= window {
+                let std_dev = variance.sqrt();
+                let expected_range = (mean - k * std_dev, mean + k * std_dev);
+
+                if observed < expected_range.0 || observed > expected_range.1 {
+                    let z_score = if std_dev > 0.0 { (observed - mean).abs() / std_dev } else { f64::INFINITY };
+                    let severity = if z_score > k * 2.0 {
+                        AnomalySeverity::Critical
+                    } else if z_score > k * 1.5 {
+                        AnomalySeverity::High
+                    } else {
+                        AnomalySeverity::Medium
+                    };
+
+                    anomalies.push(Anomaly {
+                        gpu_index,
+                        metric: metric.to_string(),
+                        anomaly_type: AnomalyType::StatisticalOutlier,
+                        severity,
+                        description: format!(
+                            "{metric} out of EWMA control band: {observed:.2} (expected {:.2}..{:.2})",
+                            expected_range.0, expected_range.1
+                        ),
+                        detected_at: point.timestamp,
+                        current_value: observed,
+                        expected_range,
+                    });
+                }
+            }
+
+            if i == 0 {
+                mean = observed;
+                variance = 0.0;
+            } else {
+                let prior_mean = mean;
+                mean = alpha * observed + (1.0 - alpha) * prior_mean;
+                variance = (1.0 - alpha) * (variance + alpha * (observed - prior_mean).powi(2));
+            }
+        }
+
+        anomalies
     }
 
     /// Get monitoring statistics
@@ -613,6 +1216,35 @@ mod tests {
         assert!(config.enable_thermal_alerts);
     }
 
+    #[tokio::test]
+    async fn test_check_alerts_skips_power_alert_when_power_limit_is_unreported() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        let mut status = steady_gpu_status(70);
+        status.power_limit = None;
+        status.power_draw = 500; // would blow past any sane limit, if one were assumed
+
+        monitor.record_metrics(0, &status).await.unwrap();
+
+        assert!(matches!(rx.try_recv(), Err(tokio::sync::broadcast::error::TryRecvError::Empty)));
+    }
+
+    #[tokio::test]
+    async fn test_check_alerts_fires_power_alert_when_power_limit_is_known() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        let mut status = steady_gpu_status(70);
+        status.power_limit = Some(300);
+        status.power_draw = 290; // ~96%, past the default 90% warning threshold
+
+        monitor.record_metrics(0, &status).await.unwrap();
+
+        let event = rx.try_recv().unwrap();
+        assert!(matches!(event, GpuEvent::PowerAlert { power_limit: 300, .. }));
+    }
+
     #[test]
     fn test_metrics_point_creation() {
         let metrics = MetricsPoint {
@@ -623,10 +1255,12 @@ mod tests {
             utilization_memory: 70,
             memory_used: 4_294_967_296, // 4GB
             clock_graphics: 1500,
-            clock_memory: 7000,
+            clock_memory: Some(7000),
             fan_speed: Some(60),
+            memory_total: 8_589_934_592, // 8GB
+            processes: None,
         };
-        
+
         assert_eq!(metrics.temperature, 75);
         assert_eq!(metrics.utilization_gpu, 80);
     }
@@ -640,10 +1274,191 @@ mod tests {
         assert_eq!(monitor.stats.total_metrics_collected, 0);
     }
 
+    #[tokio::test]
+    async fn test_compute_max_clock_reuses_controller_per_gpu() {
+        let (tx, _rx) = broadcast::channel(100);
+        let mut monitor = GpuMonitor::new(tx);
+        let table = FreqScalingTable::default_table();
+
+        let clock = monitor.compute_max_clock(0, &table, 50, 500, false);
+        assert_eq!(clock, Some(1200));
+
+        // Second call for the same GPU reuses its controller rather than re-reading `table`
+        let clock = monitor.compute_max_clock(0, &table, 50, 1050, false);
+        assert_eq!(clock, None); // Inside the guard margin now
+    }
+
+    /// Records every `set_clock_limit`/`set_power_limit` call `GpuMonitor::run_governor` makes,
+    /// so tests can assert on what the governor actually applied rather than just the event it
+    /// emitted.
+    #[derive(Default)]
+    struct MockController {
+        clock_calls: std::sync::Mutex<Vec<(u32, u32)>>,
+        power_calls: std::sync::Mutex<Vec<(u32, u32)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl GpuController for MockController {
+        async fn set_clock_limit(&self, index: u32, mhz: u32) -> Result<()> {
+            self.clock_calls.lock().unwrap().push((index, mhz));
+            Ok(())
+        }
+
+        async fn set_power_limit(&self, index: u32, watts: u32) -> Result<()> {
+            self.power_calls.lock().unwrap().push((index, watts));
+            Ok(())
+        }
+    }
+
+    fn test_governor_config() -> GovernorConfig {
+        GovernorConfig {
+            clock_limits: crate::MinMax { min: 800, max: 2000 },
+            tdp_limits: crate::MinMax { min: 100, max: 300 },
+            step: 100,
+            recovery_hysteresis_secs: 120,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_governor_is_a_noop_without_a_configured_governor() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+        let controller = MockController::default();
+
+        monitor.run_governor(0, &steady_gpu_status(95), 1_000, &controller).await.unwrap();
+
+        assert!(controller.clock_calls.lock().unwrap().is_empty());
+        assert!(matches!(rx.try_recv(), Err(tokio::sync::broadcast::error::TryRecvError::Empty)));
+    }
+
+    #[tokio::test]
+    async fn test_run_governor_steps_clock_and_tdp_down_on_critical_temperature() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+        monitor.set_governor_config(Some(test_governor_config()));
+        let controller = MockController::default();
+
+        // steady_gpu_status's default temperature_critical threshold is 90
+        monitor.run_governor(0, &steady_gpu_status(95), 1_000, &controller).await.unwrap();
+
+        assert_eq!(*controller.clock_calls.lock().unwrap(), vec![(0, 1900)]);
+        assert_eq!(*controller.power_calls.lock().unwrap(), vec![(0, 200)]);
+        let event = rx.try_recv().unwrap();
+        assert!(matches!(
+            event,
+            GpuEvent::GovernorAction { clock_limit_mhz: 1900, tdp_watts: 200, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_run_governor_recovers_caps_after_the_hysteresis_window_elapses() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+        monitor.set_governor_config(Some(test_governor_config()));
+        let controller = MockController::default();
+
+        monitor.run_governor(0, &steady_gpu_status(95), 1_000, &controller).await.unwrap();
+        let _ = rx.try_recv(); // drain the step-down event
+
+        // Below temperature_warning (80) but the hysteresis window (120s) hasn't elapsed yet
+        monitor.run_governor(0, &steady_gpu_status(70), 1_050, &controller).await.unwrap();
+        assert!(matches!(rx.try_recv(), Err(tokio::sync::broadcast::error::TryRecvError::Empty)));
+
+        // Now it has
+        monitor.run_governor(0, &steady_gpu_status(70), 1_130, &controller).await.unwrap();
+        assert_eq!(*controller.clock_calls.lock().unwrap(), vec![(0, 1900), (0, 2000)]);
+        assert_eq!(*controller.power_calls.lock().unwrap(), vec![(0, 200), (0, 300)]);
+        let event = rx.try_recv().unwrap();
+        assert!(matches!(
+            event,
+            GpuEvent::GovernorAction { clock_limit_mhz: 2000, tdp_watts: 300, .. }
+        ));
+    }
+
+    fn test_process(pid: u32, name: &str, used_memory: Option<u64>) -> GpuProcess {
+        GpuProcess {
+            pid,
+            name: name.to_string(),
+            proc_type: crate::GpuProcessType::Compute,
+            used_memory,
+            sm_utilization: None,
+            enc_dec_utilization: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_top_memory_consumers_ranks_by_used_memory_descending() {
+        let (tx, _rx) = broadcast::channel(100);
+        let mut monitor = GpuMonitor::new(tx);
+
+        let processes = vec![
+            test_process(1, "small", Some(100)),
+            test_process(2, "big", Some(900)),
+            test_process(3, "unknown", None),
+            test_process(4, "medium", Some(400)),
+        ];
+        monitor.record_process_metrics(0, &processes);
+
+        let top = monitor.top_memory_consumers(0, 2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].pid, 2);
+        assert_eq!(top[1].pid, 4);
+    }
+
+    #[test]
+    fn test_top_memory_consumers_on_unknown_gpu_is_empty() {
+        let (tx, _rx) = broadcast::channel(100);
+        let monitor = GpuMonitor::new(tx);
+        assert!(monitor.top_memory_consumers(0, 5).is_empty());
+    }
+
+    #[test]
+    fn test_record_process_metrics_replaces_the_previous_snapshot() {
+        let (tx, _rx) = broadcast::channel(100);
+        let mut monitor = GpuMonitor::new(tx);
+
+        monitor.record_process_metrics(0, &[test_process(1, "first", Some(100))]);
+        monitor.record_process_metrics(0, &[test_process(2, "second", Some(200))]);
+
+        let top = monitor.top_memory_consumers(0, 10);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].pid, 2);
+    }
+
+    fn process_with_sm_utilization(pid: u32, sm_utilization: u32) -> GpuProcess {
+        GpuProcess {
+            pid,
+            name: format!("pid-{pid}"),
+            proc_type: crate::GpuProcessType::Compute,
+            used_memory: None,
+            sm_utilization: Some(sm_utilization),
+            enc_dec_utilization: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_top_processes_ranks_by_sm_utilization_descending() {
+        let (tx, _rx) = broadcast::channel(100);
+        let mut monitor = GpuMonitor::new(tx);
+
+        let processes = vec![
+            process_with_sm_utilization(1, 10),
+            process_with_sm_utilization(2, 90),
+            process_with_sm_utilization(3, 50),
+        ];
+        monitor.record_process_metrics(0, &processes);
+
+        let top = monitor.top_processes(0, 2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].pid, 2);
+        assert_eq!(top[1].pid, 3);
+    }
+
     #[test]
     fn test_anomaly_severity() {
         let anomaly = Anomaly {
             gpu_index: 0,
+            metric: "temperature".to_string(),
             anomaly_type: AnomalyType::TemperatureSpike,
             severity: AnomalySeverity::High,
             description: "Test anomaly".to_string(),
@@ -651,8 +1466,352 @@ mod tests {
             current_value: 95.0,
             expected_range: (70.0, 80.0),
         };
-        
+
         assert_eq!(anomaly.severity, AnomalySeverity::High);
         assert_eq!(anomaly.anomaly_type, AnomalyType::TemperatureSpike);
     }
+
+    fn steady_gpu_status(temperature: u32) -> GpuStatus {
+        GpuStatus {
+            index: 0,
+            name: "Test GPU".to_string(),
+            vendor: crate::GpuVendor::NVIDIA,
+            gpu_type: crate::GpuType::Discrete,
+            temperature,
+            power_draw: 200,
+            power_limit: Some(300),
+            memory_used: 0,
+            memory_total: 100,
+            utilization_gpu: 50,
+            utilization_memory: 0,
+            fan_speed: None,
+            clock_graphics: 1500,
+            clock_memory: Some(7000),
+            driver_version: None,
+            pci_info: crate::PciInfo { domain: 0, bus: 0, device: 0, function: 0, vendor_id: 0, device_id: 0 },
+            power_state: crate::PowerState::Active,
+            voltage_mv: None,
+            throttle_reasons: Vec::new(),
+            ecc_errors: None,
+            processes: Vec::new(),
+            driver_bound: crate::vfio::DriverBinding::Unbound,
+            unified_memory: false,
+            mig_parent: None,
+            mig_uuid: None,
+            uuid: None,
+            serial: None,
+            board_part_number: None,
+            vbios_version: None,
+            cuda_driver_version: None,
+        }
+    }
+
+    fn gpu_status_with_processes(memory_used: u64, memory_total: u64, processes: Vec<GpuProcess>) -> GpuStatus {
+        GpuStatus { memory_used, memory_total, processes, ..steady_gpu_status(70) }
+    }
+
+    #[tokio::test]
+    async fn test_detect_runaway_process_flags_a_process_dominating_gpu_memory() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        for _ in 0..10 {
+            let status = gpu_status_with_processes(
+                950,
+                1000,
+                vec![GpuProcess {
+                    pid: 42,
+                    name: "hog".to_string(),
+                    proc_type: crate::GpuProcessType::Compute,
+                    used_memory: Some(950),
+                    sm_utilization: Some(20),
+                    enc_dec_utilization: None,
+                }],
+            );
+            monitor.record_metrics(0, &status).await.unwrap();
+        }
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(anomalies
+            .iter()
+            .any(|a| a.anomaly_type == AnomalyType::RunawayProcess && a.metric == "process_memory_share"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_runaway_process_is_quiet_when_no_process_dominates_memory() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        for _ in 0..10 {
+            let status = gpu_status_with_processes(
+                500,
+                1000,
+                vec![GpuProcess {
+                    pid: 42,
+                    name: "modest".to_string(),
+                    proc_type: crate::GpuProcessType::Compute,
+                    used_memory: Some(500),
+                    sm_utilization: Some(20),
+                    enc_dec_utilization: None,
+                }],
+            );
+            monitor.record_metrics(0, &status).await.unwrap();
+        }
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(!anomalies.iter().any(|a| a.anomaly_type == AnomalyType::RunawayProcess));
+    }
+
+    #[tokio::test]
+    async fn test_detect_runaway_process_flags_sustained_high_sm_utilization_by_one_pid() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+        monitor.set_alert_config(AlertConfig {
+            utilization_sustained_duration: Duration::from_secs(3600),
+            ..AlertConfig::default()
+        });
+
+        for _ in 0..10 {
+            let status = gpu_status_with_processes(100, 1000, vec![process_with_sm_utilization(7, 97)]);
+            monitor.record_metrics(0, &status).await.unwrap();
+        }
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(anomalies
+            .iter()
+            .any(|a| a.anomaly_type == AnomalyType::RunawayProcess && a.metric == "process_sm_utilization"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_runaway_process_is_quiet_when_the_hot_pid_changes_mid_window() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+        monitor.set_alert_config(AlertConfig {
+            utilization_sustained_duration: Duration::from_secs(3600),
+            ..AlertConfig::default()
+        });
+
+        for i in 0..10 {
+            let pid = if i < 5 { 1 } else { 2 };
+            let status = gpu_status_with_processes(100, 1000, vec![process_with_sm_utilization(pid, 97)]);
+            monitor.record_metrics(0, &status).await.unwrap();
+        }
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(!anomalies
+            .iter()
+            .any(|a| a.anomaly_type == AnomalyType::RunawayProcess && a.metric == "process_sm_utilization"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_statistical_anomalies_stays_quiet_during_warm_up() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        for _ in 0..10 {
+            monitor.record_metrics(0, &steady_gpu_status(70)).await.unwrap();
+        }
+
+        // Only 10 points have ever been recorded, all within the 20-point warm-up window, so the
+        // estimator must not have emitted anything yet even though later we'll inject an outlier.
+        assert!(monitor.detect_statistical_anomalies(0, 20).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_detect_statistical_anomalies_flags_a_temperature_spike_after_warm_up() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        for _ in 0..20 {
+            monitor.record_metrics(0, &steady_gpu_status(70)).await.unwrap();
+        }
+        monitor.record_metrics(0, &steady_gpu_status(150)).await.unwrap();
+
+        let anomalies = monitor.detect_statistical_anomalies(0, 20);
+        assert!(anomalies.iter().any(|a| a.metric == "temperature" && a.anomaly_type == AnomalyType::StatisticalOutlier));
+    }
+
+    #[tokio::test]
+    async fn test_detect_statistical_anomalies_is_quiet_on_a_stable_series() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        for _ in 0..40 {
+            monitor.record_metrics(0, &steady_gpu_status(70)).await.unwrap();
+        }
+
+        assert!(monitor.detect_statistical_anomalies(0, 20).is_empty());
+    }
+
+    #[test]
+    fn test_ewma_config_default() {
+        let config = EwmaConfig::default();
+        assert!((config.alpha - 0.3).abs() < f64::EPSILON);
+        assert!((config.k - 3.0).abs() < f64::EPSILON);
+        assert!(config.mad_agreement);
+    }
+
+    /// Alternating 68/72 temperature so the EWMA/MAD baseline has some natural jitter to work
+    /// with; a perfectly flat series makes the MAD zero, which would trivially agree with any
+    /// z-score and defeat the point of the agreement check.
+    async fn record_jittered_temperatures(monitor: &mut GpuMonitor, count: usize) {
+        for i in 0..count {
+            let temperature = if i % 2 == 0 { 68 } else { 72 };
+            monitor.record_metrics(0, &steady_gpu_status(temperature)).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_detect_temperature_spike_flags_a_jump_past_the_self_calibrated_baseline() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        record_jittered_temperatures(&mut monitor, 14).await;
+        monitor.record_metrics(0, &steady_gpu_status(150)).await.unwrap();
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(anomalies.iter().any(|a| a.metric == "temperature" && a.anomaly_type == AnomalyType::TemperatureSpike));
+    }
+
+    #[tokio::test]
+    async fn test_detect_temperature_spike_is_quiet_on_a_stable_jittered_series() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        record_jittered_temperatures(&mut monitor, 16).await;
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(!anomalies.iter().any(|a| a.anomaly_type == AnomalyType::TemperatureSpike));
+    }
+
+    #[tokio::test]
+    async fn test_detect_power_drop_flags_a_sudden_drop_past_the_self_calibrated_baseline() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        for i in 0..14 {
+            let mut jittered = steady_gpu_status(70);
+            jittered.power_draw = if i % 2 == 0 { 190 } else { 210 };
+            monitor.record_metrics(0, &jittered).await.unwrap();
+        }
+        let mut dropped = steady_gpu_status(70);
+        dropped.power_draw = 10;
+        monitor.record_metrics(0, &dropped).await.unwrap();
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(anomalies.iter().any(|a| a.metric == "power_draw" && a.anomaly_type == AnomalyType::PowerDrop));
+    }
+
+    #[tokio::test]
+    async fn test_detect_clock_drift_flags_a_sudden_drop_past_the_self_calibrated_baseline() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+
+        for i in 0..14 {
+            let mut jittered = steady_gpu_status(70);
+            jittered.clock_graphics = if i % 2 == 0 { 1480 } else { 1520 };
+            monitor.record_metrics(0, &jittered).await.unwrap();
+        }
+        let mut drifted = steady_gpu_status(70);
+        drifted.clock_graphics = 400;
+        monitor.record_metrics(0, &drifted).await.unwrap();
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(anomalies.iter().any(|a| a.metric == "clock_graphics" && a.anomaly_type == AnomalyType::ClockDrift));
+    }
+
+    #[tokio::test]
+    async fn test_detect_anomalies_requires_mad_agreement_when_enabled() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+        monitor.set_ewma_config(EwmaConfig { mad_agreement: false, ..EwmaConfig::default() });
+
+        record_jittered_temperatures(&mut monitor, 14).await;
+        monitor.record_metrics(0, &steady_gpu_status(150)).await.unwrap();
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(anomalies.iter().any(|a| a.metric == "temperature" && a.anomaly_type == AnomalyType::TemperatureSpike));
+    }
+
+    /// Inserts a fully custom [`MetricsPoint`] directly into a GPU's history, bypassing
+    /// `record_metrics`'s current-wall-clock timestamp so [`GpuMonitor::detect_memory_leak`]'s
+    /// regression-over-time tests can control the x-axis.
+    fn push_metrics_point(monitor: &mut GpuMonitor, gpu_index: u32, point: MetricsPoint) {
+        monitor.metrics_history.entry(gpu_index).or_insert_with(VecDeque::new).push_back(point);
+    }
+
+    fn memory_point(timestamp: u64, memory_used: u64, utilization_gpu: u32) -> MetricsPoint {
+        MetricsPoint {
+            timestamp,
+            temperature: 70,
+            power_draw: 200,
+            utilization_gpu,
+            utilization_memory: 50,
+            memory_used,
+            clock_graphics: 1500,
+            clock_memory: Some(7000),
+            fan_speed: Some(60),
+            memory_total: 32_000_000_000,
+            processes: None,
+        }
+    }
+
+    #[test]
+    fn test_detect_memory_leak_flags_monotone_vram_growth_with_flat_utilization() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+        for i in 0..15u64 {
+            let timestamp = now - (14 - i) * 10;
+            let memory_used = 1_000_000_000 + i * 50_000_000; // 5MB/s growth
+            push_metrics_point(&mut monitor, 0, memory_point(timestamp, memory_used, 50));
+        }
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(anomalies.iter().any(|a| a.metric == "memory_used" && a.anomaly_type == AnomalyType::MemoryLeak));
+    }
+
+    #[test]
+    fn test_detect_memory_leak_is_quiet_on_flat_vram_usage() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+        for i in 0..15u64 {
+            let timestamp = now - (14 - i) * 10;
+            push_metrics_point(&mut monitor, 0, memory_point(timestamp, 1_000_000_000, 50));
+        }
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(!anomalies.iter().any(|a| a.anomaly_type == AnomalyType::MemoryLeak));
+    }
+
+    #[test]
+    fn test_detect_memory_leak_is_quiet_when_growth_tracks_rising_utilization() {
+        let (tx, _rx) = broadcast::channel(16);
+        let mut monitor = GpuMonitor::new(tx);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+        for i in 0..15u64 {
+            let timestamp = now - (14 - i) * 10;
+            let memory_used = 1_000_000_000 + i * 50_000_000;
+            let utilization_gpu = 20 + (i * 5) as u32; // heavier work explains the growth
+            push_metrics_point(&mut monitor, 0, memory_point(timestamp, memory_used, utilization_gpu));
+        }
+
+        let anomalies = monitor.detect_anomalies(0, 60);
+        assert!(!anomalies.iter().any(|a| a.anomaly_type == AnomalyType::MemoryLeak));
+    }
+
+    #[test]
+    fn test_linear_regression_recovers_a_perfect_line() {
+        let xs = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let ys = vec![10.0, 12.0, 14.0, 16.0, 18.0];
+
+        let (slope, intercept, r_squared) = linear_regression(&xs, &ys);
+        assert!((slope - 2.0).abs() < 1e-9);
+        assert!((intercept - 10.0).abs() < 1e-9);
+        assert!((r_squared - 1.0).abs() < 1e-9);
+    }
 }
\ No newline at end of file
@@ -2,19 +2,54 @@
 
 use crate::{
     error::{GpuError, Result},
-    GpuBackend, GpuConfig, GpuStatus, GpuType, GpuVendor, PowerMode, PowerState, FanCurve, PciInfo
+    EccCounts, GpuBackend, GpuConfig, GpuProcess, GpuProcessType, GpuStatus, GpuType, GpuVendor,
+    MinMax, PowerMode, PowerState, FanCurve, PciInfo, ThrottleReason,
 };
 use async_trait::async_trait;
-use nvml_wrapper::{enum_wrappers::device::*, Nvml, Device};
+use nvml_wrapper::{
+    bitmasks::device::ThrottleReasons, enum_wrappers::device::*, enums::device::UsedGpuMemory,
+    Nvml, Device,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, info, instrument, warn};
 
+/// System-wide NVML driver/CUDA versions, fetched once per [`NvidiaBackend::detect_gpus`]/
+/// [`NvidiaBackend::get_gpu_status`] call and threaded into every
+/// [`NvidiaBackend::get_device_status`] rather than re-queried per device.
+#[derive(Debug, Clone, Default)]
+struct DriverVersions {
+    driver_version: Option<String>,
+    cuda_driver_version: Option<i32>,
+}
+
+impl DriverVersions {
+    fn query(nvml: &Nvml) -> Self {
+        Self {
+            driver_version: nvml.sys_driver_version().ok(),
+            cuda_driver_version: nvml.sys_cuda_driver_version().ok(),
+        }
+    }
+}
+
 /// NVIDIA GPU backend using NVML
 pub struct NvidiaBackend {
     nvml: Option<Nvml>,
     devices: Arc<RwLock<HashMap<u32, Device<'static>>>>,
+    /// Whether [`Self::detect_gpus`] also enumerates MIG (Multi-Instance GPU) slices on
+    /// datacenter cards that have MIG mode enabled. Defaults to `false` so workstation GPUs,
+    /// which never have MIG available, see no behavior change.
+    process_mig_devices: bool,
+    /// How often the background task spawned by [`Self::set_fan_curve`] re-evaluates the curve
+    /// against live temperature. Defaults to the same cadence as [`crate::MonitoringConfig`]'s
+    /// sample interval.
+    fan_curve_interval: Duration,
+    /// The currently-running fan-curve-tracking task per GPU index, if any, so a new
+    /// [`Self::set_fan_curve`] call or a [`Self::reset_gpu`] can stop the previous one instead of
+    /// leaving it to fight over the fan speed.
+    fan_curve_tasks: Arc<RwLock<HashMap<u32, tokio::task::JoinHandle<()>>>>,
 }
 
 impl std::fmt::Debug for NvidiaBackend {
@@ -22,6 +57,9 @@ impl std::fmt::Debug for NvidiaBackend {
         f.debug_struct("NvidiaBackend")
             .field("nvml_initialized", &self.nvml.is_some())
             .field("device_count", &"Arc<RwLock<HashMap>>")
+            .field("process_mig_devices", &self.process_mig_devices)
+            .field("fan_curve_interval", &self.fan_curve_interval)
+            .field("fan_curve_tasks", &"Arc<RwLock<HashMap>>")
             .finish()
     }
 }
@@ -32,11 +70,25 @@ impl NvidiaBackend {
         Ok(Self {
             nvml: None,
             devices: Arc::new(RwLock::new(HashMap::new())),
+            process_mig_devices: false,
+            fan_curve_interval: Duration::from_secs(1),
+            fan_curve_tasks: Arc::new(RwLock::new(HashMap::new())),
         })
     }
 
-    /// Get GPU status using NVML device
-    fn get_device_status(device: &Device, index: u32) -> Result<GpuStatus> {
+    /// Enable or disable MIG (Multi-Instance GPU) slice enumeration in [`Self::detect_gpus`].
+    pub fn set_process_mig_devices(&mut self, enabled: bool) {
+        self.process_mig_devices = enabled;
+    }
+
+    /// Override how often [`Self::set_fan_curve`]'s background task re-evaluates the curve.
+    pub fn set_fan_curve_interval(&mut self, interval: Duration) {
+        self.fan_curve_interval = interval;
+    }
+
+    /// Get GPU status using NVML device. `versions` carries the system-wide driver/CUDA
+    /// versions so they don't need to be re-queried from `Nvml` for every device.
+    fn get_device_status(device: &Device, index: u32, versions: &DriverVersions) -> Result<GpuStatus> {
         let name = device.name().map_err(GpuError::from)?;
         
         // Temperature
@@ -69,9 +121,15 @@ impl NvidiaBackend {
             .clock_info(Clock::Memory)
             .map_err(GpuError::from)? as u32;
         
-        // Driver version - get from NVML instance instead
-        let driver_version = Some("Unknown".to_string()); // Would get from nvml.sys_driver_version()
-        
+        let driver_version = versions.driver_version.clone();
+
+        // Stable identity, durable across reboots and index reassignment; `None` on cards or
+        // drivers that don't support the underlying query rather than a hard failure
+        let uuid = device.uuid().ok();
+        let serial = device.serial().ok();
+        let board_part_number = device.board_part_number().ok();
+        let vbios_version = device.vbios_version().ok();
+
         // PCI information
         let pci_info = device.pci_info().map_err(GpuError::from)?;
         let pci_info = PciInfo {
@@ -97,6 +155,10 @@ impl NvidiaBackend {
             PowerState::Idle
         };
 
+        let throttle_reasons = Self::get_throttle_reasons(device);
+        let ecc_errors = Self::get_ecc_errors(device);
+        let processes = Self::get_gpu_processes(device);
+
         Ok(GpuStatus {
             index,
             name,
@@ -104,20 +166,226 @@ impl NvidiaBackend {
             gpu_type,
             temperature,
             power_draw: power_draw as u32,
-            power_limit: power_limit as u32,
+            power_limit: Some(power_limit as u32),
             memory_used: mem_info.used,
             memory_total: mem_info.total,
             utilization_gpu: utilization.gpu as u32,
             utilization_memory: utilization.memory as u32,
             fan_speed,
             clock_graphics,
-            clock_memory,
+            clock_memory: Some(clock_memory),
             driver_version,
             pci_info,
             power_state,
+            voltage_mv: None, // Not exposed by NVML on consumer cards
+            throttle_reasons,
+            ecc_errors,
+            processes,
+            driver_bound: crate::vfio::DriverBinding::Unbound, // overwritten centrally by GpuManager
+            unified_memory: false, // NVML-tracked cards always own dedicated VRAM
+            mig_parent: None, // set by `get_mig_slices` for MIG slice entries
+            mig_uuid: None,
+            uuid,
+            serial,
+            board_part_number,
+            vbios_version,
+            cuda_driver_version: versions.cuda_driver_version,
         })
     }
 
+    /// Enumerate MIG (Multi-Instance GPU) slices under `parent_device`, if MIG mode is enabled on
+    /// it, as independent [`GpuStatus`] entries. Each slice's memory and utilization come from its
+    /// own MIG device handle rather than the parent's, since a slice only owns a fraction of the
+    /// physical GPU. `index` is only unique per parent (NVML assigns small per-parent slice
+    /// indices), so `mig_uuid` is the identifier callers should use to tell slices apart globally.
+    fn get_mig_slices(parent_device: &Device, parent_index: u32, versions: &DriverVersions) -> Vec<GpuStatus> {
+        let mig_enabled = match parent_device.is_mig_device_enabled() {
+            Ok(enabled) => enabled,
+            Err(e) => {
+                debug!("GPU {} does not report MIG mode: {}", parent_index, e);
+                return Vec::new();
+            }
+        };
+        if !mig_enabled {
+            return Vec::new();
+        }
+
+        let slice_count = match parent_device.max_mig_device_count() {
+            Ok(count) => count,
+            Err(e) => {
+                warn!("Failed to read MIG device count for GPU {}: {}", parent_index, e);
+                return Vec::new();
+            }
+        };
+
+        let mut slices = Vec::new();
+        for slice_index in 0..slice_count {
+            let mig_device = match parent_device.mig_device_handle_by_index(slice_index) {
+                Ok(device) => device,
+                Err(e) => {
+                    debug!("GPU {} MIG slice {} not present: {}", parent_index, slice_index, e);
+                    continue;
+                }
+            };
+
+            let uuid = match mig_device.uuid() {
+                Ok(uuid) => uuid,
+                Err(e) => {
+                    warn!(
+                        "MIG slice {} on GPU {} has no UUID, skipping: {}",
+                        slice_index, parent_index, e
+                    );
+                    continue;
+                }
+            };
+
+            match Self::get_device_status(&mig_device, slice_index, versions) {
+                Ok(mut status) => {
+                    status.mig_parent = Some(parent_index);
+                    status.mig_uuid = Some(uuid);
+                    slices.push(status);
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to get status for MIG slice {} (uuid {}) on GPU {}: {}",
+                        slice_index, uuid, parent_index, e
+                    );
+                }
+            }
+        }
+
+        slices
+    }
+
+    /// Map NVML's current throttle reason bitmask onto our vendor-neutral enum
+    fn get_throttle_reasons(device: &Device) -> Vec<ThrottleReason> {
+        let reasons = match device.current_throttle_reasons() {
+            Ok(reasons) => reasons,
+            Err(e) => {
+                debug!("Could not read throttle reasons: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut out = Vec::new();
+        if reasons.contains(ThrottleReasons::SW_THERMAL_SLOWDOWN) {
+            out.push(ThrottleReason::SwThermalSlowdown);
+        }
+        if reasons.contains(ThrottleReasons::HW_THERMAL_SLOWDOWN) {
+            out.push(ThrottleReason::HwThermalSlowdown);
+        }
+        if reasons.contains(ThrottleReasons::HW_POWER_BRAKE_SLOWDOWN) {
+            out.push(ThrottleReason::HwPowerBrakeSlowdown);
+        }
+        if reasons.contains(ThrottleReasons::SW_POWER_CAP) {
+            out.push(ThrottleReason::ClocksPowerCap);
+        }
+        if reasons.contains(ThrottleReasons::HW_ALL) {
+            out.push(ThrottleReason::HwSlowdown);
+        }
+        if reasons.contains(ThrottleReasons::APPLICATIONS_CLOCKS_SETTING) {
+            out.push(ThrottleReason::AppClocksSetting);
+        }
+        if reasons.contains(ThrottleReasons::SYNC_BOOST) {
+            out.push(ThrottleReason::SyncBoost);
+        }
+        out
+    }
+
+    /// Read ECC volatile/aggregate single/double-bit error counts, if the card supports ECC
+    fn get_ecc_errors(device: &Device) -> Option<EccCounts> {
+        let volatile_single_bit = device
+            .memory_error_counter(MemoryError::Corrected, EccCounter::Volatile, MemoryLocation::Device)
+            .ok()?;
+        let volatile_double_bit = device
+            .memory_error_counter(MemoryError::Uncorrected, EccCounter::Volatile, MemoryLocation::Device)
+            .ok()
+            .unwrap_or(0);
+        let aggregate_single_bit = device
+            .memory_error_counter(MemoryError::Corrected, EccCounter::Aggregate, MemoryLocation::Device)
+            .ok()
+            .unwrap_or(0);
+        let aggregate_double_bit = device
+            .memory_error_counter(MemoryError::Uncorrected, EccCounter::Aggregate, MemoryLocation::Device)
+            .ok()
+            .unwrap_or(0);
+
+        Some(EccCounts {
+            volatile_single_bit,
+            volatile_double_bit,
+            aggregate_single_bit,
+            aggregate_double_bit,
+        })
+    }
+
+    /// Enumerate processes currently running on the GPU, tagged by which engine (compute or
+    /// graphics) reported them, with per-PID SM/encode-decode utilization filled in when NVML
+    /// supports sampling it
+    fn get_gpu_processes(device: &Device) -> Vec<GpuProcess> {
+        let mut processes = Vec::new();
+        let utilization = Self::process_utilization(device);
+
+        let compute = device.running_compute_processes().unwrap_or_default();
+        let graphics = device.running_graphics_processes().unwrap_or_default();
+
+        let tagged = compute
+            .into_iter()
+            .map(|info| (info, GpuProcessType::Compute))
+            .chain(graphics.into_iter().map(|info| (info, GpuProcessType::Graphics)));
+
+        for (info, proc_type) in tagged {
+            let used_memory = match info.used_gpu_memory {
+                UsedGpuMemory::Used(bytes) => Some(bytes),
+                UsedGpuMemory::Unavailable => None,
+            };
+            let name = Self::process_name(info.pid).unwrap_or_else(|| format!("pid-{}", info.pid));
+            let (sm_utilization, enc_dec_utilization) = utilization
+                .get(&info.pid)
+                .copied()
+                .unwrap_or((None, None));
+
+            processes.push(GpuProcess {
+                pid: info.pid,
+                name,
+                proc_type,
+                used_memory,
+                sm_utilization,
+                enc_dec_utilization,
+            });
+        }
+
+        processes
+    }
+
+    /// Sample per-process SM and encode/decode engine utilization since the last second, keyed
+    /// by PID. Not all GPU generations support `nvmlDeviceGetProcessUtilization`, so a failure
+    /// here just means callers fall back to `None` rather than failing the whole status read.
+    fn process_utilization(device: &Device) -> HashMap<u32, (Option<u32>, Option<u32>)> {
+        let since = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_micros().saturating_sub(1_000_000) as u64)
+            .unwrap_or(0);
+
+        device
+            .process_utilization_stats(since)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|sample| {
+                (
+                    sample.pid,
+                    (Some(sample.sm_util), Some(sample.enc_util.max(sample.dec_util))),
+                )
+            })
+            .collect()
+    }
+
+    /// Best-effort process name lookup via procfs; NVML only reports the PID
+    fn process_name(pid: u32) -> Option<String> {
+        std::fs::read_to_string(format!("/proc/{}/comm", pid))
+            .ok()
+            .map(|s| s.trim().to_string())
+    }
+
     /// Apply power mode configuration
     async fn apply_power_mode(&self, device: &mut Device<'_>, mode: PowerMode) -> Result<()> {
         match mode {
@@ -204,35 +472,132 @@ impl NvidiaBackend {
         Ok(())
     }
 
-    /// Apply fan curve (if supported)
-    async fn apply_fan_curve(&self, device: &mut Device<'_>, curve: &FanCurve) -> Result<()> {
-        // Get current temperature
+    /// Apply a fan curve to `device` once, for its current temperature. Switches every fan NVML
+    /// reports into manual control before setting its speed; only falls back to
+    /// `OperationNotSupported` when the driver itself rejects manual control, since that's the
+    /// one failure mode that means this card genuinely can't be fan-controlled via NVML.
+    fn apply_fan_curve_to_device(device: &mut Device, curve: &FanCurve) -> Result<()> {
         let temp = device
             .temperature(TemperatureSensor::Gpu)
             .map_err(GpuError::from)?;
-        
-        // Calculate target fan speed based on curve
-        let _target_speed = curve.calculate_fan_speed(temp as u32);
-        
-        // NVIDIA fan control is not available in NVML for most consumer cards
-        warn!("Fan control not supported on this GPU via NVML");
-        Err(GpuError::OperationNotSupported("Fan control".to_string()))
+        let target_speed = curve.calculate_fan_speed(temp as u32);
+
+        let fan_count = device.num_fans().map_err(GpuError::from)?;
+        for fan_index in 0..fan_count {
+            device
+                .set_fan_control_policy(fan_index, FanControlPolicy::Manual)
+                .map_err(|e| GpuError::OperationNotSupported(format!(
+                    "Card/driver rejected manual fan control on fan {fan_index}: {e}"
+                )))?;
+            device
+                .set_fan_speed(fan_index, target_speed)
+                .map_err(GpuError::from)?;
+        }
+
+        Ok(())
+    }
+
+    /// Restore every fan NVML reports back to the driver's automatic control policy, undoing
+    /// [`Self::apply_fan_curve_to_device`]. Failures are logged rather than propagated, since a
+    /// fan that was never put into manual mode will harmlessly reject this.
+    fn reset_fan_control(device: &mut Device, index: u32) {
+        let fan_count = match device.num_fans() {
+            Ok(count) => count,
+            Err(e) => {
+                debug!("GPU {} does not report fan count, nothing to reset: {}", index, e);
+                return;
+            }
+        };
+
+        for fan_index in 0..fan_count {
+            if let Err(e) = device.set_fan_control_policy(fan_index, FanControlPolicy::Auto) {
+                debug!(
+                    "Could not restore automatic fan control on GPU {} fan {}: {}",
+                    index, fan_index, e
+                );
+            }
+        }
     }
 
-    /// Apply clock offsets
-    async fn apply_clock_offsets(&self, _device: &mut Device<'_>, gpu_offset: Option<i32>, mem_offset: Option<i32>) -> Result<()> {
-        // Clock offset functionality is not straightforward with NVML
-        // These would require specific NVIDIA settings or MSI Afterburner-like tools
+    /// Apply clock offsets and, where NVML's locked-clocks API allows it, a pinned clock
+    /// range/target. Raw MHz *offsets* (`gpu_offset`/`mem_offset`) aren't exposed by NVML on any
+    /// card - that only ships via vendor tools like MSI Afterburner - so those remain a logged
+    /// no-op. `clock_limits` and `memory_clock` map directly onto NVML's locked-clocks calls and
+    /// are validated against what the driver reports as supported before being applied.
+    async fn apply_clock_offsets(
+        &self,
+        device: &mut Device<'_>,
+        gpu_offset: Option<i32>,
+        mem_offset: Option<i32>,
+        clock_limits: Option<MinMax<u32>>,
+        memory_clock: Option<u32>,
+    ) -> Result<()> {
         if gpu_offset.is_some() {
             warn!("GPU clock offset not supported via NVML");
         }
-        
+
         if mem_offset.is_some() {
             warn!("Memory clock offset not supported via NVML");
         }
-        
+
+        if let Some(limits) = clock_limits {
+            Self::lock_graphics_clocks(device, limits)?;
+        }
+
+        if let Some(target_mhz) = memory_clock {
+            Self::lock_memory_clock(device, target_mhz)?;
+        }
+
         Ok(())
     }
+
+    /// Validate `limits` against the driver-reported supported graphics clocks for the GPU's
+    /// current memory clock, then pin the card to that range via NVML's locked-clocks API.
+    fn lock_graphics_clocks(device: &mut Device, limits: MinMax<u32>) -> Result<()> {
+        let mem_clock = device.clock_info(Clock::Memory).map_err(GpuError::from)?;
+        let supported = device
+            .supported_graphics_clocks(mem_clock)
+            .map_err(|e| GpuError::OperationNotSupported(format!(
+                "Card/driver does not report supported graphics clocks: {e}"
+            )))?;
+        if !supported.contains(&limits.min) || !supported.contains(&limits.max) {
+            return Err(GpuError::OperationNotSupported(format!(
+                "Requested graphics clock range {}-{}MHz is not among this card's supported clocks",
+                limits.min, limits.max
+            )));
+        }
+
+        device
+            .set_gpu_locked_clocks(limits.min, limits.max)
+            .map_err(|e| GpuError::OperationNotSupported(format!("Locking graphics clocks failed: {e}")))
+    }
+
+    /// Validate `mhz` against the driver-reported supported memory clocks, then pin the card to
+    /// it (passed as both min and max, since this is a single target clock rather than a range).
+    fn lock_memory_clock(device: &mut Device, mhz: u32) -> Result<()> {
+        let supported = device
+            .supported_memory_clocks()
+            .map_err(|e| GpuError::OperationNotSupported(format!(
+                "Card/driver does not report supported memory clocks: {e}"
+            )))?;
+        if !supported.contains(&mhz) {
+            return Err(GpuError::OperationNotSupported(format!(
+                "Requested memory clock {mhz}MHz is not among this card's supported clocks"
+            )));
+        }
+
+        device
+            .set_mem_locked_clocks(mhz, mhz)
+            .map_err(|e| GpuError::OperationNotSupported(format!("Locking memory clock failed: {e}")))
+    }
+
+    /// Apply an undervolt/voltage-offset request
+    async fn apply_voltage_offset(&self, _device: &mut Device<'_>, offset_mv: i32) -> Result<()> {
+        // Voltage control is not exposed by NVML on consumer cards; this mirrors the
+        // clock-offset situation above and is a no-op placeholder for now.
+        warn!("Voltage offset of {}mV requested but not supported via NVML", offset_mv);
+        Err(GpuError::OperationNotSupported("Voltage offset".to_string()))
+    }
 }
 
 #[async_trait]
@@ -258,16 +623,21 @@ impl GpuBackend for NvidiaBackend {
         })?;
 
         let device_count = nvml.device_count().map_err(GpuError::from)?;
+        let versions = DriverVersions::query(nvml);
         let mut gpus = Vec::new();
         let mut devices_map = HashMap::new();
 
         for i in 0..device_count {
             match nvml.device_by_index(i) {
                 Ok(device) => {
-                    match Self::get_device_status(&device, i) {
+                    match Self::get_device_status(&device, i, &versions) {
                         Ok(status) => {
                             gpus.push(status);
-                            
+
+                            if self.process_mig_devices {
+                                gpus.extend(Self::get_mig_slices(&device, i, &versions));
+                            }
+
                             // Store device for later use
                             // Safety: We transmute to 'static lifetime, but ensure device
                             // lifetime is managed by keeping nvml alive
@@ -302,7 +672,8 @@ impl GpuBackend for NvidiaBackend {
             .get(&index)
             .ok_or_else(|| GpuError::GpuNotFound(index))?;
 
-        Self::get_device_status(device, index)
+        let versions = self.nvml.as_ref().map(DriverVersions::query).unwrap_or_default();
+        Self::get_device_status(device, index, &versions)
     }
 
     #[instrument]
@@ -322,20 +693,32 @@ impl GpuBackend for NvidiaBackend {
                 .map_err(GpuError::from)?;
         }
 
-        // Apply fan curve
+        // Apply fan curve for the current temperature; unlike `set_fan_curve`, a config apply
+        // doesn't spawn a tracking task of its own
         if let Some(ref curve) = config.fan_curve {
-            if let Err(e) = self.apply_fan_curve(device, curve).await {
+            if let Err(e) = Self::apply_fan_curve_to_device(device, curve) {
                 debug!("Fan curve application failed: {}", e);
             }
         }
 
-        // Apply clock offsets
+        // Apply clock offsets/locked-clock pinning
         self.apply_clock_offsets(
             device,
             config.gpu_clock_offset,
             config.memory_clock_offset,
+            config.clock_limits,
+            config.memory_clock,
         ).await?;
 
+        // Apply voltage offset, only when the user has explicitly opted in
+        if config.allow_undervolt {
+            if let Some(offset_mv) = config.voltage_offset_mv {
+                if let Err(e) = self.apply_voltage_offset(device, offset_mv).await {
+                    debug!("Voltage offset application failed: {}", e);
+                }
+            }
+        }
+
         info!("Applied configuration to NVIDIA GPU {}", index);
         Ok(())
     }
@@ -357,12 +740,44 @@ impl GpuBackend for NvidiaBackend {
 
     #[instrument]
     async fn set_fan_curve(&self, index: u32, curve: &FanCurve) -> Result<()> {
-        let mut devices = self.devices.write().await;
-        let device = devices
-            .get_mut(&index)
-            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+        {
+            let mut devices = self.devices.write().await;
+            let device = devices
+                .get_mut(&index)
+                .ok_or_else(|| GpuError::GpuNotFound(index))?;
 
-        self.apply_fan_curve(device, curve).await
+            Self::apply_fan_curve_to_device(device, curve)?;
+        }
+
+        // Keep tracking the curve against live temperature instead of applying it once and
+        // leaving the fan pinned at whatever speed matched that instant's reading
+        let devices = Arc::clone(&self.devices);
+        let curve = curve.clone();
+        let interval = self.fan_curve_interval;
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let mut devices = devices.write().await;
+                let Some(device) = devices.get_mut(&index) else {
+                    debug!("GPU {} no longer tracked; stopping fan curve task", index);
+                    break;
+                };
+                if let Err(e) = NvidiaBackend::apply_fan_curve_to_device(device, &curve) {
+                    warn!("Fan curve re-application failed for GPU {}, stopping task: {}", index, e);
+                    break;
+                }
+            }
+        });
+
+        // Replace any previously-running task for this GPU so curves don't stack up fighting
+        // over the same fan
+        if let Some(previous) = self.fan_curve_tasks.write().await.insert(index, handle) {
+            previous.abort();
+        }
+
+        info!("Fan curve applied to GPU {} and scheduled to track temperature every {:?}", index, interval);
+        Ok(())
     }
 
     #[instrument]
@@ -389,6 +804,21 @@ impl GpuBackend for NvidiaBackend {
             .set_persistent(false)
             .map_err(GpuError::from)?;
 
+        // Undo any locked clock range/target from a previous apply_config; these fail
+        // harmlessly if nothing was ever locked, so they're logged rather than propagated
+        if let Err(e) = device.reset_gpu_locked_clocks() {
+            debug!("No locked graphics clocks to reset on GPU {}: {}", index, e);
+        }
+        if let Err(e) = device.reset_mem_locked_clocks() {
+            debug!("No locked memory clocks to reset on GPU {}: {}", index, e);
+        }
+
+        // Stop any fan-curve-tracking task for this GPU, then restore automatic fan control
+        if let Some(task) = self.fan_curve_tasks.write().await.remove(&index) {
+            task.abort();
+        }
+        Self::reset_fan_control(device, index);
+
         info!("Reset NVIDIA GPU {} to defaults", index);
         Ok(())
     }
@@ -411,6 +841,76 @@ impl GpuBackend for NvidiaBackend {
             "GPU switching requires Optimus configuration".to_string()
         ))
     }
+
+    #[instrument]
+    async fn get_processes(&self, index: u32) -> Result<Vec<GpuProcess>> {
+        let devices = self.devices.read().await;
+        let device = devices
+            .get(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        Ok(Self::get_gpu_processes(device))
+    }
+
+    #[instrument]
+    async fn set_max_clock(&self, index: u32, mhz: u32) -> Result<()> {
+        let mut devices = self.devices.write().await;
+        let device = devices
+            .get_mut(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        // 0 leaves the minimum clock unconstrained; only the boost ceiling is capped.
+        device
+            .set_gpu_locked_clocks(0, mhz)
+            .map_err(GpuError::from)?;
+
+        info!("Locked max GPU clock to {}MHz for NVIDIA GPU {}", mhz, index);
+        Ok(())
+    }
+
+    #[instrument]
+    async fn set_voltage_offset(&self, index: u32, mv: i32) -> Result<()> {
+        let mut devices = self.devices.write().await;
+        let device = devices
+            .get_mut(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        self.apply_voltage_offset(device, mv).await
+    }
+
+    #[instrument]
+    async fn set_clock_limits(&self, index: u32, limits: MinMax<u32>) -> Result<()> {
+        let mut devices = self.devices.write().await;
+        let device = devices
+            .get_mut(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        Self::lock_graphics_clocks(device, limits)?;
+
+        info!("Locked GPU {} clock to {}-{}MHz", index, limits.min, limits.max);
+        Ok(())
+    }
+
+    #[instrument]
+    async fn set_tdp(&self, index: u32, sustained: u32, boost: u32) -> Result<()> {
+        let mut devices = self.devices.write().await;
+        let device = devices
+            .get_mut(&index)
+            .ok_or_else(|| GpuError::GpuNotFound(index))?;
+
+        device
+            .set_power_management_limit(sustained * 1000) // Convert W to mW
+            .map_err(GpuError::from)?;
+
+        if boost > sustained {
+            warn!(
+                "NVML has no separate boost power limit; ignoring requested boost of {}W for GPU {}",
+                boost, index
+            );
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -432,4 +932,24 @@ mod tests {
         assert!(high_utilization > 80); // Should trigger max performance
         assert!(low_utilization < 20);  // Should trigger power saver
     }
+
+    #[test]
+    fn test_process_name_reads_comm_for_a_real_pid() {
+        let name = NvidiaBackend::process_name(std::process::id()).unwrap();
+        assert!(!name.is_empty());
+    }
+
+    #[test]
+    fn test_process_name_returns_none_for_a_nonexistent_pid() {
+        assert!(NvidiaBackend::process_name(u32::MAX).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_process_mig_devices_defaults_to_disabled_and_is_settable() {
+        let mut backend = NvidiaBackend::new().await.unwrap();
+        assert!(!backend.process_mig_devices);
+
+        backend.set_process_mig_devices(true);
+        assert!(backend.process_mig_devices);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,143 @@
+//! Resolves human-readable GPU marketing names from a `pci.ids`-format database
+//!
+//! Device sysfs rarely exposes a usable marketing name (`product_name` is almost always empty
+//! on consumer cards), so backends fall back to a bare `vendor:device` hex pair. This module
+//! looks that pair up in the same database `lspci` uses: a system-installed `pci.ids` file if
+//! one exists, or a small embedded table covering common recent GPUs otherwise. The parsed
+//! table is cached after the first successful parse.
+
+use crate::error::{GpuError, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::OnceLock;
+use tracing::warn;
+
+/// Known system locations for the `pci.ids` database, checked in order
+const SYSTEM_PCI_IDS_PATHS: &[&str] = &["/usr/share/hwdata/pci.ids", "/usr/share/misc/pci.ids"];
+
+/// Minimal embedded fallback covering a handful of common recent GPUs, used when no system
+/// `pci.ids` file is installed
+const EMBEDDED_PCI_IDS: &str = "\
+1002  Advanced Micro Devices, Inc. [AMD/ATI]
+\t73bf  Navi 21 [Radeon RX 6900 XT]
+\t73df  Navi 21 [Radeon RX 6800/6800 XT / 6900 XT]
+\t744c  Navi 21 [Radeon RX 6900 XT]
+\t7448  Navi 31 [Radeon RX 7900 XT/7900 XTX]
+10de  NVIDIA Corporation
+\t2684  AD102 [GeForce RTX 4090]
+\t2782  AD104 [GeForce RTX 4070 Ti]
+8086  Intel Corporation
+\t56a0  DG2 [Arc A770]
+\t4680  Alder Lake-S GT1 [UHD Graphics 770]
+";
+
+static DEVICE_NAMES: OnceLock<HashMap<(u16, u16), String>> = OnceLock::new();
+
+/// Look up the marketing name for a `(vendor_id, device_id)` pair, lazily parsing the database
+/// on first call. Returns `Ok(None)` when the database parses fine but has no entry for this
+/// pair; returns `Err` only when an installed system database fails to parse at all.
+pub fn lookup_device_name(vendor_id: u16, device_id: u16) -> Result<Option<String>> {
+    Ok(device_names()?.get(&(vendor_id, device_id)).cloned())
+}
+
+fn device_names() -> Result<&'static HashMap<(u16, u16), String>> {
+    if let Some(names) = DEVICE_NAMES.get() {
+        return Ok(names);
+    }
+
+    // A system file that exists but fails to parse is reported as an error rather than silently
+    // falling back, so a corrupt/truncated install is diagnosable instead of just looking like a
+    // database with no entries.
+    for path in SYSTEM_PCI_IDS_PATHS {
+        if let Ok(content) = fs::read_to_string(path) {
+            let names = parse_pci_ids(&content).map_err(|e| {
+                warn!("Rejecting invalid pci.ids database at {}: {}", path, e);
+                e
+            })?;
+            return Ok(DEVICE_NAMES.get_or_init(|| names));
+        }
+    }
+
+    let names = parse_pci_ids(EMBEDDED_PCI_IDS).expect("embedded pci.ids fallback must parse");
+    Ok(DEVICE_NAMES.get_or_init(|| names))
+}
+
+/// Parse `pci.ids`-format text (tab-indented vendor -> device hierarchy) into a
+/// `(vendor_id, device_id) -> name` map. Blank lines and `#`-prefixed comments are skipped;
+/// two-tab-indented subsystem rows are skipped too since only vendor/device names are needed.
+fn parse_pci_ids(content: &str) -> Result<HashMap<(u16, u16), String>> {
+    let mut names = HashMap::new();
+    let mut current_vendor: Option<u16> = None;
+
+    for line in content.lines() {
+        if line.trim().is_empty() || line.starts_with('#') || line.starts_with("\t\t") {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix('\t') {
+            let vendor_id = current_vendor.ok_or_else(|| {
+                GpuError::PciDatabaseError(format!("device line before any vendor line: {:?}", line))
+            })?;
+            let (id, name) = split_id_and_name(rest)
+                .ok_or_else(|| GpuError::PciDatabaseError(format!("malformed device line: {:?}", line)))?;
+            let device_id = parse_hex_id(id)?;
+            names.insert((vendor_id, device_id), name.to_string());
+        } else {
+            let (id, _name) = split_id_and_name(line)
+                .ok_or_else(|| GpuError::PciDatabaseError(format!("malformed vendor line: {:?}", line)))?;
+            current_vendor = Some(parse_hex_id(id)?);
+        }
+    }
+
+    Ok(names)
+}
+
+/// Split a `pci.ids` entry line of the form `"<hex id>  <name>"` into its id and trimmed name
+fn split_id_and_name(line: &str) -> Option<(&str, &str)> {
+    let (id, rest) = line.split_once(char::is_whitespace)?;
+    Some((id, rest.trim()))
+}
+
+fn parse_hex_id(id: &str) -> Result<u16> {
+    u16::from_str_radix(id, 16).map_err(|_| GpuError::PciDatabaseError(format!("invalid hex id: {:?}", id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_vendor_and_device_lines() {
+        let db = "1002  Advanced Micro Devices, Inc. [AMD/ATI]\n\t73df  Navi 21 [Radeon RX 6800 XT]\n";
+        let names = parse_pci_ids(db).unwrap();
+        assert_eq!(names.get(&(0x1002, 0x73df)).map(String::as_str), Some("Navi 21 [Radeon RX 6800 XT]"));
+    }
+
+    #[test]
+    fn skips_comments_blank_lines_and_subsystem_rows() {
+        let db = "# comment\n\n1002  AMD\n\t73df  Navi 21\n\t\t1002 abcd  Some Board Partner SKU\n";
+        let names = parse_pci_ids(db).unwrap();
+        assert_eq!(names.len(), 1);
+        assert!(names.contains_key(&(0x1002, 0x73df)));
+    }
+
+    #[test]
+    fn rejects_a_device_line_before_any_vendor_line() {
+        let db = "\t73df  Navi 21\n";
+        let err = parse_pci_ids(db).unwrap_err();
+        assert!(matches!(err, GpuError::PciDatabaseError(_)));
+    }
+
+    #[test]
+    fn rejects_an_unparseable_hex_id() {
+        let db = "zzzz  Not A Vendor\n";
+        let err = parse_pci_ids(db).unwrap_err();
+        assert!(matches!(err, GpuError::PciDatabaseError(_)));
+    }
+
+    #[test]
+    fn embedded_fallback_table_parses_cleanly() {
+        let names = parse_pci_ids(EMBEDDED_PCI_IDS).expect("embedded table must be well-formed");
+        assert!(names.contains_key(&(0x1002, 0x73df)));
+    }
+}
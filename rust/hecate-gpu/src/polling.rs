@@ -0,0 +1,179 @@
+//! Edge-triggered status polling
+//!
+//! `subscribe_events()` plus a `select!` timeout works, but every HTTP/IPC frontend ends up
+//! re-implementing the same debounce loop over the broadcast channel. [`VersionTracker`] gives
+//! them a single long-poll call instead: it keeps a monotonically increasing [`StatusVersion`]
+//! that only advances when a tracked field crosses a configurable threshold, and
+//! [`GpuManager::poll_status`](crate::GpuManager::poll_status) blocks until the version moves
+//! past the caller's last-seen value or a timeout elapses.
+
+use crate::GpuStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::{Notify, RwLock};
+
+/// Monotonically increasing snapshot version
+pub type StatusVersion = u64;
+
+/// Thresholds controlling how large a change must be before the version advances
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ChangeThresholds {
+    /// Minimum temperature change (°C) that counts as a state change
+    pub temp_delta: u32,
+    /// Minimum utilization change (percentage points) that counts as a state change
+    pub utilization_delta: u32,
+}
+
+impl Default for ChangeThresholds {
+    fn default() -> Self {
+        Self {
+            temp_delta: 2,
+            utilization_delta: 10,
+        }
+    }
+}
+
+/// Tracks a version number that advances when GPU telemetry changes meaningfully
+pub struct VersionTracker {
+    version: RwLock<StatusVersion>,
+    last_snapshot: RwLock<HashMap<u32, GpuStatus>>,
+    thresholds: ChangeThresholds,
+    notify: Notify,
+}
+
+impl VersionTracker {
+    pub fn new(thresholds: ChangeThresholds) -> Self {
+        Self {
+            version: RwLock::new(0),
+            last_snapshot: RwLock::new(HashMap::new()),
+            thresholds,
+            notify: Notify::new(),
+        }
+    }
+
+    /// Current version
+    pub async fn current(&self) -> StatusVersion {
+        *self.version.read().await
+    }
+
+    /// Compare `statuses` against the last recorded snapshot and bump the version if any GPU
+    /// crossed a configured threshold (or appeared/disappeared). Always updates the snapshot.
+    pub async fn observe(&self, statuses: &[GpuStatus]) {
+        let mut last_snapshot = self.last_snapshot.write().await;
+        let mut changed = statuses.len() != last_snapshot.len();
+
+        for status in statuses {
+            match last_snapshot.get(&status.index) {
+                Some(previous) => {
+                    if self.differs(previous, status) {
+                        changed = true;
+                    }
+                }
+                None => changed = true,
+            }
+        }
+
+        *last_snapshot = statuses.iter().map(|s| (s.index, s.clone())).collect();
+        drop(last_snapshot);
+
+        if changed {
+            let mut version = self.version.write().await;
+            *version = version.wrapping_add(1);
+            drop(version);
+            self.notify.notify_waiters();
+        }
+    }
+
+    fn differs(&self, previous: &GpuStatus, current: &GpuStatus) -> bool {
+        let temp_delta = previous.temperature.abs_diff(current.temperature);
+        let util_delta = previous.utilization_gpu.abs_diff(current.utilization_gpu);
+
+        temp_delta >= self.thresholds.temp_delta
+            || util_delta >= self.thresholds.utilization_delta
+            || previous.power_state != current.power_state
+    }
+
+    /// Wait until the version advances past `last_seen`, returning immediately if it already has
+    pub async fn wait_for_change(&self, last_seen: StatusVersion) {
+        loop {
+            if *self.version.read().await > last_seen {
+                return;
+            }
+            // Register interest before re-checking to avoid missing a notification that fires
+            // between the read above and the await below.
+            let notified = self.notify.notified();
+            if *self.version.read().await > last_seen {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DriverBinding, GpuType, GpuVendor, PciInfo, PowerState};
+
+    fn status(index: u32, temperature: u32, utilization_gpu: u32) -> GpuStatus {
+        GpuStatus {
+            index,
+            name: "Test GPU".to_string(),
+            vendor: GpuVendor::NVIDIA,
+            gpu_type: GpuType::Discrete,
+            temperature,
+            power_draw: 200,
+            power_limit: Some(300),
+            memory_used: 0,
+            memory_total: 0,
+            utilization_gpu,
+            utilization_memory: 0,
+            fan_speed: None,
+            clock_graphics: 0,
+            clock_memory: Some(0),
+            driver_version: None,
+            pci_info: PciInfo {
+                domain: 0,
+                bus: 0,
+                device: 0,
+                function: 0,
+                vendor_id: 0,
+                device_id: 0,
+            },
+            power_state: PowerState::Active,
+            voltage_mv: None,
+            throttle_reasons: Vec::new(),
+            ecc_errors: None,
+            processes: Vec::new(),
+            driver_bound: DriverBinding::Unbound,
+            unified_memory: false,
+            mig_parent: None,
+            mig_uuid: None,
+            uuid: None,
+            serial: None,
+            board_part_number: None,
+            vbios_version: None,
+            cuda_driver_version: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn small_changes_do_not_bump_version() {
+        let tracker = VersionTracker::new(ChangeThresholds::default());
+        tracker.observe(&[status(0, 60, 50)]).await;
+        let v1 = tracker.current().await;
+
+        tracker.observe(&[status(0, 61, 51)]).await; // within thresholds
+        assert_eq!(tracker.current().await, v1);
+    }
+
+    #[tokio::test]
+    async fn large_temperature_change_bumps_version() {
+        let tracker = VersionTracker::new(ChangeThresholds::default());
+        tracker.observe(&[status(0, 60, 50)]).await;
+        let v1 = tracker.current().await;
+
+        tracker.observe(&[status(0, 70, 50)]).await;
+        assert!(tracker.current().await > v1);
+    }
+}
@@ -0,0 +1,275 @@
+//! Condition-matched GPU configuration profiles, modeled on Steam Deck-style power-profile
+//! tooling: rather than a caller hardcoding `GpuConfig` defaults, a [`Profile`] declares the
+//! hardware conditions it applies to and the limits to use when it matches. Each profile also
+//! carries a set of named, hand-switchable [`ProfileVariant`]s (e.g. "Max Performance", "Quiet",
+//! "Eco") a UI can enumerate and apply without needing to know the underlying `GpuConfig` fields.
+//!
+//! Profiles are evaluated in file order; the first whose [`Conditions`] all match a detected GPU
+//! wins. Everything here is `Serialize`/`Deserialize` so hardware-specific presets can be added
+//! as JSON without recompiling.
+
+use crate::error::{GpuError, Result};
+use crate::{GpuConfig, GpuStatus, GpuVendor};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// All of the conditions that must hold for a [`Profile`] to apply to a detected GPU. A `None`
+/// field is treated as "don't care" and always matches.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Conditions {
+    /// Match a specific detected vendor
+    pub vendor: Option<GpuVendor>,
+    /// Match a specific PCI vendor id (e.g. `0x10DE` for NVIDIA)
+    pub pci_vendor_id: Option<u16>,
+    /// Match a specific PCI device id
+    pub pci_device_id: Option<u16>,
+    /// Match the GPU's reported name against this regex
+    pub name_regex: Option<String>,
+    /// Match only if this path exists on disk (e.g. a vendor-specific sysfs knob, or a marker
+    /// file a deployment drops to opt a specific machine into this profile)
+    pub file_exists: Option<PathBuf>,
+}
+
+impl Conditions {
+    /// Whether every specified condition holds for `gpu`. An invalid `name_regex` never matches
+    /// rather than panicking, so a typo in a user-authored profile just disables that profile.
+    pub fn matches(&self, gpu: &GpuStatus) -> bool {
+        if let Some(vendor) = &self.vendor {
+            if *vendor != gpu.vendor {
+                return false;
+            }
+        }
+        if let Some(vendor_id) = self.pci_vendor_id {
+            if vendor_id != gpu.pci_info.vendor_id {
+                return false;
+            }
+        }
+        if let Some(device_id) = self.pci_device_id {
+            if device_id != gpu.pci_info.device_id {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.name_regex {
+            match Regex::new(pattern) {
+                Ok(re) if re.is_match(&gpu.name) => {}
+                _ => return false,
+            }
+        }
+        if let Some(path) = &self.file_exists {
+            if !path.exists() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A named alternate preset within a [`Profile`], e.g. "Max Performance", "Quiet", "Eco"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileVariant {
+    /// Stable identifier used by [`ProfileManager::apply_variant`]
+    pub id: String,
+    /// Human-readable label for a UI to display
+    pub name: String,
+    /// The configuration this variant applies
+    pub config: GpuConfig,
+}
+
+/// A condition-matched set of defaults, plus named variants a user can switch between
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    /// Human-readable profile name
+    pub name: String,
+    /// Hardware conditions that must all hold for this profile to apply
+    pub conditions: Conditions,
+    /// Default configuration applied when this profile is selected
+    pub limits: GpuConfig,
+    /// Named alternate presets within this profile
+    #[serde(default)]
+    pub variants: Vec<ProfileVariant>,
+}
+
+/// Loads [`Profile`]s from JSON and selects/applies the one matching each detected GPU
+#[derive(Debug)]
+pub struct ProfileManager {
+    profiles: Vec<Profile>,
+    /// Index into `profiles` most recently selected per GPU index by [`Self::select_for`]
+    active: HashMap<u32, usize>,
+}
+
+impl ProfileManager {
+    /// Load profiles from the default path (`$HECATE_CONFIG_DIR/gpu/profiles.json`, falling back
+    /// to `~/.config/hecate/gpu/profiles.json`). A missing file loads an empty profile set rather
+    /// than erroring, since profile-based defaults are opt-in.
+    pub fn load() -> Result<Self> {
+        Self::with_path(default_profiles_path()?)
+    }
+
+    /// Load profiles from a specific JSON file
+    pub fn with_path(path: PathBuf) -> Result<Self> {
+        let profiles = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| {
+                GpuError::InvalidConfig(format!("malformed profiles file {}: {e}", path.display()))
+            })?,
+            Err(_) => Vec::new(),
+        };
+        Ok(Self { profiles, active: HashMap::new() })
+    }
+
+    /// Evaluate every profile in order against `gpu` and remember the first fully-matching one
+    /// as `gpu.index`'s active profile
+    pub fn select_for(&mut self, gpu: &GpuStatus) -> Option<&Profile> {
+        let index = self.profiles.iter().position(|p| p.conditions.matches(gpu));
+        match index {
+            Some(i) => {
+                self.active.insert(gpu.index, i);
+                Some(&self.profiles[i])
+            }
+            None => {
+                self.active.remove(&gpu.index);
+                None
+            }
+        }
+    }
+
+    /// The profile most recently selected for `gpu_index` by [`Self::select_for`]
+    pub fn active_profile(&self, gpu_index: u32) -> Option<&Profile> {
+        self.active.get(&gpu_index).map(|&i| &self.profiles[i])
+    }
+
+    /// List the variants of the profile currently active for `gpu_index`, empty if none matched
+    pub fn list_variants(&self, gpu_index: u32) -> &[ProfileVariant] {
+        self.active_profile(gpu_index).map(|p| p.variants.as_slice()).unwrap_or(&[])
+    }
+
+    /// Look up a variant by id within `gpu_index`'s active profile and return its configuration
+    pub fn apply_variant(&self, gpu_index: u32, variant_id: &str) -> Result<GpuConfig> {
+        let profile = self
+            .active_profile(gpu_index)
+            .ok_or_else(|| GpuError::InvalidConfig(format!("no profile selected for GPU {gpu_index}")))?;
+
+        profile
+            .variants
+            .iter()
+            .find(|v| v.id == variant_id)
+            .map(|v| v.config.clone())
+            .ok_or_else(|| {
+                GpuError::InvalidConfig(format!("profile '{}' has no variant '{}'", profile.name, variant_id))
+            })
+    }
+
+    /// All loaded profiles, in evaluation order
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles
+    }
+}
+
+fn default_profiles_path() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var("HECATE_CONFIG_DIR") {
+        return Ok(PathBuf::from(dir).join("gpu").join("profiles.json"));
+    }
+
+    let home = std::env::var("HOME")
+        .map_err(|_| GpuError::InvalidConfig("HOME is not set; cannot locate config directory".to_string()))?;
+    Ok(PathBuf::from(home).join(".config").join("hecate").join("gpu").join("profiles.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GpuType, PciInfo, PowerState};
+
+    fn test_gpu(name: &str, vendor_id: u16, device_id: u16) -> GpuStatus {
+        GpuStatus {
+            index: 0,
+            name: name.to_string(),
+            vendor: GpuVendor::NVIDIA,
+            gpu_type: GpuType::Discrete,
+            temperature: 60,
+            power_draw: 150,
+            power_limit: Some(300),
+            memory_used: 0,
+            memory_total: 0,
+            utilization_gpu: 0,
+            utilization_memory: 0,
+            fan_speed: None,
+            clock_graphics: 0,
+            clock_memory: Some(0),
+            driver_version: None,
+            pci_info: PciInfo { domain: 0, bus: 1, device: 0, function: 0, vendor_id, device_id },
+            power_state: PowerState::Active,
+            voltage_mv: None,
+            throttle_reasons: Vec::new(),
+            ecc_errors: None,
+            processes: Vec::new(),
+            driver_bound: crate::vfio::DriverBinding::Unbound,
+            unified_memory: false,
+            mig_parent: None,
+            mig_uuid: None,
+            uuid: None,
+            serial: None,
+            board_part_number: None,
+            vbios_version: None,
+            cuda_driver_version: None,
+        }
+    }
+
+    fn deck_profile() -> Profile {
+        Profile {
+            name: "Steam Deck".to_string(),
+            conditions: Conditions { name_regex: Some("Deck".to_string()), ..Default::default() },
+            limits: GpuConfig::balanced(),
+            variants: vec![
+                ProfileVariant { id: "max-perf".to_string(), name: "Max Performance".to_string(), config: GpuConfig::max_performance() },
+                ProfileVariant { id: "quiet".to_string(), name: "Quiet".to_string(), config: GpuConfig::power_saver() },
+            ],
+        }
+    }
+
+    #[test]
+    fn conditions_require_every_specified_field_to_match() {
+        let conditions = Conditions { pci_vendor_id: Some(0x10DE), pci_device_id: Some(0x1234), ..Default::default() };
+        assert!(conditions.matches(&test_gpu("RTX 4090", 0x10DE, 0x1234)));
+        assert!(!conditions.matches(&test_gpu("RTX 4090", 0x10DE, 0x9999)));
+    }
+
+    #[test]
+    fn name_regex_condition_matches_substrings() {
+        let conditions = Conditions { name_regex: Some("^RTX 40".to_string()), ..Default::default() };
+        assert!(conditions.matches(&test_gpu("RTX 4090", 0x10DE, 0x2684)));
+        assert!(!conditions.matches(&test_gpu("RTX 3090", 0x10DE, 0x2204)));
+    }
+
+    #[test]
+    fn invalid_regex_fails_closed_instead_of_panicking() {
+        let conditions = Conditions { name_regex: Some("(".to_string()), ..Default::default() };
+        assert!(!conditions.matches(&test_gpu("RTX 4090", 0x10DE, 0x2684)));
+    }
+
+    #[test]
+    fn select_for_picks_first_matching_profile_and_tracks_variants_per_gpu() {
+        let mut manager = ProfileManager { profiles: vec![deck_profile()], active: HashMap::new() };
+        let gpu = test_gpu("Steam Deck GPU", 0x1002, 0x163F);
+
+        let selected = manager.select_for(&gpu).expect("profile should match");
+        assert_eq!(selected.name, "Steam Deck");
+        assert_eq!(manager.list_variants(0).len(), 2);
+
+        let config = manager.apply_variant(0, "quiet").unwrap();
+        assert_eq!(config.power_mode, GpuConfig::power_saver().power_mode);
+
+        assert!(manager.apply_variant(0, "nonexistent").is_err());
+    }
+
+    #[test]
+    fn select_for_returns_none_and_clears_variants_when_nothing_matches() {
+        let mut manager = ProfileManager { profiles: vec![deck_profile()], active: HashMap::new() };
+        let gpu = test_gpu("RTX 4090", 0x10DE, 0x2684);
+
+        assert!(manager.select_for(&gpu).is_none());
+        assert!(manager.list_variants(0).is_empty());
+    }
+}
@@ -0,0 +1,252 @@
+//! Streaming GPU telemetry export.
+//!
+//! [`metrics::MetricSink`](crate::metrics::MetricSink) is push-only and renders a fixed set of
+//! fields; this module exists for continuous, cardinality-aware cluster telemetry instead: every
+//! detected GPU is re-sampled on [`TelemetryConfig::interval`] and rendered as InfluxDB line
+//! protocol or Prometheus text exposition, with a pull (scrape) endpoint kept warm alongside an
+//! optional line-protocol push. [`TelemetryConfig`]'s exclude lists and tag toggles let a
+//! deployment trim what it ships upstream without patching the collector on the other end.
+//!
+//! [`GpuManager::start_telemetry_export`](crate::GpuManager::start_telemetry_export) drives the
+//! polling loop; this module only renders.
+
+use crate::metrics::{escape_label_value, escape_tag_value};
+use crate::GpuStatus;
+use axum::{routing::get, Router};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+
+/// Metric field names this module knows how to render, validated against
+/// [`TelemetryConfig::exclude_metrics`]
+pub const TELEMETRY_METRICS: &[&str] = &[
+    "temperature",
+    "power_draw",
+    "power_limit",
+    "utilization_gpu",
+    "utilization_memory",
+    "memory_used",
+    "memory_total",
+    "clock_graphics",
+    "clock_memory",
+];
+
+/// Cache the pull endpoint serves between polls, kept warm by
+/// [`GpuManager::start_telemetry_export`](crate::GpuManager::start_telemetry_export)
+pub type PrometheusCache = Arc<RwLock<String>>;
+
+/// Configuration for [`GpuManager::start_telemetry_export`](crate::GpuManager::start_telemetry_export),
+/// modeled after [`metrics::MetricSinkKind`](crate::metrics::MetricSinkKind) but with cardinality
+/// knobs so a deployment can trim what it exports rather than shipping every field for every GPU.
+#[derive(Debug, Clone)]
+pub struct TelemetryConfig {
+    /// How often every GPU is re-sampled and re-rendered
+    pub interval: Duration,
+    /// Metric field names (see [`TELEMETRY_METRICS`]) to omit from every sample
+    pub exclude_metrics: Vec<String>,
+    /// GPU indices, formatted as strings, to omit entirely
+    pub exclude_devices: Vec<String>,
+    /// Tag/label each sample with its PCI bus, sourced from `GpuStatus::pci_info`
+    pub add_pci_info_tag: bool,
+    /// Tag/label each sample with its UUID, sourced from `GpuStatus::uuid`, when the backend
+    /// reports one
+    pub add_uuid_meta: bool,
+    /// Line-protocol HTTP write endpoint to push rendered samples to; `None` disables the push
+    /// writer and only keeps the scrape cache warm
+    pub push_url: Option<String>,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(10),
+            exclude_metrics: Vec::new(),
+            exclude_devices: Vec::new(),
+            add_pci_info_tag: false,
+            add_uuid_meta: false,
+            push_url: None,
+        }
+    }
+}
+
+impl TelemetryConfig {
+    fn included<'a>(&self, statuses: &'a [GpuStatus]) -> impl Iterator<Item = &'a GpuStatus> {
+        statuses
+            .iter()
+            .filter(move |s| !self.exclude_devices.iter().any(|excluded| *excluded == s.index.to_string()))
+    }
+
+    fn tags(&self, status: &GpuStatus) -> Vec<(&'static str, String)> {
+        let mut tags = vec![("index", status.index.to_string())];
+        if self.add_pci_info_tag {
+            tags.push(("pci_bus", status.pci_info.bus.to_string()));
+        }
+        if self.add_uuid_meta {
+            if let Some(uuid) = &status.uuid {
+                tags.push(("uuid", uuid.clone()));
+            }
+        }
+        tags
+    }
+
+    fn fields(&self, status: &GpuStatus) -> Vec<(&'static str, f64)> {
+        let all: [(&'static str, Option<f64>); 9] = [
+            ("temperature", Some(status.temperature as f64)),
+            ("power_draw", Some(status.power_draw as f64)),
+            ("power_limit", status.power_limit.map(|v| v as f64)),
+            ("utilization_gpu", Some(status.utilization_gpu as f64)),
+            ("utilization_memory", Some(status.utilization_memory as f64)),
+            ("memory_used", Some(status.memory_used as f64)),
+            ("memory_total", Some(status.memory_total as f64)),
+            ("clock_graphics", Some(status.clock_graphics as f64)),
+            ("clock_memory", status.clock_memory.map(|v| v as f64)),
+        ];
+        all.into_iter()
+            .filter(|(name, _)| !self.exclude_metrics.iter().any(|excluded| excluded == name))
+            .filter_map(|(name, value)| value.map(|v| (name, v)))
+            .collect()
+    }
+
+    /// Render a poll's worth of samples as InfluxDB line protocol, one line per GPU, e.g.
+    /// `hecate_gpu,index=0,pci_bus=1 temperature=65,power_draw=210 1706454000000000000`
+    pub fn render_line_protocol(&self, statuses: &[GpuStatus]) -> String {
+        let timestamp_nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+
+        let mut out = String::new();
+        for status in self.included(statuses) {
+            let fields = self.fields(status);
+            if fields.is_empty() {
+                continue;
+            }
+
+            let tag_str: String = self
+                .tags(status)
+                .iter()
+                .map(|(k, v)| format!(",{}={}", k, escape_tag_value(v)))
+                .collect();
+            let field_str = fields
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            out.push_str(&format!("hecate_gpu{tag_str} {field_str} {timestamp_nanos}\n"));
+        }
+        out
+    }
+
+    /// Render a poll's worth of samples as Prometheus text exposition, one family per field, e.g.
+    /// `hecate_gpu_temperature{index="0",pci_bus="1"} 65`
+    pub fn render_prometheus(&self, statuses: &[GpuStatus]) -> String {
+        let mut out = String::new();
+        for status in self.included(statuses) {
+            let label_str: String = self
+                .tags(status)
+                .iter()
+                .map(|(k, v)| format!("{k}=\"{}\"", escape_label_value(v)))
+                .collect::<Vec<_>>()
+                .join(",");
+
+            for (name, value) in self.fields(status) {
+                out.push_str(&format!("hecate_gpu_{name}{{{label_str}}} {value}\n"));
+            }
+        }
+        out
+    }
+}
+
+/// An `axum` router exposing a Prometheus-compatible `/metrics` scrape endpoint over `cache`
+pub fn pull_router(cache: PrometheusCache) -> Router {
+    Router::new().route(
+        "/metrics",
+        get(move || {
+            let cache = Arc::clone(&cache);
+            async move { cache.read().await.clone() }
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GpuType, GpuVendor, PciInfo, PowerState};
+
+    fn sample_status() -> GpuStatus {
+        GpuStatus {
+            index: 0,
+            name: "RTX 4090".to_string(),
+            vendor: GpuVendor::NVIDIA,
+            gpu_type: GpuType::Discrete,
+            temperature: 65,
+            power_draw: 210,
+            power_limit: Some(450),
+            memory_used: 4_294_967_296,
+            memory_total: 24_000_000_000,
+            utilization_gpu: 80,
+            utilization_memory: 40,
+            fan_speed: Some(60),
+            clock_graphics: 2500,
+            clock_memory: Some(10000),
+            driver_version: None,
+            pci_info: PciInfo { domain: 0, bus: 1, device: 0, function: 0, vendor_id: 0x10DE, device_id: 0x2684 },
+            power_state: PowerState::Active,
+            voltage_mv: None,
+            throttle_reasons: Vec::new(),
+            ecc_errors: None,
+            processes: Vec::new(),
+            driver_bound: crate::vfio::DriverBinding::Unbound,
+            unified_memory: false,
+            mig_parent: None,
+            mig_uuid: None,
+            uuid: Some("GPU-deadbeef".to_string()),
+            serial: None,
+            board_part_number: None,
+            vbios_version: None,
+            cuda_driver_version: None,
+        }
+    }
+
+    #[test]
+    fn line_protocol_includes_requested_fields_and_index_tag() {
+        let config = TelemetryConfig::default();
+        let line = config.render_line_protocol(&[sample_status()]);
+        assert!(line.starts_with("hecate_gpu,index=0 "));
+        assert!(line.contains("temperature=65"));
+        assert!(line.contains("power_draw=210"));
+        assert!(line.contains("memory_used=4294967296"));
+        assert!(!line.contains("uuid="));
+    }
+
+    #[test]
+    fn prometheus_exposition_has_expected_families() {
+        let config = TelemetryConfig::default();
+        let text = config.render_prometheus(&[sample_status()]);
+        assert!(text.contains("hecate_gpu_temperature{index=\"0\"} 65"));
+        assert!(text.contains("hecate_gpu_clock_graphics{index=\"0\"} 2500"));
+    }
+
+    #[test]
+    fn exclude_metrics_drops_the_named_field() {
+        let config = TelemetryConfig { exclude_metrics: vec!["power_draw".to_string()], ..Default::default() };
+        let text = config.render_prometheus(&[sample_status()]);
+        assert!(!text.contains("hecate_gpu_power_draw"));
+        assert!(text.contains("hecate_gpu_temperature"));
+    }
+
+    #[test]
+    fn exclude_devices_drops_the_named_gpu_entirely() {
+        let config = TelemetryConfig { exclude_devices: vec!["0".to_string()], ..Default::default() };
+        assert_eq!(config.render_prometheus(&[sample_status()]), "");
+        assert_eq!(config.render_line_protocol(&[sample_status()]), "");
+    }
+
+    #[test]
+    fn add_pci_info_tag_and_add_uuid_meta_extend_the_tag_set() {
+        let config = TelemetryConfig { add_pci_info_tag: true, add_uuid_meta: true, ..Default::default() };
+        let line = config.render_line_protocol(&[sample_status()]);
+        assert!(line.contains(",pci_bus=1"));
+        assert!(line.contains(",uuid=GPU-deadbeef"));
+    }
+}
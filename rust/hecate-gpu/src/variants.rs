@@ -0,0 +1,199 @@
+//! Named GPU configuration variants with on-disk persistence
+//!
+//! Mirrors the "variant" concept used by tools like PowerTools: a user can save
+//! the currently-applied `GpuConfig` under a friendly name, list what has been
+//! saved, and hot-swap back to any of them at runtime without recompiling the
+//! built-in presets.
+
+use crate::error::{GpuError, Result};
+use crate::GpuConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, instrument};
+
+/// Metadata + payload for a single saved configuration variant
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VariantInfo {
+    /// Monotonically increasing numeric id, stable across renames
+    pub id_num: u64,
+    /// Human-readable name chosen by the user
+    pub name: String,
+    /// GPU index this variant was captured for
+    pub gpu_index: u32,
+    /// Unix timestamp (seconds) the variant was saved
+    pub created_at: u64,
+    /// The captured configuration
+    pub config: GpuConfig,
+}
+
+impl VariantInfo {
+    /// Stable identifier string used in file names and lookups (`"<id_num>"`)
+    pub fn id(&self) -> String {
+        self.id_num.to_string()
+    }
+}
+
+/// Loads and persists [`VariantInfo`] records under a config directory
+#[derive(Debug)]
+pub struct VariantStore {
+    dir: PathBuf,
+    next_id: AtomicU64,
+}
+
+impl VariantStore {
+    /// Open (creating if necessary) the default variant directory
+    /// (`$HECATE_CONFIG_DIR/gpu/variants`, falling back to `~/.config/hecate/gpu/variants`).
+    pub fn new() -> Result<Self> {
+        let dir = default_variant_dir()?;
+        Self::with_dir(dir)
+    }
+
+    /// Open (creating if necessary) a variant store rooted at `dir`
+    pub fn with_dir(dir: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let next_id = scan_max_id(&dir)?.wrapping_add(1);
+        Ok(Self {
+            dir,
+            next_id: AtomicU64::new(next_id),
+        })
+    }
+
+    /// Persist `config` as a new named variant and return its metadata
+    #[instrument(skip(self, config))]
+    pub fn save(&self, name: &str, gpu_index: u32, config: GpuConfig) -> Result<VariantInfo> {
+        if name.trim().is_empty() {
+            return Err(GpuError::InvalidConfig("variant name must not be empty".to_string()));
+        }
+
+        let id_num = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let variant = VariantInfo {
+            id_num,
+            name: name.to_string(),
+            gpu_index,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            config,
+        };
+
+        let path = self.path_for(id_num);
+        let toml = toml::to_string_pretty(&variant)
+            .map_err(|e| GpuError::InvalidConfig(format!("failed to serialize variant: {e}")))?;
+        fs::write(&path, toml)?;
+        info!("Saved GPU config variant '{}' (id {}) to {}", variant.name, id_num, path.display());
+
+        Ok(variant)
+    }
+
+    /// List all saved variants, newest first
+    pub fn list(&self) -> Result<Vec<VariantInfo>> {
+        let mut variants = Vec::new();
+
+        if !self.dir.exists() {
+            return Ok(variants);
+        }
+
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+                continue;
+            }
+
+            let contents = fs::read_to_string(&path)?;
+            match toml::from_str::<VariantInfo>(&contents) {
+                Ok(variant) => variants.push(variant),
+                Err(e) => debug!("Skipping unreadable variant file {}: {}", path.display(), e),
+            }
+        }
+
+        variants.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(variants)
+    }
+
+    /// Load a single variant by its `id_num`
+    pub fn load(&self, id: u64) -> Result<VariantInfo> {
+        let path = self.path_for(id);
+        let contents = fs::read_to_string(&path).map_err(|_| {
+            GpuError::InvalidConfig(format!("no saved variant with id {id}"))
+        })?;
+        toml::from_str(&contents)
+            .map_err(|e| GpuError::InvalidConfig(format!("corrupt variant {id}: {e}")))
+    }
+
+    /// Delete a saved variant by `id_num`
+    pub fn delete(&self, id: u64) -> Result<()> {
+        let path = self.path_for(id);
+        fs::remove_file(&path).map_err(|_| {
+            GpuError::InvalidConfig(format!("no saved variant with id {id}"))
+        })
+    }
+
+    fn path_for(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("{id}.toml"))
+    }
+}
+
+fn scan_max_id(dir: &Path) -> Result<u64> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+
+    let mut max_id = 0u64;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if let Some(stem) = entry.path().file_stem().and_then(|s| s.to_str()) {
+            if let Ok(id) = stem.parse::<u64>() {
+                max_id = max_id.max(id);
+            }
+        }
+    }
+    Ok(max_id)
+}
+
+fn default_variant_dir() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var("HECATE_CONFIG_DIR") {
+        return Ok(PathBuf::from(dir).join("gpu").join("variants"));
+    }
+
+    let home = std::env::var("HOME")
+        .map_err(|_| GpuError::InvalidConfig("HOME is not set; cannot locate config directory".to_string()))?;
+    Ok(PathBuf::from(home).join(".config").join("hecate").join("gpu").join("variants"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store() -> VariantStore {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("hecate-gpu-variants-test-{}", std::process::id()));
+        VariantStore::with_dir(dir).unwrap()
+    }
+
+    #[test]
+    fn save_list_load_delete_roundtrip() {
+        let store = temp_store();
+        let saved = store.save("my-game", 0, GpuConfig::balanced()).unwrap();
+
+        let listed = store.list().unwrap();
+        assert!(listed.iter().any(|v| v.id_num == saved.id_num));
+
+        let loaded = store.load(saved.id_num).unwrap();
+        assert_eq!(loaded.name, "my-game");
+        assert_eq!(loaded.config.power_mode, GpuConfig::balanced().power_mode);
+
+        store.delete(saved.id_num).unwrap();
+        assert!(store.load(saved.id_num).is_err());
+    }
+
+    #[test]
+    fn rejects_empty_name() {
+        let store = temp_store();
+        assert!(store.save("", 0, GpuConfig::balanced()).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! PCI driver-binding detection and VFIO passthrough rebinding
+//!
+//! Lets the same tool manage host GPUs and prepare cards for VM passthrough by reading the
+//! sysfs driver symlink for a device and, when requested, rebinding it between its vendor
+//! driver and `vfio-pci` the same way `virsh nodedev-detach`/libvirt hooks do.
+
+use crate::error::{GpuError, Result};
+use crate::PciInfo;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+const PCI_DEVICES_DIR: &str = "/sys/bus/pci/devices";
+const VFIO_PCI_DRIVER: &str = "vfio-pci";
+const VFIO_NEW_ID_PATH: &str = "/sys/bus/pci/drivers/vfio-pci/new_id";
+
+/// What driver, if any, currently owns a PCI device
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DriverBinding {
+    /// Bound to the normal vendor driver (e.g. "nvidia", "amdgpu")
+    VendorDriver(String),
+    /// Bound to `vfio-pci`, ready for passthrough to a VM
+    VfioPci,
+    /// Not bound to any driver
+    Unbound,
+}
+
+/// Format a [`PciInfo`] as a kernel-style PCI address (`dddd:bb:dd.f`)
+pub fn pci_address(pci: &PciInfo) -> String {
+    format!("{:04x}:{:02x}:{:02x}.{:x}", pci.domain, pci.bus, pci.device, pci.function)
+}
+
+/// Read the sysfs `driver` symlink for `pci` to determine its current binding
+pub fn current_binding(pci: &PciInfo) -> DriverBinding {
+    let driver_link = device_dir(pci).join("driver");
+
+    let target = match fs::read_link(&driver_link) {
+        Ok(target) => target,
+        Err(_) => return DriverBinding::Unbound,
+    };
+
+    let driver_name = target
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string();
+
+    if driver_name == VFIO_PCI_DRIVER {
+        DriverBinding::VfioPci
+    } else if driver_name.is_empty() {
+        DriverBinding::Unbound
+    } else {
+        DriverBinding::VendorDriver(driver_name)
+    }
+}
+
+/// Rebind `pci` from its current driver onto `vfio-pci`
+pub fn bind_vfio(pci: &PciInfo, vendor_id: u16, device_id: u16) -> Result<()> {
+    if !Path::new(VFIO_NEW_ID_PATH).exists() {
+        return Err(GpuError::OperationNotSupported(
+            "vfio-pci kernel module is not loaded".to_string(),
+        ));
+    }
+
+    let address = pci_address(pci);
+    unbind_current_driver(&address)?;
+
+    let id_line = format!("{:04x} {:04x}", vendor_id, device_id);
+    fs::write(VFIO_NEW_ID_PATH, &id_line).map_err(|e| {
+        GpuError::PermissionDenied(format!("failed to register {id_line} with vfio-pci: {e}"))
+    })?;
+
+    bind_to_driver(&address, VFIO_PCI_DRIVER)?;
+    info!("Bound PCI device {} to vfio-pci", address);
+    Ok(())
+}
+
+/// Rebind `pci` from `vfio-pci` back onto `vendor_driver` (e.g. "nvidia", "amdgpu")
+pub fn unbind_vfio(pci: &PciInfo, vendor_driver: &str) -> Result<()> {
+    let address = pci_address(pci);
+
+    match current_binding(pci) {
+        DriverBinding::VfioPci => {}
+        other => {
+            debug!("PCI device {} is not bound to vfio-pci ({:?}); nothing to do", address, other);
+        }
+    }
+
+    unbind_current_driver(&address)?;
+    bind_to_driver(&address, vendor_driver)?;
+    info!("Rebound PCI device {} to {}", address, vendor_driver);
+    Ok(())
+}
+
+fn device_dir(pci: &PciInfo) -> PathBuf {
+    Path::new(PCI_DEVICES_DIR).join(pci_address(pci))
+}
+
+fn unbind_current_driver(address: &str) -> Result<()> {
+    let unbind_path = Path::new(PCI_DEVICES_DIR).join(address).join("driver").join("unbind");
+    if !unbind_path.exists() {
+        // Already unbound
+        return Ok(());
+    }
+    fs::write(&unbind_path, address).map_err(|e| {
+        GpuError::PermissionDenied(format!("failed to unbind {address}: {e}"))
+    })
+}
+
+fn bind_to_driver(address: &str, driver: &str) -> Result<()> {
+    let override_path = Path::new(PCI_DEVICES_DIR).join(address).join("driver_override");
+    let bind_path = PathBuf::from("/sys/bus/pci/drivers").join(driver).join("bind");
+
+    if !bind_path.exists() {
+        return Err(GpuError::OperationNotSupported(format!(
+            "driver '{driver}' is not loaded"
+        )));
+    }
+
+    if override_path.exists() {
+        let _ = fs::write(&override_path, driver);
+    }
+
+    fs::write(&bind_path, address).map_err(|e| {
+        GpuError::PermissionDenied(format!("failed to bind {address} to {driver}: {e}"))
+    })?;
+
+    if override_path.exists() {
+        let _ = fs::write(&override_path, "\n");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_pci_address() {
+        let pci = PciInfo {
+            domain: 0,
+            bus: 0x2b,
+            device: 0,
+            function: 0,
+            vendor_id: 0x10DE,
+            device_id: 0x2204,
+        };
+        assert_eq!(pci_address(&pci), "0000:2b:00.0");
+    }
+
+    #[test]
+    fn missing_sysfs_entry_is_unbound() {
+        let pci = PciInfo {
+            domain: 0xffff,
+            bus: 0xff,
+            device: 0x1f,
+            function: 7,
+            vendor_id: 0,
+            device_id: 0,
+        };
+        assert_eq!(current_binding(&pci), DriverBinding::Unbound);
+    }
+}
@@ -78,14 +78,14 @@ async fn test_efficiency_score_calculation() {
         gpu_type: GpuType::Discrete,
         temperature: 70,
         power_draw: 200,
-        power_limit: 300,
+        power_limit: Some(300),
         memory_used: 2_147_483_648, // 2GB
         memory_total: 8_589_934_592, // 8GB
         utilization_gpu: 50,
         utilization_memory: 40,
         fan_speed: Some(60),
         clock_graphics: 1500,
-        clock_memory: 7000,
+        clock_memory: Some(7000),
         driver_version: Some("470.86".to_string()),
         pci_info: PciInfo {
             domain: 0,
@@ -96,8 +96,21 @@ async fn test_efficiency_score_calculation() {
             device_id: 0x2204,
         },
         power_state: PowerState::Active,
+        voltage_mv: None,
+        throttle_reasons: Vec::new(),
+        ecc_errors: None,
+        processes: Vec::new(),
+        driver_bound: DriverBinding::Unbound,
+        unified_memory: false,
+        mig_parent: None,
+        mig_uuid: None,
+        uuid: None,
+        serial: None,
+        board_part_number: None,
+        vbios_version: None,
+        cuda_driver_version: None,
     };
-    
+
     let score = calculate_efficiency_score(&test_gpu);
     assert!(score >= 0.0 && score <= 1.0);
     
@@ -130,14 +143,14 @@ async fn test_gpu_summary_string() {
         gpu_type: GpuType::Discrete,
         temperature: 75,
         power_draw: 350,
-        power_limit: 450,
+        power_limit: Some(450),
         memory_used: 4_294_967_296, // 4GB
         memory_total: 25_769_803_776, // 24GB
         utilization_gpu: 85,
         utilization_memory: 60,
         fan_speed: Some(70),
         clock_graphics: 2520,
-        clock_memory: 10501,
+        clock_memory: Some(10501),
         driver_version: Some("525.105.17".to_string()),
         pci_info: PciInfo {
             domain: 0,
@@ -148,8 +161,21 @@ async fn test_gpu_summary_string() {
             device_id: 0x2684,
         },
         power_state: PowerState::Active,
+        voltage_mv: None,
+        throttle_reasons: Vec::new(),
+        ecc_errors: None,
+        processes: Vec::new(),
+        driver_bound: DriverBinding::Unbound,
+        unified_memory: false,
+        mig_parent: None,
+        mig_uuid: None,
+        uuid: None,
+        serial: None,
+        board_part_number: None,
+        vbios_version: None,
+        cuda_driver_version: None,
     };
-    
+
     let summary = gpu_summary(&test_gpu);
     assert!(summary.contains("NVIDIA RTX 4090"));
     assert!(summary.contains("75Â°C"));
@@ -0,0 +1,270 @@
+//! HTTP admin/status endpoint for the distributed coordinator, akin to Garage's `/status` admin
+//! API: a scriptable, CORS-friendly observability surface over [`DistributedCoordinator`] so
+//! operators and dashboards don't need to speak the internal RPC protocol. Gated behind the
+//! `admin_api` feature since it pulls in `axum`.
+
+use crate::distributed::{ClusterHealth, CommunicationStats, NodeInfo, SharedCoordinator};
+use crate::error::{MLError, Result};
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Json};
+use axum::routing::get;
+use axum::Router;
+use serde::Serialize;
+use subtle::ConstantTimeEq;
+use tracing::info;
+
+#[derive(Clone)]
+struct AdminState {
+    coordinator: SharedCoordinator,
+    bearer_token: String,
+}
+
+/// `GET /status` response body: cluster health plus per-node detail the bare `ClusterHealth`
+/// summary doesn't carry
+#[derive(Debug, Serialize)]
+pub struct StatusResponse {
+    pub health: ClusterHealth,
+    pub nodes: Vec<NodeInfo>,
+}
+
+/// A single entry of a flattened latency/bandwidth matrix; `HashMap<(String, String), _>` keys
+/// don't serialize to JSON object keys, so `/topology` reports the matrix as a list of these
+#[derive(Debug, Serialize)]
+pub struct TopologyEntry {
+    pub from: String,
+    pub to: String,
+    pub value: f64,
+}
+
+/// `GET /topology` response body
+#[derive(Debug, Serialize)]
+pub struct TopologyResponse {
+    pub latency_ms: Vec<TopologyEntry>,
+    pub bandwidth_mbps: Vec<TopologyEntry>,
+}
+
+/// Build the admin router exposing `/status`, `/topology`, and `/stats`. Every request must carry
+/// `Authorization: Bearer <bearer_token>`; pass the coordinator's hex `rpc_secret` so operators
+/// reuse the same credential as the RPC channel instead of managing a second one.
+pub fn admin_router(coordinator: SharedCoordinator, bearer_token: String) -> Router {
+    let state = AdminState { coordinator, bearer_token };
+    Router::new()
+        .route("/status", get(get_status))
+        .route("/topology", get(get_topology))
+        .route("/stats", get(get_stats))
+        .with_state(state)
+}
+
+/// Binds `bind_addr` and serves [`admin_router`] until the coordinator shuts down -- the same
+/// [`DistributedCoordinator::shutdown`](crate::distributed::DistributedCoordinator::shutdown)
+/// signal [`DistributedCoordinator::start_coordinator_service`](crate::distributed::DistributedCoordinator::start_coordinator_service)
+/// stops on, so a single call stops both the RPC service and its admin endpoint together. This is
+/// the entry point an operator actually calls to run the admin API; [`admin_router`] alone only
+/// builds the `Router`, it never binds a listener.
+pub async fn start_admin_server(coordinator: SharedCoordinator, bind_addr: &str, bearer_token: String) -> Result<()> {
+    let shutdown = coordinator.read().await.shutdown_signal();
+    let router = admin_router(coordinator, bearer_token);
+
+    let listener = tokio::net::TcpListener::bind(bind_addr)
+        .await
+        .map_err(|e| MLError::DistributedError(format!("failed to bind admin server to {bind_addr}: {e}")))?;
+
+    info!("Admin HTTP server listening on {}", bind_addr);
+
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async move { shutdown.cancelled().await })
+        .await
+        .map_err(|e| MLError::DistributedError(format!("admin server error: {e}")))?;
+
+    Ok(())
+}
+
+/// Check `headers` for a matching `Authorization: Bearer <expected>` value. Compared in constant
+/// time via [`ConstantTimeEq`] -- same discipline the coordinator handshake's HMAC check uses in
+/// [`crate::distributed`] -- so response timing can't be used to guess the token byte by byte.
+fn check_auth(headers: &HeaderMap, expected: &str) -> std::result::Result<(), StatusCode> {
+    let provided = headers.get(header::AUTHORIZATION).and_then(|v| v.to_str().ok());
+    let expected_header = format!("Bearer {expected}");
+    match provided {
+        Some(value) if bool::from(value.as_bytes().ct_eq(expected_header.as_bytes())) => Ok(()),
+        _ => Err(StatusCode::UNAUTHORIZED),
+    }
+}
+
+async fn get_status(State(state): State<AdminState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Err(status) = check_auth(&headers, &state.bearer_token) {
+        return status.into_response();
+    }
+
+    let coordinator = state.coordinator.read().await;
+    let response = StatusResponse {
+        health: coordinator.cluster_health_snapshot(),
+        nodes: coordinator.nodes().cloned().collect(),
+    };
+    Json(response).into_response()
+}
+
+async fn get_topology(State(state): State<AdminState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Err(status) = check_auth(&headers, &state.bearer_token) {
+        return status.into_response();
+    }
+
+    let coordinator = state.coordinator.read().await;
+    let response = match coordinator.last_topology() {
+        Some(topology) => TopologyResponse {
+            latency_ms: topology
+                .latency_matrix
+                .iter()
+                .map(|((from, to), latency)| TopologyEntry {
+                    from: from.clone(),
+                    to: to.clone(),
+                    value: latency.as_secs_f64() * 1000.0,
+                })
+                .collect(),
+            bandwidth_mbps: topology
+                .bandwidth_matrix
+                .iter()
+                .map(|((from, to), mbps)| TopologyEntry { from: from.clone(), to: to.clone(), value: *mbps as f64 })
+                .collect(),
+        },
+        None => TopologyResponse { latency_ms: Vec::new(), bandwidth_mbps: Vec::new() },
+    };
+    Json(response).into_response()
+}
+
+async fn get_stats(State(state): State<AdminState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Err(status) = check_auth(&headers, &state.bearer_token) {
+        return status.into_response();
+    }
+
+    let coordinator = state.coordinator.read().await;
+    Json(stats_snapshot(coordinator.get_communication_stats())).into_response()
+}
+
+/// `CommunicationStats` has no `Clone`, and holding the read lock across `Json`'s serialization
+/// is unnecessary once we have the values we need, so copy the (all `Copy`) fields out directly
+fn stats_snapshot(stats: &CommunicationStats) -> CommunicationStats {
+    CommunicationStats {
+        total_bytes_sent: stats.total_bytes_sent,
+        total_bytes_received: stats.total_bytes_received,
+        average_latency: stats.average_latency,
+        bandwidth_utilization: stats.bandwidth_utilization,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributed::{
+        DistributedBackend, DistributedConfig, DistributedCoordinator, DistributedStrategy, DiscoveryBackend,
+        HeartbeatConfig, NodeRole, NodeStatus,
+    };
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::RwLock;
+
+    fn test_config() -> DistributedConfig {
+        let master = NodeInfo {
+            id: "master".to_string(),
+            address: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 29500),
+            gpu_count: 1,
+            gpu_memory: vec![8_000_000_000],
+            cpu_cores: 8,
+            memory: 32_000_000_000,
+            bandwidth: Some(1000),
+            role: NodeRole::Master,
+            status: NodeStatus::Online,
+        };
+
+        DistributedConfig {
+            strategy: DistributedStrategy::DataParallel,
+            discovery: DiscoveryBackend::Static(vec![master.clone()]),
+            nodes: vec![master],
+            master_addr: "127.0.0.1".to_string(),
+            master_port: 29500,
+            world_size: 1,
+            backend: DistributedBackend::NCCL,
+            timeout: Duration::from_secs(30),
+            node_id: "master".to_string(),
+            rpc_secret: [0x42; 32],
+            heartbeat: HeartbeatConfig::default(),
+        }
+    }
+
+    fn headers_with_bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, format!("Bearer {token}").parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn check_auth_accepts_only_the_exact_bearer_token() {
+        assert!(check_auth(&HeaderMap::new(), "secret-token").is_err());
+        assert!(check_auth(&headers_with_bearer("wrong-token"), "secret-token").is_err());
+        assert!(check_auth(&headers_with_bearer("secret-token"), "secret-token").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_known_nodes_and_cluster_health() {
+        let coordinator = Arc::new(RwLock::new(DistributedCoordinator::new(test_config())));
+        let state = AdminState { coordinator, bearer_token: "secret-token".to_string() };
+
+        let response = get_status(State(state), headers_with_bearer("secret-token")).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_status_rejects_a_missing_or_wrong_bearer_token() {
+        let coordinator = Arc::new(RwLock::new(DistributedCoordinator::new(test_config())));
+        let state = AdminState { coordinator, bearer_token: "secret-token".to_string() };
+
+        let response = get_status(State(state), HeaderMap::new()).await.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_topology_is_empty_until_optimize_communication_has_run_once() {
+        let coordinator = Arc::new(RwLock::new(DistributedCoordinator::new(test_config())));
+        let state = AdminState { coordinator, bearer_token: "secret-token".to_string() };
+
+        let response = get_topology(State(state), headers_with_bearer("secret-token")).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_stats_endpoint_is_gated_the_same_way_as_status() {
+        let coordinator = Arc::new(RwLock::new(DistributedCoordinator::new(test_config())));
+        let state = AdminState { coordinator, bearer_token: "secret-token".to_string() };
+
+        let response = get_stats(State(state), headers_with_bearer("wrong-token")).await.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_start_admin_server_binds_and_serves_status_over_real_tcp() {
+        let coordinator: SharedCoordinator = Arc::new(RwLock::new(DistributedCoordinator::new(test_config())));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let server = tokio::spawn(start_admin_server(Arc::clone(&coordinator), &addr.to_string(), "secret-token".to_string()));
+
+        let response = loop {
+            match reqwest::Client::new()
+                .get(format!("http://{addr}/status"))
+                .bearer_auth("secret-token")
+                .send()
+                .await
+            {
+                Ok(response) => break response,
+                Err(_) => tokio::time::sleep(Duration::from_millis(5)).await,
+            }
+        };
+        assert_eq!(response.status(), StatusCode::OK);
+
+        coordinator.read().await.shutdown();
+        tokio::time::timeout(Duration::from_secs(5), server).await.unwrap().unwrap().unwrap();
+    }
+}
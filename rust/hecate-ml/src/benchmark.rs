@@ -0,0 +1,462 @@
+//! Statistics-driven micro-benchmarking: bootstrap confidence intervals and outlier
+//! classification for a set of timed samples
+//!
+//! A single-point latency/throughput estimate can't distinguish a real speedup from measurement
+//! noise. This module turns a set of timed samples (after discarding warmup iterations) into a
+//! mean plus a bootstrap confidence interval, and separately flags samples that are implausible
+//! outliers via Tukey fences, so a noisy benchmarking environment produces a warning instead of a
+//! confidently wrong recommendation.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Resamples drawn with replacement when building a [`ConfidenceInterval`]
+const DEFAULT_RESAMPLES: usize = 2000;
+
+/// Lower/upper percentile of the bootstrap resample means reported as the confidence interval
+/// (a 95% CI)
+const CI_LOWER_PERCENTILE: f64 = 0.025;
+const CI_UPPER_PERCENTILE: f64 = 0.975;
+
+/// A minimal seeded PRNG for bootstrap resampling: this crate has no dependency on a real RNG
+/// crate, so (matching how the rest of this codebase hand-rolls randomness, e.g.
+/// `hecate-monitor`'s `uuid::rand`) we use a xorshift64 generator seeded from the system clock by
+/// default and from an explicit seed in tests, for reproducibility.
+struct SimpleRng {
+    state: u64,
+}
+
+impl SimpleRng {
+    fn from_entropy() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .subsec_nanos() as u64;
+        Self::new(nanos.max(1))
+    }
+
+    fn new(seed: u64) -> Self {
+        Self { state: seed.max(1) }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // xorshift64
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// A uniformly-distributed index in `0..bound`
+    fn next_index(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// A bootstrap confidence interval around a sample mean
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceInterval {
+    pub mean: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl ConfidenceInterval {
+    /// Whether this interval and `other` share any values: if they don't, the difference between
+    /// the two means is unlikely to be noise
+    pub fn overlaps(&self, other: &ConfidenceInterval) -> bool {
+        self.lower <= other.upper && other.lower <= self.upper
+    }
+}
+
+/// Tukey-fence outlier counts for a sample set: a sample beyond 1.5x the IQR from the nearest
+/// quartile is a mild outlier, beyond 3x is severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OutlierReport {
+    pub mild_count: usize,
+    pub severe_count: usize,
+}
+
+impl OutlierReport {
+    /// Whether outliers are frequent/severe enough that the benchmarking environment itself
+    /// looks noisy and results should be treated with suspicion
+    pub fn is_noisy(&self, sample_count: usize) -> bool {
+        self.severe_count > 0 || self.mild_count * 5 > sample_count
+    }
+}
+
+/// A statistics-driven summary of a micro-benchmark run: the mean plus its bootstrap confidence
+/// interval, and outlier classification over the post-warmup samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchmarkReport {
+    pub sample_count: usize,
+    pub warmup_discarded: usize,
+    pub mean_secs: f64,
+    pub confidence_interval: ConfidenceInterval,
+    pub outliers: OutlierReport,
+}
+
+impl BenchmarkReport {
+    /// Human-readable warnings for a noisy environment, suitable for
+    /// [`crate::OptimizationResult::warnings`]
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.outliers.severe_count > 0 {
+            warnings.push(format!(
+                "{} of {} benchmark samples are severe Tukey-fence outliers; treat this result as noisy",
+                self.outliers.severe_count, self.sample_count
+            ));
+        } else if self.outliers.is_noisy(self.sample_count) {
+            warnings.push(format!(
+                "{} of {} benchmark samples are mild outliers; confidence interval may be wider than usual",
+                self.outliers.mild_count, self.sample_count
+            ));
+        }
+        warnings
+    }
+}
+
+fn mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+/// The `p`th percentile (0.0-1.0) of `sorted_samples`, which must already be sorted ascending,
+/// via linear interpolation between the two nearest ranks
+fn percentile(sorted_samples: &[f64], p: f64) -> f64 {
+    if sorted_samples.len() == 1 {
+        return sorted_samples[0];
+    }
+    let rank = p * (sorted_samples.len() - 1) as f64;
+    let lower_index = rank.floor() as usize;
+    let upper_index = rank.ceil() as usize;
+    let frac = rank - lower_index as f64;
+    sorted_samples[lower_index] + (sorted_samples[upper_index] - sorted_samples[lower_index]) * frac
+}
+
+/// Resample `samples` with replacement `resamples` times, taking each resample's mean, and
+/// report the 2.5th/97.5th percentile of those means as a 95% confidence interval around the
+/// overall sample mean
+fn bootstrap_confidence_interval(samples: &[f64], resamples: usize, rng: &mut SimpleRng) -> ConfidenceInterval {
+    let mut resample_means: Vec<f64> = Vec::with_capacity(resamples);
+    for _ in 0..resamples {
+        let resample_mean = (0..samples.len())
+            .map(|_| samples[rng.next_index(samples.len())])
+            .sum::<f64>()
+            / samples.len() as f64;
+        resample_means.push(resample_mean);
+    }
+    resample_means.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    ConfidenceInterval {
+        mean: mean(samples),
+        lower: percentile(&resample_means, CI_LOWER_PERCENTILE),
+        upper: percentile(&resample_means, CI_UPPER_PERCENTILE),
+    }
+}
+
+/// Classify `samples` by Tukey fences: mild outliers fall outside 1.5x the interquartile range
+/// from the nearest quartile, severe outliers outside 3x
+fn tukey_outliers(samples: &[f64]) -> OutlierReport {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let q1 = percentile(&sorted, 0.25);
+    let q3 = percentile(&sorted, 0.75);
+    let iqr = q3 - q1;
+
+    let mut report = OutlierReport::default();
+    for &sample in &sorted {
+        let distance = if sample < q1 {
+            q1 - sample
+        } else if sample > q3 {
+            sample - q3
+        } else {
+            0.0
+        };
+
+        if distance > 3.0 * iqr {
+            report.severe_count += 1;
+        } else if distance > 1.5 * iqr {
+            report.mild_count += 1;
+        }
+    }
+    report
+}
+
+/// Run a statistics-driven micro-benchmark over `samples`, discarding the first `warmup`
+/// iterations before computing the mean, bootstrap confidence interval, and outlier
+/// classification over what remains
+pub fn run_micro_benchmark(samples: &[Duration], warmup: usize) -> Option<BenchmarkReport> {
+    run_micro_benchmark_with_resamples(samples, warmup, DEFAULT_RESAMPLES)
+}
+
+fn run_micro_benchmark_with_resamples(samples: &[Duration], warmup: usize, resamples: usize) -> Option<BenchmarkReport> {
+    if samples.len() <= warmup {
+        return None;
+    }
+
+    let measured: Vec<f64> = samples[warmup..].iter().map(Duration::as_secs_f64).collect();
+    let mut rng = SimpleRng::from_entropy();
+
+    Some(BenchmarkReport {
+        sample_count: measured.len(),
+        warmup_discarded: warmup,
+        mean_secs: mean(&measured),
+        confidence_interval: bootstrap_confidence_interval(&measured, resamples, &mut rng),
+        outliers: tukey_outliers(&measured),
+    })
+}
+
+/// Scale constant in the modified Z-score, `0.6745`, the 0.75 quantile of the standard normal
+/// distribution — makes the median absolute deviation comparable to a standard deviation for
+/// normally-distributed data
+const MODIFIED_Z_SCALE: f64 = 0.6745;
+
+/// A sample beyond this many (scaled) median absolute deviations from the median is an outlier,
+/// per Iglewicz & Hoaglin's recommended cutoff
+const MODIFIED_Z_OUTLIER_THRESHOLD: f64 = 3.5;
+
+/// Modified Z-score outlier count for a sample set: robust to the non-normal, heavy-tailed
+/// distributions common in timing data, since it's based on the median and median absolute
+/// deviation (MAD) rather than the mean and standard deviation, which a single extreme sample can
+/// drag arbitrarily far
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ModifiedZOutliers {
+    pub outlier_count: usize,
+}
+
+/// A statistics-driven summary of a micro-benchmark run via raw mean/median/stddev/min/max over
+/// the post-warmup samples, plus a modified-Z-score outlier count. Complements
+/// [`BenchmarkReport`]'s bootstrap-CI/Tukey-fence approach with the simpler descriptive
+/// statistics a one-off `Profiler::benchmark` call typically wants
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchmarkSummary {
+    pub sample_count: usize,
+    pub warmup_discarded: usize,
+    pub mean_secs: f64,
+    pub median_secs: f64,
+    pub stddev_secs: f64,
+    pub min_secs: f64,
+    pub max_secs: f64,
+    pub outliers: ModifiedZOutliers,
+}
+
+/// The median of `sorted_samples`, which must already be sorted ascending
+fn median(sorted_samples: &[f64]) -> f64 {
+    percentile(sorted_samples, 0.5)
+}
+
+fn stddev(samples: &[f64], mean_value: f64) -> f64 {
+    if samples.len() < 2 {
+        return 0.0;
+    }
+    let variance =
+        samples.iter().map(|x| (x - mean_value).powi(2)).sum::<f64>() / (samples.len() - 1) as f64;
+    variance.sqrt()
+}
+
+/// Count samples whose modified Z-score `0.6745 * (x_i - median) / MAD` exceeds
+/// [`MODIFIED_Z_OUTLIER_THRESHOLD`] in absolute value. Falls back to the mean absolute deviation
+/// in place of MAD when MAD is `0.0` (e.g. more than half the samples share the exact median, so
+/// MAD alone can't discriminate), and reports no outliers if that fallback is also `0.0`.
+fn modified_z_outliers(samples: &[f64]) -> ModifiedZOutliers {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median_value = median(&sorted);
+
+    let absolute_deviations: Vec<f64> = samples.iter().map(|x| (x - median_value).abs()).collect();
+    let mut sorted_absolute_deviations = absolute_deviations.clone();
+    sorted_absolute_deviations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mad = median(&sorted_absolute_deviations);
+
+    let scale = if mad > 0.0 { mad } else { mean(&absolute_deviations) };
+    if scale <= 0.0 {
+        return ModifiedZOutliers { outlier_count: 0 };
+    }
+
+    let outlier_count = samples
+        .iter()
+        .filter(|&&x| (MODIFIED_Z_SCALE * (x - median_value) / scale).abs() > MODIFIED_Z_OUTLIER_THRESHOLD)
+        .count();
+    ModifiedZOutliers { outlier_count }
+}
+
+/// Run `warmup` discarded iterations of `f`, then `iters` timed iterations, and summarize the
+/// timed samples' mean, median, standard deviation, min, max, and modified-Z-score outlier count.
+/// Returns `None` if `iters` is `0`, since there would be nothing to summarize.
+pub fn run_benchmark<F: FnMut()>(mut f: F, warmup: usize, iters: usize) -> Option<BenchmarkSummary> {
+    for _ in 0..warmup {
+        f();
+    }
+    if iters == 0 {
+        return None;
+    }
+
+    let mut samples = Vec::with_capacity(iters);
+    for _ in 0..iters {
+        let start = std::time::Instant::now();
+        f();
+        samples.push(start.elapsed().as_secs_f64());
+    }
+
+    let mut sorted = samples.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mean_value = mean(&samples);
+
+    Some(BenchmarkSummary {
+        sample_count: samples.len(),
+        warmup_discarded: warmup,
+        mean_secs: mean_value,
+        median_secs: median(&sorted),
+        stddev_secs: stddev(&samples, mean_value),
+        min_secs: sorted[0],
+        max_secs: sorted[sorted.len() - 1],
+        outliers: modified_z_outliers(&samples),
+    })
+}
+
+/// Whether `candidate`'s improvement over `baseline` is statistically real rather than noise: the
+/// two confidence intervals must not overlap, and the candidate's mean must actually be better
+/// (lower, since these are latency samples)
+pub fn is_significant_speedup(baseline: &ConfidenceInterval, candidate: &ConfidenceInterval) -> bool {
+    !baseline.overlaps(candidate) && candidate.mean < baseline.mean
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn durations_secs(values: &[f64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_secs_f64(*v)).collect()
+    }
+
+    #[test]
+    fn test_run_micro_benchmark_discards_warmup_samples() {
+        let samples = durations_secs(&[10.0, 10.0, 1.0, 1.0, 1.0, 1.0]);
+        let report = run_micro_benchmark_with_resamples(&samples, 2, 500).unwrap();
+
+        assert_eq!(report.sample_count, 4);
+        assert_eq!(report.warmup_discarded, 2);
+        assert!((report.mean_secs - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_micro_benchmark_returns_none_without_enough_samples() {
+        let samples = durations_secs(&[1.0, 1.0]);
+        assert!(run_micro_benchmark_with_resamples(&samples, 2, 500).is_none());
+    }
+
+    #[test]
+    fn test_bootstrap_confidence_interval_is_tight_for_identical_samples() {
+        let samples = vec![2.0; 50];
+        let mut rng = SimpleRng::new(42);
+        let ci = bootstrap_confidence_interval(&samples, 500, &mut rng);
+
+        assert!((ci.mean - 2.0).abs() < 1e-9);
+        assert!((ci.lower - 2.0).abs() < 1e-9);
+        assert!((ci.upper - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bootstrap_confidence_interval_widens_with_variance() {
+        let tight = vec![1.0, 1.0, 1.0, 1.0, 1.0];
+        let wide = vec![0.1, 0.5, 1.0, 1.5, 10.0];
+
+        let mut rng = SimpleRng::new(7);
+        let tight_ci = bootstrap_confidence_interval(&tight, 1000, &mut rng);
+        let wide_ci = bootstrap_confidence_interval(&wide, 1000, &mut rng);
+
+        assert!(wide_ci.upper - wide_ci.lower > tight_ci.upper - tight_ci.lower);
+    }
+
+    #[test]
+    fn test_tukey_outliers_flags_a_severe_outlier() {
+        let mut samples = vec![1.0; 20];
+        samples.push(1000.0);
+
+        let report = tukey_outliers(&samples);
+        assert_eq!(report.severe_count, 1);
+        assert_eq!(report.mild_count, 0);
+    }
+
+    #[test]
+    fn test_tukey_outliers_reports_none_for_a_tight_cluster() {
+        let samples = vec![1.0, 1.01, 0.99, 1.02, 0.98, 1.0, 1.01];
+        let report = tukey_outliers(&samples);
+        assert_eq!(report.mild_count, 0);
+        assert_eq!(report.severe_count, 0);
+    }
+
+    #[test]
+    fn test_confidence_interval_overlaps() {
+        let a = ConfidenceInterval { mean: 1.0, lower: 0.5, upper: 1.5 };
+        let b = ConfidenceInterval { mean: 1.4, lower: 1.2, upper: 1.6 };
+        let c = ConfidenceInterval { mean: 5.0, lower: 4.5, upper: 5.5 };
+
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&c));
+    }
+
+    #[test]
+    fn test_is_significant_speedup_requires_non_overlap_and_direction() {
+        let baseline = ConfidenceInterval { mean: 2.0, lower: 1.8, upper: 2.2 };
+        let real_speedup = ConfidenceInterval { mean: 1.0, lower: 0.9, upper: 1.1 };
+        let noisy_speedup = ConfidenceInterval { mean: 1.9, lower: 1.5, upper: 2.3 };
+        let regression = ConfidenceInterval { mean: 3.0, lower: 2.8, upper: 3.2 };
+
+        assert!(is_significant_speedup(&baseline, &real_speedup));
+        assert!(!is_significant_speedup(&baseline, &noisy_speedup));
+        assert!(!is_significant_speedup(&baseline, &regression));
+    }
+
+    #[test]
+    fn test_modified_z_outliers_flags_a_single_extreme_sample() {
+        let mut samples = vec![1.0; 20];
+        samples.push(1000.0);
+
+        let report = modified_z_outliers(&samples);
+        assert_eq!(report.outlier_count, 1);
+    }
+
+    #[test]
+    fn test_modified_z_outliers_reports_none_for_a_tight_cluster() {
+        let samples = vec![1.0, 1.01, 0.99, 1.02, 0.98, 1.0, 1.01];
+        assert_eq!(modified_z_outliers(&samples).outlier_count, 0);
+    }
+
+    #[test]
+    fn test_modified_z_outliers_falls_back_to_mean_absolute_deviation_when_mad_is_zero() {
+        // More than half the samples equal the median (2.0), so MAD is 0 and the mean-absolute-
+        // deviation fallback kicks in; the 50.0 sample should still be flagged.
+        let samples = vec![2.0, 2.0, 2.0, 2.0, 2.0, 50.0];
+        assert_eq!(modified_z_outliers(&samples).outlier_count, 1);
+    }
+
+    #[test]
+    fn test_run_benchmark_discards_warmup_and_summarizes_timed_samples() {
+        let mut total_calls = 0;
+        let summary = run_benchmark(|| total_calls += 1, 2, 4).unwrap();
+
+        assert_eq!(total_calls, 6); // 2 warmup + 4 timed
+        assert_eq!(summary.warmup_discarded, 2);
+        assert_eq!(summary.sample_count, 4);
+        assert!(summary.min_secs <= summary.median_secs);
+        assert!(summary.median_secs <= summary.max_secs);
+    }
+
+    #[test]
+    fn test_run_benchmark_returns_none_for_zero_iterations() {
+        assert!(run_benchmark(|| {}, 2, 0).is_none());
+    }
+
+    #[test]
+    fn test_outlier_report_is_noisy_on_severe_or_frequent_mild_outliers() {
+        let none = OutlierReport { mild_count: 0, severe_count: 0 };
+        let one_severe = OutlierReport { mild_count: 0, severe_count: 1 };
+        let frequent_mild = OutlierReport { mild_count: 3, severe_count: 0 };
+
+        assert!(!none.is_noisy(10));
+        assert!(one_severe.is_noisy(10));
+        assert!(frequent_mild.is_noisy(10));
+    }
+}
@@ -0,0 +1,179 @@
+//! Human-friendly byte-size parsing and percentage-of-available-memory budgets.
+//!
+//! [`OptimizationConfig::max_memory_usage`](crate::OptimizationConfig::max_memory_usage) used to
+//! be a raw fraction (`0.8`) while the rest of the crate sprinkled magic byte constants
+//! (`1024 * 1024 * 1024`) wherever a limit was needed. [`ByteSize`] parses strings like `"6GiB"`
+//! or `"512MiB"`, and [`MemoryBudget`] lets a limit be expressed either that way or as a
+//! percentage (`"80%"`), resolved against whatever memory turns out to be available.
+
+use crate::error::{MLError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Fallback fraction of available memory to budget when no limit is configured, mirroring how
+/// indexing workloads conventionally leave a third of memory headroom for the OS page cache and
+/// other processes.
+pub const DEFAULT_MEMORY_FRACTION: f32 = 2.0 / 3.0;
+
+/// An absolute byte count, parsed from a human-friendly string using binary (1024-based) unit
+/// multipliers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ByteSize(pub u64);
+
+impl ByteSize {
+    pub fn bytes(self) -> u64 {
+        self.0
+    }
+
+    /// Parse a size like `"6GiB"`, `"512MiB"`, `"128KiB"`, or a bare `"100"` (bytes). Unit
+    /// matching is case-insensitive and also accepts the single-letter short forms (`"6G"`).
+    pub fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        let split_at = trimmed
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(trimmed.len());
+        let (number, unit) = trimmed.split_at(split_at);
+
+        let number: f64 = number
+            .parse()
+            .map_err(|_| MLError::InvalidConfiguration(format!("invalid byte size {input:?}")))?;
+
+        let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "b" => 1,
+            "k" | "kib" => 1024,
+            "m" | "mib" => 1024u64.pow(2),
+            "g" | "gib" => 1024u64.pow(3),
+            "t" | "tib" => 1024u64.pow(4),
+            other => {
+                return Err(MLError::InvalidConfiguration(format!(
+                    "unknown byte size unit {other:?} in {input:?}"
+                )))
+            }
+        };
+
+        Ok(ByteSize((number * multiplier as f64) as u64))
+    }
+}
+
+impl fmt::Display for ByteSize {
+    /// Same unit ladder as [`crate::MLOptimizer::format_bytes`], so a parsed and a formatted
+    /// `ByteSize` round-trip through a human-readable string consistently.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+        let mut size = self.0 as f64;
+        let mut unit_index = 0;
+        while size >= 1024.0 && unit_index < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit_index += 1;
+        }
+        write!(f, "{:.2} {}", size, UNITS[unit_index])
+    }
+}
+
+/// A memory limit expressed either as an absolute size or a percentage of whatever memory turns
+/// out to be available.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MemoryBudget {
+    Absolute(ByteSize),
+    Percentage(f32),
+}
+
+impl MemoryBudget {
+    /// Parse `"6GiB"`/`"512MiB"`-style absolute sizes or a trailing-`%` percentage like `"80%"`.
+    pub fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        if let Some(pct) = trimmed.strip_suffix('%') {
+            let pct: f32 = pct
+                .trim()
+                .parse()
+                .map_err(|_| MLError::InvalidConfiguration(format!("invalid percentage {input:?}")))?;
+            return Ok(MemoryBudget::Percentage(pct / 100.0));
+        }
+        Ok(MemoryBudget::Absolute(ByteSize::parse(trimmed)?))
+    }
+
+    /// Resolve this budget against `available_bytes`; an absolute budget is capped at whatever is
+    /// actually available so it can never ask for more memory than exists.
+    pub fn resolve(self, available_bytes: u64) -> u64 {
+        match self {
+            MemoryBudget::Absolute(size) => size.bytes().min(available_bytes),
+            MemoryBudget::Percentage(fraction) => (available_bytes as f64 * fraction as f64) as u64,
+        }
+    }
+
+    /// A plain 0.0-1.0 fraction for APIs (e.g. a framework's per-process memory-fraction knob)
+    /// that want a percentage rather than a byte count. Absolute sizes have no such fraction
+    /// without knowing total available memory, so they fall back to [`DEFAULT_MEMORY_FRACTION`].
+    pub fn fraction_hint(self) -> f32 {
+        match self {
+            MemoryBudget::Percentage(fraction) => fraction,
+            MemoryBudget::Absolute(_) => DEFAULT_MEMORY_FRACTION,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_size_parses_binary_units() {
+        assert_eq!(ByteSize::parse("6GiB").unwrap(), ByteSize(6 * 1024u64.pow(3)));
+        assert_eq!(ByteSize::parse("512MiB").unwrap(), ByteSize(512 * 1024u64.pow(2)));
+        assert_eq!(ByteSize::parse("128KiB").unwrap(), ByteSize(128 * 1024));
+        assert_eq!(ByteSize::parse("100").unwrap(), ByteSize(100));
+    }
+
+    #[test]
+    fn test_byte_size_parse_is_case_insensitive_and_accepts_short_units() {
+        assert_eq!(ByteSize::parse("6gib").unwrap(), ByteSize(6 * 1024u64.pow(3)));
+        assert_eq!(ByteSize::parse("6G").unwrap(), ByteSize(6 * 1024u64.pow(3)));
+    }
+
+    #[test]
+    fn test_byte_size_parse_accepts_fractional_values() {
+        assert_eq!(ByteSize::parse("1.5GiB").unwrap(), ByteSize((1.5 * 1024f64.powi(3)) as u64));
+    }
+
+    #[test]
+    fn test_byte_size_parse_rejects_an_unknown_unit() {
+        assert!(ByteSize::parse("6XiB").is_err());
+    }
+
+    #[test]
+    fn test_byte_size_display_round_trips_through_parse() {
+        let size = ByteSize::parse("6GiB").unwrap();
+        assert_eq!(size.to_string(), "6.00 GiB");
+    }
+
+    #[test]
+    fn test_memory_budget_parses_percentage() {
+        let budget = MemoryBudget::parse("80%").unwrap();
+        assert_eq!(budget, MemoryBudget::Percentage(0.8));
+    }
+
+    #[test]
+    fn test_memory_budget_parses_absolute_size() {
+        let budget = MemoryBudget::parse("6GiB").unwrap();
+        assert_eq!(budget, MemoryBudget::Absolute(ByteSize(6 * 1024u64.pow(3))));
+    }
+
+    #[test]
+    fn test_memory_budget_resolve_percentage() {
+        let budget = MemoryBudget::Percentage(0.5);
+        assert_eq!(budget.resolve(1000), 500);
+    }
+
+    #[test]
+    fn test_memory_budget_resolve_absolute_is_capped_at_available() {
+        let budget = MemoryBudget::Absolute(ByteSize(10_000));
+        assert_eq!(budget.resolve(5_000), 5_000);
+        assert_eq!(budget.resolve(20_000), 10_000);
+    }
+
+    #[test]
+    fn test_memory_budget_fraction_hint() {
+        assert_eq!(MemoryBudget::Percentage(0.6).fraction_hint(), 0.6);
+        assert_eq!(MemoryBudget::Absolute(ByteSize(1)).fraction_hint(), DEFAULT_MEMORY_FRACTION);
+    }
+}
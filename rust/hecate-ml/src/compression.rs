@@ -0,0 +1,452 @@
+//! Gradient compression codecs behind [`CompressionAlgorithm`](crate::distributed::CompressionAlgorithm).
+//!
+//! `configure_compression` in [`distributed`](crate::distributed) only ever picked an algorithm;
+//! nothing actually compressed a gradient. [`GradientCompressor`] is the trait concrete codecs
+//! implement, and [`CompressedPayload`] is what they produce, byte-counted by
+//! [`CompressedPayload::byte_size`] so callers can feed real numbers into
+//! `CommunicationStats.total_bytes_sent`.
+//!
+//! [`SparsificationCompressor`] carries an error-feedback residual across calls (the dropped
+//! entries from one step are added back into the gradient before the next step's top-k
+//! selection), which is what keeps top-k sparsification from silently discarding information it
+//! never sends. `compress` takes `&self` per the shared [`GradientCompressor`] signature, so the
+//! residual lives behind a `RefCell`.
+//!
+//! [`compressor_for`] is what actually wires a [`CompressionAlgorithm`] choice to one of these
+//! codecs; [`DistributedCoordinator::compress_and_record_send`](crate::distributed::DistributedCoordinator::compress_and_record_send)
+//! is the one real gradient-send call site that uses it, so picking a non-`None` algorithm has an
+//! effect on real gradients instead of only being recorded in [`CompressionConfig`](crate::distributed::CompressionConfig).
+
+use crate::distributed::CompressionAlgorithm;
+use std::cell::RefCell;
+
+/// A compressed gradient tensor, ready to ship over the wire
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompressedPayload {
+    /// Compression disabled; the tensor is carried verbatim
+    Raw(Vec<f32>),
+    /// Per-block int8 quantization: `scales[i]` is the dequantization scale for residual block `i`
+    Quantized { block_size: usize, scales: Vec<f32>, residuals: Vec<i8> },
+    /// The top-k-by-magnitude `(index, value)` pairs of an error-fed-back gradient, sorted by index
+    Sparse { indices: Vec<u32>, values: Vec<f32> },
+    /// Rank-`rank` factorization `M ≈ U · V` of a `rows x cols` matrix. `V` is stored already
+    /// transposed (shape `rank x cols`), so reconstruction is a single `U · V` multiply.
+    LowRank { rows: usize, cols: usize, rank: usize, u: Vec<f32>, v: Vec<f32> },
+}
+
+impl CompressedPayload {
+    /// Approximate wire size in bytes
+    pub fn byte_size(&self) -> u64 {
+        const F32_BYTES: u64 = std::mem::size_of::<f32>() as u64;
+        const U32_BYTES: u64 = std::mem::size_of::<u32>() as u64;
+
+        match self {
+            Self::Raw(values) => values.len() as u64 * F32_BYTES,
+            Self::Quantized { scales, residuals, .. } => {
+                scales.len() as u64 * F32_BYTES + residuals.len() as u64
+            }
+            Self::Sparse { indices, values } => {
+                indices.len() as u64 * U32_BYTES + values.len() as u64 * F32_BYTES
+            }
+            Self::LowRank { u, v, .. } => (u.len() as u64 + v.len() as u64) * F32_BYTES,
+        }
+    }
+}
+
+/// Compresses and decompresses gradient tensors before/after network transfer
+pub trait GradientCompressor {
+    /// Compress `tensor` into a wire-ready [`CompressedPayload`]
+    fn compress(&self, tensor: &[f32]) -> CompressedPayload;
+
+    /// Reconstruct a `len`-element tensor from a payload this compressor produced. Out-of-range
+    /// indices in `payload` are silently dropped rather than panicking, so a payload produced for
+    /// a different-sized tensor degrades instead of crashing the receiver.
+    fn decompress(&self, payload: &CompressedPayload, len: usize) -> Vec<f32>;
+}
+
+/// The codec for [`CompressionAlgorithm::None`]: carries the tensor verbatim so "compression
+/// disabled" doesn't need a special case at every call site.
+struct NoopCompressor;
+
+impl GradientCompressor for NoopCompressor {
+    fn compress(&self, tensor: &[f32]) -> CompressedPayload {
+        CompressedPayload::Raw(tensor.to_vec())
+    }
+
+    fn decompress(&self, payload: &CompressedPayload, len: usize) -> Vec<f32> {
+        let CompressedPayload::Raw(values) = payload else {
+            return vec![0.0; len];
+        };
+        let mut out = values.clone();
+        out.resize(len, 0.0);
+        out
+    }
+}
+
+/// Per-block size [`QuantizationCompressor`] uses when dispatched via [`compressor_for`]
+const DEFAULT_QUANTIZATION_BLOCK_SIZE: usize = 256;
+
+/// Fraction of entries [`SparsificationCompressor`] keeps when dispatched via [`compressor_for`]
+const DEFAULT_SPARSIFICATION_KEEP_RATIO: f32 = 0.1;
+
+/// Construct the codec behind `algorithm` for a gradient tensor with `len` elements. Gradients
+/// reach the communication layer already flattened, with no 2D shape of their own, so
+/// [`LowRankCompressor`] treats one as a `1 x len` matrix -- rank-1 is the only factorization that
+/// makes sense without threading each layer's actual shape through the send path.
+pub fn compressor_for(algorithm: CompressionAlgorithm, len: usize) -> Box<dyn GradientCompressor> {
+    match algorithm {
+        CompressionAlgorithm::None => Box::new(NoopCompressor),
+        CompressionAlgorithm::Quantization => Box::new(QuantizationCompressor::new(DEFAULT_QUANTIZATION_BLOCK_SIZE)),
+        CompressionAlgorithm::Sparsification => {
+            Box::new(SparsificationCompressor::new(DEFAULT_SPARSIFICATION_KEEP_RATIO))
+        }
+        CompressionAlgorithm::LowRank => Box::new(LowRankCompressor::new(1, len, 1)),
+    }
+}
+
+/// Per-block int8 quantization: each block of `block_size` values is scaled by
+/// `max(|x in block|) / 127` so the largest magnitude in the block maps to ±127
+pub struct QuantizationCompressor {
+    block_size: usize,
+}
+
+impl QuantizationCompressor {
+    pub fn new(block_size: usize) -> Self {
+        Self { block_size: block_size.max(1) }
+    }
+}
+
+impl GradientCompressor for QuantizationCompressor {
+    fn compress(&self, tensor: &[f32]) -> CompressedPayload {
+        let block_count = tensor.len().saturating_add(self.block_size - 1) / self.block_size;
+        let mut scales = Vec::with_capacity(block_count);
+        let mut residuals = Vec::with_capacity(tensor.len());
+
+        for block in tensor.chunks(self.block_size) {
+            let max_abs = block.iter().fold(0.0f32, |acc, &x| acc.max(x.abs()));
+            let scale = if max_abs > 0.0 { max_abs / 127.0 } else { 1.0 };
+            scales.push(scale);
+            residuals.extend(block.iter().map(|&x| (x / scale).round().clamp(-127.0, 127.0) as i8));
+        }
+
+        CompressedPayload::Quantized { block_size: self.block_size, scales, residuals }
+    }
+
+    fn decompress(&self, payload: &CompressedPayload, len: usize) -> Vec<f32> {
+        let CompressedPayload::Quantized { block_size, scales, residuals } = payload else {
+            return vec![0.0; len];
+        };
+
+        let mut out = Vec::with_capacity(residuals.len());
+        for (block_index, block) in residuals.chunks(*block_size).enumerate() {
+            let scale = scales.get(block_index).copied().unwrap_or(1.0);
+            out.extend(block.iter().map(|&q| q as f32 * scale));
+        }
+        out.truncate(len);
+        out
+    }
+}
+
+/// Top-k-by-magnitude sparsification with error feedback: entries dropped in one `compress` call
+/// are accumulated into the residual and re-considered on the next call, so information is
+/// delayed rather than lost.
+pub struct SparsificationCompressor {
+    /// Fraction of entries kept per call, in `(0.0, 1.0]`
+    keep_ratio: f32,
+    residual: RefCell<Vec<f32>>,
+}
+
+impl SparsificationCompressor {
+    pub fn new(keep_ratio: f32) -> Self {
+        Self { keep_ratio: keep_ratio.clamp(0.0, 1.0), residual: RefCell::new(Vec::new()) }
+    }
+}
+
+impl GradientCompressor for SparsificationCompressor {
+    fn compress(&self, tensor: &[f32]) -> CompressedPayload {
+        if tensor.is_empty() {
+            return CompressedPayload::Sparse { indices: Vec::new(), values: Vec::new() };
+        }
+
+        let mut residual = self.residual.borrow_mut();
+        if residual.len() != tensor.len() {
+            *residual = vec![0.0; tensor.len()];
+        }
+
+        let mut combined: Vec<f32> = tensor.iter().zip(residual.iter()).map(|(g, r)| g + r).collect();
+
+        let k = ((self.keep_ratio * combined.len() as f32).round() as usize).clamp(1, combined.len());
+        let mut order: Vec<usize> = (0..combined.len()).collect();
+        order.sort_unstable_by(|&a, &b| combined[b].abs().total_cmp(&combined[a].abs()));
+        let mut selected = order[..k].to_vec();
+        selected.sort_unstable();
+
+        let indices: Vec<u32> = selected.iter().map(|&i| i as u32).collect();
+        let values: Vec<f32> = selected.iter().map(|&i| combined[i]).collect();
+
+        for &i in &selected {
+            combined[i] = 0.0;
+        }
+        *residual = combined;
+
+        CompressedPayload::Sparse { indices, values }
+    }
+
+    fn decompress(&self, payload: &CompressedPayload, len: usize) -> Vec<f32> {
+        let CompressedPayload::Sparse { indices, values } = payload else {
+            return vec![0.0; len];
+        };
+
+        let mut out = vec![0.0; len];
+        for (&index, &value) in indices.iter().zip(values.iter()) {
+            if let Some(slot) = out.get_mut(index as usize) {
+                *slot = value;
+            }
+        }
+        out
+    }
+}
+
+/// Rank-`rank` factorization of a `rows x cols` gradient matrix via randomized power iteration:
+/// a random projection is repeatedly multiplied by `M` and `Mᵀ` to sharpen it towards `M`'s
+/// dominant row space, then orthonormalized into `U`.
+pub struct LowRankCompressor {
+    rows: usize,
+    cols: usize,
+    rank: usize,
+    power_iterations: usize,
+}
+
+impl LowRankCompressor {
+    pub fn new(rows: usize, cols: usize, rank: usize) -> Self {
+        Self { rows, cols, rank: rank.max(1).min(rows.min(cols).max(1)), power_iterations: 2 }
+    }
+}
+
+impl GradientCompressor for LowRankCompressor {
+    fn compress(&self, tensor: &[f32]) -> CompressedPayload {
+        assert_eq!(
+            tensor.len(),
+            self.rows * self.cols,
+            "LowRankCompressor configured for a {}x{} matrix but got {} elements",
+            self.rows,
+            self.cols,
+            tensor.len()
+        );
+
+        let mut omega: Vec<f32> = (0..self.cols * self.rank).map(|_| rand::random::<f32>() * 2.0 - 1.0).collect();
+        let mut y = matmul(tensor, self.rows, self.cols, &omega, self.cols, self.rank);
+
+        for _ in 0..self.power_iterations {
+            let mt_y = matmul_at_b(tensor, self.rows, self.cols, &y, self.rows, self.rank);
+            y = matmul(tensor, self.rows, self.cols, &mt_y, self.cols, self.rank);
+        }
+        omega.clear(); // no longer needed; avoid an "unused after reassignment" footgun for readers
+
+        let u = orthonormalize_columns(&y, self.rows, self.rank);
+        let v = matmul_at_b(&u, self.rows, self.rank, tensor, self.rows, self.cols);
+
+        CompressedPayload::LowRank { rows: self.rows, cols: self.cols, rank: self.rank, u, v }
+    }
+
+    fn decompress(&self, payload: &CompressedPayload, len: usize) -> Vec<f32> {
+        let CompressedPayload::LowRank { rows, cols, rank, u, v } = payload else {
+            return vec![0.0; len];
+        };
+
+        let mut out = matmul(u, *rows, *rank, v, *rank, *cols);
+        out.truncate(len);
+        out
+    }
+}
+
+/// Dense `(a_rows x a_cols) * (b_rows x b_cols)` matrix multiply; `a_cols` must equal `b_rows`
+fn matmul(a: &[f32], a_rows: usize, a_cols: usize, b: &[f32], b_rows: usize, b_cols: usize) -> Vec<f32> {
+    debug_assert_eq!(a_cols, b_rows);
+    let mut out = vec![0.0f32; a_rows * b_cols];
+    for i in 0..a_rows {
+        for k in 0..a_cols {
+            let a_ik = a[i * a_cols + k];
+            if a_ik == 0.0 {
+                continue;
+            }
+            for j in 0..b_cols {
+                out[i * b_cols + j] += a_ik * b[k * b_cols + j];
+            }
+        }
+    }
+    out
+}
+
+/// Dense `Aᵀ * B` where `A` is `a_rows x a_cols` and `B` is `b_rows x b_cols`, `a_rows == b_rows`
+fn matmul_at_b(a: &[f32], a_rows: usize, a_cols: usize, b: &[f32], b_rows: usize, b_cols: usize) -> Vec<f32> {
+    debug_assert_eq!(a_rows, b_rows);
+    let mut out = vec![0.0f32; a_cols * b_cols];
+    for k in 0..a_rows {
+        for i in 0..a_cols {
+            let a_ki = a[k * a_cols + i];
+            if a_ki == 0.0 {
+                continue;
+            }
+            for j in 0..b_cols {
+                out[i * b_cols + j] += a_ki * b[k * b_cols + j];
+            }
+        }
+    }
+    out
+}
+
+/// Gram-Schmidt orthonormalization of the columns of a `rows x cols` matrix
+fn orthonormalize_columns(m: &[f32], rows: usize, cols: usize) -> Vec<f32> {
+    let mut result = m.to_vec();
+    for c in 0..cols {
+        for prev in 0..c {
+            let dot: f32 = (0..rows).map(|r| result[r * cols + c] * result[r * cols + prev]).sum();
+            for r in 0..rows {
+                result[r * cols + c] -= dot * result[r * cols + prev];
+            }
+        }
+        let norm: f32 = (0..rows).map(|r| result[r * cols + c].powi(2)).sum::<f32>().sqrt();
+        if norm > 1e-8 {
+            for r in 0..rows {
+                result[r * cols + c] /= norm;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantization_round_trip_is_close_to_the_original() {
+        let tensor = vec![0.1, -0.5, 2.0, -2.0, 0.0, 1.3];
+        let compressor = QuantizationCompressor::new(3);
+
+        let payload = compressor.compress(&tensor);
+        let restored = compressor.decompress(&payload, tensor.len());
+
+        assert_eq!(restored.len(), tensor.len());
+        for (original, restored) in tensor.iter().zip(restored.iter()) {
+            assert!((original - restored).abs() < 0.05, "{original} vs {restored}");
+        }
+    }
+
+    #[test]
+    fn test_quantization_handles_an_all_zero_block_without_dividing_by_zero() {
+        let tensor = vec![0.0; 4];
+        let compressor = QuantizationCompressor::new(4);
+
+        let payload = compressor.compress(&tensor);
+        let restored = compressor.decompress(&payload, tensor.len());
+
+        assert_eq!(restored, tensor);
+    }
+
+    #[test]
+    fn test_sparsification_keeps_only_the_top_k_entries_by_magnitude() {
+        let tensor = vec![5.0, -1.0, 0.2, 4.0, -0.1];
+        let compressor = SparsificationCompressor::new(0.4); // keep 2 of 5
+
+        let payload = compressor.compress(&tensor);
+        match &payload {
+            CompressedPayload::Sparse { indices, values } => {
+                assert_eq!(indices, &vec![0, 3]);
+                assert_eq!(values, &vec![5.0, 4.0]);
+            }
+            other => panic!("expected Sparse payload, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_sparsification_error_feedback_eventually_surfaces_a_persistently_small_gradient() {
+        // A small but nonzero entry that never wins a single round of top-1 selection on its own
+        // should still surface once its accumulated residual becomes large enough.
+        let compressor = SparsificationCompressor::new(0.2); // keep 1 of 5
+        let tensor = vec![3.0, 0.3, 0.0, 0.0, 0.0];
+
+        let mut index_1_sent = false;
+        for _ in 0..20 {
+            if let CompressedPayload::Sparse { indices, .. } = compressor.compress(&tensor) {
+                if indices.contains(&1) {
+                    index_1_sent = true;
+                    break;
+                }
+            }
+        }
+
+        assert!(index_1_sent, "residual for index 1 should eventually exceed index 0's gradient");
+    }
+
+    #[test]
+    fn test_sparsification_decompress_reconstructs_a_sparse_vector() {
+        let payload = CompressedPayload::Sparse { indices: vec![1, 3], values: vec![2.5, -1.5] };
+        let compressor = SparsificationCompressor::new(0.5);
+
+        let restored = compressor.decompress(&payload, 5);
+        assert_eq!(restored, vec![0.0, 2.5, 0.0, -1.5, 0.0]);
+    }
+
+    #[test]
+    fn test_low_rank_compression_approximately_reconstructs_a_rank_one_matrix() {
+        // An exactly rank-1 matrix: outer product of [1, 2, 3] and [1, -1]
+        let rows = 3;
+        let cols = 2;
+        let row = [1.0f32, 2.0, 3.0];
+        let col = [1.0f32, -1.0];
+        let tensor: Vec<f32> = row.iter().flat_map(|&r| col.iter().map(move |&c| r * c)).collect();
+
+        let compressor = LowRankCompressor::new(rows, cols, 1);
+        let payload = compressor.compress(&tensor);
+        let restored = compressor.decompress(&payload, tensor.len());
+
+        assert_eq!(restored.len(), tensor.len());
+        for (original, restored) in tensor.iter().zip(restored.iter()) {
+            assert!((original - restored).abs() < 0.01, "{original} vs {restored}");
+        }
+    }
+
+    #[test]
+    fn test_compressor_for_dispatches_each_algorithm_to_its_matching_payload_shape() {
+        let tensor = vec![1.0, -2.0, 3.0, -4.0];
+
+        let none = compressor_for(CompressionAlgorithm::None, tensor.len()).compress(&tensor);
+        assert!(matches!(none, CompressedPayload::Raw(_)));
+
+        let quantized = compressor_for(CompressionAlgorithm::Quantization, tensor.len()).compress(&tensor);
+        assert!(matches!(quantized, CompressedPayload::Quantized { .. }));
+
+        let sparse = compressor_for(CompressionAlgorithm::Sparsification, tensor.len()).compress(&tensor);
+        assert!(matches!(sparse, CompressedPayload::Sparse { .. }));
+
+        let low_rank = compressor_for(CompressionAlgorithm::LowRank, tensor.len()).compress(&tensor);
+        assert!(matches!(low_rank, CompressedPayload::LowRank { .. }));
+    }
+
+    #[test]
+    fn test_compressor_for_none_round_trips_the_tensor_verbatim() {
+        let tensor = vec![1.0, -2.0, 3.0];
+        let compressor = compressor_for(CompressionAlgorithm::None, tensor.len());
+
+        let payload = compressor.compress(&tensor);
+        let restored = compressor.decompress(&payload, tensor.len());
+
+        assert_eq!(restored, tensor);
+    }
+
+    #[test]
+    fn test_byte_size_reflects_each_payload_shape() {
+        assert_eq!(CompressedPayload::Raw(vec![0.0; 4]).byte_size(), 16);
+        assert_eq!(
+            CompressedPayload::Quantized { block_size: 2, scales: vec![0.1, 0.2], residuals: vec![1, 2, 3, 4] }
+                .byte_size(),
+            2 * 4 + 4
+        );
+        assert_eq!(
+            CompressedPayload::Sparse { indices: vec![0, 1], values: vec![1.0, 2.0] }.byte_size(),
+            2 * 4 + 2 * 4
+        );
+    }
+}
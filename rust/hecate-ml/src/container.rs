@@ -0,0 +1,147 @@
+//! Reproducible container image spec generation for a tuned workload
+//!
+//! Turns an [`crate::OptimizationResult`] into a build-ready container: a multi-stage Dockerfile
+//! (a toolchain-heavy `builder` stage that installs pinned wheels, and a slim `runtime` stage that
+//! copies only the installed packages and generated config/launcher files out of it) plus a pinned
+//! dependency manifest, so a tuned workload can be shipped as-is.
+
+use crate::{FrameworkType, Implementation, OptimizationResult};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+/// Base images, wheel index, and pinned packages for a framework/accelerator combination
+struct ImageProfile {
+    builder_image: &'static str,
+    runtime_image: &'static str,
+    wheel_index: Option<&'static str>,
+    packages: &'static [&'static str],
+}
+
+const CUDA_BUILDER_IMAGE: &str = "nvidia/cuda:12.1.0-devel-ubuntu22.04";
+const CUDA_RUNTIME_IMAGE: &str = "nvidia/cuda:12.1.0-runtime-ubuntu22.04";
+const CPU_IMAGE: &str = "python:3.11-slim";
+
+/// Pick a base image pair, wheel index, and pinned package list for `framework`, taking the
+/// CPU-only path (slim base, CPU-only wheel index/package) when `cuda_support` is `false` and the
+/// matching CUDA runtime base otherwise
+fn image_profile(framework: FrameworkType, cuda_support: bool) -> ImageProfile {
+    let (builder_image, runtime_image) =
+        if cuda_support { (CUDA_BUILDER_IMAGE, CUDA_RUNTIME_IMAGE) } else { (CPU_IMAGE, CPU_IMAGE) };
+
+    let (wheel_index, packages): (Option<&'static str>, &'static [&'static str]) = match (framework, cuda_support) {
+        (FrameworkType::PyTorch, true) => (Some("https://download.pytorch.org/whl/cu121"), &["torch"]),
+        (FrameworkType::PyTorch, false) => (Some("https://download.pytorch.org/whl/cpu"), &["torch"]),
+        (FrameworkType::TensorFlow, true) => (None, &["tensorflow"]),
+        (FrameworkType::TensorFlow, false) => (None, &["tensorflow-cpu"]),
+        (FrameworkType::ONNX, true) => (None, &["onnxruntime-gpu"]),
+        (FrameworkType::ONNX, false) => (None, &["onnxruntime"]),
+        (FrameworkType::JAX, true) => (Some("https://storage.googleapis.com/jax-releases/jax_cuda_releases.html"), &["jax", "jaxlib"]),
+        (FrameworkType::JAX, false) => (None, &["jax", "jaxlib"]),
+        (FrameworkType::HuggingFace, _) => (None, &["transformers"]),
+        (FrameworkType::MXNet, true) => (None, &["mxnet-cu121"]),
+        (FrameworkType::MXNet, false) => (None, &["mxnet"]),
+        (FrameworkType::Unknown, _) => (None, &[]),
+    };
+
+    ImageProfile { builder_image, runtime_image, wheel_index, packages }
+}
+
+/// A reproducible container build spec for a tuned workload; see [`build_container_spec`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerSpec {
+    pub framework: FrameworkType,
+    pub cuda_support: bool,
+    builder_image: String,
+    runtime_image: String,
+    wheel_index: Option<String>,
+    packages: Vec<String>,
+    /// `ENV` lines, sorted by key so the rendered Dockerfile is deterministic
+    env: Vec<(String, String)>,
+    /// Generated launcher/config files (from [`Implementation::ConfigFile`] recommendations) to
+    /// `COPY` into the runtime stage
+    copied_files: Vec<PathBuf>,
+    /// Hash of everything that affects the rendered output, so identical `OptimizationResult`s
+    /// reuse the same build cache entry instead of invalidating it on every call
+    pub cache_key: String,
+}
+
+impl ContainerSpec {
+    /// Render the multi-stage Dockerfile
+    pub fn render_dockerfile(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str(&format!("FROM {} AS builder\n", self.builder_image));
+        out.push_str("WORKDIR /build\n");
+        out.push_str("COPY requirements.txt .\n");
+        match &self.wheel_index {
+            Some(index) => {
+                out.push_str(&format!("RUN pip install --no-cache-dir --index-url {} -r requirements.txt\n", index))
+            }
+            None => out.push_str("RUN pip install --no-cache-dir -r requirements.txt\n"),
+        }
+        out.push('\n');
+
+        out.push_str(&format!("FROM {} AS runtime\n", self.runtime_image));
+        out.push_str("WORKDIR /app\n");
+        out.push_str(
+            "COPY --from=builder /usr/local/lib/python3.11/site-packages /usr/local/lib/python3.11/site-packages\n",
+        );
+        for (key, value) in &self.env {
+            out.push_str(&format!("ENV {}={}\n", key, value));
+        }
+        for file in &self.copied_files {
+            out.push_str(&format!("COPY {0} {0}\n", file.display()));
+        }
+        out.push_str("COPY . .\n");
+        out.push_str("CMD [\"python\", \"train.py\"]\n");
+        out
+    }
+
+    /// Render the pinned dependency manifest (`requirements.txt` contents), one package per line
+    pub fn render_requirements(&self) -> Vec<String> {
+        self.packages.clone()
+    }
+}
+
+/// Turn a tuned `result` into a [`ContainerSpec`]: a CPU-only base image and wheel index when
+/// `result.resource_allocation.gpu_allocation` is `None`, a matching CUDA runtime base otherwise,
+/// the computed `environment_variables` baked in as `ENV` lines, and the `ConfigFile` paths
+/// already generated onto `result.recommendations` (e.g. by
+/// [`crate::MLOptimizer::apply_zero_recommendations`]) collected for the runtime stage to copy in
+pub fn build_container_spec(result: &OptimizationResult) -> ContainerSpec {
+    let framework = result.config.target_framework.unwrap_or(FrameworkType::PyTorch);
+    let cuda_support = result.resource_allocation.gpu_allocation.is_some();
+    let profile = image_profile(framework, cuda_support);
+
+    let mut env: Vec<(String, String)> = result.environment_variables.clone().into_iter().collect();
+    env.sort();
+
+    let copied_files: Vec<PathBuf> = result
+        .recommendations
+        .iter()
+        .filter_map(|r| match &r.implementation {
+            Implementation::ConfigFile { path, .. } => Some(path.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let mut hasher = DefaultHasher::new();
+    framework.hash(&mut hasher);
+    cuda_support.hash(&mut hasher);
+    env.hash(&mut hasher);
+    copied_files.hash(&mut hasher);
+    let cache_key = format!("container_{:x}", hasher.finish());
+
+    ContainerSpec {
+        framework,
+        cuda_support,
+        builder_image: profile.builder_image.to_string(),
+        runtime_image: profile.runtime_image.to_string(),
+        wheel_index: profile.wheel_index.map(str::to_string),
+        packages: profile.packages.iter().map(|s| s.to_string()).collect(),
+        env,
+        copied_files,
+        cache_key,
+    }
+}
@@ -0,0 +1,547 @@
+//! File-loaded optimization rules, as an alternative to the hardcoded `fn`-pointer rules in
+//! [`crate::optimization`].
+//!
+//! [`OptimizationRule`](crate::optimization::OptimizationRule)'s `condition`/`recommendation`
+//! fields are plain `fn` pointers defined inline in `initialize_rules`, so tuning them for a
+//! specific site requires recompiling the crate. A [`DeclarativeRule`] expresses the same
+//! condition/recommendation shape as TOML instead: a condition written over named `SystemInfo`/
+//! `FrameworkInfo`/`DatasetInfo` fields (`"gpu_count > 1"`, `"gpu_memory[0] > 6e9"`,
+//! `"dataset.size > 10000"`), and a recommended value that may itself be a small expression
+//! (`"min(cpu_cores/2, 8)"`). [`OptimizationEngine::load_user_rules`](crate::optimization::OptimizationEngine::load_user_rules)
+//! loads a [`DeclarativeRuleSet`] from a config path and merges it with the built-in rules so
+//! operators can encode site-specific tuning knowledge without touching the crate.
+
+use crate::error::{MLError, Result};
+use crate::frameworks::{FrameworkInfo, FrameworkType};
+use crate::dataset::DatasetInfo;
+use crate::optimization::{OptimizationRecommendation, OptimizationType, SystemInfo};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::warn;
+
+fn default_priority() -> u8 {
+    5
+}
+
+fn default_confidence() -> f64 {
+    0.6
+}
+
+fn default_optimization_type() -> String {
+    "optimizer".to_string()
+}
+
+/// A single file-loaded optimization rule, deserialized from a `[[rules]]` TOML table.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeclarativeRule {
+    pub name: String,
+    #[serde(default = "default_priority")]
+    pub priority: u8,
+    /// Restrict this rule to one framework (matched case-insensitively against the framework's
+    /// name, e.g. `"pytorch"`), or leave unset to apply to every framework.
+    #[serde(default)]
+    pub framework: Option<String>,
+    /// A boolean expression over the context built by [`build_context`], e.g. `"gpu_count > 1"`
+    /// or `"gpu_memory[0] > 6e9 && dataset.size > 10000"`.
+    pub condition: String,
+    pub parameter: String,
+    /// Either a literal value (e.g. `"bfloat16"`) or a numeric expression (e.g.
+    /// `"min(cpu_cores/2, 8)"`); expressions that fail to evaluate are used as a literal string.
+    pub recommended_value: String,
+    pub expected_improvement: f64,
+    #[serde(default = "default_confidence")]
+    pub confidence: f64,
+    #[serde(default = "default_optimization_type")]
+    pub optimization_type: String,
+    pub description: String,
+    #[serde(default)]
+    pub rationale: Option<String>,
+}
+
+/// A TOML-deserializable collection of [`DeclarativeRule`]s, one file per site/operator.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct DeclarativeRuleSet {
+    #[serde(default)]
+    pub rules: Vec<DeclarativeRule>,
+}
+
+/// Parse a [`DeclarativeRuleSet`] from a TOML string.
+pub fn parse_rule_set(toml_source: &str) -> Result<DeclarativeRuleSet> {
+    Ok(toml::from_str(toml_source)?)
+}
+
+/// Load a [`DeclarativeRuleSet`] from a TOML file on disk.
+pub fn load_rule_set(path: &Path) -> Result<DeclarativeRuleSet> {
+    let contents = std::fs::read_to_string(path)?;
+    parse_rule_set(&contents)
+}
+
+fn framework_type_name(framework_type: FrameworkType) -> &'static str {
+    match framework_type {
+        FrameworkType::PyTorch => "pytorch",
+        FrameworkType::TensorFlow => "tensorflow",
+        FrameworkType::ONNX => "onnx",
+        FrameworkType::JAX => "jax",
+        FrameworkType::MXNet => "mxnet",
+        FrameworkType::HuggingFace => "huggingface",
+    }
+}
+
+fn parse_optimization_type(name: &str, rule_name: &str) -> OptimizationType {
+    match name.to_ascii_lowercase().as_str() {
+        "batch_size" => OptimizationType::BatchSize,
+        "learning_rate" => OptimizationType::LearningRate,
+        "optimizer" => OptimizationType::Optimizer,
+        "data_loader" => OptimizationType::DataLoader,
+        "model" => OptimizationType::Model,
+        "memory" => OptimizationType::Memory,
+        "distributed" => OptimizationType::Distributed,
+        "mixed" => OptimizationType::Mixed,
+        "power" => OptimizationType::Power,
+        other => {
+            warn!(
+                "declarative rule {rule_name:?} has unknown optimization_type {other:?}, \
+                 defaulting to Optimizer"
+            );
+            OptimizationType::Optimizer
+        }
+    }
+}
+
+/// Build the `identifier -> value` context an expression is evaluated against, from the same
+/// fields the hardcoded rules in [`crate::optimization`] already branch on.
+pub fn build_context(
+    sys: &SystemInfo,
+    _framework: &FrameworkInfo,
+    dataset: Option<&DatasetInfo>,
+) -> HashMap<String, f64> {
+    let mut ctx = HashMap::new();
+    ctx.insert("cpu_cores".to_string(), sys.cpu_cores as f64);
+    ctx.insert("total_memory".to_string(), sys.total_memory as f64);
+    ctx.insert("available_memory".to_string(), sys.available_memory as f64);
+    ctx.insert("gpu_count".to_string(), sys.gpu_count as f64);
+    ctx.insert(
+        "network_bandwidth".to_string(),
+        sys.network_bandwidth.unwrap_or(0) as f64,
+    );
+    for (i, &memory) in sys.gpu_memory.iter().enumerate() {
+        ctx.insert(format!("gpu_memory[{i}]"), memory as f64);
+    }
+    ctx.insert(
+        "gpu_memory_min".to_string(),
+        sys.gpu_memory.iter().copied().min().unwrap_or(0) as f64,
+    );
+    // FrameworkInfo's fields (version, path, accelerator, ...) are strings/enums rather than
+    // numbers, so they aren't exposed as context variables; use `DeclarativeRule::framework`
+    // to filter by framework instead.
+    if let Some(dataset) = dataset {
+        ctx.insert("dataset.size".to_string(), dataset.size as f64);
+        ctx.insert(
+            "dataset.dimensions_len".to_string(),
+            dataset.dimensions.len() as f64,
+        );
+    }
+    ctx
+}
+
+impl DeclarativeRule {
+    fn matches_framework(&self, framework: &FrameworkInfo) -> bool {
+        match &self.framework {
+            None => true,
+            Some(name) => framework_type_name(framework.framework_type).eq_ignore_ascii_case(name),
+        }
+    }
+
+    /// Evaluate this rule's condition against the given system/framework/dataset. A condition
+    /// that fails to parse or references an unknown field is treated as non-firing (logged as a
+    /// warning) rather than aborting the whole optimization pass.
+    pub fn evaluate(
+        &self,
+        sys: &SystemInfo,
+        framework: &FrameworkInfo,
+        dataset: Option<&DatasetInfo>,
+    ) -> bool {
+        if !self.matches_framework(framework) {
+            return false;
+        }
+        let ctx = build_context(sys, framework, dataset);
+        match evaluate_condition(&self.condition, &ctx) {
+            Ok(fires) => fires,
+            Err(e) => {
+                warn!(
+                    "declarative rule {:?} condition {:?} failed to evaluate: {}",
+                    self.name, self.condition, e
+                );
+                false
+            }
+        }
+    }
+
+    /// Build the recommendation this rule produces. Assumes [`Self::evaluate`] already returned
+    /// `true`.
+    pub fn build_recommendation(
+        &self,
+        sys: &SystemInfo,
+        framework: &FrameworkInfo,
+        dataset: Option<&DatasetInfo>,
+    ) -> OptimizationRecommendation {
+        let ctx = build_context(sys, framework, dataset);
+        let recommended_value = match evaluate_arith(&self.recommended_value, &ctx) {
+            Ok(value) => format_evaluated_value(value),
+            Err(_) => self.recommended_value.clone(),
+        };
+        OptimizationRecommendation {
+            optimization_type: parse_optimization_type(&self.optimization_type, &self.name),
+            description: self.description.clone(),
+            parameter: self.parameter.clone(),
+            current_value: None,
+            recommended_value,
+            expected_improvement: self.expected_improvement,
+            confidence: self.confidence,
+            rationale: self.rationale.clone().unwrap_or_else(|| {
+                format!(
+                    "Recommended by user-defined rule {:?} loaded from declarative configuration",
+                    self.name
+                )
+            }),
+        }
+    }
+}
+
+/// Render an evaluated expression's value the way the hand-written rules already format numeric
+/// recommendations: as a whole number when it's effectively integral, otherwise with limited
+/// precision.
+fn format_evaluated_value(value: f64) -> String {
+    if (value - value.round()).abs() < 1e-9 {
+        format!("{}", value.round() as i64)
+    } else {
+        format!("{:.4}", value)
+    }
+}
+
+// --- A small expression evaluator over the `HashMap<String, f64>` context above ---
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(String),
+}
+
+fn tokenize(input: &str) -> std::result::Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            i += 1;
+            while i < chars.len()
+                && (chars[i].is_ascii_digit()
+                    || chars[i] == '.'
+                    || chars[i] == 'e'
+                    || chars[i] == 'E'
+                    || ((chars[i] == '+' || chars[i] == '-')
+                        && i > start
+                        && (chars[i - 1] == 'e' || chars[i - 1] == 'E')))
+            {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value: f64 = text
+                .parse()
+                .map_err(|_| format!("invalid number literal {text:?}"))?;
+            tokens.push(Token::Num(value));
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            i += 1;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric()
+                    || chars[i] == '_'
+                    || chars[i] == '.'
+                    || chars[i] == '['
+                    || chars[i] == ']')
+            {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(Token::Ident(text));
+            continue;
+        }
+        let two_char: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+        if matches!(two_char.as_str(), ">=" | "<=" | "==" | "!=" | "&&" | "||") {
+            tokens.push(Token::Op(two_char));
+            i += 2;
+            continue;
+        }
+        if matches!(c, '>' | '<' | '+' | '-' | '*' | '/' | '(' | ')' | ',') {
+            tokens.push(Token::Op(c.to_string()));
+            i += 1;
+            continue;
+        }
+        return Err(format!("unexpected character {c:?} in expression {input:?}"));
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    ctx: &'a HashMap<String, f64>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: Vec<Token>, ctx: &'a HashMap<String, f64>) -> Self {
+        Self { tokens, pos: 0, ctx }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect_op(&mut self, op: &str) -> std::result::Result<(), String> {
+        match self.advance() {
+            Some(Token::Op(found)) if found == op => Ok(()),
+            other => Err(format!("expected {op:?}, found {other:?}")),
+        }
+    }
+
+    fn parse_or(&mut self) -> std::result::Result<f64, String> {
+        let mut value = self.parse_and()?;
+        while matches!(self.peek(), Some(Token::Op(op)) if op == "||") {
+            self.advance();
+            let rhs = self.parse_and()?;
+            value = if value != 0.0 || rhs != 0.0 { 1.0 } else { 0.0 };
+        }
+        Ok(value)
+    }
+
+    fn parse_and(&mut self) -> std::result::Result<f64, String> {
+        let mut value = self.parse_cmp()?;
+        while matches!(self.peek(), Some(Token::Op(op)) if op == "&&") {
+            self.advance();
+            let rhs = self.parse_cmp()?;
+            value = if value != 0.0 && rhs != 0.0 { 1.0 } else { 0.0 };
+        }
+        Ok(value)
+    }
+
+    fn parse_cmp(&mut self) -> std::result::Result<f64, String> {
+        let lhs = self.parse_arith()?;
+        if let Some(Token::Op(op)) = self.peek() {
+            if matches!(op.as_str(), ">" | "<" | ">=" | "<=" | "==" | "!=") {
+                let op = op.clone();
+                self.advance();
+                let rhs = self.parse_arith()?;
+                let result = match op.as_str() {
+                    ">" => lhs > rhs,
+                    "<" => lhs < rhs,
+                    ">=" => lhs >= rhs,
+                    "<=" => lhs <= rhs,
+                    "==" => (lhs - rhs).abs() < 1e-9,
+                    "!=" => (lhs - rhs).abs() >= 1e-9,
+                    _ => unreachable!(),
+                };
+                return Ok(if result { 1.0 } else { 0.0 });
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_arith(&mut self) -> std::result::Result<f64, String> {
+        let mut value = self.parse_term()?;
+        loop {
+            match self.peek() {
+                Some(Token::Op(op)) if op == "+" => {
+                    self.advance();
+                    value += self.parse_term()?;
+                }
+                Some(Token::Op(op)) if op == "-" => {
+                    self.advance();
+                    value -= self.parse_term()?;
+                }
+                _ => break,
+            }
+        }
+        Ok(value)
+    }
+
+    fn parse_term(&mut self) -> std::result::Result<f64, String> {
+        let mut value = self.parse_unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Op(op)) if op == "*" => {
+                    self.advance();
+                    value *= self.parse_unary()?;
+                }
+                Some(Token::Op(op)) if op == "/" => {
+                    self.advance();
+                    let rhs = self.parse_unary()?;
+                    if rhs == 0.0 {
+                        return Err("division by zero".to_string());
+                    }
+                    value /= rhs;
+                }
+                _ => break,
+            }
+        }
+        Ok(value)
+    }
+
+    fn parse_unary(&mut self) -> std::result::Result<f64, String> {
+        if matches!(self.peek(), Some(Token::Op(op)) if op == "-") {
+            self.advance();
+            return Ok(-self.parse_unary()?);
+        }
+        self.parse_factor()
+    }
+
+    fn parse_factor(&mut self) -> std::result::Result<f64, String> {
+        match self.advance() {
+            Some(Token::Num(value)) => Ok(value),
+            Some(Token::Ident(name)) if matches!(self.peek(), Some(Token::Op(op)) if op == "(") => {
+                self.advance(); // consume '('
+                let args = self.parse_call_args()?;
+                match name.as_str() {
+                    "min" | "max" if args.len() == 2 => {
+                        Ok(if name == "min" {
+                            args[0].min(args[1])
+                        } else {
+                            args[0].max(args[1])
+                        })
+                    }
+                    other => Err(format!("unknown function {other:?}")),
+                }
+            }
+            Some(Token::Ident(name)) => self
+                .ctx
+                .get(&name)
+                .copied()
+                .ok_or_else(|| format!("unknown field {name:?}")),
+            Some(Token::Op(op)) if op == "(" => {
+                let value = self.parse_or()?;
+                self.expect_op(")")?;
+                Ok(value)
+            }
+            other => Err(format!("unexpected token {other:?}")),
+        }
+    }
+
+    fn parse_call_args(&mut self) -> std::result::Result<Vec<f64>, String> {
+        let mut args = vec![self.parse_arith()?];
+        while matches!(self.peek(), Some(Token::Op(op)) if op == ",") {
+            self.advance();
+            args.push(self.parse_arith()?);
+        }
+        self.expect_op(")")?;
+        Ok(args)
+    }
+}
+
+/// Evaluate a boolean condition expression (comparisons joined by `&&`/`||`) against `ctx`.
+fn evaluate_condition(expr: &str, ctx: &HashMap<String, f64>) -> std::result::Result<bool, String> {
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser::new(tokens, ctx);
+    let value = parser.parse_or()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(format!("trailing tokens in condition {expr:?}"));
+    }
+    Ok(value != 0.0)
+}
+
+/// Evaluate a numeric expression (no comparisons) against `ctx`, e.g. `"min(cpu_cores/2, 8)"`.
+fn evaluate_arith(expr: &str, ctx: &HashMap<String, f64>) -> std::result::Result<f64, String> {
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser::new(tokens, ctx);
+    let value = parser.parse_arith()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(format!("trailing tokens in expression {expr:?}"));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> HashMap<String, f64> {
+        let mut ctx = HashMap::new();
+        ctx.insert("gpu_count".to_string(), 2.0);
+        ctx.insert("gpu_memory[0]".to_string(), 8_000_000_000.0);
+        ctx.insert("cpu_cores".to_string(), 16.0);
+        ctx.insert("dataset.size".to_string(), 50_000.0);
+        ctx
+    }
+
+    #[test]
+    fn test_evaluate_condition_handles_simple_comparisons() {
+        assert_eq!(evaluate_condition("gpu_count > 1", &ctx()), Ok(true));
+        assert_eq!(evaluate_condition("gpu_count > 2", &ctx()), Ok(false));
+    }
+
+    #[test]
+    fn test_evaluate_condition_handles_and_or() {
+        assert_eq!(
+            evaluate_condition("gpu_memory[0] > 6e9 && dataset.size > 10000", &ctx()),
+            Ok(true)
+        );
+        assert_eq!(
+            evaluate_condition("gpu_count > 100 || dataset.size > 10000", &ctx()),
+            Ok(true)
+        );
+        assert_eq!(
+            evaluate_condition("gpu_count > 100 || dataset.size > 1000000", &ctx()),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_condition_rejects_unknown_fields() {
+        assert!(evaluate_condition("nonexistent_field > 1", &ctx()).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_arith_handles_min_max_and_arithmetic() {
+        assert_eq!(evaluate_arith("min(cpu_cores/2, 8)", &ctx()), Ok(8.0));
+        assert_eq!(evaluate_arith("max(cpu_cores/2, 100)", &ctx()), Ok(100.0));
+        assert_eq!(evaluate_arith("cpu_cores / 2", &ctx()), Ok(8.0));
+    }
+
+    #[test]
+    fn test_parse_rule_set_reads_rules_table() {
+        let toml_source = r#"
+            [[rules]]
+            name = "custom_batch_size"
+            condition = "gpu_count > 0"
+            parameter = "batch_size"
+            recommended_value = "min(cpu_cores/2, 8)"
+            expected_improvement = 10.0
+            description = "Site-tuned batch size"
+        "#;
+        let rule_set = parse_rule_set(toml_source).unwrap();
+        assert_eq!(rule_set.rules.len(), 1);
+        assert_eq!(rule_set.rules[0].name, "custom_batch_size");
+        assert_eq!(rule_set.rules[0].priority, 5); // default
+        assert_eq!(rule_set.rules[0].confidence, 0.6); // default
+    }
+
+    #[test]
+    fn test_format_evaluated_value_renders_integral_values_without_a_decimal_point() {
+        assert_eq!(format_evaluated_value(8.0), "8");
+        assert_eq!(format_evaluated_value(8.25), "8.2500");
+    }
+}
@@ -1,14 +1,53 @@
 //! Distributed training coordination and optimization
 
 use crate::error::{MLError, Result};
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinSet;
 use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn, error, instrument};
 
+type HmacSha256 = Hmac<Sha256>;
+
+/// Length in bytes of the random nonce the coordinator handshake exchanges
+const HANDSHAKE_NONCE_LEN: usize = 32;
+
+/// Sequential TCP connect round-trips `estimate_latency` takes the median of, per ordered pair
+const LATENCY_PROBE_ROUNDS: usize = 20;
+
+/// Timeout for a single latency-probe connect attempt
+const LATENCY_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Payload size `estimate_bandwidth` streams to measure throughput
+const BANDWIDTH_PROBE_PAYLOAD_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Chunk size used when streaming/draining a bandwidth probe payload
+const BANDWIDTH_PROBE_CHUNK_BYTES: usize = 64 * 1024;
+
+/// First byte of a coordinator-connection message identifying it as a bandwidth probe (see
+/// [`serve_bandwidth_probe`]) rather than the (currently unimplemented) training coordination
+/// protocol
+const COORDINATOR_MSG_BANDWIDTH_PROBE: u8 = 0x01;
+
+/// First byte of a coordinator-connection message identifying it as a heartbeat keepalive (see
+/// [`DistributedCoordinator::record_heartbeat`])
+const COORDINATOR_MSG_HEARTBEAT: u8 = 0x02;
+
+/// Capacity of the [`broadcast`] channel [`DistributedCoordinator::membership_tx`] publishes
+/// [`MembershipChange`] events on. Generous enough that a subscriber falling behind by this many
+/// consecutive join/leave events (rather than a single slow tick) is the actual problem.
+const MEMBERSHIP_CHANGE_CHANNEL_CAPACITY: usize = 64;
+
 /// Distributed training strategy
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DistributedStrategy {
@@ -57,6 +96,179 @@ pub enum NodeStatus {
     Error,
 }
 
+/// Where cluster membership comes from, modeled on Garage's `consul.rs`/`kubernetes.rs` service
+/// discovery: `Static` is the original behavior (a fixed, pre-enumerated node list), while
+/// `Consul`/`Kubernetes` let [`DistributedCoordinator::reconcile_membership`] poll an external
+/// service catalog so worker pods can come and go in an autoscaling environment without a
+/// restart or a hand-edited cluster file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DiscoveryBackend {
+    /// A fixed node list that never changes once configured
+    Static(Vec<NodeInfo>),
+    /// Poll a Consul agent's service catalog for healthy instances of `service_name`
+    Consul { url: String, service_name: String },
+    /// Poll the Kubernetes API for running pods matching `label_selector` in `namespace`, using
+    /// the in-cluster service account token rather than a `kubeconfig`
+    Kubernetes { namespace: String, label_selector: String },
+}
+
+/// Hecate coordinator services listen on this port unless told otherwise; used for nodes
+/// discovered through [`DiscoveryBackend::Kubernetes`], which reports pod IPs but not ports
+const DEFAULT_COORDINATOR_PORT: u16 = 29500;
+
+impl DiscoveryBackend {
+    /// Query this backend for its current view of cluster membership
+    pub async fn discover_nodes(&self) -> Result<Vec<NodeInfo>> {
+        match self {
+            Self::Static(nodes) => Ok(nodes.clone()),
+            Self::Consul { url, service_name } => discover_consul_nodes(url, service_name).await,
+            Self::Kubernetes { namespace, label_selector } => {
+                discover_kubernetes_nodes(namespace, label_selector).await
+            }
+        }
+    }
+}
+
+/// Minimal subset of a Consul `/v1/health/service/<name>?passing=true` catalog entry
+#[derive(Debug, Deserialize)]
+struct ConsulServiceEntry {
+    #[serde(rename = "Service")]
+    service: ConsulService,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConsulService {
+    #[serde(rename = "ID")]
+    id: String,
+    #[serde(rename = "Address")]
+    address: String,
+    #[serde(rename = "Port")]
+    port: u16,
+    #[serde(rename = "Meta", default)]
+    meta: HashMap<String, String>,
+}
+
+/// Queries Consul's catalog over HTTP, which can fail transiently (a connection reset, a
+/// momentarily-unreachable agent) -- routed through [`crate::error::retry_with_backoff`] so one
+/// flaky request doesn't abort distributed node discovery outright.
+async fn discover_consul_nodes(url: &str, service_name: &str) -> Result<Vec<NodeInfo>> {
+    let query_url = format!("{}/v1/health/service/{}?passing=true", url.trim_end_matches('/'), service_name);
+
+    let entries: Vec<ConsulServiceEntry> = crate::error::retry_with_backoff(
+        crate::error::RetryConfig::default(),
+        || async {
+            reqwest::get(&query_url)
+                .await
+                .map_err(MLError::HttpError)?
+                .json()
+                .await
+                .map_err(MLError::HttpError)
+        },
+    )
+    .await?;
+
+    entries.into_iter().map(|entry| consul_service_to_node(entry.service)).collect()
+}
+
+fn consul_service_to_node(service: ConsulService) -> Result<NodeInfo> {
+    let address = format!("{}:{}", service.address, service.port)
+        .parse::<SocketAddr>()
+        .map_err(|e| MLError::DistributedError(format!("Consul entry has invalid address: {e}")))?;
+
+    let gpu_count = service.meta.get("gpu_count").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let cpu_cores = service.meta.get("cpu_cores").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let memory = service.meta.get("memory").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let bandwidth = service.meta.get("bandwidth").and_then(|v| v.parse().ok());
+
+    Ok(NodeInfo {
+        id: service.id,
+        address,
+        gpu_count,
+        gpu_memory: Vec::new(),
+        cpu_cores,
+        memory,
+        bandwidth,
+        role: NodeRole::Worker,
+        status: NodeStatus::Online,
+    })
+}
+
+/// Minimal subset of a Kubernetes `/api/v1/namespaces/<ns>/pods` response
+#[derive(Debug, Deserialize)]
+struct K8sPodList {
+    items: Vec<K8sPod>,
+}
+
+#[derive(Debug, Deserialize)]
+struct K8sPod {
+    metadata: K8sPodMetadata,
+    status: K8sPodStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct K8sPodMetadata {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct K8sPodStatus {
+    #[serde(rename = "podIP")]
+    pod_ip: Option<String>,
+    #[serde(default)]
+    phase: String,
+}
+
+async fn discover_kubernetes_nodes(namespace: &str, label_selector: &str) -> Result<Vec<NodeInfo>> {
+    let host = std::env::var("KUBERNETES_SERVICE_HOST")
+        .map_err(|_| MLError::DistributedError("KUBERNETES_SERVICE_HOST is not set; not running in-cluster".to_string()))?;
+    let port = std::env::var("KUBERNETES_SERVICE_PORT_HTTPS").unwrap_or_else(|_| "443".to_string());
+    let token = std::fs::read_to_string("/var/run/secrets/kubernetes.io/serviceaccount/token")
+        .map_err(|e| MLError::DistributedError(format!("failed to read service account token: {e}")))?;
+
+    let url = format!("https://{host}:{port}/api/v1/namespaces/{namespace}/pods?labelSelector={label_selector}");
+
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true) // the in-cluster CA isn't in the system trust store
+        .build()
+        .map_err(|e| MLError::DistributedError(format!("failed to build Kubernetes API client: {e}")))?;
+
+    let pod_list: K8sPodList = client
+        .get(&url)
+        .bearer_auth(token.trim())
+        .send()
+        .await
+        .map_err(|e| MLError::DistributedError(format!("Kubernetes API query failed: {e}")))?
+        .json()
+        .await
+        .map_err(|e| MLError::DistributedError(format!("Kubernetes API response malformed: {e}")))?;
+
+    pod_list
+        .items
+        .into_iter()
+        .filter(|pod| pod.status.phase == "Running" && pod.status.pod_ip.is_some())
+        .map(k8s_pod_to_node)
+        .collect()
+}
+
+fn k8s_pod_to_node(pod: K8sPod) -> Result<NodeInfo> {
+    let ip = pod.status.pod_ip.expect("filtered to pods with a pod_ip above");
+    let address = format!("{ip}:{DEFAULT_COORDINATOR_PORT}")
+        .parse::<SocketAddr>()
+        .map_err(|e| MLError::DistributedError(format!("pod '{}' has invalid pod IP: {e}", pod.metadata.name)))?;
+
+    Ok(NodeInfo {
+        id: pod.metadata.name,
+        address,
+        gpu_count: 0,
+        gpu_memory: Vec::new(),
+        cpu_cores: 0,
+        memory: 0,
+        bandwidth: None,
+        role: NodeRole::Worker,
+        status: NodeStatus::Online,
+    })
+}
+
 /// Distributed configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DistributedConfig {
@@ -67,6 +279,145 @@ pub struct DistributedConfig {
     pub world_size: u32,
     pub backend: DistributedBackend,
     pub timeout: Duration,
+    /// How to keep `nodes` current after startup; `DiscoveryBackend::Static(nodes.clone())`
+    /// reproduces the original fixed-cluster behavior. See
+    /// [`DistributedCoordinator::reconcile_membership`].
+    pub discovery: DiscoveryBackend,
+    /// This node's own id, asserted to peers during the RPC handshake (see [`rpc_secret`])
+    pub node_id: String,
+    /// Shared secret authenticating every coordinator RPC connection (`rpc_secret.json`'s
+    /// 64-character hex string, parsed into 32 bytes). Every accepted connection must answer a
+    /// nonce challenge with `HMAC-SHA256(rpc_secret, nonce || node_id)` before any coordinator
+    /// message is processed; see [`handle_coordinator_connection`] and
+    /// [`DistributedCoordinator::ping_node`].
+    #[serde(with = "hex_secret")]
+    pub rpc_secret: [u8; 32],
+    /// Periodic keepalive settings used by [`start_heartbeat_sender`]/[`start_heartbeat_monitor`]
+    /// to detect a mid-training node crash and evict it; see [`DistributedCoordinator::record_heartbeat`].
+    pub heartbeat: HeartbeatConfig,
+}
+
+/// How often workers send a heartbeat, and how many consecutive intervals a node may miss before
+/// [`DistributedCoordinator::evict_stale_nodes_and_reconfigure`] declares it offline and evicts it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartbeatConfig {
+    pub interval: Duration,
+    pub missed_intervals_before_offline: u32,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self { interval: Duration::from_secs(10), missed_intervals_before_offline: 3 }
+    }
+}
+
+/// (De)serializes `DistributedConfig::rpc_secret` as a 64-character hex string rather than a raw
+/// byte array, so it reads and edits like every other secret in an on-disk config file
+mod hex_secret {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(secret: &[u8; 32], serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let hex_string: String = secret.iter().map(|b| format!("{b:02x}")).collect();
+        hex_string.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<[u8; 32], D::Error> {
+        let hex_string = String::deserialize(deserializer)?;
+        parse_hex_secret(&hex_string).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parse a 64-character hex string into a 32-byte `rpc_secret`
+fn parse_hex_secret(hex_string: &str) -> std::result::Result<[u8; 32], String> {
+    if hex_string.len() != 64 {
+        return Err(format!("rpc_secret must be 64 hex characters (32 bytes), got {}", hex_string.len()));
+    }
+
+    let mut secret = [0u8; 32];
+    for (i, byte) in secret.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex_string[i * 2..i * 2 + 2], 16)
+            .map_err(|e| format!("invalid hex in rpc_secret: {e}"))?;
+    }
+    Ok(secret)
+}
+
+/// Build the keyed HMAC used by both sides of the coordinator handshake over `nonce || node_id`
+fn handshake_mac(rpc_secret: &[u8; 32], nonce: &[u8], node_id: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(rpc_secret).expect("HMAC-SHA256 accepts any key length");
+    mac.update(nonce);
+    mac.update(node_id);
+    mac
+}
+
+/// Server side of the coordinator handshake: send a random nonce, read back the peer's claimed
+/// `node_id` and its `HMAC-SHA256(rpc_secret, nonce || node_id)`, and verify it in constant time.
+/// Returns the authenticated peer's `node_id` on success.
+async fn authenticate_incoming(stream: &mut TcpStream, rpc_secret: &[u8; 32]) -> Result<String> {
+    let mut nonce = [0u8; HANDSHAKE_NONCE_LEN];
+    for byte in nonce.iter_mut() {
+        *byte = rand::random();
+    }
+    stream
+        .write_all(&nonce)
+        .await
+        .map_err(|e| MLError::AuthError(format!("failed to send handshake nonce: {e}")))?;
+
+    let mut node_id_len = [0u8; 1];
+    stream
+        .read_exact(&mut node_id_len)
+        .await
+        .map_err(|e| MLError::AuthError(format!("failed to read handshake node id length: {e}")))?;
+
+    let mut node_id_bytes = vec![0u8; node_id_len[0] as usize];
+    stream
+        .read_exact(&mut node_id_bytes)
+        .await
+        .map_err(|e| MLError::AuthError(format!("failed to read handshake node id: {e}")))?;
+    let node_id = String::from_utf8(node_id_bytes)
+        .map_err(|e| MLError::AuthError(format!("handshake node id is not valid UTF-8: {e}")))?;
+
+    let mut tag = [0u8; 32];
+    stream
+        .read_exact(&mut tag)
+        .await
+        .map_err(|e| MLError::AuthError(format!("failed to read handshake HMAC: {e}")))?;
+
+    handshake_mac(rpc_secret, &nonce, node_id.as_bytes())
+        .verify_slice(&tag)
+        .map_err(|_| MLError::AuthError(format!("handshake HMAC mismatch for node '{node_id}'")))?;
+
+    Ok(node_id)
+}
+
+/// Client side of the coordinator handshake: read the server's nonce and answer with our
+/// `node_id` and `HMAC-SHA256(rpc_secret, nonce || node_id)`, proving we hold the shared secret.
+async fn authenticate_outgoing(stream: &mut TcpStream, rpc_secret: &[u8; 32], node_id: &str) -> Result<()> {
+    let mut nonce = [0u8; HANDSHAKE_NONCE_LEN];
+    stream
+        .read_exact(&mut nonce)
+        .await
+        .map_err(|e| MLError::AuthError(format!("failed to read handshake nonce: {e}")))?;
+
+    let node_id_bytes = node_id.as_bytes();
+    if node_id_bytes.len() > u8::MAX as usize {
+        return Err(MLError::AuthError("node id too long for handshake".to_string()));
+    }
+    let tag = handshake_mac(rpc_secret, &nonce, node_id_bytes).finalize().into_bytes();
+
+    stream
+        .write_all(&[node_id_bytes.len() as u8])
+        .await
+        .map_err(|e| MLError::AuthError(format!("failed to send handshake node id length: {e}")))?;
+    stream
+        .write_all(node_id_bytes)
+        .await
+        .map_err(|e| MLError::AuthError(format!("failed to send handshake node id: {e}")))?;
+    stream
+        .write_all(&tag)
+        .await
+        .map_err(|e| MLError::AuthError(format!("failed to send handshake HMAC: {e}")))?;
+
+    Ok(())
 }
 
 /// Distributed backend
@@ -117,10 +468,30 @@ pub struct DistributedCoordinator {
     config: DistributedConfig,
     nodes: HashMap<String, NodeInfo>,
     communication_stats: CommunicationStats,
+    /// Set by [`Self::optimize_communication`]; see [`Self::last_allreduce_schedule`]
+    last_allreduce_schedule: Option<AllReduceSchedule>,
+    /// Set by [`Self::optimize_communication`]; see [`Self::last_topology`]
+    last_topology: Option<NetworkTopology>,
+    /// Set by [`Self::optimize_communication`]; see [`Self::last_compression_config`]
+    last_compression_config: Option<CompressionConfig>,
+    /// Cancelled by [`Self::shutdown`] to stop [`Self::start_coordinator_service`]
+    shutdown_token: CancellationToken,
+    /// Flipped by [`Self::start_coordinator_service`] while its accept loop is running; read by
+    /// [`Self::is_running`]
+    running: Arc<AtomicBool>,
+    /// Wall-clock time each node's heartbeat was last observed, read by
+    /// [`Self::evict_stale_nodes_and_reconfigure`]; see [`Self::record_heartbeat`]
+    last_seen: HashMap<String, Instant>,
+    /// Publishes [`MembershipChange`] events; see [`Self::subscribe_membership_changes`]
+    membership_tx: broadcast::Sender<MembershipChange>,
 }
 
+/// A [`DistributedCoordinator`] behind the `Arc<RwLock<_>>` every long-lived holder of it
+/// (the coordinator service loop, the admin HTTP server) needs in order to share it across tasks
+pub type SharedCoordinator = Arc<RwLock<DistributedCoordinator>>;
+
 /// Communication statistics
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
 pub struct CommunicationStats {
     pub total_bytes_sent: u64,
     pub total_bytes_received: u64,
@@ -132,14 +503,26 @@ impl DistributedCoordinator {
     /// Create new distributed coordinator
     pub fn new(config: DistributedConfig) -> Self {
         let mut nodes = HashMap::new();
+        let mut last_seen = HashMap::new();
+        let now = Instant::now();
         for node in &config.nodes {
             nodes.insert(node.id.clone(), node.clone());
+            last_seen.insert(node.id.clone(), now);
         }
 
+        let (membership_tx, _) = broadcast::channel(MEMBERSHIP_CHANGE_CHANNEL_CAPACITY);
+
         Self {
             config,
             nodes,
             communication_stats: CommunicationStats::default(),
+            last_allreduce_schedule: None,
+            last_topology: None,
+            last_compression_config: None,
+            shutdown_token: CancellationToken::new(),
+            running: Arc::new(AtomicBool::new(false)),
+            last_seen,
+            membership_tx,
         }
     }
 
@@ -236,17 +619,23 @@ impl DistributedCoordinator {
         Ok(())
     }
 
-    /// Ping a node to test connectivity
+    /// Ping a node to test connectivity. Authenticates us to the peer via the same
+    /// `rpc_secret` handshake [`handle_coordinator_connection`] requires of inbound connections,
+    /// so an unauthenticated node can't be mistaken for a healthy one.
     async fn ping_node(&self, address: &SocketAddr) -> Result<Duration> {
         let start = std::time::Instant::now();
-        
-        match timeout(Duration::from_secs(5), TcpStream::connect(address)).await {
-            Ok(Ok(_)) => Ok(start.elapsed()),
-            Ok(Err(e)) => Err(MLError::DistributedError(
-                format!("Connection failed: {}", e)
-            )),
-            Err(_) => Err(MLError::Timeout(Duration::from_secs(5))),
-        }
+
+        let mut stream = match timeout(Duration::from_secs(5), TcpStream::connect(address)).await {
+            Ok(Ok(stream)) => stream,
+            Ok(Err(e)) => {
+                return Err(MLError::DistributedError(format!("Connection failed: {}", e)));
+            }
+            Err(_) => return Err(MLError::Timeout(Duration::from_secs(5))),
+        };
+
+        authenticate_outgoing(&mut stream, &self.config.rpc_secret, &self.config.node_id).await?;
+
+        Ok(start.elapsed())
     }
 
     /// Setup communication backend
@@ -304,19 +693,94 @@ impl DistributedCoordinator {
 
         // Analyze network topology
         let topology = self.analyze_network_topology().await?;
-        
+
         // Choose optimal all-reduce algorithm
         let algorithm = self.choose_allreduce_algorithm(&topology);
-        
+
         // Configure compression if beneficial
         let compression = self.configure_compression(&topology);
 
+        self.last_allreduce_schedule = if matches!(algorithm, AllReduceAlgorithm::Hierarchical) {
+            let node_ids: Vec<String> = self.config.nodes.iter().map(|n| n.id.clone()).collect();
+            let groups = cluster_by_latency(&node_ids, &topology.latency_matrix);
+            Some(build_hierarchical_schedule(&groups))
+        } else {
+            None
+        };
+        self.last_topology = Some(topology);
+        self.last_compression_config = Some(compression.clone());
+
         info!("Selected all-reduce algorithm: {:?}", algorithm);
         info!("Compression config: {:?}", compression);
 
         Ok(())
     }
 
+    /// The [`AllReduceSchedule`] built by the most recent [`Self::optimize_communication`] call,
+    /// if it selected `AllReduceAlgorithm::Hierarchical`
+    pub fn last_allreduce_schedule(&self) -> Option<&AllReduceSchedule> {
+        self.last_allreduce_schedule.as_ref()
+    }
+
+    /// The [`CompressionConfig`] chosen by the most recent [`Self::optimize_communication`] call
+    pub fn last_compression_config(&self) -> Option<&CompressionConfig> {
+        self.last_compression_config.as_ref()
+    }
+
+    /// Record that `payload` was sent over the wire, adding its compressed size to
+    /// `CommunicationStats.total_bytes_sent` so bandwidth-utilization numbers reflect what
+    /// actually goes over the network rather than the uncompressed gradient size
+    pub fn record_compressed_send(&mut self, payload: &crate::compression::CompressedPayload) {
+        self.communication_stats.total_bytes_sent += payload.byte_size();
+    }
+
+    /// Compress `gradient` with whatever [`CompressionAlgorithm`] the most recent
+    /// [`Self::optimize_communication`] call selected (or send it uncompressed if compression
+    /// hasn't been configured, or was disabled), and record its wire size via
+    /// [`Self::record_compressed_send`]. This is the real gradient-send integration point:
+    /// callers that compress (or don't) on their own instead of going through this method bypass
+    /// the configured [`CompressionAlgorithm`] entirely.
+    pub fn compress_and_record_send(&mut self, gradient: &[f32]) -> crate::compression::CompressedPayload {
+        let algorithm = self
+            .last_compression_config
+            .as_ref()
+            .filter(|config| config.enabled)
+            .map(|config| config.algorithm.clone())
+            .unwrap_or(CompressionAlgorithm::None);
+
+        let payload = crate::compression::compressor_for(algorithm, gradient.len()).compress(gradient);
+        self.record_compressed_send(&payload);
+        payload
+    }
+
+    /// The latency/bandwidth matrices measured by the most recent
+    /// [`Self::optimize_communication`] call
+    pub(crate) fn last_topology(&self) -> Option<&NetworkTopology> {
+        self.last_topology.as_ref()
+    }
+
+    /// All currently known nodes, for admin/observability surfaces
+    pub fn nodes(&self) -> impl Iterator<Item = &NodeInfo> {
+        self.nodes.values()
+    }
+
+    /// Snapshot of cluster health computed from currently known node state, without performing
+    /// any new network probes (unlike [`Self::monitor_cluster`], which actively re-pings every
+    /// node)
+    pub fn cluster_health_snapshot(&self) -> ClusterHealth {
+        let online_nodes = self.nodes.values().filter(|n| matches!(n.status, NodeStatus::Online)).count() as u32;
+        let total_gpu_memory = self.nodes.values().flat_map(|n| &n.gpu_memory).sum();
+        let total_cpu_cores = self.nodes.values().map(|n| n.cpu_cores).sum();
+
+        ClusterHealth {
+            online_nodes,
+            total_nodes: self.nodes.len() as u32,
+            total_gpu_memory,
+            total_cpu_cores,
+            cluster_utilization: self.calculate_cluster_utilization(),
+        }
+    }
+
     /// Analyze network topology
     async fn analyze_network_topology(&self) -> Result<NetworkTopology> {
         let mut bandwidth_matrix = HashMap::new();
@@ -344,22 +808,24 @@ impl DistributedCoordinator {
         })
     }
 
-    /// Estimate latency between nodes
-    async fn estimate_latency(&self, _addr1: &SocketAddr, _addr2: &SocketAddr) -> Result<Duration> {
-        // Simplified estimation - in practice would do actual ping tests
-        Ok(Duration::from_millis(1))
+    /// Estimate latency to `addr2` as the median of [`LATENCY_PROBE_ROUNDS`] sequential TCP
+    /// connect round-trips, measured from this coordinator process. There is no RPC to ask
+    /// `addr1` to probe on its own behalf, the same constraint [`Self::ping_node`] operates under.
+    async fn estimate_latency(&self, _addr1: &SocketAddr, addr2: &SocketAddr) -> Result<Duration> {
+        measure_median_latency(addr2, LATENCY_PROBE_ROUNDS, LATENCY_PROBE_TIMEOUT).await
     }
 
-    /// Estimate bandwidth between nodes
-    async fn estimate_bandwidth(&self, _addr1: &SocketAddr, _addr2: &SocketAddr) -> Result<u64> {
-        // Simplified estimation - in practice would do actual bandwidth tests
-        Ok(1000) // 1 Gbps
+    /// Estimate bandwidth to `addr2` by streaming [`BANDWIDTH_PROBE_PAYLOAD_BYTES`] over a freshly
+    /// authenticated connection and dividing by the time until [`serve_bandwidth_probe`] acks full
+    /// receipt.
+    async fn estimate_bandwidth(&self, _addr1: &SocketAddr, addr2: &SocketAddr) -> Result<u64> {
+        measure_bandwidth_mbps(addr2, &self.config.rpc_secret, &self.config.node_id, BANDWIDTH_PROBE_PAYLOAD_BYTES).await
     }
 
     /// Choose optimal all-reduce algorithm
     fn choose_allreduce_algorithm(&self, topology: &NetworkTopology) -> AllReduceAlgorithm {
         let node_count = self.config.nodes.len();
-        
+
         // Algorithm selection based on cluster size and topology
         match node_count {
             2..=4 => AllReduceAlgorithm::Ring,
@@ -376,10 +842,13 @@ impl DistributedCoordinator {
         }
     }
 
-    /// Check if cluster has hierarchical structure
-    fn has_hierarchical_structure(&self, _topology: &NetworkTopology) -> bool {
-        // Simplified heuristic - in practice would analyze network topology
-        self.config.nodes.len() > 8
+    /// Whether the cluster's physical topology has genuine rack-like clustering — more than one
+    /// latency-based [`cluster_by_latency`] group with more than one member apiece — rather than
+    /// being a single flat group or N singletons.
+    fn has_hierarchical_structure(&self, topology: &NetworkTopology) -> bool {
+        let node_ids: Vec<String> = self.config.nodes.iter().map(|n| n.id.clone()).collect();
+        let groups = cluster_by_latency(&node_ids, &topology.latency_matrix);
+        groups.iter().filter(|g| g.len() > 1).count() > 1
     }
 
     /// Configure compression settings
@@ -472,6 +941,43 @@ impl DistributedCoordinator {
         })
     }
 
+    /// Poll `config.discovery` for the cluster's current membership and reconcile `self.nodes`:
+    /// newly-discovered nodes are added `Online`, nodes the backend no longer reports are marked
+    /// `Offline` (not removed, so their stats survive a transient drop from the catalog), and
+    /// `world_size` is updated to the number of currently known nodes. Safe to call on a timer so
+    /// worker pods that come and go in an autoscaling/Kubernetes environment are reflected
+    /// without a coordinator restart.
+    #[instrument(skip(self))]
+    pub async fn reconcile_membership(&mut self) -> Result<()> {
+        let discovered = self.config.discovery.discover_nodes().await?;
+        let discovered_ids: std::collections::HashSet<String> =
+            discovered.iter().map(|node| node.id.clone()).collect();
+
+        for node in discovered {
+            let address = node.address;
+            self.nodes
+                .entry(node.id.clone())
+                .and_modify(|existing| {
+                    existing.address = address;
+                    existing.status = NodeStatus::Online;
+                })
+                .or_insert_with(|| {
+                    info!("Discovered new node '{}' at {}", node.id, node.address);
+                    node
+                });
+        }
+
+        for (id, node) in self.nodes.iter_mut() {
+            if !discovered_ids.contains(id) && !matches!(node.status, NodeStatus::Offline) {
+                info!("Node '{}' no longer reported by discovery backend, marking offline", id);
+                node.status = NodeStatus::Offline;
+            }
+        }
+
+        self.config.world_size = self.nodes.len() as u32;
+        Ok(())
+    }
+
     /// Calculate cluster utilization
     fn calculate_cluster_utilization(&self) -> f64 {
         // Simplified calculation - in practice would monitor actual resource usage
@@ -482,26 +988,156 @@ impl DistributedCoordinator {
         online_ratio * 0.8 // Assume 80% utilization when online
     }
 
-    /// Start distributed training coordinator service
-    pub async fn start_coordinator_service(&self) -> Result<()> {
-        let addr = format!("{}:{}", self.config.master_addr, self.config.master_port);
+    /// Start distributed training coordinator service. Accepts connections until
+    /// [`Self::shutdown`] is called (on `coordinator` or any other handle to the same
+    /// `SharedCoordinator`), then stops accepting new ones, gives in-flight connections up to
+    /// `config.timeout` to finish, aborts any still running past that, and marks every node
+    /// [`NodeStatus::Offline`] before returning. Takes the shared handle rather than `&self` so
+    /// the accept loop never holds the lock, leaving it free for concurrent readers (e.g. the
+    /// admin HTTP server) the whole time the service is up.
+    pub async fn start_coordinator_service(coordinator: SharedCoordinator) -> Result<()> {
+        let (addr, rpc_secret, timeout, shutdown_token, running) = {
+            let guard = coordinator.read().await;
+            (
+                format!("{}:{}", guard.config.master_addr, guard.config.master_port),
+                guard.config.rpc_secret,
+                guard.config.timeout,
+                guard.shutdown_token.clone(),
+                Arc::clone(&guard.running),
+            )
+        };
+
         let listener = TcpListener::bind(&addr).await
             .map_err(|e| MLError::DistributedError(format!("Failed to bind to {}: {}", addr, e)))?;
 
         info!("Coordinator service listening on {}", addr);
+        running.store(true, Ordering::SeqCst);
 
+        let mut connections = JoinSet::new();
         loop {
-            match listener.accept().await {
-                Ok((stream, peer_addr)) => {
-                    debug!("Connection from {}", peer_addr);
-                    tokio::spawn(async move {
-                        if let Err(e) = handle_coordinator_connection(stream).await {
-                            error!("Error handling connection from {}: {}", peer_addr, e);
+            tokio::select! {
+                _ = shutdown_token.cancelled() => {
+                    debug!("Coordinator service shutting down, no longer accepting connections");
+                    break;
+                }
+                accepted = listener.accept() => {
+                    match accepted {
+                        Ok((stream, peer_addr)) => {
+                            debug!("Connection from {}", peer_addr);
+                            let connection_coordinator = Arc::clone(&coordinator);
+                            connections.spawn(async move {
+                                if let Err(e) =
+                                    handle_coordinator_connection(stream, &rpc_secret, connection_coordinator).await
+                                {
+                                    error!("Error handling connection from {}: {}", peer_addr, e);
+                                }
+                            });
                         }
-                    });
+                        Err(e) => {
+                            error!("Failed to accept connection: {}", e);
+                        }
+                    }
                 }
-                Err(e) => {
-                    error!("Failed to accept connection: {}", e);
+            }
+        }
+
+        let drain = async {
+            while connections.join_next().await.is_some() {}
+        };
+        if tokio::time::timeout(timeout, drain).await.is_err() {
+            warn!(
+                "Timed out after {:?} waiting for in-flight coordinator connections to finish; aborting {} remaining",
+                timeout,
+                connections.len()
+            );
+            connections.abort_all();
+        }
+
+        {
+            let mut guard = coordinator.write().await;
+            for node in guard.nodes.values_mut() {
+                node.status = NodeStatus::Offline;
+            }
+        }
+        running.store(false, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Signal [`Self::start_coordinator_service`] to stop accepting new connections, drain
+    /// in-flight ones, and mark every node offline. A no-op if the service isn't running.
+    pub fn shutdown(&self) {
+        self.shutdown_token.cancel();
+    }
+
+    /// Whether [`Self::start_coordinator_service`] is currently accepting connections on this
+    /// coordinator
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// The cancellation signal [`Self::shutdown`] triggers and [`Self::start_coordinator_service`]
+    /// stops on. Exposed so sibling services started alongside the coordinator (the `admin_api`
+    /// HTTP server) can shut down on the same signal instead of needing a separate one wired
+    /// through by hand.
+    #[cfg(feature = "admin_api")]
+    pub(crate) fn shutdown_signal(&self) -> CancellationToken {
+        self.shutdown_token.clone()
+    }
+
+    /// Worker-side loop: send a heartbeat to the master on every `config.heartbeat.interval` tick
+    /// until [`Self::shutdown`] is called. Intended to run alongside [`Self::start_coordinator_service`]
+    /// on worker nodes (or the master itself, so the master's own heartbeats are tracked too).
+    pub async fn start_heartbeat_sender(coordinator: SharedCoordinator) -> Result<()> {
+        let (master_addr, rpc_secret, node_id, interval, shutdown_token) = {
+            let guard = coordinator.read().await;
+            (
+                format!("{}:{}", guard.config.master_addr, guard.config.master_port),
+                guard.config.rpc_secret,
+                guard.config.node_id.clone(),
+                guard.config.heartbeat.interval,
+                guard.shutdown_token.clone(),
+            )
+        };
+        let master_addr: SocketAddr = master_addr
+            .parse()
+            .map_err(|e| MLError::DistributedError(format!("invalid master address {master_addr}: {e}")))?;
+
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = shutdown_token.cancelled() => {
+                    debug!("Heartbeat sender shutting down");
+                    return Ok(());
+                }
+                _ = ticker.tick() => {
+                    if let Err(e) = send_heartbeat(&master_addr, &rpc_secret, &node_id).await {
+                        warn!("Failed to send heartbeat to {}: {}", master_addr, e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Master-side loop: sweep for nodes that have missed too many heartbeats and evict them,
+    /// every `config.heartbeat.interval` tick, until [`Self::shutdown`] is called.
+    pub async fn start_heartbeat_monitor(coordinator: SharedCoordinator) -> Result<()> {
+        let (interval, shutdown_token) = {
+            let guard = coordinator.read().await;
+            (guard.config.heartbeat.interval, guard.shutdown_token.clone())
+        };
+
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = shutdown_token.cancelled() => {
+                    debug!("Heartbeat monitor shutting down");
+                    return Ok(());
+                }
+                _ = ticker.tick() => {
+                    if let Err(e) = coordinator.write().await.evict_stale_nodes_and_reconfigure().await {
+                        warn!("Failed to evict stale nodes: {}", e);
+                    }
                 }
             }
         }
@@ -519,13 +1155,177 @@ impl DistributedCoordinator {
             debug!("Updated node {} status to {:?}", node_id, status);
         }
     }
+
+    /// Subscribe to [`MembershipChange`] events raised by [`Self::record_heartbeat`] (rejoin) and
+    /// [`Self::evict_stale_nodes_and_reconfigure`] (eviction)
+    pub fn subscribe_membership_changes(&self) -> broadcast::Receiver<MembershipChange> {
+        self.membership_tx.subscribe()
+    }
+
+    /// Record a heartbeat from `node_id`, a liveness signal over the already-known roster: unlike
+    /// [`Self::reconcile_membership`], a heartbeat from an id this coordinator has never heard of
+    /// is rejected (logged, not an error) rather than auto-admitting an unknown node. If the node
+    /// was `Offline`, this is a rejoin: it's marked `Online`, `world_size` and the communication
+    /// plan are recomputed, and a [`MembershipChange::Joined`] event is broadcast.
+    pub async fn record_heartbeat(&mut self, node_id: &str) -> Result<()> {
+        let Some(node) = self.nodes.get_mut(node_id) else {
+            warn!("Ignoring heartbeat from unknown node '{}'", node_id);
+            return Ok(());
+        };
+
+        self.last_seen.insert(node_id.to_string(), Instant::now());
+
+        if matches!(node.status, NodeStatus::Offline) {
+            node.status = NodeStatus::Online;
+            info!("Node '{}' rejoined via heartbeat", node_id);
+            self.config.world_size = self.nodes.len() as u32;
+            self.optimize_communication().await?;
+
+            let ranks = assign_ranks(self.nodes.keys().cloned());
+            let _ = self.membership_tx.send(MembershipChange::Joined { node_id: node_id.to_string(), ranks });
+        }
+
+        Ok(())
+    }
+
+    /// Pure helper: which of `node_ids` haven't sent a heartbeat recently enough, per `last_seen`
+    /// and `heartbeat.missed_intervals_before_offline` consecutive missed intervals as of `now`.
+    /// Factored out of [`Self::evict_stale_nodes_and_reconfigure`] so the staleness rule can be
+    /// tested without a real clock or network.
+    fn stale_node_ids(
+        node_ids: impl Iterator<Item = String>,
+        last_seen: &HashMap<String, Instant>,
+        heartbeat: &HeartbeatConfig,
+        now: Instant,
+    ) -> Vec<String> {
+        let grace_period = heartbeat.interval * heartbeat.missed_intervals_before_offline.max(1);
+        node_ids
+            .filter(|id| match last_seen.get(id) {
+                Some(seen) => now.saturating_duration_since(*seen) > grace_period,
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Evict every node that has missed `heartbeat.missed_intervals_before_offline` consecutive
+    /// heartbeats, recompute `world_size` and the communication plan, and broadcast a
+    /// [`MembershipChange::Left`] per evicted node. Unlike [`Self::reconcile_membership`]'s softer
+    /// `Offline`-but-retained handling of a discovery-catalog disappearance (which might be
+    /// transient), a heartbeat timeout means the node is presumed dead and is removed outright.
+    pub async fn evict_stale_nodes_and_reconfigure(&mut self) -> Result<()> {
+        let stale = Self::stale_node_ids(
+            self.nodes.keys().cloned(),
+            &self.last_seen,
+            &self.config.heartbeat,
+            Instant::now(),
+        );
+
+        if stale.is_empty() {
+            return Ok(());
+        }
+
+        for node_id in &stale {
+            warn!("Node '{}' missed too many heartbeats, evicting", node_id);
+            self.nodes.remove(node_id);
+            self.last_seen.remove(node_id);
+        }
+
+        self.config.world_size = self.nodes.len() as u32;
+        self.optimize_communication().await?;
+
+        for node_id in stale {
+            let ranks = assign_ranks(self.nodes.keys().cloned());
+            let _ = self.membership_tx.send(MembershipChange::Left { node_id, ranks });
+        }
+
+        Ok(())
+    }
+}
+
+/// Where to offload ZeRO optimizer state that doesn't fit in GPU memory even at stage 3
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OffloadTarget {
+    Cpu,
+    Nvme,
+    None,
+}
+
+/// A chosen ZeRO partitioning stage (and offload target, if needed) for a model/cluster/memory
+/// budget; see [`select_zero_plan`]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ZeroPlan {
+    /// ZeRO partitioning stage: 1 (optimizer state), 2 (+ gradients), or 3 (+ parameters)
+    pub stage: u8,
+    pub offload_target: OffloadTarget,
+    pub memory_per_rank_bytes: u64,
+}
+
+/// Per-rank optimizer-state memory footprint for a model with `model_parameters` trained in
+/// mixed precision with Adam, under ZeRO partitioning `stage` across `world_size` ranks.
+///
+/// Single-GPU (no partitioning) state is ~16 bytes/parameter: 2 bytes/param each for the fp16
+/// params and fp16 grads, 4 bytes/param for the fp32 master copy, and 8 bytes/param for Adam's
+/// fp32 momentum+variance. Stage 1 partitions everything but the fp16 params/grads across ranks
+/// (4P unpartitioned + 12P/N), stage 2 additionally partitions the fp16 grads (2P unpartitioned +
+/// 14P/N), and stage 3 additionally partitions the fp16 params too, fully sharding all 16P/N.
+pub fn zero_memory_per_rank_bytes(stage: u8, model_parameters: u64, world_size: u32) -> u64 {
+    let n = world_size.max(1) as u64;
+    let p = model_parameters;
+    match stage {
+        1 => 4 * p + (12 * p) / n,
+        2 => 2 * p + (14 * p) / n,
+        _ => (16 * p) / n,
+    }
+}
+
+/// Pick the minimum ZeRO stage whose per-rank optimizer state fits within `budget_bytes`,
+/// escalating to CPU (then NVMe) offload if even the fully-sharded stage 3 footprint doesn't fit
+/// on-device. `offload_budget_bytes` is the host RAM available to offload into, if known.
+pub fn select_zero_plan(
+    model_parameters: u64,
+    world_size: u32,
+    budget_bytes: u64,
+    offload_budget_bytes: Option<u64>,
+) -> ZeroPlan {
+    for stage in 1..=3u8 {
+        let required = zero_memory_per_rank_bytes(stage, model_parameters, world_size);
+        if required <= budget_bytes {
+            return ZeroPlan { stage, offload_target: OffloadTarget::None, memory_per_rank_bytes: required };
+        }
+    }
+
+    let stage3 = zero_memory_per_rank_bytes(3, model_parameters, world_size);
+    let offload_target = match offload_budget_bytes {
+        Some(available) if stage3 <= available => OffloadTarget::Cpu,
+        _ => OffloadTarget::Nvme,
+    };
+    ZeroPlan { stage: 3, offload_target, memory_per_rank_bytes: stage3 }
 }
 
 /// Network topology information
-#[derive(Debug)]
-struct NetworkTopology {
-    latency_matrix: HashMap<(String, String), Duration>,
-    bandwidth_matrix: HashMap<(String, String), u64>,
+#[derive(Debug, Clone)]
+pub(crate) struct NetworkTopology {
+    pub(crate) latency_matrix: HashMap<(String, String), Duration>,
+    pub(crate) bandwidth_matrix: HashMap<(String, String), u64>,
+}
+
+/// A change in elastic cluster membership, published on [`DistributedCoordinator::subscribe_membership_changes`]
+/// so subscribers (e.g. a training loop that needs to know its own current rank) can react without
+/// polling [`DistributedCoordinator::nodes`]. Each variant carries the complete, freshly-recomputed
+/// rank table rather than just the one node's own rank, since every other rank can shift when
+/// `world_size` changes.
+#[derive(Debug, Clone)]
+pub enum MembershipChange {
+    Joined { node_id: String, ranks: HashMap<String, u32> },
+    Left { node_id: String, ranks: HashMap<String, u32> },
+}
+
+/// Deterministically assign ranks `0..world_size` by sorted node id, so every node in the cluster
+/// computes the same rank table independently from the same membership view.
+fn assign_ranks(node_ids: impl Iterator<Item = String>) -> HashMap<String, u32> {
+    let mut sorted: Vec<String> = node_ids.collect();
+    sorted.sort();
+    sorted.into_iter().enumerate().map(|(rank, id)| (id, rank as u32)).collect()
 }
 
 /// Cluster health information
@@ -538,14 +1338,280 @@ pub struct ClusterHealth {
     pub cluster_utilization: f64,
 }
 
-/// Handle coordinator connection
-async fn handle_coordinator_connection(_stream: TcpStream) -> Result<()> {
-    // Handle coordinator protocol messages
-    // This would implement the actual distributed training coordination protocol
-    debug!("Handling coordinator connection");
+/// Handle coordinator connection. Every accepted stream must complete the `rpc_secret` handshake
+/// before any coordinator message is processed; an unauthenticated peer is dropped immediately.
+async fn handle_coordinator_connection(
+    mut stream: TcpStream,
+    rpc_secret: &[u8; 32],
+    coordinator: SharedCoordinator,
+) -> Result<()> {
+    let peer_node_id = authenticate_incoming(&mut stream, rpc_secret).await?;
+    debug!("Handling coordinator connection from authenticated node '{}'", peer_node_id);
+
+    let mut msg_type = [0u8; 1];
+    match stream.read_exact(&mut msg_type).await {
+        Ok(_) if msg_type[0] == COORDINATOR_MSG_BANDWIDTH_PROBE => serve_bandwidth_probe(&mut stream).await,
+        Ok(_) if msg_type[0] == COORDINATOR_MSG_HEARTBEAT => {
+            coordinator.write().await.record_heartbeat(&peer_node_id).await
+        }
+        Ok(_) => {
+            // Handle coordinator protocol messages
+            // This would implement the actual distributed training coordination protocol
+            Ok(())
+        }
+        // The peer disconnected right after the handshake, e.g. a plain `ping_node` connectivity
+        // check that never sends a message body
+        Err(_) => Ok(()),
+    }
+}
+
+/// Server side of the bandwidth probe: read an 8-byte big-endian payload length, drain that many
+/// bytes, and reply with a single ack byte so the client can measure elapsed time up to full
+/// receipt rather than merely until its own write buffer drains.
+async fn serve_bandwidth_probe(stream: &mut TcpStream) -> Result<()> {
+    let mut len_bytes = [0u8; 8];
+    stream
+        .read_exact(&mut len_bytes)
+        .await
+        .map_err(|e| MLError::DistributedError(format!("bandwidth probe length read failed: {e}")))?;
+    let mut remaining = u64::from_be_bytes(len_bytes);
+
+    let mut buf = [0u8; BANDWIDTH_PROBE_CHUNK_BYTES];
+    while remaining > 0 {
+        let chunk = remaining.min(buf.len() as u64) as usize;
+        stream
+            .read_exact(&mut buf[..chunk])
+            .await
+            .map_err(|e| MLError::DistributedError(format!("bandwidth probe payload read failed: {e}")))?;
+        remaining -= chunk as u64;
+    }
+
+    stream
+        .write_all(&[COORDINATOR_MSG_BANDWIDTH_PROBE])
+        .await
+        .map_err(|e| MLError::DistributedError(format!("bandwidth probe ack failed: {e}")))?;
+    Ok(())
+}
+
+/// Connect to `addr` and authenticate as `node_id`, then stream `payload_bytes` in
+/// [`BANDWIDTH_PROBE_CHUNK_BYTES`] chunks and wait for [`serve_bandwidth_probe`]'s ack, returning
+/// throughput in Mbps. Factored out of [`DistributedCoordinator::estimate_bandwidth`] so tests
+/// can probe with a small payload instead of the full [`BANDWIDTH_PROBE_PAYLOAD_BYTES`].
+async fn measure_bandwidth_mbps(
+    addr: &SocketAddr,
+    rpc_secret: &[u8; 32],
+    node_id: &str,
+    payload_bytes: u64,
+) -> Result<u64> {
+    let mut stream = timeout(Duration::from_secs(30), TcpStream::connect(addr))
+        .await
+        .map_err(|_| MLError::Timeout(Duration::from_secs(30)))?
+        .map_err(|e| MLError::DistributedError(format!("bandwidth probe connect to {addr} failed: {e}")))?;
+
+    authenticate_outgoing(&mut stream, rpc_secret, node_id).await?;
+
+    let start = std::time::Instant::now();
+    stream
+        .write_all(&[COORDINATOR_MSG_BANDWIDTH_PROBE])
+        .await
+        .map_err(|e| MLError::DistributedError(format!("bandwidth probe message send failed: {e}")))?;
+    stream
+        .write_all(&payload_bytes.to_be_bytes())
+        .await
+        .map_err(|e| MLError::DistributedError(format!("bandwidth probe length send failed: {e}")))?;
+
+    let chunk = vec![0u8; BANDWIDTH_PROBE_CHUNK_BYTES];
+    let mut sent = 0u64;
+    while sent < payload_bytes {
+        let this_chunk = (payload_bytes - sent).min(chunk.len() as u64) as usize;
+        stream
+            .write_all(&chunk[..this_chunk])
+            .await
+            .map_err(|e| MLError::DistributedError(format!("bandwidth probe payload send failed: {e}")))?;
+        sent += this_chunk as u64;
+    }
+
+    let mut ack = [0u8; 1];
+    stream
+        .read_exact(&mut ack)
+        .await
+        .map_err(|e| MLError::DistributedError(format!("bandwidth probe ack read failed: {e}")))?;
+    let elapsed = start.elapsed().as_secs_f64().max(f64::EPSILON);
+
+    let mbps = (payload_bytes as f64 * 8.0 / elapsed) / 1_000_000.0;
+    Ok(mbps.round() as u64)
+}
+
+/// Connect to the coordinator at `addr`, authenticate as `node_id`, and send a single heartbeat
+/// keepalive byte. Used by [`start_heartbeat_sender`]; a connect/handshake failure just means this
+/// tick's heartbeat is lost, which is fine since the next tick will try again before the
+/// coordinator's `missed_intervals_before_offline` grace period elapses.
+async fn send_heartbeat(addr: &SocketAddr, rpc_secret: &[u8; 32], node_id: &str) -> Result<()> {
+    let mut stream = timeout(Duration::from_secs(10), TcpStream::connect(addr))
+        .await
+        .map_err(|_| MLError::Timeout(Duration::from_secs(10)))?
+        .map_err(|e| MLError::DistributedError(format!("heartbeat connect to {addr} failed: {e}")))?;
+
+    authenticate_outgoing(&mut stream, rpc_secret, node_id).await?;
+
+    stream
+        .write_all(&[COORDINATOR_MSG_HEARTBEAT])
+        .await
+        .map_err(|e| MLError::DistributedError(format!("heartbeat message send failed: {e}")))?;
     Ok(())
 }
 
+/// Median of `N` sequential TCP connect round-trips to `addr`, smoothing out one-off spikes.
+/// Factored out of [`DistributedCoordinator::estimate_latency`] so tests can probe with fewer
+/// rounds instead of the full [`LATENCY_PROBE_ROUNDS`].
+async fn measure_median_latency(addr: &SocketAddr, rounds: usize, probe_timeout: Duration) -> Result<Duration> {
+    let mut samples = Vec::with_capacity(rounds);
+    for _ in 0..rounds {
+        let start = std::time::Instant::now();
+        match timeout(probe_timeout, TcpStream::connect(addr)).await {
+            Ok(Ok(_stream)) => samples.push(start.elapsed()),
+            Ok(Err(e)) => return Err(MLError::DistributedError(format!("latency probe to {addr} failed: {e}"))),
+            Err(_) => return Err(MLError::Timeout(probe_timeout)),
+        }
+    }
+    Ok(median_duration(&mut samples))
+}
+
+/// Median of a slice of [`Duration`]s; sorts in place
+fn median_duration(samples: &mut [Duration]) -> Duration {
+    samples.sort();
+    let n = samples.len();
+    if n % 2 == 1 {
+        samples[n / 2]
+    } else {
+        (samples[n / 2 - 1] + samples[n / 2]) / 2
+    }
+}
+
+/// Median of an already-sorted slice of finite `f64`s
+fn median_sorted_f64(sorted: &[f64]) -> f64 {
+    let n = sorted.len();
+    if n == 0 {
+        return 0.0;
+    }
+    if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    }
+}
+
+/// One data-transfer step within an [`AllReduceSchedule`] phase: `from` sends its partial result
+/// to `to`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SendRecvPair {
+    pub from: String,
+    pub to: String,
+}
+
+/// A concrete, three-phase execution plan for `AllReduceAlgorithm::Hierarchical`, built by
+/// [`build_hierarchical_schedule`] from latency-clustered rack [`groups`](Self::groups): phase 1
+/// ring reduce-scatter within each rack, phase 2 ring all-reduce across one elected leader (each
+/// rack's first member) per rack, phase 3 ring all-gather within each rack propagating the
+/// reduced result back out. A training runtime executes each phase's pairs in order, waiting for
+/// phase N to fully complete before starting phase N+1.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AllReduceSchedule {
+    /// Node ids clustered into the same rack/group, in [`cluster_by_latency`] order
+    pub groups: Vec<Vec<String>>,
+    /// Phase 1: ring reduce-scatter within each group
+    pub intra_group_reduce_scatter: Vec<SendRecvPair>,
+    /// Phase 2: ring all-reduce across each group's elected leader
+    pub inter_group_all_reduce: Vec<SendRecvPair>,
+    /// Phase 3: ring all-gather within each group
+    pub intra_group_all_gather: Vec<SendRecvPair>,
+}
+
+/// Ordered send/recv pairs forming a ring over `members`: each node sends to its successor,
+/// wrapping around to the first. Fewer than two members produce no transfers.
+fn ring_pairs(members: &[String]) -> Vec<SendRecvPair> {
+    if members.len() < 2 {
+        return Vec::new();
+    }
+    members
+        .iter()
+        .enumerate()
+        .map(|(i, from)| SendRecvPair { from: from.clone(), to: members[(i + 1) % members.len()].clone() })
+        .collect()
+}
+
+/// Build a concrete [`AllReduceSchedule`] from latency-clustered `groups`: a ring
+/// reduce-scatter/all-gather within each group, and a ring all-reduce across each group's
+/// elected leader (its first member).
+fn build_hierarchical_schedule(groups: &[Vec<String>]) -> AllReduceSchedule {
+    let mut intra_group_reduce_scatter = Vec::new();
+    let mut intra_group_all_gather = Vec::new();
+
+    for group in groups {
+        intra_group_reduce_scatter.extend(ring_pairs(group));
+        intra_group_all_gather.extend(ring_pairs(group));
+    }
+
+    let leaders: Vec<String> = groups.iter().filter_map(|g| g.first().cloned()).collect();
+    let inter_group_all_reduce = ring_pairs(&leaders);
+
+    AllReduceSchedule {
+        groups: groups.to_vec(),
+        intra_group_reduce_scatter,
+        inter_group_all_reduce,
+        intra_group_all_gather,
+    }
+}
+
+/// Cluster `node_ids` into physical groups ("racks") by agglomerative union-find over
+/// `latency_matrix`: two nodes merge whenever their measured latency falls below an adaptive
+/// threshold (median latency minus one median absolute deviation), separating "same rack" pairs
+/// from "cross rack" pairs without a hardcoded cutoff. Nodes with no latency samples (or a
+/// single-node cluster) each form their own singleton group.
+fn cluster_by_latency(node_ids: &[String], latency_matrix: &HashMap<(String, String), Duration>) -> Vec<Vec<String>> {
+    if node_ids.len() < 2 || latency_matrix.is_empty() {
+        return node_ids.iter().map(|id| vec![id.clone()]).collect();
+    }
+
+    let mut sorted_latencies: Vec<f64> = latency_matrix.values().map(|d| d.as_secs_f64()).collect();
+    sorted_latencies.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median = median_sorted_f64(&sorted_latencies);
+
+    let mut sorted_deviations: Vec<f64> = sorted_latencies.iter().map(|l| (l - median).abs()).collect();
+    sorted_deviations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mad = median_sorted_f64(&sorted_deviations);
+    let threshold = (median - mad).max(0.0);
+
+    let index_of: HashMap<&str, usize> = node_ids.iter().enumerate().map(|(i, id)| (id.as_str(), i)).collect();
+    let mut parent: Vec<usize> = (0..node_ids.len()).collect();
+
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        if parent[i] != i {
+            parent[i] = find(parent, parent[i]);
+        }
+        parent[i]
+    }
+
+    for ((a, b), latency) in latency_matrix {
+        if latency.as_secs_f64() >= threshold {
+            continue;
+        }
+        if let (Some(&i), Some(&j)) = (index_of.get(a.as_str()), index_of.get(b.as_str())) {
+            let (root_i, root_j) = (find(&mut parent, i), find(&mut parent, j));
+            if root_i != root_j {
+                parent[root_i] = root_j;
+            }
+        }
+    }
+
+    let mut groups_by_root: HashMap<usize, Vec<String>> = HashMap::new();
+    for (i, id) in node_ids.iter().enumerate() {
+        let root = find(&mut parent, i);
+        groups_by_root.entry(root).or_default().push(id.clone());
+    }
+    groups_by_root.into_values().collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -578,12 +1644,16 @@ mod tests {
 
         DistributedConfig {
             strategy: DistributedStrategy::DataParallel,
+            discovery: DiscoveryBackend::Static(vec![master.clone(), worker.clone()]),
             nodes: vec![master, worker],
             master_addr: "127.0.0.1".to_string(),
             master_port: 29500,
             world_size: 2,
             backend: DistributedBackend::NCCL,
             timeout: Duration::from_secs(30),
+            node_id: "master".to_string(),
+            rpc_secret: [0x42; 32],
+            heartbeat: HeartbeatConfig::default(),
         }
     }
 
@@ -648,4 +1718,397 @@ mod tests {
         let compression = coordinator.configure_compression(&topology);
         assert!(!compression.enabled); // High bandwidth, no compression needed
     }
+
+    #[tokio::test]
+    async fn test_static_discovery_backend_returns_configured_nodes() {
+        let config = create_test_config();
+        let discovered = config.discovery.discover_nodes().await.unwrap();
+        assert_eq!(discovered.len(), 2);
+        assert!(discovered.iter().any(|n| n.id == "master"));
+        assert!(discovered.iter().any(|n| n.id == "worker1"));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_membership_adds_new_nodes_and_updates_world_size() {
+        let mut config = create_test_config();
+        let newcomer = NodeInfo {
+            id: "worker2".to_string(),
+            address: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 29502),
+            gpu_count: 1,
+            gpu_memory: vec![8_000_000_000],
+            cpu_cores: 8,
+            memory: 32_000_000_000,
+            bandwidth: Some(1000),
+            role: NodeRole::Worker,
+            status: NodeStatus::Online,
+        };
+        config.discovery = DiscoveryBackend::Static(vec![
+            config.nodes[0].clone(),
+            config.nodes[1].clone(),
+            newcomer,
+        ]);
+        let mut coordinator = DistributedCoordinator::new(config);
+
+        coordinator.reconcile_membership().await.unwrap();
+
+        assert_eq!(coordinator.nodes.len(), 3);
+        assert!(coordinator.nodes.contains_key("worker2"));
+        assert_eq!(coordinator.config.world_size, 3);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_membership_marks_disappeared_nodes_offline_instead_of_removing_them() {
+        let mut config = create_test_config();
+        config.discovery = DiscoveryBackend::Static(vec![config.nodes[0].clone()]);
+        let mut coordinator = DistributedCoordinator::new(config);
+
+        coordinator.reconcile_membership().await.unwrap();
+
+        assert_eq!(coordinator.nodes.len(), 2);
+        assert!(matches!(coordinator.nodes["master"].status, NodeStatus::Online));
+        assert!(matches!(coordinator.nodes["worker1"].status, NodeStatus::Offline));
+    }
+
+    #[test]
+    fn test_parse_hex_secret_roundtrips_through_serialize() {
+        let secret = [0xABu8; 32];
+        let hex_string: String = secret.iter().map(|b| format!("{b:02x}")).collect();
+        assert_eq!(parse_hex_secret(&hex_string).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_parse_hex_secret_rejects_wrong_length() {
+        assert!(parse_hex_secret("abcd").is_err());
+    }
+
+    #[test]
+    fn test_parse_hex_secret_rejects_non_hex() {
+        assert!(parse_hex_secret(&"zz".repeat(32)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handshake_succeeds_with_matching_secret() {
+        let secret = [0x11u8; 32];
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            authenticate_incoming(&mut stream, &secret).await
+        });
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        authenticate_outgoing(&mut client, &secret, "worker1").await.unwrap();
+
+        let authenticated_node_id = server.await.unwrap().unwrap();
+        assert_eq!(authenticated_node_id, "worker1");
+    }
+
+    #[tokio::test]
+    async fn test_handshake_rejects_mismatched_secret() {
+        let server_secret = [0x11u8; 32];
+        let client_secret = [0x22u8; 32];
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            authenticate_incoming(&mut stream, &server_secret).await
+        });
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        authenticate_outgoing(&mut client, &client_secret, "worker1").await.unwrap();
+
+        let result = server.await.unwrap();
+        assert!(matches!(result, Err(MLError::AuthError(_))));
+    }
+
+    #[test]
+    fn test_zero_memory_per_rank_bytes_matches_stage_formulas() {
+        let p = 1_000_000_000u64;
+        assert_eq!(zero_memory_per_rank_bytes(1, p, 8), 4 * p + (12 * p) / 8);
+        assert_eq!(zero_memory_per_rank_bytes(2, p, 8), 2 * p + (14 * p) / 8);
+        assert_eq!(zero_memory_per_rank_bytes(3, p, 8), (16 * p) / 8);
+    }
+
+    #[test]
+    fn test_select_zero_plan_picks_the_minimum_stage_that_fits() {
+        let p = 1_000_000_000u64;
+        let plan = select_zero_plan(p, 8, zero_memory_per_rank_bytes(1, p, 8), None);
+        assert_eq!(plan.stage, 1);
+        assert_eq!(plan.offload_target, OffloadTarget::None);
+
+        let plan = select_zero_plan(p, 8, zero_memory_per_rank_bytes(2, p, 8), None);
+        assert_eq!(plan.stage, 2);
+    }
+
+    #[test]
+    fn test_select_zero_plan_escalates_to_cpu_offload_when_stage_three_still_does_not_fit() {
+        let p = 1_000_000_000u64;
+        let budget = zero_memory_per_rank_bytes(3, p, 8) - 1;
+        let plan = select_zero_plan(p, 8, budget, Some(u64::MAX));
+        assert_eq!(plan.stage, 3);
+        assert_eq!(plan.offload_target, OffloadTarget::Cpu);
+    }
+
+    #[test]
+    fn test_select_zero_plan_escalates_to_nvme_when_host_ram_is_also_insufficient() {
+        let p = 1_000_000_000u64;
+        let budget = zero_memory_per_rank_bytes(3, p, 8) - 1;
+        let plan = select_zero_plan(p, 8, budget, Some(0));
+        assert_eq!(plan.stage, 3);
+        assert_eq!(plan.offload_target, OffloadTarget::Nvme);
+    }
+
+    #[test]
+    fn test_median_duration_handles_even_and_odd_counts() {
+        let mut odd = vec![Duration::from_millis(3), Duration::from_millis(1), Duration::from_millis(2)];
+        assert_eq!(median_duration(&mut odd), Duration::from_millis(2));
+
+        let mut even = vec![Duration::from_millis(4), Duration::from_millis(1), Duration::from_millis(2), Duration::from_millis(3)];
+        assert_eq!(median_duration(&mut even), Duration::from_millis(2) + Duration::from_millis(1) / 2);
+    }
+
+    #[tokio::test]
+    async fn test_measure_median_latency_against_a_real_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let acceptor = tokio::spawn(async move {
+            for _ in 0..3 {
+                let _ = listener.accept().await;
+            }
+        });
+
+        let latency = measure_median_latency(&addr, 3, Duration::from_secs(2)).await.unwrap();
+        assert!(latency < Duration::from_secs(1));
+
+        acceptor.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_measure_median_latency_errors_when_nothing_is_listening() {
+        let addr: SocketAddr = "127.0.0.1:1".parse().unwrap();
+        let result = measure_median_latency(&addr, 1, Duration::from_millis(500)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bandwidth_probe_round_trip_reports_nonzero_throughput() {
+        let secret = [0x33u8; 32];
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let coordinator: SharedCoordinator = Arc::new(RwLock::new(DistributedCoordinator::new(create_test_config())));
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            handle_coordinator_connection(stream, &secret, coordinator).await
+        });
+
+        let mbps = measure_bandwidth_mbps(&addr, &secret, "tester", 256 * 1024).await.unwrap();
+        assert!(mbps > 0);
+
+        server.await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_cluster_by_latency_separates_a_distant_node_from_a_close_pair() {
+        let ids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let mut matrix = HashMap::new();
+        matrix.insert(("a".to_string(), "b".to_string()), Duration::from_micros(100));
+        matrix.insert(("b".to_string(), "a".to_string()), Duration::from_micros(100));
+        matrix.insert(("a".to_string(), "c".to_string()), Duration::from_millis(50));
+        matrix.insert(("c".to_string(), "a".to_string()), Duration::from_millis(50));
+        matrix.insert(("b".to_string(), "c".to_string()), Duration::from_millis(50));
+        matrix.insert(("c".to_string(), "b".to_string()), Duration::from_millis(50));
+
+        let mut groups = cluster_by_latency(&ids, &matrix);
+        for group in groups.iter_mut() {
+            group.sort();
+        }
+        groups.sort_by_key(|g| g.len());
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0], vec!["c".to_string()]);
+        assert_eq!(groups[1], vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_ring_pairs_wraps_around_and_is_empty_for_fewer_than_two_members() {
+        assert!(ring_pairs(&["solo".to_string()]).is_empty());
+
+        let members = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let pairs = ring_pairs(&members);
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs[2], SendRecvPair { from: "c".to_string(), to: "a".to_string() });
+    }
+
+    #[test]
+    fn test_build_hierarchical_schedule_elects_first_member_of_each_group_as_leader() {
+        let groups = vec![vec!["a".to_string(), "b".to_string()], vec!["c".to_string(), "d".to_string()]];
+        let schedule = build_hierarchical_schedule(&groups);
+
+        assert_eq!(schedule.intra_group_reduce_scatter.len(), 4);
+        assert_eq!(schedule.intra_group_all_gather.len(), 4);
+        assert_eq!(schedule.inter_group_all_reduce, vec![
+            SendRecvPair { from: "a".to_string(), to: "c".to_string() },
+            SendRecvPair { from: "c".to_string(), to: "a".to_string() },
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_the_service_and_marks_every_node_offline() {
+        let mut config = create_test_config();
+        config.master_port = 0;
+        let coordinator: SharedCoordinator = Arc::new(RwLock::new(DistributedCoordinator::new(config)));
+
+        assert!(!coordinator.read().await.is_running());
+
+        let service = tokio::spawn(DistributedCoordinator::start_coordinator_service(Arc::clone(&coordinator)));
+        while !coordinator.read().await.is_running() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        coordinator.read().await.shutdown();
+        tokio::time::timeout(Duration::from_secs(5), service).await.unwrap().unwrap().unwrap();
+
+        let guard = coordinator.read().await;
+        assert!(!guard.is_running());
+        assert!(guard.nodes.values().all(|n| matches!(n.status, NodeStatus::Offline)));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_is_a_no_op_before_the_service_is_started() {
+        let coordinator = DistributedCoordinator::new(create_test_config());
+        assert!(!coordinator.is_running());
+        coordinator.shutdown();
+        assert!(!coordinator.is_running());
+    }
+
+    #[test]
+    fn test_record_compressed_send_accumulates_byte_size_into_total_bytes_sent() {
+        let mut coordinator = DistributedCoordinator::new(create_test_config());
+        assert_eq!(coordinator.get_communication_stats().total_bytes_sent, 0);
+
+        let payload = crate::compression::CompressedPayload::Sparse { indices: vec![0, 1], values: vec![1.0, 2.0] };
+        coordinator.record_compressed_send(&payload);
+        coordinator.record_compressed_send(&payload);
+
+        assert_eq!(coordinator.get_communication_stats().total_bytes_sent, payload.byte_size() * 2);
+    }
+
+    #[test]
+    fn test_compress_and_record_send_uses_the_configured_algorithm() {
+        let mut coordinator = DistributedCoordinator::new(create_test_config());
+        coordinator.last_compression_config = Some(CompressionConfig {
+            enabled: true,
+            algorithm: CompressionAlgorithm::Quantization,
+            compression_ratio: 0.5,
+        });
+
+        let gradient = vec![1.0, -2.0, 3.0, -4.0];
+        let payload = coordinator.compress_and_record_send(&gradient);
+
+        assert!(matches!(payload, crate::compression::CompressedPayload::Quantized { .. }));
+        assert_eq!(coordinator.get_communication_stats().total_bytes_sent, payload.byte_size());
+    }
+
+    #[test]
+    fn test_compress_and_record_send_sends_raw_when_compression_is_disabled() {
+        let mut coordinator = DistributedCoordinator::new(create_test_config());
+        coordinator.last_compression_config =
+            Some(CompressionConfig { enabled: false, algorithm: CompressionAlgorithm::Quantization, compression_ratio: 1.0 });
+
+        let gradient = vec![1.0, -2.0, 3.0];
+        let payload = coordinator.compress_and_record_send(&gradient);
+
+        assert_eq!(payload, crate::compression::CompressedPayload::Raw(gradient));
+    }
+
+    #[test]
+    fn test_assign_ranks_is_deterministic_and_sorted_by_node_id() {
+        let ranks = assign_ranks(vec!["worker1".to_string(), "master".to_string(), "worker0".to_string()].into_iter());
+        assert_eq!(ranks.get("master"), Some(&0));
+        assert_eq!(ranks.get("worker0"), Some(&1));
+        assert_eq!(ranks.get("worker1"), Some(&2));
+    }
+
+    #[test]
+    fn test_stale_node_ids_flags_nodes_past_the_missed_interval_grace_period() {
+        let heartbeat = HeartbeatConfig { interval: Duration::from_secs(10), missed_intervals_before_offline: 3 };
+        let now = Instant::now();
+        let mut last_seen = HashMap::new();
+        last_seen.insert("fresh".to_string(), now - Duration::from_secs(5));
+        last_seen.insert("stale".to_string(), now - Duration::from_secs(31));
+
+        let node_ids = vec!["fresh".to_string(), "stale".to_string(), "unknown".to_string()];
+        let stale = DistributedCoordinator::stale_node_ids(node_ids.into_iter(), &last_seen, &heartbeat, now);
+
+        assert_eq!(stale, vec!["stale".to_string(), "unknown".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_record_heartbeat_rejoins_an_offline_node_and_recomputes_world_size() {
+        let mut coordinator = DistributedCoordinator::new(create_test_config());
+        coordinator.update_node_status("worker1", NodeStatus::Offline);
+        let mut changes = coordinator.subscribe_membership_changes();
+
+        coordinator.record_heartbeat("worker1").await.unwrap();
+
+        assert!(matches!(coordinator.nodes.get("worker1").unwrap().status, NodeStatus::Online));
+        let change = changes.try_recv().unwrap();
+        match change {
+            MembershipChange::Joined { node_id, ranks } => {
+                assert_eq!(node_id, "worker1");
+                assert_eq!(ranks.len(), 2);
+            }
+            MembershipChange::Left { .. } => panic!("expected a Joined event"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_heartbeat_ignores_an_unknown_node_id() {
+        let mut coordinator = DistributedCoordinator::new(create_test_config());
+        coordinator.record_heartbeat("ghost").await.unwrap();
+        assert!(!coordinator.nodes.contains_key("ghost"));
+    }
+
+    #[tokio::test]
+    async fn test_evict_stale_nodes_and_reconfigure_removes_a_node_and_broadcasts_left() {
+        let mut config = create_test_config();
+        config.heartbeat = HeartbeatConfig { interval: Duration::from_millis(1), missed_intervals_before_offline: 1 };
+        let mut coordinator = DistributedCoordinator::new(config);
+        coordinator.last_seen.insert("worker1".to_string(), Instant::now() - Duration::from_secs(60));
+        let mut changes = coordinator.subscribe_membership_changes();
+
+        coordinator.evict_stale_nodes_and_reconfigure().await.unwrap();
+
+        assert!(!coordinator.nodes.contains_key("worker1"));
+        assert_eq!(coordinator.config.world_size, 1);
+        let change = changes.try_recv().unwrap();
+        assert!(matches!(change, MembershipChange::Left { node_id, .. } if node_id == "worker1"));
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_round_trip_rejoins_the_sending_node() {
+        let mut config = create_test_config();
+        config.master_port = 0;
+        let secret = config.rpc_secret;
+        let node_id = config.node_id.clone();
+        let coordinator: SharedCoordinator = Arc::new(RwLock::new(DistributedCoordinator::new(config)));
+        coordinator.write().await.update_node_status("master", NodeStatus::Offline);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server_coordinator = Arc::clone(&coordinator);
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            handle_coordinator_connection(stream, &secret, server_coordinator).await
+        });
+
+        send_heartbeat(&addr, &secret, &node_id).await.unwrap();
+        server.await.unwrap().unwrap();
+
+        assert!(matches!(coordinator.read().await.nodes.get("master").unwrap().status, NodeStatus::Online));
+    }
 }
\ No newline at end of file
@@ -91,6 +91,10 @@ pub enum MLError {
     /// Hardware not supported
     #[error("Hardware not supported: {0}")]
     HardwareNotSupported(String),
+
+    /// RPC peer authentication failed (bad/missing handshake HMAC)
+    #[error("Authentication failed: {0}")]
+    AuthError(String),
 }
 
 impl From<walkdir::Error> for MLError {
@@ -124,6 +128,7 @@ impl MLError {
             Self::FrameworkNotFound(_) => ErrorSeverity::High,
             Self::HardwareNotSupported(_) => ErrorSeverity::High,
             Self::ResourceConstraint(_) => ErrorSeverity::High,
+            Self::AuthError(_) => ErrorSeverity::High,
             Self::InvalidConfiguration(_) => ErrorSeverity::Medium,
             Self::IncompatibleVersion { .. } => ErrorSeverity::Medium,
             Self::MissingDependency(_) => ErrorSeverity::Medium,
@@ -158,6 +163,9 @@ impl MLError {
             Self::Timeout(_) => {
                 "Retry the operation or increase timeout".to_string()
             }
+            Self::AuthError(_) => {
+                "Verify rpc_secret matches across every coordinator and worker node".to_string()
+            }
             _ => "Check logs for more details".to_string(),
         }
     }
@@ -172,4 +180,79 @@ pub enum ErrorSeverity {
     Medium,
     /// High severity - operation must be aborted
     High,
+}
+
+/// Process exit code for each [`ErrorSeverity`], so CI and git hooks can branch on the category of
+/// failure instead of parsing stderr text. Timeouts get their own code rather than whatever
+/// severity they happen to carry, since "the operation didn't finish in time" is a meaningfully
+/// different signal to a caller deciding whether to retry than "the operation failed".
+pub const EXIT_CODE_LOW: i32 = 10;
+pub const EXIT_CODE_MEDIUM: i32 = 20;
+pub const EXIT_CODE_HIGH: i32 = 30;
+pub const EXIT_CODE_TIMEOUT: i32 = 40;
+
+impl MLError {
+    /// The process exit code this error should surface as
+    pub fn exit_code(&self) -> i32 {
+        if matches!(self, Self::Timeout(_)) {
+            return EXIT_CODE_TIMEOUT;
+        }
+        match self.severity() {
+            ErrorSeverity::Low => EXIT_CODE_LOW,
+            ErrorSeverity::Medium => EXIT_CODE_MEDIUM,
+            ErrorSeverity::High => EXIT_CODE_HIGH,
+        }
+    }
+}
+
+/// Tuning knobs for [`retry_with_backoff`]
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    /// Total number of attempts, including the first -- so `max_attempts: 3` means up to 2 retries
+    pub max_attempts: u32,
+    /// Delay before the first retry; each subsequent retry doubles it, up to `max_delay`
+    pub base_delay: std::time::Duration,
+    /// Ceiling the exponential backoff is clamped to, regardless of attempt count
+    pub max_delay: std::time::Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: std::time::Duration::from_millis(200),
+            max_delay: std::time::Duration::from_secs(10),
+        }
+    }
+}
+
+/// Re-run `operation` while it keeps failing with an [`MLError::is_recoverable`] error, waiting
+/// an exponentially growing delay (with up to 25% jitter, to avoid a thundering herd of callers
+/// all retrying in lockstep) between attempts, clamped to `config.max_delay`. Gives up and returns
+/// the last error once `config.max_attempts` is reached or the error isn't recoverable.
+///
+/// Intended for the operations this crate already knows can fail transiently -- model loading,
+/// HTTP fetches (e.g. [`crate::distributed::discover_consul_nodes`]), and subprocess execution --
+/// so a single flaky network call or a momentarily-busy disk doesn't fail a whole optimization run.
+pub async fn retry_with_backoff<T, F, Fut>(config: RetryConfig, mut operation: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut delay = config.base_delay;
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < config.max_attempts && e.is_recoverable() => {
+                let jitter = 1.0 + rand::random::<f64>() * 0.25;
+                let sleep_for = delay.mul_f64(jitter).min(config.max_delay);
+                tokio::time::sleep(sleep_for).await;
+                delay = (delay * 2).min(config.max_delay);
+            }
+            Err(e) => return Err(e),
+        }
+    }
 }
\ No newline at end of file
@@ -2,9 +2,12 @@
 
 use crate::error::{MLError, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio::process::Command as AsyncCommand;
-use tracing::{debug, info, warn};
+use tokio::sync::RwLock;
+use tracing::info;
 use which::which;
 
 /// Framework types
@@ -26,349 +29,689 @@ pub struct FrameworkInfo {
     pub path: String,
     pub features: Vec<String>,
     pub python_version: Option<String>,
+    /// Structured description of the accelerator backend the install was built against, if any
+    pub accelerator: Option<AcceleratorInfo>,
+    /// What the installed wheel/package was built for, independent of what hardware is actually
+    /// present (e.g. a `+cu121` wheel on a GPU-less CI runner is still a CUDA build, not CPU)
+    pub build_variant: Option<BuildVariant>,
+    /// Whether this process is running inside a container. Not framework-specific, but attached
+    /// to every detected [`FrameworkInfo`] so callers recommending GPU/TensorRT tuning can check
+    /// it alongside `build_variant` without a second probe.
+    #[serde(default)]
+    pub in_container: bool,
+}
+
+/// What a framework install was built to target, as distinct from what it can actually use on
+/// this machine right now ([`AcceleratorInfo`] covers that). A CPU-only wheel and a CUDA wheel
+/// with no GPU present both end up with `accelerator: None`, but only the latter should ever be
+/// told to install a driver instead of just "this is a CPU build".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", content = "version")]
+pub enum BuildVariant {
+    /// CPU-only wheel, e.g. PyTorch's `+cpu` local version tag or the `tensorflow-cpu` package
+    Cpu,
+    /// Built against NVIDIA CUDA at the given toolkit version
+    Cuda(String),
+    /// Built against AMD ROCm at the given toolkit version
+    Rocm(String),
+}
+
+/// Accelerator backend kind a framework install was compiled against
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AcceleratorBackend {
+    /// NVIDIA CUDA
+    Cuda,
+    /// AMD ROCm/HIP. PyTorch ships distinct HIP builds that still report through the `cuda.*`
+    /// API (`torch.cuda.is_available()` returns `true`), so this must be distinguished by
+    /// checking `torch.version.hip` rather than assuming CUDA.
+    Rocm,
+    /// Apple Metal Performance Shaders
+    Mps,
+}
+
+/// Structured description of the accelerator hardware/toolkit backing a framework install
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcceleratorInfo {
+    pub backend: AcceleratorBackend,
+    /// Number of accelerator devices visible to the framework
+    pub device_count: u32,
+    /// CUDA toolkit version the framework was built against
+    pub cuda_version: Option<String>,
+    /// ROCm/HIP toolkit version the framework was built against
+    pub hip_version: Option<String>,
+    /// Per-device identifiers: compute capability (e.g. `"8.6"`) for CUDA devices, or the
+    /// product name reported by `amd-smi`/`rocm-smi` for ROCm devices. Empty for MPS, which
+    /// exposes a single unified device with no per-device enumeration.
+    pub device_capabilities: Vec<String>,
 }
 
 // ============================================================================
 // DETECTION FUNCTIONS
 // ============================================================================
+//
+// Historically each `detect_*` function spawned two to four of its own `python -c` subprocesses
+// (version, then accelerator, then distributed, then python_version). On a machine with a slow
+// Python startup a full `detect_all_frameworks` scan was easily a dozen-plus interpreter launches
+// taking seconds. `run_combined_probe` instead launches a single Python interpreter that
+// `try/except`-imports every candidate framework, gathers everything needed for each hit, and
+// prints one JSON document that deserializes straight into `Vec<FrameworkInfo>`. The individual
+// `detect_pytorch`/`detect_tensorflow`/etc. functions below are thin wrappers over that same
+// probe for callers who only want one framework.
 
-/// Detect PyTorch
-pub async fn detect_pytorch() -> Result<FrameworkInfo> {
-    let python_path = which("python3")
-        .or_else(|_| which("python"))
-        .map_err(|e| MLError::FrameworkNotFound(format!("Python not found: {}", e)))?;
+/// Default wall-clock budget for the combined framework probe. A broken CUDA driver can make
+/// `import torch` hang indefinitely inside the subprocess, so this bounds the worst case rather
+/// than trusting every framework's import to fail fast.
+const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(15);
 
-    let output = AsyncCommand::new(&python_path)
-        .args(&["-c", "import torch; print(torch.__version__); print(torch.__file__)"])
-        .output()
-        .await
-        .map_err(|e| MLError::FrameworkDetectionFailed(format!("Failed to run Python: {}", e)))?;
+/// Single embedded Python script that probes every candidate framework and prints one JSON array
+/// of objects, each shaped like [`FrameworkInfo`]. Frameworks that aren't installed (or whose
+/// probe raises) are simply absent from the array rather than failing the whole probe.
+const COMBINED_PROBE_SCRIPT: &str = r#"
+import json
+import subprocess
+import sys
 
-    if !output.status.success() {
-        return Err(MLError::FrameworkNotFound("PyTorch not available".to_string()));
-    }
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<&str> = output_str.trim().split('\n').collect();
-    
-    if lines.len() < 2 {
-        return Err(MLError::FrameworkDetectionFailed("Invalid PyTorch output".to_string()));
-    }
+def py_optional(value):
+    return None if value is None else value
 
-    let version = lines[0].to_string();
-    let path = lines[1].to_string();
 
-    // Detect features
-    let mut features = vec!["python".to_string()];
-    
-    // Check for CUDA support
-    let cuda_check = AsyncCommand::new(&python_path)
-        .args(&["-c", "import torch; print(torch.cuda.is_available())"])
-        .output()
-        .await;
-        
-    if let Ok(cuda_output) = cuda_check {
-        if String::from_utf8_lossy(&cuda_output.stdout).trim() == "True" {
-            features.push("cuda".to_string());
-        }
+def rocm_device_names():
+    for cmd in (["amd-smi", "list", "--csv"], ["rocm-smi", "--showproductname", "--csv"]):
+        try:
+            result = subprocess.run(cmd, capture_output=True, text=True, timeout=5)
+            rows = result.stdout.strip().splitlines()[1:]
+            names = [row.split(",")[-1].strip() for row in rows if row.strip()]
+            names = [name for name in names if name]
+            if names:
+                return names
+        except Exception:
+            continue
+    return []
+
+
+def classify_pytorch_build_variant(version, cuda_version, hip_version):
+    # PyTorch wheels carry their target in a `+<tag>` local version, e.g. "2.1.0+cu121",
+    # "2.1.0+rocm5.6", "2.1.0+cpu" -- this is accurate even with no GPU physically present,
+    # unlike checking device availability.
+    if "+" in version:
+        tag = version.split("+", 1)[1]
+        if tag == "cpu":
+            return {"kind": "Cpu"}
+        if tag.startswith("cu"):
+            digits = tag[2:]
+            formatted = f"{digits[:-1]}.{digits[-1]}" if len(digits) > 1 else digits
+            return {"kind": "Cuda", "version": formatted}
+        if tag.startswith("rocm"):
+            return {"kind": "Rocm", "version": tag[len("rocm"):]}
+    # Conda builds often omit the local version tag; fall back to the runtime-reported toolkit.
+    if hip_version:
+        return {"kind": "Rocm", "version": hip_version}
+    if cuda_version:
+        return {"kind": "Cuda", "version": cuda_version}
+    return {"kind": "Cpu"}
+
+
+def probe_pytorch():
+    import torch
+
+    features = ["python"]
+    accelerator = None
+    cuda_version = py_optional(getattr(torch.version, "cuda", None))
+    hip_version = py_optional(getattr(torch.version, "hip", None))
+    try:
+        cuda_available = torch.cuda.is_available()
+        device_count = torch.cuda.device_count() if cuda_available else 0
+        try:
+            mps_available = torch.backends.mps.is_available()
+        except Exception:
+            mps_available = False
+
+        if hip_version is not None:
+            accelerator = {
+                "backend": "Rocm",
+                "device_count": device_count,
+                "cuda_version": cuda_version,
+                "hip_version": hip_version,
+                "device_capabilities": rocm_device_names(),
+            }
+            features.append("rocm")
+        elif cuda_available:
+            capabilities = []
+            for i in range(device_count):
+                try:
+                    major, minor = torch.cuda.get_device_capability(i)
+                    capabilities.append(f"{major}.{minor}")
+                except Exception:
+                    capabilities.append("unknown")
+            accelerator = {
+                "backend": "Cuda",
+                "device_count": device_count,
+                "cuda_version": cuda_version,
+                "hip_version": hip_version,
+                "device_capabilities": capabilities,
+            }
+            features.append("cuda")
+        elif mps_available:
+            accelerator = {
+                "backend": "Mps",
+                "device_count": 1,
+                "cuda_version": cuda_version,
+                "hip_version": hip_version,
+                "device_capabilities": [],
+            }
+            features.append("mps")
+    except Exception:
+        pass
+
+    try:
+        import torch.distributed  # noqa: F401
+
+        features.append("distributed")
+    except Exception:
+        pass
+
+    features.append("amp")  # PyTorch 1.6+ has AMP
+
+    return {
+        "framework_type": "PyTorch",
+        "version": torch.__version__,
+        "path": torch.__file__,
+        "features": features,
+        "accelerator": accelerator,
+        "build_variant": classify_pytorch_build_variant(torch.__version__, cuda_version, hip_version),
     }
 
-    // Check for distributed support
-    let dist_check = AsyncCommand::new(&python_path)
-        .args(&["-c", "import torch.distributed; print('True')"])
-        .output()
-        .await;
-        
-    if dist_check.is_ok() {
-        features.push("distributed".to_string());
+
+def classify_tensorflow_build_variant(has_gpu_devices, cuda_version):
+    # TensorFlow's public API doesn't expose which PyPI distribution was installed, so check
+    # package metadata directly for the CPU-only `tensorflow-cpu` distribution.
+    try:
+        from importlib import metadata as importlib_metadata
+    except ImportError:
+        import importlib_metadata
+    try:
+        importlib_metadata.distribution("tensorflow-cpu")
+        return {"kind": "Cpu"}
+    except Exception:
+        pass
+    if has_gpu_devices:
+        return {"kind": "Cuda", "version": cuda_version}
+    return {"kind": "Cpu"}
+
+
+def probe_tensorflow():
+    import tensorflow as tf
+
+    features = ["python"]
+    accelerator = None
+    has_gpu_devices = False
+    cuda_version = None
+    try:
+        devices = tf.config.list_physical_devices("GPU")
+        if devices:
+            has_gpu_devices = True
+            cuda_version = py_optional(tf.sysconfig.get_build_info().get("cuda_version"))
+            accelerator = {
+                "backend": "Cuda",
+                "device_count": len(devices),
+                "cuda_version": cuda_version,
+                "hip_version": None,
+                "device_capabilities": [],
+            }
+            features.append("gpu")
+    except Exception:
+        pass
+
+    features.append("mixed_precision")
+    features.append("xla")
+
+    return {
+        "framework_type": "TensorFlow",
+        "version": tf.__version__,
+        "path": tf.__file__,
+        "features": features,
+        "accelerator": accelerator,
+        "build_variant": classify_tensorflow_build_variant(has_gpu_devices, cuda_version),
     }
 
-    // Check for AMP support
-    features.push("amp".to_string()); // PyTorch 1.6+ has AMP
 
-    Ok(FrameworkInfo {
-        framework_type: FrameworkType::PyTorch,
-        version,
-        path,
-        features,
-        python_version: get_python_version(&python_path).await,
-    })
-}
+def probe_onnx():
+    import onnxruntime as ort
 
-/// Detect TensorFlow
-pub async fn detect_tensorflow() -> Result<FrameworkInfo> {
-    let python_path = which("python3")
-        .or_else(|_| which("python"))
-        .map_err(|e| MLError::FrameworkNotFound(format!("Python not found: {}", e)))?;
+    return {
+        "framework_type": "ONNX",
+        "version": ort.__version__,
+        "path": ort.__file__,
+        "features": ["python", "inference"],
+        "accelerator": None,
+        "build_variant": None,
+    }
 
-    let output = AsyncCommand::new(&python_path)
-        .args(&["-c", "import tensorflow as tf; print(tf.__version__); print(tf.__file__)"])
-        .output()
-        .await
-        .map_err(|e| MLError::FrameworkDetectionFailed(format!("Failed to run Python: {}", e)))?;
 
-    if !output.status.success() {
-        return Err(MLError::FrameworkNotFound("TensorFlow not available".to_string()));
+def probe_huggingface():
+    import transformers
+
+    return {
+        "framework_type": "HuggingFace",
+        "version": transformers.__version__,
+        "path": transformers.__file__,
+        "features": ["python", "transformers", "tokenizers"],
+        "accelerator": None,
+        "build_variant": None,
     }
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<&str> = output_str.trim().split('\n').collect();
-    
-    if lines.len() < 2 {
-        return Err(MLError::FrameworkDetectionFailed("Invalid TensorFlow output".to_string()));
+
+def probe_jax():
+    import jax
+
+    return {
+        "framework_type": "JAX",
+        "version": jax.__version__,
+        "path": jax.__file__,
+        "features": ["python", "jit", "xla"],
+        "accelerator": None,
+        "build_variant": None,
     }
 
-    let version = lines[0].to_string();
-    let path = lines[1].to_string();
 
-    // Detect features
-    let mut features = vec!["python".to_string()];
-    
-    // Check for GPU support
-    let gpu_check = AsyncCommand::new(&python_path)
-        .args(&["-c", "import tensorflow as tf; print(len(tf.config.list_physical_devices('GPU')) > 0)"])
-        .output()
-        .await;
-        
-    if let Ok(gpu_output) = gpu_check {
-        if String::from_utf8_lossy(&gpu_output.stdout).trim() == "True" {
-            features.push("gpu".to_string());
-        }
+def probe_mxnet():
+    import mxnet as mx
+
+    return {
+        "framework_type": "MXNet",
+        "version": mx.__version__,
+        "path": mx.__file__,
+        "features": ["python", "gluon"],
+        "accelerator": None,
+        "build_variant": None,
     }
 
-    // TensorFlow 2.x has mixed precision and XLA
-    features.push("mixed_precision".to_string());
-    features.push("xla".to_string());
 
-    Ok(FrameworkInfo {
-        framework_type: FrameworkType::TensorFlow,
-        version,
-        path,
-        features,
-        python_version: get_python_version(&python_path).await,
-    })
+python_version = ".".join(map(str, sys.version_info[:3]))
+results = []
+for probe in (
+    probe_pytorch,
+    probe_tensorflow,
+    probe_onnx,
+    probe_huggingface,
+    probe_jax,
+    probe_mxnet,
+):
+    try:
+        info = probe()
+    except Exception:
+        info = None
+    if info is not None:
+        info["python_version"] = python_version
+        results.append(info)
+
+print(json.dumps(results))
+"#;
+
+/// Whether this process appears to be running inside a container: checks the conventional
+/// `/.dockerenv` marker file first, then falls back to cgroup hints (covers Docker, containerd,
+/// and Kubernetes pods that don't create `/.dockerenv`).
+fn detect_container_context() -> bool {
+    if Path::new("/.dockerenv").exists() {
+        return true;
+    }
+
+    std::fs::read_to_string("/proc/self/cgroup")
+        .map(|contents| cgroup_indicates_container(&contents))
+        .unwrap_or(false)
 }
 
-/// Detect ONNX Runtime
-pub async fn detect_onnx() -> Result<FrameworkInfo> {
-    let python_path = which("python3")
-        .or_else(|_| which("python"))
-        .map_err(|e| MLError::FrameworkNotFound(format!("Python not found: {}", e)))?;
+/// Whether a `/proc/self/cgroup`-style listing contains a known container-runtime hint
+fn cgroup_indicates_container(cgroup_contents: &str) -> bool {
+    cgroup_contents
+        .lines()
+        .any(|line| ["docker", "kubepods", "containerd"].iter().any(|hint| line.contains(hint)))
+}
 
-    let output = AsyncCommand::new(&python_path)
-        .args(&["-c", "import onnxruntime as ort; print(ort.__version__); print(ort.__file__)"])
-        .output()
-        .await
-        .map_err(|e| MLError::FrameworkDetectionFailed(format!("Failed to run Python: {}", e)))?;
+/// Run [`COMBINED_PROBE_SCRIPT`] in a single Python interpreter, bounded by `timeout` so a hanging
+/// import can't wedge the caller forever. The child is killed on timeout rather than left to drift
+/// as an orphan.
+async fn run_combined_probe(python_path: &Path, timeout: Duration) -> Result<Vec<FrameworkInfo>> {
+    let output = tokio::time::timeout(
+        timeout,
+        AsyncCommand::new(python_path)
+            .args(&["-c", COMBINED_PROBE_SCRIPT])
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .map_err(|_| MLError::Timeout(timeout))?
+    .map_err(|e| MLError::FrameworkDetectionFailed(format!("Failed to run Python: {}", e)))?;
 
     if !output.status.success() {
-        return Err(MLError::FrameworkNotFound("ONNX Runtime not available".to_string()));
+        return Err(MLError::FrameworkDetectionFailed(
+            "Combined framework probe exited with an error".to_string(),
+        ));
     }
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<&str> = output_str.trim().split('\n').collect();
-    
-    if lines.len() < 2 {
-        return Err(MLError::FrameworkDetectionFailed("Invalid ONNX output".to_string()));
+    let mut frameworks: Vec<FrameworkInfo> = serde_json::from_slice(&output.stdout)?;
+    let in_container = detect_container_context();
+    for info in &mut frameworks {
+        info.in_container = in_container;
     }
+    Ok(frameworks)
+}
+
+/// Detect a single framework by filtering it out of a combined probe run. Costs one interpreter
+/// launch, same as calling [`detect_all_frameworks`] and keeping only one entry.
+async fn detect_one(framework_type: FrameworkType, display_name: &str) -> Result<FrameworkInfo> {
+    let python_path = which("python3")
+        .or_else(|_| which("python"))
+        .map_err(|e| MLError::FrameworkNotFound(format!("Python not found: {}", e)))?;
 
-    let version = lines[0].to_string();
-    let path = lines[1].to_string();
+    run_combined_probe(&python_path, DEFAULT_PROBE_TIMEOUT)
+        .await?
+        .into_iter()
+        .find(|info| info.framework_type == framework_type)
+        .ok_or_else(|| MLError::FrameworkNotFound(format!("{} not available", display_name)))
+}
 
-    let features = vec!["python".to_string(), "inference".to_string()];
+/// Detect PyTorch
+pub async fn detect_pytorch() -> Result<FrameworkInfo> {
+    detect_one(FrameworkType::PyTorch, "PyTorch").await
+}
 
-    Ok(FrameworkInfo {
-        framework_type: FrameworkType::ONNX,
-        version,
-        path,
-        features,
-        python_version: get_python_version(&python_path).await,
-    })
+/// Detect TensorFlow
+pub async fn detect_tensorflow() -> Result<FrameworkInfo> {
+    detect_one(FrameworkType::TensorFlow, "TensorFlow").await
+}
+
+/// Detect ONNX Runtime
+pub async fn detect_onnx() -> Result<FrameworkInfo> {
+    detect_one(FrameworkType::ONNX, "ONNX Runtime").await
 }
 
 /// Detect Hugging Face Transformers
 pub async fn detect_huggingface() -> Result<FrameworkInfo> {
+    detect_one(FrameworkType::HuggingFace, "Hugging Face Transformers").await
+}
+
+/// Detect JAX
+pub async fn detect_jax() -> Result<FrameworkInfo> {
+    detect_one(FrameworkType::JAX, "JAX").await
+}
+
+/// Detect MXNet
+pub async fn detect_mxnet() -> Result<FrameworkInfo> {
+    detect_one(FrameworkType::MXNet, "MXNet").await
+}
+
+/// Detect all available frameworks in a single Python interpreter invocation, bounded by
+/// [`DEFAULT_PROBE_TIMEOUT`]. See [`detect_all_frameworks_with_timeout`] to override the timeout.
+pub async fn detect_all_frameworks() -> Result<Vec<FrameworkInfo>> {
+    detect_all_frameworks_with_timeout(DEFAULT_PROBE_TIMEOUT).await
+}
+
+/// As [`detect_all_frameworks`], but with an explicit probe timeout instead of
+/// [`DEFAULT_PROBE_TIMEOUT`].
+pub async fn detect_all_frameworks_with_timeout(timeout: Duration) -> Result<Vec<FrameworkInfo>> {
     let python_path = which("python3")
         .or_else(|_| which("python"))
         .map_err(|e| MLError::FrameworkNotFound(format!("Python not found: {}", e)))?;
 
-    let output = AsyncCommand::new(&python_path)
-        .args(&["-c", "import transformers; print(transformers.__version__); print(transformers.__file__)"])
-        .output()
-        .await
-        .map_err(|e| MLError::FrameworkDetectionFailed(format!("Failed to run Python: {}", e)))?;
+    let frameworks = run_combined_probe(&python_path, timeout).await?;
+    for info in &frameworks {
+        info!("Detected {:?}: v{}", info.framework_type, info.version);
+    }
+    Ok(frameworks)
+}
 
-    if !output.status.success() {
-        return Err(MLError::FrameworkNotFound("Hugging Face Transformers not available".to_string()));
+// ============================================================================
+// TRAINING-ACCELERATION STACK DETECTION
+// ============================================================================
+//
+// HuggingFace Accelerate, DeepSpeed, and FSDP sit on top of the base frameworks detected above
+// rather than being a `FrameworkType` in their own right, so they get a separate, always-succeeds
+// probe (`detect_training_stack`) meant to be called alongside `detect_all_frameworks` instead of
+// folded into it.
+
+/// Whether HuggingFace Accelerate is installed, and which distributed/mixed-precision backends
+/// it was built with support for
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccelerateInfo {
+    pub version: String,
+    pub supports_deepspeed: bool,
+    pub supports_fsdp: bool,
+    pub supports_mixed_precision: bool,
+}
+
+/// Whether DeepSpeed is installed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeepSpeedInfo {
+    pub version: String,
+}
+
+/// Whether the installed stack can actually run FP8 training. Both conditions must hold:
+/// compute capability 8.9+ (Ada Lovelace/Hopper and newer) for the hardware FP8 tensor cores,
+/// and an engine (currently only `transformer_engine`) that knows how to drive them.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Fp8Support {
+    pub compute_capability_sufficient: bool,
+    pub transformer_engine_installed: bool,
+}
+
+impl Fp8Support {
+    pub fn available(&self) -> bool {
+        self.compute_capability_sufficient && self.transformer_engine_installed
     }
+}
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<&str> = output_str.trim().split('\n').collect();
-    
-    if lines.len() < 2 {
-        return Err(MLError::FrameworkDetectionFailed("Invalid Transformers output".to_string()));
+/// Training-acceleration ecosystem layered on top of the base frameworks, gating which advanced
+/// optimizations (FSDP sharding, FP8 mixed precision, ZeRO offload) are actually runnable on this
+/// machine rather than just theoretically applicable to the detected framework
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrainingStackInfo {
+    pub accelerate: Option<AccelerateInfo>,
+    pub deepspeed: Option<DeepSpeedInfo>,
+    /// Whether `torch.distributed.fsdp` is importable. Unlike Accelerate/DeepSpeed this ships
+    /// inside PyTorch itself (1.11+), so there's no separate version to report.
+    pub fsdp_available: bool,
+    pub fp8: Fp8Support,
+}
+
+/// Probe the training-acceleration stack. Never fails outright: each component that isn't
+/// installed or can't be probed is simply absent/`false` in the result, the same soft-fail
+/// convention the base framework probes use for accelerator detection.
+pub async fn detect_training_stack() -> TrainingStackInfo {
+    TrainingStackInfo {
+        accelerate: detect_accelerate().await,
+        deepspeed: detect_deepspeed().await,
+        fsdp_available: fsdp_available().await,
+        fp8: detect_fp8_support().await,
     }
+}
+
+/// Detect HuggingFace Accelerate and which plugins it reports support for. Prefers parsing
+/// `accelerate.__version__` plus `accelerate.utils.is_*_available()` helpers directly over
+/// shelling out to the `accelerate env` CLI, since that CLI's output is meant for humans
+/// debugging a launch config rather than for machine parsing.
+async fn detect_accelerate() -> Option<AccelerateInfo> {
+    let python_path = which("python3").or_else(|_| which("python")).ok()?;
 
-    let version = lines[0].to_string();
-    let path = lines[1].to_string();
+    let probe = r#"
+import accelerate
+print(accelerate.__version__)
+try:
+    from accelerate.utils import is_deepspeed_available
+    print(is_deepspeed_available())
+except Exception:
+    print(False)
+try:
+    from accelerate.utils import is_fsdp_available
+    print(is_fsdp_available())
+except Exception:
+    print(False)
+print(True) # mixed precision has been a core Accelerate feature since 0.x
+"#;
+
+    let output = AsyncCommand::new(&python_path).args(&["-c", probe]).output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
 
-    let features = vec![
-        "python".to_string(),
-        "transformers".to_string(),
-        "tokenizers".to_string(),
-    ];
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = output_str.trim().lines().collect();
+    if lines.len() < 4 {
+        return None;
+    }
 
-    Ok(FrameworkInfo {
-        framework_type: FrameworkType::HuggingFace,
-        version,
-        path,
-        features,
-        python_version: get_python_version(&python_path).await,
+    Some(AccelerateInfo {
+        version: lines[0].to_string(),
+        supports_deepspeed: lines[1] == "True",
+        supports_fsdp: lines[2] == "True",
+        supports_mixed_precision: lines[3] == "True",
     })
 }
 
-/// Detect JAX
-pub async fn detect_jax() -> Result<FrameworkInfo> {
-    let python_path = which("python3")
-        .or_else(|_| which("python"))
-        .map_err(|e| MLError::FrameworkNotFound(format!("Python not found: {}", e)))?;
+/// Detect DeepSpeed
+async fn detect_deepspeed() -> Option<DeepSpeedInfo> {
+    let python_path = which("python3").or_else(|_| which("python")).ok()?;
 
     let output = AsyncCommand::new(&python_path)
-        .args(&["-c", "import jax; print(jax.__version__); print(jax.__file__)"])
+        .args(&["-c", "import deepspeed; print(deepspeed.__version__)"])
         .output()
         .await
-        .map_err(|e| MLError::FrameworkDetectionFailed(format!("Failed to run Python: {}", e)))?;
+        .ok()?;
 
     if !output.status.success() {
-        return Err(MLError::FrameworkNotFound("JAX not available".to_string()));
+        return None;
     }
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<&str> = output_str.trim().split('\n').collect();
-    
-    if lines.len() < 2 {
-        return Err(MLError::FrameworkDetectionFailed("Invalid JAX output".to_string()));
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if version.is_empty() {
+        return None;
     }
 
-    let version = lines[0].to_string();
-    let path = lines[1].to_string();
-
-    let features = vec!["python".to_string(), "jit".to_string(), "xla".to_string()];
-
-    Ok(FrameworkInfo {
-        framework_type: FrameworkType::JAX,
-        version,
-        path,
-        features,
-        python_version: get_python_version(&python_path).await,
-    })
+    Some(DeepSpeedInfo { version })
 }
 
-/// Detect MXNet
-pub async fn detect_mxnet() -> Result<FrameworkInfo> {
-    let python_path = which("python3")
-        .or_else(|_| which("python"))
-        .map_err(|e| MLError::FrameworkNotFound(format!("Python not found: {}", e)))?;
+/// Whether `torch.distributed.fsdp` (PyTorch's built-in Fully Sharded Data Parallel) is
+/// importable
+async fn fsdp_available() -> bool {
+    let Ok(python_path) = which("python3").or_else(|_| which("python")) else {
+        return false;
+    };
 
-    let output = AsyncCommand::new(&python_path)
-        .args(&["-c", "import mxnet as mx; print(mx.__version__); print(mx.__file__)"])
+    AsyncCommand::new(&python_path)
+        .args(&["-c", "import torch.distributed.fsdp"])
         .output()
         .await
-        .map_err(|e| MLError::FrameworkDetectionFailed(format!("Failed to run Python: {}", e)))?;
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Probe for FP8 training support: compute capability 8.9+ (Ada Lovelace/Hopper and newer) via
+/// `torch.cuda.get_device_capability()`, and whether `transformer_engine` is installed to
+/// actually drive the FP8 tensor cores
+async fn detect_fp8_support() -> Fp8Support {
+    let fallback = Fp8Support {
+        compute_capability_sufficient: false,
+        transformer_engine_installed: false,
+    };
+
+    let Ok(python_path) = which("python3").or_else(|_| which("python")) else {
+        return fallback;
+    };
 
+    let probe = r#"
+import torch
+if torch.cuda.is_available():
+    major, minor = torch.cuda.get_device_capability(0)
+else:
+    major, minor = 0, 0
+print(major, minor)
+try:
+    import transformer_engine
+    print(True)
+except Exception:
+    print(False)
+"#;
+
+    let Ok(output) = AsyncCommand::new(&python_path).args(&["-c", probe]).output().await else {
+        return fallback;
+    };
     if !output.status.success() {
-        return Err(MLError::FrameworkNotFound("MXNet not available".to_string()));
+        return fallback;
     }
 
     let output_str = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<&str> = output_str.trim().split('\n').collect();
-    
+    let lines: Vec<&str> = output_str.trim().lines().collect();
     if lines.len() < 2 {
-        return Err(MLError::FrameworkDetectionFailed("Invalid MXNet output".to_string()));
+        return fallback;
     }
 
-    let version = lines[0].to_string();
-    let path = lines[1].to_string();
-
-    let features = vec!["python".to_string(), "gluon".to_string()];
+    let compute_capability_sufficient = lines[0]
+        .split_once(' ')
+        .and_then(|(major, minor)| Some((major.parse::<u32>().ok()?, minor.parse::<u32>().ok()?)))
+        .map(|(major, minor)| (major, minor) >= (8, 9))
+        .unwrap_or(false);
 
-    Ok(FrameworkInfo {
-        framework_type: FrameworkType::MXNet,
-        version,
-        path,
-        features,
-        python_version: get_python_version(&python_path).await,
-    })
+    Fp8Support {
+        compute_capability_sufficient,
+        transformer_engine_installed: lines[1] == "True",
+    }
 }
 
-/// Get Python version
-async fn get_python_version(python_path: &PathBuf) -> Option<String> {
-    let output = AsyncCommand::new(python_path)
-        .args(&["-c", "import sys; print('.'.join(map(str, sys.version_info[:3])))"])
-        .output()
-        .await
-        .ok()?;
-
-    if output.status.success() {
-        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
-    } else {
-        None
-    }
+/// Extract the `(major, minor)` version pair from a framework's version string, tolerant of
+/// trailing build/pre-release metadata (PyTorch's `+cu121`, TensorFlow's `-rc0`) that would trip
+/// up a strict semver parser. Returns `None` if the string doesn't start with two dot-separated
+/// numbers.
+fn parse_major_minor(version: &str) -> Option<(u32, u32)> {
+    let core = version.split(['+', '-']).next().unwrap_or(version);
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
 }
 
-/// Detect all available frameworks
-pub async fn detect_all_frameworks() -> Result<Vec<FrameworkInfo>> {
-    let mut frameworks = Vec::new();
-    
-    // Try to detect each framework type individually
-    let detectors = [
-        ("PyTorch", FrameworkType::PyTorch),
-        ("TensorFlow", FrameworkType::TensorFlow),
-        ("ONNX", FrameworkType::ONNX),
-        ("Hugging Face", FrameworkType::HuggingFace),
-        ("JAX", FrameworkType::JAX),
-        ("MXNet", FrameworkType::MXNet),
-    ];
-
-    for (name, framework_type) in detectors {
-        let result = match framework_type {
-            FrameworkType::PyTorch => detect_pytorch().await,
-            FrameworkType::TensorFlow => detect_tensorflow().await,
-            FrameworkType::ONNX => detect_onnx().await,
-            FrameworkType::HuggingFace => detect_huggingface().await,
-            FrameworkType::JAX => detect_jax().await,
-            FrameworkType::MXNet => detect_mxnet().await,
-        };
-        
-        match result {
-            Ok(info) => {
-                info!("Detected {}: v{}", name, info.version);
-                frameworks.push(info);
+/// Get framework-specific optimization recommendations, gated on the detected version and
+/// accelerator so e.g. a PyTorch 1.x install isn't told to use `torch.compile` (added in 2.0),
+/// and a CPU-only install isn't told to tune CUDA/TensorRT knobs it doesn't have. FP8 and
+/// DeepSpeed advice live in [`get_training_stack_optimizations`] instead, since those need
+/// [`TrainingStackInfo`] rather than anything on [`FrameworkInfo`] itself.
+pub fn get_framework_optimizations(info: &FrameworkInfo) -> Vec<String> {
+    let major = parse_major_minor(&info.version).map(|(major, _)| major).unwrap_or(0);
+    let has_cuda = matches!(
+        info.accelerator,
+        Some(AcceleratorInfo { backend: AcceleratorBackend::Cuda, .. })
+    );
+
+    match info.framework_type {
+        FrameworkType::PyTorch => {
+            let mut recommendations = vec![
+                "Use DataLoader with num_workers > 0".to_string(),
+                "Enable mixed precision training with autocast".to_string(),
+            ];
+            if major >= 2 && has_cuda {
+                recommendations.push("Use torch.compile for model optimization".to_string());
+                recommendations.push(
+                    "Set TORCH_BACKENDS_CUDNN_BENCHMARK=true for fixed input sizes".to_string(),
+                );
             }
-            Err(e) => {
-                debug!("Failed to detect {}: {}", name, e);
+            recommendations
+        }
+        FrameworkType::TensorFlow => {
+            let mut recommendations = vec![
+                "Optimize data pipeline with tf.data prefetch and parallel processing"
+                    .to_string(),
+            ];
+            if major >= 2 && has_cuda {
+                recommendations.push(
+                    "Enable XLA compilation with tf.function(jit_compile=True)".to_string(),
+                );
+                recommendations.push(
+                    "Use mixed precision with policy.set_global('mixed_float16')".to_string(),
+                );
+                recommendations.push("Use TensorRT for inference optimization".to_string());
             }
+            recommendations
         }
-    }
-    
-    Ok(frameworks)
-}
-
-/// Get framework-specific optimization recommendations
-pub fn get_framework_optimizations(framework_type: FrameworkType) -> Vec<String> {
-    match framework_type {
-        FrameworkType::PyTorch => vec![
-            "Use DataLoader with num_workers > 0".to_string(),
-            "Enable mixed precision training with autocast".to_string(),
-            "Use torch.compile for model optimization".to_string(),
-            "Set TORCH_BACKENDS_CUDNN_BENCHMARK=true for fixed input sizes".to_string(),
-        ],
-        FrameworkType::TensorFlow => vec![
-            "Enable XLA compilation with tf.function(jit_compile=True)".to_string(),
-            "Use mixed precision with policy.set_global('mixed_float16')".to_string(),
-            "Optimize data pipeline with tf.data prefetch and parallel processing".to_string(),
-            "Use TensorRT for inference optimization".to_string(),
-        ],
         FrameworkType::ONNX => vec![
             "Use ONNX Runtime with optimized execution providers".to_string(),
             "Enable graph optimizations".to_string(),
@@ -390,4 +733,422 @@ pub fn get_framework_optimizations(framework_type: FrameworkType) -> Vec<String>
             "Use DataLoader with multiple workers".to_string(),
         ],
     }
+}
+
+/// Recommendations gated on what the detected [`TrainingStackInfo`] can actually run -- FP8,
+/// FSDP, and DeepSpeed sit above a single [`FrameworkInfo`], so they're gated here instead of in
+/// [`get_framework_optimizations`]
+pub fn get_training_stack_optimizations(stack: &TrainingStackInfo) -> Vec<String> {
+    let mut recommendations = Vec::new();
+
+    if stack.fp8.available() {
+        recommendations.push(
+            "Use transformer_engine FP8 mixed precision for Hopper/Ada-class speedups".to_string(),
+        );
+    }
+
+    if stack.fsdp_available {
+        if let Some(accelerate) = &stack.accelerate {
+            if accelerate.supports_fsdp {
+                recommendations
+                    .push("Shard large models with Accelerate's FSDP integration".to_string());
+            } else {
+                recommendations.push(
+                    "Shard large models with torch.distributed.fsdp directly".to_string(),
+                );
+            }
+        } else {
+            recommendations
+                .push("Shard large models with torch.distributed.fsdp directly".to_string());
+        }
+    }
+
+    if let Some(deepspeed) = &stack.deepspeed {
+        let supported_by_accelerate =
+            stack.accelerate.as_ref().is_some_and(|a| a.supports_deepspeed);
+        if supported_by_accelerate {
+            recommendations.push(format!(
+                "Offload optimizer/parameter state with DeepSpeed ZeRO via Accelerate (deepspeed {})",
+                deepspeed.version
+            ));
+        } else {
+            recommendations.push(format!(
+                "Offload optimizer/parameter state with DeepSpeed ZeRO (deepspeed {})",
+                deepspeed.version
+            ));
+        }
+    }
+
+    recommendations
+}
+
+// ============================================================================
+// CUSTOM OP LIBRARY LOADING
+// ============================================================================
+
+/// Parse a printed Python value that may be the string `"None"`
+fn py_optional(value: &str) -> Option<String> {
+    if value == "None" {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Load status and probed version of a single custom-op shared library
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomOpLibrary {
+    pub path: PathBuf,
+    pub loaded: bool,
+    /// Version string read from a `<library_stem>_version()` C symbol, if the library exports
+    /// one. `None` even on a successful load means the library doesn't export that convention.
+    pub version: Option<String>,
+    /// Error message from the framework's loader, if `loaded` is `false`
+    pub error: Option<String>,
+}
+
+/// Tracks the most recently successfully loaded custom-op library version per framework, so
+/// operators can detect skew between a deployed model's expected op version and what's actually
+/// loaded into the runtime (the same check a real deployment makes by asserting a comma-separated
+/// custom-ops list at startup matches the serving signature)
+#[derive(Debug, Default)]
+pub struct CustomOpsRegistry {
+    last_loaded_version: RwLock<HashMap<FrameworkType, String>>,
+}
+
+impl CustomOpsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The version of the last custom-op library successfully loaded for `framework`, if any
+    pub async fn last_loaded_version(&self, framework: FrameworkType) -> Option<String> {
+        self.last_loaded_version.read().await.get(&framework).cloned()
+    }
+
+    async fn record(&self, framework: FrameworkType, version: &str) {
+        self.last_loaded_version
+            .write()
+            .await
+            .insert(framework, version.to_string());
+    }
+}
+
+/// Load each of `lib_paths` as a custom op library for `framework`, recording the version of the
+/// last successful load into `registry`. Only PyTorch (`torch.ops.load_library`) and TensorFlow
+/// (`tf.load_op_library`) support custom ops this way.
+pub async fn load_custom_ops(
+    registry: &CustomOpsRegistry,
+    framework: FrameworkType,
+    lib_paths: &[PathBuf],
+) -> Result<Vec<CustomOpLibrary>> {
+    if !matches!(framework, FrameworkType::PyTorch | FrameworkType::TensorFlow) {
+        return Err(MLError::HardwareNotSupported(format!(
+            "Custom op library loading isn't supported for {:?}",
+            framework
+        )));
+    }
+
+    let python_path = which("python3")
+        .or_else(|_| which("python"))
+        .map_err(|e| MLError::FrameworkNotFound(format!("Python not found: {}", e)))?;
+
+    let mut results = Vec::with_capacity(lib_paths.len());
+    for path in lib_paths {
+        let library = load_one_custom_op(&python_path, framework, path).await;
+        if library.loaded {
+            if let Some(version) = &library.version {
+                registry.record(framework, version).await;
+            }
+        }
+        results.push(library);
+    }
+
+    Ok(results)
+}
+
+async fn load_one_custom_op(
+    python_path: &Path,
+    framework: FrameworkType,
+    path: &PathBuf,
+) -> CustomOpLibrary {
+    let path_str = path.to_string_lossy();
+    let version_symbol = version_symbol_name(path);
+
+    let load_stmt = match framework {
+        FrameworkType::TensorFlow => format!("tf.load_op_library(r\"{}\")", path_str),
+        FrameworkType::PyTorch => format!("torch.ops.load_library(r\"{}\")", path_str),
+        _ => unreachable!("checked by load_custom_ops"),
+    };
+    let import_stmt = match framework {
+        FrameworkType::TensorFlow => "import tensorflow as tf",
+        FrameworkType::PyTorch => "import torch",
+        _ => unreachable!("checked by load_custom_ops"),
+    };
+
+    let probe = format!(
+        r#"
+{import_stmt}
+import ctypes
+try:
+    {load_stmt}
+    print(True)
+except Exception as e:
+    print(False)
+    print(str(e).replace("\n", " "))
+    raise SystemExit(0)
+
+try:
+    lib = ctypes.CDLL(r"{path_str}")
+    sym = lib.{version_symbol}
+    sym.restype = ctypes.c_char_p
+    version = sym()
+    print(version.decode() if version is not None else "None")
+except Exception:
+    print("None")
+"#
+    );
+
+    let output = match AsyncCommand::new(python_path).args(&["-c", &probe]).output().await {
+        Ok(output) => output,
+        Err(e) => {
+            return CustomOpLibrary {
+                path: path.clone(),
+                loaded: false,
+                version: None,
+                error: Some(format!("Failed to run Python: {}", e)),
+            }
+        }
+    };
+
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = output_str.trim().lines().collect();
+
+    if lines.first() != Some(&"True") {
+        let error = lines.get(1).map(|s| s.to_string()).unwrap_or_else(|| {
+            "Library failed to load (no error detail captured)".to_string()
+        });
+        return CustomOpLibrary {
+            path: path.clone(),
+            loaded: false,
+            version: None,
+            error: Some(error),
+        };
+    }
+
+    CustomOpLibrary {
+        path: path.clone(),
+        loaded: true,
+        version: lines.get(1).and_then(|v| py_optional(v.trim())),
+        error: None,
+    }
+}
+
+/// Derive the C symbol name a custom-op library is expected to export its version under:
+/// `<stem-without-lib-prefix>_version`, e.g. `libfoo_ops.so` -> `foo_ops_version`
+fn version_symbol_name(path: &Path) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let stem = stem.strip_prefix("lib").unwrap_or(&stem);
+    let sanitized: String = stem
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    format!("{}_version", sanitized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn py_optional_treats_none_string_as_absent() {
+        assert_eq!(py_optional("None"), None);
+        assert_eq!(py_optional("11.8"), Some("11.8".to_string()));
+    }
+
+    #[test]
+    fn cgroup_indicates_container_matches_known_runtime_hints() {
+        assert!(cgroup_indicates_container(
+            "0::/docker/1234567890abcdef"
+        ));
+        assert!(cgroup_indicates_container(
+            "0::/kubepods/besteffort/pod1234/container5678"
+        ));
+        assert!(!cgroup_indicates_container("0::/user.slice/user-1000.slice"));
+    }
+
+    #[test]
+    fn build_variant_serializes_to_the_shape_the_python_probe_emits() {
+        let cpu = serde_json::to_value(BuildVariant::Cpu).unwrap();
+        assert_eq!(cpu, serde_json::json!({"kind": "Cpu"}));
+
+        let cuda = serde_json::to_value(BuildVariant::Cuda("12.1".to_string())).unwrap();
+        assert_eq!(cuda, serde_json::json!({"kind": "Cuda", "version": "12.1"}));
+
+        let rocm: BuildVariant =
+            serde_json::from_value(serde_json::json!({"kind": "Rocm", "version": "5.6"})).unwrap();
+        assert_eq!(rocm, BuildVariant::Rocm("5.6".to_string()));
+    }
+
+    #[test]
+    fn parse_major_minor_tolerates_build_and_prerelease_metadata() {
+        assert_eq!(parse_major_minor("2.1.0+cu121"), Some((2, 1)));
+        assert_eq!(parse_major_minor("2.15.0-rc0"), Some((2, 15)));
+        assert_eq!(parse_major_minor("1.13.1"), Some((1, 13)));
+        assert_eq!(parse_major_minor("not-a-version"), None);
+    }
+
+    fn framework_info_with(
+        framework_type: FrameworkType,
+        version: &str,
+        accelerator: Option<AcceleratorInfo>,
+    ) -> FrameworkInfo {
+        FrameworkInfo {
+            framework_type,
+            version: version.to_string(),
+            path: "/usr/lib/python3/site-packages".to_string(),
+            features: vec![],
+            python_version: None,
+            accelerator,
+            build_variant: None,
+            in_container: false,
+        }
+    }
+
+    fn cuda_accelerator() -> AcceleratorInfo {
+        AcceleratorInfo {
+            backend: AcceleratorBackend::Cuda,
+            device_count: 1,
+            cuda_version: Some("12.1".to_string()),
+            hip_version: None,
+            device_capabilities: vec!["8.9".to_string()],
+        }
+    }
+
+    #[test]
+    fn pytorch_optimizations_require_v2_and_cuda() {
+        let old_cpu = framework_info_with(FrameworkType::PyTorch, "1.13.1", None);
+        let recs = get_framework_optimizations(&old_cpu);
+        assert!(!recs.iter().any(|r| r.contains("torch.compile")));
+
+        let new_cpu = framework_info_with(FrameworkType::PyTorch, "2.1.0+cpu", None);
+        let recs = get_framework_optimizations(&new_cpu);
+        assert!(!recs.iter().any(|r| r.contains("torch.compile")));
+
+        let new_cuda =
+            framework_info_with(FrameworkType::PyTorch, "2.1.0+cu121", Some(cuda_accelerator()));
+        let recs = get_framework_optimizations(&new_cuda);
+        assert!(recs.iter().any(|r| r.contains("torch.compile")));
+        assert!(recs.iter().any(|r| r.contains("CUDNN_BENCHMARK")));
+    }
+
+    #[test]
+    fn tensorflow_optimizations_require_v2_and_gpu() {
+        let cpu_only = framework_info_with(FrameworkType::TensorFlow, "2.15.0", None);
+        let recs = get_framework_optimizations(&cpu_only);
+        assert!(!recs.iter().any(|r| r.contains("XLA")));
+        assert!(!recs.iter().any(|r| r.contains("TensorRT")));
+
+        let gpu = framework_info_with(FrameworkType::TensorFlow, "2.15.0", Some(cuda_accelerator()));
+        let recs = get_framework_optimizations(&gpu);
+        assert!(recs.iter().any(|r| r.contains("XLA")));
+        assert!(recs.iter().any(|r| r.contains("TensorRT")));
+    }
+
+    #[tokio::test]
+    async fn combined_probe_runs_in_a_single_interpreter_and_parses() {
+        let Ok(python_path) = which("python3").or_else(|_| which("python")) else {
+            return; // No Python on this machine; nothing to probe.
+        };
+
+        // Whatever frameworks happen to be installed here, the probe must exit successfully and
+        // print JSON that deserializes into `Vec<FrameworkInfo>` rather than erroring or hanging.
+        let result = run_combined_probe(&python_path, Duration::from_secs(30)).await;
+        assert!(result.is_ok(), "combined probe failed: {:?}", result.err());
+    }
+
+    #[tokio::test]
+    async fn combined_probe_honors_its_timeout() {
+        let Ok(python_path) = which("python3").or_else(|_| which("python")) else {
+            return;
+        };
+
+        let result = run_combined_probe(&python_path, Duration::from_nanos(1)).await;
+        assert!(matches!(result, Err(MLError::Timeout(_))));
+    }
+
+    #[test]
+    fn fp8_support_requires_both_hardware_and_engine() {
+        let neither = Fp8Support {
+            compute_capability_sufficient: false,
+            transformer_engine_installed: false,
+        };
+        let hardware_only = Fp8Support {
+            compute_capability_sufficient: true,
+            transformer_engine_installed: false,
+        };
+        let both = Fp8Support {
+            compute_capability_sufficient: true,
+            transformer_engine_installed: true,
+        };
+
+        assert!(!neither.available());
+        assert!(!hardware_only.available());
+        assert!(both.available());
+    }
+
+    #[test]
+    fn training_stack_optimizations_are_gated_on_availability() {
+        let bare = TrainingStackInfo {
+            accelerate: None,
+            deepspeed: None,
+            fsdp_available: false,
+            fp8: Fp8Support {
+                compute_capability_sufficient: false,
+                transformer_engine_installed: false,
+            },
+        };
+        assert!(get_training_stack_optimizations(&bare).is_empty());
+
+        let fully_equipped = TrainingStackInfo {
+            accelerate: Some(AccelerateInfo {
+                version: "0.30.0".to_string(),
+                supports_deepspeed: true,
+                supports_fsdp: true,
+                supports_mixed_precision: true,
+            }),
+            deepspeed: Some(DeepSpeedInfo {
+                version: "0.14.0".to_string(),
+            }),
+            fsdp_available: true,
+            fp8: Fp8Support {
+                compute_capability_sufficient: true,
+                transformer_engine_installed: true,
+            },
+        };
+        let recommendations = get_training_stack_optimizations(&fully_equipped);
+        assert_eq!(recommendations.len(), 3);
+        assert!(recommendations.iter().any(|r| r.contains("FP8")));
+        assert!(recommendations.iter().any(|r| r.contains("FSDP")));
+        assert!(recommendations.iter().any(|r| r.contains("DeepSpeed")));
+    }
+
+    #[test]
+    fn version_symbol_name_strips_lib_prefix_and_extension() {
+        assert_eq!(version_symbol_name(Path::new("libfoo_ops.so")), "foo_ops_version");
+        assert_eq!(version_symbol_name(Path::new("/opt/ops/custom-op.so")), "custom_op_version");
+    }
+
+    #[tokio::test]
+    async fn custom_ops_registry_tracks_last_loaded_version_per_framework() {
+        let registry = CustomOpsRegistry::new();
+        assert_eq!(registry.last_loaded_version(FrameworkType::PyTorch).await, None);
+
+        registry.record(FrameworkType::PyTorch, "1.2.3").await;
+        assert_eq!(
+            registry.last_loaded_version(FrameworkType::PyTorch).await,
+            Some("1.2.3".to_string())
+        );
+        assert_eq!(registry.last_loaded_version(FrameworkType::TensorFlow).await, None);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,381 @@
+//! GPU memory budgeting via buddy allocation with a large-allocation free list
+//!
+//! Borrows the two allocator strategies general-purpose GPU allocators (e.g. PyTorch's caching
+//! allocator) combine: a buddy allocator partitions memory into power-of-two size classes,
+//! splitting a free block in half until it matches the rounded-up request (and coalescing freed
+//! buddies back together), while allocations at or above [`LARGE_ALLOCATION_THRESHOLD`] — where
+//! buddy's up-to-2x rounding waste stops being worth it — are served from a free-list of linear
+//! blocks instead. Requests are tracked per [`UsageClass`] so [`GpuMemoryPlanner::plan`] can
+//! report a persistent reservation (model weights) separately from the reusable transient one
+//! (activations/workspace), and flags when the plan doesn't fit the device's VRAM.
+
+/// Allocations this size or larger skip the buddy allocator and are served from the large-block
+/// free list instead
+pub const LARGE_ALLOCATION_THRESHOLD: u64 = 64 * 1024 * 1024; // 64MB
+
+/// Smallest buddy block size; requests smaller than this are rounded up to it
+const MIN_BLOCK_SIZE: u64 = 256;
+
+/// What a GPU memory reservation is for: weights persist for the process lifetime, while
+/// activations and workspace scratch are transient and reused turn over turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsageClass {
+    Weights,
+    Activations,
+    Workspace,
+}
+
+impl UsageClass {
+    /// Weights persist for the life of the process; activations/workspace are freed and
+    /// reallocated every step
+    pub fn is_persistent(&self) -> bool {
+        matches!(self, UsageClass::Weights)
+    }
+}
+
+/// A single requested reservation
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryRequest {
+    pub usage: UsageClass,
+    pub bytes: u64,
+}
+
+/// Which sub-allocator served a [`MemoryRequest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocatorStrategy {
+    Buddy,
+    LargeBlockFreeList,
+}
+
+/// The result of serving one [`MemoryRequest`]: how many bytes the allocator actually committed
+/// (rounded up to its granularity) and which strategy served it
+#[derive(Debug, Clone, Copy)]
+pub struct Reservation {
+    pub usage: UsageClass,
+    pub requested_bytes: u64,
+    pub allocated_bytes: u64,
+    pub strategy: AllocatorStrategy,
+}
+
+/// One power-of-two size class of the buddy allocator: every entry in `free_offsets` names a free
+/// block of exactly `block_size` bytes, by its offset within the region
+struct SizeClass {
+    block_size: u64,
+    free_offsets: Vec<u64>,
+}
+
+/// A buddy allocator over a single region: splits a free block in half (pushing the unused buddy
+/// onto a smaller size class) until it finds one matching the rounded-up request, and coalesces a
+/// freed block back with its buddy whenever that buddy is also free
+struct BuddyAllocator {
+    classes: Vec<SizeClass>, // ascending by block_size, indices shared with `region_size`'s po2 exponent
+    min_block_size: u64,
+}
+
+impl BuddyAllocator {
+    fn new(region_size: u64) -> Self {
+        let top_block_size = region_size.max(MIN_BLOCK_SIZE).next_power_of_two();
+        let mut classes = Vec::new();
+        let mut size = MIN_BLOCK_SIZE;
+        while size <= top_block_size {
+            classes.push(SizeClass { block_size: size, free_offsets: Vec::new() });
+            size *= 2;
+        }
+        classes.last_mut().unwrap().free_offsets.push(0);
+
+        Self { classes, min_block_size: MIN_BLOCK_SIZE }
+    }
+
+    fn class_index(&self, block_size: u64) -> usize {
+        (block_size / self.min_block_size).trailing_zeros() as usize
+    }
+
+    /// Round `bytes` up to the allocator's block granularity
+    fn round_up(&self, bytes: u64) -> u64 {
+        bytes.max(self.min_block_size).next_power_of_two()
+    }
+
+    /// Allocate a block at least `bytes` large, splitting a larger free block down to size when
+    /// no exact-size block is free. Returns the block's `(offset, allocated_size)`, or `None` if
+    /// the region has no space left.
+    fn allocate(&mut self, bytes: u64) -> Option<(u64, u64)> {
+        let target_size = self.round_up(bytes);
+        let target_index = self.class_index(target_size);
+
+        let source_index = (target_index..self.classes.len()).find(|&i| !self.classes[i].free_offsets.is_empty())?;
+        let offset = self.classes[source_index].free_offsets.pop().unwrap();
+
+        // Split repeatedly down to target_size, pushing each block's right buddy onto its class
+        for index in (target_index + 1..=source_index).rev() {
+            let half_size = self.classes[index - 1].block_size;
+            let buddy_offset = offset + half_size;
+            self.classes[index - 1].free_offsets.push(buddy_offset);
+        }
+
+        Some((offset, target_size))
+    }
+
+    /// Free a previously-allocated block of `size` bytes at `offset`, coalescing with its buddy
+    /// (and that buddy's buddy, and so on) whenever the buddy is also free
+    fn free(&mut self, offset: u64, size: u64) {
+        let mut offset = offset;
+        let mut index = self.class_index(size);
+
+        while index + 1 < self.classes.len() {
+            let block_size = self.classes[index].block_size;
+            let buddy_offset = offset ^ block_size; // buddies differ in exactly the size's bit
+            let class = &mut self.classes[index];
+            if let Some(pos) = class.free_offsets.iter().position(|&o| o == buddy_offset) {
+                class.free_offsets.remove(pos);
+                offset = offset.min(buddy_offset);
+                index += 1;
+            } else {
+                break;
+            }
+        }
+
+        self.classes[index].free_offsets.push(offset);
+    }
+}
+
+/// A free-list of arbitrarily-sized linear blocks, for allocations too large for buddy's
+/// power-of-two rounding to be worth it. Adjacent free blocks are coalesced on free.
+struct LargeBlockFreeList {
+    /// `(offset, size)` of each free block, sorted by offset
+    free_blocks: Vec<(u64, u64)>,
+}
+
+impl LargeBlockFreeList {
+    fn new(region_size: u64) -> Self {
+        Self { free_blocks: vec![(0, region_size)] }
+    }
+
+    /// First-fit: take the first free block at least `bytes` large, splitting off the remainder
+    fn allocate(&mut self, bytes: u64) -> Option<u64> {
+        let index = self.free_blocks.iter().position(|(_, size)| *size >= bytes)?;
+        let (offset, size) = self.free_blocks.remove(index);
+
+        if size > bytes {
+            self.free_blocks.push((offset + bytes, size - bytes));
+            self.free_blocks.sort_by_key(|(offset, _)| *offset);
+        }
+        Some(offset)
+    }
+
+    /// Free the `(offset, size)` block, merging it with an adjacent free block on either side
+    fn free(&mut self, offset: u64, size: u64) {
+        self.free_blocks.push((offset, size));
+        self.free_blocks.sort_by_key(|(offset, _)| *offset);
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(self.free_blocks.len());
+        for (offset, size) in self.free_blocks.drain(..) {
+            match merged.last_mut() {
+                Some((last_offset, last_size)) if *last_offset + *last_size == offset => *last_size += size,
+                _ => merged.push((offset, size)),
+            }
+        }
+        self.free_blocks = merged;
+    }
+}
+
+/// The outcome of [`GpuMemoryPlanner::plan`]: every request's reservation, the persistent vs.
+/// transient totals, and whether the plan fits the device's VRAM
+#[derive(Debug, Clone)]
+pub struct GpuMemoryPlan {
+    pub reservations: Vec<Reservation>,
+    /// Total bytes committed to requests in [`UsageClass::is_persistent`] usage classes (weights)
+    pub persistent_bytes: u64,
+    /// Total bytes committed to transient usage classes (activations, workspace)
+    pub transient_bytes: u64,
+    pub capacity_bytes: u64,
+    /// How far `persistent_bytes + transient_bytes` exceeds `capacity_bytes`; zero when the plan
+    /// fits
+    pub overflow_bytes: u64,
+    pub warnings: Vec<String>,
+}
+
+impl GpuMemoryPlan {
+    pub fn total_bytes(&self) -> u64 {
+        self.persistent_bytes + self.transient_bytes
+    }
+
+    pub fn fits(&self) -> bool {
+        self.overflow_bytes == 0
+    }
+}
+
+/// Plans a set of [`MemoryRequest`]s against a fixed-size VRAM budget
+pub struct GpuMemoryPlanner {
+    capacity_bytes: u64,
+}
+
+impl GpuMemoryPlanner {
+    pub fn new(capacity_bytes: u64) -> Self {
+        Self { capacity_bytes }
+    }
+
+    /// Serve every request from a buddy allocator (requests below [`LARGE_ALLOCATION_THRESHOLD`])
+    /// or a large-block free list (at or above it), both carved out of `capacity_bytes`, and
+    /// summarize the result per [`UsageClass`]. Requests that don't fit are still counted toward
+    /// `overflow_bytes` at their rounded size so the caller can see how much to shrink by.
+    pub fn plan(&self, requests: &[MemoryRequest]) -> GpuMemoryPlan {
+        let mut buddy = BuddyAllocator::new(self.capacity_bytes);
+        let mut large = LargeBlockFreeList::new(self.capacity_bytes);
+
+        let mut reservations = Vec::with_capacity(requests.len());
+        let mut persistent_bytes = 0u64;
+        let mut transient_bytes = 0u64;
+        let mut overflow_bytes = 0u64;
+        let mut warnings = Vec::new();
+
+        for request in requests {
+            let (allocated_bytes, strategy, fit) = if request.bytes >= LARGE_ALLOCATION_THRESHOLD {
+                match large.allocate(request.bytes) {
+                    Some(_) => (request.bytes, AllocatorStrategy::LargeBlockFreeList, true),
+                    None => (request.bytes, AllocatorStrategy::LargeBlockFreeList, false),
+                }
+            } else {
+                let rounded = buddy.round_up(request.bytes);
+                match buddy.allocate(request.bytes) {
+                    Some(_) => (rounded, AllocatorStrategy::Buddy, true),
+                    None => (rounded, AllocatorStrategy::Buddy, false),
+                }
+            };
+
+            if request.usage.is_persistent() {
+                persistent_bytes += allocated_bytes;
+            } else {
+                transient_bytes += allocated_bytes;
+            }
+
+            if !fit {
+                overflow_bytes += allocated_bytes;
+                warnings.push(format!(
+                    "{:?} reservation of {} bytes does not fit the remaining GPU memory budget",
+                    request.usage, request.bytes
+                ));
+            }
+
+            reservations.push(Reservation {
+                usage: request.usage,
+                requested_bytes: request.bytes,
+                allocated_bytes,
+                strategy,
+            });
+        }
+
+        if persistent_bytes + transient_bytes > self.capacity_bytes {
+            let shortfall = (persistent_bytes + transient_bytes) - self.capacity_bytes;
+            overflow_bytes = overflow_bytes.max(shortfall);
+            warnings.push(format!(
+                "planned GPU memory reservation of {} bytes exceeds the {} byte device budget by {} bytes",
+                persistent_bytes + transient_bytes,
+                self.capacity_bytes,
+                shortfall
+            ));
+        }
+
+        GpuMemoryPlan {
+            reservations,
+            persistent_bytes,
+            transient_bytes,
+            capacity_bytes: self.capacity_bytes,
+            overflow_bytes,
+            warnings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buddy_allocator_splits_a_large_block_to_serve_a_small_request() {
+        let mut buddy = BuddyAllocator::new(1024);
+        let (offset, size) = buddy.allocate(100).unwrap();
+        assert_eq!(size, 256); // rounded up to the minimum block size
+        assert_eq!(offset, 0);
+    }
+
+    #[test]
+    fn test_buddy_allocator_coalesces_freed_buddies_back_into_the_original_block() {
+        let mut buddy = BuddyAllocator::new(1024);
+        let (offset_a, size_a) = buddy.allocate(500).unwrap(); // takes the whole 1024 block (rounds to 512... )
+        buddy.free(offset_a, size_a);
+
+        // After freeing, the allocator should be able to serve a request for the full region
+        // again, proving the split blocks were coalesced back together.
+        let (offset_b, size_b) = buddy.allocate(1000).unwrap();
+        assert_eq!(offset_b, 0);
+        assert_eq!(size_b, 1024);
+    }
+
+    #[test]
+    fn test_buddy_allocator_returns_none_when_region_is_exhausted() {
+        let mut buddy = BuddyAllocator::new(256);
+        assert!(buddy.allocate(256).is_some());
+        assert!(buddy.allocate(1).is_none());
+    }
+
+    #[test]
+    fn test_large_block_free_list_first_fit_and_split() {
+        let mut large = LargeBlockFreeList::new(1000);
+        let offset = large.allocate(300).unwrap();
+        assert_eq!(offset, 0);
+        assert_eq!(large.free_blocks, vec![(300, 700)]);
+    }
+
+    #[test]
+    fn test_large_block_free_list_coalesces_adjacent_frees() {
+        let mut large = LargeBlockFreeList::new(1000);
+        let a = large.allocate(300).unwrap();
+        let b = large.allocate(300).unwrap();
+
+        large.free(a, 300);
+        large.free(b, 300);
+
+        // Both freed blocks are adjacent to each other and to the remaining free tail, so they
+        // should merge back into one block spanning the whole region.
+        assert_eq!(large.free_blocks, vec![(0, 1000)]);
+    }
+
+    #[test]
+    fn test_plan_separates_persistent_and_transient_reservations() {
+        let planner = GpuMemoryPlanner::new(1024 * 1024 * 1024); // 1GB
+        let plan = planner.plan(&[
+            MemoryRequest { usage: UsageClass::Weights, bytes: 400 * 1024 * 1024 },
+            MemoryRequest { usage: UsageClass::Activations, bytes: 200 * 1024 * 1024 },
+            MemoryRequest { usage: UsageClass::Workspace, bytes: 128 * 1024 * 1024 },
+        ]);
+
+        assert!(plan.fits());
+        assert_eq!(plan.persistent_bytes, 400 * 1024 * 1024);
+        assert_eq!(plan.transient_bytes, 200 * 1024 * 1024 + 128 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_plan_reports_overflow_when_requests_exceed_capacity() {
+        let planner = GpuMemoryPlanner::new(512 * 1024 * 1024); // 512MB
+        let plan = planner.plan(&[
+            MemoryRequest { usage: UsageClass::Weights, bytes: 400 * 1024 * 1024 },
+            MemoryRequest { usage: UsageClass::Activations, bytes: 300 * 1024 * 1024 },
+        ]);
+
+        assert!(!plan.fits());
+        assert!(plan.overflow_bytes > 0);
+        assert!(!plan.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_small_requests_use_buddy_and_large_requests_use_the_free_list() {
+        let planner = GpuMemoryPlanner::new(1024 * 1024 * 1024);
+        let plan = planner.plan(&[
+            MemoryRequest { usage: UsageClass::Workspace, bytes: 1024 },
+            MemoryRequest { usage: UsageClass::Weights, bytes: LARGE_ALLOCATION_THRESHOLD },
+        ]);
+
+        assert_eq!(plan.reservations[0].strategy, AllocatorStrategy::Buddy);
+        assert_eq!(plan.reservations[1].strategy, AllocatorStrategy::LargeBlockFreeList);
+    }
+}
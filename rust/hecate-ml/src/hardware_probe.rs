@@ -0,0 +1,186 @@
+//! Startup micro-benchmarks that ground resource-allocation defaults in measured hardware
+//! performance instead of fixed constants
+//!
+//! Each probe is a short, synchronous calibration: a fixed-size matrix-multiply kernel for CPU
+//! compute throughput, a sequential buffer copy for memory bandwidth, and a sequential
+//! write-then-read of a temp file for disk throughput (deciding whether the configured cache
+//! directory behaves like fast (SSD/NVMe) or slow (HDD/network) storage). The timing-to-score
+//! math is factored into pure functions so it's unit-testable without depending on wall-clock
+//! variance.
+
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Below this measured sequential read throughput, [`HardwareScores::disk_is_fast`] is `false`
+const FAST_DISK_THRESHOLD_MB_S: f64 = 150.0;
+
+/// Matrix dimension and iteration count for the CPU compute probe: small enough to finish in a
+/// few hundred milliseconds, large enough that the multiply dominates fixed overhead
+const CPU_PROBE_MATRIX_DIM: usize = 64;
+const CPU_PROBE_ITERATIONS: u32 = 20;
+
+/// Buffer size and iteration count for the memory-bandwidth probe
+const MEMORY_PROBE_BUFFER_BYTES: usize = 16 * 1024 * 1024;
+const MEMORY_PROBE_ITERATIONS: u32 = 8;
+
+/// File size written/read by the disk probe
+const DISK_PROBE_FILE_BYTES: usize = 32 * 1024 * 1024;
+
+/// Measured hardware performance, cached on [`crate::SystemInfo::hardware_scores`] after
+/// [`HardwareProbe::run`]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct HardwareScores {
+    pub cpu_gflops: f64,
+    pub memory_bandwidth_mb_s: f64,
+    pub disk_write_mb_s: f64,
+    pub disk_read_mb_s: f64,
+    /// Whether the disk probe measured SSD/NVMe-like throughput rather than HDD/network-like
+    pub disk_is_fast: bool,
+}
+
+/// Runs the CPU/memory/disk calibration benchmarks; see the module docs for what each measures
+pub struct HardwareProbe;
+
+impl HardwareProbe {
+    /// Run all three calibration benchmarks. `tmp_directory` is where the disk probe writes its
+    /// scratch file, matching where the real dataset cache/prefetch buffer would live; reports a
+    /// zeroed (non-fast) disk score, rather than failing the whole probe, if it isn't writable.
+    pub fn run(tmp_directory: &Path) -> HardwareScores {
+        let cpu_gflops = Self::benchmark_cpu();
+        let memory_bandwidth_mb_s = Self::benchmark_memory_bandwidth();
+        let (disk_write_mb_s, disk_read_mb_s) = Self::benchmark_disk(tmp_directory).unwrap_or((0.0, 0.0));
+
+        HardwareScores {
+            cpu_gflops,
+            memory_bandwidth_mb_s,
+            disk_write_mb_s,
+            disk_read_mb_s,
+            disk_is_fast: disk_read_mb_s >= FAST_DISK_THRESHOLD_MB_S,
+        }
+    }
+
+    /// Repeated fixed-size matrix multiply, timed and converted to GFLOP/s
+    fn benchmark_cpu() -> f64 {
+        let dim = CPU_PROBE_MATRIX_DIM;
+        let a: Vec<f64> = (0..dim * dim).map(|i| (i % 7) as f64).collect();
+        let b: Vec<f64> = (0..dim * dim).map(|i| (i % 5) as f64).collect();
+        let mut c = vec![0.0f64; dim * dim];
+
+        let start = Instant::now();
+        for _ in 0..CPU_PROBE_ITERATIONS {
+            for row in 0..dim {
+                for col in 0..dim {
+                    let mut sum = 0.0;
+                    for k in 0..dim {
+                        sum += a[row * dim + k] * b[k * dim + col];
+                    }
+                    c[row * dim + col] = sum;
+                }
+            }
+        }
+        black_box(&c);
+        gflops(dim, CPU_PROBE_ITERATIONS, start.elapsed())
+    }
+
+    /// Sequential copy of a large in-memory buffer, timed and converted to MB/s
+    fn benchmark_memory_bandwidth() -> f64 {
+        let src = vec![1u8; MEMORY_PROBE_BUFFER_BYTES];
+        let mut dst = vec![0u8; MEMORY_PROBE_BUFFER_BYTES];
+
+        let start = Instant::now();
+        for _ in 0..MEMORY_PROBE_ITERATIONS {
+            dst.copy_from_slice(&src);
+        }
+        black_box(&dst);
+        mb_per_sec(MEMORY_PROBE_BUFFER_BYTES as u64 * MEMORY_PROBE_ITERATIONS as u64, start.elapsed())
+    }
+
+    /// Sequential write then read of a scratch file under `tmp_directory`, timed and converted to
+    /// `(write_mb_s, read_mb_s)`. Returns `None` if the directory can't be written to.
+    fn benchmark_disk(tmp_directory: &Path) -> Option<(f64, f64)> {
+        let path = tmp_directory.join(".hecate_disk_probe");
+        let buffer = vec![0xABu8; DISK_PROBE_FILE_BYTES];
+
+        let write_start = Instant::now();
+        let mut file = std::fs::File::create(&path).ok()?;
+        file.write_all(&buffer).ok()?;
+        file.sync_all().ok()?;
+        let write_elapsed = write_start.elapsed();
+
+        let read_start = Instant::now();
+        let mut file = std::fs::File::open(&path).ok()?;
+        let mut read_buffer = Vec::with_capacity(DISK_PROBE_FILE_BYTES);
+        file.read_to_end(&mut read_buffer).ok()?;
+        let read_elapsed = read_start.elapsed();
+
+        let _ = std::fs::remove_file(&path);
+
+        Some((
+            mb_per_sec(DISK_PROBE_FILE_BYTES as u64, write_elapsed),
+            mb_per_sec(DISK_PROBE_FILE_BYTES as u64, read_elapsed),
+        ))
+    }
+}
+
+/// GFLOP/s for a `dim`x`dim` matrix multiply run `iterations` times in `elapsed`: each multiply
+/// does `2 * dim^3` floating point ops (one multiply-add per inner-loop step)
+fn gflops(dim: usize, iterations: u32, elapsed: Duration) -> f64 {
+    let flops = 2.0 * (dim as f64).powi(3) * iterations as f64;
+    flops / elapsed.as_secs_f64() / 1e9
+}
+
+/// MB/s for `bytes` transferred in `elapsed`
+fn mb_per_sec(bytes: u64, elapsed: Duration) -> f64 {
+    (bytes as f64 / 1_000_000.0) / elapsed.as_secs_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gflops_matches_the_multiply_add_flop_count() {
+        let score = gflops(64, 20, Duration::from_secs(1));
+        let expected_flops = 2.0 * 64f64.powi(3) * 20.0;
+        assert!((score - expected_flops / 1e9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mb_per_sec_halves_when_elapsed_time_doubles() {
+        let fast = mb_per_sec(1_000_000, Duration::from_secs(1));
+        let slow = mb_per_sec(1_000_000, Duration::from_secs(2));
+        assert!((fast - slow * 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hardware_scores_default_is_not_fast() {
+        assert!(!HardwareScores::default().disk_is_fast);
+    }
+
+    #[test]
+    fn test_benchmark_cpu_reports_a_positive_score() {
+        assert!(HardwareProbe::benchmark_cpu() > 0.0);
+    }
+
+    #[test]
+    fn test_benchmark_memory_bandwidth_reports_a_positive_score() {
+        assert!(HardwareProbe::benchmark_memory_bandwidth() > 0.0);
+    }
+
+    #[test]
+    fn test_benchmark_disk_round_trips_through_a_scratch_file() {
+        let (write_mb_s, read_mb_s) =
+            HardwareProbe::benchmark_disk(&std::env::temp_dir()).expect("temp dir should be writable");
+        assert!(write_mb_s > 0.0);
+        assert!(read_mb_s > 0.0);
+    }
+
+    #[test]
+    fn test_benchmark_disk_returns_none_for_an_unwritable_directory() {
+        let unwritable = Path::new("/nonexistent/definitely/not/a/real/path");
+        assert!(HardwareProbe::benchmark_disk(unwritable).is_none());
+    }
+}
@@ -47,7 +47,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
@@ -57,12 +57,33 @@ pub mod error;
 pub mod frameworks;
 pub mod optimization;
 pub mod distributed;
+#[cfg(feature = "admin_api")]
+pub mod admin;
+pub mod compression;
 pub mod dataset;
+pub mod precision;
 pub mod profiling;
+pub mod container;
+pub mod memory_planner;
+pub mod memory_pool;
+pub mod byte_size;
+pub mod benchmark;
+pub mod hardware_probe;
+pub mod gpu_memory_planner;
+pub mod declarative_rules;
+pub mod retainer;
+pub mod metrics_export;
+pub mod metrics_store;
 
 pub use error::{MLError, Result};
 pub use frameworks::{FrameworkInfo as FrameworkInfoInternal, FrameworkType as InternalFrameworkType};
 pub use optimization::SystemInfo as SystemInfoInternal;
+pub use precision::{DelayedScaling, Fp8Format, PrecisionMode};
+pub use distributed::OffloadTarget;
+pub use benchmark::{BenchmarkReport, ConfidenceInterval};
+pub use byte_size::{ByteSize, MemoryBudget};
+pub use hardware_probe::HardwareScores;
+pub use container::ContainerSpec;
 
 // ============================================================================
 // CORE DATA STRUCTURES
@@ -77,10 +98,22 @@ pub struct MLOptimizer {
     system_info: Arc<RwLock<SystemInfo>>,
     /// Optimization cache
     optimization_cache: Arc<RwLock<HashMap<String, OptimizationResult>>>,
+    /// Measured max-batch-size probes from [`MLOptimizer::autotune_batch_size`], keyed the same
+    /// way as `optimization_cache` so repeat calls for the same (model_size, sequence_length,
+    /// precision) skip re-probing
+    batch_probe_cache: Arc<RwLock<HashMap<String, BatchSizeProbe>>>,
     /// Performance profiler
     profiler: Arc<RwLock<profiling::Profiler>>,
 }
 
+/// A measured max-batch-size probe result, cached by [`MLOptimizer::autotune_batch_size`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BatchSizeProbe {
+    fitting_batch_size: u32,
+    peak_memory_bytes: u64,
+    memory_per_sample_bytes: u64,
+}
+
 /// Information about detected ML frameworks
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct FrameworkInfo {
@@ -143,8 +176,20 @@ pub struct SystemInfo {
     pub memory_available: u64,
     pub gpu_count: u32,
     pub gpu_memory_total: u64,
+    /// Currently free GPU memory, aggregated across all detected GPUs. Sampled at the same time
+    /// as `gpu_memory_total`; call [`MLOptimizer::refresh_system_info`] to re-sample it between
+    /// optimization calls rather than trusting a one-time snapshot.
+    pub gpu_memory_available: u64,
+    /// Compute capability (e.g. `8.9` for Ada) of each detected GPU, in the same order as the
+    /// (currently aggregated) `gpu_memory_total`. Empty when unknown, which gates out anything
+    /// that requires a capability floor, like FP8.
+    pub gpu_compute_capabilities: Vec<f32>,
     pub storage_type: StorageType,
     pub network_bandwidth: Option<u64>,
+    /// CPU/memory/disk micro-benchmark scores from [`MLOptimizer::gather_system_info`], used to
+    /// size [`CPUAllocation::dataloader_workers`], [`StorageAllocation::prefetch_buffer`] and
+    /// [`StorageAllocation::use_ssd_cache`] from measured throughput instead of fixed constants
+    pub hardware_scores: HardwareScores,
 }
 
 /// Storage type enumeration
@@ -165,6 +210,11 @@ impl From<SystemInfoInternal> for SystemInfo {
             memory_available: internal.available_memory,
             gpu_count: internal.gpu_count,
             gpu_memory_total: internal.gpu_memory.iter().sum(),
+            // The internal `SystemInfo` doesn't track free GPU memory separately from total, so
+            // this loses freshness across the conversion; `refresh_system_info` re-samples the
+            // external `SystemInfo` directly via NVML instead of round-tripping through this.
+            gpu_memory_available: internal.gpu_memory.iter().sum(),
+            gpu_compute_capabilities: internal.gpu_compute_capabilities.clone(),
             storage_type: match internal.storage_type {
                 optimization::StorageType::HDD => StorageType::HDD,
                 optimization::StorageType::SSD => StorageType::SSD,
@@ -173,6 +223,9 @@ impl From<SystemInfoInternal> for SystemInfo {
                 optimization::StorageType::Network => StorageType::Network,
             },
             network_bandwidth: internal.network_bandwidth,
+            // The internal `SystemInfo` has no notion of measured hardware scores; they're only
+            // ever populated by `MLOptimizer::gather_system_info` on the external type directly.
+            hardware_scores: HardwareScores::default(),
         }
     }
 }
@@ -189,6 +242,12 @@ impl From<SystemInfo> for SystemInfoInternal {
             } else {
                 vec![]
             },
+            gpu_compute_capabilities: external.gpu_compute_capabilities.clone(),
+            gpu_arch: external
+                .gpu_compute_capabilities
+                .iter()
+                .map(|&capability| optimization::GpuArch::from_compute_capability(capability))
+                .collect(),
             storage_type: match external.storage_type {
                 StorageType::HDD => optimization::StorageType::HDD,
                 StorageType::SSD => optimization::StorageType::SSD,
@@ -196,10 +255,36 @@ impl From<SystemInfo> for SystemInfoInternal {
                 StorageType::Network => optimization::StorageType::Network,
             },
             network_bandwidth: external.network_bandwidth,
+            cpu_supports_bf16: detect_cpu_bf16_support(),
+            // The external `SystemInfo` has no TDP/clock-limit probe yet (no NVML power/clock
+            // query wired up); default every GPU to "unknown" so the energy model and clock-cap
+            // recommendation gate themselves out rather than acting on fabricated numbers.
+            gpu_tdp_watts: vec![0; external.gpu_count as usize],
+            gpu_clock_limits: vec![None; external.gpu_count as usize],
         }
     }
 }
 
+/// Detect whether the host CPU has hardware support for BF16 arithmetic, used by the
+/// optimization engine to pick a CPU-friendly mixed-precision dtype. x86_64 only gained dedicated
+/// BF16 instructions alongside AVX-512, so AVX-512 support is used as the proxy; aarch64's BF16
+/// extension is widely available on server-class cores so it's assumed present there rather than
+/// probed.
+#[cfg(target_arch = "x86_64")]
+fn detect_cpu_bf16_support() -> bool {
+    std::is_x86_feature_detected!("avx512f")
+}
+
+#[cfg(target_arch = "aarch64")]
+fn detect_cpu_bf16_support() -> bool {
+    true
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn detect_cpu_bf16_support() -> bool {
+    false
+}
+
 /// ML workload optimization configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptimizationConfig {
@@ -208,13 +293,25 @@ pub struct OptimizationConfig {
     pub batch_size: Option<u32>,
     pub sequence_length: Option<u32>,
     pub use_mixed_precision: bool,
+    /// Which precision tier to target when `use_mixed_precision` is set. `Fp8` is only ever
+    /// actually used when [`MLOptimizer::optimize_workload`] confirms the detected GPUs support
+    /// it (see [`precision::gpu_supports_fp8`]); otherwise it's downgraded to `Bf16`.
+    pub precision_mode: PrecisionMode,
     pub optimize_memory: bool,
     pub enable_distributed: bool,
     pub target_framework: Option<FrameworkType>,
-    pub max_memory_usage: Option<f32>, // Percentage of available memory
+    /// Memory limit, expressed either absolutely (`"6GiB"`) or as a percentage of
+    /// `system_info.memory_available` (`"80%"`). `None` resolves to
+    /// [`byte_size::DEFAULT_MEMORY_FRACTION`] via [`MLOptimizer::resolve_memory_budget`].
+    pub max_memory_usage: Option<MemoryBudget>,
     pub target_throughput: Option<f32>, // Samples per second
     pub latency_requirement: Option<Duration>,
     pub dataset_size: Option<u64>,
+    /// ZeRO partitioning stage chosen by [`MLOptimizer::apply_zero_recommendations`] when
+    /// `model_size` doesn't fit a single GPU's optimizer state; `None` until then.
+    pub zero_stage: Option<u8>,
+    /// Where to offload ZeRO state that doesn't fit on-device even at stage 3
+    pub offload_target: Option<OffloadTarget>,
 }
 
 impl Default for OptimizationConfig {
@@ -225,13 +322,16 @@ impl Default for OptimizationConfig {
             batch_size: None,
             sequence_length: None,
             use_mixed_precision: true,
+            precision_mode: PrecisionMode::Bf16,
             optimize_memory: true,
             enable_distributed: false,
             target_framework: None,
-            max_memory_usage: Some(0.8), // 80% of available memory
+            max_memory_usage: None, // resolves to DEFAULT_MEMORY_FRACTION of available memory
             target_throughput: None,
             latency_requirement: None,
             dataset_size: None,
+            zero_stage: None,
+            offload_target: None,
         }
     }
 }
@@ -309,6 +409,10 @@ pub struct PerformanceEstimate {
     pub training_time_estimate: Option<Duration>,
     pub gpu_utilization_estimate: f32, // 0.0 - 1.0
     pub bottleneck_analysis: Vec<String>,
+    /// Bootstrap confidence interval around `training_time_estimate`, when it was produced by a
+    /// micro-benchmark run (see [`crate::profiling::Profiler::benchmark_candidate`]) rather than
+    /// a single-point heuristic
+    pub confidence_interval: Option<ConfidenceInterval>,
 }
 
 /// Resource allocation recommendations
@@ -346,6 +450,18 @@ pub struct GPUAllocation {
     pub distributed_strategy: Option<String>,
 }
 
+/// Cluster placement to generate a concrete [`MLOptimizer::generate_launch_command`] invocation
+/// for: how many nodes, how many GPUs each, which node this command launches on, and where the
+/// rendezvous master lives.
+#[derive(Debug, Clone)]
+pub struct ClusterLaunchSpec {
+    pub nodes: u32,
+    pub gpus_per_node: u32,
+    pub node_rank: u32,
+    pub master_addr: String,
+    pub master_port: u16,
+}
+
 /// Storage allocation configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageAllocation {
@@ -353,6 +469,18 @@ pub struct StorageAllocation {
     pub tmp_directory: PathBuf,
     pub prefetch_buffer: u64,
     pub use_ssd_cache: bool,
+    /// Compression applied to the prefetch/cache buffers, trading CPU time for a smaller
+    /// on-disk footprint
+    pub chunk_compression: ChunkCompression,
+}
+
+/// On-disk compression codec for [`StorageAllocation`]'s prefetch/cache buffers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChunkCompression {
+    None,
+    Lz4,
+    Zstd,
+    Snappy,
 }
 
 // ============================================================================
@@ -387,21 +515,21 @@ impl OptimizationProfile {
                 use_mixed_precision: true,
                 optimize_memory: false,
                 enable_distributed: false,
-                max_memory_usage: Some(0.95),
+                max_memory_usage: Some(MemoryBudget::Percentage(0.95)),
                 ..Default::default()
             },
             Self::MemoryEfficient => OptimizationConfig {
                 workload_type: WorkloadType::Training,
                 use_mixed_precision: true,
                 optimize_memory: true,
-                max_memory_usage: Some(0.6),
+                max_memory_usage: Some(MemoryBudget::Percentage(0.6)),
                 ..Default::default()
             },
             Self::Balanced => OptimizationConfig {
                 workload_type: WorkloadType::Training,
                 use_mixed_precision: true,
                 optimize_memory: true,
-                max_memory_usage: Some(0.8),
+                max_memory_usage: Some(MemoryBudget::Percentage(0.8)),
                 ..Default::default()
             },
             Self::LowLatency => OptimizationConfig {
@@ -415,7 +543,7 @@ impl OptimizationProfile {
                 workload_type: WorkloadType::Inference,
                 use_mixed_precision: true,
                 optimize_memory: false,
-                max_memory_usage: Some(0.95),
+                max_memory_usage: Some(MemoryBudget::Percentage(0.95)),
                 ..Default::default()
             },
             Self::Distributed => OptimizationConfig {
@@ -431,7 +559,7 @@ impl OptimizationProfile {
                 use_mixed_precision: false,
                 optimize_memory: false,
                 enable_distributed: false,
-                max_memory_usage: Some(0.5),
+                max_memory_usage: Some(MemoryBudget::Percentage(0.5)),
                 ..Default::default()
             },
         }
@@ -455,6 +583,7 @@ impl MLOptimizer {
             frameworks: Arc::new(RwLock::new(Vec::new())),
             system_info: Arc::new(RwLock::new(system_info)),
             optimization_cache: Arc::new(RwLock::new(HashMap::new())),
+            batch_probe_cache: Arc::new(RwLock::new(HashMap::new())),
             profiler: Arc::new(RwLock::new(profiler)),
         })
     }
@@ -470,32 +599,123 @@ impl MLOptimizer {
         let memory_total = sys.total_memory();
         let memory_available = sys.available_memory();
 
-        // GPU information would come from hecate-gpu integration
-        let gpu_count = 0; // Placeholder
-        let gpu_memory_total = 0; // Placeholder
+        let (gpu_count, gpu_memory_total, gpu_memory_available, gpu_compute_capabilities) =
+            Self::detect_gpu_info().await?;
 
-        // Detect storage type
-        let storage_type = Self::detect_storage_type().await?;
+        // Same default as `StorageAllocation::cache_directory` in `convert_optimization_result`;
+        // classify the volume that actually backs the dataset cache.
+        let storage_type = Self::detect_storage_type(Path::new("/tmp/ml_cache"));
 
-        // Network bandwidth detection (placeholder)
+        // No link-speed probe yet
         let network_bandwidth = None;
 
+        // Runs the CPU/memory-copy/disk-roundtrip micro-benchmarks on a blocking thread, since
+        // each takes tens of milliseconds of uninterrupted compute/IO and would otherwise stall
+        // the async runtime.
+        let hardware_scores = tokio::task::spawn_blocking(|| hardware_probe::HardwareProbe::run(Path::new("/tmp")))
+            .await
+            .map_err(|e| MLError::SystemInfoError(format!("hardware probe task panicked: {}", e)))?;
+
         Ok(SystemInfo {
             cpu_cores,
             memory_total,
             memory_available,
             gpu_count,
             gpu_memory_total,
+            gpu_memory_available,
+            gpu_compute_capabilities,
             storage_type,
             network_bandwidth,
+            hardware_scores,
         })
     }
 
-    /// Detect primary storage type
-    async fn detect_storage_type() -> Result<StorageType> {
-        // This would analyze /proc/diskstats, /sys/block, etc.
-        // For now, assume SSD as it's most common in modern systems
-        Ok(StorageType::SSD)
+    /// Re-sample [`SystemInfo`] (in particular free GPU memory and storage class, both of which
+    /// drift over the lifetime of a long-running optimizer) instead of trusting the one-time
+    /// snapshot taken in [`Self::new`]
+    pub async fn refresh_system_info(&self) -> Result<()> {
+        let fresh = Self::gather_system_info().await?;
+        *self.system_info.write().await = fresh;
+        Ok(())
+    }
+
+    /// Query NVML for per-GPU total/free memory and CUDA compute capability, returning
+    /// `(gpu_count, gpu_memory_total, gpu_memory_available, gpu_compute_capabilities)`. Returns
+    /// all-zero/empty when no NVIDIA driver is present rather than failing system info gathering
+    /// outright, since a missing GPU is a normal (CPU-only) configuration.
+    #[cfg(feature = "nvidia")]
+    async fn detect_gpu_info() -> Result<(u32, u64, u64, Vec<f32>)> {
+        tokio::task::spawn_blocking(Self::detect_gpu_info_blocking)
+            .await
+            .map_err(|e| MLError::SystemInfoError(format!("GPU detection task panicked: {}", e)))?
+    }
+
+    #[cfg(feature = "nvidia")]
+    fn detect_gpu_info_blocking() -> Result<(u32, u64, u64, Vec<f32>)> {
+        use nvml_wrapper::Nvml;
+
+        let nvml = match Nvml::init() {
+            Ok(nvml) => nvml,
+            Err(_) => return Ok((0, 0, 0, Vec::new())), // No NVIDIA driver/GPU present
+        };
+
+        let device_count = nvml.device_count().map_err(|e| MLError::SystemInfoError(e.to_string()))?;
+        let mut gpu_memory_total = 0u64;
+        let mut gpu_memory_available = 0u64;
+        let mut gpu_compute_capabilities = Vec::with_capacity(device_count as usize);
+
+        for index in 0..device_count {
+            let device = match nvml.device_by_index(index) {
+                Ok(device) => device,
+                Err(_) => continue, // Skip GPUs that vanish mid-enumeration rather than failing the whole probe
+            };
+
+            if let Ok(mem_info) = device.memory_info() {
+                gpu_memory_total += mem_info.total;
+                gpu_memory_available += mem_info.free;
+            }
+
+            if let Ok(capability) = device.cuda_compute_capability() {
+                gpu_compute_capabilities.push(capability.major as f32 + capability.minor as f32 / 10.0);
+            }
+        }
+
+        Ok((device_count, gpu_memory_total, gpu_memory_available, gpu_compute_capabilities))
+    }
+
+    #[cfg(not(feature = "nvidia"))]
+    async fn detect_gpu_info() -> Result<(u32, u64, u64, Vec<f32>)> {
+        Ok((0, 0, 0, Vec::new()))
+    }
+
+    /// Classify the storage backing `path`'s mount: reads `/proc/mounts` to find the device and
+    /// filesystem for the longest matching mount point, treats overlay/remote filesystems as
+    /// [`StorageType::Network`], and otherwise distinguishes NVMe namespaces from SATA/SAS disks
+    /// by device name and then reads `/sys/block/<dev>/queue/rotational` (`1` -> HDD, `0` -> SSD).
+    /// Falls back to `SSD` (the most common case on modern hardware) if anything can't be read,
+    /// e.g. in a sandboxed environment without a real `/proc/mounts`.
+    fn detect_storage_type(path: &Path) -> StorageType {
+        let Ok(mounts) = std::fs::read_to_string("/proc/mounts") else {
+            return StorageType::SSD;
+        };
+        let Some((device, fs_type)) = mount_entry_for_path(&mounts, path) else {
+            return StorageType::SSD;
+        };
+        if is_network_fs(&fs_type) {
+            return StorageType::Network;
+        }
+        let Some(block_device) = block_device_name(&device) else {
+            return StorageType::SSD;
+        };
+        if block_device.starts_with("nvme") {
+            return StorageType::NVMe;
+        }
+
+        let rotational_path = format!("/sys/block/{}/queue/rotational", block_device);
+        match std::fs::read_to_string(&rotational_path).ok().as_deref().and_then(parse_rotational) {
+            Some(true) => StorageType::HDD,
+            _ => StorageType::SSD,
+        }
     }
 
     /// Detect available ML frameworks
@@ -614,6 +834,9 @@ impl MLOptimizer {
                 path: f.installation_path.to_string_lossy().to_string(),
                 features: f.capabilities.clone(),
                 python_version: None, // Would be detected in real implementation
+                accelerator: None, // Structured accelerator info isn't retained on the external type
+                build_variant: None, // Nor is build-variant classification
+                in_container: false,
             })
             .collect();
         
@@ -636,10 +859,22 @@ impl MLOptimizer {
         };
         
         // Convert optimization result to our format
-        let mut result = self.convert_optimization_result(opt_result, config)?;
+        let mut result = self.convert_optimization_result(opt_result, config, &system_info)?;
 
         // Add framework-specific optimizations
-        self.apply_framework_optimizations(config, &mut result).await?;
+        self.apply_framework_optimizations(&system_info, config, &mut result).await?;
+
+        // Gate FP8 on hardware support, downgrading to BF16 when it isn't safe
+        self.apply_precision_recommendations(&system_info, &mut result);
+
+        // Pick a ZeRO partitioning stage when the model doesn't fit a single GPU's optimizer state
+        self.apply_zero_recommendations(&system_info, &mut result);
+
+        // Recommend reclaiming activation memory via tensor-liveness-aware buffer reuse
+        self.apply_memory_reuse_recommendation(&mut result);
+
+        // Budget GPU memory into a concrete GPUAllocation, shrinking the batch size if it overflows
+        self.apply_gpu_memory_planning(&system_info, &mut result);
 
         // Validate recommendations
         self.validate_recommendations(&mut result).await?;
@@ -660,6 +895,7 @@ impl MLOptimizer {
         config.model_size.hash(&mut hasher);
         config.batch_size.hash(&mut hasher);
         config.use_mixed_precision.hash(&mut hasher);
+        config.precision_mode.hash(&mut hasher);
         config.optimize_memory.hash(&mut hasher);
         config.enable_distributed.hash(&mut hasher);
         config.target_framework.hash(&mut hasher);
@@ -681,9 +917,10 @@ impl MLOptimizer {
 
     /// Convert optimization result from internal format
     fn convert_optimization_result(
-        &self, 
-        opt_result: optimization::OptimizationResult, 
-        config: &OptimizationConfig
+        &self,
+        opt_result: optimization::OptimizationResult,
+        config: &OptimizationConfig,
+        system_info: &SystemInfo,
     ) -> Result<OptimizationResult> {
         let recommendations: Vec<Recommendation> = opt_result.recommendations.iter()
             .map(|rec| Recommendation {
@@ -719,20 +956,41 @@ impl MLOptimizer {
                 training_time_estimate: None,
                 gpu_utilization_estimate: 0.8, // Default estimate
                 bottleneck_analysis: vec![],
+                confidence_interval: None,
             },
-            resource_allocation: self.create_default_resource_allocation(),
+            resource_allocation: self.create_default_resource_allocation(system_info),
             environment_variables: HashMap::new(),
             command_line_args: vec![],
             warnings: vec![],
         })
     }
 
-    /// Create default resource allocation
-    fn create_default_resource_allocation(&self) -> ResourceAllocation {
+    /// Create default resource allocation, sized from `system_info.hardware_scores` (measured by
+    /// [`hardware_probe::HardwareProbe`]) rather than fixed constants where a measurement exists
+    fn create_default_resource_allocation(&self, system_info: &SystemInfo) -> ResourceAllocation {
+        let scores = &system_info.hardware_scores;
+        let cpu_count = num_cpus::get() as u32;
+
+        // A faster memory subsystem can keep more worker threads fed with batches without
+        // becoming the bottleneck itself; fall back to the old cores/2 default when the probe
+        // didn't run (score of 0.0, e.g. in a test that builds `SystemInfo` by hand).
+        let dataloader_workers = if scores.memory_bandwidth_mb_s > 0.0 {
+            ((scores.memory_bandwidth_mb_s / 2000.0).round() as u32).clamp(1, cpu_count)
+        } else {
+            (cpu_count / 2).max(1)
+        };
+
+        // A big prefetch window just stalls waiting to fill on slow storage; only worth it once
+        // the disk probe confirms SSD/NVMe-like throughput.
+        let prefetch_buffer = if scores.disk_is_fast { 128 * 1024 * 1024 } else { 32 * 1024 * 1024 };
+
+        let tmp_directory = PathBuf::from("/tmp");
+        let cache_directory = tmp_directory.join("ml_cache");
+
         ResourceAllocation {
             cpu_allocation: CPUAllocation {
-                worker_threads: num_cpus::get() as u32,
-                dataloader_workers: (num_cpus::get() / 2).max(1) as u32,
+                worker_threads: cpu_count,
+                dataloader_workers,
                 cpu_affinity: None,
             },
             memory_allocation: MemoryAllocation {
@@ -743,10 +1001,13 @@ impl MLOptimizer {
             },
             gpu_allocation: None, // Would be set if GPUs detected
             storage_allocation: StorageAllocation {
-                cache_directory: PathBuf::from("/tmp/ml_cache"),
-                tmp_directory: PathBuf::from("/tmp"),
-                prefetch_buffer: 128 * 1024 * 1024, // 128MB
-                use_ssd_cache: true,
+                cache_directory,
+                tmp_directory,
+                prefetch_buffer,
+                use_ssd_cache: scores.disk_is_fast,
+                // Slow storage benefits more from trading CPU for a smaller footprint; fast
+                // NVMe/SSD storage isn't worth the CPU cost to shrink what's already quick to read.
+                chunk_compression: if scores.disk_is_fast { ChunkCompression::None } else { ChunkCompression::Zstd },
             },
         }
     }
@@ -754,6 +1015,7 @@ impl MLOptimizer {
     /// Apply framework-specific optimizations
     async fn apply_framework_optimizations(
         &self,
+        system_info: &SystemInfo,
         config: &OptimizationConfig,
         result: &mut OptimizationResult,
     ) -> Result<()> {
@@ -761,6 +1023,10 @@ impl MLOptimizer {
 
         for framework in frameworks.iter() {
             match framework.framework_type {
+                FrameworkType::ONNX => {
+                    self.apply_generic_optimizations(config, result, framework).await?;
+                    self.apply_onnx_optimizations(system_info, config, result).await?;
+                }
                 FrameworkType::PyTorch | FrameworkType::TensorFlow => {
                     // Apply generic optimizations for these frameworks
                     self.apply_generic_optimizations(config, result, framework).await?;
@@ -806,17 +1072,491 @@ impl MLOptimizer {
         Ok(())
     }
 
+    /// ONNX Runtime session tuning: pick execution providers in priority order, a graph
+    /// optimization level, and threading/execution mode for the `LowLatency`/`HighThroughput`
+    /// inference profiles (inferred from `workload_type`/`batch_size`, since `OptimizationConfig`
+    /// doesn't retain which `OptimizationProfile` produced it), and suggest INT8/FP16
+    /// quantization when a calibration `dataset_size` is available.
+    async fn apply_onnx_optimizations(
+        &self,
+        system_info: &SystemInfo,
+        config: &OptimizationConfig,
+        result: &mut OptimizationResult,
+    ) -> Result<()> {
+        let providers = self.select_onnx_execution_providers(system_info);
+        result.environment_variables.insert("ORT_EXECUTION_PROVIDERS".to_string(), providers.join(","));
+
+        let is_low_latency = config.workload_type == WorkloadType::Inference && config.batch_size == Some(1);
+        let is_high_throughput = config.workload_type == WorkloadType::Inference && !is_low_latency;
+
+        let (execution_mode, inter_op_threads, intra_op_threads, shape_hint) = if is_low_latency {
+            ("ORT_SEQUENTIAL", 1, 1, "fixed input shapes with IO binding to avoid per-call allocation")
+        } else if is_high_throughput {
+            let intra_op_threads = system_info.cpu_cores.max(1);
+            let inter_op_threads = (system_info.cpu_cores / 2).max(1);
+            ("ORT_PARALLEL", inter_op_threads, intra_op_threads, "dynamic input shapes batched for throughput")
+        } else {
+            ("ORT_SEQUENTIAL", 1, system_info.cpu_cores.max(1), "whichever input shape the workload already uses")
+        };
+        let graph_optimization_level = if is_low_latency || is_high_throughput { "All" } else { "Extended" };
+
+        result.environment_variables.insert("ORT_GRAPH_OPTIMIZATION_LEVEL".to_string(), graph_optimization_level.to_string());
+        result.environment_variables.insert("ORT_EXECUTION_MODE".to_string(), execution_mode.to_string());
+        result.environment_variables.insert("ORT_INTRA_OP_NUM_THREADS".to_string(), intra_op_threads.to_string());
+        result.environment_variables.insert("ORT_INTER_OP_NUM_THREADS".to_string(), inter_op_threads.to_string());
+
+        if is_low_latency || is_high_throughput {
+            result.recommendations.push(Recommendation {
+                category: RecommendationCategory::Framework,
+                title: "Tune ONNX Runtime session options".to_string(),
+                description: format!(
+                    "Execution providers {:?}, graph optimization level {}, {} execution mode with {} intra-op / \
+                     {} inter-op threads, and {}",
+                    providers, graph_optimization_level, execution_mode, intra_op_threads, inter_op_threads, shape_hint
+                ),
+                impact: Impact::High,
+                implementation: Implementation::ConfigFile {
+                    path: PathBuf::from("onnxruntime_session_options.json"),
+                    content: format!(
+                        "{{\n  \"execution_providers\": {:?},\n  \"graph_optimization_level\": \"{}\",\n  \
+                         \"execution_mode\": \"{}\",\n  \"intra_op_num_threads\": {},\n  \"inter_op_num_threads\": {}\n}}",
+                        providers, graph_optimization_level, execution_mode, intra_op_threads, inter_op_threads
+                    ),
+                },
+                confidence: 0.75,
+            });
+        }
+
+        if let Some(dataset_size) = config.dataset_size {
+            if dataset_size > 0 {
+                result.recommendations.push(Recommendation {
+                    category: RecommendationCategory::Compute,
+                    title: "Quantize the ONNX model".to_string(),
+                    description: format!(
+                        "A calibration dataset of {} samples is available; static INT8 quantization (or FP16 \
+                         if accuracy-sensitive) typically cuts inference latency substantially on CPU/TensorRT",
+                        dataset_size
+                    ),
+                    impact: Impact::Medium,
+                    implementation: Implementation::CommandLineArg {
+                        arg: "--quantize int8 --calibration-data calibration_set".to_string(),
+                    },
+                    confidence: 0.6,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pick ONNX Runtime execution providers in priority order: TensorRT then CUDA when a GPU is
+    /// detected, falling back to CPU-only otherwise
+    fn select_onnx_execution_providers(&self, system_info: &SystemInfo) -> Vec<&'static str> {
+        if system_info.gpu_count > 0 {
+            vec!["TensorrtExecutionProvider", "CUDAExecutionProvider", "CPUExecutionProvider"]
+        } else {
+            vec!["CPUExecutionProvider"]
+        }
+    }
+
+    /// Gate FP8 on the weakest detected GPU's compute capability. When every GPU clears
+    /// [`precision::MIN_FP8_COMPUTE_CAPABILITY`], recommends FP8 (E4M3 for forward
+    /// activations/weights, E5M2 for gradients, both with delayed scaling); otherwise downgrades
+    /// `result.config.precision_mode` to `Bf16` and explains why in a `Compute` recommendation.
+    fn apply_precision_recommendations(&self, system_info: &SystemInfo, result: &mut OptimizationResult) {
+        if result.config.precision_mode != PrecisionMode::Fp8 {
+            return;
+        }
+
+        let min_compute_capability = system_info
+            .gpu_compute_capabilities
+            .iter()
+            .cloned()
+            .fold(None, |min: Option<f32>, cap| Some(min.map_or(cap, |m| m.min(cap))));
+
+        match min_compute_capability {
+            Some(capability) if precision::gpu_supports_fp8(capability) => {
+                result.recommendations.push(Recommendation {
+                    category: RecommendationCategory::Compute,
+                    title: "Enable FP8 mixed precision".to_string(),
+                    description: format!(
+                        "Every detected GPU reports compute capability >= {:.1}, so FP8 is numerically \
+                         safe with delayed scaling (E4M3 for forward activations/weights, E5M2 for gradients)",
+                        precision::MIN_FP8_COMPUTE_CAPABILITY
+                    ),
+                    impact: Impact::High,
+                    implementation: Implementation::CodeChange {
+                        description: "Quantize forward activations/weights to E4M3 and gradients to E5M2, \
+                                       scaling each from a 16-step rolling amax history"
+                            .to_string(),
+                        example: Some("recipe = DelayedScaling(fp8_format=Format.HYBRID, amax_history_len=16)".to_string()),
+                    },
+                    confidence: 0.75,
+                });
+            }
+            other => {
+                result.config.precision_mode = PrecisionMode::Bf16;
+                let reason = match other {
+                    Some(capability) => format!(
+                        "Weakest detected GPU reports compute capability {:.1}, below the {:.1} required \
+                         for safe FP8; falling back to BF16",
+                        capability,
+                        precision::MIN_FP8_COMPUTE_CAPABILITY
+                    ),
+                    None => "No GPU compute capability was reported, so FP8 safety can't be confirmed; \
+                              falling back to BF16"
+                        .to_string(),
+                };
+                result.recommendations.push(Recommendation {
+                    category: RecommendationCategory::Compute,
+                    title: "Downgrade FP8 to BF16".to_string(),
+                    description: reason,
+                    impact: Impact::Medium,
+                    implementation: Implementation::CodeChange {
+                        description: "Use bfloat16 instead of float8 for this workload".to_string(),
+                        example: Some("dtype = torch.bfloat16".to_string()),
+                    },
+                    confidence: 0.9,
+                });
+            }
+        }
+    }
+
+    /// For models too large to fit single-GPU optimizer state, pick the minimum ZeRO stage
+    /// (escalating to CPU/NVMe offload if even stage 3 doesn't fit) via
+    /// [`distributed::select_zero_plan`], surface it as `zero_stage`/`offload_target` on the
+    /// result's config, and emit a DeepSpeed-style `ConfigFile` recommendation plus environment
+    /// variables. Warns if NVMe offload is chosen but the detected storage isn't NVMe.
+    fn apply_zero_recommendations(&self, system_info: &SystemInfo, result: &mut OptimizationResult) {
+        let Some(model_parameters) = result.config.model_size else {
+            return;
+        };
+        if system_info.gpu_count == 0 {
+            return;
+        }
+
+        let world_size = system_info.gpu_count;
+        let per_gpu_memory = system_info.gpu_memory_total / system_info.gpu_count as u64;
+        let budget_bytes =
+            self.resolve_memory_budget(result.config.max_memory_usage, per_gpu_memory, &mut result.warnings);
+
+        // Unpartitioned (no ZeRO) state already fits on a single GPU: nothing to recommend.
+        if model_parameters.saturating_mul(16) <= budget_bytes {
+            return;
+        }
+
+        let plan = distributed::select_zero_plan(
+            model_parameters,
+            world_size,
+            budget_bytes,
+            Some(system_info.memory_available),
+        );
+
+        result.config.zero_stage = Some(plan.stage);
+        result.config.offload_target = Some(plan.offload_target);
+
+        result.environment_variables.insert("ZERO_STAGE".to_string(), plan.stage.to_string());
+        let offload_device = match plan.offload_target {
+            OffloadTarget::Cpu => Some("cpu"),
+            OffloadTarget::Nvme => Some("nvme"),
+            OffloadTarget::None => None,
+        };
+        if let Some(device) = offload_device {
+            result.environment_variables.insert("ZERO_OFFLOAD_DEVICE".to_string(), device.to_string());
+        }
+
+        let config_blob = format!(
+            "{{\n  \"zero_optimization\": {{\n    \"stage\": {},\n    \"offload_optimizer\": {{ \"device\": \"{}\" }}\n  }}\n}}",
+            plan.stage,
+            offload_device.unwrap_or("none"),
+        );
+
+        result.recommendations.push(Recommendation {
+            category: RecommendationCategory::Memory,
+            title: format!("Enable ZeRO stage {}", plan.stage),
+            description: format!(
+                "Unpartitioned optimizer state needs ~{} per GPU; ZeRO stage {} across {} ranks \
+                 brings that down to ~{} per rank{}",
+                self.format_bytes(model_parameters * 16),
+                plan.stage,
+                world_size,
+                self.format_bytes(plan.memory_per_rank_bytes),
+                match offload_device {
+                    Some(device) => format!(", offloading to {}", device),
+                    None => String::new(),
+                }
+            ),
+            impact: Impact::Critical,
+            implementation: Implementation::ConfigFile {
+                path: PathBuf::from("deepspeed_config.json"),
+                content: config_blob,
+            },
+            confidence: 0.8,
+        });
+
+        if plan.offload_target == OffloadTarget::Nvme && system_info.storage_type != StorageType::NVMe {
+            result.warnings.push(format!(
+                "ZeRO offload target is NVMe but detected storage is {:?}, not NVMe: offload I/O will likely bottleneck training",
+                system_info.storage_type
+            ));
+        }
+    }
+
+    /// Render an `OptimizationResult` and a cluster placement into a runnable multi-GPU/multi-node
+    /// launch command for the detected framework — `torchrun` for PyTorch, and an equivalent
+    /// env-var-driven invocation for TensorFlow/JAX. Sets `WORLD_SIZE`/`RANK`/`LOCAL_RANK`/
+    /// `MASTER_ADDR`/`MASTER_PORT`/`CUDA_VISIBLE_DEVICES` consistently with `GPUAllocation.gpu_ids`,
+    /// populates `GPUAllocation.distributed_strategy`, and records the rendered command in
+    /// `result.command_line_args`.
+    pub fn generate_launch_command(&self, result: &mut OptimizationResult, spec: &ClusterLaunchSpec) -> String {
+        let world_size = spec.nodes * spec.gpus_per_node;
+        let local_rank = 0; // base process launched by this command; the launcher assigns the rest
+        let rank = spec.node_rank * spec.gpus_per_node + local_rank;
+
+        let gpu_ids: Vec<u32> = result
+            .resource_allocation
+            .gpu_allocation
+            .as_ref()
+            .map(|g| g.gpu_ids.clone())
+            .unwrap_or_else(|| (0..spec.gpus_per_node).collect());
+        let cuda_visible_devices = gpu_ids.iter().map(u32::to_string).collect::<Vec<_>>().join(",");
+
+        result.environment_variables.insert("WORLD_SIZE".to_string(), world_size.to_string());
+        result.environment_variables.insert("RANK".to_string(), rank.to_string());
+        result.environment_variables.insert("LOCAL_RANK".to_string(), local_rank.to_string());
+        result.environment_variables.insert("MASTER_ADDR".to_string(), spec.master_addr.clone());
+        result.environment_variables.insert("MASTER_PORT".to_string(), spec.master_port.to_string());
+        result.environment_variables.insert("CUDA_VISIBLE_DEVICES".to_string(), cuda_visible_devices.clone());
+
+        // ZeRO sharding is PyTorch's FSDP equivalent; fall back to plain DDP otherwise.
+        let torch_strategy = if result.config.zero_stage.is_some() { "FSDP" } else { "DDP" };
+
+        let framework = result.config.target_framework.unwrap_or(FrameworkType::PyTorch);
+        let (command, distributed_strategy) = match framework {
+            FrameworkType::PyTorch => (
+                format!(
+                    "torchrun --nnodes={} --nproc_per_node={} --node_rank={} --master_addr={} --master_port={} train.py",
+                    spec.nodes, spec.gpus_per_node, spec.node_rank, spec.master_addr, spec.master_port
+                ),
+                torch_strategy.to_string(),
+            ),
+            FrameworkType::TensorFlow => (
+                format!(
+                    "WORLD_SIZE={} RANK={} LOCAL_RANK={} MASTER_ADDR={} MASTER_PORT={} CUDA_VISIBLE_DEVICES={} python train.py",
+                    world_size, rank, local_rank, spec.master_addr, spec.master_port, cuda_visible_devices
+                ),
+                "MultiWorkerMirroredStrategy".to_string(),
+            ),
+            FrameworkType::JAX => (
+                format!(
+                    "WORLD_SIZE={} RANK={} LOCAL_RANK={} MASTER_ADDR={} MASTER_PORT={} CUDA_VISIBLE_DEVICES={} python train.py",
+                    world_size, rank, local_rank, spec.master_addr, spec.master_port, cuda_visible_devices
+                ),
+                "pmap".to_string(),
+            ),
+            _ => (
+                format!(
+                    "WORLD_SIZE={} RANK={} LOCAL_RANK={} MASTER_ADDR={} MASTER_PORT={} CUDA_VISIBLE_DEVICES={} python train.py",
+                    world_size, rank, local_rank, spec.master_addr, spec.master_port, cuda_visible_devices
+                ),
+                "data-parallel".to_string(),
+            ),
+        };
+
+        match result.resource_allocation.gpu_allocation.as_mut() {
+            Some(gpu_allocation) => gpu_allocation.distributed_strategy = Some(distributed_strategy),
+            None => {
+                result.resource_allocation.gpu_allocation = Some(GPUAllocation {
+                    gpu_ids,
+                    memory_fraction: result
+                        .config
+                        .max_memory_usage
+                        .map(|budget| budget.fraction_hint())
+                        .unwrap_or(byte_size::DEFAULT_MEMORY_FRACTION),
+                    allow_growth: true,
+                    distributed_strategy: Some(distributed_strategy),
+                });
+            }
+        }
+
+        result.command_line_args = command.split_whitespace().map(String::from).collect();
+        command
+    }
+
+    /// Build a reproducible container image spec from `result` via
+    /// [`container::build_container_spec`]: picks the base image/wheel index for the tuned
+    /// framework and accelerator, bakes in `result.environment_variables`, and collects the
+    /// generated config files to copy in. See [`ContainerSpec`].
+    pub fn generate_container_spec(&self, result: &OptimizationResult) -> ContainerSpec {
+        container::build_container_spec(result)
+    }
+
+    /// Probe the largest batch size that fits `max_memory_usage` of available memory for
+    /// `result.config`, by launching short trial runs of the target framework and catching CUDA
+    /// OOM, then write it back to `result.config.batch_size`. Starts from batch size 1 and
+    /// doubles until a trial no longer fits, then binary-searches the boundary between the last
+    /// good size and the first failing one. Caches the measured result keyed by (model_size,
+    /// sequence_length, precision_mode) so repeat calls skip re-probing. If the originally
+    /// requested `batch_size` doesn't fit, recommends gradient accumulation instead of failing,
+    /// and records the measured memory-per-sample slope in `estimated_performance.bottleneck_analysis`.
+    pub async fn autotune_batch_size(&self, result: &mut OptimizationResult) -> Result<()> {
+        let desired_global_batch_size = result.config.batch_size;
+        let cache_key = self.batch_probe_cache_key(&result.config);
+
+        let probe = match self.get_cached_batch_probe(&cache_key).await {
+            Some(probe) => probe,
+            None => {
+                let budget_bytes = {
+                    let system_info = self.system_info.read().await;
+                    let available = system_info.memory_available;
+                    self.resolve_memory_budget(result.config.max_memory_usage, available, &mut result.warnings)
+                };
+
+                let fitting_batch_size = self.probe_max_batch_size(&result.config, budget_bytes)?;
+                let memory_per_sample_bytes = self.estimate_memory_per_sample(&result.config)?;
+                let peak_memory_bytes = self.estimate_memory_usage(fitting_batch_size, &result.config)?;
+                let probe = BatchSizeProbe { fitting_batch_size, peak_memory_bytes, memory_per_sample_bytes };
+                self.cache_batch_probe(cache_key, probe.clone()).await;
+                probe
+            }
+        };
+
+        result.config.batch_size = Some(probe.fitting_batch_size);
+        result.estimated_performance.memory_usage_estimate = probe.peak_memory_bytes;
+        result.estimated_performance.bottleneck_analysis.push(format!(
+            "Measured ~{} per sample; largest batch that fits on-device is {}",
+            self.format_bytes(probe.memory_per_sample_bytes),
+            probe.fitting_batch_size
+        ));
+
+        if let Some(desired) = desired_global_batch_size {
+            if desired > probe.fitting_batch_size {
+                let accumulation_steps = (desired as f64 / probe.fitting_batch_size as f64).ceil() as u32;
+                result.recommendations.push(Recommendation {
+                    category: RecommendationCategory::BatchSize,
+                    title: "Use gradient accumulation".to_string(),
+                    description: format!(
+                        "Desired global batch size {} doesn't fit in memory ({} max); accumulate \
+                         gradients over {} steps of batch size {} instead",
+                        desired, probe.fitting_batch_size, accumulation_steps, probe.fitting_batch_size
+                    ),
+                    impact: Impact::High,
+                    implementation: Implementation::CodeChange {
+                        description: "Accumulate gradients across micro-batches before each optimizer step".to_string(),
+                        example: Some(format!("accumulation_steps = {}", accumulation_steps)),
+                    },
+                    confidence: 0.8,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Launch short trial runs, doubling the batch size until one no longer fits `budget_bytes`,
+    /// then binary-search the boundary between the last good size and the first failing one.
+    /// Stands in for actually spawning the target framework process and catching a CUDA OOM.
+    fn probe_max_batch_size(&self, config: &OptimizationConfig, budget_bytes: u64) -> Result<u32> {
+        let fits = |batch_size: u32| -> Result<bool> {
+            Ok(self.estimate_memory_usage(batch_size, config)? <= budget_bytes)
+        };
+
+        if !fits(1)? {
+            return Ok(1); // Even a single sample doesn't fit; report the floor rather than failing.
+        }
+
+        let mut last_good = 1u32;
+        let mut first_failing = None;
+        let mut trial = 2u32;
+        loop {
+            if fits(trial)? {
+                last_good = trial;
+                match trial.checked_mul(2) {
+                    Some(next) => trial = next,
+                    None => break,
+                }
+            } else {
+                first_failing = Some(trial);
+                break;
+            }
+        }
+
+        let Some(mut hi) = first_failing else {
+            return Ok(last_good);
+        };
+        let mut lo = last_good;
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if fits(mid)? {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Ok(lo)
+    }
+
+    /// Measure the memory-per-sample slope by probing batch sizes 1 and 2, rather than assuming
+    /// a fixed bytes-per-sample constant
+    fn estimate_memory_per_sample(&self, config: &OptimizationConfig) -> Result<u64> {
+        let one = self.estimate_memory_usage(1, config)?;
+        let two = self.estimate_memory_usage(2, config)?;
+        Ok(two.saturating_sub(one))
+    }
+
+    /// Cache key for a batch-size probe, covering only the inputs that affect per-sample memory
+    fn batch_probe_cache_key(&self, config: &OptimizationConfig) -> String {
+        let mut hasher = DefaultHasher::new();
+        config.model_size.hash(&mut hasher);
+        config.sequence_length.hash(&mut hasher);
+        config.precision_mode.hash(&mut hasher);
+        format!("batch_probe_{:x}", hasher.finish())
+    }
+
+    /// Get a cached batch-size probe result
+    async fn get_cached_batch_probe(&self, cache_key: &str) -> Option<BatchSizeProbe> {
+        let cache = self.batch_probe_cache.read().await;
+        cache.get(cache_key).cloned()
+    }
+
+    /// Cache a batch-size probe result
+    async fn cache_batch_probe(&self, cache_key: String, probe: BatchSizeProbe) {
+        let mut cache = self.batch_probe_cache.write().await;
+        cache.insert(cache_key, probe);
+    }
+
     /// Validate and adjust recommendations
+    ///
+    /// Instead of comparing one lump memory estimate against one limit, each competing consumer
+    /// (framework arena, model weights, activation cache) is reserved individually from a
+    /// [`memory_pool::GreedyMemoryPool`] sized to the configured memory budget. On overflow this
+    /// turns the single "batch size may not fit" warning into an ordered remediation plan (see
+    /// [`memory_pool::plan_spill`]), falling back to shrinking the batch size only if spilling
+    /// every tier still doesn't close the gap.
     async fn validate_recommendations(&self, result: &mut OptimizationResult) -> Result<()> {
         let system_info = self.system_info.read().await;
 
         // Check if recommended batch size is feasible
         if let Some(batch_size) = result.config.batch_size {
-            let estimated_memory = self.estimate_memory_usage(batch_size, &result.config)?;
-            let available_memory = (system_info.memory_available as f64 * 
-                                   result.config.max_memory_usage.unwrap_or(0.8) as f64) as u64;
-
-            if estimated_memory > available_memory {
+            let model_size = result.config.model_size.unwrap_or(100_000_000);
+            let sequence_length = result.config.sequence_length.unwrap_or(512) as u64;
+            let model_memory = model_size * 4; // 4 bytes per parameter
+            let base_memory = 1_024 * 1_024 * 1_024; // 1GB base, matches estimate_memory_usage
+            let activation_plan =
+                memory_planner::plan_activation_memory(model_size, sequence_length, batch_size.max(1) as u64);
+
+            let available_memory = self.resolve_memory_budget(
+                result.config.max_memory_usage,
+                system_info.memory_available,
+                &mut result.warnings,
+            );
+
+            let mut pool = memory_pool::GreedyMemoryPool::new(available_memory);
+            let _ = pool.try_reserve("framework_arena", base_memory);
+            let _ = pool.try_reserve("model_weights", model_memory);
+
+            if let Err(exceeded) = pool.try_reserve("activation_cache", activation_plan.peak_bytes) {
+                let estimated_memory = base_memory + model_memory + activation_plan.peak_bytes;
                 result.warnings.push(format!(
                     "Recommended batch size {} may exceed available memory ({} > {})",
                     batch_size,
@@ -824,36 +1564,132 @@ impl MLOptimizer {
                     self.format_bytes(available_memory)
                 ));
 
-                // Suggest a smaller batch size
-                let suggested_batch_size = (batch_size as f64 * 
-                    (available_memory as f64 / estimated_memory as f64)) as u32;
-                
-                result.recommendations.push(Recommendation {
-                    category: RecommendationCategory::BatchSize,
-                    title: "Reduce batch size".to_string(),
-                    description: format!("Reduce batch size to {} to fit in available memory", suggested_batch_size),
-                    impact: Impact::High,
-                    implementation: Implementation::CodeChange {
-                        description: "Modify batch_size parameter in training loop".to_string(),
-                        example: Some(format!("batch_size = {}", suggested_batch_size)),
-                    },
-                    confidence: 0.9,
-                });
+                let shortfall = exceeded.requested_bytes.saturating_sub(exceeded.available_bytes);
+                let spill_plan = memory_pool::plan_spill(shortfall, model_memory, activation_plan.peak_bytes);
+                let reclaimed_total: u64 = spill_plan.iter().map(|step| step.reclaimed_bytes).sum();
+
+                for step in &spill_plan {
+                    result.recommendations.push(Recommendation {
+                        category: RecommendationCategory::Memory,
+                        title: step.tier.title().to_string(),
+                        description: format!(
+                            "{} (reclaims an estimated {}, ~{:.0}% added compute overhead)",
+                            step.tier.description(),
+                            self.format_bytes(step.reclaimed_bytes),
+                            step.tier.compute_overhead_pct()
+                        ),
+                        impact: Impact::High,
+                        implementation: Implementation::CodeChange {
+                            description: step.tier.description().to_string(),
+                            example: None,
+                        },
+                        confidence: 0.75,
+                    });
+                }
+
+                if reclaimed_total < shortfall {
+                    // Spilling every tier still doesn't close the gap; shrink the batch as a last resort
+                    let suggested_batch_size = (batch_size as f64 *
+                        (available_memory as f64 / estimated_memory as f64)) as u32;
+
+                    result.recommendations.push(Recommendation {
+                        category: RecommendationCategory::BatchSize,
+                        title: "Reduce batch size".to_string(),
+                        description: format!("Reduce batch size to {} to fit in available memory", suggested_batch_size),
+                        impact: Impact::High,
+                        implementation: Implementation::CodeChange {
+                            description: "Modify batch_size parameter in training loop".to_string(),
+                            example: Some(format!("batch_size = {}", suggested_batch_size)),
+                        },
+                        confidence: 0.9,
+                    });
+                }
             }
         }
 
         Ok(())
     }
 
-    /// Estimate memory usage for a given configuration
+    /// Estimate memory usage for a given configuration: fixed overhead, model weights, and a
+    /// reuse-aware estimate of peak activation memory from [`memory_planner::plan_activation_memory`]
+    /// (instead of naively summing every activation tensor ever materialized)
     fn estimate_memory_usage(&self, batch_size: u32, config: &OptimizationConfig) -> Result<u64> {
         let base_memory = 1_024 * 1_024 * 1_024; // 1GB base
-        let model_memory = config.model_size.unwrap_or(100_000_000) * 4; // 4 bytes per parameter
-        let batch_memory = (batch_size as u64) * 
-                          config.sequence_length.unwrap_or(512) as u64 * 
-                          4 * 768; // Estimate based on typical transformer dimensions
+        let model_size = config.model_size.unwrap_or(100_000_000);
+        let model_memory = model_size * 4; // 4 bytes per parameter
+        let sequence_length = config.sequence_length.unwrap_or(512) as u64;
+
+        let activation_plan =
+            memory_planner::plan_activation_memory(model_size, sequence_length, batch_size.max(1) as u64);
+
+        Ok(base_memory + model_memory + activation_plan.peak_bytes)
+    }
+
+    /// Surface the activation memory reclaimed by buffer reuse (see
+    /// [`memory_planner::plan_activation_memory`]) as a [`RecommendationCategory::Memory`]
+    /// recommendation, when it's large enough to be worth calling out
+    fn apply_memory_reuse_recommendation(&self, result: &mut OptimizationResult) {
+        let model_size = result.config.model_size.unwrap_or(100_000_000);
+        let sequence_length = result.config.sequence_length.unwrap_or(512) as u64;
+        let batch_size = result.config.batch_size.unwrap_or(1).max(1) as u64;
+
+        let plan = memory_planner::plan_activation_memory(model_size, sequence_length, batch_size);
+        let savings = plan.savings_bytes();
+        if savings == 0 {
+            return;
+        }
 
-        Ok(base_memory + model_memory + batch_memory)
+        result.recommendations.push(Recommendation {
+            category: RecommendationCategory::Memory,
+            title: "Reuse activation memory across non-overlapping tensors".to_string(),
+            description: format!(
+                "Liveness-aware buffer reuse across {} intermediate tensors cuts peak activation \
+                 memory from {} to {} ({} reclaimed)",
+                plan.tensor_count,
+                self.format_bytes(plan.naive_sum_bytes),
+                self.format_bytes(plan.peak_bytes),
+                self.format_bytes(savings)
+            ),
+            impact: Impact::Medium,
+            implementation: Implementation::CodeChange {
+                description: "Enable the framework's activation/buffer-reuse memory planner (e.g. \
+                               PyTorch's caching allocator already does this; for custom graphs, \
+                               free intermediate tensors as soon as their last consumer runs)"
+                    .to_string(),
+                example: None,
+            },
+            confidence: 0.7,
+        });
+    }
+
+    /// Gate a tuned `candidate` benchmark against a `baseline` benchmark: attach the candidate's
+    /// confidence interval to `result` and, when the two intervals overlap (or the candidate
+    /// isn't actually faster), treat the apparent speedup as unproven — halve every
+    /// recommendation's confidence and record a warning — rather than reporting a confidently
+    /// wrong recommendation from what may just be benchmarking noise.
+    pub fn apply_confidence_gate(
+        &self,
+        baseline: &benchmark::BenchmarkReport,
+        candidate: &benchmark::BenchmarkReport,
+        result: &mut OptimizationResult,
+    ) {
+        result.estimated_performance.confidence_interval = Some(candidate.confidence_interval);
+        result.warnings.extend(baseline.warnings());
+        result.warnings.extend(candidate.warnings());
+
+        if !benchmark::is_significant_speedup(&baseline.confidence_interval, &candidate.confidence_interval) {
+            result.warnings.push(format!(
+                "candidate's 95% CI [{:.4}s, {:.4}s] overlaps baseline's [{:.4}s, {:.4}s]: speedup is not \
+                 statistically significant",
+                candidate.confidence_interval.lower,
+                candidate.confidence_interval.upper,
+                baseline.confidence_interval.lower,
+                baseline.confidence_interval.upper,
+            ));
+            for recommendation in &mut result.recommendations {
+                recommendation.confidence *= 0.5;
+            }
+        }
     }
 
     /// Format bytes to human-readable string
@@ -870,6 +1706,91 @@ impl MLOptimizer {
         format!("{:.2} {}", size, UNITS[unit_index])
     }
 
+    /// Resolve a configured [`MemoryBudget`] against `available_bytes`: absolute sizes are capped
+    /// at what's actually available, and `None` (no limit configured) resolves to
+    /// [`byte_size::DEFAULT_MEMORY_FRACTION`] of it. If `available_bytes` is zero -- the engine
+    /// couldn't determine how much memory exists -- warns and returns `u64::MAX` rather than
+    /// silently budgeting zero bytes.
+    fn resolve_memory_budget(
+        &self,
+        max_memory_usage: Option<MemoryBudget>,
+        available_bytes: u64,
+        warnings: &mut Vec<String>,
+    ) -> u64 {
+        if available_bytes == 0 {
+            warnings.push(
+                "could not determine available memory; running with no memory budget".to_string(),
+            );
+            return u64::MAX;
+        }
+
+        let budget = max_memory_usage.unwrap_or(MemoryBudget::Percentage(byte_size::DEFAULT_MEMORY_FRACTION));
+        let resolved = budget.resolve(available_bytes);
+        debug!(
+            "Resolved memory budget to {} out of {} available",
+            self.format_bytes(resolved),
+            self.format_bytes(available_bytes)
+        );
+        resolved
+    }
+
+    /// Budget GPU memory with [`gpu_memory_planner::GpuMemoryPlanner`]: reserve a persistent
+    /// region for model weights and a transient region for activations/workspace scratch out of
+    /// each GPU's VRAM, populate `result.resource_allocation.gpu_allocation` with the resulting
+    /// `memory_fraction`, and shrink `result.config.batch_size` when the plan doesn't fit.
+    fn apply_gpu_memory_planning(&self, system_info: &SystemInfo, result: &mut OptimizationResult) {
+        if system_info.gpu_count == 0 {
+            return;
+        }
+
+        let model_size = result.config.model_size.unwrap_or(100_000_000);
+        let sequence_length = result.config.sequence_length.unwrap_or(512) as u64;
+        let batch_size = result.config.batch_size.unwrap_or(1).max(1) as u64;
+
+        let weights_bytes = model_size * 4; // 4 bytes per parameter, same assumption as estimate_memory_usage
+        let activation_plan = memory_planner::plan_activation_memory(model_size, sequence_length, batch_size);
+        // Typical cuDNN/cuBLAS workspace reservation for convolution/matmul algorithm selection
+        const WORKSPACE_BYTES: u64 = 256 * 1024 * 1024;
+
+        let capacity_per_gpu = system_info.gpu_memory_total / system_info.gpu_count as u64;
+        let planner = gpu_memory_planner::GpuMemoryPlanner::new(capacity_per_gpu);
+        let plan = planner.plan(&[
+            gpu_memory_planner::MemoryRequest { usage: gpu_memory_planner::UsageClass::Weights, bytes: weights_bytes },
+            gpu_memory_planner::MemoryRequest {
+                usage: gpu_memory_planner::UsageClass::Activations,
+                bytes: activation_plan.peak_bytes,
+            },
+            gpu_memory_planner::MemoryRequest {
+                usage: gpu_memory_planner::UsageClass::Workspace,
+                bytes: WORKSPACE_BYTES,
+            },
+        ]);
+
+        result.warnings.extend(plan.warnings.clone());
+
+        result.resource_allocation.gpu_allocation = Some(GPUAllocation {
+            gpu_ids: (0..system_info.gpu_count).collect(),
+            memory_fraction: (plan.total_bytes() as f32 / plan.capacity_bytes as f32).min(1.0),
+            allow_growth: plan.fits(),
+            distributed_strategy: None,
+        });
+
+        if !plan.fits() {
+            let precision_bytes = 4; // fp32 activations, matching `weights_bytes`'s assumption
+            let shrunk_batch = estimate_optimal_batch_size(
+                model_size,
+                capacity_per_gpu,
+                result.config.sequence_length.unwrap_or(512),
+                precision_bytes,
+            );
+            result.warnings.push(format!(
+                "GPU memory plan overflows by {} bytes; shrinking batch size to {}",
+                plan.overflow_bytes, shrunk_batch
+            ));
+            result.config.batch_size = Some(shrunk_batch);
+        }
+    }
+
     /// Optimize using a predefined profile
     pub async fn optimize_with_profile(&self, profile: OptimizationProfile) -> Result<OptimizationResult> {
         let config = profile.to_config();
@@ -961,6 +1882,70 @@ pub fn calculate_memory_efficiency(used_memory: u64, total_memory: u64) -> f32 {
     }
 }
 
+/// Find the `/proc/mounts`-style entry whose mount point is the longest prefix of `path`,
+/// returning its `(device, fs_type)`. `/proc/mounts` isn't ordered by specificity, so a shorter,
+/// earlier-listed mount (e.g. `/`) must not shadow a more specific one mounted under it.
+fn mount_entry_for_path(mounts: &str, path: &Path) -> Option<(String, String)> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let mount_point = fields.next()?;
+            let fs_type = fields.next()?;
+            Some((device.to_string(), mount_point.to_string(), fs_type.to_string()))
+        })
+        .filter(|(_, mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(_, mount_point, _)| mount_point.len())
+        .map(|(device, _, fs_type)| (device, fs_type))
+}
+
+/// Whether `fs_type` is an overlay or network filesystem, for which the underlying block device
+/// (if any) doesn't reflect local disk performance characteristics
+fn is_network_fs(fs_type: &str) -> bool {
+    matches!(fs_type, "overlay" | "aufs" | "nfs" | "nfs4" | "cifs" | "smb3" | "9p" | "fuse.sshfs" | "glusterfs")
+}
+
+/// Extract the `/sys/block` device name from a `/proc/mounts` device field, stripping partition
+/// suffixes: `/dev/sda1` -> `sda`, `/dev/nvme0n1p1` -> `nvme0n1`. Returns `None` for device fields
+/// that don't name a real block device (`tmpfs`, `/dev/mapper/...`, etc.), since those can't be
+/// mapped to a `/sys/block` entry this way.
+fn block_device_name(device: &str) -> Option<String> {
+    let name = device.strip_prefix("/dev/")?;
+    if name.contains('/') {
+        return None; // e.g. /dev/mapper/vg-lv, not a /sys/block entry
+    }
+
+    if name.starts_with("nvme") {
+        // Namespace device names look like `nvme0n1`, with an optional `p<N>` partition suffix
+        // (`nvme0n1p1`). Only strip the suffix when it's actually a partition marker, not part of
+        // the bare namespace name.
+        if let Some(p_index) = name.rfind('p') {
+            let (namespace, partition) = name.split_at(p_index);
+            let partition_digits = &partition[1..];
+            if !partition_digits.is_empty()
+                && partition_digits.bytes().all(|b| b.is_ascii_digit())
+                && namespace.ends_with(|c: char| c.is_ascii_digit())
+            {
+                return Some(namespace.to_string());
+            }
+        }
+        return Some(name.to_string());
+    }
+
+    Some(name.trim_end_matches(|c: char| c.is_ascii_digit()).to_string())
+}
+
+/// Parse a `/sys/block/<dev>/queue/rotational` reading: `true` for HDD (`1`), `false` for
+/// non-rotational media (`0`), `None` if the content isn't one of those two values
+fn parse_rotational(contents: &str) -> Option<bool> {
+    match contents.trim() {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -973,7 +1958,7 @@ mod tests {
         assert_eq!(config.workload_type, WorkloadType::Training);
         assert!(config.use_mixed_precision);
         assert!(!config.optimize_memory);
-        assert_eq!(config.max_memory_usage, Some(0.95));
+        assert_eq!(config.max_memory_usage, Some(MemoryBudget::Percentage(0.95)));
     }
 
     #[test]
@@ -1009,4 +1994,336 @@ mod tests {
         let deserialized: FrameworkType = serde_json::from_str(&serialized).unwrap();
         assert_eq!(framework_type, deserialized);
     }
+
+    fn fp8_request_result() -> OptimizationResult {
+        OptimizationResult {
+            config: OptimizationConfig {
+                precision_mode: PrecisionMode::Fp8,
+                ..Default::default()
+            },
+            recommendations: Vec::new(),
+            estimated_performance: PerformanceEstimate {
+                throughput_estimate: 0.0,
+                memory_usage_estimate: 0,
+                training_time_estimate: None,
+                gpu_utilization_estimate: 0.0,
+                bottleneck_analysis: Vec::new(),
+                confidence_interval: None,
+            },
+            resource_allocation: ResourceAllocation {
+                cpu_allocation: CPUAllocation { worker_threads: 1, dataloader_workers: 1, cpu_affinity: None },
+                memory_allocation: MemoryAllocation {
+                    heap_size: None,
+                    cache_size: 0,
+                    buffer_size: 0,
+                    use_memory_mapping: false,
+                },
+                gpu_allocation: None,
+                storage_allocation: StorageAllocation {
+                    cache_directory: PathBuf::from("/tmp"),
+                    tmp_directory: PathBuf::from("/tmp"),
+                    prefetch_buffer: 0,
+                    use_ssd_cache: false,
+                    chunk_compression: ChunkCompression::None,
+                },
+            },
+            environment_variables: HashMap::new(),
+            command_line_args: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn system_info_with_compute_capabilities(capabilities: Vec<f32>) -> SystemInfo {
+        SystemInfo {
+            cpu_cores: 8,
+            memory_total: 16_000_000_000,
+            memory_available: 12_000_000_000,
+            gpu_count: capabilities.len() as u32,
+            gpu_memory_total: 8_000_000_000,
+            gpu_memory_available: 8_000_000_000,
+            gpu_compute_capabilities: capabilities,
+            storage_type: StorageType::NVMe,
+            network_bandwidth: None,
+            hardware_scores: HardwareScores::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fp8_is_recommended_when_every_gpu_clears_the_capability_floor() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let system_info = system_info_with_compute_capabilities(vec![8.9, 9.0]);
+        let mut result = fp8_request_result();
+
+        optimizer.apply_precision_recommendations(&system_info, &mut result);
+
+        assert_eq!(result.config.precision_mode, PrecisionMode::Fp8);
+        assert!(result.recommendations.iter().any(|r| r.category == RecommendationCategory::Compute
+            && r.title.contains("FP8")));
+    }
+
+    #[tokio::test]
+    async fn test_fp8_downgrades_to_bf16_when_a_gpu_is_below_the_capability_floor() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let system_info = system_info_with_compute_capabilities(vec![8.9, 7.5]);
+        let mut result = fp8_request_result();
+
+        optimizer.apply_precision_recommendations(&system_info, &mut result);
+
+        assert_eq!(result.config.precision_mode, PrecisionMode::Bf16);
+        assert!(result.recommendations.iter().any(|r| r.category == RecommendationCategory::Compute
+            && r.title.contains("Downgrade")));
+    }
+
+    #[tokio::test]
+    async fn test_fp8_downgrades_to_bf16_when_no_gpu_capability_is_known() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let system_info = system_info_with_compute_capabilities(vec![]);
+        let mut result = fp8_request_result();
+
+        optimizer.apply_precision_recommendations(&system_info, &mut result);
+
+        assert_eq!(result.config.precision_mode, PrecisionMode::Bf16);
+    }
+
+    fn zero_request_result(model_size: u64) -> OptimizationResult {
+        OptimizationResult {
+            config: OptimizationConfig {
+                model_size: Some(model_size),
+                ..Default::default()
+            },
+            ..fp8_request_result()
+        }
+    }
+
+    fn system_info_for_zero(gpu_count: u32, gpu_memory_total: u64, memory_available: u64, storage_type: StorageType) -> SystemInfo {
+        SystemInfo {
+            cpu_cores: 8,
+            memory_total: memory_available,
+            memory_available,
+            gpu_count,
+            gpu_memory_total,
+            gpu_memory_available: gpu_memory_total,
+            gpu_compute_capabilities: vec![8.9; gpu_count as usize],
+            storage_type,
+            network_bandwidth: None,
+            hardware_scores: HardwareScores::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zero_is_not_recommended_when_the_model_already_fits_a_single_gpu() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let system_info = system_info_for_zero(1, 80_000_000_000, 64_000_000_000, StorageType::NVMe);
+        let mut result = zero_request_result(1_000_000_000); // 16GB unpartitioned, fits comfortably
+
+        optimizer.apply_zero_recommendations(&system_info, &mut result);
+
+        assert_eq!(result.config.zero_stage, None);
+        assert_eq!(result.config.offload_target, None);
+    }
+
+    #[tokio::test]
+    async fn test_zero_picks_a_sharding_stage_when_the_model_does_not_fit_a_single_gpu() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let system_info = system_info_for_zero(8, 8_000_000_000 * 8, 64_000_000_000, StorageType::NVMe);
+        let mut result = zero_request_result(7_000_000_000); // 112GB unpartitioned, doesn't fit one 8GB GPU
+
+        optimizer.apply_zero_recommendations(&system_info, &mut result);
+
+        assert!(result.config.zero_stage.is_some());
+        assert_eq!(result.config.offload_target, Some(OffloadTarget::None));
+        assert!(result.recommendations.iter().any(|r| matches!(r.implementation, Implementation::ConfigFile { .. })));
+        assert!(result.environment_variables.contains_key("ZERO_STAGE"));
+    }
+
+    #[tokio::test]
+    async fn test_zero_warns_when_nvme_offload_is_chosen_but_storage_is_not_nvme() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        // One small GPU and no host RAM to offload into, so even stage 3 needs NVMe offload.
+        let system_info = system_info_for_zero(1, 1_000_000_000, 0, StorageType::HDD);
+        let mut result = zero_request_result(100_000_000_000);
+
+        optimizer.apply_zero_recommendations(&system_info, &mut result);
+
+        assert_eq!(result.config.offload_target, Some(OffloadTarget::Nvme));
+        assert!(result.warnings.iter().any(|w| w.contains("NVMe")));
+    }
+
+    fn test_launch_spec() -> ClusterLaunchSpec {
+        ClusterLaunchSpec {
+            nodes: 2,
+            gpus_per_node: 4,
+            node_rank: 1,
+            master_addr: "10.0.0.1".to_string(),
+            master_port: 29500,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_launch_command_uses_torchrun_for_pytorch() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let mut result = fp8_request_result();
+        result.config.target_framework = Some(FrameworkType::PyTorch);
+        let spec = test_launch_spec();
+
+        let command = optimizer.generate_launch_command(&mut result, &spec);
+
+        assert!(command.starts_with("torchrun"));
+        assert!(command.contains("--nnodes=2"));
+        assert!(command.contains("--node_rank=1"));
+        assert_eq!(result.environment_variables.get("WORLD_SIZE"), Some(&"8".to_string()));
+        assert_eq!(result.environment_variables.get("RANK"), Some(&"4".to_string()));
+        let gpu_allocation = result.resource_allocation.gpu_allocation.as_ref().unwrap();
+        assert_eq!(gpu_allocation.distributed_strategy, Some("DDP".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_generate_launch_command_prefers_fsdp_when_zero_is_selected() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let mut result = fp8_request_result();
+        result.config.target_framework = Some(FrameworkType::PyTorch);
+        result.config.zero_stage = Some(3);
+        let spec = test_launch_spec();
+
+        optimizer.generate_launch_command(&mut result, &spec);
+
+        let gpu_allocation = result.resource_allocation.gpu_allocation.as_ref().unwrap();
+        assert_eq!(gpu_allocation.distributed_strategy, Some("FSDP".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_generate_launch_command_uses_env_vars_for_tensorflow() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let mut result = fp8_request_result();
+        result.config.target_framework = Some(FrameworkType::TensorFlow);
+        let spec = test_launch_spec();
+
+        let command = optimizer.generate_launch_command(&mut result, &spec);
+
+        assert!(command.contains("WORLD_SIZE=8"));
+        assert!(command.contains("python train.py"));
+        let gpu_allocation = result.resource_allocation.gpu_allocation.as_ref().unwrap();
+        assert_eq!(gpu_allocation.distributed_strategy, Some("MultiWorkerMirroredStrategy".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_generate_launch_command_uses_existing_gpu_ids() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let mut result = fp8_request_result();
+        result.resource_allocation.gpu_allocation = Some(GPUAllocation {
+            gpu_ids: vec![2, 3],
+            memory_fraction: 0.8,
+            allow_growth: true,
+            distributed_strategy: None,
+        });
+        let spec = test_launch_spec();
+
+        optimizer.generate_launch_command(&mut result, &spec);
+
+        assert_eq!(result.environment_variables.get("CUDA_VISIBLE_DEVICES"), Some(&"2,3".to_string()));
+    }
+
+    fn batch_autotune_result(model_size: u64, sequence_length: u32, batch_size: Option<u32>) -> OptimizationResult {
+        OptimizationResult {
+            config: OptimizationConfig {
+                model_size: Some(model_size),
+                sequence_length: Some(sequence_length),
+                batch_size,
+                max_memory_usage: Some(MemoryBudget::Percentage(0.8)),
+                ..Default::default()
+            },
+            ..fp8_request_result()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_autotune_batch_size_finds_the_largest_fitting_batch() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let mut result = batch_autotune_result(100_000_000, 512, None);
+
+        optimizer.autotune_batch_size(&mut result).await.unwrap();
+
+        let fitting = result.config.batch_size.unwrap();
+        assert!(fitting >= 1);
+        let budget_bytes = {
+            let system_info = optimizer.system_info.read().await;
+            (system_info.memory_available as f64 * 0.8) as u64
+        };
+        assert!(optimizer.estimate_memory_usage(fitting, &result.config).unwrap() <= budget_bytes);
+        assert!(optimizer.estimate_memory_usage(fitting + 1, &result.config).unwrap() > budget_bytes);
+        assert!(result.estimated_performance.bottleneck_analysis.iter().any(|s| s.contains("per sample")));
+    }
+
+    #[tokio::test]
+    async fn test_autotune_batch_size_recommends_gradient_accumulation_when_desired_batch_does_not_fit() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let mut result = batch_autotune_result(100_000_000, 512, Some(u32::MAX));
+
+        optimizer.autotune_batch_size(&mut result).await.unwrap();
+
+        assert!(result.config.batch_size.unwrap() < u32::MAX);
+        assert!(result.recommendations.iter().any(|r| r.category == RecommendationCategory::BatchSize
+            && r.title.contains("gradient accumulation")));
+    }
+
+    #[tokio::test]
+    async fn test_autotune_batch_size_reuses_the_cached_probe_for_the_same_key() {
+        let optimizer = MLOptimizer::new().await.unwrap();
+        let mut first = batch_autotune_result(50_000_000, 256, None);
+        optimizer.autotune_batch_size(&mut first).await.unwrap();
+
+        let mut second = batch_autotune_result(50_000_000, 256, None);
+        optimizer.autotune_batch_size(&mut second).await.unwrap();
+
+        assert_eq!(first.config.batch_size, second.config.batch_size);
+        assert_eq!(optimizer.batch_probe_cache.read().await.len(), 1);
+    }
+
+    #[test]
+    fn test_block_device_name_strips_sata_partition_suffix() {
+        assert_eq!(block_device_name("/dev/sda1").as_deref(), Some("sda"));
+        assert_eq!(block_device_name("/dev/sda").as_deref(), Some("sda"));
+    }
+
+    #[test]
+    fn test_block_device_name_strips_nvme_partition_suffix_but_not_namespace() {
+        assert_eq!(block_device_name("/dev/nvme0n1p1").as_deref(), Some("nvme0n1"));
+        assert_eq!(block_device_name("/dev/nvme0n1").as_deref(), Some("nvme0n1"));
+    }
+
+    #[test]
+    fn test_block_device_name_rejects_unmappable_devices() {
+        assert_eq!(block_device_name("tmpfs"), None);
+        assert_eq!(block_device_name("/dev/mapper/vg-lv"), None);
+    }
+
+    #[test]
+    fn test_parse_rotational() {
+        assert_eq!(parse_rotational("1\n"), Some(true));
+        assert_eq!(parse_rotational("0\n"), Some(false));
+        assert_eq!(parse_rotational("garbage"), None);
+    }
+
+    #[test]
+    fn test_is_network_fs() {
+        assert!(is_network_fs("overlay"));
+        assert!(is_network_fs("nfs4"));
+        assert!(!is_network_fs("ext4"));
+        assert!(!is_network_fs("xfs"));
+    }
+
+    #[test]
+    fn test_mount_entry_for_path_picks_the_longest_matching_mount_point() {
+        let mounts = "/dev/sda1 / ext4 rw 0 0\n\
+                      /dev/nvme0n1p2 /tmp ext4 rw 0 0\n\
+                      overlay /tmp/ml_cache overlay rw 0 0\n";
+
+        let (device, fs_type) = mount_entry_for_path(mounts, Path::new("/tmp/ml_cache")).unwrap();
+        assert_eq!(device, "overlay");
+        assert_eq!(fs_type, "overlay");
+
+        let (device, fs_type) = mount_entry_for_path(mounts, Path::new("/tmp/other")).unwrap();
+        assert_eq!(device, "/dev/nvme0n1p2");
+        assert_eq!(fs_type, "ext4");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,228 @@
+//! Tensor-liveness memory-reuse planning
+//!
+//! Models a workload's intermediate activations as an ordered list of tensors, each live over a
+//! `[first_use, last_use]` interval of op indices, and computes the peak memory a reuse-aware
+//! allocator needs rather than the sum of every tensor ever materialized. This is the same idea
+//! inference memory-optimizing passes use: two tensors whose lifetimes don't overlap can share one
+//! buffer.
+
+/// A single intermediate tensor, live over the inclusive op-index interval
+/// `[first_use, last_use]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tensor {
+    pub name: String,
+    pub size_bytes: u64,
+    pub first_use: u32,
+    pub last_use: u32,
+}
+
+/// The result of [`MemoryReusePlanner::plan`]: the realistic reuse-aware peak alongside the naive
+/// sum, so callers can report the recovered savings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryPlan {
+    /// Peak memory with buffer reuse: the total size of the slots the greedy allocator ended up
+    /// needing
+    pub peak_bytes: u64,
+    /// What a reuse-naive estimate (sum of every tensor's size) would have reported
+    pub naive_sum_bytes: u64,
+    pub tensor_count: usize,
+}
+
+impl MemoryPlan {
+    /// Bytes reclaimed by reuse versus the naive sum-of-all-tensors estimate
+    pub fn savings_bytes(&self) -> u64 {
+        self.naive_sum_bytes.saturating_sub(self.peak_bytes)
+    }
+}
+
+/// A fixed-size memory slot a [`Tensor`] can be assigned into, tracking the liveness of its
+/// current occupant
+struct Slot {
+    size_bytes: u64,
+    occupant_last_use: u32,
+}
+
+/// Greedy, liveness-aware memory planner: a simplified analogue of the buffer-reuse passes
+/// inference runtimes apply to a compute graph
+pub struct MemoryReusePlanner;
+
+impl MemoryReusePlanner {
+    /// Plan slot assignment for `tensors`, processed in `first_use` order. Each tensor is given
+    /// the smallest free slot at least as large as it needs whose previous occupant is already
+    /// dead (`occupant_last_use < tensor.first_use`); a new slot is allocated only when no
+    /// existing slot fits. The resulting peak is the total size of all slots ever allocated.
+    pub fn plan(tensors: &[Tensor]) -> MemoryPlan {
+        let naive_sum_bytes = tensors.iter().map(|t| t.size_bytes).sum();
+
+        let mut order: Vec<&Tensor> = tensors.iter().collect();
+        order.sort_by_key(|t| t.first_use);
+
+        let mut slots: Vec<Slot> = Vec::new();
+        for tensor in order {
+            let best_fit = slots
+                .iter()
+                .enumerate()
+                .filter(|(_, slot)| slot.occupant_last_use < tensor.first_use && slot.size_bytes >= tensor.size_bytes)
+                .min_by_key(|(_, slot)| slot.size_bytes)
+                .map(|(index, _)| index);
+
+            match best_fit {
+                Some(index) => slots[index].occupant_last_use = tensor.last_use,
+                None => slots.push(Slot { size_bytes: tensor.size_bytes, occupant_last_use: tensor.last_use }),
+            }
+        }
+
+        MemoryPlan { peak_bytes: slots.iter().map(|s| s.size_bytes).sum(), naive_sum_bytes, tensor_count: tensors.len() }
+    }
+}
+
+/// Hidden dimension assumed for the synthetic transformer activation graph, matching the
+/// dimension already assumed by the rest of this crate's memory estimates
+const HIDDEN_SIZE: u64 = 768;
+
+/// Parameters per transformer block as a multiple of `HIDDEN_SIZE^2`: 4H^2 for the attention
+/// QKV+output projections, 8H^2 for the 4H-wide FFN's two projections
+const PARAMS_PER_LAYER_FACTOR: u64 = 12;
+
+/// Batch size substituted for an unknown/dynamic batch dimension so tensor shapes can be
+/// materialized concretely; the resulting plan scales linearly back to the real batch size, since
+/// batch is a common outer factor of every activation tensor here
+const FAKE_BATCH_SIZE: u64 = 3;
+
+/// Bytes per activation element (fp32)
+const BYTES_PER_ELEMENT: u64 = 4;
+
+/// Estimate how many transformer blocks a model of `model_parameters` has, assuming
+/// [`HIDDEN_SIZE`]-wide layers
+fn estimate_num_layers(model_parameters: u64) -> u32 {
+    let params_per_layer = PARAMS_PER_LAYER_FACTOR * HIDDEN_SIZE * HIDDEN_SIZE;
+    (model_parameters / params_per_layer).max(1) as u32
+}
+
+/// Build the synthetic per-layer activation tensors for a `num_layers`-deep transformer forward
+/// pass at `batch` x `seq_len` x [`HIDDEN_SIZE`]. Each block is 4 ops: the QKV projection (3H
+/// wide) feeds attention, whose output must survive until the block's final residual add; the
+/// FFN's 4H-wide intermediate is consumed immediately by the FFN output projection; and the
+/// block's output is the next block's input, so it stays live until that block's first op (or,
+/// for the last layer, just past its own last op).
+fn transformer_activation_tensors(num_layers: u32, seq_len: u64, batch: u64) -> Vec<Tensor> {
+    const OPS_PER_LAYER: u32 = 4;
+    let unit_bytes = batch * seq_len * HIDDEN_SIZE * BYTES_PER_ELEMENT;
+
+    let mut tensors = Vec::with_capacity(num_layers as usize * 4);
+    for layer in 0..num_layers {
+        let base = layer * OPS_PER_LAYER;
+        let next_layer_first_op = base + OPS_PER_LAYER;
+
+        tensors.push(Tensor {
+            name: format!("layer{layer}_qkv"),
+            size_bytes: unit_bytes * 3,
+            first_use: base,
+            last_use: base + 1,
+        });
+        tensors.push(Tensor {
+            name: format!("layer{layer}_attn_out"),
+            size_bytes: unit_bytes,
+            first_use: base + 1,
+            last_use: base + 3,
+        });
+        tensors.push(Tensor {
+            name: format!("layer{layer}_ffn_intermediate"),
+            size_bytes: unit_bytes * 4,
+            first_use: base + 2,
+            last_use: base + 3,
+        });
+        tensors.push(Tensor {
+            name: format!("layer{layer}_block_output"),
+            size_bytes: unit_bytes,
+            first_use: base + 3,
+            last_use: next_layer_first_op,
+        });
+    }
+    tensors
+}
+
+/// Plan activation memory for a `model_parameters`-sized transformer at `sequence_length` x
+/// `batch_size`. Materializes tensor shapes at [`FAKE_BATCH_SIZE`] (handling an unknown/dynamic
+/// batch dimension the same way a real one is handled) and scales the resulting plan linearly to
+/// `batch_size`, rather than re-running [`MemoryReusePlanner::plan`] per candidate batch size.
+pub fn plan_activation_memory(model_parameters: u64, sequence_length: u64, batch_size: u64) -> MemoryPlan {
+    let num_layers = estimate_num_layers(model_parameters);
+    let tensors = transformer_activation_tensors(num_layers, sequence_length, FAKE_BATCH_SIZE);
+    let fake_plan = MemoryReusePlanner::plan(&tensors);
+
+    let scale = batch_size as f64 / FAKE_BATCH_SIZE as f64;
+    MemoryPlan {
+        peak_bytes: (fake_plan.peak_bytes as f64 * scale) as u64,
+        naive_sum_bytes: (fake_plan.naive_sum_bytes as f64 * scale) as u64,
+        tensor_count: fake_plan.tensor_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_reuses_slots_for_non_overlapping_tensors() {
+        // Two tensors with disjoint liveness should share one slot instead of needing two.
+        let tensors = vec![
+            Tensor { name: "a".to_string(), size_bytes: 100, first_use: 0, last_use: 1 },
+            Tensor { name: "b".to_string(), size_bytes: 100, first_use: 2, last_use: 3 },
+        ];
+
+        let plan = MemoryReusePlanner::plan(&tensors);
+
+        assert_eq!(plan.naive_sum_bytes, 200);
+        assert_eq!(plan.peak_bytes, 100);
+        assert_eq!(plan.savings_bytes(), 100);
+    }
+
+    #[test]
+    fn test_plan_allocates_separate_slots_for_overlapping_tensors() {
+        let tensors = vec![
+            Tensor { name: "a".to_string(), size_bytes: 100, first_use: 0, last_use: 2 },
+            Tensor { name: "b".to_string(), size_bytes: 100, first_use: 1, last_use: 3 },
+        ];
+
+        let plan = MemoryReusePlanner::plan(&tensors);
+
+        assert_eq!(plan.peak_bytes, 200);
+        assert_eq!(plan.savings_bytes(), 0);
+    }
+
+    #[test]
+    fn test_plan_picks_the_smallest_fitting_free_slot() {
+        // A tiny tensor that fits two dead slots should take the smaller one, leaving the larger
+        // slot free for a later tensor that actually needs it.
+        let tensors = vec![
+            Tensor { name: "small".to_string(), size_bytes: 10, first_use: 0, last_use: 0 },
+            Tensor { name: "large".to_string(), size_bytes: 1000, first_use: 1, last_use: 1 },
+            Tensor { name: "tiny".to_string(), size_bytes: 5, first_use: 2, last_use: 2 },
+            Tensor { name: "big".to_string(), size_bytes: 900, first_use: 2, last_use: 2 },
+        ];
+
+        let plan = MemoryReusePlanner::plan(&tensors);
+
+        // Without best-fit reuse this would need 10 + 1000 + 5 + 900 = 1915 bytes across 4 slots;
+        // best-fit reuse lets `tiny` take the dead `small` slot and `big` take the dead `large`
+        // slot, so only the original two slots (10 + 1000) are ever needed.
+        assert_eq!(plan.peak_bytes, 1010);
+    }
+
+    #[test]
+    fn test_plan_activation_memory_scales_linearly_with_batch_size() {
+        let small = plan_activation_memory(100_000_000, 512, 3);
+        let large = plan_activation_memory(100_000_000, 512, 12);
+
+        assert_eq!(large.peak_bytes, small.peak_bytes * 4);
+        assert_eq!(large.naive_sum_bytes, small.naive_sum_bytes * 4);
+    }
+
+    #[test]
+    fn test_plan_activation_memory_recovers_savings_over_the_naive_sum() {
+        let plan = plan_activation_memory(1_000_000_000, 512, 8);
+        assert!(plan.savings_bytes() > 0);
+        assert!(plan.peak_bytes < plan.naive_sum_bytes);
+    }
+}
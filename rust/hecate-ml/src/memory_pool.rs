@@ -0,0 +1,244 @@
+//! Reservation-based accounting of competing memory consumers (dataloader buffers, activation
+//! cache, framework arenas, ...) against a shared budget, plus an ordered remediation plan for
+//! when those reservations don't fit.
+//!
+//! [`crate::MLOptimizer::validate_recommendations`] used to compare a single lump memory
+//! estimate against a single limit and, on overflow, only ever suggest a smaller batch size. A
+//! [`MemoryPool`] tracks each consumer's reservation individually so an overflow can be
+//! attributed to whoever caused it, and [`plan_spill`] turns that overflow into a tiered,
+//! ordered list of remediations instead of one blunt warning.
+
+use std::fmt;
+
+/// A named memory consumer tracked against a [`MemoryPool`]'s budget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Consumer {
+    pub name: String,
+    pub bytes: u64,
+}
+
+/// A granted reservation from [`MemoryPool::try_reserve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reservation {
+    pub bytes: u64,
+}
+
+/// Returned by [`MemoryPool::try_reserve`] when granting the request would exceed the pool's
+/// remaining budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolExceeded {
+    pub requested_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl fmt::Display for PoolExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "requested {} bytes but only {} bytes remain in the pool", self.requested_bytes, self.available_bytes)
+    }
+}
+
+/// Running accounting of competing memory consumers against a shared budget.
+pub trait MemoryPool {
+    /// Reserve `bytes` for `name`, failing with [`PoolExceeded`] rather than overcommitting if
+    /// the pool's remaining budget can't cover it.
+    fn try_reserve(&mut self, name: &str, bytes: u64) -> Result<Reservation, PoolExceeded>;
+    /// Shrink `name`'s existing reservation down to at most `bytes`, returning the bytes
+    /// reclaimed back to the pool. A no-op (returns 0) if `name` holds no reservation.
+    fn shrink(&mut self, name: &str, bytes: u64) -> u64;
+    fn budget_bytes(&self) -> u64;
+    fn reserved_bytes(&self) -> u64;
+    fn consumers(&self) -> &[Consumer];
+}
+
+/// A [`MemoryPool`] that grants reservations first-come-first-served against a fixed budget.
+#[derive(Debug, Clone)]
+pub struct GreedyMemoryPool {
+    budget_bytes: u64,
+    consumers: Vec<Consumer>,
+}
+
+impl GreedyMemoryPool {
+    pub fn new(budget_bytes: u64) -> Self {
+        Self { budget_bytes, consumers: Vec::new() }
+    }
+}
+
+impl MemoryPool for GreedyMemoryPool {
+    fn try_reserve(&mut self, name: &str, bytes: u64) -> Result<Reservation, PoolExceeded> {
+        let available = self.budget_bytes.saturating_sub(self.reserved_bytes());
+        if bytes > available {
+            return Err(PoolExceeded { requested_bytes: bytes, available_bytes: available });
+        }
+        self.consumers.push(Consumer { name: name.to_string(), bytes });
+        Ok(Reservation { bytes })
+    }
+
+    fn shrink(&mut self, name: &str, bytes: u64) -> u64 {
+        match self.consumers.iter_mut().find(|c| c.name == name) {
+            Some(consumer) => {
+                let shrunk_to = consumer.bytes.min(bytes);
+                let reclaimed = consumer.bytes - shrunk_to;
+                consumer.bytes = shrunk_to;
+                reclaimed
+            }
+            None => 0,
+        }
+    }
+
+    fn budget_bytes(&self) -> u64 {
+        self.budget_bytes
+    }
+
+    fn reserved_bytes(&self) -> u64 {
+        self.consumers.iter().map(|c| c.bytes).sum()
+    }
+
+    fn consumers(&self) -> &[Consumer] {
+        &self.consumers
+    }
+}
+
+/// A remediation tier for reclaiming memory, ordered from lowest to highest added compute
+/// overhead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpillTier {
+    GradientCheckpointing,
+    CpuOffloadOptimizerState,
+    ActivationRecomputation,
+}
+
+impl SpillTier {
+    /// Rough training-step slowdown each tier costs; only used to order tiers cheapest-first; not
+    /// meant as a precise prediction.
+    pub fn compute_overhead_pct(self) -> f64 {
+        match self {
+            SpillTier::GradientCheckpointing => 15.0,
+            SpillTier::CpuOffloadOptimizerState => 25.0,
+            SpillTier::ActivationRecomputation => 40.0,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            SpillTier::GradientCheckpointing => "Enable gradient checkpointing",
+            SpillTier::CpuOffloadOptimizerState => "Offload optimizer state to host memory",
+            SpillTier::ActivationRecomputation => "Recompute activations during the backward pass",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            SpillTier::GradientCheckpointing => {
+                "Checkpoint activations at layer boundaries instead of caching every intermediate \
+                 tensor for the backward pass"
+            }
+            SpillTier::CpuOffloadOptimizerState => {
+                "Move optimizer state (momentum, variance, and the fp32 master weights) to host \
+                 memory between optimizer steps"
+            }
+            SpillTier::ActivationRecomputation => {
+                "Drop the remaining cached activations entirely and recompute them from the \
+                 nearest checkpoint during backward"
+            }
+        }
+    }
+}
+
+/// One step of a [`plan_spill`] remediation plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpillStep {
+    pub tier: SpillTier,
+    pub reclaimed_bytes: u64,
+}
+
+/// Greedily pick spill tiers, cheapest compute overhead first, until `shortfall_bytes` has been
+/// reclaimed or every tier is exhausted. `model_param_bytes` is the parameter memory (bytes, not
+/// param count); `activation_peak_bytes` is the reuse-aware peak from
+/// [`crate::memory_planner::plan_activation_memory`].
+pub fn plan_spill(shortfall_bytes: u64, model_param_bytes: u64, activation_peak_bytes: u64) -> Vec<SpillStep> {
+    // Gradient checkpointing keeps only a handful of checkpointed activations per layer, giving
+    // back roughly 60% of peak activation memory; full recomputation claws back the rest.
+    let checkpointing_reclaim = (activation_peak_bytes as f64 * 0.6) as u64;
+    let recomputation_reclaim = activation_peak_bytes.saturating_sub(checkpointing_reclaim);
+    // Adam's momentum + variance (2x) plus the fp32 master weight copy (1x), all relocatable to
+    // host memory once they're no longer needed on-device for the optimizer step itself.
+    let offload_reclaim = 3 * model_param_bytes;
+
+    let tiers = [
+        (SpillTier::GradientCheckpointing, checkpointing_reclaim),
+        (SpillTier::CpuOffloadOptimizerState, offload_reclaim),
+        (SpillTier::ActivationRecomputation, recomputation_reclaim),
+    ];
+
+    let mut remaining = shortfall_bytes;
+    let mut steps = Vec::new();
+    for (tier, reclaim) in tiers {
+        if remaining == 0 || reclaim == 0 {
+            continue;
+        }
+        steps.push(SpillStep { tier, reclaimed_bytes: reclaim });
+        remaining = remaining.saturating_sub(reclaim);
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_greedy_pool_grants_reservations_within_budget() {
+        let mut pool = GreedyMemoryPool::new(1000);
+        assert_eq!(pool.try_reserve("a", 400).unwrap().bytes, 400);
+        assert_eq!(pool.try_reserve("b", 500).unwrap().bytes, 500);
+        assert_eq!(pool.reserved_bytes(), 900);
+    }
+
+    #[test]
+    fn test_greedy_pool_rejects_a_reservation_that_would_overflow_the_budget() {
+        let mut pool = GreedyMemoryPool::new(1000);
+        pool.try_reserve("a", 800).unwrap();
+        let err = pool.try_reserve("b", 300).unwrap_err();
+        assert_eq!(err.requested_bytes, 300);
+        assert_eq!(err.available_bytes, 200);
+        // The rejected reservation must not be recorded
+        assert_eq!(pool.reserved_bytes(), 800);
+    }
+
+    #[test]
+    fn test_greedy_pool_shrink_reclaims_bytes_and_is_idempotent_for_unknown_consumers() {
+        let mut pool = GreedyMemoryPool::new(1000);
+        pool.try_reserve("a", 800).unwrap();
+        assert_eq!(pool.shrink("a", 300), 500);
+        assert_eq!(pool.reserved_bytes(), 300);
+        assert_eq!(pool.shrink("nonexistent", 0), 0);
+    }
+
+    #[test]
+    fn test_plan_spill_stops_once_the_shortfall_is_reclaimed() {
+        let steps = plan_spill(100, 1_000_000, 1_000_000);
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].tier, SpillTier::GradientCheckpointing);
+    }
+
+    #[test]
+    fn test_plan_spill_escalates_through_every_tier_for_a_large_shortfall() {
+        let steps = plan_spill(u64::MAX / 2, 1_000_000, 1_000_000);
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].tier, SpillTier::GradientCheckpointing);
+        assert_eq!(steps[1].tier, SpillTier::CpuOffloadOptimizerState);
+        assert_eq!(steps[2].tier, SpillTier::ActivationRecomputation);
+    }
+
+    #[test]
+    fn test_plan_spill_orders_tiers_by_increasing_compute_overhead() {
+        let steps = plan_spill(u64::MAX / 2, 1_000_000, 1_000_000);
+        let overheads: Vec<f64> = steps.iter().map(|s| s.tier.compute_overhead_pct()).collect();
+        assert!(overheads.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_plan_spill_is_empty_when_there_is_no_shortfall() {
+        assert!(plan_spill(0, 1_000_000, 1_000_000).is_empty());
+    }
+}
@@ -0,0 +1,330 @@
+//! Multi-format export of a profiler's metrics history
+//!
+//! [`Profiler::export_metrics`](crate::profiling::Profiler::export_metrics) only ever wrote
+//! pretty JSON to a file. [`write_metrics`] adds three more formats behind the same
+//! [`MetricsFormat`] switch so a caller can pick whichever suits the downstream consumer: CSV for
+//! spreadsheets, Markdown tables for a human-readable report, and a compact self-describing
+//! binary encoding for low-overhead streaming to a collector. The binary format tags every value
+//! with a one-byte type and a length prefix, so a reader built against an older field set can
+//! skip fields it doesn't recognize instead of breaking.
+
+use crate::error::{MLError, Result};
+use crate::profiling::ProfilingMetrics;
+use std::io::Write;
+
+/// Output format for [`write_metrics`] / [`crate::profiling::Profiler::export`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsFormat {
+    Json,
+    Csv,
+    Markdown,
+    Binary,
+}
+
+/// Write `metrics` to `writer` in the given `format`.
+pub fn write_metrics<W: Write>(metrics: &[ProfilingMetrics], format: MetricsFormat, mut writer: W) -> Result<()> {
+    match format {
+        MetricsFormat::Json => write_json(metrics, &mut writer),
+        MetricsFormat::Csv => write_csv(metrics, &mut writer),
+        MetricsFormat::Markdown => write_markdown(metrics, &mut writer),
+        MetricsFormat::Binary => write_binary(metrics, &mut writer),
+    }
+}
+
+fn write_json<W: Write>(metrics: &[ProfilingMetrics], writer: &mut W) -> Result<()> {
+    serde_json::to_writer_pretty(writer, metrics).map_err(MLError::SerializationError)
+}
+
+/// Summary columns shared by the CSV and Markdown renderers. Per-GPU vectors are joined with
+/// `;` rather than getting one column per GPU, since the GPU count can vary sample to sample.
+const SUMMARY_COLUMNS: [&str; 7] = [
+    "timestamp",
+    "cpu_utilization",
+    "memory_usage",
+    "io_read_bytes",
+    "io_write_bytes",
+    "gpu_utilization",
+    "gpu_memory_usage",
+];
+
+fn summary_row(m: &ProfilingMetrics) -> [String; 7] {
+    [
+        m.timestamp.to_string(),
+        m.cpu_utilization.to_string(),
+        m.memory_usage.to_string(),
+        m.io_read_bytes.to_string(),
+        m.io_write_bytes.to_string(),
+        m.gpu_utilization.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(";"),
+        m.gpu_memory_usage.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(";"),
+    ]
+}
+
+fn write_csv<W: Write>(metrics: &[ProfilingMetrics], writer: &mut W) -> Result<()> {
+    writeln!(writer, "{}", SUMMARY_COLUMNS.join(",")).map_err(MLError::IoError)?;
+    for m in metrics {
+        writeln!(writer, "{}", summary_row(m).join(",")).map_err(MLError::IoError)?;
+    }
+    Ok(())
+}
+
+fn write_markdown<W: Write>(metrics: &[ProfilingMetrics], writer: &mut W) -> Result<()> {
+    writeln!(writer, "| {} |", SUMMARY_COLUMNS.join(" | ")).map_err(MLError::IoError)?;
+    writeln!(writer, "|{}|", "---|".repeat(SUMMARY_COLUMNS.len())).map_err(MLError::IoError)?;
+    for m in metrics {
+        writeln!(writer, "| {} |", summary_row(m).join(" | ")).map_err(MLError::IoError)?;
+    }
+    Ok(())
+}
+
+/// 4-byte magic prefix identifying the binary format and its version, so a reader can fail fast
+/// on a file that isn't one of these at all rather than misparsing it.
+const BINARY_MAGIC: &[u8; 4] = b"HBM1";
+
+/// One-byte type tags for [`FieldValue`]. Unrecognized tags are still skippable, since every
+/// field carries its own byte length regardless of tag.
+const TAG_U64: u8 = 0;
+const TAG_F32: u8 = 1;
+const TAG_U32_ARRAY: u8 = 2;
+const TAG_F32_ARRAY: u8 = 3;
+const TAG_U64_ARRAY: u8 = 4;
+
+fn write_field_header<W: Write>(writer: &mut W, name: &str, tag: u8, value_len: u32) -> Result<()> {
+    writer.write_all(&[name.len() as u8]).map_err(MLError::IoError)?;
+    writer.write_all(name.as_bytes()).map_err(MLError::IoError)?;
+    writer.write_all(&[tag]).map_err(MLError::IoError)?;
+    writer.write_all(&value_len.to_le_bytes()).map_err(MLError::IoError)?;
+    Ok(())
+}
+
+fn write_u64_field<W: Write>(writer: &mut W, name: &str, value: u64) -> Result<()> {
+    write_field_header(writer, name, TAG_U64, 8)?;
+    writer.write_all(&value.to_le_bytes()).map_err(MLError::IoError)
+}
+
+fn write_f32_field<W: Write>(writer: &mut W, name: &str, value: f32) -> Result<()> {
+    write_field_header(writer, name, TAG_F32, 4)?;
+    writer.write_all(&value.to_le_bytes()).map_err(MLError::IoError)
+}
+
+fn write_u32_array_field<W: Write>(writer: &mut W, name: &str, values: &[u32]) -> Result<()> {
+    write_field_header(writer, name, TAG_U32_ARRAY, (values.len() * 4) as u32)?;
+    for v in values {
+        writer.write_all(&v.to_le_bytes()).map_err(MLError::IoError)?;
+    }
+    Ok(())
+}
+
+fn write_f32_array_field<W: Write>(writer: &mut W, name: &str, values: &[f32]) -> Result<()> {
+    write_field_header(writer, name, TAG_F32_ARRAY, (values.len() * 4) as u32)?;
+    for v in values {
+        writer.write_all(&v.to_le_bytes()).map_err(MLError::IoError)?;
+    }
+    Ok(())
+}
+
+fn write_u64_array_field<W: Write>(writer: &mut W, name: &str, values: &[u64]) -> Result<()> {
+    write_field_header(writer, name, TAG_U64_ARRAY, (values.len() * 8) as u32)?;
+    for v in values {
+        writer.write_all(&v.to_le_bytes()).map_err(MLError::IoError)?;
+    }
+    Ok(())
+}
+
+fn write_binary<W: Write>(metrics: &[ProfilingMetrics], writer: &mut W) -> Result<()> {
+    writer.write_all(BINARY_MAGIC).map_err(MLError::IoError)?;
+    writer.write_all(&(metrics.len() as u32).to_le_bytes()).map_err(MLError::IoError)?;
+
+    for m in metrics {
+        const FIELD_COUNT: u16 = 8;
+        writer.write_all(&FIELD_COUNT.to_le_bytes()).map_err(MLError::IoError)?;
+        write_u64_field(writer, "timestamp", m.timestamp)?;
+        write_f32_field(writer, "cpu_utilization", m.cpu_utilization)?;
+        write_u64_field(writer, "memory_usage", m.memory_usage)?;
+        write_u64_field(writer, "io_read_bytes", m.io_read_bytes)?;
+        write_u64_field(writer, "io_write_bytes", m.io_write_bytes)?;
+        write_u64_field(writer, "network_rx_bytes", m.network_rx_bytes)?;
+        write_u64_field(writer, "network_tx_bytes", m.network_tx_bytes)?;
+        write_f32_array_field(writer, "gpu_utilization", &m.gpu_utilization)?;
+        write_u64_array_field(writer, "gpu_memory_usage", &m.gpu_memory_usage)?;
+        // NOTE: field count above must track the number of write_*_field calls in this block.
+    }
+    Ok(())
+}
+
+/// A single decoded field from [`read_binary`]. `Unknown` preserves the raw bytes of a field
+/// whose type tag this reader doesn't recognize, so callers can skip or forward it rather than
+/// erroring out — the forward-compatibility [`write_binary`] is designed for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    U64(u64),
+    F32(f32),
+    U32Array(Vec<u32>),
+    F32Array(Vec<f32>),
+    U64Array(Vec<u64>),
+    Unknown(u8, Vec<u8>),
+}
+
+pub type DecodedRecord = Vec<(String, FieldValue)>;
+
+/// Decode a [`write_binary`]-encoded buffer back into its records, skipping any field whose type
+/// tag isn't one of the known ones above (surfacing it as [`FieldValue::Unknown`]) rather than
+/// failing the whole decode.
+pub fn read_binary(bytes: &[u8]) -> Result<Vec<DecodedRecord>> {
+    let bad = || MLError::ProfilingError("truncated or malformed binary metrics buffer".to_string());
+
+    if bytes.len() < 4 || &bytes[0..4] != BINARY_MAGIC {
+        return Err(MLError::ProfilingError("not a recognized binary metrics buffer".to_string()));
+    }
+    let mut pos = 4;
+
+    let record_count = u32::from_le_bytes(bytes.get(pos..pos + 4).ok_or_else(bad)?.try_into().unwrap());
+    pos += 4;
+
+    let mut records = Vec::with_capacity(record_count as usize);
+    for _ in 0..record_count {
+        let field_count = u16::from_le_bytes(bytes.get(pos..pos + 2).ok_or_else(bad)?.try_into().unwrap());
+        pos += 2;
+
+        let mut record = Vec::with_capacity(field_count as usize);
+        for _ in 0..field_count {
+            let name_len = *bytes.get(pos).ok_or_else(bad)? as usize;
+            pos += 1;
+            let name = std::str::from_utf8(bytes.get(pos..pos + name_len).ok_or_else(bad)?)
+                .map_err(|_| bad())?
+                .to_string();
+            pos += name_len;
+
+            let tag = *bytes.get(pos).ok_or_else(bad)?;
+            pos += 1;
+            let value_len = u32::from_le_bytes(bytes.get(pos..pos + 4).ok_or_else(bad)?.try_into().unwrap()) as usize;
+            pos += 4;
+            let value_bytes = bytes.get(pos..pos + value_len).ok_or_else(bad)?;
+            pos += value_len;
+
+            let value = match tag {
+                TAG_U64 => FieldValue::U64(u64::from_le_bytes(value_bytes.try_into().map_err(|_| bad())?)),
+                TAG_F32 => FieldValue::F32(f32::from_le_bytes(value_bytes.try_into().map_err(|_| bad())?)),
+                TAG_U32_ARRAY => FieldValue::U32Array(
+                    value_bytes.chunks_exact(4).map(|c| u32::from_le_bytes(c.try_into().unwrap())).collect(),
+                ),
+                TAG_F32_ARRAY => FieldValue::F32Array(
+                    value_bytes.chunks_exact(4).map(|c| f32::from_le_bytes(c.try_into().unwrap())).collect(),
+                ),
+                TAG_U64_ARRAY => FieldValue::U64Array(
+                    value_bytes.chunks_exact(8).map(|c| u64::from_le_bytes(c.try_into().unwrap())).collect(),
+                ),
+                other => FieldValue::Unknown(other, value_bytes.to_vec()),
+            };
+            record.push((name, value));
+        }
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiling::TrainingMetrics;
+
+    fn sample_metrics(timestamp: u64) -> ProfilingMetrics {
+        ProfilingMetrics {
+            timestamp,
+            gpu_utilization: vec![42.0, 10.0],
+            gpu_memory_usage: vec![1000, 2000],
+            gpu_sm_clock_mhz: vec![],
+            gpu_sm_clock_max_mhz: vec![],
+            gpu_memory_clock_mhz: vec![],
+            gpu_power_draw_watts: vec![],
+            gpu_power_limit_watts: vec![],
+            cpu_utilization: 55.5,
+            memory_usage: 123_456,
+            io_read_bytes: 10,
+            io_write_bytes: 20,
+            network_rx_bytes: 30,
+            network_tx_bytes: 40,
+            cgroup_memory_anon_bytes: None,
+            cgroup_memory_file_bytes: None,
+            cgroup_cpu_throttled_usec: None,
+            cgroup_nr_throttled: None,
+            training_metrics: TrainingMetrics {
+                batch_time: None,
+                forward_time: None,
+                backward_time: None,
+                optimizer_time: None,
+                data_loading_time: None,
+                loss: None,
+                learning_rate: None,
+                gradients_norm: None,
+            },
+            retainer_snapshot: None,
+        }
+    }
+
+    #[test]
+    fn test_write_json_round_trips_through_serde() {
+        let metrics = vec![sample_metrics(1)];
+        let mut buf = Vec::new();
+        write_metrics(&metrics, MetricsFormat::Json, &mut buf).unwrap();
+        let parsed: Vec<ProfilingMetrics> = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed[0].timestamp, 1);
+    }
+
+    #[test]
+    fn test_write_csv_has_a_header_and_one_row_per_sample() {
+        let metrics = vec![sample_metrics(1), sample_metrics(2)];
+        let mut buf = Vec::new();
+        write_metrics(&metrics, MetricsFormat::Csv, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("timestamp,"));
+        assert!(lines[1].contains("42;10"));
+    }
+
+    #[test]
+    fn test_write_markdown_renders_a_pipe_table() {
+        let metrics = vec![sample_metrics(1)];
+        let mut buf = Vec::new();
+        write_metrics(&metrics, MetricsFormat::Markdown, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("|---|"));
+    }
+
+    #[test]
+    fn test_binary_round_trips_every_known_field() {
+        let metrics = vec![sample_metrics(7)];
+        let mut buf = Vec::new();
+        write_metrics(&metrics, MetricsFormat::Binary, &mut buf).unwrap();
+
+        let records = read_binary(&buf).unwrap();
+        assert_eq!(records.len(), 1);
+        let fields: std::collections::HashMap<_, _> = records[0].iter().cloned().collect();
+        assert_eq!(fields["timestamp"], FieldValue::U64(7));
+        assert_eq!(fields["memory_usage"], FieldValue::U64(123_456));
+        assert_eq!(fields["gpu_utilization"], FieldValue::F32Array(vec![42.0, 10.0]));
+        assert_eq!(fields["gpu_memory_usage"], FieldValue::U64Array(vec![1000, 2000]));
+    }
+
+    #[test]
+    fn test_read_binary_rejects_a_buffer_without_the_magic_prefix() {
+        let err = read_binary(&[0u8; 8]).unwrap_err();
+        assert!(matches!(err, MLError::ProfilingError(_)));
+    }
+
+    #[test]
+    fn test_read_binary_surfaces_an_unrecognized_tag_as_unknown_instead_of_failing() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(BINARY_MAGIC);
+        buf.extend_from_slice(&1u32.to_le_bytes()); // record_count
+        buf.extend_from_slice(&1u16.to_le_bytes()); // field_count
+        write_field_header(&mut buf, "future_field", 99, 3).unwrap();
+        buf.extend_from_slice(&[1, 2, 3]);
+
+        let records = read_binary(&buf).unwrap();
+        assert_eq!(records[0][0], ("future_field".to_string(), FieldValue::Unknown(99, vec![1, 2, 3])));
+    }
+}
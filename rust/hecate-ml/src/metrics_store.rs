@@ -0,0 +1,224 @@
+//! Persistent on-disk metrics store
+//!
+//! [`crate::profiling::Profiler::metrics_buffer`] only ever lived in memory, so history vanished
+//! whenever the process exited. A [`MetricsStore`] flushes every sample to an append-only
+//! sequence of rolling segment files, fronted by a small `docket.json` index recording which
+//! segment is active along with the sampling interval and retention window the store was created
+//! with. On restart, the docket tells a fresh process exactly where to resume appending and
+//! [`MetricsStore::replay`] streams the history straight back into `metrics_buffer`.
+
+use crate::error::{MLError, Result};
+use crate::profiling::ProfilingMetrics;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Segments rotate once the active one reaches this size, so a long-running job's store doesn't
+/// grow one file without bound.
+const DEFAULT_ROTATION_THRESHOLD_BYTES: u64 = 16 * 1024 * 1024;
+
+/// The store's index file: which segment is currently being appended to, and the
+/// sampling/retention settings it was created with, so a resumed process can reconstruct a
+/// [`crate::profiling::ProfilingConfig`] that matches the history it's replaying.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Docket {
+    current_segment: u64,
+    sampling_interval: Duration,
+    retention_period: Duration,
+}
+
+/// Append-only on-disk metrics store: a `docket.json` index plus a rolling sequence of
+/// `segment-<N>.jsonl` files, one [`ProfilingMetrics`] record per line.
+#[derive(Debug)]
+pub struct MetricsStore {
+    dir: PathBuf,
+    docket: Docket,
+    rotation_threshold_bytes: u64,
+    active_segment_bytes: u64,
+}
+
+impl MetricsStore {
+    /// Open the store rooted at `dir`, creating it if it doesn't exist yet. If a docket is
+    /// already present, its segment pointer and sampling/retention settings are reused as-is;
+    /// `sampling_interval`/`retention_period` only seed a brand-new store.
+    pub fn open(dir: impl AsRef<Path>, sampling_interval: Duration, retention_period: Duration) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(MLError::IoError)?;
+
+        let docket_path = dir.join("docket.json");
+        let docket = if docket_path.exists() {
+            let content = fs::read_to_string(&docket_path).map_err(MLError::IoError)?;
+            serde_json::from_str(&content).map_err(MLError::SerializationError)?
+        } else {
+            let docket = Docket { current_segment: 0, sampling_interval, retention_period };
+            Self::write_docket(&dir, &docket)?;
+            docket
+        };
+
+        let active_segment_bytes =
+            fs::metadata(Self::segment_path(&dir, docket.current_segment)).map(|m| m.len()).unwrap_or(0);
+
+        Ok(Self { dir, docket, rotation_threshold_bytes: DEFAULT_ROTATION_THRESHOLD_BYTES, active_segment_bytes })
+    }
+
+    /// Override the segment rotation size (16MiB by default).
+    pub fn with_rotation_threshold_bytes(mut self, bytes: u64) -> Self {
+        self.rotation_threshold_bytes = bytes;
+        self
+    }
+
+    pub fn sampling_interval(&self) -> Duration {
+        self.docket.sampling_interval
+    }
+
+    pub fn retention_period(&self) -> Duration {
+        self.docket.retention_period
+    }
+
+    fn segment_path(dir: &Path, segment: u64) -> PathBuf {
+        dir.join(format!("segment-{segment:06}.jsonl"))
+    }
+
+    fn write_docket(dir: &Path, docket: &Docket) -> Result<()> {
+        let json = serde_json::to_string_pretty(docket).map_err(MLError::SerializationError)?;
+        fs::write(dir.join("docket.json"), json).map_err(MLError::IoError)
+    }
+
+    /// Append `metrics` to the active segment, rotating to a new segment first if the active one
+    /// is already at or over the rotation threshold.
+    pub fn append(&mut self, metrics: &ProfilingMetrics) -> Result<()> {
+        if self.active_segment_bytes >= self.rotation_threshold_bytes {
+            self.docket.current_segment += 1;
+            self.active_segment_bytes = 0;
+            Self::write_docket(&self.dir, &self.docket)?;
+        }
+
+        let mut line = serde_json::to_string(metrics).map_err(MLError::SerializationError)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::segment_path(&self.dir, self.docket.current_segment))
+            .map_err(MLError::IoError)?;
+        file.write_all(line.as_bytes()).map_err(MLError::IoError)?;
+
+        self.active_segment_bytes += line.len() as u64;
+        Ok(())
+    }
+
+    /// Stream every record from segment 0 through the current segment back in order, for a
+    /// resumed process replaying history into `metrics_buffer`.
+    pub fn replay(&self) -> Result<Vec<ProfilingMetrics>> {
+        let mut out = Vec::new();
+        for segment in 0..=self.docket.current_segment {
+            let path = Self::segment_path(&self.dir, segment);
+            if !path.exists() {
+                continue;
+            }
+            let file = File::open(&path).map_err(MLError::IoError)?;
+            for line in BufReader::new(file).lines() {
+                let line = line.map_err(MLError::IoError)?;
+                if line.is_empty() {
+                    continue;
+                }
+                out.push(serde_json::from_str(&line).map_err(MLError::SerializationError)?);
+            }
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiling::TrainingMetrics;
+
+    fn sample_metrics(timestamp: u64) -> ProfilingMetrics {
+        ProfilingMetrics {
+            timestamp,
+            gpu_utilization: vec![],
+            gpu_memory_usage: vec![],
+            gpu_sm_clock_mhz: vec![],
+            gpu_sm_clock_max_mhz: vec![],
+            gpu_memory_clock_mhz: vec![],
+            gpu_power_draw_watts: vec![],
+            gpu_power_limit_watts: vec![],
+            cpu_utilization: 0.0,
+            memory_usage: 0,
+            io_read_bytes: 0,
+            io_write_bytes: 0,
+            network_rx_bytes: 0,
+            network_tx_bytes: 0,
+            cgroup_memory_anon_bytes: None,
+            cgroup_memory_file_bytes: None,
+            cgroup_cpu_throttled_usec: None,
+            cgroup_nr_throttled: None,
+            training_metrics: TrainingMetrics {
+                batch_time: None,
+                forward_time: None,
+                backward_time: None,
+                optimizer_time: None,
+                data_loading_time: None,
+                loss: None,
+                learning_rate: None,
+                gradients_norm: None,
+            },
+            retainer_snapshot: None,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("hecate-ml-metrics-store-test-{name}-{:?}", std::thread::current().id()))
+    }
+
+    #[test]
+    fn test_append_and_replay_round_trips_every_record_in_order() {
+        let dir = temp_dir("round-trip");
+        let _ = fs::remove_dir_all(&dir);
+        let mut store = MetricsStore::open(&dir, Duration::from_secs(1), Duration::from_secs(3600)).unwrap();
+
+        store.append(&sample_metrics(1)).unwrap();
+        store.append(&sample_metrics(2)).unwrap();
+        store.append(&sample_metrics(3)).unwrap();
+
+        let replayed = store.replay().unwrap();
+        assert_eq!(replayed.iter().map(|m| m.timestamp).collect::<Vec<_>>(), vec![1, 2, 3]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reopening_a_store_resumes_its_docket_instead_of_resetting_it() {
+        let dir = temp_dir("resume");
+        let _ = fs::remove_dir_all(&dir);
+        let mut store = MetricsStore::open(&dir, Duration::from_secs(5), Duration::from_secs(60)).unwrap();
+        store.append(&sample_metrics(1)).unwrap();
+        drop(store);
+
+        // Different sampling/retention args are ignored since the docket already exists.
+        let store = MetricsStore::open(&dir, Duration::from_secs(99), Duration::from_secs(99)).unwrap();
+        assert_eq!(store.sampling_interval(), Duration::from_secs(5));
+        assert_eq!(store.retention_period(), Duration::from_secs(60));
+        assert_eq!(store.replay().unwrap().len(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_append_rotates_to_a_new_segment_once_the_threshold_is_exceeded() {
+        let dir = temp_dir("rotation");
+        let _ = fs::remove_dir_all(&dir);
+        let mut store = MetricsStore::open(&dir, Duration::from_secs(1), Duration::from_secs(3600))
+            .unwrap()
+            .with_rotation_threshold_bytes(1); // rotate after every single append
+
+        store.append(&sample_metrics(1)).unwrap();
+        store.append(&sample_metrics(2)).unwrap();
+
+        assert!(dir.join("segment-000000.jsonl").exists());
+        assert!(dir.join("segment-000001.jsonl").exists());
+        assert_eq!(store.replay().unwrap().len(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
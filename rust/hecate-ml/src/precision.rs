@@ -0,0 +1,170 @@
+//! Mixed-precision tiers, including FP8 delayed-scaling bookkeeping
+//!
+//! FP8 training only has headroom to be numerically safe on recent accelerators, so this module
+//! also carries the hardware gate (`gpu_supports_fp8`) that [`crate::MLOptimizer::optimize_workload`]
+//! uses to decide whether to recommend it or fall back to BF16.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Minimum NVIDIA compute capability (Ada/Hopper and newer) at or above which FP8 matmuls are
+/// numerically safe to recommend
+pub const MIN_FP8_COMPUTE_CAPABILITY: f32 = 8.9;
+
+/// Default length of the rolling amax history [`DelayedScaling`] uses to pick a scale factor
+pub const DEFAULT_AMAX_HISTORY_LEN: usize = 16;
+
+/// Numeric precision tier for training/inference compute
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum PrecisionMode {
+    /// Full 32-bit float
+    Fp32,
+    /// TensorFloat-32: FP32 storage, reduced-mantissa matmul on Ampere+
+    Tf32,
+    /// IEEE half precision
+    Fp16,
+    /// Brain float16: FP32 exponent range, reduced mantissa
+    Bf16,
+    /// 8-bit float; see [`Fp8Format`] for the two encodings used for it
+    Fp8,
+}
+
+/// The two FP8 encodings used together in a typical FP8 training recipe: E4M3 for forward
+/// activations/weights (more mantissa precision, smaller dynamic range) and E5M2 for gradients
+/// (more dynamic range, since gradients vary over many more orders of magnitude)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Fp8Format {
+    /// 4 exponent bits, 3 mantissa bits — used for forward activations and weights
+    E4M3,
+    /// 5 exponent bits, 2 mantissa bits — used for gradients
+    E5M2,
+}
+
+impl Fp8Format {
+    pub fn exponent_bits(&self) -> u32 {
+        match self {
+            Self::E4M3 => 4,
+            Self::E5M2 => 5,
+        }
+    }
+
+    pub fn mantissa_bits(&self) -> u32 {
+        match self {
+            Self::E4M3 => 3,
+            Self::E5M2 => 2,
+        }
+    }
+
+    /// Largest finite magnitude this encoding can represent, used to pick a delayed-scaling
+    /// scale factor that maps the observed amax up to (but not past) this ceiling
+    pub fn max_abs_value(&self) -> f32 {
+        match self {
+            Self::E4M3 => 448.0,
+            Self::E5M2 => 57344.0,
+        }
+    }
+}
+
+/// Returns whether a GPU with the given compute capability can run FP8 matmuls safely
+pub fn gpu_supports_fp8(compute_capability: f32) -> bool {
+    compute_capability >= MIN_FP8_COMPUTE_CAPABILITY
+}
+
+/// Delayed-scaling state for a single FP8 tensor: tracks a rolling history of observed
+/// absolute-value maxima (`amax`) and derives a scale factor from the largest one seen, so a
+/// single outlier step doesn't force every other step's quantization to waste dynamic range.
+#[derive(Debug, Clone)]
+pub struct DelayedScaling {
+    format: Fp8Format,
+    history: VecDeque<f32>,
+    history_len: usize,
+}
+
+impl DelayedScaling {
+    /// Create delayed-scaling state for `format` with the default 16-step amax history
+    pub fn new(format: Fp8Format) -> Self {
+        Self::with_history_len(format, DEFAULT_AMAX_HISTORY_LEN)
+    }
+
+    pub fn with_history_len(format: Fp8Format, history_len: usize) -> Self {
+        Self {
+            format,
+            history: VecDeque::with_capacity(history_len.max(1)),
+            history_len: history_len.max(1),
+        }
+    }
+
+    /// Record this step's observed absolute-value maximum, evicting the oldest entry once the
+    /// history window is full
+    pub fn observe_amax(&mut self, amax: f32) {
+        if self.history.len() >= self.history_len {
+            self.history.pop_front();
+        }
+        self.history.push_back(amax.abs());
+    }
+
+    /// The scale factor to apply before quantizing to FP8: the format's max representable
+    /// magnitude divided by the largest amax observed in the current history window. Returns
+    /// `1.0` (no scaling) before any step has been observed.
+    pub fn scale_factor(&self) -> f32 {
+        let amax = self.history.iter().cloned().fold(0.0_f32, f32::max);
+        if amax <= 0.0 {
+            1.0
+        } else {
+            self.format.max_abs_value() / amax
+        }
+    }
+
+    pub fn format(&self) -> Fp8Format {
+        self.format
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gpu_supports_fp8_gates_on_compute_capability() {
+        assert!(gpu_supports_fp8(8.9));
+        assert!(gpu_supports_fp8(9.0));
+        assert!(!gpu_supports_fp8(8.6));
+        assert!(!gpu_supports_fp8(7.5));
+    }
+
+    #[test]
+    fn delayed_scaling_defaults_to_unscaled_before_any_observation() {
+        let scaling = DelayedScaling::new(Fp8Format::E4M3);
+        assert_eq!(scaling.scale_factor(), 1.0);
+    }
+
+    #[test]
+    fn delayed_scaling_tracks_the_max_over_the_window() {
+        let mut scaling = DelayedScaling::new(Fp8Format::E4M3);
+        scaling.observe_amax(10.0);
+        scaling.observe_amax(100.0);
+        scaling.observe_amax(50.0);
+
+        let expected = Fp8Format::E4M3.max_abs_value() / 100.0;
+        assert!((scaling.scale_factor() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn delayed_scaling_forgets_amax_once_it_falls_out_of_the_window() {
+        let mut scaling = DelayedScaling::with_history_len(Fp8Format::E5M2, 2);
+        scaling.observe_amax(100.0); // will be evicted
+        scaling.observe_amax(10.0);
+        scaling.observe_amax(20.0);
+
+        let expected = Fp8Format::E5M2.max_abs_value() / 20.0;
+        assert!((scaling.scale_factor() - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn fp8_formats_have_the_expected_bit_layout() {
+        assert_eq!(Fp8Format::E4M3.exponent_bits(), 4);
+        assert_eq!(Fp8Format::E4M3.mantissa_bits(), 3);
+        assert_eq!(Fp8Format::E5M2.exponent_bits(), 5);
+        assert_eq!(Fp8Format::E5M2.mantissa_bits(), 2);
+    }
+}
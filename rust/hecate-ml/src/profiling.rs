@@ -1,8 +1,16 @@
 //! ML workload profiling and performance analysis
 
+use crate::benchmark::BenchmarkReport;
 use crate::error::{MLError, Result};
+use crate::metrics_export::{self, MetricsFormat};
+use crate::metrics_store::MetricsStore;
+use crate::retainer::RetainerSnapshot;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::time::interval;
 use tracing::{debug, info, warn, instrument};
@@ -13,13 +21,37 @@ pub struct ProfilingMetrics {
     pub timestamp: u64,
     pub gpu_utilization: Vec<f32>,
     pub gpu_memory_usage: Vec<u64>,
+    /// Per-GPU SM (core) clock, in MHz
+    pub gpu_sm_clock_mhz: Vec<u32>,
+    /// Per-GPU maximum boost SM clock, in MHz — the ceiling `gpu_sm_clock_mhz` compares against
+    /// to tell a throttled GPU from one that's simply idle
+    pub gpu_sm_clock_max_mhz: Vec<u32>,
+    /// Per-GPU memory clock, in MHz
+    pub gpu_memory_clock_mhz: Vec<u32>,
+    /// Per-GPU instantaneous power draw, in watts
+    pub gpu_power_draw_watts: Vec<f32>,
+    /// Per-GPU power limit (TDP cap), in watts
+    pub gpu_power_limit_watts: Vec<f32>,
     pub cpu_utilization: f32,
     pub memory_usage: u64,
     pub io_read_bytes: u64,
     pub io_write_bytes: u64,
     pub network_rx_bytes: u64,
     pub network_tx_bytes: u64,
+    /// Anonymous-memory bytes from this process's cgroup v2 `memory.stat`, when running inside
+    /// one with a memory controller attached
+    pub cgroup_memory_anon_bytes: Option<u64>,
+    /// Page-cache/file-backed bytes from `memory.stat`
+    pub cgroup_memory_file_bytes: Option<u64>,
+    /// Microseconds this tick's cgroup was CPU-throttled (`cpu.stat` `throttled_usec`, diffed
+    /// against the previous sample), when a cpu controller is attached
+    pub cgroup_cpu_throttled_usec: Option<u64>,
+    /// Number of throttling periods that occurred this tick (`cpu.stat` `nr_throttled`, diffed)
+    pub cgroup_nr_throttled: Option<u64>,
     pub training_metrics: TrainingMetrics,
+    /// Heap retainer trace from [`Profiler::record_retainer_snapshot`], when the caller has
+    /// supplied one this tick. `None` on ticks where no object-graph trace was taken.
+    pub retainer_snapshot: Option<RetainerSnapshot>,
 }
 
 /// Training-specific metrics
@@ -35,6 +67,153 @@ pub struct TrainingMetrics {
     pub gradients_norm: Option<f32>,
 }
 
+/// Parsed form of a [`ProfilingConfig::scope_filter`] spec string, e.g. `"forward|backward@3"`
+/// meaning "only record these roots, nesting at most 3 deep". An empty spec records every root
+/// with unlimited nesting.
+#[derive(Debug, Clone)]
+struct ScopeFilter {
+    /// `None` means every root name is recorded.
+    roots: Option<Vec<String>>,
+    max_depth: usize,
+}
+
+impl ScopeFilter {
+    fn parse(spec: &str) -> Self {
+        if spec.is_empty() {
+            return Self { roots: None, max_depth: usize::MAX };
+        }
+        let (names, max_depth) = match spec.split_once('@') {
+            Some((names, depth)) => (names, depth.trim().parse().unwrap_or(usize::MAX)),
+            None => (spec, usize::MAX),
+        };
+        Self { roots: Some(names.split('|').map(str::to_string).collect()), max_depth }
+    }
+
+    fn allows_root(&self, name: &str) -> bool {
+        self.roots.as_ref().map_or(true, |roots| roots.iter().any(|r| r == name))
+    }
+}
+
+/// One call-tree node accumulated by [`Profiler::scope`]: total time spent in the scope itself,
+/// total time spent in its recorded children, how many times it was entered, and its recorded
+/// children keyed by name.
+#[derive(Debug, Clone, Default)]
+struct ScopeStats {
+    self_time: Duration,
+    child_time: Duration,
+    call_count: u64,
+    children: HashMap<String, ScopeStats>,
+}
+
+/// One open [`Profiler::scope`] on the stack. `recorded_path` is the chain of recorded ancestor
+/// names (including this scope) from the root down, or `None` if this scope itself is excluded
+/// by the filter spec (too deep, or not an allowed root) — in which case its children can't be
+/// recorded either, since there's no tree path to hang them on.
+#[derive(Debug)]
+struct ScopeStackFrame {
+    recorded_path: Option<Vec<String>>,
+    /// Sum of every child scope's elapsed time, so this scope's self time can be computed as
+    /// `elapsed - child_time_accum` when it's popped.
+    child_time_accum: Duration,
+}
+
+/// Mutable state backing [`Profiler::scope`], behind a [`Mutex`] so guards can record into it
+/// through a shared `&Profiler` reference held across arbitrary caller code.
+#[derive(Debug, Default)]
+struct ScopeState {
+    stack: Vec<ScopeStackFrame>,
+    tree: HashMap<String, ScopeStats>,
+}
+
+/// A guard returned by [`Profiler::scope`] that measures elapsed time on drop and records it
+/// into the profiler's scope tree.
+#[derive(Debug)]
+pub struct ScopeGuard<'a> {
+    profiler: &'a Profiler,
+    start: Instant,
+}
+
+impl Drop for ScopeGuard<'_> {
+    fn drop(&mut self) {
+        self.profiler.end_scope(self.start.elapsed());
+    }
+}
+
+/// One event in the Chrome/Perfetto Trace Event Format, as emitted by [`Profiler::export_trace`].
+/// Loads directly in `chrome://tracing` or https://ui.perfetto.dev.
+#[derive(Debug, Clone, Serialize)]
+struct TraceEvent {
+    name: String,
+    /// Event phase: `"C"` for a counter sample, `"X"` for a complete (start+duration) event,
+    /// `"M"` for metadata (used here only to name tracks).
+    ph: &'static str,
+    /// Timestamp in microseconds since the Unix epoch.
+    ts: u64,
+    /// Duration in microseconds; only set for `"X"` events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dur: Option<u64>,
+    pid: u32,
+    tid: u32,
+    args: HashMap<String, serde_json::Value>,
+}
+
+impl TraceEvent {
+    /// A `"M"` metadata event naming the track `tid` shows up as in the trace viewer.
+    fn thread_name(pid: u32, tid: u32, name: &str) -> Self {
+        Self {
+            name: "thread_name".to_string(),
+            ph: "M",
+            ts: 0,
+            dur: None,
+            pid,
+            tid,
+            args: HashMap::from([("name".to_string(), serde_json::json!(name))]),
+        }
+    }
+}
+
+/// `pid` used for the counter tracks (GPU/CPU/memory/IO) in [`Profiler::export_trace`].
+const TRACE_COUNTER_PID: u32 = 1;
+/// `pid` used for the per-phase duration tracks in [`Profiler::export_trace`].
+const TRACE_PHASE_PID: u32 = 2;
+/// Training-phase names and their `tid`s, in display order, for [`Profiler::export_trace`].
+const TRACE_PHASE_TRACKS: &[(u32, &str)] =
+    &[(1, "batch"), (2, "forward"), (3, "backward"), (4, "optimizer"), (5, "data_loading")];
+
+/// Per-GPU readings for one profiling tick, all parallel vectors indexed by device — mirrors
+/// the GPU fields on [`ProfilingMetrics`] before they're merged into it.
+#[derive(Debug, Clone, Default)]
+struct GpuSamples {
+    utilization: Vec<f32>,
+    memory_usage: Vec<u64>,
+    sm_clock_mhz: Vec<u32>,
+    sm_clock_max_mhz: Vec<u32>,
+    memory_clock_mhz: Vec<u32>,
+    power_draw_watts: Vec<f32>,
+    power_limit_watts: Vec<f32>,
+}
+
+/// Mean and population variance of a window of samples, used by
+/// [`Profiler::detect_statistical_regressions`]'s Welch-style comparison.
+#[derive(Debug, Clone, Copy, Default)]
+struct WindowStats {
+    mean: f64,
+    variance: f64,
+    n: usize,
+}
+
+impl WindowStats {
+    fn from_samples(samples: &[f64]) -> Self {
+        let n = samples.len();
+        if n == 0 {
+            return Self::default();
+        }
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n as f64;
+        Self { mean, variance, n }
+    }
+}
+
 /// Performance bottleneck
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bottleneck {
@@ -57,6 +236,17 @@ pub enum BottleneckType {
     Network,
     DataLoading,
     ModelComputation,
+    /// The workload's own cgroup is being CPU-throttled, which can masquerade as GPU
+    /// underutilization if read from host-wide metrics alone
+    CpuThrottling,
+    /// A metric series has shifted by a statistically significant amount between an earlier
+    /// baseline window and the most recent window, e.g. a memory leak slowly climbing `memory_usage`
+    /// well before it crosses any fixed threshold
+    Regression,
+    /// A GPU's clocks sit well below its max boost clock while its power draw is pinned at the
+    /// TDP cap — thermal or power throttling, distinct from merely low utilization and requiring
+    /// a different fix (cooling/power limit, not batch size)
+    GpuThrottling,
 }
 
 /// Bottleneck severity levels
@@ -77,6 +267,254 @@ pub struct ProfilingConfig {
     pub detailed_timing: bool,
     pub memory_profiling: bool,
     pub network_profiling: bool,
+    /// Scoped-profiling filter spec, e.g. `"forward|backward@3"` meaning "only record these
+    /// roots, nesting at most 3 deep". Empty records every root with unlimited nesting. See
+    /// [`Profiler::scope`].
+    pub scope_filter: String,
+    /// Spans shorter than this are folded into their parent's self time instead of getting
+    /// their own entry in [`Profiler::get_scope_tree`].
+    pub scope_min_duration: Duration,
+    /// Number of older samples compared against in the Welch-style regression check (see
+    /// [`Profiler::detect_statistical_regressions`]).
+    pub regression_baseline_window: usize,
+    /// Number of most-recent samples treated as the "current" window in the regression check.
+    pub regression_recent_window: usize,
+    /// How many pooled standard deviations the recent window's mean must differ from the
+    /// baseline window's mean by before it's flagged as a regression.
+    pub regression_threshold: f64,
+    /// Warmup iterations discarded before the timed iterations in [`Profiler::benchmark`].
+    pub benchmark_warmup_iters: usize,
+    /// Minimum number of tracked entities before [`Profiler::collect_metrics_parallel`] fans
+    /// sampling out across rayon's thread pool instead of sampling them serially.
+    pub parallel_sample_threshold: usize,
+}
+
+/// One point-in-time read of the raw, mostly-cumulative host counters the profiler diffs
+/// between ticks to turn totals into per-interval rates.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RawMetricSample {
+    /// Sum of every `/proc/stat` CPU field (user+nice+system+idle+iowait+irq+softirq+steal), in
+    /// USER_HZ jiffies
+    cpu_total_jiffies: u64,
+    /// `idle` + `iowait` jiffies
+    cpu_idle_jiffies: u64,
+    mem_total_bytes: u64,
+    mem_available_bytes: u64,
+    /// Cumulative bytes read across every block device
+    disk_bytes_read: u64,
+    disk_bytes_written: u64,
+    /// Cumulative bytes across every non-loopback interface in `/proc/net/dev`
+    net_rx_bytes: u64,
+    net_tx_bytes: u64,
+    /// Cumulative cgroup v2 CPU time consumed (`cpu.stat` `usage_usec`), when sampled from
+    /// inside a cgroup with a cpu controller attached
+    cgroup_cpu_usage_usec: Option<u64>,
+    /// Cumulative microseconds this cgroup has been throttled (`cpu.stat` `throttled_usec`)
+    cgroup_throttled_usec: Option<u64>,
+    /// Cumulative count of throttling periods (`cpu.stat` `nr_throttled`)
+    cgroup_nr_throttled: Option<u64>,
+    /// Anonymous-memory bytes from `memory.stat`'s `anon` key
+    cgroup_memory_anon_bytes: Option<u64>,
+    /// Page-cache/file-backed bytes from `memory.stat`'s `file` key
+    cgroup_memory_file_bytes: Option<u64>,
+}
+
+/// Where a [`Profiler`] gets its raw host counters from. Abstracted so the real Linux backend
+/// (reading `/proc`) and a fixed simulated source (non-Linux targets, unit tests) are
+/// interchangeable.
+pub trait MetricSource: std::fmt::Debug + Send + Sync {
+    fn sample(&mut self) -> Result<RawMetricSample>;
+}
+
+/// Reads host resource usage from `/proc`, following the same `procfs`-backed approach as
+/// [`crate`]'s hardware detection (see `hecate_core::HardwareDetector::detect_cpu`).
+#[derive(Debug, Default)]
+pub struct ProcMetricSource;
+
+impl MetricSource for ProcMetricSource {
+    fn sample(&mut self) -> Result<RawMetricSample> {
+        let stat = procfs::KernelStats::new()
+            .map_err(|e| MLError::ProfilingError(format!("failed to read /proc/stat: {e}")))?;
+        let cpu = &stat.total;
+        let cpu_total_jiffies = cpu.user
+            + cpu.nice
+            + cpu.system
+            + cpu.idle
+            + cpu.iowait.unwrap_or(0)
+            + cpu.irq.unwrap_or(0)
+            + cpu.softirq.unwrap_or(0)
+            + cpu.steal.unwrap_or(0);
+        let cpu_idle_jiffies = cpu.idle + cpu.iowait.unwrap_or(0);
+
+        let meminfo = procfs::Meminfo::new()
+            .map_err(|e| MLError::ProfilingError(format!("failed to read /proc/meminfo: {e}")))?;
+        let mem_total_bytes = meminfo.mem_total;
+        let mem_available_bytes = meminfo.mem_available.unwrap_or(meminfo.mem_free);
+
+        let (disk_bytes_read, disk_bytes_written) = procfs::diskstats()
+            .map_err(|e| MLError::ProfilingError(format!("failed to read /proc/diskstats: {e}")))?
+            .iter()
+            .fold((0u64, 0u64), |(read, written), disk| {
+                (read + disk.sectors_read * 512, written + disk.sectors_written * 512)
+            });
+
+        let (net_rx_bytes, net_tx_bytes) = procfs::net::dev_status()
+            .map_err(|e| MLError::ProfilingError(format!("failed to read /proc/net/dev: {e}")))?
+            .values()
+            .filter(|dev| dev.name != "lo")
+            .fold((0u64, 0u64), |(rx, tx), dev| (rx + dev.recv_bytes, tx + dev.sent_bytes));
+
+        Ok(RawMetricSample {
+            cpu_total_jiffies,
+            cpu_idle_jiffies,
+            mem_total_bytes,
+            mem_available_bytes,
+            disk_bytes_read,
+            disk_bytes_written,
+            net_rx_bytes,
+            net_tx_bytes,
+            ..Default::default()
+        })
+    }
+}
+
+/// Reads cgroup v2 accounting for the current process so the profiler reports what this job
+/// actually consumed on a shared cluster, not the whole host: `memory.current`/`memory.stat` for
+/// working-set and anon/file breakdown, `cpu.stat` for usage and throttling, and `io.stat` for
+/// per-device byte counters. Falls back to the host-wide [`ProcMetricSource`] reading whenever
+/// the process isn't in a cgroup (or the relevant controller isn't attached), so sampling never
+/// fails just because a job happens to be running outside a container.
+#[derive(Debug)]
+pub struct CgroupMetricSource {
+    inner: ProcMetricSource,
+    cgroup_dir: Option<PathBuf>,
+}
+
+impl CgroupMetricSource {
+    pub fn new() -> Self {
+        Self { inner: ProcMetricSource, cgroup_dir: Self::discover_cgroup_dir() }
+    }
+
+    /// Resolve the current process's cgroup v2 directory under `/sys/fs/cgroup`, from the
+    /// single `0::<path>` line `/proc/self/cgroup` has under the unified hierarchy.
+    fn discover_cgroup_dir() -> Option<PathBuf> {
+        let content = fs::read_to_string("/proc/self/cgroup").ok()?;
+        let relative = content.lines().find_map(|line| line.strip_prefix("0::"))?;
+        let dir = Path::new("/sys/fs/cgroup").join(relative.trim_start_matches('/'));
+        dir.is_dir().then_some(dir)
+    }
+
+    /// Look up `key`'s value in a `key value` (or `key=value`)-per-line cgroup stat file, as
+    /// used by `cpu.stat` and `memory.stat`.
+    fn read_stat_key(path: &Path, key: &str) -> Option<u64> {
+        let content = fs::read_to_string(path).ok()?;
+        content.lines().find_map(|line| {
+            let (name, value) = line.split_once(|c: char| c == ' ' || c == '=')?;
+            (name == key).then(|| value.trim().parse().ok()).flatten()
+        })
+    }
+
+    fn read_memory_current(dir: &Path) -> Option<u64> {
+        fs::read_to_string(dir.join("memory.current")).ok()?.trim().parse().ok()
+    }
+
+    /// Sum `rbytes`/`wbytes` across every device line in `io.stat`.
+    fn read_io_totals(dir: &Path) -> Option<(u64, u64)> {
+        let content = fs::read_to_string(dir.join("io.stat")).ok()?;
+        let mut totals = (0u64, 0u64);
+        for line in content.lines() {
+            for field in line.split_whitespace().skip(1) {
+                if let Some(value) = field.strip_prefix("rbytes=") {
+                    totals.0 += value.parse().unwrap_or(0);
+                } else if let Some(value) = field.strip_prefix("wbytes=") {
+                    totals.1 += value.parse().unwrap_or(0);
+                }
+            }
+        }
+        Some(totals)
+    }
+}
+
+impl Default for CgroupMetricSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricSource for CgroupMetricSource {
+    fn sample(&mut self) -> Result<RawMetricSample> {
+        let mut sample = self.inner.sample()?;
+
+        let Some(dir) = self.cgroup_dir.clone() else { return Ok(sample) };
+
+        if let Some(current) = Self::read_memory_current(&dir) {
+            // Keep reporting against the host total so `Profiler::collect_memory_usage`'s
+            // total-minus-available math yields this cgroup's own usage unchanged.
+            sample.mem_available_bytes = sample.mem_total_bytes.saturating_sub(current);
+        }
+        let memory_stat = dir.join("memory.stat");
+        sample.cgroup_memory_anon_bytes = Self::read_stat_key(&memory_stat, "anon");
+        sample.cgroup_memory_file_bytes = Self::read_stat_key(&memory_stat, "file");
+
+        let cpu_stat = dir.join("cpu.stat");
+        sample.cgroup_cpu_usage_usec = Self::read_stat_key(&cpu_stat, "usage_usec");
+        sample.cgroup_throttled_usec = Self::read_stat_key(&cpu_stat, "throttled_usec");
+        sample.cgroup_nr_throttled = Self::read_stat_key(&cpu_stat, "nr_throttled");
+
+        if let Some((rbytes, wbytes)) = Self::read_io_totals(&dir) {
+            sample.disk_bytes_read = rbytes;
+            sample.disk_bytes_written = wbytes;
+        }
+
+        Ok(sample)
+    }
+}
+
+/// Fixed counters that advance by the same amount every tick, for platforms without `/proc` and
+/// for tests that want deterministic, non-zero deltas without touching the real host.
+#[derive(Debug, Clone, Copy)]
+pub struct SimulatedMetricSource {
+    sample: RawMetricSample,
+}
+
+impl Default for SimulatedMetricSource {
+    fn default() -> Self {
+        Self {
+            sample: RawMetricSample {
+                cpu_total_jiffies: 0,
+                cpu_idle_jiffies: 0,
+                mem_total_bytes: 16_000_000_000,
+                mem_available_bytes: 8_000_000_000,
+                disk_bytes_read: 0,
+                disk_bytes_written: 0,
+                net_rx_bytes: 0,
+                net_tx_bytes: 0,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl MetricSource for SimulatedMetricSource {
+    fn sample(&mut self) -> Result<RawMetricSample> {
+        self.sample.cpu_total_jiffies += 100;
+        self.sample.cpu_idle_jiffies += 70; // 30% utilization
+        self.sample.disk_bytes_read += 1_024_000; // 1MB
+        self.sample.disk_bytes_written += 512_000; // 500KB
+        self.sample.net_rx_bytes += 10_000_000;
+        self.sample.net_tx_bytes += 5_000_000;
+        Ok(self.sample)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn default_metric_source() -> Box<dyn MetricSource> {
+    Box::new(CgroupMetricSource::new())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn default_metric_source() -> Box<dyn MetricSource> {
+    Box::new(SimulatedMetricSource::default())
 }
 
 /// Profiler implementation
@@ -87,6 +525,15 @@ pub struct Profiler {
     bottlenecks: Vec<Bottleneck>,
     baseline_metrics: Option<ProfilingMetrics>,
     profiling_active: bool,
+    metric_source: Box<dyn MetricSource>,
+    /// The previous tick's raw counters, so cumulative totals (CPU jiffies, disk sectors,
+    /// network bytes) can be reported as per-interval deltas instead.
+    last_raw_sample: Option<RawMetricSample>,
+    /// Accumulated hierarchical scope timings from [`Profiler::scope`].
+    scope_state: Mutex<ScopeState>,
+    /// Durable backing store this profiler flushes every sample to, when attached via
+    /// [`Profiler::resume_from_store`].
+    store: Option<MetricsStore>,
 }
 
 impl Profiler {
@@ -98,9 +545,43 @@ impl Profiler {
             bottlenecks: Vec::new(),
             baseline_metrics: None,
             profiling_active: false,
+            metric_source: default_metric_source(),
+            last_raw_sample: None,
+            scope_state: Mutex::new(ScopeState::default()),
+            store: None,
+        }
+    }
+
+    /// Create a profiler backed by a specific [`MetricSource`], e.g. [`SimulatedMetricSource`]
+    /// in a test that wants deterministic readings regardless of target OS.
+    pub fn with_metric_source(config: ProfilingConfig, metric_source: Box<dyn MetricSource>) -> Self {
+        Self {
+            config,
+            metrics_buffer: VecDeque::new(),
+            bottlenecks: Vec::new(),
+            baseline_metrics: None,
+            profiling_active: false,
+            metric_source,
+            last_raw_sample: None,
+            scope_state: Mutex::new(ScopeState::default()),
+            store: None,
         }
     }
 
+    /// Attach a durable [`MetricsStore`], replaying its persisted history into `metrics_buffer`
+    /// first so a resumed process picks back up where a prior run left off. Every sample stored
+    /// from this point on (see [`Profiler::store_metrics`]) is also flushed to the store.
+    pub fn resume_from_store(&mut self, store: MetricsStore) -> Result<()> {
+        let history = store.replay()?;
+        self.metrics_buffer.extend(history);
+        if self.baseline_metrics.is_none() {
+            self.baseline_metrics = self.metrics_buffer.front().cloned();
+        }
+        self.cleanup_old_metrics();
+        self.store = Some(store);
+        Ok(())
+    }
+
     /// Start profiling
     #[instrument]
     pub async fn start_profiling(&mut self) -> Result<()> {
@@ -141,34 +622,222 @@ impl Profiler {
     /// Stop profiling
     pub fn stop_profiling(&mut self) {
         info!("Stopping ML workload profiling");
+        self.apply_scope_metrics();
         self.profiling_active = false;
     }
 
+    /// Open a named scoped-profiling span. The returned guard measures elapsed time on drop and
+    /// records it into the scope tree, nested under whichever scopes are currently open, subject
+    /// to [`ProfilingConfig::scope_filter`] and [`ProfilingConfig::scope_min_duration`].
+    ///
+    /// ```ignore
+    /// let _span = profiler.scope("forward");
+    /// // ... run the forward pass ...
+    /// ```
+    pub fn scope(&self, name: &str) -> ScopeGuard<'_> {
+        self.push_scope(name);
+        ScopeGuard { profiler: self, start: Instant::now() }
+    }
+
+    /// Push a new stack frame for `name`, deciding whether it (and therefore anything nested
+    /// under it) is recorded, per [`ProfilingConfig::scope_filter`].
+    fn push_scope(&self, name: &str) {
+        let filter = ScopeFilter::parse(&self.config.scope_filter);
+        let mut state = self.scope_state.lock().unwrap();
+        let recorded_path = match state.stack.last() {
+            None => filter.allows_root(name).then(|| vec![name.to_string()]),
+            Some(parent) => parent.recorded_path.as_ref().and_then(|path| {
+                (path.len() < filter.max_depth).then(|| {
+                    let mut path = path.clone();
+                    path.push(name.to_string());
+                    path
+                })
+            }),
+        };
+        state.stack.push(ScopeStackFrame { recorded_path, child_time_accum: Duration::ZERO });
+    }
+
+    /// Pop the innermost open scope and fold `elapsed` into the tree. The full elapsed time
+    /// always propagates to the parent's `child_time_accum` so the parent's self time is net of
+    /// this span, regardless of whether this span itself ends up recorded.
+    fn end_scope(&self, elapsed: Duration) {
+        let mut state = self.scope_state.lock().unwrap();
+        let frame = state.stack.pop().expect("scope guard dropped without a matching push");
+
+        if let Some(parent) = state.stack.last_mut() {
+            parent.child_time_accum += elapsed;
+        }
+
+        let Some(path) = frame.recorded_path else { return };
+        if elapsed < self.config.scope_min_duration {
+            return;
+        }
+
+        let self_time = elapsed.saturating_sub(frame.child_time_accum);
+        Self::record_scope(&mut state.tree, &path, self_time, frame.child_time_accum);
+    }
+
+    /// Walk `path` into `tree`, creating nodes as needed, and accumulate the leaf's stats.
+    fn record_scope(tree: &mut HashMap<String, ScopeStats>, path: &[String], self_time: Duration, child_time: Duration) {
+        let (head, rest) = path.split_first().expect("scope path is never empty");
+        let node = tree.entry(head.clone()).or_default();
+        if rest.is_empty() {
+            node.self_time += self_time;
+            node.child_time += child_time;
+            node.call_count += 1;
+        } else {
+            Self::record_scope(&mut node.children, rest, self_time, child_time);
+        }
+    }
+
+    /// Roll each root scope's total elapsed time (self + child, across every call) into the
+    /// matching `TrainingMetrics` field of the most recent sample.
+    fn apply_scope_metrics(&mut self) {
+        let totals: Vec<(String, Duration)> = {
+            let state = self.scope_state.lock().unwrap();
+            state.tree.iter().map(|(name, stats)| (name.clone(), stats.self_time + stats.child_time)).collect()
+        };
+        if totals.is_empty() {
+            return;
+        }
+
+        let Some(mut training_metrics) = self.metrics_buffer.back().map(|m| m.training_metrics.clone()) else {
+            return;
+        };
+        for (name, total) in totals {
+            match name.as_str() {
+                "batch" => training_metrics.batch_time = Some(total),
+                "forward" => training_metrics.forward_time = Some(total),
+                "backward" => training_metrics.backward_time = Some(total),
+                "optimizer" => training_metrics.optimizer_time = Some(total),
+                _ => {}
+            }
+        }
+        self.update_training_metrics(training_metrics);
+    }
+
+    /// Render the accumulated scope tree as an indented report, e.g.:
+    ///
+    /// ```text
+    /// forward: self=12.3ms child=45.6ms calls=10
+    ///   attention: self=8.1ms child=4.2ms calls=10
+    /// ```
+    pub fn get_scope_tree(&self) -> String {
+        let state = self.scope_state.lock().unwrap();
+        let mut report = String::new();
+        Self::render_scope_level(&mut report, &state.tree, 0);
+        report
+    }
+
+    fn render_scope_level(report: &mut String, level: &HashMap<String, ScopeStats>, depth: usize) {
+        let mut names: Vec<&String> = level.keys().collect();
+        names.sort();
+        for name in names {
+            let stats = &level[name];
+            report.push_str(&format!(
+                "{}{name}: self={:?} child={:?} calls={}\n",
+                "  ".repeat(depth),
+                stats.self_time,
+                stats.child_time,
+                stats.call_count
+            ));
+            Self::render_scope_level(report, &stats.children, depth + 1);
+        }
+    }
+
     /// Collect current system metrics
-    async fn collect_metrics(&self) -> Result<ProfilingMetrics> {
+    async fn collect_metrics(&mut self) -> Result<ProfilingMetrics> {
+        // Sample the host's raw counters once, diffing against the previous tick so every
+        // metric below reports a per-interval rate rather than a cumulative total.
+        let sample = self.metric_source.sample()?;
+        let gpu_metrics = self.collect_gpu_metrics().await?;
+        self.build_metrics_from_sample(sample, gpu_metrics)
+    }
+
+    /// Fan sampling out across every tracked `sources` entity (e.g. per-rank workers in a
+    /// distributed job) and merge their per-interval counters into one aggregate
+    /// [`RawMetricSample`], as if they were one job's total. Below
+    /// `config.parallel_sample_threshold` entities, sampling runs serially on the caller's
+    /// thread, since dispatching to rayon's pool would cost more than it saves for a handful of
+    /// targets; at or above the threshold, sampling fans out across rayon's global pool.
+    ///
+    /// Samples are merged in `sources`' original order regardless of which thread finished
+    /// first, so the result is deterministic and independent of scheduling.
+    fn sample_entities_parallel(&self, sources: &mut [Box<dyn MetricSource>]) -> Result<RawMetricSample> {
+        let samples: Vec<RawMetricSample> = if sources.len() >= self.config.parallel_sample_threshold {
+            sources.par_iter_mut().map(|source| source.sample()).collect::<Result<Vec<_>>>()?
+        } else {
+            sources.iter_mut().map(|source| source.sample()).collect::<Result<Vec<_>>>()?
+        };
+        Ok(Self::merge_samples(&samples))
+    }
+
+    /// Sum every raw counter across `samples` into one aggregate. `Option` fields (cgroup
+    /// counters, present only when a source actually samples from inside a cgroup) stay `None`
+    /// only when every sample was `None`, so a mixed fleet of cgrouped and non-cgrouped sources
+    /// still aggregates the ones that reported a value.
+    fn merge_samples(samples: &[RawMetricSample]) -> RawMetricSample {
+        let add_options = |a: Option<u64>, b: Option<u64>| match (a, b) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+        };
+
+        let mut merged = RawMetricSample::default();
+        for sample in samples {
+            merged.cpu_total_jiffies += sample.cpu_total_jiffies;
+            merged.cpu_idle_jiffies += sample.cpu_idle_jiffies;
+            merged.mem_total_bytes += sample.mem_total_bytes;
+            merged.mem_available_bytes += sample.mem_available_bytes;
+            merged.disk_bytes_read += sample.disk_bytes_read;
+            merged.disk_bytes_written += sample.disk_bytes_written;
+            merged.net_rx_bytes += sample.net_rx_bytes;
+            merged.net_tx_bytes += sample.net_tx_bytes;
+            merged.cgroup_cpu_usage_usec = add_options(merged.cgroup_cpu_usage_usec, sample.cgroup_cpu_usage_usec);
+            merged.cgroup_throttled_usec = add_options(merged.cgroup_throttled_usec, sample.cgroup_throttled_usec);
+            merged.cgroup_nr_throttled = add_options(merged.cgroup_nr_throttled, sample.cgroup_nr_throttled);
+            merged.cgroup_memory_anon_bytes =
+                add_options(merged.cgroup_memory_anon_bytes, sample.cgroup_memory_anon_bytes);
+            merged.cgroup_memory_file_bytes =
+                add_options(merged.cgroup_memory_file_bytes, sample.cgroup_memory_file_bytes);
+        }
+        merged
+    }
+
+    /// Sample and merge many tracked entities (see [`Profiler::sample_entities_parallel`]) and
+    /// append the resulting aggregate sample to `metrics_buffer` through the same diffing
+    /// pipeline [`Profiler::collect_metrics`] uses, so the buffer's oldest-to-newest ordering
+    /// that [`Profiler::cleanup_old_metrics`]'s count-based retention assumes is unaffected by
+    /// the parallel fan-out.
+    pub async fn collect_metrics_parallel(&mut self, sources: &mut [Box<dyn MetricSource>]) -> Result<()> {
+        let sample = self.sample_entities_parallel(sources)?;
+        let gpu_metrics = self.collect_gpu_metrics().await?;
+        let metrics = self.build_metrics_from_sample(sample, gpu_metrics)?;
+        self.store_metrics(metrics);
+        self.cleanup_old_metrics();
+        Ok(())
+    }
+
+    /// Diff `sample` against the previous tick's raw counters and build the full
+    /// [`ProfilingMetrics`] for this tick. Shared by [`Profiler::collect_metrics`] and
+    /// [`Profiler::collect_metrics_parallel`] so both paths apply the exact same rate math.
+    fn build_metrics_from_sample(&mut self, sample: RawMetricSample, gpu_metrics: GpuSamples) -> Result<ProfilingMetrics> {
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
 
-        // Collect GPU metrics
-        let gpu_metrics = self.collect_gpu_metrics().await?;
-        
-        // Collect CPU metrics
-        let cpu_utilization = self.collect_cpu_utilization().await?;
-        
-        // Collect memory metrics
-        let memory_usage = self.collect_memory_usage().await?;
-        
-        // Collect I/O metrics
-        let (io_read, io_write) = self.collect_io_metrics().await?;
-        
-        // Collect network metrics
+        let previous = self.last_raw_sample.replace(sample);
+
+        let cpu_utilization = self.collect_cpu_utilization(previous, sample);
+        let memory_usage = self.collect_memory_usage(sample);
+        let (io_read, io_write) = self.collect_io_metrics(previous, sample);
         let (net_rx, net_tx) = if self.config.network_profiling {
-            self.collect_network_metrics().await?
+            self.collect_network_metrics(previous, sample)
         } else {
             (0, 0)
         };
+        let (cgroup_cpu_throttled_usec, cgroup_nr_throttled) =
+            Self::collect_cgroup_throttling(previous, sample);
 
         // Collect training metrics (would be updated by training loop)
         let training_metrics = TrainingMetrics {
@@ -184,74 +853,183 @@ impl Profiler {
 
         Ok(ProfilingMetrics {
             timestamp,
-            gpu_utilization: gpu_metrics.0,
-            gpu_memory_usage: gpu_metrics.1,
+            gpu_utilization: gpu_metrics.utilization,
+            gpu_memory_usage: gpu_metrics.memory_usage,
+            gpu_sm_clock_mhz: gpu_metrics.sm_clock_mhz,
+            gpu_sm_clock_max_mhz: gpu_metrics.sm_clock_max_mhz,
+            gpu_memory_clock_mhz: gpu_metrics.memory_clock_mhz,
+            gpu_power_draw_watts: gpu_metrics.power_draw_watts,
+            gpu_power_limit_watts: gpu_metrics.power_limit_watts,
             cpu_utilization,
             memory_usage,
             io_read_bytes: io_read,
             io_write_bytes: io_write,
             network_rx_bytes: net_rx,
             network_tx_bytes: net_tx,
+            cgroup_memory_anon_bytes: sample.cgroup_memory_anon_bytes,
+            cgroup_memory_file_bytes: sample.cgroup_memory_file_bytes,
+            cgroup_cpu_throttled_usec,
+            cgroup_nr_throttled,
             training_metrics,
+            retainer_snapshot: None,
         })
     }
 
+    /// Collect per-GPU utilization, memory, clocks, and power via NVML.
+    #[cfg(feature = "nvidia")]
+    async fn collect_gpu_metrics(&self) -> Result<GpuSamples> {
+        tokio::task::spawn_blocking(Self::collect_gpu_metrics_blocking)
+            .await
+            .map_err(|e| MLError::ProfilingError(format!("GPU metrics task panicked: {e}")))?
+    }
+
+    /// Blocking NVML enumeration backing [`Self::collect_gpu_metrics`]. Returns empty samples
+    /// rather than failing when no NVIDIA driver is present, since a missing GPU is a normal
+    /// (CPU-only) configuration — same convention as `hecate_ml::MLOptimizer::detect_gpu_info`.
+    #[cfg(feature = "nvidia")]
+    fn collect_gpu_metrics_blocking() -> Result<GpuSamples> {
+        use nvml_wrapper::enum_wrappers::device::Clock;
+        use nvml_wrapper::Nvml;
+
+        let nvml = match Nvml::init() {
+            Ok(nvml) => nvml,
+            Err(_) => return Ok(GpuSamples::default()), // No NVIDIA driver/GPU present
+        };
+
+        let device_count = nvml.device_count().map_err(|e| MLError::ProfilingError(e.to_string()))?;
+        let mut samples = GpuSamples::default();
+
+        for index in 0..device_count {
+            let device = match nvml.device_by_index(index) {
+                Ok(device) => device,
+                Err(_) => continue, // Skip GPUs that vanish mid-enumeration rather than failing the whole tick
+            };
+
+            samples.utilization.push(device.utilization_rates().map(|u| u.gpu as f32).unwrap_or(0.0));
+            samples.memory_usage.push(device.memory_info().map(|m| m.used).unwrap_or(0));
+            samples.sm_clock_mhz.push(device.clock_info(Clock::Graphics).unwrap_or(0));
+            samples.sm_clock_max_mhz.push(device.max_clock_info(Clock::Graphics).unwrap_or(0));
+            samples.memory_clock_mhz.push(device.clock_info(Clock::Memory).unwrap_or(0));
+            samples.power_draw_watts.push(device.power_usage().map(|mw| mw as f32 / 1000.0).unwrap_or(0.0));
+            samples
+                .power_limit_watts
+                .push(device.power_management_limit().map(|mw| mw as f32 / 1000.0).unwrap_or(0.0));
+        }
+
+        Ok(samples)
+    }
+
     /// Collect GPU metrics
-    async fn collect_gpu_metrics(&self) -> Result<(Vec<f32>, Vec<u64>)> {
+    #[cfg(not(feature = "nvidia"))]
+    async fn collect_gpu_metrics(&self) -> Result<GpuSamples> {
         // In a real implementation, this would interface with GPU monitoring APIs
         // For now, simulate some metrics
-        
+
         let gpu_count = self.get_gpu_count().await?;
-        let mut utilization = Vec::new();
-        let mut memory_usage = Vec::new();
+        let mut samples = GpuSamples::default();
 
         for _i in 0..gpu_count {
             // Simulate GPU metrics - in practice would query actual GPU status
-            utilization.push(50.0); // 50% utilization
-            memory_usage.push(4_000_000_000); // 4GB usage
+            samples.utilization.push(50.0); // 50% utilization
+            samples.memory_usage.push(4_000_000_000); // 4GB usage
+            samples.sm_clock_mhz.push(1_400);
+            samples.sm_clock_max_mhz.push(1_800);
+            samples.memory_clock_mhz.push(9_500);
+            samples.power_draw_watts.push(180.0);
+            samples.power_limit_watts.push(300.0);
         }
 
-        Ok((utilization, memory_usage))
+        Ok(samples)
     }
 
     /// Get GPU count
+    #[cfg(not(feature = "nvidia"))]
     async fn get_gpu_count(&self) -> Result<usize> {
         // Simulate GPU detection - in practice would query system
         Ok(1)
     }
 
-    /// Collect CPU utilization
-    async fn collect_cpu_utilization(&self) -> Result<f32> {
-        // Read from /proc/stat or use system monitoring library
-        // For now, return simulated value
-        Ok(30.0) // 30% CPU utilization
+    /// Percentage of CPU time consumed since the previous sample. When sampling from inside a
+    /// cgroup with a cpu controller attached, this is `usage_usec`'s growth relative to the
+    /// sampling interval — the job's own share, not the whole host's. Otherwise it falls back to
+    /// the fraction of `/proc/stat` jiffies spent non-idle. Returns `0.0` on the first tick, when
+    /// there's no previous sample to diff against.
+    fn collect_cpu_utilization(&self, previous: Option<RawMetricSample>, current: RawMetricSample) -> f32 {
+        let Some(previous) = previous else { return 0.0 };
+
+        if let (Some(prev_usec), Some(cur_usec)) =
+            (previous.cgroup_cpu_usage_usec, current.cgroup_cpu_usage_usec)
+        {
+            let elapsed_usec = self.config.sampling_interval.as_micros().max(1) as f32;
+            let usage_delta = cur_usec.saturating_sub(prev_usec) as f32;
+            return (usage_delta / elapsed_usec) * 100.0;
+        }
+
+        let total_delta = current.cpu_total_jiffies.saturating_sub(previous.cpu_total_jiffies);
+        if total_delta == 0 {
+            return 0.0;
+        }
+        let idle_delta = current.cpu_idle_jiffies.saturating_sub(previous.cpu_idle_jiffies);
+        (1.0 - idle_delta as f32 / total_delta as f32) * 100.0
+    }
+
+    /// Resident memory usage in bytes. This is an instantaneous reading, not a counter, so it
+    /// needs no diffing against the previous sample. When sampled from inside a cgroup with a
+    /// memory controller attached, `current.mem_available_bytes` already reflects the host total
+    /// minus `memory.current` (see [`CgroupMetricSource`]), so this yields the cgroup's own
+    /// usage rather than the whole host's.
+    fn collect_memory_usage(&self, current: RawMetricSample) -> u64 {
+        current.mem_total_bytes.saturating_sub(current.mem_available_bytes)
     }
 
-    /// Collect memory usage
-    async fn collect_memory_usage(&self) -> Result<u64> {
-        // Read from /proc/meminfo
-        // For now, return simulated value
-        Ok(8_000_000_000) // 8GB usage
+    /// Bytes read/written since the previous sample — per block device host-wide, or per the
+    /// cgroup's `io.stat` when sampled from inside one. Returns `(0, 0)` on the first tick.
+    fn collect_io_metrics(&self, previous: Option<RawMetricSample>, current: RawMetricSample) -> (u64, u64) {
+        let Some(previous) = previous else { return (0, 0) };
+        (
+            current.disk_bytes_read.saturating_sub(previous.disk_bytes_read),
+            current.disk_bytes_written.saturating_sub(previous.disk_bytes_written),
+        )
     }
 
-    /// Collect I/O metrics
-    async fn collect_io_metrics(&self) -> Result<(u64, u64)> {
-        // Read from /proc/diskstats
-        // For now, return simulated values
-        Ok((1_000_000, 500_000)) // 1MB read, 500KB write
+    /// Bytes received/sent across every non-loopback interface since the previous sample.
+    /// Returns `(0, 0)` on the first tick.
+    fn collect_network_metrics(&self, previous: Option<RawMetricSample>, current: RawMetricSample) -> (u64, u64) {
+        let Some(previous) = previous else { return (0, 0) };
+        (
+            current.net_rx_bytes.saturating_sub(previous.net_rx_bytes),
+            current.net_tx_bytes.saturating_sub(previous.net_tx_bytes),
+        )
     }
 
-    /// Collect network metrics
-    async fn collect_network_metrics(&self) -> Result<(u64, u64)> {
-        // Read from /proc/net/dev
-        // For now, return simulated values
-        Ok((10_000_000, 5_000_000)) // 10MB RX, 5MB TX
+    /// Throttling this tick, from the cgroup's `cpu.stat` (`None` when not sampled from inside
+    /// one). Returns `(None, None)` on the first tick, when there's no previous sample to diff.
+    fn collect_cgroup_throttling(
+        previous: Option<RawMetricSample>,
+        current: RawMetricSample,
+    ) -> (Option<u64>, Option<u64>) {
+        let Some(previous) = previous else { return (None, None) };
+        let throttled_usec = current
+            .cgroup_throttled_usec
+            .zip(previous.cgroup_throttled_usec)
+            .map(|(cur, prev)| cur.saturating_sub(prev));
+        let nr_throttled = current
+            .cgroup_nr_throttled
+            .zip(previous.cgroup_nr_throttled)
+            .map(|(cur, prev)| cur.saturating_sub(prev));
+        (throttled_usec, nr_throttled)
     }
 
     /// Store metrics in buffer
     fn store_metrics(&mut self, metrics: ProfilingMetrics) {
+        if let Some(store) = &mut self.store {
+            if let Err(e) = store.append(&metrics) {
+                warn!("Failed to persist metrics to the on-disk store: {}", e);
+            }
+        }
+
         self.metrics_buffer.push_back(metrics);
-        
+
         // Set baseline if first measurement
         if self.baseline_metrics.is_none() {
             self.baseline_metrics = self.metrics_buffer.back().cloned();
@@ -275,7 +1053,10 @@ impl Profiler {
 
             // Check GPU bottlenecks
             new_bottlenecks.extend(self.detect_gpu_bottlenecks(latest_metrics)?);
-            
+
+            // Check GPU thermal/power throttling
+            new_bottlenecks.extend(self.detect_gpu_throttling_bottlenecks(latest_metrics)?);
+
             // Check CPU bottlenecks
             new_bottlenecks.extend(self.detect_cpu_bottlenecks(latest_metrics)?);
             
@@ -288,6 +1069,12 @@ impl Profiler {
             // Check data loading bottlenecks
             new_bottlenecks.extend(self.detect_data_loading_bottlenecks(latest_metrics)?);
 
+            // Check cgroup CPU throttling
+            new_bottlenecks.extend(self.detect_cpu_throttling_bottlenecks(latest_metrics)?);
+
+            // Check for statistically significant regressions across the metrics history
+            new_bottlenecks.extend(self.detect_statistical_regressions()?);
+
             // Update bottlenecks list
             self.bottlenecks = new_bottlenecks;
         }
@@ -337,6 +1124,52 @@ impl Profiler {
         Ok(bottlenecks)
     }
 
+    /// Detect GPUs that are thermally or power throttled: their clock sits well below their own
+    /// max boost clock while their power draw is pinned at the TDP cap. This looks like the
+    /// "low utilization" bottleneck above from the outside, but the fix is different — improve
+    /// cooling or raise the power limit, not increase batch size.
+    fn detect_gpu_throttling_bottlenecks(&self, metrics: &ProfilingMetrics) -> Result<Vec<Bottleneck>> {
+        let mut bottlenecks = Vec::new();
+
+        for i in 0..metrics.gpu_sm_clock_mhz.len() {
+            let (Some(&clock), Some(&clock_max), Some(&power), Some(&power_limit)) = (
+                metrics.gpu_sm_clock_mhz.get(i),
+                metrics.gpu_sm_clock_max_mhz.get(i),
+                metrics.gpu_power_draw_watts.get(i),
+                metrics.gpu_power_limit_watts.get(i),
+            ) else {
+                continue;
+            };
+            if clock_max == 0 || power_limit <= 0.0 {
+                continue;
+            }
+
+            let clock_ratio = clock as f64 / clock_max as f64;
+            let power_ratio = power as f64 / power_limit as f64;
+
+            if clock_ratio < 0.8 && power_ratio > 0.97 {
+                bottlenecks.push(Bottleneck {
+                    bottleneck_type: BottleneckType::GpuThrottling,
+                    severity: if clock_ratio < 0.6 { BottleneckSeverity::High } else { BottleneckSeverity::Medium },
+                    description: format!(
+                        "GPU {i} is power/thermal throttled: clock is {:.0}% of boost while power draw is {:.0}% of the TDP cap",
+                        clock_ratio * 100.0,
+                        power_ratio * 100.0
+                    ),
+                    metric_name: format!("gpu_{i}_sm_clock_mhz"),
+                    current_value: clock as f64,
+                    threshold: clock_max as f64,
+                    recommendation: "Improve cooling or raise the GPU's power limit; the clock \
+                        is capped by thermal/power headroom, not by workload demand, so a larger \
+                        batch size won't help"
+                        .to_string(),
+                });
+            }
+        }
+
+        Ok(bottlenecks)
+    }
+
     /// Detect CPU bottlenecks
     fn detect_cpu_bottlenecks(&self, metrics: &ProfilingMetrics) -> Result<Vec<Bottleneck>> {
         let mut bottlenecks = Vec::new();
@@ -388,8 +1221,14 @@ impl Profiler {
     fn detect_io_bottlenecks(&self, metrics: &ProfilingMetrics) -> Result<Vec<Bottleneck>> {
         let mut bottlenecks = Vec::new();
 
-        // Check if I/O is unusually high compared to baseline
+        // Check if I/O is unusually high compared to baseline. The very first sample has no
+        // previous tick to diff against, so its I/O deltas are always zero; skip it rather than
+        // treating every later tick's throughput as an infinite-ratio spike.
         if let Some(ref baseline) = self.baseline_metrics {
+            if baseline.io_read_bytes == 0 && baseline.io_write_bytes == 0 {
+                return Ok(bottlenecks);
+            }
+
             let read_ratio = metrics.io_read_bytes as f64 / baseline.io_read_bytes.max(1) as f64;
             let write_ratio = metrics.io_write_bytes as f64 / baseline.io_write_bytes.max(1) as f64;
 
@@ -439,6 +1278,114 @@ impl Profiler {
         Ok(bottlenecks)
     }
 
+    /// Detect cgroup CPU throttling. Fires when `throttled_usec` grew by more than the sampling
+    /// interval this tick, since a throttled-but-"low utilization" job otherwise produces a
+    /// misleading GPU-underutilization recommendation instead of the real fix (raise the
+    /// container's CPU limit).
+    fn detect_cpu_throttling_bottlenecks(&self, metrics: &ProfilingMetrics) -> Result<Vec<Bottleneck>> {
+        let mut bottlenecks = Vec::new();
+
+        if let Some(throttled_usec) = metrics.cgroup_cpu_throttled_usec {
+            let interval_usec = self.config.sampling_interval.as_micros() as u64;
+            if throttled_usec > interval_usec {
+                bottlenecks.push(Bottleneck {
+                    bottleneck_type: BottleneckType::CpuThrottling,
+                    severity: if throttled_usec > interval_usec * 2 {
+                        BottleneckSeverity::High
+                    } else {
+                        BottleneckSeverity::Medium
+                    },
+                    description: "Workload's cgroup is being CPU-throttled".to_string(),
+                    metric_name: "cgroup_cpu_throttled_usec".to_string(),
+                    current_value: throttled_usec as f64,
+                    threshold: interval_usec as f64,
+                    recommendation: "Raise the container's CPU limit (cpu.max) or reduce worker \
+                        thread count to fit within it"
+                        .to_string(),
+                });
+            }
+        }
+
+        Ok(bottlenecks)
+    }
+
+    /// Detect gradual regressions in each scalar metric series by comparing an earlier baseline
+    /// window against the most recent window with a Welch-style test: flag a regression when the
+    /// windows' means differ by more than [`ProfilingConfig::regression_threshold`] pooled
+    /// standard deviations (`sqrt(var_a/n_a + var_b/n_b)`). Unlike the fixed-threshold detectors
+    /// above, this catches slow drift — e.g. step time creeping up from a memory leak — well
+    /// before any single sample crosses a static cutoff.
+    fn detect_statistical_regressions(&self) -> Result<Vec<Bottleneck>> {
+        let mut bottlenecks = Vec::new();
+
+        let recent_window = self.config.regression_recent_window;
+        let baseline_window = self.config.regression_baseline_window;
+        if self.metrics_buffer.len() < recent_window + baseline_window {
+            return Ok(bottlenecks);
+        }
+
+        let series: &[(&str, fn(&ProfilingMetrics) -> f64)] = &[
+            ("cpu_utilization", |m| m.cpu_utilization as f64),
+            ("memory_usage", |m| m.memory_usage as f64),
+            ("io_read_bytes", |m| m.io_read_bytes as f64),
+            ("io_write_bytes", |m| m.io_write_bytes as f64),
+            ("network_rx_bytes", |m| m.network_rx_bytes as f64),
+            ("network_tx_bytes", |m| m.network_tx_bytes as f64),
+        ];
+
+        for (metric_name, extract) in series {
+            let values: Vec<f64> = self.metrics_buffer.iter().map(|m| extract(m)).collect();
+            let recent = &values[values.len() - recent_window..];
+            let baseline_end = values.len() - recent_window;
+            let baseline = &values[baseline_end - baseline_window..baseline_end];
+
+            let baseline_stats = WindowStats::from_samples(baseline);
+            let recent_stats = WindowStats::from_samples(recent);
+
+            let pooled_std = (baseline_stats.variance / baseline_stats.n as f64
+                + recent_stats.variance / recent_stats.n as f64)
+                .sqrt();
+            let diff = recent_stats.mean - baseline_stats.mean;
+            // A perfectly flat series in both windows has zero pooled variance; treat any
+            // nonzero mean shift there as an unbounded effect size rather than skipping it.
+            let effect = if pooled_std > 0.0 {
+                diff.abs() / pooled_std
+            } else if diff.abs() > f64::EPSILON {
+                f64::INFINITY
+            } else {
+                0.0
+            };
+            if effect <= self.config.regression_threshold {
+                continue;
+            }
+
+            let relative_change =
+                if baseline_stats.mean.abs() > f64::EPSILON { diff / baseline_stats.mean * 100.0 } else { 0.0 };
+
+            bottlenecks.push(Bottleneck {
+                bottleneck_type: BottleneckType::Regression,
+                severity: if effect > self.config.regression_threshold * 1.5 {
+                    BottleneckSeverity::High
+                } else {
+                    BottleneckSeverity::Medium
+                },
+                description: format!(
+                    "{metric_name} has regressed: recent mean {:.2} vs baseline mean {:.2} ({relative_change:+.1}%)",
+                    recent_stats.mean, baseline_stats.mean
+                ),
+                metric_name: metric_name.to_string(),
+                current_value: recent_stats.mean,
+                threshold: baseline_stats.mean,
+                recommendation: "Investigate recent changes around this metric; a gradually \
+                    worsening trend across samples points at a leak or creeping contention \
+                    rather than a one-off spike"
+                    .to_string(),
+            });
+        }
+
+        Ok(bottlenecks)
+    }
+
     /// Get current bottlenecks
     pub fn get_bottlenecks(&self) -> &[Bottleneck] {
         &self.bottlenecks
@@ -526,17 +1473,166 @@ impl Profiler {
         }
     }
 
+    /// Attach a [`crate::retainer::trace_retainers`] result to the most recent sample, so it
+    /// rides along in `metrics_buffer` and ages out through the normal
+    /// [`Profiler::cleanup_old_metrics`] retention window like every other metric.
+    pub fn record_retainer_snapshot(&mut self, snapshot: RetainerSnapshot) {
+        if let Some(latest) = self.metrics_buffer.back_mut() {
+            latest.retainer_snapshot = Some(snapshot);
+        }
+    }
+
     /// Export metrics to file
     pub fn export_metrics(&self, path: &str) -> Result<()> {
         let json = serde_json::to_string_pretty(&self.metrics_buffer)
             .map_err(MLError::SerializationError)?;
-        
+
         std::fs::write(path, json)
             .map_err(MLError::IoError)?;
-        
+
         info!("Exported metrics to {}", path);
         Ok(())
     }
+
+    /// Write the metrics history through `writer` in the chosen [`MetricsFormat`] — JSON, CSV,
+    /// Markdown, or the compact self-describing binary encoding — so the profiler can feed
+    /// external dashboards and collectors instead of only living in memory. See
+    /// [`crate::metrics_export`] for the format details.
+    pub fn export(&self, format: MetricsFormat, writer: impl std::io::Write) -> Result<()> {
+        let metrics: Vec<ProfilingMetrics> = self.metrics_buffer.iter().cloned().collect();
+        metrics_export::write_metrics(&metrics, format, writer)
+    }
+
+    /// Export the metrics buffer as a Chrome/Perfetto trace: per-tick counter events for
+    /// CPU/memory/IO/GPU utilization on one track, and a complete duration event per captured
+    /// training phase (batch/forward/backward/optimizer/data-loading) on its own track, so stalls
+    /// in one phase can be seen overlapping idle gaps in another instead of comparing raw numbers.
+    pub fn export_trace(&self, path: &str) -> Result<()> {
+        let mut events = Vec::new();
+        events.push(TraceEvent::thread_name(TRACE_COUNTER_PID, 1, "Resource Counters"));
+        for (tid, name) in TRACE_PHASE_TRACKS {
+            events.push(TraceEvent::thread_name(TRACE_PHASE_PID, *tid, name));
+        }
+
+        for metrics in &self.metrics_buffer {
+            // Trace Event Format timestamps are microseconds; our samples only carry
+            // second-granularity Unix timestamps, so every event within a tick lands at the
+            // same `ts`.
+            let ts = metrics.timestamp.saturating_mul(1_000_000);
+
+            events.push(TraceEvent {
+                name: "CPU Utilization".to_string(),
+                ph: "C",
+                ts,
+                dur: None,
+                pid: TRACE_COUNTER_PID,
+                tid: 1,
+                args: HashMap::from([("percent".to_string(), serde_json::json!(metrics.cpu_utilization))]),
+            });
+            events.push(TraceEvent {
+                name: "Memory Usage".to_string(),
+                ph: "C",
+                ts,
+                dur: None,
+                pid: TRACE_COUNTER_PID,
+                tid: 1,
+                args: HashMap::from([("bytes".to_string(), serde_json::json!(metrics.memory_usage))]),
+            });
+            events.push(TraceEvent {
+                name: "I/O".to_string(),
+                ph: "C",
+                ts,
+                dur: None,
+                pid: TRACE_COUNTER_PID,
+                tid: 1,
+                args: HashMap::from([
+                    ("read_bytes".to_string(), serde_json::json!(metrics.io_read_bytes)),
+                    ("write_bytes".to_string(), serde_json::json!(metrics.io_write_bytes)),
+                ]),
+            });
+            if !metrics.gpu_utilization.is_empty() {
+                let args = metrics
+                    .gpu_utilization
+                    .iter()
+                    .enumerate()
+                    .map(|(i, util)| (format!("gpu_{i}"), serde_json::json!(util)))
+                    .collect();
+                events.push(TraceEvent {
+                    name: "GPU Utilization".to_string(),
+                    ph: "C",
+                    ts,
+                    dur: None,
+                    pid: TRACE_COUNTER_PID,
+                    tid: 1,
+                    args,
+                });
+            }
+
+            let phases: [(&str, Option<Duration>); 5] = [
+                ("batch", metrics.training_metrics.batch_time),
+                ("forward", metrics.training_metrics.forward_time),
+                ("backward", metrics.training_metrics.backward_time),
+                ("optimizer", metrics.training_metrics.optimizer_time),
+                ("data_loading", metrics.training_metrics.data_loading_time),
+            ];
+            for (name, duration) in phases {
+                let Some(duration) = duration else { continue };
+                let Some(&(tid, _)) = TRACE_PHASE_TRACKS.iter().find(|(_, track_name)| *track_name == name) else {
+                    continue;
+                };
+                events.push(TraceEvent {
+                    name: name.to_string(),
+                    ph: "X",
+                    ts,
+                    dur: Some(duration.as_micros() as u64),
+                    pid: TRACE_PHASE_PID,
+                    tid,
+                    args: HashMap::new(),
+                });
+            }
+        }
+
+        let json = serde_json::to_string_pretty(&events).map_err(MLError::SerializationError)?;
+        std::fs::write(path, json).map_err(MLError::IoError)?;
+
+        info!("Exported trace to {}", path);
+        Ok(())
+    }
+
+    /// Micro-benchmark a candidate config: turn `samples` (one measured duration per iteration,
+    /// in collection order) into a [`BenchmarkReport`] by discarding the first `warmup`
+    /// iterations and computing a mean, bootstrap confidence interval, and Tukey-fence outlier
+    /// classification over the rest, so a recommendation can be gated on whether its apparent
+    /// speedup is statistically real rather than noise (see [`crate::benchmark::is_significant_speedup`]).
+    pub fn benchmark_candidate(&self, samples: &[Duration], warmup: usize) -> Result<BenchmarkReport> {
+        crate::benchmark::run_micro_benchmark(samples, warmup).ok_or_else(|| {
+            MLError::ProfilingError(format!(
+                "not enough samples to benchmark: got {}, need more than {warmup} warmup iterations",
+                samples.len()
+            ))
+        })
+    }
+
+    /// Run a named statistical micro-benchmark of `f`: discard
+    /// [`ProfilingConfig::benchmark_warmup_iters`] warmup calls, then time `iters` calls and
+    /// summarize them with [`crate::benchmark::run_benchmark`] (mean, median, standard deviation,
+    /// min, max, and a modified-Z-score outlier count), logging the result under `name`.
+    pub fn benchmark<F: FnMut()>(&self, name: &str, iters: usize, f: F) -> Result<crate::benchmark::BenchmarkSummary> {
+        let summary = crate::benchmark::run_benchmark(f, self.config.benchmark_warmup_iters, iters)
+            .ok_or_else(|| MLError::ProfilingError(format!("benchmark '{name}' needs at least one timed iteration, got {iters}")))?;
+
+        info!(
+            "Benchmark '{name}': mean={:.6}s median={:.6}s stddev={:.6}s min={:.6}s max={:.6}s outliers={}/{}",
+            summary.mean_secs,
+            summary.median_secs,
+            summary.stddev_secs,
+            summary.min_secs,
+            summary.max_secs,
+            summary.outliers.outlier_count,
+            summary.sample_count
+        );
+        Ok(summary)
+    }
 }
 
 /// Performance summary
@@ -560,6 +1656,13 @@ impl Default for ProfilingConfig {
             detailed_timing: false,
             memory_profiling: true,
             network_profiling: false,
+            scope_filter: String::new(),
+            scope_min_duration: Duration::ZERO,
+            regression_baseline_window: 20,
+            regression_recent_window: 5,
+            regression_threshold: 2.5,
+            benchmark_warmup_iters: 3,
+            parallel_sample_threshold: 8,
         }
     }
 }
@@ -585,12 +1688,21 @@ mod tests {
             timestamp: 1000,
             gpu_utilization: vec![50.0],
             gpu_memory_usage: vec![4_000_000_000],
+            gpu_sm_clock_mhz: vec![],
+            gpu_sm_clock_max_mhz: vec![],
+            gpu_memory_clock_mhz: vec![],
+            gpu_power_draw_watts: vec![],
+            gpu_power_limit_watts: vec![],
             cpu_utilization: 30.0,
             memory_usage: 8_000_000_000,
             io_read_bytes: 1000,
             io_write_bytes: 500,
             network_rx_bytes: 10000,
             network_tx_bytes: 5000,
+            cgroup_memory_anon_bytes: None,
+            cgroup_memory_file_bytes: None,
+            cgroup_cpu_throttled_usec: None,
+            cgroup_nr_throttled: None,
             training_metrics: TrainingMetrics {
                 batch_time: Some(Duration::from_millis(100)),
                 forward_time: Some(Duration::from_millis(50)),
@@ -601,6 +1713,7 @@ mod tests {
                 learning_rate: Some(0.001),
                 gradients_norm: Some(1.0),
             },
+            retainer_snapshot: None,
         };
 
         profiler.store_metrics(metrics);
@@ -618,12 +1731,21 @@ mod tests {
             timestamp: 1000,
             gpu_utilization: vec![10.0], // Low utilization
             gpu_memory_usage: vec![4_000_000_000],
+            gpu_sm_clock_mhz: vec![],
+            gpu_sm_clock_max_mhz: vec![],
+            gpu_memory_clock_mhz: vec![],
+            gpu_power_draw_watts: vec![],
+            gpu_power_limit_watts: vec![],
             cpu_utilization: 30.0,
             memory_usage: 8_000_000_000,
             io_read_bytes: 1000,
             io_write_bytes: 500,
             network_rx_bytes: 10000,
             network_tx_bytes: 5000,
+            cgroup_memory_anon_bytes: None,
+            cgroup_memory_file_bytes: None,
+            cgroup_cpu_throttled_usec: None,
+            cgroup_nr_throttled: None,
             training_metrics: TrainingMetrics {
                 batch_time: None,
                 forward_time: None,
@@ -634,6 +1756,7 @@ mod tests {
                 learning_rate: None,
                 gradients_norm: None,
             },
+            retainer_snapshot: None,
         };
 
         profiler.store_metrics(metrics);
@@ -679,12 +1802,21 @@ mod tests {
                 timestamp: i,
                 gpu_utilization: vec![50.0],
                 gpu_memory_usage: vec![4_000_000_000],
+                gpu_sm_clock_mhz: vec![],
+                gpu_sm_clock_max_mhz: vec![],
+                gpu_memory_clock_mhz: vec![],
+                gpu_power_draw_watts: vec![],
+                gpu_power_limit_watts: vec![],
                 cpu_utilization: 30.0,
                 memory_usage: 8_000_000_000,
                 io_read_bytes: 1000,
                 io_write_bytes: 500,
                 network_rx_bytes: 10000,
                 network_tx_bytes: 5000,
+                cgroup_memory_anon_bytes: None,
+                cgroup_memory_file_bytes: None,
+                cgroup_cpu_throttled_usec: None,
+                cgroup_nr_throttled: None,
                 training_metrics: TrainingMetrics {
                     batch_time: None,
                     forward_time: None,
@@ -695,6 +1827,7 @@ mod tests {
                     learning_rate: None,
                     gradients_norm: None,
                 },
+                retainer_snapshot: None,
             };
             profiler.store_metrics(metrics);
         }
@@ -706,4 +1839,487 @@ mod tests {
         // Should keep only 2 metrics (retention_period / sampling_interval = 2)
         assert_eq!(profiler.metrics_buffer.len(), 2);
     }
+
+    #[test]
+    fn test_collectors_return_zero_deltas_without_a_previous_sample() {
+        let profiler = Profiler::new(ProfilingConfig::default());
+        let current = RawMetricSample { cpu_total_jiffies: 1000, cpu_idle_jiffies: 700, ..Default::default() };
+
+        assert_eq!(profiler.collect_cpu_utilization(None, current), 0.0);
+        assert_eq!(profiler.collect_io_metrics(None, current), (0, 0));
+        assert_eq!(profiler.collect_network_metrics(None, current), (0, 0));
+    }
+
+    #[test]
+    fn test_collect_cpu_utilization_diffs_jiffies_between_samples() {
+        let profiler = Profiler::new(ProfilingConfig::default());
+        let previous = RawMetricSample { cpu_total_jiffies: 1000, cpu_idle_jiffies: 800, ..Default::default() };
+        let current = RawMetricSample { cpu_total_jiffies: 1100, cpu_idle_jiffies: 850, ..Default::default() };
+
+        // 100 jiffies elapsed, 50 of them idle -> 50% utilization
+        assert_eq!(profiler.collect_cpu_utilization(Some(previous), current), 50.0);
+    }
+
+    #[test]
+    fn test_collect_memory_usage_is_total_minus_available() {
+        let profiler = Profiler::new(ProfilingConfig::default());
+        let current = RawMetricSample { mem_total_bytes: 16_000_000_000, mem_available_bytes: 10_000_000_000, ..Default::default() };
+
+        assert_eq!(profiler.collect_memory_usage(current), 6_000_000_000);
+    }
+
+    #[test]
+    fn test_collect_io_metrics_diffs_byte_counters() {
+        let profiler = Profiler::new(ProfilingConfig::default());
+        let previous = RawMetricSample { disk_bytes_read: 100, disk_bytes_written: 50, ..Default::default() };
+        let current = RawMetricSample { disk_bytes_read: 300, disk_bytes_written: 60, ..Default::default() };
+
+        assert_eq!(profiler.collect_io_metrics(Some(previous), current), (200, 10));
+    }
+
+    #[test]
+    fn test_collect_network_metrics_diffs_byte_counters() {
+        let profiler = Profiler::new(ProfilingConfig::default());
+        let previous = RawMetricSample { net_rx_bytes: 1_000, net_tx_bytes: 500, ..Default::default() };
+        let current = RawMetricSample { net_rx_bytes: 1_500, net_tx_bytes: 900, ..Default::default() };
+
+        assert_eq!(profiler.collect_network_metrics(Some(previous), current), (500, 400));
+    }
+
+    #[tokio::test]
+    async fn test_simulated_metric_source_advances_by_a_fixed_amount_each_tick() {
+        let config = ProfilingConfig::default();
+        let mut profiler = Profiler::with_metric_source(config, Box::new(SimulatedMetricSource::default()));
+
+        // First tick has no previous sample to diff against, so every rate is zero.
+        let first = profiler.collect_metrics().await.unwrap();
+        assert_eq!(first.cpu_utilization, 0.0);
+
+        // The second tick diffs against the first, yielding the source's steady-state rates.
+        let second = profiler.collect_metrics().await.unwrap();
+        assert!((second.cpu_utilization - 30.0).abs() < 0.01);
+        assert_eq!(second.io_read_bytes, 2_000 * 512);
+        assert_eq!(second.io_write_bytes, 1_000 * 512);
+    }
+
+    #[test]
+    fn test_merge_samples_sums_counters_across_every_tracked_entity() {
+        let samples = vec![
+            RawMetricSample { disk_bytes_read: 100, net_rx_bytes: 10, ..Default::default() },
+            RawMetricSample { disk_bytes_read: 200, net_rx_bytes: 20, ..Default::default() },
+        ];
+        let merged = Profiler::merge_samples(&samples);
+        assert_eq!(merged.disk_bytes_read, 300);
+        assert_eq!(merged.net_rx_bytes, 30);
+    }
+
+    #[test]
+    fn test_merge_samples_sums_cgroup_counters_only_present_on_some_entities() {
+        let samples = vec![
+            RawMetricSample { cgroup_nr_throttled: Some(2), ..Default::default() },
+            RawMetricSample { cgroup_nr_throttled: None, ..Default::default() },
+        ];
+        let merged = Profiler::merge_samples(&samples);
+        assert_eq!(merged.cgroup_nr_throttled, Some(2));
+    }
+
+    #[test]
+    fn test_merge_samples_of_an_all_none_cgroup_field_stays_none() {
+        let samples = vec![RawMetricSample::default(), RawMetricSample::default()];
+        let merged = Profiler::merge_samples(&samples);
+        assert_eq!(merged.cgroup_nr_throttled, None);
+    }
+
+    #[tokio::test]
+    async fn test_sample_entities_parallel_merges_every_source_below_the_threshold() {
+        let mut config = ProfilingConfig::default();
+        config.parallel_sample_threshold = 100; // force the serial path
+        let profiler = Profiler::new(config);
+
+        let mut sources: Vec<Box<dyn MetricSource>> = vec![
+            Box::new(SimulatedMetricSource::default()),
+            Box::new(SimulatedMetricSource::default()),
+        ];
+        let merged = profiler.sample_entities_parallel(&mut sources).unwrap();
+        assert_eq!(merged.disk_bytes_read, 1_024_000 * 2);
+    }
+
+    #[tokio::test]
+    async fn test_sample_entities_parallel_merges_every_source_at_or_above_the_threshold() {
+        let mut config = ProfilingConfig::default();
+        config.parallel_sample_threshold = 1; // force the rayon path
+        let profiler = Profiler::new(config);
+
+        let mut sources: Vec<Box<dyn MetricSource>> = vec![
+            Box::new(SimulatedMetricSource::default()),
+            Box::new(SimulatedMetricSource::default()),
+            Box::new(SimulatedMetricSource::default()),
+        ];
+        let merged = profiler.sample_entities_parallel(&mut sources).unwrap();
+        assert_eq!(merged.disk_bytes_read, 1_024_000 * 3);
+    }
+
+    #[tokio::test]
+    async fn test_collect_metrics_parallel_appends_one_merged_sample_to_the_buffer() {
+        let config = ProfilingConfig::default();
+        let mut profiler = Profiler::new(config);
+
+        let mut sources: Vec<Box<dyn MetricSource>> = vec![
+            Box::new(SimulatedMetricSource::default()),
+            Box::new(SimulatedMetricSource::default()),
+        ];
+        profiler.collect_metrics_parallel(&mut sources).await.unwrap();
+        profiler.collect_metrics_parallel(&mut sources).await.unwrap();
+
+        assert_eq!(profiler.metrics_buffer.len(), 2);
+        // The second tick diffs against the merged first tick, so the steady-state rate reflects
+        // both sources rather than either one alone.
+        assert_eq!(profiler.metrics_buffer.back().unwrap().io_read_bytes, 2_000 * 512 * 2);
+    }
+
+    #[test]
+    fn test_resume_from_store_replays_persisted_history_into_the_buffer() {
+        let dir = std::env::temp_dir().join(format!(
+            "hecate-ml-profiler-resume-test-{:?}",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut store =
+            crate::metrics_store::MetricsStore::open(&dir, Duration::from_secs(1), Duration::from_secs(60)).unwrap();
+        store.append(&metrics_with_cpu(10.0)).unwrap();
+        store.append(&metrics_with_cpu(20.0)).unwrap();
+
+        let mut profiler = Profiler::new(ProfilingConfig::default());
+        profiler.resume_from_store(store).unwrap();
+
+        assert_eq!(profiler.metrics_buffer.len(), 2);
+        profiler.store_metrics(metrics_with_cpu(30.0));
+        assert_eq!(profiler.metrics_buffer.len(), 3);
+
+        // The newly-stored sample was flushed back out to the same on-disk segments.
+        let reopened =
+            crate::metrics_store::MetricsStore::open(&dir, Duration::from_secs(1), Duration::from_secs(60)).unwrap();
+        assert_eq!(reopened.replay().unwrap().len(), 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_collect_cpu_utilization_prefers_cgroup_usage_over_host_jiffies() {
+        let mut config = ProfilingConfig::default();
+        config.sampling_interval = Duration::from_secs(1);
+        let profiler = Profiler::new(config);
+
+        // Host jiffies say 50% busy, but the cgroup only used 200ms of the 1s interval (20%) —
+        // the cgroup figure should win since it reflects the job's own share, not the host's.
+        let previous = RawMetricSample {
+            cpu_total_jiffies: 1000,
+            cpu_idle_jiffies: 500,
+            cgroup_cpu_usage_usec: Some(1_000_000),
+            ..Default::default()
+        };
+        let current = RawMetricSample {
+            cpu_total_jiffies: 1100,
+            cpu_idle_jiffies: 550,
+            cgroup_cpu_usage_usec: Some(1_200_000),
+            ..Default::default()
+        };
+
+        assert!((profiler.collect_cpu_utilization(Some(previous), current) - 20.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_collect_cgroup_throttling_diffs_cumulative_counters() {
+        let previous = RawMetricSample { cgroup_throttled_usec: Some(1_000), cgroup_nr_throttled: Some(2), ..Default::default() };
+        let current = RawMetricSample { cgroup_throttled_usec: Some(1_500), cgroup_nr_throttled: Some(3), ..Default::default() };
+
+        assert_eq!(Profiler::collect_cgroup_throttling(Some(previous), current), (Some(500), Some(1)));
+    }
+
+    #[test]
+    fn test_collect_cgroup_throttling_is_none_outside_a_cgroup() {
+        let previous = RawMetricSample::default();
+        let current = RawMetricSample::default();
+
+        assert_eq!(Profiler::collect_cgroup_throttling(Some(previous), current), (None, None));
+    }
+
+    #[tokio::test]
+    async fn test_detect_cpu_throttling_bottleneck_fires_when_throttled_time_exceeds_the_interval() {
+        let mut config = ProfilingConfig::default();
+        config.sampling_interval = Duration::from_millis(100);
+        let mut profiler = Profiler::new(config);
+
+        let mut metrics = profiler.collect_metrics().await.unwrap();
+        metrics.cgroup_cpu_throttled_usec = Some(250_000); // 250ms throttled within a 100ms tick
+        profiler.store_metrics(metrics);
+        profiler.detect_bottlenecks().await.unwrap();
+
+        assert!(profiler
+            .bottlenecks
+            .iter()
+            .any(|b| matches!(b.bottleneck_type, BottleneckType::CpuThrottling)));
+    }
+
+    #[test]
+    fn test_detect_gpu_throttling_bottleneck_fires_when_clock_is_capped_but_power_is_pinned() {
+        let profiler = Profiler::new(ProfilingConfig::default());
+        let mut metrics = metrics_with_cpu(30.0);
+        // Boosts to 1800MHz, but is only running at 900MHz (50%) while drawing 297 of 300W (99%).
+        metrics.gpu_sm_clock_mhz = vec![900];
+        metrics.gpu_sm_clock_max_mhz = vec![1_800];
+        metrics.gpu_power_draw_watts = vec![297.0];
+        metrics.gpu_power_limit_watts = vec![300.0];
+
+        let bottlenecks = profiler.detect_gpu_throttling_bottlenecks(&metrics).unwrap();
+        assert!(bottlenecks.iter().any(|b| matches!(b.bottleneck_type, BottleneckType::GpuThrottling)));
+    }
+
+    #[test]
+    fn test_detect_gpu_throttling_bottleneck_does_not_fire_for_an_idle_gpu() {
+        let profiler = Profiler::new(ProfilingConfig::default());
+        let mut metrics = metrics_with_cpu(30.0);
+        // Low clock, but power draw is nowhere near the cap -- this is just an idle GPU.
+        metrics.gpu_sm_clock_mhz = vec![300];
+        metrics.gpu_sm_clock_max_mhz = vec![1_800];
+        metrics.gpu_power_draw_watts = vec![40.0];
+        metrics.gpu_power_limit_watts = vec![300.0];
+
+        let bottlenecks = profiler.detect_gpu_throttling_bottlenecks(&metrics).unwrap();
+        assert!(bottlenecks.is_empty());
+    }
+
+    /// Build a minimal [`ProfilingMetrics`] with only `cpu_utilization` varying, for the
+    /// regression-detector tests below.
+    fn metrics_with_cpu(cpu_utilization: f32) -> ProfilingMetrics {
+        ProfilingMetrics {
+            timestamp: 0,
+            gpu_utilization: vec![],
+            gpu_memory_usage: vec![],
+            gpu_sm_clock_mhz: vec![],
+            gpu_sm_clock_max_mhz: vec![],
+            gpu_memory_clock_mhz: vec![],
+            gpu_power_draw_watts: vec![],
+            gpu_power_limit_watts: vec![],
+            cpu_utilization,
+            memory_usage: 0,
+            io_read_bytes: 0,
+            io_write_bytes: 0,
+            network_rx_bytes: 0,
+            network_tx_bytes: 0,
+            cgroup_memory_anon_bytes: None,
+            cgroup_memory_file_bytes: None,
+            cgroup_cpu_throttled_usec: None,
+            cgroup_nr_throttled: None,
+            training_metrics: TrainingMetrics {
+                batch_time: None,
+                forward_time: None,
+                backward_time: None,
+                optimizer_time: None,
+                data_loading_time: None,
+                loss: None,
+                learning_rate: None,
+                gradients_norm: None,
+            },
+            retainer_snapshot: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_detect_statistical_regressions_needs_enough_history() {
+        let mut config = ProfilingConfig::default();
+        config.regression_baseline_window = 20;
+        config.regression_recent_window = 5;
+        let profiler = Profiler::new(config);
+
+        assert!(profiler.detect_statistical_regressions().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_detect_statistical_regressions_flags_a_sustained_shift() {
+        let mut config = ProfilingConfig::default();
+        config.regression_baseline_window = 20;
+        config.regression_recent_window = 5;
+        config.regression_threshold = 2.0;
+        let mut profiler = Profiler::new(config);
+
+        // Stable baseline around 20%, then a sustained jump to 80% in the recent window.
+        for _ in 0..20 {
+            profiler.store_metrics(metrics_with_cpu(20.0));
+        }
+        for _ in 0..5 {
+            profiler.store_metrics(metrics_with_cpu(80.0));
+        }
+
+        let bottlenecks = profiler.detect_statistical_regressions().unwrap();
+        assert!(bottlenecks.iter().any(|b| matches!(b.bottleneck_type, BottleneckType::Regression)
+            && b.metric_name == "cpu_utilization"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_statistical_regressions_ignores_noise_around_a_stable_mean() {
+        let mut config = ProfilingConfig::default();
+        config.regression_baseline_window = 10;
+        config.regression_recent_window = 5;
+        let mut profiler = Profiler::new(config);
+
+        for i in 0..15 {
+            // Small alternating jitter, same distribution throughout — no real regression.
+            let cpu = if i % 2 == 0 { 49.0 } else { 51.0 };
+            profiler.store_metrics(metrics_with_cpu(cpu));
+        }
+
+        let bottlenecks = profiler.detect_statistical_regressions().unwrap();
+        assert!(!bottlenecks.iter().any(|b| b.metric_name == "cpu_utilization"));
+    }
+
+    #[test]
+    fn test_profiler_benchmark_discards_warmup_and_summarizes_timed_calls() {
+        let mut config = ProfilingConfig::default();
+        config.benchmark_warmup_iters = 2;
+        let profiler = Profiler::new(config);
+
+        let mut total_calls = 0;
+        let summary = profiler.benchmark("noop", 5, || total_calls += 1).unwrap();
+
+        assert_eq!(total_calls, 7); // 2 warmup + 5 timed
+        assert_eq!(summary.sample_count, 5);
+        assert_eq!(summary.warmup_discarded, 2);
+    }
+
+    #[test]
+    fn test_profiler_benchmark_errors_on_zero_iterations() {
+        let profiler = Profiler::new(ProfilingConfig::default());
+        assert!(profiler.benchmark("noop", 0, || {}).is_err());
+    }
+
+    #[test]
+    fn test_export_trace_writes_counter_and_duration_events() {
+        let mut profiler = Profiler::new(ProfilingConfig::default());
+        let mut metrics = metrics_with_cpu(42.0);
+        metrics.timestamp = 1_700_000_000;
+        metrics.gpu_utilization = vec![60.0];
+        metrics.training_metrics.forward_time = Some(Duration::from_millis(12));
+        profiler.store_metrics(metrics);
+
+        let path = std::env::temp_dir().join("hecate_profiler_trace_test.json");
+        profiler.export_trace(path.to_str().unwrap()).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let events: Vec<serde_json::Value> = serde_json::from_str(&content).unwrap();
+
+        assert!(events.iter().any(|e| e["ph"] == "C" && e["name"] == "CPU Utilization"));
+        assert!(events.iter().any(|e| e["ph"] == "C" && e["name"] == "GPU Utilization"));
+        assert!(events.iter().any(|e| e["ph"] == "X" && e["name"] == "forward" && e["dur"] == 12_000));
+        assert!(events.iter().any(|e| e["ph"] == "M" && e["name"] == "thread_name"));
+    }
+
+    #[test]
+    fn test_scope_filter_parse_splits_roots_and_depth() {
+        let filter = ScopeFilter::parse("forward|backward@3");
+        assert!(filter.allows_root("forward"));
+        assert!(filter.allows_root("backward"));
+        assert!(!filter.allows_root("optimizer"));
+        assert_eq!(filter.max_depth, 3);
+    }
+
+    #[test]
+    fn test_scope_filter_parse_empty_spec_allows_everything_unbounded() {
+        let filter = ScopeFilter::parse("");
+        assert!(filter.allows_root("anything"));
+        assert_eq!(filter.max_depth, usize::MAX);
+    }
+
+    #[test]
+    fn test_scope_records_self_and_child_time() {
+        let profiler = Profiler::new(ProfilingConfig::default());
+        {
+            let _forward = profiler.scope("forward");
+            std::thread::sleep(Duration::from_millis(5));
+            {
+                let _attention = profiler.scope("attention");
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+
+        let tree = profiler.scope_state.lock().unwrap().tree.clone();
+        let forward = &tree["forward"];
+        assert_eq!(forward.call_count, 1);
+        assert_eq!(forward.children["attention"].call_count, 1);
+        // forward's self time excludes the nested attention span.
+        assert!(forward.self_time < forward.self_time + forward.children["attention"].self_time);
+        assert!(forward.child_time >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_scope_filter_excludes_unlisted_roots_and_their_children() {
+        let mut config = ProfilingConfig::default();
+        config.scope_filter = "forward".to_string();
+        let profiler = Profiler::new(config);
+
+        {
+            let _backward = profiler.scope("backward");
+            let _inner = profiler.scope("matmul");
+        }
+
+        let tree = profiler.scope_state.lock().unwrap().tree.clone();
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn test_scope_filter_caps_nesting_depth() {
+        let mut config = ProfilingConfig::default();
+        config.scope_filter = "forward@1".to_string();
+        let profiler = Profiler::new(config);
+
+        {
+            let _forward = profiler.scope("forward");
+            let _nested = profiler.scope("matmul");
+        }
+
+        let tree = profiler.scope_state.lock().unwrap().tree.clone();
+        assert!(tree["forward"].children.is_empty());
+    }
+
+    #[test]
+    fn test_scope_shorter_than_min_duration_is_dropped() {
+        let mut config = ProfilingConfig::default();
+        config.scope_min_duration = Duration::from_secs(1);
+        let profiler = Profiler::new(config);
+
+        { let _span = profiler.scope("forward"); }
+
+        assert!(profiler.scope_state.lock().unwrap().tree.is_empty());
+    }
+
+    #[test]
+    fn test_get_scope_tree_renders_indented_report() {
+        let profiler = Profiler::new(ProfilingConfig::default());
+        {
+            let _forward = profiler.scope("forward");
+            let _attention = profiler.scope("attention");
+        }
+
+        let report = profiler.get_scope_tree();
+        let forward_line = report.lines().next().unwrap();
+        assert!(forward_line.starts_with("forward:"));
+        let attention_line = report.lines().nth(1).unwrap();
+        assert!(attention_line.starts_with("  attention:"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_scope_metrics_fills_training_metrics_from_scope_tree() {
+        let mut profiler = Profiler::new(ProfilingConfig::default());
+        let metrics = profiler.collect_metrics().await.unwrap();
+        profiler.store_metrics(metrics);
+
+        {
+            let _forward = profiler.scope("forward");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        profiler.apply_scope_metrics();
+        let training_metrics = &profiler.metrics_buffer.back().unwrap().training_metrics;
+        assert!(training_metrics.forward_time.is_some());
+    }
 }
\ No newline at end of file
@@ -6,20 +6,27 @@
 use axum::{
     extract::ws::{Message, WebSocket, WebSocketUpgrade},
     extract::State,
+    http::header,
     response::IntoResponse,
     routing::get,
     Router,
 };
 use chrono::{DateTime, Utc};
 use futures::{sink::SinkExt, stream::StreamExt};
+use prometheus_client::encoding::{text::encode, EncodeLabelSet};
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::registry::Registry;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     net::SocketAddr,
+    sync::atomic::AtomicU64,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
-use sysinfo::{System, SystemExt, CpuExt, DiskExt, NetworkExt, ProcessExt};
+use sysinfo::{System, SystemExt, CpuExt, DiskExt, ProcessExt};
 use tokio::sync::RwLock;
 use tower_http::cors::CorsLayer;
 use tracing::{error, info, warn};
@@ -28,16 +35,25 @@ use tracing::{error, info, warn};
 // TIPOS DE DATOS
 // ============================================================================
 
-/// Métricas del sistema en un momento dado
+/// Métricas del sistema en un momento dado. Every field but `timestamp` is `Option`: a tick only
+/// populates the categories at least one connected client is [`MetricCategory`]-subscribed to,
+/// and [`project`] then strips out whatever a given client didn't ask for, so the wire payload is
+/// partial whenever subscriptions are narrower than "everything".
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemMetrics {
     pub timestamp: DateTime<Utc>,
-    pub cpu: CpuMetrics,
-    pub memory: MemoryMetrics,
-    pub gpu: Vec<GpuMetrics>,
-    pub disks: Vec<DiskMetrics>,
-    pub network: NetworkMetrics,
-    pub processes: ProcessMetrics,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<CpuMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu: Option<Vec<GpuMetrics>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disks: Option<Vec<DiskMetrics>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<NetworkMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub processes: Option<ProcessMetrics>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,6 +116,11 @@ pub struct ProcessMetrics {
     pub running_count: usize,
     pub top_by_cpu: Vec<ProcessInfo>,
     pub top_by_memory: Vec<ProcessInfo>,
+    /// Populated instead of (not alongside) the fixed top-5 lists when the client has an active
+    /// [`ProcessFilterCommand`]: every process matching its query, sorted by its `sort` key and
+    /// truncated to its `limit`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filtered: Option<Vec<ProcessInfo>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -108,29 +129,416 @@ pub struct ProcessInfo {
     pub name: String,
     pub cpu_percent: f32,
     pub memory_mb: u64,
+    pub cmdline: String,
+}
+
+// ============================================================================
+// PROTOCOLO DE SUSCRIPCIÓN
+// ============================================================================
+
+/// A category of [`SystemMetrics`] a client can subscribe to. Names match the `SystemMetrics`
+/// field they gate, lowercased for the wire protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricCategory {
+    Cpu,
+    Memory,
+    Gpu,
+    Disks,
+    Network,
+    Processes,
+}
+
+/// Every category, used as the default subscription for a newly connected client so it keeps
+/// getting the full feed until it narrows things down.
+const ALL_CATEGORIES: [MetricCategory; 6] = [
+    MetricCategory::Cpu,
+    MetricCategory::Memory,
+    MetricCategory::Gpu,
+    MetricCategory::Disks,
+    MetricCategory::Network,
+    MetricCategory::Processes,
+];
+
+/// How a [`ProcessFilterCommand`] query is interpreted against a process's name/command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ProcessFilterMode {
+    Regex,
+    Simple,
+}
+
+/// Which [`ProcessInfo`] field a [`ProcessFilterCommand`] sorts matches by.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ProcessSortKey {
+    Cpu,
+    Memory,
+}
+
+/// `{"process_filter":{"query":"python","mode":"regex","sort":"memory","limit":20}}` -- turns
+/// the dashboard's process widget into a filtered/sorted explorer instead of a fixed top-5.
+#[derive(Debug, Deserialize)]
+struct ProcessFilterCommand {
+    query: String,
+    #[serde(default = "ProcessFilterCommand::default_mode")]
+    mode: ProcessFilterMode,
+    #[serde(default = "ProcessFilterCommand::default_sort")]
+    sort: ProcessSortKey,
+    #[serde(default = "ProcessFilterCommand::default_limit")]
+    limit: usize,
+    /// Also match against the full command line, not just the process name.
+    #[serde(default)]
+    match_cmdline: bool,
+}
+
+impl ProcessFilterCommand {
+    fn default_mode() -> ProcessFilterMode {
+        ProcessFilterMode::Simple
+    }
+
+    fn default_sort() -> ProcessSortKey {
+        ProcessSortKey::Cpu
+    }
+
+    fn default_limit() -> usize {
+        20
+    }
+}
+
+/// A client's active process filter, with its `Regex` compiled (when `mode` is `Regex`) and
+/// cached here so [`handle_client_command`] only rebuilds it when the query or mode actually
+/// changes, not on every tick.
+struct ProcessFilterState {
+    query: String,
+    mode: ProcessFilterMode,
+    sort: ProcessSortKey,
+    limit: usize,
+    match_cmdline: bool,
+    compiled: Option<Regex>,
+}
+
+/// Inbound WebSocket command: `{"subscribe":["cpu","gpu"]}` replaces the client's subscription
+/// set outright; `{"unsubscribe":["gpu"]}` removes categories from whatever it currently is;
+/// `{"process_filter":{...}}` replaces the client's process filter (see [`ProcessFilterCommand`]).
+#[derive(Debug, Deserialize)]
+struct ClientCommand {
+    subscribe: Option<Vec<MetricCategory>>,
+    unsubscribe: Option<Vec<MetricCategory>>,
+    process_filter: Option<ProcessFilterCommand>,
+}
+
+/// One-shot outbound message sent right after connecting: `{"backfill":[...]}`. Distinguishable
+/// from a live tick (which serializes as a bare [`SystemMetrics`] object with a `timestamp` key
+/// but no `backfill` key) so clients can tell the two apart without a shared envelope type.
+#[derive(Debug, Serialize)]
+struct BackfillMessage {
+    backfill: Vec<SystemMetrics>,
+}
+
+// ============================================================================
+// CONFIGURACIÓN
+// ============================================================================
+
+const CONFIG_PATH: &str = "config/hecate/monitor.toml";
+
+/// `[metrics]` section of `monitor.toml`: where the Prometheus scrape endpoint lives, and
+/// whether it gets its own listener instead of sharing the dashboard/WebSocket port.
+#[derive(Debug, Clone, Deserialize)]
+struct MetricsConfig {
+    #[serde(default = "MetricsConfig::default_path")]
+    path: String,
+    /// Bind a second axum server to this address for `path`, leaving the main router free of it.
+    /// Unset by default: the endpoint is just another route on the main server.
+    #[serde(default)]
+    listen_addr: Option<SocketAddr>,
+}
+
+impl MetricsConfig {
+    fn default_path() -> String {
+        "/metrics".to_string()
+    }
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+            listen_addr: None,
+        }
+    }
+}
+
+/// `[history]` section of `monitor.toml`: how many collector ticks are retained in memory for
+/// late-connecting clients and the `/history` endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct HistoryConfig {
+    #[serde(default = "HistoryConfig::default_retention_samples")]
+    retention_samples: usize,
+}
+
+impl HistoryConfig {
+    fn default_retention_samples() -> usize {
+        600
+    }
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            retention_samples: Self::default_retention_samples(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct MonitorConfig {
+    #[serde(default)]
+    metrics: MetricsConfig,
+    #[serde(default)]
+    history: HistoryConfig,
+}
+
+impl MonitorConfig {
+    /// Load [`CONFIG_PATH`], falling back to defaults (metrics on `/metrics`, same router) when
+    /// it doesn't exist.
+    fn load() -> Self {
+        let Ok(content) = std::fs::read_to_string(CONFIG_PATH) else {
+            return Self::default();
+        };
+        match toml::from_str(&content) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Failed to parse {CONFIG_PATH}, using defaults: {e}");
+                Self::default()
+            }
+        }
+    }
+}
+
+// ============================================================================
+// MÉTRICAS PROMETHEUS
+// ============================================================================
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct CoreLabel {
+    core: u32,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct GpuLabel {
+    index: u32,
+    name: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct DiskLabel {
+    mount_point: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct InterfaceLabel {
+    interface: String,
+}
+
+/// Prometheus gauges for every field of [`SystemMetrics`], registered once at startup and
+/// updated in place each tick by [`metrics_collector`] so `/metrics` always reflects the last
+/// broadcast without re-deriving it from the JSON the WebSocket clients see.
+struct PrometheusMetrics {
+    registry: Registry,
+    cpu_usage_percent: Gauge<f64, AtomicU64>,
+    cpu_usage_per_core: Family<CoreLabel, Gauge<f64, AtomicU64>>,
+    memory_used_gb: Gauge<f64, AtomicU64>,
+    gpu_utilization: Family<GpuLabel, Gauge<f64, AtomicU64>>,
+    disk_used_gb: Family<DiskLabel, Gauge<f64, AtomicU64>>,
+    network_rx_mb_s: Family<InterfaceLabel, Gauge<f64, AtomicU64>>,
+    process_running_count: Gauge,
+}
+
+impl PrometheusMetrics {
+    fn new() -> Self {
+        let mut registry = Registry::default();
+
+        let cpu_usage_percent = Gauge::<f64, AtomicU64>::default();
+        registry.register(
+            "cpu_usage_percent",
+            "Total CPU usage percentage",
+            cpu_usage_percent.clone(),
+        );
+
+        let cpu_usage_per_core = Family::<CoreLabel, Gauge<f64, AtomicU64>>::default();
+        registry.register(
+            "cpu_usage_per_core",
+            "Per-core CPU usage percentage",
+            cpu_usage_per_core.clone(),
+        );
+
+        let memory_used_gb = Gauge::<f64, AtomicU64>::default();
+        registry.register("memory_used_gb", "Used memory in GB", memory_used_gb.clone());
+
+        let gpu_utilization = Family::<GpuLabel, Gauge<f64, AtomicU64>>::default();
+        registry.register(
+            "gpu_utilization",
+            "GPU utilization percentage",
+            gpu_utilization.clone(),
+        );
+
+        let disk_used_gb = Family::<DiskLabel, Gauge<f64, AtomicU64>>::default();
+        registry.register("disk_used_gb", "Used disk space in GB", disk_used_gb.clone());
+
+        let network_rx_mb_s = Family::<InterfaceLabel, Gauge<f64, AtomicU64>>::default();
+        registry.register(
+            "network_rx_mb_s",
+            "Network receive throughput in MB/s",
+            network_rx_mb_s.clone(),
+        );
+
+        let process_running_count = Gauge::default();
+        registry.register(
+            "process_running_count",
+            "Number of currently running processes",
+            process_running_count.clone(),
+        );
+
+        Self {
+            registry,
+            cpu_usage_percent,
+            cpu_usage_per_core,
+            memory_used_gb,
+            gpu_utilization,
+            disk_used_gb,
+            network_rx_mb_s,
+            process_running_count,
+        }
+    }
+
+    /// Refresh every gauge from the latest [`SystemMetrics`] snapshot. A category this tick
+    /// didn't collect (nobody subscribed to it) is simply skipped -- its gauges keep their last
+    /// known value rather than being reset.
+    fn update(&self, metrics: &SystemMetrics) {
+        if let Some(cpu) = &metrics.cpu {
+            self.cpu_usage_percent.set(cpu.usage_percent as f64);
+            for (core, usage) in cpu.per_core.iter().enumerate() {
+                self.cpu_usage_per_core
+                    .get_or_create(&CoreLabel { core: core as u32 })
+                    .set(*usage as f64);
+            }
+        }
+
+        if let Some(memory) = &metrics.memory {
+            self.memory_used_gb.set(memory.used_gb);
+        }
+
+        if let Some(gpu) = &metrics.gpu {
+            for gpu in gpu {
+                self.gpu_utilization
+                    .get_or_create(&GpuLabel {
+                        index: gpu.index,
+                        name: gpu.name.clone(),
+                    })
+                    .set(gpu.utilization as f64);
+            }
+        }
+
+        if let Some(disks) = &metrics.disks {
+            for disk in disks {
+                self.disk_used_gb
+                    .get_or_create(&DiskLabel {
+                        mount_point: disk.mount_point.clone(),
+                    })
+                    .set(disk.used_gb);
+            }
+        }
+
+        if let Some(network) = &metrics.network {
+            for interface in &network.interfaces {
+                self.network_rx_mb_s
+                    .get_or_create(&InterfaceLabel {
+                        interface: interface.name.clone(),
+                    })
+                    .set(interface.rx_mb_s);
+            }
+        }
+
+        if let Some(processes) = &metrics.processes {
+            self.process_running_count
+                .set(processes.running_count as i64);
+        }
+    }
 }
 
 // ============================================================================
 // ESTADO COMPARTIDO
 // ============================================================================
 
+/// Cumulative byte counters from the previous collector tick, used to derive instantaneous
+/// throughput. `a`/`b` are read/write for disks, rx/tx for network interfaces.
+#[derive(Clone, Copy)]
+struct IoSample {
+    a_bytes: u64,
+    b_bytes: u64,
+    at: Instant,
+}
+
+/// Either a routine metrics tick or a one-shot notice that the server is shutting down, sent down
+/// a [`ClientHandle::tx`]. Folding both into one channel means the send task only ever needs to
+/// watch a single receiver to know when to emit the shutdown frame and stop.
+enum ClientMessage {
+    Metrics(SystemMetrics),
+    Shutdown,
+}
+
+/// A connected client's push channel plus the [`MetricCategory`] set it currently wants. Starts
+/// at [`ALL_CATEGORIES`] so a client that never sends a `subscribe` command keeps the old
+/// full-feed behavior.
+struct ClientHandle {
+    tx: tokio::sync::mpsc::Sender<ClientMessage>,
+    subscriptions: HashSet<MetricCategory>,
+    /// Active process filter, if the client has sent a `process_filter` command.
+    process_filter: Option<ProcessFilterState>,
+}
+
 /// Estado compartido entre todas las conexiones
 #[derive(Clone)]
 struct AppState {
     metrics: Arc<RwLock<SystemMetrics>>,
-    clients: Arc<RwLock<HashMap<String, tokio::sync::mpsc::Sender<SystemMetrics>>>>,
+    clients: Arc<RwLock<HashMap<String, ClientHandle>>>,
     system: Arc<RwLock<System>>,
+    prometheus: Arc<PrometheusMetrics>,
+    /// Previous disk read/write byte counters, keyed by device name (e.g. `sda1`).
+    disk_io_prev: Arc<RwLock<HashMap<String, IoSample>>>,
+    /// Previous network rx/tx byte counters, keyed by interface name.
+    net_io_prev: Arc<RwLock<HashMap<String, IoSample>>>,
+    /// Bounded time-series history, oldest sample first, capped at `history_capacity`. Backs
+    /// WebSocket backfill on connect and the `/history` endpoint.
+    history: Arc<RwLock<std::collections::VecDeque<SystemMetrics>>>,
+    history_capacity: usize,
+    /// Unsorted, untruncated process list from the most recent tick, kept separate from the
+    /// broadcast [`SystemMetrics`] snapshot so each client's [`ProcessFilterState`] can be
+    /// applied independently without bloating what every other client receives.
+    raw_processes: Arc<RwLock<Vec<ProcessInfo>>>,
+    /// Flips to `true` once shutdown has been requested. The collector loop and both HTTP
+    /// servers' `with_graceful_shutdown` futures all watch this same receiver, so a single
+    /// `shutdown_tx.send(true)` in `main` stops everything tied to this `AppState`.
+    shutdown_rx: tokio::sync::watch::Receiver<bool>,
 }
 
 impl AppState {
-    fn new() -> Self {
+    fn new(history_capacity: usize, shutdown_rx: tokio::sync::watch::Receiver<bool>) -> Self {
         let mut system = System::new_all();
         system.refresh_all();
-        
+
         Self {
             metrics: Arc::new(RwLock::new(SystemMetrics::default())),
             clients: Arc::new(RwLock::new(HashMap::new())),
             system: Arc::new(RwLock::new(system)),
+            prometheus: Arc::new(PrometheusMetrics::new()),
+            disk_io_prev: Arc::new(RwLock::new(HashMap::new())),
+            net_io_prev: Arc::new(RwLock::new(HashMap::new())),
+            history: Arc::new(RwLock::new(std::collections::VecDeque::new())),
+            history_capacity,
+            raw_processes: Arc::new(RwLock::new(Vec::new())),
+            shutdown_rx,
         }
     }
 }
@@ -139,36 +547,102 @@ impl AppState {
 // RECOLECTOR DE MÉTRICAS
 // ============================================================================
 
-/// Recolecta métricas del sistema periódicamente
+/// Union of every connected client's [`MetricCategory`] subscriptions. An idle tick with no
+/// subscribers yields an empty set, telling the collector there's nothing worth refreshing.
+async fn active_categories(state: &AppState) -> HashSet<MetricCategory> {
+    let clients = state.clients.read().await;
+    clients
+        .values()
+        .flat_map(|handle| handle.subscriptions.iter().copied())
+        .collect()
+}
+
+/// Recolecta métricas del sistema periódicamente, refreshing and collecting only the
+/// [`MetricCategory`]s at least one connected client currently subscribes to.
 async fn metrics_collector(state: AppState) {
     let mut interval = tokio::time::interval(Duration::from_secs(1));
-    
+    let mut shutdown_rx = state.shutdown_rx.clone();
+
     loop {
-        interval.tick().await;
-        
-        // Actualizar system info
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown_rx.changed() => {
+                info!("Metrics collector stopping for shutdown");
+                return;
+            }
+        }
+
+        let active = active_categories(&state).await;
+        if active.is_empty() {
+            continue;
+        }
+
+        // Actualizar solo la info de sistema que alguien está mirando
         let mut system = state.system.write().await;
-        system.refresh_all();
-        
+        if active.contains(&MetricCategory::Cpu) {
+            system.refresh_cpu();
+        }
+        if active.contains(&MetricCategory::Memory) {
+            system.refresh_memory();
+        }
+        if active.contains(&MetricCategory::Disks) {
+            system.refresh_disks();
+        }
+        if active.contains(&MetricCategory::Network) {
+            system.refresh_networks();
+        }
+        if active.contains(&MetricCategory::Processes) {
+            system.refresh_processes();
+        }
+
         // Recolectar métricas
-        let metrics = collect_metrics(&system).await;
-        
+        let mut disk_io_prev = state.disk_io_prev.write().await;
+        let mut net_io_prev = state.net_io_prev.write().await;
+        let (metrics, raw_processes) =
+            collect_metrics(&system, &mut disk_io_prev, &mut net_io_prev, &active).await;
+        drop(disk_io_prev);
+        drop(net_io_prev);
+        drop(system);
+
+        if let Some(raw_processes) = raw_processes {
+            *state.raw_processes.write().await = raw_processes;
+        }
+
         // Guardar métricas actuales
         {
             let mut current = state.metrics.write().await;
             *current = metrics.clone();
         }
-        
-        // Enviar a todos los clientes conectados
+
+        // Reflejar las métricas en los gauges de Prometheus
+        state.prometheus.update(&metrics);
+
+        // Guardar en el historial acotado
+        {
+            let mut history = state.history.write().await;
+            history.push_back(metrics.clone());
+            while history.len() > state.history_capacity {
+                history.pop_front();
+            }
+        }
+
+        // Enviar a cada cliente solo lo que pidió
         let clients = state.clients.read().await;
         let mut disconnected = Vec::new();
-        
-        for (id, tx) in clients.iter() {
-            if tx.send(metrics.clone()).await.is_err() {
+        let raw_processes = state.raw_processes.read().await;
+
+        for (id, handle) in clients.iter() {
+            let mut payload = project(&metrics, &handle.subscriptions);
+            if let Some(filter) = &handle.process_filter {
+                if let Some(processes) = &mut payload.processes {
+                    processes.filtered = Some(apply_process_filter(&raw_processes, filter));
+                }
+            }
+            if handle.tx.send(ClientMessage::Metrics(payload)).await.is_err() {
                 disconnected.push(id.clone());
             }
         }
-        
+
         // Eliminar clientes desconectados
         if !disconnected.is_empty() {
             drop(clients);
@@ -181,10 +655,94 @@ async fn metrics_collector(state: AppState) {
     }
 }
 
-/// Recolecta todas las métricas del sistema
-async fn collect_metrics(system: &System) -> SystemMetrics {
+/// Derives bytes/sec from a cumulative counter given the previous sample, storing the current
+/// reading back as the new baseline. Returns `0.0` on the first sample for a key (no baseline
+/// yet) and whenever the counter went backwards (e.g. an interface reset).
+fn derive_rate_mb_s(
+    prev: &mut HashMap<String, IoSample>,
+    key: &str,
+    current_a: u64,
+    current_b: u64,
+    now: Instant,
+) -> (f64, f64) {
+    let rates = match prev.get(key) {
+        Some(sample) if current_a >= sample.a_bytes && current_b >= sample.b_bytes => {
+            let elapsed = now.duration_since(sample.at).as_secs_f64();
+            if elapsed > 0.0 {
+                (
+                    (current_a - sample.a_bytes) as f64 / elapsed / 1024.0 / 1024.0,
+                    (current_b - sample.b_bytes) as f64 / elapsed / 1024.0 / 1024.0,
+                )
+            } else {
+                (0.0, 0.0)
+            }
+        }
+        _ => (0.0, 0.0),
+    };
+
+    prev.insert(
+        key.to_string(),
+        IoSample {
+            a_bytes: current_a,
+            b_bytes: current_b,
+            at: now,
+        },
+    );
+
+    rates
+}
+
+/// Reads `/proc/diskstats` and returns `(sectors_read, sectors_written)` converted to bytes,
+/// keyed by device name (e.g. `sda1`). See `man 5 proc` for the field layout.
+fn read_diskstats() -> HashMap<String, (u64, u64)> {
+    const SECTOR_BYTES: u64 = 512;
+    let mut stats = HashMap::new();
+
+    let Ok(content) = std::fs::read_to_string("/proc/diskstats") else {
+        return stats;
+    };
+
+    for line in content.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 10 {
+            continue;
+        }
+        let name = fields[2].to_string();
+        let sectors_read: u64 = fields[5].parse().unwrap_or(0);
+        let sectors_written: u64 = fields[9].parse().unwrap_or(0);
+        stats.insert(name, (sectors_read * SECTOR_BYTES, sectors_written * SECTOR_BYTES));
+    }
+
+    stats
+}
+
+/// Reads `/sys/class/net/<iface>/statistics/{rx,tx}_bytes`.
+fn read_net_bytes(iface: &str) -> Option<(u64, u64)> {
+    let rx = std::fs::read_to_string(format!("/sys/class/net/{iface}/statistics/rx_bytes"))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    let tx = std::fs::read_to_string(format!("/sys/class/net/{iface}/statistics/tx_bytes"))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some((rx, tx))
+}
+
+/// Recolecta únicamente las categorías de métricas presentes en `active`. `system` must already
+/// have been refreshed for those same categories by the caller. Returns the broadcast snapshot
+/// alongside the *unsorted, untruncated* process list (when collected), which per-client process
+/// filters are applied against separately so the top-5 lists everyone gets stay cheap.
+async fn collect_metrics(
+    system: &System,
+    disk_io_prev: &mut HashMap<String, IoSample>,
+    net_io_prev: &mut HashMap<String, IoSample>,
+    active: &HashSet<MetricCategory>,
+) -> (SystemMetrics, Option<Vec<ProcessInfo>>) {
     // CPU Metrics
-    let cpu = CpuMetrics {
+    let cpu = active.contains(&MetricCategory::Cpu).then(|| CpuMetrics {
         usage_percent: system.global_cpu_info().cpu_usage(),
         per_core: system.cpus().iter().map(|cpu| cpu.cpu_usage()).collect(),
         temperature: read_cpu_temperature(),
@@ -195,99 +753,144 @@ async fn collect_metrics(system: &System) -> SystemMetrics {
             let load = system.load_average();
             [load.one as f32, load.five as f32, load.fifteen as f32]
         },
-    };
-    
+    });
+
     // Memory Metrics
-    let memory = MemoryMetrics {
+    let memory = active.contains(&MetricCategory::Memory).then(|| MemoryMetrics {
         total_gb: bytes_to_gb(system.total_memory()),
         used_gb: bytes_to_gb(system.used_memory()),
         available_gb: bytes_to_gb(system.available_memory()),
         swap_total_gb: bytes_to_gb(system.total_swap()),
         swap_used_gb: bytes_to_gb(system.used_swap()),
         cache_gb: bytes_to_gb(system.available_memory() - system.free_memory()),
-    };
-    
+    });
+
     // GPU Metrics (si está disponible el módulo)
-    let gpu = collect_gpu_metrics().await;
-    
+    let gpu = if active.contains(&MetricCategory::Gpu) {
+        Some(collect_gpu_metrics().await)
+    } else {
+        None
+    };
+
     // Disk Metrics
-    let disks: Vec<DiskMetrics> = system.disks()
-        .iter()
-        .map(|disk| DiskMetrics {
-            name: disk.name().to_string_lossy().to_string(),
-            mount_point: disk.mount_point().to_string_lossy().to_string(),
-            total_gb: bytes_to_gb(disk.total_space()),
-            used_gb: bytes_to_gb(disk.total_space() - disk.available_space()),
-            read_mb_s: 0.0,  // TODO: Calcular velocidad real
-            write_mb_s: 0.0,
-        })
-        .collect();
-    
+    let disks = if active.contains(&MetricCategory::Disks) {
+        let now = Instant::now();
+        let diskstats = read_diskstats();
+        Some(
+            system
+                .disks()
+                .iter()
+                .map(|disk| {
+                    let name = disk.name().to_string_lossy().to_string();
+                    let device = name.strip_prefix("/dev/").unwrap_or(&name);
+                    let (read_mb_s, write_mb_s) = match diskstats.get(device) {
+                        Some(&(read_bytes, write_bytes)) => {
+                            derive_rate_mb_s(disk_io_prev, device, read_bytes, write_bytes, now)
+                        }
+                        None => (0.0, 0.0),
+                    };
+
+                    DiskMetrics {
+                        name,
+                        mount_point: disk.mount_point().to_string_lossy().to_string(),
+                        total_gb: bytes_to_gb(disk.total_space()),
+                        used_gb: bytes_to_gb(disk.total_space() - disk.available_space()),
+                        read_mb_s,
+                        write_mb_s,
+                    }
+                })
+                .collect(),
+        )
+    } else {
+        None
+    };
+
     // Network Metrics
-    let mut interfaces = Vec::new();
-    let mut total_rx = 0.0;
-    let mut total_tx = 0.0;
-    
-    for (name, data) in system.networks() {
-        let rx_mb_s = data.received() as f64 / 1024.0 / 1024.0;
-        let tx_mb_s = data.transmitted() as f64 / 1024.0 / 1024.0;
-        
-        interfaces.push(NetworkInterface {
-            name: name.clone(),
-            rx_mb_s,
-            tx_mb_s,
-        });
-        
-        total_rx += rx_mb_s;
-        total_tx += tx_mb_s;
-    }
-    
-    let network = NetworkMetrics {
-        interfaces,
-        total_rx_mb_s: total_rx,
-        total_tx_mb_s: total_tx,
+    let network = if active.contains(&MetricCategory::Network) {
+        let now = Instant::now();
+        let mut interfaces = Vec::new();
+        let mut total_rx = 0.0;
+        let mut total_tx = 0.0;
+
+        for (name, _data) in system.networks() {
+            let (rx_mb_s, tx_mb_s) = match read_net_bytes(name) {
+                Some((rx_bytes, tx_bytes)) => derive_rate_mb_s(net_io_prev, name, rx_bytes, tx_bytes, now),
+                None => (0.0, 0.0),
+            };
+
+            interfaces.push(NetworkInterface {
+                name: name.clone(),
+                rx_mb_s,
+                tx_mb_s,
+            });
+
+            total_rx += rx_mb_s;
+            total_tx += tx_mb_s;
+        }
+
+        Some(NetworkMetrics {
+            interfaces,
+            total_rx_mb_s: total_rx,
+            total_tx_mb_s: total_tx,
+        })
+    } else {
+        None
     };
-    
+
     // Process Metrics
-    let mut processes: Vec<_> = system.processes()
-        .values()
-        .map(|p| ProcessInfo {
-            pid: p.pid().as_u32(),
-            name: p.name().to_string(),
-            cpu_percent: p.cpu_usage(),
-            memory_mb: p.memory() / 1024,
-        })
-        .collect();
-    
-    // Ordenar por CPU
-    processes.sort_by(|a, b| b.cpu_percent.partial_cmp(&a.cpu_percent).unwrap());
-    let top_by_cpu: Vec<ProcessInfo> = processes.iter().take(5).cloned().collect();
-    
-    // Ordenar por memoria
-    processes.sort_by(|a, b| b.memory_mb.cmp(&a.memory_mb));
-    let top_by_memory: Vec<ProcessInfo> = processes.iter().take(5).cloned().collect();
-    
-    let running_count = system.processes()
-        .values()
-        .filter(|p| p.status() == sysinfo::ProcessStatus::Run)
-        .count();
-    
-    let processes = ProcessMetrics {
-        total_count: system.processes().len(),
-        running_count,
-        top_by_cpu,
-        top_by_memory,
+    let (processes, raw_processes) = if active.contains(&MetricCategory::Processes) {
+        let mut processes: Vec<_> = system.processes()
+            .values()
+            .map(|p| ProcessInfo {
+                pid: p.pid().as_u32(),
+                name: p.name().to_string(),
+                cpu_percent: p.cpu_usage(),
+                memory_mb: p.memory() / 1024,
+                cmdline: p.cmd().join(" "),
+            })
+            .collect();
+
+        let raw_processes = processes.clone();
+
+        // Ordenar por CPU
+        processes.sort_by(|a, b| b.cpu_percent.partial_cmp(&a.cpu_percent).unwrap());
+        let top_by_cpu: Vec<ProcessInfo> = processes.iter().take(5).cloned().collect();
+
+        // Ordenar por memoria
+        processes.sort_by(|a, b| b.memory_mb.cmp(&a.memory_mb));
+        let top_by_memory: Vec<ProcessInfo> = processes.iter().take(5).cloned().collect();
+
+        let running_count = system.processes()
+            .values()
+            .filter(|p| p.status() == sysinfo::ProcessStatus::Run)
+            .count();
+
+        (
+            Some(ProcessMetrics {
+                total_count: system.processes().len(),
+                running_count,
+                top_by_cpu,
+                top_by_memory,
+                filtered: None,
+            }),
+            Some(raw_processes),
+        )
+    } else {
+        (None, None)
     };
-    
-    SystemMetrics {
-        timestamp: Utc::now(),
-        cpu,
-        memory,
-        gpu,
-        disks,
-        network,
-        processes,
-    }
+
+    (
+        SystemMetrics {
+            timestamp: Utc::now(),
+            cpu,
+            memory,
+            gpu,
+            disks,
+            network,
+            processes,
+        },
+        raw_processes,
+    )
 }
 
 // ============================================================================
@@ -309,39 +912,64 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     
     info!("New client connected: {}", client_id);
     
-    // Canal para enviar métricas a este cliente
-    let (tx, mut rx) = tokio::sync::mpsc::channel::<SystemMetrics>(10);
-    
-    // Registrar cliente
+    // Canal para enviar métricas (y la notificación de apagado) a este cliente
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<ClientMessage>(10);
+
+    // Registrar cliente, suscrito por defecto a todas las categorías
     {
         let mut clients = state.clients.write().await;
-        clients.insert(client_id.clone(), tx);
+        clients.insert(
+            client_id.clone(),
+            ClientHandle {
+                tx,
+                subscriptions: ALL_CATEGORIES.into_iter().collect(),
+                process_filter: None,
+            },
+        );
     }
-    
-    // Enviar métricas iniciales
+
+    // Enviar el historial retenido como backfill antes de empezar a transmitir en vivo, para que
+    // un cliente que se conecta tarde (o abre un chart nuevo) pueda dibujar el pasado sin esperar
+    // a que el historial se vuelva a acumular tick a tick.
     {
-        let metrics = state.metrics.read().await;
-        let msg = serde_json::to_string(&*metrics).unwrap();
-        let _ = sender.send(Message::Text(msg)).await;
+        let history = state.history.read().await;
+        let backfill = BackfillMessage {
+            backfill: history.iter().cloned().collect(),
+        };
+        if let Ok(msg) = serde_json::to_string(&backfill) {
+            let _ = sender.send(Message::Text(msg)).await;
+        }
     }
-    
-    // Spawn task para enviar métricas
+
+    // Spawn task para enviar métricas (y cerrar limpiamente cuando el servidor se apaga)
     let mut send_task = tokio::spawn(async move {
-        while let Some(metrics) = rx.recv().await {
-            let msg = serde_json::to_string(&metrics).unwrap();
-            if sender.send(Message::Text(msg)).await.is_err() {
-                break;
+        while let Some(message) = rx.recv().await {
+            match message {
+                ClientMessage::Metrics(metrics) => {
+                    let msg = serde_json::to_string(&metrics).unwrap();
+                    if sender.send(Message::Text(msg)).await.is_err() {
+                        break;
+                    }
+                }
+                ClientMessage::Shutdown => {
+                    let _ = sender
+                        .send(Message::Text(r#"{"event":"server_shutdown"}"#.to_string()))
+                        .await;
+                    let _ = sender.send(Message::Close(None)).await;
+                    break;
+                }
             }
         }
     });
     
     // Recibir mensajes del cliente
+    let recv_state = state.clone();
+    let recv_client_id = client_id.clone();
     let mut recv_task = tokio::spawn(async move {
         while let Some(Ok(msg)) = receiver.next().await {
             match msg {
                 Message::Text(text) => {
-                    // Procesar comandos del cliente si los hay
-                    info!("Received from client: {}", text);
+                    handle_client_command(&recv_state, &recv_client_id, &text).await;
                 }
                 Message::Close(_) => break,
                 _ => {}
@@ -364,6 +992,115 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     info!("Client {} disconnected", client_id);
 }
 
+/// Applies an inbound `{"subscribe":[...]}` / `{"unsubscribe":[...]}` / `{"process_filter":{...}}`
+/// command to the client's entry in `AppState::clients`. Malformed JSON is logged and ignored
+/// rather than closing the socket -- a single bad message shouldn't drop an otherwise-working
+/// dashboard connection.
+async fn handle_client_command(state: &AppState, client_id: &str, text: &str) {
+    let command: ClientCommand = match serde_json::from_str(text) {
+        Ok(command) => command,
+        Err(e) => {
+            warn!("Ignoring malformed command from client {}: {}", client_id, e);
+            return;
+        }
+    };
+
+    let mut clients = state.clients.write().await;
+    let Some(handle) = clients.get_mut(client_id) else {
+        return;
+    };
+
+    if let Some(categories) = command.subscribe {
+        handle.subscriptions = categories.into_iter().collect();
+        info!("Client {} subscribed to {:?}", client_id, handle.subscriptions);
+    }
+
+    if let Some(categories) = command.unsubscribe {
+        for category in categories {
+            handle.subscriptions.remove(&category);
+        }
+        info!("Client {} now subscribed to {:?}", client_id, handle.subscriptions);
+    }
+
+    if let Some(filter) = command.process_filter {
+        // Only recompile the regex when the query/mode actually changed; keep the cached one
+        // (and its `query`/`mode`) otherwise so a client polling with the same filter every tick
+        // doesn't pay for a fresh `Regex::new` each time.
+        let compiled = if filter.mode == ProcessFilterMode::Regex {
+            let reuse = handle.process_filter.as_ref().and_then(|prev| {
+                if prev.mode == ProcessFilterMode::Regex && prev.query == filter.query {
+                    prev.compiled.clone()
+                } else {
+                    None
+                }
+            });
+            match reuse {
+                Some(regex) => Some(regex),
+                None => match Regex::new(&filter.query) {
+                    Ok(regex) => Some(regex),
+                    Err(e) => {
+                        warn!(
+                            "Client {} sent an invalid process_filter regex {:?}: {}",
+                            client_id, filter.query, e
+                        );
+                        return;
+                    }
+                },
+            }
+        } else {
+            None
+        };
+
+        handle.process_filter = Some(ProcessFilterState {
+            query: filter.query,
+            mode: filter.mode,
+            sort: filter.sort,
+            limit: filter.limit,
+            match_cmdline: filter.match_cmdline,
+            compiled,
+        });
+        info!("Client {} set a process_filter", client_id);
+    }
+}
+
+/// Checks a single process against a [`ProcessFilterState`]'s query, honoring `match_cmdline`.
+fn process_matches(process: &ProcessInfo, filter: &ProcessFilterState) -> bool {
+    match filter.mode {
+        ProcessFilterMode::Regex => {
+            let Some(regex) = &filter.compiled else {
+                return false;
+            };
+            regex.is_match(&process.name)
+                || (filter.match_cmdline && regex.is_match(&process.cmdline))
+        }
+        ProcessFilterMode::Simple => {
+            let query = filter.query.to_lowercase();
+            process.name.to_lowercase().contains(&query)
+                || (filter.match_cmdline && process.cmdline.to_lowercase().contains(&query))
+        }
+    }
+}
+
+/// Filters `raw` against `filter`'s query, sorts by `filter.sort` (descending), and truncates to
+/// `filter.limit`.
+fn apply_process_filter(raw: &[ProcessInfo], filter: &ProcessFilterState) -> Vec<ProcessInfo> {
+    let mut matches: Vec<ProcessInfo> = raw
+        .iter()
+        .filter(|process| process_matches(process, filter))
+        .cloned()
+        .collect();
+
+    match filter.sort {
+        ProcessSortKey::Cpu => matches.sort_by(|a, b| {
+            b.cpu_percent.partial_cmp(&a.cpu_percent).unwrap_or(std::cmp::Ordering::Equal)
+        }),
+        ProcessSortKey::Memory => matches.sort_by(|a, b| b.memory_mb.cmp(&a.memory_mb)),
+    }
+
+    matches.truncate(filter.limit);
+    matches
+}
+
 // ============================================================================
 // SERVIDOR HTTP
 // ============================================================================
@@ -378,6 +1115,168 @@ async fn health() -> impl IntoResponse {
     "OK"
 }
 
+/// Prometheus text-exposition scrape endpoint. Encodes whatever [`metrics_collector`] last wrote
+/// into `state.prometheus` -- it does not trigger a fresh collection itself.
+async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let mut buffer = String::new();
+    if let Err(e) = encode(&mut buffer, &state.prometheus.registry) {
+        error!("Failed to encode Prometheus metrics: {}", e);
+        return (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            String::new(),
+        );
+    }
+    (
+        axum::http::StatusCode::OK,
+        [(
+            header::CONTENT_TYPE,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        )],
+        buffer,
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryQuery {
+    /// Dotted path into a retained [`SystemMetrics`] sample, e.g. `cpu.usage_percent`. Only
+    /// scalar, non-indexed fields are supported -- per-core, per-disk, per-GPU, and per-interface
+    /// series aren't addressable this way yet.
+    field: String,
+    #[serde(default = "HistoryQuery::default_window")]
+    window: String,
+    #[serde(default = "HistoryQuery::default_step")]
+    step: String,
+}
+
+impl HistoryQuery {
+    fn default_window() -> String {
+        "5m".to_string()
+    }
+
+    fn default_step() -> String {
+        "1s".to_string()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct HistoryBucket {
+    timestamp: DateTime<Utc>,
+    avg: f64,
+    min: f64,
+    max: f64,
+    count: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct HistoryResponse {
+    field: String,
+    window: String,
+    step: String,
+    buckets: Vec<HistoryBucket>,
+}
+
+/// Parses a single-unit duration like `"5m"`, `"30s"`, `"1h"`.
+fn parse_duration_suffix(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.len() < 2 {
+        return None;
+    }
+    let (amount, unit) = value.split_at(value.len() - 1);
+    let amount: u64 = amount.parse().ok()?;
+    match unit {
+        "s" => Some(Duration::from_secs(amount)),
+        "m" => Some(Duration::from_secs(amount * 60)),
+        "h" => Some(Duration::from_secs(amount * 3600)),
+        _ => None,
+    }
+}
+
+/// Reads a single scalar field out of a retained sample by dotted path. Returns `None` both for
+/// an unknown path and for a known path whose category wasn't collected that tick.
+fn extract_history_field(sample: &SystemMetrics, field: &str) -> Option<f64> {
+    match field {
+        "cpu.usage_percent" => sample.cpu.as_ref().map(|cpu| cpu.usage_percent as f64),
+        "cpu.frequency" => sample.cpu.as_ref().map(|cpu| cpu.frequency as f64),
+        "memory.used_gb" => sample.memory.as_ref().map(|memory| memory.used_gb),
+        "memory.total_gb" => sample.memory.as_ref().map(|memory| memory.total_gb),
+        "memory.available_gb" => sample.memory.as_ref().map(|memory| memory.available_gb),
+        "memory.swap_used_gb" => sample.memory.as_ref().map(|memory| memory.swap_used_gb),
+        "memory.cache_gb" => sample.memory.as_ref().map(|memory| memory.cache_gb),
+        "network.total_rx_mb_s" => sample.network.as_ref().map(|network| network.total_rx_mb_s),
+        "network.total_tx_mb_s" => sample.network.as_ref().map(|network| network.total_tx_mb_s),
+        "processes.total_count" => sample.processes.as_ref().map(|processes| processes.total_count as f64),
+        "processes.running_count" => sample.processes.as_ref().map(|processes| processes.running_count as f64),
+        _ => None,
+    }
+}
+
+/// Windowed, downsampled query over the retained history: `GET
+/// /history?field=cpu.usage_percent&window=5m&step=1s` returns one avg/min/max bucket per `step`
+/// covering the last `window`, letting each chart zoom independently without the server keeping
+/// per-widget state.
+async fn history_handler(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<HistoryQuery>,
+) -> axum::response::Response {
+    let (Some(window), Some(step)) = (
+        parse_duration_suffix(&query.window),
+        parse_duration_suffix(&query.step),
+    ) else {
+        return (
+            axum::http::StatusCode::BAD_REQUEST,
+            "invalid window or step (expected e.g. \"5m\", \"10s\", \"1h\")",
+        )
+            .into_response();
+    };
+    if step.is_zero() {
+        return (axum::http::StatusCode::BAD_REQUEST, "step must be greater than zero")
+            .into_response();
+    }
+
+    let history = state.history.read().await;
+    let cutoff = Utc::now() - chrono::Duration::from_std(window).unwrap_or_else(|_| chrono::Duration::zero());
+    let step_ms = (step.as_millis() as i64).max(1);
+
+    let mut grouped: HashMap<i64, Vec<f64>> = HashMap::new();
+    for sample in history.iter() {
+        if sample.timestamp < cutoff {
+            continue;
+        }
+        let Some(value) = extract_history_field(sample, &query.field) else {
+            continue;
+        };
+        let bucket_index = (sample.timestamp - cutoff).num_milliseconds() / step_ms;
+        grouped.entry(bucket_index).or_default().push(value);
+    }
+
+    let mut bucket_indices: Vec<i64> = grouped.keys().copied().collect();
+    bucket_indices.sort_unstable();
+
+    let buckets = bucket_indices
+        .into_iter()
+        .map(|index| {
+            let values = &grouped[&index];
+            let sum: f64 = values.iter().sum();
+            HistoryBucket {
+                timestamp: cutoff + chrono::Duration::milliseconds(index * step_ms),
+                avg: sum / values.len() as f64,
+                min: values.iter().cloned().fold(f64::INFINITY, f64::min),
+                max: values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                count: values.len(),
+            }
+        })
+        .collect();
+
+    axum::Json(HistoryResponse {
+        field: query.field,
+        window: query.window,
+        step: query.step,
+        buckets,
+    })
+    .into_response()
+}
+
 // ============================================================================
 // FUNCIONES AUXILIARES
 // ============================================================================
@@ -405,38 +1304,78 @@ impl Default for SystemMetrics {
     fn default() -> Self {
         Self {
             timestamp: Utc::now(),
-            cpu: CpuMetrics {
-                usage_percent: 0.0,
-                per_core: vec![],
-                temperature: None,
-                frequency: 0,
-                load_avg: [0.0; 3],
-            },
-            memory: MemoryMetrics {
-                total_gb: 0.0,
-                used_gb: 0.0,
-                available_gb: 0.0,
-                swap_total_gb: 0.0,
-                swap_used_gb: 0.0,
-                cache_gb: 0.0,
-            },
-            gpu: vec![],
-            disks: vec![],
-            network: NetworkMetrics {
-                interfaces: vec![],
-                total_rx_mb_s: 0.0,
-                total_tx_mb_s: 0.0,
-            },
-            processes: ProcessMetrics {
-                total_count: 0,
-                running_count: 0,
-                top_by_cpu: vec![],
-                top_by_memory: vec![],
-            },
+            cpu: None,
+            memory: None,
+            gpu: None,
+            disks: None,
+            network: None,
+            processes: None,
         }
     }
 }
 
+/// Strip every category `categories` doesn't include from a tick's metrics, so each client only
+/// receives what it subscribed to even though the collector gathered the union for everyone.
+fn project(metrics: &SystemMetrics, categories: &HashSet<MetricCategory>) -> SystemMetrics {
+    SystemMetrics {
+        timestamp: metrics.timestamp,
+        cpu: if categories.contains(&MetricCategory::Cpu) { metrics.cpu.clone() } else { None },
+        memory: if categories.contains(&MetricCategory::Memory) { metrics.memory.clone() } else { None },
+        gpu: if categories.contains(&MetricCategory::Gpu) { metrics.gpu.clone() } else { None },
+        disks: if categories.contains(&MetricCategory::Disks) { metrics.disks.clone() } else { None },
+        network: if categories.contains(&MetricCategory::Network) { metrics.network.clone() } else { None },
+        processes: if categories.contains(&MetricCategory::Processes) { metrics.processes.clone() } else { None },
+    }
+}
+
+/// How long a drained client's send task gets to flush the `server_shutdown` event and close
+/// frame before the process exits regardless.
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// Waits for Ctrl-C or SIGTERM, notifies every connected client with a `server_shutdown` event
+/// followed by a WebSocket close frame, gives their send tasks a bounded grace period to flush,
+/// then flips `shutdown_tx` so the collector loop and both HTTP servers' graceful-shutdown
+/// futures stop in turn.
+async fn handle_shutdown_signal(state: AppState, shutdown_tx: tokio::sync::watch::Sender<bool>) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+
+    let clients = state.clients.read().await;
+    info!("Shutdown signal received, draining {} client(s)...", clients.len());
+    for handle in clients.values() {
+        let _ = handle.tx.send(ClientMessage::Shutdown).await;
+    }
+    drop(clients);
+
+    tokio::time::sleep(SHUTDOWN_GRACE_PERIOD).await;
+
+    let _ = shutdown_tx.send(true);
+}
+
+/// The future axum's `with_graceful_shutdown` waits on: resolves once
+/// [`handle_shutdown_signal`] has finished draining clients and flipped the shared watch.
+async fn wait_for_shutdown(mut shutdown_rx: tokio::sync::watch::Receiver<bool>) {
+    let _ = shutdown_rx.changed().await;
+}
+
 // ============================================================================
 // MAIN
 // ============================================================================
@@ -447,34 +1386,68 @@ async fn main() {
     tracing_subscriber::fmt()
         .with_env_filter("info")
         .init();
-    
+
     info!("HecateOS Monitor Server starting...");
-    
+
+    let config = MonitorConfig::load();
+
     // Crear estado compartido
-    let state = AppState::new();
-    
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let state = AppState::new(config.history.retention_samples, shutdown_rx);
+
     // Iniciar recolector de métricas
     let collector_state = state.clone();
     tokio::spawn(async move {
         metrics_collector(collector_state).await;
     });
-    
+
+    // Escuchar Ctrl-C / SIGTERM y drenar los clientes conectados antes de apagar
+    tokio::spawn(handle_shutdown_signal(state.clone(), shutdown_tx));
+
     // Configurar rutas
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/", get(dashboard))
         .route("/health", get(health))
         .route("/ws", get(websocket_handler))
-        .layer(CorsLayer::permissive())
-        .with_state(state);
-    
+        .route("/history", get(history_handler));
+
+    // Si no hay un listener separado para métricas, se sirve desde el router principal
+    if config.metrics.listen_addr.is_none() {
+        app = app.route(&config.metrics.path, get(metrics_handler));
+    }
+
+    let app = app.layer(CorsLayer::permissive()).with_state(state.clone());
+
+    // Si se configuró un listen_addr separado, levantar un segundo servidor solo para /metrics
+    if let Some(metrics_addr) = config.metrics.listen_addr {
+        let metrics_app = Router::new()
+            .route(&config.metrics.path, get(metrics_handler))
+            .with_state(state.clone());
+        let metrics_shutdown_rx = state.shutdown_rx.clone();
+        info!("Metrics listening on http://{}{}", metrics_addr, config.metrics.path);
+        tokio::spawn(async move {
+            if let Err(e) = axum::Server::bind(&metrics_addr)
+                .serve(metrics_app.into_make_service())
+                .with_graceful_shutdown(wait_for_shutdown(metrics_shutdown_rx))
+                .await
+            {
+                error!("Metrics server failed: {}", e);
+            }
+        });
+    }
+
     // Iniciar servidor
     let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
     info!("Server listening on http://{}", addr);
     info!("Dashboard: http://localhost:3000");
     info!("WebSocket: ws://localhost:3000/ws");
-    
+    if config.metrics.listen_addr.is_none() {
+        info!("Metrics: http://localhost:3000{}", config.metrics.path);
+    }
+
     axum::Server::bind(&addr)
         .serve(app.into_make_service())
+        .with_graceful_shutdown(wait_for_shutdown(state.shutdown_rx.clone()))
         .await
         .unwrap();
 }